@@ -0,0 +1,162 @@
+// Hit Testing Benchmark
+// Compares finding the widget under the mouse with the spatial index against a linear scan, and
+// measures how long the spatial index takes to catch up after one widget has moved
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Run with `cargo bench --bench hit_test`.
+
+extern crate pushrod;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use pushrod::core::point::*;
+use pushrod::core::widget_id::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::widget::*;
+
+const CELL_SIZE: i32 = 20;
+const COLUMNS: i32 = 100;
+
+/// The least time that each path is measured for, at each size.
+const MEASURE_TIME: Duration = Duration::from_millis(500);
+
+/// Builds a grid of `count` cells, inside of a panel, as a spreadsheet would.
+fn make_grid(count: i32) -> WidgetStore {
+    let mut widget_store = WidgetStore::new();
+    let mut panel = CanvasWidget::new();
+    let rows = (count + COLUMNS - 1) / COLUMNS;
+
+    panel.set_origin(0, 0);
+    panel.set_size(COLUMNS * CELL_SIZE, rows * CELL_SIZE);
+
+    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+
+    for index in 0..count - 1 {
+        let mut cell = CanvasWidget::new();
+
+        cell.set_origin((index % COLUMNS) * CELL_SIZE, (index / COLUMNS) * CELL_SIZE);
+        cell.set_size(CELL_SIZE, CELL_SIZE);
+        widget_store.add_widget_to_parent("Cell", Box::new(cell), panel_id);
+    }
+
+    widget_store
+}
+
+/// The points that the mouse is moved over, spread across the grid.
+fn make_points(count: i32) -> Vec<Point> {
+    let width = COLUMNS.min(count) * CELL_SIZE;
+    let height = ((count + COLUMNS - 1) / COLUMNS) * CELL_SIZE;
+
+    (0..64)
+        .map(|i| make_point_i32((i * 37) % width, (i * 53) % height))
+        .collect()
+}
+
+/// Returns the average time that `find` takes to find the widgets under each of `points`.
+fn measure<F>(widget_store: &mut WidgetStore, points: &[Point], mut find: F) -> Duration
+where
+    F: FnMut(&mut WidgetStore, Point) -> Vec<WidgetId>,
+{
+    let start = Instant::now();
+    let mut queries = 0;
+
+    while queries == 0 || start.elapsed() < MEASURE_TIME {
+        for point in points {
+            black_box(find(widget_store, point.clone()));
+            queries += 1;
+        }
+    }
+
+    start.elapsed() / queries
+}
+
+/// Returns the average time that moving one cell of the grid, and then finding the widgets under
+/// each of `points`, takes, as when a widget is dragged across a spreadsheet.
+fn measure_moves(widget_store: &mut WidgetStore, points: &[Point]) -> Duration {
+    let panel_id = widget_store.get_widget_id_by_name("Panel").unwrap();
+    let cell_id = widget_store.get_children_of(panel_id)[0];
+    let start = Instant::now();
+    let mut moves = 0;
+    let mut x = 0;
+
+    while moves == 0 || start.elapsed() < MEASURE_TIME {
+        for point in points {
+            // The cell moves back and forth, so every move changes its origin.
+            x = CELL_SIZE - x;
+            widget_store
+                .get_widget_for_id(cell_id)
+                .borrow_mut()
+                .set_origin(x, 0);
+            black_box(widget_store.get_widget_ids_for_point(point.clone()));
+            moves += 1;
+        }
+    }
+
+    start.elapsed() / moves
+}
+
+fn main() {
+    println!(
+        "{:>8}  {:>14}  {:>14}  {:>8}",
+        "widgets", "linear scan", "spatial index", "speedup"
+    );
+
+    for count in &[10, 1_000, 10_000] {
+        let mut widget_store = make_grid(*count);
+        let points = make_points(*count);
+
+        // Both paths have to find the same widgets, or the comparison means nothing.
+        for point in &points {
+            assert_eq!(
+                widget_store.get_widget_ids_for_point(point.clone()),
+                widget_store.scan_widget_ids_for_point(point.clone())
+            );
+        }
+
+        let scan = measure(&mut widget_store, &points[..4], |widget_store, point| {
+            widget_store.scan_widget_ids_for_point(point)
+        });
+        let index = measure(&mut widget_store, &points, |widget_store, point| {
+            widget_store.get_widget_ids_for_point(point)
+        });
+
+        println!(
+            "{:>8}  {:>14?}  {:>14?}  {:>7.0}x",
+            count,
+            scan,
+            index,
+            scan.as_secs_f64() / index.as_secs_f64()
+        );
+    }
+
+    println!();
+    println!("{:>8}  {:>14}", "widgets", "move and query");
+
+    for count in &[10, 1_000, 10_000] {
+        let mut widget_store = make_grid(*count);
+        let points = make_points(*count);
+        let moved = measure_moves(&mut widget_store, &points);
+
+        // The index has to have followed the moved cell.
+        for point in &points {
+            assert_eq!(
+                widget_store.get_widget_ids_for_point(point.clone()),
+                widget_store.scan_widget_ids_for_point(point.clone())
+            );
+        }
+
+        println!("{:>8}  {:>14?}", count, moved);
+    }
+}
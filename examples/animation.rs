@@ -0,0 +1,114 @@
+// Animation Example
+// Moves a box across the window using an AnimationWidget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::draw_target::*;
+use pushrod::core::main::*;
+use pushrod::core::point::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::animation_widget::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::config::*;
+use pushrod::widget::widget::*;
+
+/// A `BoxWidget` whose X origin is driven by an `AnimationWidget`.  The animation runs back and
+/// forth between its start and end values.
+struct SlidingBoxWidget {
+    box_widget: BoxWidget,
+    animation: AnimationWidget,
+    x: Rc<Cell<f64>>,
+}
+
+impl SlidingBoxWidget {
+    fn new() -> Self {
+        let x = Rc::new(Cell::new(0.0));
+        let x_clone = x.clone();
+        let box_widget = BoxWidget::builder()
+            .origin(0, 100)
+            .size(100, 100)
+            .color([0.5, 0.5, 1.0, 1.0])
+            .border([0.0, 0.0, 1.0, 1.0], 2)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build SlidingBox: {}", error));
+        let mut animation = AnimationWidget::new(0.0, 400.0, 2000);
+
+        animation.set_easing(Easing::EaseInOut);
+        animation.on_value(Box::new(move |value| x_clone.set(value)));
+
+        Self {
+            box_widget,
+            animation,
+            x,
+        }
+    }
+}
+
+impl Widget for SlidingBoxWidget {
+    fn config(&mut self) -> &mut Configurable {
+        self.box_widget.config()
+    }
+
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.box_widget.set_origin(x, y);
+    }
+
+    fn get_origin(&mut self) -> Point {
+        self.box_widget.get_origin()
+    }
+
+    fn get_size(&mut self) -> pushrod::core::point::Size {
+        self.box_widget.get_size()
+    }
+
+    fn update(&mut self, delta_ms: u64) {
+        self.animation.update(delta_ms);
+
+        if !self.animation.is_running() {
+            self.animation.reverse();
+        }
+
+        let y = self.get_origin().y;
+
+        self.set_origin(self.x.get() as i32, y);
+    }
+
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.box_widget.draw(c, g, clip);
+    }
+}
+
+struct AnimationEventHandler {}
+
+impl PushrodCallbackEvents for AnimationEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Animation", [500, 300])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = AnimationEventHandler {};
+
+    pushrod.add_widget("SlidingBox", Box::new(SlidingBoxWidget::new()));
+    pushrod.run(&mut handler);
+}
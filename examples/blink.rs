@@ -0,0 +1,64 @@
+// Blink Example
+// Toggles the color of a widget from a TimerWidget callback
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::timer_widget::*;
+
+struct BlinkEventHandler {}
+
+impl PushrodCallbackEvents for BlinkEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Blink", [300, 300])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = BlinkEventHandler {};
+    let mut lit = false;
+    let box_widget = BoxWidget::builder()
+        .origin(50, 50)
+        .size(200, 200)
+        .color([1.0; 4])
+        .border([0.0, 0.0, 0.0, 1.0], 2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BlinkBox: {}", error));
+    let timer = TimerWidget::builder()
+        .timeout_ms(500)
+        .on_timeout_with_store(Box::new(move |widget_store| {
+            lit = !lit;
+
+            let color = if lit { [1.0, 0.5, 0.0, 1.0] } else { [1.0; 4] };
+
+            widget_store
+                .get_widget_for_name("BlinkBox")
+                .borrow_mut()
+                .set_color(color);
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BlinkTimer: {}", error));
+
+    pushrod.add_widget("BlinkBox", Box::new(box_widget));
+    pushrod.add_widget("BlinkTimer", Box::new(timer));
+    pushrod.run(&mut handler);
+}
@@ -0,0 +1,84 @@
+// Crossfade Example
+// Fades two overlapping boxes in and out of each other using widget opacity
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::time::Instant;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::timer_widget::*;
+
+/// Number of milliseconds it takes to fade from one box to the other.
+const FADE_TIME: f64 = 2000.0;
+
+struct CrossfadeEventHandler {}
+
+impl PushrodCallbackEvents for CrossfadeEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Crossfade", [400, 400])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = CrossfadeEventHandler {};
+    let started = Instant::now();
+    let back_box = BoxWidget::builder()
+        .origin(50, 50)
+        .size(200, 200)
+        .color([1.0, 0.0, 0.0, 1.0])
+        .border([0.0, 0.0, 0.0, 1.0], 2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BackBox: {}", error));
+    let front_box = BoxWidget::builder()
+        .origin(150, 150)
+        .size(200, 200)
+        .color([0.0, 0.0, 1.0, 1.0])
+        .border([0.0, 0.0, 0.0, 1.0], 2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build FrontBox: {}", error));
+
+    pushrod.add_widget("BackBox", Box::new(back_box));
+    pushrod.add_widget("FrontBox", Box::new(front_box));
+
+    // Swings the opacity of the front box between 0.0 and 1.0, fading the back box the other way.
+    let fade_timer = TimerWidget::builder()
+        .timeout_ms(16)
+        .on_timeout_with_store(Box::new(move |widget_store| {
+            let phase = started.elapsed().as_millis() as f64 / FADE_TIME * std::f64::consts::PI;
+            let opacity = ((1.0 - phase.cos()) / 2.0) as f32;
+
+            widget_store
+                .get_widget_for_name("FrontBox")
+                .borrow_mut()
+                .set_opacity(opacity);
+            widget_store
+                .get_widget_for_name("BackBox")
+                .borrow_mut()
+                .set_opacity(1.0 - opacity);
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build FadeTimer: {}", error));
+
+    pushrod.add_widget("FadeTimer", Box::new(fade_timer));
+
+    pushrod.run(&mut handler);
+}
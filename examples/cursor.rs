@@ -0,0 +1,115 @@
+// Cursor Example
+// Draws a dot under the mouse pointer using widget-local mouse move events
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::draw_target::*;
+use pushrod::core::main::*;
+use pushrod::core::point::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::config::*;
+use pushrod::widget::widget::*;
+
+/// A `BoxWidget` that draws a dot wherever the mouse is while it is hovered.
+struct CursorDotWidget {
+    box_widget: BoxWidget,
+    cursor: Option<Point>,
+}
+
+impl CursorDotWidget {
+    fn new() -> Self {
+        let mut box_widget = BoxWidget::new();
+
+        box_widget.set_color([1.0; 4]);
+        box_widget.set_border([0.0, 0.0, 0.0, 1.0], 2);
+
+        Self {
+            box_widget,
+            cursor: None,
+        }
+    }
+}
+
+impl Widget for CursorDotWidget {
+    fn config(&mut self) -> &mut Configurable {
+        self.box_widget.config()
+    }
+
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.box_widget.set_origin(x, y);
+    }
+
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.box_widget.set_size(w, h);
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                self.cursor = Some(point);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseExited { .. } => {
+                self.cursor = None;
+                self.invalidate();
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.box_widget.draw(c, g, clip);
+
+        if let Some(point) = &self.cursor {
+            Ellipse::new(apply_opacity([1.0, 0.0, 0.0, 1.0])).draw(
+                [point.x as f64 - 4.0, point.y as f64 - 4.0, 8.0, 8.0],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        self.clear_invalidate();
+    }
+}
+
+struct CursorEventHandler {}
+
+impl PushrodCallbackEvents for CursorEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Cursor", [400, 400])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = CursorEventHandler {};
+    let mut dot_widget = CursorDotWidget::new();
+
+    dot_widget.set_origin(50, 50);
+    dot_widget.set_size(300, 300);
+
+    pushrod.add_widget("CursorDot", Box::new(dot_widget));
+    pushrod.run(&mut handler);
+}
@@ -0,0 +1,118 @@
+// Drag and Drop Example
+// Rearranges colored tiles by dragging one tile and dropping it on another
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_id::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+
+const TILE_COLORS: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 1.0],
+    [0.0, 1.0, 0.0, 1.0],
+    [0.0, 0.0, 1.0, 1.0],
+    [1.0, 1.0, 0.0, 1.0],
+];
+
+/// Starts dragging a tile's color when it is pressed, showing a small faded copy of the tile under
+/// the mouse pointer, and swaps the colors of two tiles when one is dropped on the other.
+struct DragDropEventHandler {}
+
+impl PushrodCallbackEvents for DragDropEventHandler {
+    fn handle_event(&mut self, event: CallbackEvent, widget_store: &mut WidgetStore) {
+        match event {
+            CallbackEvent::MouseButtonDown { widget_id, .. } if widget_id > WidgetId::ROOT => {
+                let color = widget_store
+                    .get_widget_for_id(widget_id)
+                    .borrow_mut()
+                    .get_color();
+                let ghost = BoxWidget::builder()
+                    .size(40, 40)
+                    .color(color)
+                    .opacity(0.5)
+                    .build()
+                    .unwrap_or_else(|error| panic!("Failed to build ghost: {}", error));
+
+                widget_store.begin_drag(widget_id, Box::new(color), Some(Box::new(ghost)));
+            }
+
+            CallbackEvent::DragEntered { widget_id, .. } if widget_id > WidgetId::ROOT => {
+                widget_store
+                    .get_widget_for_id(widget_id)
+                    .borrow_mut()
+                    .set_opacity(0.75);
+            }
+
+            CallbackEvent::DragExited { widget_id, .. } if widget_id > WidgetId::ROOT => {
+                widget_store
+                    .get_widget_for_id(widget_id)
+                    .borrow_mut()
+                    .set_opacity(1.0);
+            }
+
+            CallbackEvent::Dropped {
+                widget_id,
+                source_id,
+                payload,
+                ..
+            } => {
+                if let Some(color) = payload.take_as::<types::Color>() {
+                    let target_color = widget_store
+                        .get_widget_for_id(widget_id)
+                        .borrow_mut()
+                        .get_color();
+
+                    widget_store
+                        .get_widget_for_id(source_id)
+                        .borrow_mut()
+                        .set_color(target_color);
+
+                    let target = &mut widget_store.get_widget_for_id(widget_id).borrow_mut();
+
+                    target.set_color(*color);
+                    target.set_opacity(1.0);
+                }
+            }
+
+            _ => (),
+        }
+    }
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Drag and Drop", [400, 400])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = DragDropEventHandler {};
+
+    for (index, color) in TILE_COLORS.iter().enumerate() {
+        let tile = BoxWidget::builder()
+            .origin(40 + (index as i32 % 2) * 170, 40 + (index as i32 / 2) * 170)
+            .size(150, 150)
+            .color(*color)
+            .border([0.0, 0.0, 0.0, 1.0], 2)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build Tile{}: {}", index, error));
+
+        pushrod.add_widget(&format!("Tile{}", index), Box::new(tile));
+    }
+
+    pushrod.run(&mut handler);
+}
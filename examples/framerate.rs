@@ -0,0 +1,116 @@
+// Frame Rate Example
+// Logs the number of frames drawn per second, with and without lazy rendering
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::timer_widget::*;
+
+/// Toggles lazy rendering when the `L` key is pressed.  The run loop is driven by `main`, which
+/// owns the `Pushrod` object, so the handler only records the request.
+struct FrameRateEventHandler {
+    toggle_lazy: Rc<Cell<bool>>,
+}
+
+impl PushrodCallbackEvents for FrameRateEventHandler {
+    fn handle_event(&mut self, event: CallbackEvent, _widget_store: &mut WidgetStore) {
+        if let CallbackEvent::KeyPressed {
+            key: Key::L,
+            state: ButtonState::Press,
+            ..
+        } = event
+        {
+            self.toggle_lazy.set(true);
+        }
+    }
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Frame Rate", [300, 300])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let toggle_lazy = Rc::new(Cell::new(false));
+    let mut handler = FrameRateEventHandler {
+        toggle_lazy: toggle_lazy.clone(),
+    };
+    let mut lit = false;
+
+    pushrod.set_max_fps(60);
+    pushrod.set_ups(120);
+
+    let box_widget = BoxWidget::builder()
+        .origin(50, 50)
+        .size(200, 200)
+        .color([1.0; 4])
+        .border([0.0, 0.0, 0.0, 1.0], 2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build FrameBox: {}", error));
+
+    // The box changes twice per second, so lazy rendering only has to draw a few frames.
+    let timer = TimerWidget::builder()
+        .timeout_ms(500)
+        .on_timeout_with_store(Box::new(move |widget_store| {
+            lit = !lit;
+
+            let color = if lit { [0.0, 0.5, 1.0, 1.0] } else { [1.0; 4] };
+
+            widget_store
+                .get_widget_for_name("FrameBox")
+                .borrow_mut()
+                .set_color(color);
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build FrameTimer: {}", error));
+
+    pushrod.add_widget("FrameBox", Box::new(box_widget));
+    pushrod.add_widget("FrameTimer", Box::new(timer));
+
+    eprintln!("Press L to turn lazy rendering on or off.");
+
+    let mut report_time = Instant::now();
+    let mut report_frames = pushrod.get_frames_drawn();
+
+    while let Some(event) = pushrod.next_event() {
+        pushrod.handle_window_event(&event, &mut handler);
+
+        if toggle_lazy.replace(false) {
+            let lazy = !pushrod.is_lazy_rendering();
+
+            pushrod.set_lazy_rendering(lazy);
+            eprintln!("Lazy rendering: {}", lazy);
+        }
+
+        if report_time.elapsed().as_millis() >= 1000 {
+            eprintln!(
+                "Frames drawn in the last second: {} (lazy rendering: {})",
+                pushrod.get_frames_drawn() - report_frames,
+                pushrod.is_lazy_rendering()
+            );
+
+            report_time = Instant::now();
+            report_frames = pushrod.get_frames_drawn();
+        }
+    }
+}
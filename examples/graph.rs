@@ -0,0 +1,90 @@
+// Graph Example
+// Plots the number of frames drawn, and updates run, every second
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::graph_widget::*;
+use pushrod::widget::timer_widget::*;
+use pushrod::widget::widget::*;
+
+struct GraphEventHandler {}
+
+impl PushrodCallbackEvents for GraphEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Graph", [500, 300])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = GraphEventHandler {};
+    let mut graph = GraphWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+    let samples = graph.get_samples();
+    let fps = graph.add_series([0.0, 0.5, 1.0, 1.0]);
+    let ups = graph.add_series([1.0, 0.5, 0.0, 1.0]);
+    let frames_drawn = Rc::new(Cell::new(0));
+    let frames_drawn_clone = frames_drawn.clone();
+    let updates = Rc::new(Cell::new(0));
+    let updates_clone = updates.clone();
+    let mut last_frames_drawn = 0;
+
+    pushrod.set_max_fps(60);
+    pushrod.set_ups(120);
+
+    graph.set_origin(20, 20);
+    graph.set_size(460, 260);
+    graph.set_capacity(120);
+    graph.set_y_range(Some((0.0, 150.0)));
+
+    // A timer with a timeout of 0 fires on every update of the run loop.
+    let update_counter = TimerWidget::builder()
+        .timeout_ms(0)
+        .on_timeout(move |_| updates_clone.set(updates_clone.get() + 1))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build UpdateCounter: {}", error));
+
+    // One sample of each series is pushed every second.
+    let sample_timer = TimerWidget::builder()
+        .timeout_ms(1000)
+        .on_timeout(move |_| {
+            let frames = frames_drawn_clone.get();
+
+            samples.push_sample(fps, (frames - last_frames_drawn) as f64);
+            samples.push_sample(ups, f64::from(updates.replace(0)));
+            last_frames_drawn = frames;
+        })
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build SampleTimer: {}", error));
+
+    pushrod.add_widget("Graph", Box::new(graph));
+    pushrod.add_widget("UpdateCounter", Box::new(update_counter));
+    pushrod.add_widget("SampleTimer", Box::new(sample_timer));
+
+    eprintln!("Frames per second are plotted in blue, and updates per second in orange.");
+
+    while let Some(event) = pushrod.next_event() {
+        pushrod.handle_window_event(&event, &mut handler);
+        frames_drawn.set(pushrod.get_frames_drawn());
+    }
+}
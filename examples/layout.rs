@@ -0,0 +1,117 @@
+// Layout Example
+// Builds a window from a TOML layout, attaches behavior to the widgets by name, and switches themes
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::layout::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::theme::*;
+
+const LAYOUT: &str = r#"
+[[widget]]
+type = "BoxWidget"
+name = "Panel"
+origin = [20, 20]
+size = [360, 260]
+border_color = [0.0, 0.0, 1.0, 1.0]
+border_width = 2
+
+[[widget]]
+type = "TextWidget"
+name = "Title"
+parent = "Panel"
+origin = [10, 10]
+size = [340, 32]
+text = "Loaded from a layout"
+font_size = 24
+justify = "center"
+
+[[widget]]
+type = "ProgressWidget"
+name = "Progress"
+parent = "Panel"
+origin = [30, 80]
+size = [300, 32]
+progress = 25
+
+[[widget]]
+type = "PushButtonWidget"
+name = "AdvanceButton"
+parent = "Panel"
+origin = [110, 160]
+size = [140, 40]
+text = "Advance"
+border_width = 2
+tooltip = "Changes the color of the panel"
+"#;
+
+struct LayoutEventHandler {}
+
+impl PushrodCallbackEvents for LayoutEventHandler {
+    fn handle_event(&mut self, event: CallbackEvent, widget_store: &mut WidgetStore) {
+        match event {
+            CallbackEvent::WidgetClicked { widget_id, .. }
+                if widget_store.get_name_for_widget_id(widget_id) == "AdvanceButton" =>
+            {
+                widget_store
+                    .get_widget_for_name("Panel")
+                    .borrow_mut()
+                    .set_color([
+                        rand::random::<u8>() as f32 / 255.0,
+                        rand::random::<u8>() as f32 / 255.0,
+                        rand::random::<u8>() as f32 / 255.0,
+                        1.0,
+                    ]);
+            }
+
+            // The T key switches between the light and dark themes.  The panel keeps its color
+            // once the button has given it one.
+            CallbackEvent::KeyPressed {
+                key: Key::T,
+                state: ButtonState::Press,
+                ..
+            } => {
+                let theme = if widget_store.get_theme() == Theme::light() {
+                    Theme::dark()
+                } else {
+                    Theme::light()
+                };
+
+                widget_store.set_theme(theme);
+            }
+
+            _ => (),
+        }
+    }
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Layout", [400, 300])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = LayoutEventHandler {};
+    let mut loader = LayoutLoader::new();
+
+    pushrod
+        .load_layout(&mut loader, LAYOUT)
+        .unwrap_or_else(|error| panic!("Failed to load layout: {}", error));
+    pushrod.enable_tooltips("OpenSans-Regular.ttf", 14);
+    pushrod.run(&mut handler);
+}
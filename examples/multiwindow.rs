@@ -0,0 +1,122 @@
+// Multiple Window Example
+// Runs a main window and a tool palette window, each with its own timer-driven widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::core::window_manager::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::timer_widget::*;
+
+/// Prints the events of one window, to show that each window only receives its own events.
+struct WindowEventHandler {
+    window_name: &'static str,
+}
+
+impl PushrodCallbackEvents for WindowEventHandler {
+    fn handle_event(&mut self, event: CallbackEvent, _widget_store: &mut WidgetStore) {
+        if let CallbackEvent::MouseEntered { widget_id } = event {
+            eprintln!("{}: mouse entered widget {}", self.window_name, widget_id);
+        }
+    }
+}
+
+fn build_window(title: &str, size: [u32; 2]) -> Pushrod {
+    let window: PistonWindow = WindowSettings::new(title, size)
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+
+    Pushrod::new(window)
+}
+
+/// The main window grows a bar across the window, and starts over once it is full.
+fn build_main_window() -> Pushrod {
+    let mut pushrod = build_window("Pushrod Main Window", [400, 200]);
+    let mut width = 2;
+    let bar_widget = BoxWidget::builder()
+        .origin(50, 80)
+        .size(width, 40)
+        .color([0.0, 0.5, 1.0, 1.0])
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build Bar: {}", error));
+    let timer = TimerWidget::builder()
+        .timeout_ms(20)
+        .on_timeout_with_store(Box::new(move |widget_store| {
+            width = (width + 2) % 302;
+
+            if let Some(widget) = widget_store.get_widget_by_name("Bar") {
+                widget.set_size(width, 40);
+            }
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BarTimer: {}", error));
+
+    pushrod.add_widget("Bar", Box::new(bar_widget));
+    pushrod.add_widget("BarTimer", Box::new(timer));
+    pushrod
+}
+
+/// The palette window blinks a box.
+fn build_palette_window() -> Pushrod {
+    let mut pushrod = build_window("Pushrod Palette", [200, 200]);
+    let mut lit = false;
+    let box_widget = BoxWidget::builder()
+        .origin(50, 50)
+        .size(100, 100)
+        .border([0.0, 0.0, 0.0, 1.0], 2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BlinkBox: {}", error));
+    let timer = TimerWidget::builder()
+        .timeout_ms(500)
+        .on_timeout_with_store(Box::new(move |widget_store| {
+            lit = !lit;
+
+            let color = if lit { [1.0, 0.5, 0.0, 1.0] } else { [1.0; 4] };
+
+            widget_store
+                .get_widget_for_name("BlinkBox")
+                .borrow_mut()
+                .set_color(color);
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build BlinkTimer: {}", error));
+
+    pushrod.add_widget("BlinkBox", Box::new(box_widget));
+    pushrod.add_widget("BlinkTimer", Box::new(timer));
+    pushrod
+}
+
+fn main() {
+    let mut window_manager = WindowManager::new();
+
+    window_manager.add_window(
+        build_main_window(),
+        Box::new(WindowEventHandler {
+            window_name: "Main",
+        }),
+    );
+    window_manager.add_window(
+        build_palette_window(),
+        Box::new(WindowEventHandler {
+            window_name: "Palette",
+        }),
+    );
+
+    window_manager.run();
+}
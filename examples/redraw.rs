@@ -0,0 +1,137 @@
+// Redraw Example
+// Updates one small widget at 60 frames per second, while the rest of the window stays static
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::draw_target::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::widget::box_widget::*;
+use pushrod::widget::config::*;
+use pushrod::widget::font_cache::*;
+use pushrod::widget::text_widget::*;
+use pushrod::widget::timer_widget::*;
+use pushrod::widget::widget::*;
+
+/// A `TextWidget` that shows the number of times its `TimerWidget` has fired.  The text is only
+/// changed, and the widget only invalidated, when the count changes.
+struct TickerWidget {
+    text_widget: TextWidget,
+    timer: TimerWidget,
+    ticks: Rc<Cell<u64>>,
+    displayed_ticks: u64,
+}
+
+impl TickerWidget {
+    fn new(font: SharedGlyphs) -> Self {
+        let ticks = Rc::new(Cell::new(0));
+        let ticks_clone = ticks.clone();
+        let text_widget = TextWidget::builder(font)
+            .origin(170, 280)
+            .size(100, 32)
+            .color([1.0; 4])
+            .text("0")
+            .text_color([0.0, 0.0, 0.0, 1.0])
+            .font_size(24)
+            .justify(TextJustify::Center)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build Ticker: {}", error));
+        let timer = TimerWidget::builder()
+            .timeout_ms(16)
+            .on_timeout(move |_| ticks_clone.set(ticks_clone.get() + 1))
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build Ticker timer: {}", error));
+
+        Self {
+            text_widget,
+            timer,
+            ticks,
+            displayed_ticks: 0,
+        }
+    }
+}
+
+impl Widget for TickerWidget {
+    fn config(&mut self) -> &mut Configurable {
+        self.text_widget.config()
+    }
+
+    fn update(&mut self, delta_ms: u64) {
+        self.timer.update(delta_ms);
+
+        if self.ticks.get() != self.displayed_ticks {
+            self.displayed_ticks = self.ticks.get();
+            self.text_widget
+                .set_text(format!("{}", self.displayed_ticks));
+        }
+    }
+
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.text_widget.draw(c, g, clip);
+    }
+}
+
+struct RedrawEventHandler {}
+
+impl PushrodCallbackEvents for RedrawEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Redraw", [440, 340])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = RedrawEventHandler {};
+
+    // A static grid of boxes, which should not be redrawn once the window is up.
+    for row in 0..4 {
+        for column in 0..5 {
+            let box_widget = BoxWidget::builder()
+                .origin(20 + column * 80, 20 + row * 60)
+                .size(70, 50)
+                .color([0.75, 0.75, 1.0, 1.0])
+                .border([0.0, 0.0, 1.0, 1.0], 2)
+                .build()
+                .unwrap_or_else(|error| panic!("Failed to build box: {}", error));
+
+            pushrod.add_widget(&format!("Box{}x{}", column, row), Box::new(box_widget));
+        }
+    }
+
+    let font = load_font(pushrod.get_factory(), "OpenSans-Regular.ttf")
+        .unwrap_or_else(|error| panic!("Failed to load font: {}", error));
+    let report_timer = TimerWidget::builder()
+        .timeout_ms(1000)
+        .on_timeout_with_store(Box::new(|widget_store| {
+            eprintln!(
+                "Widgets drawn in the last frame: {}",
+                widget_store.get_draw_count()
+            );
+        }))
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build ReportTimer: {}", error));
+
+    pushrod.add_widget("Ticker", Box::new(TickerWidget::new(font)));
+    pushrod.add_widget("ReportTimer", Box::new(report_timer));
+
+    pushrod.run(&mut handler);
+}
@@ -21,7 +21,11 @@ use piston_window::*;
 use pushrod::core::callbacks::*;
 use pushrod::core::main::*;
 use pushrod::core::widget_store::*;
+use pushrod::pushrod_ui;
 use pushrod::widget::box_widget::*;
+use pushrod::widget::builder::*;
+use pushrod::widget::config::*;
+use pushrod::widget::font_cache::*;
 use pushrod::widget::image_widget::*;
 use pushrod::widget::progress_widget::*;
 use pushrod::widget::push_button_widget::*;
@@ -103,187 +107,101 @@ impl SimpleWindow {
         }
     }
 
-    fn add_hello_world(&mut self) {
-        let mut text_widget = TextWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Welcome to rust-pushrod!".to_string(),
-            32,
-            TextJustify::Left,
-        );
+    fn get_font(&self) -> SharedGlyphs {
+        load_font(self.pushrod.borrow_mut().get_factory(), "OpenSans-Regular.ttf")
+            .unwrap_or_else(|error| panic!("Failed to load font: {}", error))
+    }
 
-        text_widget.set_origin(20, 20);
-        text_widget.set_size(400, 40);
-        text_widget.set_color([0.75, 0.75, 1.0, 1.0]);
-        text_widget.set_text_color([0.75, 0.25, 1.0, 1.0]);
+    fn add_hello_world(&mut self) {
+        let text_widget = TextWidget::builder(self.get_font())
+            .origin(20, 20)
+            .size(400, 40)
+            .color([0.75, 0.75, 1.0, 1.0])
+            .text("Welcome to rust-pushrod!")
+            .text_color([0.75, 0.25, 1.0, 1.0])
+            .font_size(32)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build TextWidget: {}", error));
 
         self.pushrod.borrow_mut().add_widget("TextWidget",Box::new(text_widget));
     }
 
     fn add_base_widget(&mut self) {
-        let mut base_widget = CanvasWidget::new();
-
-        base_widget.set_origin(20, 80);
-        base_widget.set_size(200, 200);
-        base_widget.set_color([0.5, 0.5, 0.5, 1.0]);
-
-        let base_widget_id = self.pushrod.borrow_mut().add_widget("BaseWidget1",Box::new(base_widget));
-
-        let mut button1 = PushButtonWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Random Color".to_string(),
-            18,
-            TextJustify::Center,
-        );
-        button1.set_origin(30, 236);
-        button1.set_size(180, 32);
-        button1.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        button1.set_border([0.0, 0.0, 0.0, 1.0], 2);
-
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("RandomColorButton1", Box::new(button1), base_widget_id);
-
-        let mut button2 = PushButtonWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Hide".to_string(),
-            18,
-            TextJustify::Center,
-        );
-
-        button2.set_origin(20, 290);
-        button2.set_size(200, 32);
-        button2.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        button2.set_border([0.0, 0.0, 0.0, 1.0], 2);
-
-        self.pushrod.borrow_mut().add_widget("HideButton1", Box::new(button2));
+        pushrod_ui!(self.pushrod.borrow_mut(), {
+            "BaseWidget1": CanvasWidget::builder()
+                .origin(20, 80)
+                .size(200, 200)
+                .color([0.5, 0.5, 0.5, 1.0]) => {
+                "RandomColorButton1": self
+                    .build_button("Random Color", 10, 156, 180)
+                    .tooltip("Changes the color of the gray box")
+                    .cursor(CursorStyle::Hand),
+            },
+            "HideButton1": self.build_button("Hide", 20, 290, 200),
+        })
+        .unwrap_or_else(|error| panic!("Failed to build the base widget: {}", error));
     }
 
     fn add_box_widgets(&mut self) {
-        let mut box_widget = BoxWidget::new();
-
-        box_widget.set_origin(250, 80);
-        box_widget.set_size(200, 200);
-        box_widget.set_color([0.0, 1.0, 0.0, 1.0]);
-        box_widget.set_border([1.0, 0.0, 0.0, 1.0], 4);
-        let box_widget_id = self.pushrod.borrow_mut().add_widget("BoxWidget1",Box::new(box_widget));
-
-        let mut text_widget2 = TextWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Left".to_string(),
-            24,
-            TextJustify::Left,
-        );
-        text_widget2.set_origin(265, 100);
-        text_widget2.set_size(170, 32);
-        text_widget2.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("LeftJustifiedText", Box::new(text_widget2), box_widget_id);
-
-        let mut text_widget3 = TextWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Center".to_string(),
-            24,
-            TextJustify::Center,
-        );
-        text_widget3.set_origin(265, 166);
-        text_widget3.set_size(170, 32);
-        text_widget3.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("CenterJustifiedText", Box::new(text_widget3), box_widget_id);
-
-        let mut text_widget4 = TextWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Right".to_string(),
-            24,
-            TextJustify::Right,
-        );
-        text_widget4.set_origin(265, 230);
-        text_widget4.set_size(170, 32);
-        text_widget4.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("RightJustifiedText", Box::new(text_widget4), box_widget_id);
-
-        let mut button2 = PushButtonWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Hide".to_string(),
-            18,
-            TextJustify::Center,
-        );
-
-        button2.set_origin(250, 290);
-        button2.set_size(200, 32);
-        button2.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        button2.set_border([0.0, 0.0, 0.0, 1.0], 2);
-
-        self.pushrod.borrow_mut().add_widget("HideButton2", Box::new(button2));
-
-        let mut box_1 = BoxWidget::new();
-        box_1.set_origin(480, 80);
-        box_1.set_size(200, 200);
-        box_1.set_color([0.5, 0.5, 1.0, 1.0]);
-        box_1.set_border([0.0, 0.0, 1.0, 1.0], 2);
-        let box_1_id = self.pushrod.borrow_mut().add_widget("Box1", Box::new(box_1));
-
-        let mut inner_box_1 = BoxWidget::new();
-        inner_box_1.set_origin(505, 105);
-        inner_box_1.set_size(70, 60);
-        inner_box_1.set_color([0.75, 0.75, 1.0, 1.0]);
-        inner_box_1.set_border([1.0, 0.0, 1.0, 1.0], 1);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("Box2", Box::new(inner_box_1), box_1_id);
-
-        let mut inner_box_2 = BoxWidget::new();
-        inner_box_2.set_origin(585, 105);
-        inner_box_2.set_size(70, 60);
-        inner_box_2.set_color([0.75, 0.25, 1.0, 1.0]);
-        inner_box_2.set_border([1.0, 1.0, 0.0, 1.0], 1);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("Box3", Box::new(inner_box_2), box_1_id);
-
-        let mut inner_box_3 = BoxWidget::new();
-        inner_box_3.set_origin(505, 190);
-        inner_box_3.set_size(70, 60);
-        inner_box_3.set_color([0.25, 0.50, 0.75, 1.0]);
-        inner_box_3.set_border([1.0, 0.50, 1.0, 1.0], 1);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("Box4", Box::new(inner_box_3), box_1_id);
-
-        let mut inner_box_4 = BoxWidget::new();
-        inner_box_4.set_origin(585, 190);
-        inner_box_4.set_size(70, 60);
-        inner_box_4.set_color([0.75, 0.50, 0.0, 1.0]);
-        inner_box_4.set_border([0.50, 0.0, 0.25, 1.0], 1);
-        self.pushrod
-            .borrow_mut()
-            .add_widget_to_parent("Box5", Box::new(inner_box_4), box_1_id);
-
-        let mut button = PushButtonWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Hide".to_string(),
-            18,
-            TextJustify::Center,
-        );
-
-        button.set_origin(480, 290);
-        button.set_size(200, 32);
-        button.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        button.set_border([0.0, 0.0, 0.0, 1.0], 2);
+        let font = self.get_font();
+        let text = |text: &str, y: i32, justify: TextJustify| {
+            TextWidget::builder(font.clone())
+                .origin(15, y)
+                .size(170, 32)
+                .text(text)
+                .text_color([0.0, 0.0, 0.0, 1.0])
+                .font_size(24)
+                .justify(justify)
+        };
+        let inner_box = |x: i32, y: i32, color: types::Color, border_color: types::Color| {
+            BoxWidget::builder()
+                .origin(x, y)
+                .size(70, 60)
+                .color(color)
+                .border(border_color, 1)
+        };
+
+        pushrod_ui!(self.pushrod.borrow_mut(), {
+            "BoxWidget1": BoxWidget::builder()
+                .origin(250, 80)
+                .size(200, 200)
+                .color([0.0, 1.0, 0.0, 1.0])
+                .border([1.0, 0.0, 0.0, 1.0], 4) => {
+                "LeftJustifiedText": text("Left", 20, TextJustify::Left),
+                "CenterJustifiedText": text("Center", 86, TextJustify::Center),
+                "RightJustifiedText": text("Right", 150, TextJustify::Right),
+            },
+            "HideButton2": self.build_button("Hide", 250, 290, 200),
+            "Box1": BoxWidget::builder()
+                .origin(480, 80)
+                .size(200, 200)
+                .color([0.5, 0.5, 1.0, 1.0])
+                .border([0.0, 0.0, 1.0, 1.0], 2) => {
+                "Box2": inner_box(25, 25, [0.75, 0.75, 1.0, 1.0], [1.0, 0.0, 1.0, 1.0]),
+                "Box3": inner_box(105, 25, [0.75, 0.25, 1.0, 1.0], [1.0, 1.0, 0.0, 1.0]),
+                "Box4": inner_box(25, 110, [0.25, 0.50, 0.75, 1.0], [1.0, 0.50, 1.0, 1.0]),
+                "Box5": inner_box(105, 110, [0.75, 0.50, 0.0, 1.0], [0.50, 0.0, 0.25, 1.0]),
+            },
+            "HideButton3": self.build_button("Hide", 480, 290, 200),
+        })
+        .unwrap_or_else(|error| panic!("Failed to build the boxes: {}", error));
+    }
 
-        self.pushrod.borrow_mut().add_widget("HideButton3", Box::new(button));
+    /// Starts building a push button with black text and a black border, 32 points high.
+    fn build_button(
+        &self,
+        text: &str,
+        x: i32,
+        y: i32,
+        width: i32,
+    ) -> WidgetBuilder<PushButtonWidget> {
+        PushButtonWidget::builder(self.get_font())
+            .origin(x, y)
+            .size(width, 32)
+            .text(text)
+            .text_color([0.0, 0.0, 0.0, 1.0])
+            .font_size(18)
+            .border([0.0, 0.0, 0.0, 1.0], 2)
     }
 
     fn add_powered_by(&mut self) {
@@ -323,40 +241,33 @@ impl SimpleWindow {
 
         self.pushrod.borrow_mut().add_widget("AnimateButton1", Box::new(button1));
 
-        let mut button2 = PushButtonWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Randomize".to_string(),
-            18,
-            TextJustify::Center,
-        );
-
-        button2.set_origin(520, 360);
-        button2.set_size(160, 32);
-        button2.set_text_color([0.0, 0.0, 0.0, 1.0]);
-        button2.set_border([0.0, 0.0, 0.0, 1.0], 2);
+        let button2 = self
+            .build_button("Randomize", 520, 360, 160)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build RandomColorButton2: {}", error));
 
         self.pushrod.borrow_mut().add_widget("RandomColorButton2", Box::new(button2));
     }
 
     fn add_timer(&mut self) {
-        let mut timer = TimerWidget::new();
-        timer.set_timeout(10000);
-        timer.set_enabled(true);
+        let timer = TimerWidget::builder()
+            .timeout_ms(10000)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build TimerWidget1: {}", error));
+
         self.pushrod.borrow_mut().add_widget("TimerWidget1", Box::new(timer));
     }
 
     fn add_debugging(&mut self) {
-        let mut text_widget1 = TextWidget::new(
-            self.pushrod.borrow_mut().get_factory(),
-            "OpenSans-Regular.ttf".to_string(),
-            "Current Widget: 0".to_string(),
-            20,
-            TextJustify::Left,
-        );
-        text_widget1.set_origin(20, 560);
-        text_widget1.set_size(400, 28);
-        text_widget1.set_text_color([0.0, 0.0, 0.0, 1.0]);
+        let text_widget1 = TextWidget::builder(self.get_font())
+            .origin(20, 560)
+            .size(400, 28)
+            .text("Current Widget: 0")
+            .text_color([0.0, 0.0, 0.0, 1.0])
+            .font_size(20)
+            .build()
+            .unwrap_or_else(|error| panic!("Failed to build DebugText1: {}", error));
+
         self.pushrod
             .borrow_mut()
             .add_widget("DebugText1", Box::new(text_widget1));
@@ -380,6 +291,7 @@ impl SimpleWindow {
         let mut handler = SimpleWindowEventHandler::new();
 
         self.build();
+        self.get_pushrod().enable_tooltips("OpenSans-Regular.ttf", 14);
         self.get_pushrod().run(&mut handler);
     }
 }
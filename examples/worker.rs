@@ -0,0 +1,125 @@
+// Worker Example
+// Shows the progress of a background thread, which reports it through the event bus
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate pushrod;
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use piston_window::*;
+use pushrod::core::callbacks::*;
+use pushrod::core::draw_target::*;
+use pushrod::core::main::*;
+use pushrod::core::widget_store::*;
+use pushrod::event::event::*;
+use pushrod::widget::config::*;
+use pushrod::widget::progress_widget::*;
+use pushrod::widget::widget::*;
+
+/// Name of the custom event that the worker thread sends with its progress, from 0 to 100.
+const PROGRESS_EVENT: &str = "WorkerProgress";
+
+/// A `ProgressWidget` that follows the progress events of the worker thread.
+struct WorkerProgressWidget {
+    progress_widget: ProgressWidget,
+}
+
+impl Widget for WorkerProgressWidget {
+    fn config(&mut self) -> &mut Configurable {
+        self.progress_widget.config()
+    }
+
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.progress_widget.set_origin(x, y);
+    }
+
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.progress_widget.set_size(w, h);
+    }
+
+    fn event_mask(&mut self) -> EventMask {
+        MASK_EVENT_CUSTOM
+    }
+
+    fn handle_pushrod_event(&mut self, event: &PushrodEvent) {
+        if let PushrodEvent::Custom { name, payload } = event {
+            if let (PROGRESS_EVENT, Some(progress)) = (name.as_str(), payload.downcast_ref()) {
+                self.progress_widget.set_progress(*progress);
+            }
+        }
+    }
+
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.progress_widget.draw(c, g, clip);
+    }
+}
+
+struct WorkerEventHandler {}
+
+impl PushrodCallbackEvents for WorkerEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+fn main() {
+    let window: PistonWindow = WindowSettings::new("Pushrod Worker", [340, 100])
+        .opengl(OpenGL::V3_2)
+        .build()
+        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+    let mut pushrod = Pushrod::new(window);
+    let mut handler = WorkerEventHandler {};
+    let mut progress_widget = ProgressWidget::new();
+
+    progress_widget.set_origin(20, 34);
+    progress_widget.set_size(300, 32);
+    progress_widget.set_secondary_color([0.0, 0.5, 1.0, 1.0]);
+    pushrod.add_widget(
+        "WorkerProgress",
+        Box::new(WorkerProgressWidget { progress_widget }),
+    );
+
+    // The application is told when the work is done through the same events.
+    pushrod.widget_store.borrow_mut().subscribe(
+        MASK_EVENT_CUSTOM,
+        Box::new(|event, _widget_store| {
+            if let PushrodEvent::Custom { name, payload } = event {
+                if name == PROGRESS_EVENT && payload.downcast_ref::<u16>() == Some(&100) {
+                    eprintln!("The worker has finished.");
+                }
+            }
+        }),
+    );
+
+    let sender = pushrod.widget_store.borrow_mut().get_event_sender();
+
+    // Pretends to do some slow work, reporting the progress along the way.  The thread stops
+    // early if the window is closed.
+    thread::spawn(move || {
+        for progress in 0..=100u16 {
+            thread::sleep(Duration::from_millis(50));
+
+            let event = PushrodEvent::Custom {
+                name: PROGRESS_EVENT.to_string(),
+                payload: Arc::new(progress),
+            };
+
+            if !sender.send(event) {
+                break;
+            }
+        }
+    });
+
+    pushrod.run(&mut handler);
+}
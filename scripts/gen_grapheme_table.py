@@ -0,0 +1,131 @@
+#!/usr/bin/env python3
+#
+# Generates the GRAPHEME_CLASSES table of src/widget/grapheme.rs from the Unicode Character
+# Database.  The table was last generated from Unicode 17.0.0.  To update it, download these
+# files of the new version into one directory:
+#
+#   https://www.unicode.org/Public/<version>/ucd/auxiliary/GraphemeBreakProperty.txt
+#   https://www.unicode.org/Public/<version>/ucd/emoji/emoji-data.txt
+#   https://www.unicode.org/Public/<version>/ucd/DerivedCoreProperties.txt
+#
+# then run the script with that directory, which replaces the table in place:
+#
+#   scripts/gen_grapheme_table.py <directory>
+#
+# and update the Unicode version in this comment, and in the documentation of grapheme.rs.
+
+import os
+import re
+import sys
+
+GRAPHEME_RS = os.path.join(os.path.dirname(__file__), "..", "src", "widget", "grapheme.rs")
+
+# Grapheme_Cluster_Break values, and the GraphemeClass that each becomes.  LV and LVT are left
+# out, as precomposed Hangul syllables are told apart by arithmetic.
+BREAK_CLASSES = {
+    "CR": "CarriageReturn",
+    "LF": "LineFeed",
+    "Control": "Control",
+    "Prepend": "Prepend",
+    "Extend": "Extend",
+    "SpacingMark": "SpacingMark",
+    "ZWJ": "ZeroWidthJoiner",
+    "Regional_Indicator": "RegionalIndicator",
+    "L": "HangulL",
+    "V": "HangulV",
+    "T": "HangulT",
+}
+
+
+def read_property(path, field_count):
+    """Yields (first, last, fields) for each line of a UCD file, where fields are the values
+    after the code points."""
+    with open(path, encoding="utf-8") as ucd_file:
+        for line in ucd_file:
+            line = line.split("#", 1)[0].strip()
+
+            if not line:
+                continue
+
+            fields = [field.strip() for field in line.split(";")]
+            first, _, last = fields[0].partition("..")
+
+            yield int(first, 16), int(last or first, 16), fields[1 : 1 + field_count]
+
+
+def read_classes(directory):
+    classes = {}
+
+    for first, last, (value,) in read_property(
+        os.path.join(directory, "GraphemeBreakProperty.txt"), 1
+    ):
+        if value in BREAK_CLASSES:
+            for code in range(first, last + 1):
+                classes[code] = BREAK_CLASSES[value]
+
+    for first, last, (value,) in read_property(os.path.join(directory, "emoji-data.txt"), 1):
+        if value == "Extended_Pictographic":
+            for code in range(first, last + 1):
+                classes.setdefault(code, "Pictographic")
+
+    for first, last, fields in read_property(
+        os.path.join(directory, "DerivedCoreProperties.txt"), 2
+    ):
+        if fields[0] != "InCB":
+            continue
+
+        for code in range(first, last + 1):
+            current = classes.get(code)
+
+            if fields[1] == "Consonant" and current is None:
+                classes[code] = "IndicConsonant"
+            elif fields[1] == "Linker" and current == "Extend":
+                classes[code] = "IndicLinker"
+            elif fields[1] == "Extend" and current == "Extend":
+                classes[code] = "IndicExtend"
+
+    # A char can never be a surrogate.
+    for code in range(0xD800, 0xE000):
+        classes.pop(code, None)
+
+    return classes
+
+
+def make_ranges(classes):
+    ranges = []
+
+    for code in sorted(classes):
+        if ranges and ranges[-1][1] == code - 1 and ranges[-1][2] == classes[code]:
+            ranges[-1][1] = code
+        else:
+            ranges.append([code, code, classes[code]])
+
+    return ranges
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit("usage: gen_grapheme_table.py <directory of UCD files>")
+
+    table = "".join(
+        "    (0x%04x, 0x%04x, %s),\n" % (first, last, name)
+        for first, last, name in make_ranges(read_classes(sys.argv[1]))
+    )
+
+    with open(GRAPHEME_RS, encoding="utf-8") as source_file:
+        source = source_file.read()
+
+    pattern = re.compile(
+        r"(const GRAPHEME_CLASSES: &\[\(u32, u32, GraphemeClass\)\] = &\[\n).*?(^\];)",
+        re.S | re.M,
+    )
+
+    if not pattern.search(source):
+        sys.exit("the GRAPHEME_CLASSES table was not found in grapheme.rs")
+
+    with open(GRAPHEME_RS, "w", encoding="utf-8") as source_file:
+        source_file.write(pattern.sub(lambda match: match[1] + table + match[2], source, 1))
+
+
+if __name__ == "__main__":
+    main()
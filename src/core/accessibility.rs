@@ -0,0 +1,253 @@
+// Accessibility
+// Describes widgets to assistive tools: their roles, names, values, and states
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::config::CheckState;
+
+/// This `enum` specifies what a `Widget` is to assistive tools, such as screen readers.  Each
+/// `Widget` has the role of its type (see `Widget::default_accessible_role`), which can be
+/// changed with `Widget::set_accessible_role`, such as for a `CanvasWidget` that draws a button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum AccessibleRole {
+    /// A `Widget` that has no particular role, such as a `CanvasWidget` or a `BoxWidget`.
+    Generic,
+
+    /// The top level `Widget` of a `WidgetStore`, which covers the window.
+    Window,
+
+    /// A `Widget` that groups other `Widget`s, such as a `GroupBoxWidget`.
+    Group,
+
+    /// Text that cannot be edited, such as a `TextWidget`.
+    Text,
+
+    /// A picture, such as an `ImageWidget`.
+    Image,
+
+    /// A button that does something when it is clicked, such as a `ButtonWidget`.
+    Button,
+
+    /// A button that stays pressed until it is clicked again, such as a `ToggleButtonWidget`.
+    ToggleButton,
+
+    /// A box that can be checked, such as a `CheckboxWidget`.
+    CheckBox,
+
+    /// One of a group of buttons, of which only one is selected, such as a `RadioButtonWidget`.
+    RadioButton,
+
+    /// Text that can be edited, such as a `TextInputWidget`.
+    TextInput,
+
+    /// A number that is edited by moving a thumb, such as a `SliderWidget`.
+    Slider,
+
+    /// A number that is edited by typing, or by stepping it up and down, such as a
+    /// `SpinnerWidget`.
+    SpinButton,
+
+    /// How far a task has got, such as a `ProgressBarWidget`.
+    ProgressBar,
+
+    /// A choice of one of a list of items, which is opened to choose, such as a `DropdownWidget`.
+    ComboBox,
+
+    /// A list of items, such as a `ListWidget`.
+    List,
+
+    /// A tree of items, such as a `TreeWidget`.
+    Tree,
+
+    /// Rows of items, in columns, such as a `TableWidget`.
+    Table,
+
+    /// Tabs, each of which shows a page, such as a `TabWidget`.
+    TabList,
+
+    /// A menu, or a bar of menus, such as a `MenuBarWidget`.
+    Menu,
+
+    /// A row of tools, such as a `ToolbarWidget`.
+    Toolbar,
+
+    /// A bar along the bottom of the window that shows messages, such as a `StatusBarWidget`.
+    StatusBar,
+
+    /// A `Widget` whose contents can be scrolled, such as a `ScrollContainerWidget`.
+    ScrollArea,
+
+    /// Two panes with a divider between them that can be dragged, such as a `SplitPaneWidget`.
+    SplitPane,
+
+    /// A dialog, such as a `DialogWidget`.
+    Dialog,
+
+    /// A tooltip, such as a `TooltipWidget`.
+    Tooltip,
+}
+
+/// This is an `AccessibleNode`, which describes a `Widget`, and its visible children, to
+/// assistive tools.  A tree of nodes is returned by `WidgetStore::get_accessibility_tree`.  It
+/// can be serialized with `serde`, such as with `toml::to_string`, to be handed to a process
+/// that bridges it to the accessibility API of the platform.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AccessibleNode {
+    /// ID of the `Widget`.
+    pub id: WidgetId,
+
+    /// Name that the `Widget` was added to the `WidgetStore` with.
+    pub widget_name: String,
+
+    /// What the `Widget` is.  See `Widget::get_accessible_role`.
+    pub role: AccessibleRole,
+
+    /// Name that is read out for the `Widget`, such as the text of a button.  See
+    /// `Widget::get_accessible_name`.
+    pub name: String,
+
+    /// Current value of the `Widget`, such as the text of a `TextInputWidget`, if it has one.
+    /// See `Widget::get_accessible_value`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// Whether the `Widget` is checked, if it can be.  See `Widget::get_accessible_checked`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<CheckState>,
+
+    /// Indicates whether the `Widget` can receive keyboard focus.
+    pub focusable: bool,
+
+    /// Indicates whether the `Widget` has keyboard focus.
+    pub focused: bool,
+
+    /// Indicates whether the `Widget` cannot be used: it, or any of its parents, is disabled, or
+    /// a modal dialog keeps it from receiving events.
+    pub disabled: bool,
+
+    /// Bounds of the `Widget`, in window coordinates.
+    pub bounds: Rect,
+
+    /// The visible children of the `Widget`, in the order in which they were added.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<AccessibleNode>,
+}
+
+/// Implementation of the `AccessibleNode`.
+impl AccessibleNode {
+    /// Finds the node of the `Widget` with the ID `widget_id` in this node, or in any of its
+    /// children.
+    pub fn find(&self, widget_id: WidgetId) -> Option<&AccessibleNode> {
+        if self.id == widget_id {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.find(widget_id))
+    }
+}
+
+/// This `enum` specifies what changed about a `Widget`, with the new value, in a
+/// `PushrodEvent::AccessibilityChanged` event.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccessibleChange {
+    /// The accessible name changed, such as the text of a button.
+    Name(String),
+
+    /// The value changed, such as when a `SliderWidget` was moved.
+    Value(Option<String>),
+
+    /// The check state changed, such as when a `CheckboxWidget` was toggled.
+    Checked(Option<CheckState>),
+
+    /// The `Widget` got the keyboard focus, or lost it.
+    Focused(bool),
+}
+
+/// Remembers the names, values, and check states of the `Widget`s of a `WidgetStore`, and the
+/// focused `Widget`, so that the changes to them can be posted as events.
+pub(crate) struct AccessibilityTracker {
+    states: HashMap<WidgetId, (String, Option<String>, Option<CheckState>)>,
+    focused_id: WidgetId,
+}
+
+/// Implementation of the `AccessibilityTracker`.
+impl AccessibilityTracker {
+    /// Creates a new tracker, with the `Widget` that has the focus now.
+    pub(crate) fn new(focused_id: WidgetId) -> Self {
+        Self {
+            states: HashMap::new(),
+            focused_id,
+        }
+    }
+
+    /// Remembers the name, value, and check state of the `Widget` that `node` describes, and
+    /// returns how they changed since they were last remembered.  Nothing has changed about a
+    /// `Widget` that is seen for the first time.
+    pub(crate) fn track(&mut self, node: &AccessibleNode) -> Vec<AccessibleChange> {
+        let state = (node.name.clone(), node.value.clone(), node.checked);
+        let previous = match self.states.insert(node.id, state) {
+            Some(previous) => previous,
+            None => return Vec::new(),
+        };
+        let mut changes = Vec::new();
+
+        if previous.0 != node.name {
+            changes.push(AccessibleChange::Name(node.name.clone()));
+        }
+
+        if previous.1 != node.value {
+            changes.push(AccessibleChange::Value(node.value.clone()));
+        }
+
+        if previous.2 != node.checked {
+            changes.push(AccessibleChange::Checked(node.checked));
+        }
+
+        changes
+    }
+
+    /// Forgets a `Widget` that has been removed.
+    pub(crate) fn forget(&mut self, widget_id: WidgetId) {
+        self.states.remove(&widget_id);
+    }
+
+    /// Remembers the `Widget` that has the focus, and returns the `Widget`s that lost and got
+    /// the focus since it was last remembered.
+    pub(crate) fn track_focus(
+        &mut self,
+        focused_id: WidgetId,
+    ) -> Vec<(WidgetId, AccessibleChange)> {
+        let previous_id = std::mem::replace(&mut self.focused_id, focused_id);
+        let mut changes = Vec::new();
+
+        if previous_id == focused_id {
+            return changes;
+        }
+
+        if previous_id.is_valid() {
+            changes.push((previous_id, AccessibleChange::Focused(false)));
+        }
+
+        if focused_id.is_valid() {
+            changes.push((focused_id, AccessibleChange::Focused(true)));
+        }
+
+        changes
+    }
+}
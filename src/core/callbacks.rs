@@ -13,64 +13,506 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::core::error::PushrodError;
 use crate::core::point::Point;
+use crate::core::widget_id::*;
 use crate::core::widget_store::*;
 
 use piston_window::*;
 
+/// Closure type that is given mutable access to the `WidgetStore`, so that it can modify other
+/// `Widget`s.  These closures are never called while the `WidgetStore` is iterating over its
+/// `Widget`s; they are queued by the `Widget`, and called afterward.
+pub type WidgetStoreCallback = Box<dyn FnMut(&mut WidgetStore)>;
+
+/// A shared handle to a `WidgetStoreCallback`, which a `Widget` queues each time the callback
+/// needs to be called.  See `Widget::take_deferred_callbacks`.
+pub type DeferredCallback = Rc<RefCell<WidgetStoreCallback>>;
+
+/// Closure type that is called when the window is asked to close.  Returning `false` cancels
+/// the close.  See `Pushrod::on_close_requested`.
+pub type CloseRequestedCallback = Box<dyn FnMut(&mut WidgetStore) -> bool>;
+
+/// Closure type that is called when the window gains (`true`) or loses (`false`) focus.  See
+/// `Pushrod::on_focus_changed`.
+pub type FocusChangedCallback = Box<dyn FnMut(bool, &mut WidgetStore)>;
+
+/// Closure type that is called once for every frame, before it is drawn.  See
+/// `Pushrod::on_frame`.
+pub type FrameCallback = Box<dyn FnMut(&mut WidgetStore)>;
+
+/// Closure type that is called with every event from the window, before it is handled.  See
+/// `Pushrod::on_raw_event`.
+pub type RawEventCallback = Box<dyn FnMut(&Event, &mut WidgetStore)>;
+
+/// Closure type that is called with each error that is reported while the run loop is running.
+/// See `Pushrod::on_error`.
+pub type ErrorCallback = Box<dyn FnMut(&PushrodError, &mut WidgetStore)>;
+
+/// Closure type that is called with the result of a modal dialog once it has been closed, or
+/// `None` if it was dismissed.  See `DialogWidget::on_closed`.
+pub type ModalClosedCallback = Box<dyn FnMut(Option<String>, &mut WidgetStore)>;
+
+/// Closure type that is called with the ID of the newly selected `Widget` of a radio group when the
+/// selection of the group changes, or with `WidgetId::INVALID` when the selected `Widget` is
+/// removed.
+pub type RadioSelectionCallback = Box<dyn FnMut(WidgetId)>;
+
+/// This is the `DragPayload`, which carries the data of a drag-and-drop operation that was
+/// started with `WidgetStore::begin_drag`.  It is sent along with the `Dropped` and
+/// `DragCancelled` events.  Copies of the event share the same payload, and only the first
+/// recipient to `take` it receives it, so the data is only ever owned by one recipient.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::callbacks::*;
+/// # fn main() {
+///    let payload = DragPayload::new(Box::new(String::from("Tile 3")));
+///    let shared_payload = payload.clone();
+///
+///    assert!(payload.is::<String>());
+///    assert!(payload.take_as::<u32>().is_none());
+///    assert_eq!(*shared_payload.take_as::<String>().unwrap(), "Tile 3");
+///    assert!(payload.take().is_none());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DragPayload(Rc<RefCell<Option<Box<dyn Any>>>>);
+
+/// Implementation of the `DragPayload`.
+impl DragPayload {
+    /// Creates a new `DragPayload` holding the specified data.
+    pub fn new(payload: Box<dyn Any>) -> Self {
+        DragPayload(Rc::new(RefCell::new(Some(payload))))
+    }
+
+    /// Takes the data out of the payload, or returns `None` if it has already been taken.
+    pub fn take(&self) -> Option<Box<dyn Any>> {
+        self.0.borrow_mut().take()
+    }
+
+    /// Takes the data out of the payload if it is of type `T`.  If it is of another type, or has
+    /// already been taken, `None` is returned, and the payload is left as it was.
+    pub fn take_as<T: 'static>(&self) -> Option<Box<T>> {
+        if !self.is::<T>() {
+            return None;
+        }
+
+        self.take().and_then(|payload| payload.downcast::<T>().ok())
+    }
+
+    /// Indicates whether or not the payload still holds data of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        match &*self.0.borrow() {
+            Some(payload) => payload.is::<T>(),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("DragPayload")
+    }
+}
+
 /// This is the callback event class that is used to handle events that are produced when a widget
 /// is interacted with in the Pushrod Run Loop.
 pub trait PushrodCallbackEvents {
     fn handle_event(&mut self, event: CallbackEvent, widget_store: &mut WidgetStore);
 }
 
+/// This `enum` is returned by `Widget::handle_input_event`, to decide whether a mouse button,
+/// scroll, or key event keeps propagating once a `Widget` has seen it.  Mouse events are offered
+/// to the `Widget`s under the mouse, topmost first, and key events to the focused `Widget`, and
+/// then to each of its parents in turn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventResult {
+    /// The `Widget` did not handle the event, so it is offered to the next `Widget`: the one
+    /// underneath, for a mouse event, or the parent, for a key event.  If no `Widget` handles
+    /// the event, the application still receives it.
+    NotHandled,
+
+    /// The `Widget` handled the event, so no other `Widget` receives it, but the application
+    /// does.  This is the default.
+    Handled,
+
+    /// The `Widget` handled the event, and neither other `Widget`s nor the application receive
+    /// it.
+    Consumed,
+}
+
+/// The modifier keys that were held down when a key event was generated.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub gui: bool,
+}
+
+/// Implementation of the `KeyModifiers`.
+impl KeyModifiers {
+    /// Updates the modifier state based on a key being pressed or released.  Keys that are not
+    /// modifiers are ignored.
+    ///
+    /// Example:
+    /// ```
+    /// # use piston_window::*;
+    /// # use pushrod::core::callbacks::*;
+    /// # fn main() {
+    ///    let mut modifiers = KeyModifiers::default();
+    ///
+    ///    modifiers.update(Key::LShift, ButtonState::Press);
+    ///    modifiers.update(Key::A, ButtonState::Press);
+    ///    assert!(modifiers.shift);
+    ///    assert!(!modifiers.ctrl);
+    ///
+    ///    modifiers.update(Key::LShift, ButtonState::Release);
+    ///    assert_eq!(modifiers, KeyModifiers::default());
+    /// # }
+    /// ```
+    pub fn update(&mut self, key: Key, state: ButtonState) {
+        let pressed = state == ButtonState::Press;
+
+        match key {
+            Key::LShift | Key::RShift => self.shift = pressed,
+            Key::LCtrl | Key::RCtrl => self.ctrl = pressed,
+            Key::LAlt | Key::RAlt => self.alt = pressed,
+            Key::LGui | Key::RGui => self.gui = pressed,
+            _ => (),
+        }
+    }
+
+    /// Indicates whether the modifier for keyboard shortcuts, such as `Ctrl-C` to copy, is held
+    /// down.  This is `Cmd` (the `gui` modifier) on macOS, and `Ctrl` everywhere else.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::callbacks::*;
+    /// # fn main() {
+    ///    let ctrl = KeyModifiers {
+    ///        ctrl: true,
+    ///        ..KeyModifiers::default()
+    ///    };
+    ///
+    ///    assert_eq!(ctrl.is_command(), !cfg!(target_os = "macos"));
+    ///    assert!(!KeyModifiers::default().is_command());
+    /// # }
+    /// ```
+    pub fn is_command(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.gui
+        } else {
+            self.ctrl
+        }
+    }
+}
+
+/// The number of points that one line of a `ScrollDelta`, or one notch of a mouse wheel,
+/// scrolls by.
+pub const SCROLL_LINE_PIXELS: f64 = 20.0;
+
+/// How the distances of a `ScrollDelta` are measured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollUnit {
+    /// Lines, or notches of a mouse wheel, of `SCROLL_LINE_PIXELS` points each.
+    Lines,
+
+    /// Points, as touchpads scroll precisely.
+    Pixels,
+}
+
+/// The distance that was scrolled along each axis, by a mouse wheel or a touchpad.  Positive
+/// distances scroll back towards the top or the left of the content, as turning a mouse wheel
+/// away from the user does.  Distances keep their fractions, as touchpads scroll by less than a
+/// line at a time.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::callbacks::*;
+/// # fn main() {
+///    let wheel = ScrollDelta::new(0.0, -1.5, ScrollUnit::Lines);
+///
+///    assert_eq!(wheel.to_points(), (0.0, -30.0));
+///    assert_eq!(wheel.to_lines(), (0.0, -1.5));
+///    assert_eq!(wheel.to_horizontal(), ScrollDelta::new(-1.5, 0.0, ScrollUnit::Lines));
+///
+///    let touchpad = ScrollDelta::new(4.0, 10.0, ScrollUnit::Pixels);
+///
+///    assert_eq!(touchpad.to_lines(), (0.2, 0.5));
+///    assert_eq!(touchpad.to_horizontal(), touchpad);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollDelta {
+    pub x: f64,
+    pub y: f64,
+    pub unit: ScrollUnit,
+}
+
+/// Implementation of the `ScrollDelta`.
+impl ScrollDelta {
+    /// Creates a new `ScrollDelta` of `x` and `y`, measured in `unit`.
+    pub fn new(x: f64, y: f64, unit: ScrollUnit) -> Self {
+        Self { x, y, unit }
+    }
+
+    /// Returns the distances along each axis in points.
+    pub fn to_points(&self) -> (f64, f64) {
+        match self.unit {
+            ScrollUnit::Lines => (self.x * SCROLL_LINE_PIXELS, self.y * SCROLL_LINE_PIXELS),
+            ScrollUnit::Pixels => (self.x, self.y),
+        }
+    }
+
+    /// Returns the distances along each axis in lines, which may be fractions of a line.
+    pub fn to_lines(&self) -> (f64, f64) {
+        match self.unit {
+            ScrollUnit::Lines => (self.x, self.y),
+            ScrollUnit::Pixels => (self.x / SCROLL_LINE_PIXELS, self.y / SCROLL_LINE_PIXELS),
+        }
+    }
+
+    /// Turns a scroll that is only vertical into a horizontal one, as a mouse wheel scrolls
+    /// while Shift is held down.  A scroll that is already horizontal is left as it is.  The
+    /// `Pushrod` run loop does this before the scroll is sent to any `Widget`.
+    pub fn to_horizontal(&self) -> Self {
+        if self.x == 0.0 {
+            Self::new(self.y, 0.0, self.unit)
+        } else {
+            *self
+        }
+    }
+
+    /// Indicates whether nothing was scrolled.
+    pub fn is_zero(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0
+    }
+}
+
 /// These are the different types of events that can be triggered.  Any other callback events
 /// should be extended in this enum definition.
 #[derive(Clone, Debug)]
 pub enum CallbackEvent {
     MouseEntered {
-        widget_id: i32,
+        widget_id: WidgetId,
     },
     MouseExited {
-        widget_id: i32,
+        widget_id: WidgetId,
     },
+    /// The mouse wheel or a touchpad was scrolled over a `Widget`.  `delta` is the distance
+    /// scrolled along each axis, and `modifiers` are the modifier keys that were held down.  A
+    /// vertical scroll with Shift held down has already been turned into a horizontal one (see
+    /// `ScrollDelta::to_horizontal`).  A `Widget` that cannot scroll any further returns
+    /// `EventResult::NotHandled` from `Widget::handle_input_event`, so that the scroll is
+    /// offered to the `Widget` behind it, such as an enclosing `ScrollContainerWidget`.
     MouseScrolled {
-        widget_id: i32,
-        point: Point,
+        widget_id: WidgetId,
+        delta: ScrollDelta,
+        modifiers: KeyModifiers,
     },
+    /// The mouse moved within a `Widget`.  This is sent after `MouseEntered` when the mouse
+    /// moves into a new `Widget`.  `point` is relative to the `Widget`'s origin.
     MouseMoved {
-        widget_id: i32,
+        widget_id: WidgetId,
         point: Point,
     },
+    /// A key was pressed or released, as indicated by `state`.  This is sent to the `Widget`
+    /// that has keyboard focus.  If no `Widget` has focus, it is only sent to the application's
+    /// `PushrodCallbackEvents`, with a `widget_id` of `WidgetId::INVALID`.
     KeyPressed {
-        widget_id: i32,
+        widget_id: WidgetId,
         key: Key,
         state: ButtonState,
+        modifiers: KeyModifiers,
     },
+
+    /// Text was typed, as `text`.  This is sent after the `KeyPressed` events of the keys that
+    /// typed it, to the `Widget` that has keyboard focus, and then to each of its parents until
+    /// one of them handles it.  If no `Widget` has focus, it is only sent to the application's
+    /// `PushrodCallbackEvents`, with a `widget_id` of `WidgetId::INVALID`.
+    TextInput {
+        widget_id: WidgetId,
+        text: String,
+    },
+
+    /// The `Widget` received keyboard focus.
+    GotFocus {
+        widget_id: WidgetId,
+    },
+
+    /// The `Widget` lost keyboard focus.  This is sent before the newly focused `Widget` is sent
+    /// `GotFocus`.
+    LostFocus {
+        widget_id: WidgetId,
+    },
+
+    /// The popup was closed, by `WidgetStore::close_popup`, or by a mouse button press outside
+    /// of it.  The popup has already been hidden.
+    PopupClosed {
+        widget_id: WidgetId,
+    },
+
+    /// The window was resized to `size`.  This is sent to the application, and then to every
+    /// `Widget`, so they can reposition themselves.  When the window is resized many times in
+    /// quick succession, only the final size is sent, once per update or draw.  The base widget
+    /// is resized to the window, and the whole window is redrawn afterward.
     WindowResized {
         size: crate::core::point::Size,
     },
+    /// The number of device pixels per point of the window changed, such as when the window was
+    /// moved to a display with a different density.  This is sent to the application, and then
+    /// to every `Widget`, once `Widget::scale_factor_changed` has been called for every `Widget`.
+    /// Origins and sizes are in points, so layouts do not need to change, and the whole window
+    /// is redrawn afterward.
+    ScaleFactorChanged {
+        scale_factor: f64,
+    },
+    /// The window gained (`flag` is `true`) or lost focus.  This is sent to the application, and
+    /// then to every `Widget`.  When the window loses focus, the mouse is first released: the
+    /// hovered `Widget` receives `MouseExited`, any `Widget` with a button pressed receives
+    /// `MouseButtonUpOutside`, and any drag is cancelled, so visual states can be reset.
     WindowFocused {
         flag: bool,
     },
+    /// A mouse button was pressed inside of a `Widget`.  `point` is relative to the `Widget`'s
+    /// origin.
     MouseButtonDown {
-        widget_id: i32,
+        widget_id: WidgetId,
         button: Button,
+        point: Point,
     },
+
+    /// A mouse button was released inside of the `Widget` that it was pressed in.  `point` is
+    /// relative to the `Widget`'s origin.
     MouseButtonUpInside {
-        widget_id: i32,
+        widget_id: WidgetId,
         button: Button,
+        point: Point,
     },
+
+    /// A mouse button was released outside of the `Widget` that it was pressed in.  This event
+    /// is sent to the `Widget` that received the press.  `point` is relative to that `Widget`'s
+    /// origin, so it may be negative, or larger than the `Widget`'s size.
     MouseButtonUpOutside {
-        widget_id: i32,
+        widget_id: WidgetId,
+        button: Button,
+        point: Point,
+    },
+
+    /// A finger touched a `Widget` that handles multi-touch; see `Widget::set_multi_touch`.
+    /// `touch_id` tells the fingers apart, and is the same for all of the events of a touch.
+    /// `point` is relative to the `Widget`'s origin.
+    TouchStarted {
+        widget_id: WidgetId,
+        touch_id: i64,
+        point: Point,
+    },
+
+    /// A finger that touched a `Widget` that handles multi-touch moved.  This event is sent to
+    /// that `Widget`, even once the finger has left it, so `point`, which is relative to the
+    /// `Widget`'s origin, may be negative, or larger than the `Widget`'s size.
+    TouchMoved {
+        widget_id: WidgetId,
+        touch_id: i64,
+        point: Point,
+    },
+
+    /// A finger that touched a `Widget` that handles multi-touch was lifted, at `point`,
+    /// relative to the `Widget`'s origin.
+    TouchEnded {
+        widget_id: WidgetId,
+        touch_id: i64,
+        point: Point,
+    },
+
+    /// A touch on a `Widget` that handles multi-touch was cancelled, such as when the window
+    /// lost focus, so it should be forgotten, rather than treated as lifted.
+    TouchCancelled {
+        widget_id: WidgetId,
+        touch_id: i64,
+    },
+
+    /// A mouse button was pressed and released inside of a `Widget`, without the mouse being
+    /// dragged in between.  `click_count` is 1 for a single click, 2 for a double click, and
+    /// so on.  `point` is relative to the `Widget`'s origin, and `modifiers` are the modifier
+    /// keys that were held down, such as `Ctrl` or `Shift` to extend a selection.  This is sent
+    /// after `MouseButtonUpInside`.
+    MouseClicked {
+        widget_id: WidgetId,
         button: Button,
+        point: Point,
+        click_count: u32,
+        modifiers: KeyModifiers,
+    },
+
+    /// The `Widget` was disabled or enabled using `WidgetStore::set_widget_disabled`.  `Widget`s
+    /// receive this event even when they are disabled.
+    DisabledChanged {
+        widget_id: WidgetId,
+        disabled: bool,
+    },
+
+    /// The mouse entered a `Widget` while dragging something from the `Widget` with the ID
+    /// `source_id`.  While a drag is active, this is sent instead of `MouseEntered`.
+    DragEntered {
+        widget_id: WidgetId,
+        source_id: WidgetId,
+    },
+
+    /// The mouse left a `Widget` while dragging something.  While a drag is active, this is sent
+    /// instead of `MouseExited`.
+    DragExited {
+        widget_id: WidgetId,
+        source_id: WidgetId,
+    },
+
+    /// The mouse moved within a `Widget` while dragging something.  `point` is relative to the
+    /// `Widget`'s origin.  While a drag is active, this is sent instead of `MouseMoved`.
+    DragMoved {
+        widget_id: WidgetId,
+        source_id: WidgetId,
+        point: Point,
+    },
+
+    /// Something dragged from the `Widget` with the ID `source_id` was dropped on this `Widget`.
+    /// `point` is relative to the `Widget`'s origin.  The `Widget` is sent this event before the
+    /// application, so it has the first chance to take the `payload`.
+    Dropped {
+        widget_id: WidgetId,
+        source_id: WidgetId,
+        point: Point,
+        payload: DragPayload,
+    },
+
+    /// A drag from this `Widget` was cancelled, either because it was dropped where there was
+    /// no `Widget` to drop it on, or because the Escape key was pressed.  The `payload` is
+    /// returned to the `Widget` that started the drag.
+    DragCancelled {
+        widget_id: WidgetId,
+        payload: DragPayload,
+    },
+
+    /// The configuration of this `Widget` was changed since the last time the run loop
+    /// checked.  `keys` contains the name of each `Configurable` key that was set or removed,
+    /// once per key, no matter how many times it changed.  The `Widget` has already been
+    /// invalidated, and if its origin or size changed, the area it covered before is repainted.
+    ConfigChanged {
+        widget_id: WidgetId,
+        keys: Vec<&'static str>,
     },
 
     WidgetClicked {
-        widget_id: i32,
+        widget_id: WidgetId,
         button: Button,
     },
     TimerTriggered {
-        widget_id: i32,
+        widget_id: WidgetId,
     }
 }
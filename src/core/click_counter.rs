@@ -0,0 +1,198 @@
+// Click Counter
+// Synthesizes single, double, and multiple clicks from mouse press and release pairs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Instant;
+
+use piston_window::*;
+
+use crate::core::clock::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+
+/// Default number of milliseconds allowed between clicks for them to count as a multiple click.
+pub const DEFAULT_CLICK_INTERVAL: u64 = 400;
+
+/// Default number of pixels the mouse may move between clicks, or between a press and its
+/// release, for them to count as part of the same click.
+pub const DEFAULT_CLICK_DISTANCE: i32 = 4;
+
+/// The press that was last seen for a mouse button, waiting for its release.
+struct PendingPress {
+    widget_id: WidgetId,
+    button: Button,
+    point: Point,
+}
+
+/// The click that was last counted, used to detect the next click in a series.
+struct LastClick {
+    widget_id: WidgetId,
+    button: Button,
+    point: Point,
+    time: Instant,
+    count: u32,
+}
+
+/// This is the `ClickCounter`, which is used by the `Pushrod` run loop to turn mouse button
+/// presses and releases into clicks.  A click occurs when a button is pressed and released in
+/// the same `Widget` without the mouse moving further than the click distance.  Clicks that
+/// follow each other on the same `Widget` with the same button, within the click interval and
+/// click distance, increase the click count: 1 for a single click, 2 for a double click, and so
+/// on.  Points are absolute window coordinates.
+///
+/// Example:
+/// ```
+/// # use piston_window::*;
+/// # use pushrod::core::click_counter::*;
+/// # use pushrod::core::clock::*;
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_id::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let mut counter = ClickCounter::with_clock(Box::new(clock.clone()));
+///    let left = Button::Mouse(MouseButton::Left);
+///    let widget_id = WidgetId::from(1);
+///
+///    counter.press(widget_id, left, make_point_i32(10, 10));
+///    assert_eq!(counter.release(widget_id, left, make_point_i32(10, 10)), Some(1));
+///
+///    clock.advance(100);
+///    counter.press(widget_id, left, make_point_i32(11, 10));
+///    assert_eq!(counter.release(widget_id, left, make_point_i32(11, 10)), Some(2));
+///
+///    // Too slow to count as a triple click.
+///    clock.advance(1000);
+///    counter.press(widget_id, left, make_point_i32(11, 10));
+///    assert_eq!(counter.release(widget_id, left, make_point_i32(11, 10)), Some(1));
+///
+///    // Dragging between the press and release is not a click.
+///    counter.press(widget_id, left, make_point_i32(10, 10));
+///    assert_eq!(counter.release(widget_id, left, make_point_i32(50, 10)), None);
+/// # }
+/// ```
+pub struct ClickCounter {
+    clock: Box<dyn Clock>,
+    interval: u64,
+    distance: i32,
+    pending: Vec<PendingPress>,
+    last_click: Option<LastClick>,
+}
+
+/// Implementation of the `ClickCounter`.
+impl ClickCounter {
+    /// Creates a new `ClickCounter` using the default click interval and distance.
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Creates a new `ClickCounter` that measures time using the specified `Clock`.  This is
+    /// mainly useful for testing, by supplying a `ManualClock`.
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            clock,
+            interval: DEFAULT_CLICK_INTERVAL,
+            distance: DEFAULT_CLICK_DISTANCE,
+            pending: Vec::new(),
+            last_click: None,
+        }
+    }
+
+    /// Sets the maximum number of milliseconds between clicks for them to count as a multiple
+    /// click.
+    pub fn set_interval(&mut self, interval: u64) {
+        self.interval = interval;
+    }
+
+    /// Retrieves the multiple click interval in milliseconds.
+    pub fn get_interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Sets the maximum number of pixels the mouse may move, either between a press and its
+    /// release, or between clicks, for them to count as part of the same click.
+    pub fn set_distance(&mut self, distance: i32) {
+        self.distance = distance.max(0);
+    }
+
+    /// Retrieves the click distance in pixels.
+    pub fn get_distance(&self) -> i32 {
+        self.distance
+    }
+
+    /// Records that `button` was pressed inside the `Widget` with ID `widget_id`, at `point`.
+    pub fn press(&mut self, widget_id: WidgetId, button: Button, point: Point) {
+        self.pending.retain(|press| press.button != button);
+        self.pending.push(PendingPress {
+            widget_id,
+            button,
+            point,
+        });
+    }
+
+    /// Records that `button` was released inside the `Widget` with ID `widget_id`, at `point`.
+    /// Returns the click count if the release completes a click, or `None` if the button was
+    /// pressed in a different `Widget`, or the mouse was dragged too far.
+    pub fn release(&mut self, widget_id: WidgetId, button: Button, point: Point) -> Option<u32> {
+        let index = self
+            .pending
+            .iter()
+            .position(|press| press.button == button)?;
+        let press = self.pending.remove(index);
+
+        if press.widget_id != widget_id || !self.is_near(&press.point, &point) {
+            self.last_click = None;
+            return None;
+        }
+
+        let now = self.clock.now();
+        let count = match &self.last_click {
+            Some(last)
+                if last.widget_id == widget_id
+                    && last.button == button
+                    && self.is_near(&last.point, &point)
+                    && now.saturating_duration_since(last.time).as_millis() as u64
+                        <= self.interval =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+
+        self.last_click = Some(LastClick {
+            widget_id,
+            button,
+            point,
+            time: now,
+            count,
+        });
+
+        Some(count)
+    }
+
+    /// Forgets any pending presses and the last click, so the next click starts a new series.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_click = None;
+    }
+
+    fn is_near(&self, a: &Point, b: &Point) -> bool {
+        (a.x - b.x).abs() <= self.distance && (a.y - b.y).abs() <= self.distance
+    }
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,92 @@
+// Clipboard
+// Copies and pastes text between widgets, and between the application and the system
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+
+/// This is the `Clipboard` trait, which holds the text that is cut or copied, and then pasted.
+/// Applications can implement it to use the clipboard of the system, through a clipboard crate,
+/// and set it with `set_clipboard`.  Until they do, a `LocalClipboard` is used, which only
+/// shares text within the application.
+pub trait Clipboard {
+    /// Retrieves the text on the clipboard, or `None` if there is no text on it.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replaces the contents of the clipboard with `text`.
+    fn set_text(&mut self, text: &str);
+}
+
+/// This is the `LocalClipboard`, which keeps the text that is copied in the application itself.
+/// It is used when there is no system clipboard, such as in tests that run without a display.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::clipboard::*;
+/// # fn main() {
+///    let mut clipboard = LocalClipboard::default();
+///
+///    assert_eq!(clipboard.get_text(), None);
+///
+///    clipboard.set_text("Copied");
+///    assert_eq!(clipboard.get_text(), Some("Copied".to_string()));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LocalClipboard {
+    text: Option<String>,
+}
+
+/// Implementation of the `Clipboard` for the `LocalClipboard`.
+impl Clipboard for LocalClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+}
+
+thread_local! {
+    static CLIPBOARD: RefCell<Box<dyn Clipboard>> =
+        RefCell::new(Box::new(LocalClipboard::default()));
+}
+
+/// Sets the `Clipboard` that `Widget`s cut, copy, and paste text with, replacing the
+/// `LocalClipboard` that is used by default.
+pub fn set_clipboard(clipboard: Box<dyn Clipboard>) {
+    CLIPBOARD.with(|current| *current.borrow_mut() = clipboard);
+}
+
+/// Retrieves the text on the current `Clipboard`, or `None` if there is no text on it.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::clipboard::*;
+/// # fn main() {
+///    set_clipboard_text("Hello, world!");
+///    assert_eq!(get_clipboard_text(), Some("Hello, world!".to_string()));
+///
+///    set_clipboard(Box::new(LocalClipboard::default()));
+///    assert_eq!(get_clipboard_text(), None);
+/// # }
+/// ```
+pub fn get_clipboard_text() -> Option<String> {
+    CLIPBOARD.with(|current| current.borrow_mut().get_text())
+}
+
+/// Replaces the contents of the current `Clipboard` with `text`.
+pub fn set_clipboard_text(text: &str) {
+    CLIPBOARD.with(|current| current.borrow_mut().set_text(text));
+}
@@ -0,0 +1,91 @@
+// Clock Sources
+// Monotonic time sources used by time-based widgets.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Implementable trait that provides the current time to time-based `Widget`s, such as the
+/// `TimerWidget`.  Times are `Instant`s, so they are monotonic: they never jump backwards or
+/// forwards when the system clock is adjusted.
+pub trait Clock {
+    /// Returns the current point in time.
+    fn now(&self) -> Instant;
+
+    /// Returns the number of milliseconds that have passed since `since`.  If `since` is in the
+    /// future, this returns 0.
+    fn elapsed_ms(&self, since: Instant) -> u64 {
+        self.now().saturating_duration_since(since).as_millis() as u64
+    }
+}
+
+/// This is the default `Clock`, which reads the monotonic system clock.
+#[derive(Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// This is a `Clock` that only moves forward when told to, which is useful for driving
+/// time-based `Widget`s in tests.  Clones of a `ManualClock` share the same time, so a clone can
+/// be handed to a `Widget` while the original is used to advance it.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::clock::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let start = clock.now();
+///
+///    clock.advance(1500);
+///    assert_eq!(clock.elapsed_ms(start), 1500);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ManualClock {
+    start: Instant,
+    offset: Rc<Cell<Duration>>,
+}
+
+/// Implementation of the `ManualClock`.
+impl ManualClock {
+    /// Creates a new `ManualClock`, starting at the current time.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            offset: Rc::new(Cell::new(Duration::from_millis(0))),
+        }
+    }
+
+    /// Moves the clock forward by the specified number of milliseconds.
+    pub fn advance(&self, ms: u64) {
+        self.offset.set(self.offset.get() + Duration::from_millis(ms));
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.offset.get()
+    }
+}
@@ -0,0 +1,271 @@
+// Debug Overlay
+// Draws the bounds, IDs, and names of widgets over the window, to debug layouts
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use piston_window::*;
+
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::font_cache::*;
+use crate::widget::shape::{stroke_border, BorderStyle};
+use crate::widget::text_cache::*;
+use crate::widget::text_widget::text_width;
+
+/// The key that turns the debug overlay on and off in debug builds, or prints the tree of
+/// `Widget`s when it is pressed with Shift.  See `Pushrod::set_debug_key`.
+pub const DEBUG_OVERLAY_KEY: Key = Key::F12;
+
+/// Number of frames that a `Widget` is highlighted for once it has been invalidated.
+pub const DEBUG_FLASH_FRAMES: u32 = 8;
+
+/// Size of the font that the IDs and names of `Widget`s are drawn in.
+const DEBUG_LABEL_FONT_SIZE: u32 = 10;
+
+/// Colors of the outlines of `Widget`s, picked by ID, so that neighbours are told apart.
+const DEBUG_OUTLINE_COLORS: [types::Color; 6] = [
+    [1.0, 0.0, 1.0, 1.0],
+    [0.0, 0.8, 1.0, 1.0],
+    [0.2, 0.9, 0.2, 1.0],
+    [1.0, 0.6, 0.0, 1.0],
+    [0.5, 0.3, 1.0, 1.0],
+    [1.0, 0.9, 0.0, 1.0],
+];
+
+/// The color that the `Widget` under the mouse is filled with.
+const DEBUG_HOVER_COLOR: types::Color = [1.0, 1.0, 0.0, 0.25];
+
+/// The color that `Widget`s that have just been invalidated are filled with, fading out.
+const DEBUG_FLASH_COLOR: types::Color = [1.0, 0.2, 0.2, 0.4];
+
+/// This is the `DebugOverlay`, which the `WidgetStore` draws over all of its `Widget`s when it
+/// is enabled, to show where each `Widget` is: an outline around the bounds of every visible
+/// `Widget`, labelled with its ID and name, a highlight over the `Widget` under the mouse, and a
+/// flash over each `Widget` that has been invalidated, so that partial redraws can be seen.  It
+/// is only drawn, so it never changes which `Widget` is under the mouse.  While it is enabled,
+/// the whole window is drawn on every frame; while it is disabled, it costs nothing.
+///
+/// It is turned on with `Pushrod::set_debug_overlay`, or with F12 in debug builds.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::debug_overlay::*;
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::test_harness::*;
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut harness = TestHarness::new(400, 300);
+///    let mut panel = BoxWidget::new();
+///
+///    panel.set_origin(20, 20);
+///    panel.set_size(100, 50);
+///    panel.set_color([1.0; 4]);
+///
+///    let panel_id = harness.add_widget("Panel", Box::new(panel));
+///
+///    harness.get_pushrod().set_debug_overlay(true);
+///    harness.mouse_move(50, 40);
+///
+///    let overlay_commands = harness.draw().get_commands_at(&make_point_i32(50, 40)).len();
+///
+///    assert_eq!(
+///        harness.get_widget_store().get_debug_overlay().get_hovered_widget(),
+///        panel_id
+///    );
+///
+///    harness.get_pushrod().set_debug_overlay(false);
+///    assert!(harness.draw().get_commands_at(&make_point_i32(50, 40)).len() < overlay_commands);
+/// # }
+/// ```
+pub struct DebugOverlay {
+    enabled: bool,
+    font: Option<SharedGlyphs>,
+    hovered_id: WidgetId,
+    flashes: HashMap<WidgetId, u32>,
+}
+
+/// Implementation of the `DebugOverlay`.
+impl DebugOverlay {
+    /// Creates a new, disabled `DebugOverlay`, without a font for its labels.
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            font: None,
+            hovered_id: WidgetId::INVALID,
+            flashes: HashMap::new(),
+        }
+    }
+
+    /// Turns the overlay on or off.  Use `WidgetStore::set_debug_overlay`, which also redraws
+    /// the window, to change this for a `WidgetStore`.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.flashes.clear();
+    }
+
+    /// Indicates whether the overlay is drawn.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets the font that the IDs and names of `Widget`s are drawn in.  Without a font, only the
+    /// outlines are drawn.
+    pub fn set_font(&mut self, font: SharedGlyphs) {
+        self.font = Some(font);
+    }
+
+    /// Indicates whether the overlay has a font to draw the IDs and names of `Widget`s in.
+    pub fn has_font(&self) -> bool {
+        self.font.is_some()
+    }
+
+    /// Sets the `Widget` that is under the mouse, which is highlighted.  The `Pushrod` run loop
+    /// sets this once it has handled each event.
+    pub fn set_hovered_widget(&mut self, widget_id: WidgetId) {
+        self.hovered_id = widget_id;
+    }
+
+    /// Retrieves the `Widget` that is highlighted as being under the mouse.
+    pub fn get_hovered_widget(&self) -> WidgetId {
+        self.hovered_id
+    }
+
+    /// Highlights a `Widget` for the next `DEBUG_FLASH_FRAMES` frames, as one that has been
+    /// invalidated.
+    pub fn flash(&mut self, widget_id: WidgetId) {
+        self.flashes.insert(widget_id, DEBUG_FLASH_FRAMES);
+    }
+
+    /// Indicates whether a `Widget` is highlighted as having been invalidated.
+    pub fn is_flashing(&self, widget_id: WidgetId) -> bool {
+        self.flashes.contains_key(&widget_id)
+    }
+
+    /// Indicates whether any `Widget` is highlighted as having been invalidated, so that the
+    /// highlight has to be drawn fading out on the next frame.
+    pub fn has_flashes(&self) -> bool {
+        !self.flashes.is_empty()
+    }
+
+    /// Draws the overlay for `widgets`, each an ID, a name, and the bounds of a `Widget` in
+    /// window coordinates, in the order in which they are drawn.  `scale_factor` is the number
+    /// of device pixels per point, at which the labels are rasterized.  Each flash fades out by
+    /// one frame.
+    pub fn draw(
+        &mut self,
+        widgets: &[(WidgetId, &str, Rect)],
+        scale_factor: f64,
+        c: Context,
+        g: &mut DrawTarget,
+    ) {
+        for (widget_id, _, bounds) in widgets {
+            let rect = [
+                f64::from(bounds.origin.x),
+                f64::from(bounds.origin.y),
+                f64::from(bounds.size.w),
+                f64::from(bounds.size.h),
+            ];
+
+            if let Some(frames) = self.flashes.get(widget_id) {
+                let mut color = DEBUG_FLASH_COLOR;
+
+                color[3] *= *frames as f32 / DEBUG_FLASH_FRAMES as f32;
+                rectangle(color, rect, c.transform, g);
+            }
+
+            let thickness = if *widget_id == self.hovered_id {
+                rectangle(DEBUG_HOVER_COLOR, rect, c.transform, g);
+                2
+            } else {
+                1
+            };
+
+            stroke_border(
+                get_outline_color(*widget_id),
+                rect,
+                &make_uniform_insets(thickness),
+                BorderStyle::Solid,
+                &c.draw_state,
+                c.transform,
+                g,
+            );
+        }
+
+        if let Some(font) = &self.font {
+            for (widget_id, name, bounds) in widgets {
+                draw_label(font, *widget_id, name, bounds, scale_factor, c, g);
+            }
+        }
+
+        for frames in self.flashes.values_mut() {
+            *frames -= 1;
+        }
+
+        self.flashes.retain(|_, frames| *frames > 0);
+    }
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Picks the color of the outline of a `Widget` from its ID.
+fn get_outline_color(widget_id: WidgetId) -> types::Color {
+    DEBUG_OUTLINE_COLORS[i32::from(widget_id).unsigned_abs() as usize % DEBUG_OUTLINE_COLORS.len()]
+}
+
+/// Draws the ID and name of a `Widget` in its top left corner, on a dark background, in the
+/// color of its outline.
+fn draw_label(
+    font: &SharedGlyphs,
+    widget_id: WidgetId,
+    name: &str,
+    bounds: &Rect,
+    scale_factor: f64,
+    c: Context,
+    g: &mut DrawTarget,
+) {
+    let label = if name.is_empty() {
+        format!("#{}", widget_id)
+    } else {
+        format!("#{} {}", widget_id, name)
+    };
+    let font_size = DEBUG_LABEL_FONT_SIZE as i32;
+    let width = text_width(&mut font.borrow_mut(), DEBUG_LABEL_FONT_SIZE, &label);
+    let (x, y) = (f64::from(bounds.origin.x), f64::from(bounds.origin.y));
+    let device_font_size = (f64::from(DEBUG_LABEL_FONT_SIZE) * scale_factor).round() as u32;
+
+    rectangle(
+        [0.0, 0.0, 0.0, 0.7],
+        [x, y, f64::from(width + 4), f64::from(font_size + 4)],
+        c.transform,
+        g,
+    );
+    draw_cached_text(
+        font,
+        &label,
+        device_font_size,
+        get_outline_color(widget_id),
+        &c.draw_state,
+        c.transform
+            .trans(x + 2.0, y + f64::from(font_size) + 1.0)
+            .scale(1.0 / scale_factor, 1.0 / scale_factor),
+        g,
+    );
+}
@@ -0,0 +1,290 @@
+// Draw Target
+// Draws into the window, or records what is drawn for tests that run without a window
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::point::{make_rect, Point, Rect, Size};
+
+/// A primitive that was drawn into a `DrawRecorder`.  Vertices are in points, from the top left
+/// of the window, and clip rectangles are in device pixels, as they are in the `DrawState`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    /// The whole window was cleared to a color.
+    Clear { color: types::Color },
+
+    /// The stencil buffer was cleared to a value.
+    ClearStencil { value: u8 },
+
+    /// Triangles were filled with a color, three vertices each.
+    Triangles {
+        color: types::Color,
+        clip: Option<[u32; 4]>,
+        vertices: Vec<[f64; 2]>,
+    },
+
+    /// Triangles were filled with a texture of the given size in pixels, tinted with a color, such
+    /// as an image, or a line of text.
+    TexturedTriangles {
+        color: types::Color,
+        clip: Option<[u32; 4]>,
+        texture_size: [u32; 2],
+        vertices: Vec<[f64; 2]>,
+    },
+}
+
+/// Implementation of the `DrawCommand`.
+impl DrawCommand {
+    /// Retrieves the color that the command was drawn in.
+    pub fn get_color(&self) -> types::Color {
+        match self {
+            DrawCommand::Clear { color }
+            | DrawCommand::Triangles { color, .. }
+            | DrawCommand::TexturedTriangles { color, .. } => *color,
+            DrawCommand::ClearStencil { .. } => [0.0; 4],
+        }
+    }
+
+    /// Retrieves the smallest rectangle, in whole points, that contains all of the vertices of
+    /// the command, or `None` if it has no vertices, as a clear has.
+    pub fn get_bounds(&self) -> Option<Rect> {
+        let vertices = match self {
+            DrawCommand::Triangles { vertices, .. }
+            | DrawCommand::TexturedTriangles { vertices, .. } => vertices,
+            _ => return None,
+        };
+        let first = vertices.first()?;
+        let (min, max) = vertices
+            .iter()
+            .fold((*first, *first), |(min, max), vertex| {
+                (
+                    [min[0].min(vertex[0]), min[1].min(vertex[1])],
+                    [max[0].max(vertex[0]), max[1].max(vertex[1])],
+                )
+            });
+        let left = snap(min[0]).floor() as i32;
+        let top = snap(min[1]).floor() as i32;
+
+        Some(make_rect(
+            left,
+            top,
+            snap(max[0]).ceil() as i32 - left,
+            snap(max[1]).ceil() as i32 - top,
+        ))
+    }
+}
+
+/// Rounds a coordinate that is within a hundredth of a point of a whole point, as vertices are
+/// drawn in single precision.
+fn snap(value: f64) -> f64 {
+    if (value - value.round()).abs() < 0.01 {
+        value.round()
+    } else {
+        value
+    }
+}
+
+/// This is the `DrawRecorder`, which stands in for the window in tests: everything that is drawn
+/// into it is kept as a list of `DrawCommand`s, rather than being drawn, so that tests can check
+/// what a `Widget` drew, and where, without an OpenGL context.  The `TestHarness` draws its
+/// `Widget`s into a `DrawRecorder`.
+///
+/// Example:
+/// ```
+/// # use piston_window::*;
+/// # use pushrod::core::draw_target::*;
+/// # use pushrod::core::point::*;
+/// # fn main() {
+///    let mut recorder = DrawRecorder::new(200, 100);
+///    let c = recorder.get_context();
+///
+///    rectangle(
+///        [1.0, 0.0, 0.0, 1.0],
+///        [10.0, 20.0, 30.0, 40.0],
+///        c.transform,
+///        &mut DrawTarget::Recorder(&mut recorder),
+///    );
+///
+///    assert_eq!(recorder.len(), 1);
+///    assert_eq!(recorder.get_commands()[0].get_color(), [1.0, 0.0, 0.0, 1.0]);
+///    assert_eq!(
+///        recorder.get_commands()[0].get_bounds(),
+///        Some(make_rect(10, 20, 30, 40))
+///    );
+///    assert_eq!(recorder.get_commands_at(&make_point_i32(15, 25)).len(), 1);
+///    assert!(recorder.get_commands_at(&make_point_i32(150, 25)).is_empty());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DrawRecorder {
+    size: Size,
+    commands: Vec<DrawCommand>,
+}
+
+/// Implementation of the `DrawRecorder`.
+impl DrawRecorder {
+    /// Creates a new, empty `DrawRecorder` that stands in for a window of `width` by `height`
+    /// points.
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            size: Size {
+                w: width,
+                h: height,
+            },
+            commands: Vec::new(),
+        }
+    }
+
+    /// Retrieves the size of the window that the recorder stands in for.
+    pub fn get_size(&self) -> &Size {
+        &self.size
+    }
+
+    /// Sets the size of the window that the recorder stands in for.
+    pub fn set_size(&mut self, width: i32, height: i32) {
+        self.size = Size {
+            w: width,
+            h: height,
+        };
+    }
+
+    /// Creates the `Context` to draw into the recorder with, whose coordinates are in points,
+    /// from the top left of the window.
+    pub fn get_context(&self) -> Context {
+        Context::new_abs(f64::from(self.size.w), f64::from(self.size.h))
+    }
+
+    /// Retrieves the commands that have been drawn, in the order that they were drawn.
+    pub fn get_commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Retrieves the commands whose bounds contain `point`, in the order that they were drawn.
+    pub fn get_commands_at(&self, point: &Point) -> Vec<&DrawCommand> {
+        self.commands
+            .iter()
+            .filter(|command| {
+                command
+                    .get_bounds()
+                    .is_some_and(|bounds| bounds.contains(point))
+            })
+            .collect()
+    }
+
+    /// Returns the number of commands that have been drawn.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Indicates whether nothing has been drawn.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Forgets all of the commands that have been drawn.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Converts a vertex from the normalized device coordinates of the `Graphics` back end into
+    /// points.
+    fn to_points(&self, vertex: &[f32; 2]) -> [f64; 2] {
+        [
+            (f64::from(vertex[0]) + 1.0) / 2.0 * f64::from(self.size.w),
+            (1.0 - f64::from(vertex[1])) / 2.0 * f64::from(self.size.h),
+        ]
+    }
+}
+
+/// This is the `DrawTarget`, which every `Widget` draws into: either the window, or a
+/// `DrawRecorder`.  It is a `Graphics` back end, so it is drawn into with the drawing functions
+/// of Piston, such as `rectangle`, `Image::draw`, and `Text::draw`, just as the `G2d` of the
+/// window is.
+pub enum DrawTarget<'a, 'b> {
+    /// Draws into the window.
+    Window(&'a mut G2d<'b>),
+
+    /// Records what is drawn, without drawing it.
+    Recorder(&'a mut DrawRecorder),
+}
+
+impl Graphics for DrawTarget<'_, '_> {
+    type Texture = G2dTexture;
+
+    fn clear_color(&mut self, color: types::Color) {
+        match self {
+            DrawTarget::Window(g) => g.clear_color(color),
+            DrawTarget::Recorder(recorder) => recorder.commands.push(DrawCommand::Clear { color }),
+        }
+    }
+
+    fn clear_stencil(&mut self, value: u8) {
+        match self {
+            DrawTarget::Window(g) => g.clear_stencil(value),
+            DrawTarget::Recorder(recorder) => {
+                recorder.commands.push(DrawCommand::ClearStencil { value })
+            }
+        }
+    }
+
+    fn tri_list<F>(&mut self, draw_state: &DrawState, color: &[f32; 4], mut f: F)
+    where
+        F: FnMut(&mut dyn FnMut(&[[f32; 2]])),
+    {
+        match self {
+            DrawTarget::Window(g) => g.tri_list(draw_state, color, f),
+            DrawTarget::Recorder(recorder) => {
+                let mut vertices = Vec::new();
+
+                f(&mut |chunk: &[[f32; 2]]| {
+                    vertices.extend(chunk.iter().map(|vertex| recorder.to_points(vertex)))
+                });
+
+                recorder.commands.push(DrawCommand::Triangles {
+                    color: *color,
+                    clip: draw_state.scissor,
+                    vertices,
+                });
+            }
+        }
+    }
+
+    fn tri_list_uv<F>(
+        &mut self,
+        draw_state: &DrawState,
+        color: &[f32; 4],
+        texture: &G2dTexture,
+        mut f: F,
+    ) where
+        F: FnMut(&mut dyn FnMut(&[[f32; 2]], &[[f32; 2]])),
+    {
+        match self {
+            DrawTarget::Window(g) => g.tri_list_uv(draw_state, color, texture, f),
+            DrawTarget::Recorder(recorder) => {
+                let mut vertices = Vec::new();
+
+                f(&mut |chunk: &[[f32; 2]], _: &[[f32; 2]]| {
+                    vertices.extend(chunk.iter().map(|vertex| recorder.to_points(vertex)))
+                });
+
+                recorder.commands.push(DrawCommand::TexturedTriangles {
+                    color: *color,
+                    clip: draw_state.scissor,
+                    texture_size: [texture.get_size().0, texture.get_size().1],
+                    vertices,
+                });
+            }
+        }
+    }
+}
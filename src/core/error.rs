@@ -0,0 +1,113 @@
+// Pushrod Error
+// Errors that Pushrod returns, or reports while the run loop is running
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+use crate::core::layout::LayoutError;
+use crate::core::widget_store::WidgetStoreError;
+use crate::widget::builder::BuildError;
+
+/// Errors that can be returned by fallible constructors and loaders, such as `Pushrod::try_new`
+/// and `font_cache::load_font`, or reported to `Pushrod::on_error` when something fails while
+/// the run loop is running.  Time is read from a monotonic `Clock`, which cannot fail, so there
+/// are no clock errors.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PushrodError {
+    /// The window could not be created, such as when no OpenGL context is available.
+    WindowCreation(String),
+
+    /// A resource, such as a font or an image, could not be found, read, or decoded.  `name` is
+    /// the name or path of the resource.
+    ResourceLoading { name: String, reason: String },
+
+    /// A setting, a layout, or a `Widget` makes no sense, or needs something that is not
+    /// there, such as a window for a headless `Pushrod`.
+    InvalidConfiguration(String),
+
+    /// Something could not be drawn while the run loop was running, such as a texture that could
+    /// not be created again once the window was resized.  The rest of the window is drawn.
+    Rendering(String),
+}
+
+impl fmt::Display for PushrodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PushrodError::WindowCreation(reason) => {
+                write!(f, "Failed to create the window: {}", reason)
+            }
+            PushrodError::ResourceLoading { name, reason } => {
+                write!(f, "Failed to load '{}': {}", name, reason)
+            }
+            PushrodError::InvalidConfiguration(reason) => write!(f, "{}", reason),
+            PushrodError::Rendering(reason) => write!(f, "Failed to draw: {}", reason),
+        }
+    }
+}
+
+impl Error for PushrodError {}
+
+impl From<BuildError> for PushrodError {
+    fn from(error: BuildError) -> Self {
+        PushrodError::InvalidConfiguration(error.to_string())
+    }
+}
+
+impl From<LayoutError> for PushrodError {
+    fn from(error: LayoutError) -> Self {
+        PushrodError::InvalidConfiguration(error.to_string())
+    }
+}
+
+impl From<WidgetStoreError> for PushrodError {
+    fn from(error: WidgetStoreError) -> Self {
+        PushrodError::InvalidConfiguration(error.to_string())
+    }
+}
+
+thread_local! {
+    static REPORTED_ERRORS: RefCell<Vec<PushrodError>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reports an error that happened while drawing or handling an event, where it cannot be
+/// returned, such as an image that could not be loaded in `Widget::draw`.  The error is handed
+/// to the `Pushrod::on_error` callback once the event that is being handled is done with, rather
+/// than taking the whole application down.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::error::*;
+/// # use pushrod::core::test_harness::*;
+/// # fn main() {
+///    let mut harness = TestHarness::new(400, 300);
+///
+///    report_error(PushrodError::Rendering(String::from("The texture was lost")));
+///    harness.advance(10);
+///
+///    assert_eq!(
+///        harness.take_errors(),
+///        vec![PushrodError::Rendering(String::from("The texture was lost"))]
+///    );
+/// # }
+/// ```
+pub fn report_error(error: PushrodError) {
+    REPORTED_ERRORS.with(|errors| errors.borrow_mut().push(error));
+}
+
+/// Returns, and clears, the errors that have been reported with `report_error`, from the oldest.
+pub(crate) fn take_reported_errors() -> Vec<PushrodError> {
+    REPORTED_ERRORS.with(|errors| std::mem::take(&mut *errors.borrow_mut()))
+}
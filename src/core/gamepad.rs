@@ -0,0 +1,396 @@
+// Gamepad Navigation
+// Moves the focus between widgets, and activates them, with a gamepad instead of a mouse
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Instant;
+
+use piston_window::*;
+
+use crate::core::clock::*;
+use crate::core::point::Rect;
+use crate::core::widget_id::*;
+
+/// The number of milliseconds that a direction is held before it starts to repeat.
+pub const GAMEPAD_REPEAT_DELAY_MS: u64 = 400;
+
+/// The number of milliseconds between the first repeats of a direction that is held.  Each
+/// repeat comes sooner than the one before, down to `GAMEPAD_MIN_REPEAT_MS`.
+pub const GAMEPAD_REPEAT_INTERVAL_MS: u64 = 150;
+
+/// The fewest milliseconds between the repeats of a direction that is held.
+pub const GAMEPAD_MIN_REPEAT_MS: u64 = 40;
+
+/// How much sooner each repeat of a direction that is held comes than the one before.
+const GAMEPAD_REPEAT_ACCELERATION: f64 = 0.8;
+
+/// The direction in which the focus is moved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Implementation of the `FocusDirection`.
+impl FocusDirection {
+    /// Returns the arrow key for this direction.
+    pub fn get_key(self) -> Key {
+        match self {
+            FocusDirection::Up => Key::Up,
+            FocusDirection::Down => Key::Down,
+            FocusDirection::Left => Key::Left,
+            FocusDirection::Right => Key::Right,
+        }
+    }
+
+    /// Indicates whether this direction is `Left` or `Right`.
+    pub fn is_horizontal(self) -> bool {
+        self == FocusDirection::Left || self == FocusDirection::Right
+    }
+}
+
+/// Finds the candidate that the focus moves to from `from` in `direction`, out of the
+/// `candidates`, each of which is the ID of a `Widget` with its bounds.  Only the candidates
+/// whose center is further in `direction` than the center of `from`, and whose far edge is
+/// further than that of `from`, are considered.  Of those, the nearest is chosen, by the gap
+/// between their facing edges, plus twice the gap between them across `direction`, so that a
+/// candidate in line with `from` wins over one that is slightly closer, but off to the side.
+/// Ties go to the candidate whose center is nearest to the line through the center of `from`.
+/// Returns `None` if there is no candidate in that direction.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::gamepad::*;
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_id::*;
+/// # fn main() {
+///    // A row of three buttons, with a wide button underneath the first two.
+///    let candidates = vec![
+///        (WidgetId::from(1), make_rect(10, 10, 80, 30)),
+///        (WidgetId::from(2), make_rect(110, 10, 80, 30)),
+///        (WidgetId::from(3), make_rect(210, 10, 80, 30)),
+///        (WidgetId::from(4), make_rect(10, 60, 180, 30)),
+///    ];
+///    let find = |from: usize, direction| {
+///        let bounds = candidates[from - 1].1.clone();
+///
+///        find_focus_in_direction(&bounds, &candidates, direction).map(i32::from)
+///    };
+///
+///    assert_eq!(find(1, FocusDirection::Right), Some(2));
+///    assert_eq!(find(2, FocusDirection::Right), Some(3));
+///    assert_eq!(find(3, FocusDirection::Right), None);
+///    assert_eq!(find(3, FocusDirection::Left), Some(2));
+///    assert_eq!(find(2, FocusDirection::Down), Some(4));
+///    assert_eq!(find(3, FocusDirection::Down), Some(4));
+///    assert_eq!(find(4, FocusDirection::Up), Some(1));
+///    assert_eq!(find(4, FocusDirection::Down), None);
+///
+///    // A button that is in line is chosen over one that is nearer, but off to the side.
+///    let candidates = vec![
+///        (WidgetId::from(1), make_rect(0, 0, 50, 50)),
+///        (WidgetId::from(2), make_rect(200, 0, 50, 50)),
+///        (WidgetId::from(3), make_rect(80, 120, 50, 50)),
+///    ];
+///
+///    assert_eq!(
+///        find_focus_in_direction(&candidates[0].1, &candidates, FocusDirection::Right),
+///        Some(WidgetId::from(2))
+///    );
+/// # }
+/// ```
+pub fn find_focus_in_direction(
+    from: &Rect,
+    candidates: &[(WidgetId, Rect)],
+    direction: FocusDirection,
+) -> Option<WidgetId> {
+    // Each rectangle is turned into its extent along the direction and across it, in the
+    // direction, so that every direction can be measured in the same way.
+    let extents = |rect: &Rect| {
+        let (left, top) = (f64::from(rect.origin.x), f64::from(rect.origin.y));
+        let (right, bottom) = (f64::from(rect.get_right()), f64::from(rect.get_bottom()));
+
+        match direction {
+            FocusDirection::Right => ((left, right), (top, bottom)),
+            FocusDirection::Left => ((-right, -left), (top, bottom)),
+            FocusDirection::Down => ((top, bottom), (left, right)),
+            FocusDirection::Up => ((-bottom, -top), (left, right)),
+        }
+    };
+    let ((from_start, from_end), (from_side_start, from_side_end)) = extents(from);
+    let from_center = (from_start + from_end) / 2.0;
+    let from_side_center = (from_side_start + from_side_end) / 2.0;
+
+    candidates
+        .iter()
+        .filter(|(_, rect)| rect != from && !rect.is_empty())
+        .filter_map(|(widget_id, rect)| {
+            let ((start, end), (side_start, side_end)) = extents(rect);
+
+            if (start + end) / 2.0 <= from_center || end <= from_end {
+                return None;
+            }
+
+            let gap = (start - from_end).max(0.0);
+            let side_gap = (side_start - from_side_end)
+                .max(from_side_start - side_end)
+                .max(0.0);
+            let side_offset = ((side_start + side_end) / 2.0 - from_side_center).abs();
+
+            Some((gap + 2.0 * side_gap, side_offset, *widget_id))
+        })
+        .min_by(|a, b| {
+            (a.0, a.1)
+                .partial_cmp(&(b.0, b.1))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(_, _, widget_id)| widget_id)
+}
+
+/// The buttons and axes of a gamepad that `Pushrod` navigates with.  Buttons and axes are
+/// numbered by the window backend; the defaults are the numbers of the SDL game controller
+/// layout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadMapping {
+    /// The button that activates the focused `Widget`, as `Enter` does.  Defaults to `A`.
+    pub activate: u8,
+
+    /// The button that goes back, as `Escape` does, closing popups and dialogs.  Defaults to
+    /// `B`.
+    pub back: u8,
+
+    /// The buttons of the directional pad, up, down, left, and right.
+    pub dpad: [u8; 4],
+
+    /// The horizontal and vertical axes of the stick that moves the focus.  Defaults to the
+    /// left stick.
+    pub stick: [u8; 2],
+
+    /// How far the stick has to be pushed, from 0.0 to 1.0, to move the focus.
+    pub dead_zone: f64,
+}
+
+impl Default for GamepadMapping {
+    fn default() -> Self {
+        Self {
+            activate: 0,
+            back: 1,
+            dpad: [11, 12, 13, 14],
+            stick: [0, 1],
+            dead_zone: 0.5,
+        }
+    }
+}
+
+/// What a gamepad asks `Pushrod` to do.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GamepadAction {
+    /// Moves the focus, or presses an arrow key in a `Widget` that uses it.
+    Move(FocusDirection),
+
+    /// Presses or releases the activate button.
+    Activate(ButtonState),
+
+    /// Presses or releases the back button.
+    Back(ButtonState),
+}
+
+/// This is the `GamepadNavigator`, which turns the buttons, hat, and stick of a gamepad into
+/// `GamepadAction`s.  A direction that is held, on the directional pad or the stick, repeats
+/// after `GAMEPAD_REPEAT_DELAY_MS`, faster and faster, so that long lists can be scrolled
+/// through quickly.  `Pushrod` uses a `GamepadNavigator` to move the focus.
+///
+/// Example:
+/// ```
+/// # use piston_window::*;
+/// # use pushrod::core::clock::*;
+/// # use pushrod::core::gamepad::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let mut navigator = GamepadNavigator::with_clock(Box::new(clock.clone()));
+///    let down = Some(GamepadAction::Move(FocusDirection::Down));
+///
+///    assert_eq!(
+///        navigator.button(0, ButtonState::Press),
+///        Some(GamepadAction::Activate(ButtonState::Press))
+///    );
+///
+///    // Pushing the stick down moves the focus once, and then repeats, faster and faster.
+///    assert_eq!(navigator.axis(1, 0.9), down);
+///    assert_eq!(navigator.axis(1, 0.95), None);
+///
+///    clock.advance(GAMEPAD_REPEAT_DELAY_MS - 1);
+///    assert_eq!(navigator.update(), None);
+///    clock.advance(1);
+///    assert_eq!(navigator.update(), down);
+///    clock.advance(GAMEPAD_REPEAT_INTERVAL_MS);
+///    assert_eq!(navigator.update(), down);
+///    clock.advance(120);
+///    assert_eq!(navigator.update(), down);
+///
+///    // Letting go of the stick stops the repeat.
+///    assert_eq!(navigator.axis(1, 0.1), None);
+///    clock.advance(1000);
+///    assert_eq!(navigator.update(), None);
+/// # }
+/// ```
+pub struct GamepadNavigator {
+    mapping: GamepadMapping,
+    stick: [f64; 2],
+    held: Option<FocusDirection>,
+    next_repeat: Instant,
+    repeat_interval: f64,
+    clock: Box<dyn Clock>,
+}
+
+/// Implementation of the `GamepadNavigator`.
+impl GamepadNavigator {
+    /// Creates a new `GamepadNavigator` with the default `GamepadMapping`, which times repeats
+    /// with the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Creates a new `GamepadNavigator` with the default `GamepadMapping`, which times repeats
+    /// with `clock`.
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            mapping: GamepadMapping::default(),
+            stick: [0.0, 0.0],
+            held: None,
+            next_repeat: clock.now(),
+            repeat_interval: GAMEPAD_REPEAT_INTERVAL_MS as f64,
+            clock,
+        }
+    }
+
+    /// Sets the buttons and axes that are used.
+    pub fn set_mapping(&mut self, mapping: GamepadMapping) {
+        self.mapping = mapping;
+    }
+
+    /// Retrieves the buttons and axes that are used.
+    pub fn get_mapping(&self) -> &GamepadMapping {
+        &self.mapping
+    }
+
+    /// Handles a press or release of the gamepad button `button`.
+    pub fn button(&mut self, button: u8, state: ButtonState) -> Option<GamepadAction> {
+        if button == self.mapping.activate {
+            return Some(GamepadAction::Activate(state));
+        }
+
+        if button == self.mapping.back {
+            return Some(GamepadAction::Back(state));
+        }
+
+        let directions = [
+            FocusDirection::Up,
+            FocusDirection::Down,
+            FocusDirection::Left,
+            FocusDirection::Right,
+        ];
+        let direction = self
+            .mapping
+            .dpad
+            .iter()
+            .position(|dpad_button| *dpad_button == button)
+            .map(|index| directions[index])?;
+
+        match state {
+            ButtonState::Press => self.hold(Some(direction)),
+            ButtonState::Release if self.held == Some(direction) => self.hold(None),
+            ButtonState::Release => None,
+        }
+    }
+
+    /// Handles the hat of the gamepad moving to `state`.  A diagonal is treated as its vertical
+    /// direction.
+    pub fn hat(&mut self, state: HatState) -> Option<GamepadAction> {
+        let direction = match state {
+            HatState::Centered => None,
+            HatState::Up | HatState::LeftUp | HatState::RightUp => Some(FocusDirection::Up),
+            HatState::Down | HatState::LeftDown | HatState::RightDown => Some(FocusDirection::Down),
+            HatState::Left => Some(FocusDirection::Left),
+            HatState::Right => Some(FocusDirection::Right),
+        };
+
+        self.hold(direction)
+    }
+
+    /// Handles the gamepad axis `axis` moving to `position`, from -1.0 to 1.0.  The stick
+    /// points in the direction of whichever of its axes is pushed furthest past the dead zone.
+    pub fn axis(&mut self, axis: u8, position: f64) -> Option<GamepadAction> {
+        match self.mapping.stick.iter().position(|stick| *stick == axis) {
+            Some(index) => self.stick[index] = position,
+            None => return None,
+        }
+
+        let [x, y] = self.stick;
+        let direction = if x.abs().max(y.abs()) < self.mapping.dead_zone {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x < 0.0 {
+                FocusDirection::Left
+            } else {
+                FocusDirection::Right
+            })
+        } else {
+            Some(if y < 0.0 {
+                FocusDirection::Up
+            } else {
+                FocusDirection::Down
+            })
+        };
+
+        if direction == self.held {
+            None
+        } else {
+            self.hold(direction)
+        }
+    }
+
+    /// Repeats the direction that is held, once it is time to.  Call this on every update.
+    pub fn update(&mut self) -> Option<GamepadAction> {
+        let direction = self.held?;
+        let now = self.clock.now();
+
+        if now < self.next_repeat {
+            return None;
+        }
+
+        self.next_repeat = now + std::time::Duration::from_millis(self.repeat_interval as u64);
+        self.repeat_interval =
+            (self.repeat_interval * GAMEPAD_REPEAT_ACCELERATION).max(GAMEPAD_MIN_REPEAT_MS as f64);
+
+        Some(GamepadAction::Move(direction))
+    }
+
+    /// Starts holding `direction`, moving in it once, or stops holding any direction.
+    fn hold(&mut self, direction: Option<FocusDirection>) -> Option<GamepadAction> {
+        self.held = direction;
+        self.next_repeat =
+            self.clock.now() + std::time::Duration::from_millis(GAMEPAD_REPEAT_DELAY_MS);
+        self.repeat_interval = GAMEPAD_REPEAT_INTERVAL_MS as f64;
+
+        direction.map(GamepadAction::Move)
+    }
+}
+
+impl Default for GamepadNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
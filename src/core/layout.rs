@@ -0,0 +1,1039 @@
+// Layout Loader
+// Builds a tree of Widgets from a TOML layout description.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use piston_window::*;
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::box_widget::*;
+use crate::widget::config::*;
+use crate::widget::button_widget::*;
+use crate::widget::checkbox_widget::*;
+use crate::widget::font_cache::*;
+use crate::widget::image_widget::*;
+use crate::widget::layout_widget::*;
+use crate::widget::list_widget::*;
+use crate::widget::progress_bar_widget::*;
+use crate::widget::radio_button_widget::*;
+use crate::widget::progress_widget::*;
+use crate::widget::push_button_widget::*;
+use crate::widget::scroll_container_widget::*;
+use crate::widget::slider_widget::*;
+use crate::widget::split_pane_widget::*;
+use crate::widget::text_input_widget::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::timer_widget::*;
+use crate::widget::toggle_button_widget::*;
+use crate::widget::widget::*;
+
+/// Font that is used by text `Widget`s when a layout does not specify one.
+pub const DEFAULT_LAYOUT_FONT: &str = "OpenSans-Regular.ttf";
+
+/// A function that creates a `Widget` from its `WidgetDescription`, given the current
+/// `PistonWindow`'s factory object.  Only the settings that are specific to the type of `Widget`
+/// need to be applied: the origin, size, color, padding, margin, tooltip, preferred size, and
+/// layout weight are applied by the `LayoutLoader` afterward.  If the description cannot be
+/// used, an error message is returned instead.
+pub type WidgetConstructor =
+    Box<dyn FnMut(&mut GfxFactory, &WidgetDescription) -> Result<Box<dyn Widget>, String>>;
+
+/// Errors that can be returned when loading a layout.  Line and column numbers start at 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutError {
+    /// The layout is not a valid TOML document, or a setting has the wrong type.
+    Parse {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+
+    /// No constructor is registered for the type of a `Widget`.
+    UnknownWidgetType {
+        widget_type: String,
+        line: usize,
+        column: usize,
+    },
+
+    /// The parent of a `Widget` is not defined earlier in the layout, and does not already exist.
+    UnknownParent {
+        parent: String,
+        line: usize,
+        column: usize,
+    },
+
+    /// A `Widget` with this name is defined earlier in the layout, or already exists.
+    DuplicateName {
+        name: String,
+        line: usize,
+        column: usize,
+    },
+
+    /// The constructor of a `Widget` could not use its description.
+    InvalidWidget {
+        name: String,
+        message: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::Parse {
+                message,
+                line,
+                column,
+            } => write!(f, "{} (line {}, column {})", message, line, column),
+            LayoutError::UnknownWidgetType {
+                widget_type,
+                line,
+                column,
+            } => write!(
+                f,
+                "Unknown widget type '{}' (line {}, column {})",
+                widget_type, line, column
+            ),
+            LayoutError::UnknownParent {
+                parent,
+                line,
+                column,
+            } => write!(
+                f,
+                "Unknown parent widget '{}' (line {}, column {})",
+                parent, line, column
+            ),
+            LayoutError::DuplicateName { name, line, column } => write!(
+                f,
+                "A widget named '{}' already exists (line {}, column {})",
+                name, line, column
+            ),
+            LayoutError::InvalidWidget {
+                name,
+                message,
+                line,
+                column,
+            } => write!(
+                f,
+                "Invalid widget '{}': {} (line {}, column {})",
+                name, message, line, column
+            ),
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+/// This is the `WidgetDescription`, which is the static configuration of one `Widget` in a
+/// layout.  Each `[[widget]]` table in a layout becomes one `WidgetDescription`.  Only `type`
+/// and `name` are required; any other setting that is left out keeps the `Widget`'s default.
+/// Colors are lists of four floating point numbers, and points and sizes are lists of two
+/// integers.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WidgetDescription {
+    /// The type name of the `Widget`, which selects the constructor that creates it.
+    #[serde(rename = "type")]
+    pub widget_type: String,
+
+    /// The name that the `Widget` is registered under, so that code can look it up.
+    pub name: String,
+
+    /// The name of the parent `Widget`, which must be defined earlier in the layout, or already
+    /// exist.  When left out, the `Widget` is added to the base widget.
+    pub parent: Option<String>,
+
+    /// The origin of the `Widget` as `[x, y]`, relative to its parent.
+    pub origin: Option<[i32; 2]>,
+
+    /// The size of the `Widget` as `[w, h]`.
+    pub size: Option<[i32; 2]>,
+
+    /// The background color of the `Widget`.
+    pub color: Option<types::Color>,
+
+    /// The padding of the `Widget` as `[top, right, bottom, left]`.
+    pub padding: Option<[i32; 4]>,
+
+    /// The margin of the `Widget` as `[top, right, bottom, left]`.
+    pub margin: Option<[i32; 4]>,
+
+    /// The text displayed by text `Widget`s and buttons, or edited by a `TextInputWidget`.
+    pub text: Option<String>,
+
+    /// The color of the text displayed by text `Widget`s and buttons.
+    pub text_color: Option<types::Color>,
+
+    /// The font used by text `Widget`s and buttons (the path to a TTF file, or the filename in
+    /// the `assets` directory).
+    pub font: Option<String>,
+
+    /// The font size used by text `Widget`s and buttons.  Defaults to the font size of the current
+    /// `Theme`.
+    pub font_size: Option<u32>,
+
+    /// The justification of text: `left`, `center`, or `right`.
+    pub justify: Option<String>,
+
+    /// The vertical alignment of text: `top`, `middle`, or `bottom`.
+    pub vertical_align: Option<String>,
+
+    /// The distance between the baselines of lines of text, as a multiple of the font size.
+    pub line_spacing: Option<f64>,
+
+    /// Whether lines of text that are too wide to fit are shortened, ending in `...`.
+    pub ellipsize: Option<bool>,
+
+    /// The color of the border of boxes and buttons.
+    pub border_color: Option<types::Color>,
+
+    /// The thickness of the border of boxes and buttons.
+    pub border_width: Option<u8>,
+
+    /// The image displayed by an `ImageWidget` (the path to an image file, or the filename in
+    /// the `assets` directory).
+    pub image: Option<String>,
+
+    /// How an `ImageWidget` fits its image into its bounds: `original`, `stretch`, or `fit`.
+    pub scaling: Option<String>,
+
+    /// Whether a `CheckboxWidget` is checked, or a `ToggleButtonWidget` is selected.
+    pub checked: Option<bool>,
+
+    /// The radio group of a `RadioButtonWidget`, or the exclusive group of a
+    /// `ToggleButtonWidget`.
+    pub group: Option<String>,
+
+    /// The progress shown by a `ProgressWidget` or `ProgressBarWidget`, from 0 to 100.
+    pub progress: Option<u16>,
+
+    /// The direction of `Widget`s such as a `ProgressBarWidget` or a `SplitPaneWidget`:
+    /// `horizontal` or `vertical`.
+    pub orientation: Option<String>,
+
+    /// The smallest value of a `SliderWidget`.
+    pub min: Option<f64>,
+
+    /// The largest value of a `SliderWidget`.
+    pub max: Option<f64>,
+
+    /// The step between the values of a `SliderWidget`.
+    pub step: Option<f64>,
+
+    /// The value of a `SliderWidget`, or the position of the divider of a `SplitPaneWidget`, as
+    /// a percentage.
+    pub value: Option<f64>,
+
+    /// The timeout of a `TimerWidget` in milliseconds.
+    pub timeout: Option<u64>,
+
+    /// The tooltip shown when the mouse rests over the `Widget`.
+    pub tooltip: Option<String>,
+
+    /// The number of pixels between the children of layout `Widget`s.
+    pub spacing: Option<i32>,
+
+    /// The preferred size of the `Widget` as `[w, h]`, which layout `Widget`s give it.
+    pub preferred_size: Option<[i32; 2]>,
+
+    /// The size of the content area of a `ScrollContainerWidget` as `[w, h]`.
+    pub content_size: Option<[i32; 2]>,
+
+    /// The items of a `ListWidget`.
+    pub items: Option<Vec<String>>,
+
+    /// The height of the rows of a `ListWidget`.
+    pub row_height: Option<i32>,
+
+    /// The text shown by a `TextInputWidget` when it has no text.
+    pub placeholder: Option<String>,
+
+    /// The layout weight of the `Widget`, which sets how much of the space left over in a layout
+    /// `Widget` it is stretched to fill.
+    pub weight: Option<u32>,
+
+    /// The line on which the `type` of the `Widget` is specified.
+    #[serde(skip)]
+    pub line: usize,
+
+    /// The column at which the `type` of the `Widget` is specified.
+    #[serde(skip)]
+    pub column: usize,
+
+    #[serde(skip)]
+    name_position: (usize, usize),
+
+    #[serde(skip)]
+    parent_position: (usize, usize),
+}
+
+/// Implementation of the `WidgetDescription`.
+impl WidgetDescription {
+    /// Retrieves the text justification of this description, or `default` if none is specified.
+    /// Returns an error message if the justification is not `left`, `center`, or `right`.
+    pub fn get_justify(&self, default: TextJustify) -> Result<TextJustify, String> {
+        match self.justify.as_deref() {
+            None => Ok(default),
+            Some("left") => Ok(TextJustify::Left),
+            Some("center") => Ok(TextJustify::Center),
+            Some("right") => Ok(TextJustify::Right),
+            Some(justify) => Err(format!("Unknown justification '{}'", justify)),
+        }
+    }
+
+    /// Retrieves the vertical alignment of text in this description, or `default` if none is
+    /// specified.  Returns an error message if the alignment is not `top`, `middle`, or `bottom`.
+    pub fn get_vertical_align(
+        &self,
+        default: TextVerticalAlign,
+    ) -> Result<TextVerticalAlign, String> {
+        match self.vertical_align.as_deref() {
+            None => Ok(default),
+            Some("top") => Ok(TextVerticalAlign::Top),
+            Some("middle") => Ok(TextVerticalAlign::Middle),
+            Some("bottom") => Ok(TextVerticalAlign::Bottom),
+            Some(align) => Err(format!("Unknown vertical alignment '{}'", align)),
+        }
+    }
+
+    /// Retrieves the image scaling of this description, or `default` if none is specified.
+    /// Returns an error message if the scaling is not `original`, `stretch`, or `fit`.
+    pub fn get_scaling(&self, default: ImageScaling) -> Result<ImageScaling, String> {
+        match self.scaling.as_deref() {
+            None => Ok(default),
+            Some("original") => Ok(ImageScaling::Original),
+            Some("stretch") => Ok(ImageScaling::Stretch),
+            Some("fit") => Ok(ImageScaling::Fit),
+            Some(scaling) => Err(format!("Unknown image scaling '{}'", scaling)),
+        }
+    }
+
+    /// Retrieves the orientation of this description, or `default` if none is specified.
+    /// Returns an error message if the orientation is not `horizontal` or `vertical`.
+    pub fn get_orientation(&self, default: Orientation) -> Result<Orientation, String> {
+        match self.orientation.as_deref() {
+            None => Ok(default),
+            Some("horizontal") => Ok(Orientation::Horizontal),
+            Some("vertical") => Ok(Orientation::Vertical),
+            Some(orientation) => Err(format!("Unknown orientation '{}'", orientation)),
+        }
+    }
+
+    fn get_font(&self) -> String {
+        self.font
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LAYOUT_FONT.to_string())
+    }
+
+    fn get_text(&self) -> String {
+        self.text.clone().unwrap_or_default()
+    }
+
+    fn get_font_size(&self) -> u32 {
+        self.font_size.unwrap_or(get_current_theme().font_size)
+    }
+
+    fn get_border_color(&self) -> types::Color {
+        self.border_color.unwrap_or([0.0, 0.0, 0.0, 1.0])
+    }
+
+    fn get_border_width(&self) -> u8 {
+        self.border_width.unwrap_or(1)
+    }
+
+    fn has_border(&self) -> bool {
+        self.border_color.is_some() || self.border_width.is_some()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawLayout {
+    #[serde(default)]
+    widget: Vec<WidgetDescription>,
+}
+
+/// The positions of the `Widget` settings that are checked after parsing.  The layout is parsed
+/// a second time into this, as positions are not available for fields of a `WidgetDescription`.
+#[derive(Deserialize)]
+struct RawPositions {
+    #[serde(default)]
+    widget: Vec<RawWidgetPositions>,
+}
+
+#[derive(Deserialize)]
+struct RawWidgetPositions {
+    #[serde(rename = "type")]
+    widget_type: Spanned<String>,
+    name: Spanned<String>,
+    parent: Option<Spanned<String>>,
+}
+
+/// Loads the font of a `Widget` description into the font cache, returning its name, or an error
+/// message if it cannot be loaded.  The constructors of `Widget`s that draw text panic when their
+/// font cannot be loaded, so the font is loaded first, and the constructor finds it in the cache.
+fn load_description_font(
+    factory: &mut GfxFactory,
+    description: &WidgetDescription,
+) -> Result<String, String> {
+    let font_name = description.get_font();
+
+    load_font(factory, &font_name).map_err(|error| error.to_string())?;
+    Ok(font_name)
+}
+
+/// Converts a byte offset in `source` to a line and column, both starting at 1.
+fn get_position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+
+    (line, column)
+}
+
+fn parse_error(source: &str, error: toml::de::Error) -> LayoutError {
+    let (line, column) = error
+        .line_col()
+        .map(|(line, column)| (line + 1, column + 1))
+        .unwrap_or_else(|| get_position(source, source.len()));
+
+    LayoutError::Parse {
+        message: error.to_string(),
+        line,
+        column,
+    }
+}
+
+/// Parses a TOML layout into the list of `WidgetDescription`s that it contains, in the order in
+/// which they appear.  Each `Widget` is a `[[widget]]` table.  Unknown settings, and settings
+/// of the wrong type, are reported as a `LayoutError::Parse` with their position.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::layout::*;
+/// # fn main() {
+///    let layout = r#"
+///    [[widget]]
+///    type = "BoxWidget"
+///    name = "Panel"
+///    origin = [20, 20]
+///    size = [200, 100]
+///    color = [1.0, 1.0, 1.0, 1.0]
+///
+///    [[widget]]
+///    type = "TimerWidget"
+///    name = "Timer"
+///    parent = "Panel"
+///    timeout = 500
+///    "#;
+///
+///    let descriptions = parse_layout(layout).unwrap();
+///
+///    assert_eq!(descriptions.len(), 2);
+///    assert_eq!(descriptions[0].size, Some([200, 100]));
+///    assert_eq!(descriptions[1].parent, Some(String::from("Panel")));
+///    assert_eq!(descriptions[1].timeout, Some(500));
+///    assert_eq!((descriptions[1].line, descriptions[1].column), (10, 11));
+///
+///    match parse_layout("[[widget]]\ntype = \"BoxWidget\"\nname = \"Box\"\nsize = 10\n") {
+///        Err(LayoutError::Parse { line, .. }) => assert_eq!(line, 4),
+///        _ => panic!("Expected a parse error"),
+///    }
+/// # }
+/// ```
+pub fn parse_layout(source: &str) -> Result<Vec<WidgetDescription>, LayoutError> {
+    let mut descriptions = toml::from_str::<RawLayout>(source)
+        .map_err(|error| parse_error(source, error))?
+        .widget;
+    let positions = toml::from_str::<RawPositions>(source)
+        .map_err(|error| parse_error(source, error))?
+        .widget;
+
+    for (description, position) in descriptions.iter_mut().zip(positions.iter()) {
+        let (line, column) = get_position(source, position.widget_type.start());
+
+        description.line = line;
+        description.column = column;
+        description.name_position = get_position(source, position.name.start());
+        description.parent_position = match &position.parent {
+            Some(parent) => get_position(source, parent.start()),
+            None => (line, column),
+        };
+    }
+
+    Ok(descriptions)
+}
+
+/// This is the `LayoutLoader`, which creates `Widget`s from a TOML layout, and adds them to a
+/// `WidgetStore` under their names.  Constructors for the standard `Widget`s are registered
+/// when the loader is created, each under the name of its `struct`: `CanvasWidget`,
+/// `BoxWidget`, `TextWidget`, `ImageWidget`, `ButtonWidget`, `PushButtonWidget`,
+/// `ToggleButtonWidget`, `CheckboxWidget`, `RadioButtonWidget`, `ProgressWidget`,
+/// `ProgressBarWidget`, `SliderWidget`, `ScrollContainerWidget`, `ListWidget`,
+/// `TextInputWidget`, `SplitPaneWidget`, and `TimerWidget`.  Applications can `register`
+/// constructors for their own `Widget`s.
+///
+/// A layout only describes the structure and static configuration of the `Widget`s.  Callbacks
+/// are attached in code afterward, by looking up the `Widget`s by name.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::layout::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut loader = LayoutLoader::new();
+///    let widget_store = WidgetStore::new();
+///    let layout = "[[widget]]\ntype = \"GaugeWidget\"\nname = \"Gauge\"\n";
+///    let descriptions = parse_layout(layout).unwrap();
+///
+///    assert_eq!(
+///        loader.validate(&descriptions, &widget_store),
+///        Err(LayoutError::UnknownWidgetType {
+///            widget_type: String::from("GaugeWidget"),
+///            line: 2,
+///            column: 8,
+///        })
+///    );
+///
+///    loader.register(
+///        "GaugeWidget",
+///        Box::new(|_factory, _description| Ok(Box::new(CanvasWidget::new()))),
+///    );
+///    assert_eq!(loader.validate(&descriptions, &widget_store), Ok(()));
+/// # }
+/// ```
+pub struct LayoutLoader {
+    constructors: HashMap<String, WidgetConstructor>,
+}
+
+/// Implementation of the `LayoutLoader`.
+impl LayoutLoader {
+    /// Creates a new `LayoutLoader`, with constructors registered for the standard `Widget`s.
+    pub fn new() -> Self {
+        let mut loader = Self {
+            constructors: HashMap::new(),
+        };
+
+        loader.register(
+            "CanvasWidget",
+            Box::new(|_factory, _description| Ok(Box::new(CanvasWidget::new()))),
+        );
+
+        loader.register(
+            "BoxWidget",
+            Box::new(|_factory, description| {
+                let mut widget = BoxWidget::new();
+
+                if description.has_border() {
+                    widget.set_border(
+                        description.get_border_color(),
+                        description.get_border_width(),
+                    );
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "TextWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = TextWidget::new(
+                    factory,
+                    font_name,
+                    description.get_text(),
+                    description.get_font_size(),
+                    description.get_justify(TextJustify::Left)?,
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                widget
+                    .set_vertical_align(description.get_vertical_align(TextVerticalAlign::Middle)?);
+
+                if let Some(line_spacing) = description.line_spacing {
+                    widget.set_line_spacing(line_spacing);
+                }
+
+                if let Some(ellipsize) = description.ellipsize {
+                    widget.set_ellipsize(ellipsize);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ImageWidget",
+            Box::new(|factory, description| match &description.image {
+                Some(image) => {
+                    let mut widget = ImageWidget::new(factory, image.clone());
+
+                    widget.set_scaling(description.get_scaling(ImageScaling::Stretch)?);
+                    Ok(Box::new(widget))
+                }
+                None => Err(String::from("An ImageWidget requires an image")),
+            }),
+        );
+
+        loader.register(
+            "ButtonWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = ButtonWidget::with_font_size(
+                    factory,
+                    font_name,
+                    description.get_text(),
+                    description.get_font_size(),
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if description.has_border() {
+                    widget.set_border(
+                        description.get_border_color(),
+                        description.get_border_width(),
+                    );
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "PushButtonWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = PushButtonWidget::new(
+                    factory,
+                    font_name,
+                    description.get_text(),
+                    description.get_font_size(),
+                    description.get_justify(TextJustify::Center)?,
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if description.has_border() {
+                    widget.set_border(
+                        description.get_border_color(),
+                        description.get_border_width(),
+                    );
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ToggleButtonWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = ToggleButtonWidget::new(
+                    factory,
+                    font_name,
+                    description.get_text(),
+                    description.get_font_size(),
+                    description.get_justify(TextJustify::Center)?,
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if description.has_border() {
+                    widget.set_border(
+                        description.get_border_color(),
+                        description.get_border_width(),
+                    );
+                }
+
+                if let Some(group) = &description.group {
+                    widget.set_exclusive_group(group);
+                }
+
+                if let Some(selected) = description.checked {
+                    widget.set_selected_silently(selected);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "CheckboxWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = CheckboxWidget::new(factory, font_name, description.get_text());
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if let Some(checked) = description.checked {
+                    widget.set_checked(checked);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "RadioButtonWidget",
+            Box::new(|factory, description| {
+                let group = match &description.group {
+                    Some(group) => group.clone(),
+                    None => return Err(String::from("A RadioButtonWidget requires a group")),
+                };
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = RadioButtonWidget::new(
+                    factory,
+                    font_name,
+                    description.get_text(),
+                    &group,
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ProgressBarWidget",
+            Box::new(|_factory, description| {
+                let mut widget = ProgressBarWidget::new();
+
+                if let Some(progress) = description.progress {
+                    widget.set_progress(f64::from(progress) / 100.0);
+                }
+
+                widget.set_orientation(description.get_orientation(Orientation::Horizontal)?);
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "SliderWidget",
+            Box::new(|_factory, description| {
+                let mut widget = SliderWidget::new();
+
+                widget.set_range(
+                    description.min.unwrap_or(0.0),
+                    description.max.unwrap_or(1.0),
+                    description.step.unwrap_or(0.0),
+                );
+
+                if let Some(value) = description.value {
+                    widget.set_value(value);
+                }
+
+                widget.set_orientation(description.get_orientation(Orientation::Horizontal)?);
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ProgressWidget",
+            Box::new(|_factory, description| {
+                let mut widget = ProgressWidget::new();
+
+                if let Some(progress) = description.progress {
+                    widget.set_progress(progress);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ScrollContainerWidget",
+            Box::new(|_factory, description| {
+                let mut widget = ScrollContainerWidget::new();
+
+                if let Some([w, h]) = description.content_size {
+                    widget.set_content_size(w, h);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "ListWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = ListWidget::new(
+                    factory,
+                    font_name,
+                    description.items.clone().unwrap_or_default(),
+                );
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if let Some(row_height) = description.row_height {
+                    widget.set_row_height(row_height);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "TextInputWidget",
+            Box::new(|factory, description| {
+                let font_name = load_description_font(factory, description)?;
+                let mut widget = TextInputWidget::new(factory, font_name);
+
+                widget.set_font_size(description.get_font_size());
+
+                if let Some(color) = description.text_color {
+                    widget.set_text_color(color);
+                }
+
+                if let Some(placeholder) = &description.placeholder {
+                    widget.set_placeholder(placeholder);
+                }
+
+                if let Some(text) = &description.text {
+                    widget.set_text(text);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "HorizontalLayoutWidget",
+            Box::new(|_factory, description| {
+                let mut widget = HorizontalLayoutWidget::new();
+
+                widget.set_spacing(description.spacing.unwrap_or(DEFAULT_LAYOUT_SPACING));
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "VerticalLayoutWidget",
+            Box::new(|_factory, description| {
+                let mut widget = VerticalLayoutWidget::new();
+
+                widget.set_spacing(description.spacing.unwrap_or(DEFAULT_LAYOUT_SPACING));
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "SplitPaneWidget",
+            Box::new(|_factory, description| {
+                let mut widget =
+                    SplitPaneWidget::new(description.get_orientation(Orientation::Horizontal)?);
+
+                if let Some(percent) = description.value {
+                    widget.set_split_position(Dimension::Percent(percent as f32));
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader.register(
+            "TimerWidget",
+            Box::new(|_factory, description| {
+                let mut widget = TimerWidget::new();
+
+                if let Some(timeout) = description.timeout {
+                    widget.set_timeout(timeout);
+                }
+
+                Ok(Box::new(widget))
+            }),
+        );
+
+        loader
+    }
+
+    /// Registers the constructor that creates `Widget`s of the type `type_name`.  Registering a
+    /// constructor for a type that is already registered replaces it.
+    pub fn register(&mut self, type_name: &str, constructor: WidgetConstructor) {
+        self.constructors
+            .insert(String::from(type_name), constructor);
+    }
+
+    /// Indicates whether or not a constructor is registered for the type `type_name`.
+    pub fn is_registered(&self, type_name: &str) -> bool {
+        self.constructors.contains_key(type_name)
+    }
+
+    /// Checks that every `Widget` in `descriptions` has a registered type, a name that is not
+    /// used by an earlier `Widget` or by a `Widget` in the `widget_store`, and a parent that is
+    /// either defined earlier or already exists.
+    pub fn validate(
+        &self,
+        descriptions: &[WidgetDescription],
+        widget_store: &WidgetStore,
+    ) -> Result<(), LayoutError> {
+        let mut names: Vec<&str> = Vec::new();
+
+        for description in descriptions {
+            if !self.is_registered(&description.widget_type) {
+                return Err(LayoutError::UnknownWidgetType {
+                    widget_type: description.widget_type.clone(),
+                    line: description.line,
+                    column: description.column,
+                });
+            }
+
+            if names.contains(&description.name.as_str())
+                || widget_store
+                    .get_widget_id_by_name(&description.name)
+                    .is_some()
+            {
+                return Err(LayoutError::DuplicateName {
+                    name: description.name.clone(),
+                    line: description.name_position.0,
+                    column: description.name_position.1,
+                });
+            }
+
+            if let Some(parent) = &description.parent {
+                if !names.contains(&parent.as_str())
+                    && widget_store.get_widget_id_by_name(parent).is_none()
+                {
+                    return Err(LayoutError::UnknownParent {
+                        parent: parent.clone(),
+                        line: description.parent_position.0,
+                        column: description.parent_position.1,
+                    });
+                }
+            }
+
+            names.push(&description.name);
+        }
+
+        Ok(())
+    }
+
+    /// Creates the `Widget`s described by the TOML layout in `source`, and adds them to the
+    /// `widget_store` under their names, in the order in which they appear.  Returns the IDs of
+    /// the new `Widget`s in the same order.  The whole layout is checked, and every `Widget` is
+    /// created, before any are added, so if an error is returned, the `widget_store` is left
+    /// unchanged.
+    pub fn load(
+        &mut self,
+        source: &str,
+        factory: &mut GfxFactory,
+        widget_store: &mut WidgetStore,
+    ) -> Result<Vec<WidgetId>, LayoutError> {
+        let descriptions = parse_layout(source)?;
+
+        self.validate(&descriptions, widget_store)?;
+
+        let mut widgets = Vec::new();
+
+        for description in &descriptions {
+            // Every type has a constructor, as the layout has been validated.
+            let constructor = self
+                .constructors
+                .get_mut(&description.widget_type)
+                .unwrap();
+            let mut widget =
+                constructor(factory, description).map_err(|message| LayoutError::InvalidWidget {
+                    name: description.name.clone(),
+                    message,
+                    line: description.line,
+                    column: description.column,
+                })?;
+
+            if let Some([x, y]) = description.origin {
+                widget.set_origin(x, y);
+            }
+
+            if let Some([w, h]) = description.size {
+                widget.set_size(w, h);
+            }
+
+            if let Some(color) = description.color {
+                widget.set_color(color);
+            }
+
+            if let Some([top, right, bottom, left]) = description.padding {
+                widget.set_padding(make_insets(top, right, bottom, left));
+            }
+
+            if let Some([top, right, bottom, left]) = description.margin {
+                widget.set_margin(make_insets(top, right, bottom, left));
+            }
+
+            if let Some(tooltip) = &description.tooltip {
+                widget.set_tooltip(tooltip);
+            }
+
+            if let Some([w, h]) = description.preferred_size {
+                widget.set_preferred_size(w, h);
+            }
+
+            if let Some(weight) = description.weight {
+                widget.set_layout_weight(weight);
+            }
+
+            widgets.push(widget);
+        }
+
+        let mut widget_ids = Vec::new();
+
+        for (description, widget) in descriptions.iter().zip(widgets) {
+            let parent_id = match &description.parent {
+                Some(parent) => widget_store
+                    .get_widget_id_by_name(parent)
+                    .unwrap_or(WidgetId::ROOT),
+                None => WidgetId::ROOT,
+            };
+
+            widget_ids.push(widget_store.add_widget_to_parent(
+                &description.name,
+                widget,
+                parent_id,
+            ));
+        }
+
+        Ok(widget_ids)
+    }
+}
+
+impl Default for LayoutLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -13,15 +13,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use crate::core::accessibility::*;
 use crate::core::callbacks::*;
+use crate::core::click_counter::*;
+use crate::core::clock::*;
+use crate::core::debug_overlay::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::core::gamepad::*;
+use crate::core::layout::*;
 use crate::core::point::*;
+use crate::core::screenshot;
+use crate::core::touch::*;
+use crate::core::widget_id::*;
 use crate::core::widget_store::*;
+use crate::event::event::*;
+use crate::widget::config::*;
+use crate::widget::dialog_widget::*;
+use crate::widget::font_cache::*;
+use crate::widget::theme::*;
+use crate::widget::timer_widget::*;
+use crate::widget::tooltip_widget::*;
 use crate::widget::widget::*;
 
+use glutin::MouseCursor;
+use ::image::RgbaImage;
 use piston_window::*;
 
 /// This structure is returned when instantiating a new Pushrod main object.
@@ -32,10 +53,71 @@ use piston_window::*;
 /// The objects contained within this structure are used by the `Pushrod` run loop, and
 /// are not intended to be modified except through methods in the `Pushrod` impl.
 pub struct Pushrod {
-    window: PistonWindow,
+    surface: Surface,
     pub widget_store: RefCell<WidgetStore>,
+    click_counter: ClickCounter,
+    tooltip_id: WidgetId,
+    tooltip_delay: u64,
+    tooltip_timer: TimerWidget,
+    tooltip_browsing: bool,
+    pending_resize: Option<crate::core::point::Size>,
+    cursor: CursorStyle,
+    last_widget_id: WidgetId,
+    previous_mouse_position: Point,
+    button_map: HashMap<WidgetId, HashSet<Button>>,
+    passed_press_ids: Vec<WidgetId>,
+    modifiers: KeyModifiers,
+    on_close_requested: Option<CloseRequestedCallback>,
+    on_focus_changed: Option<FocusChangedCallback>,
+    on_frame: Option<FrameCallback>,
+    on_raw_event: Option<RawEventCallback>,
+    on_error: Option<ErrorCallback>,
+    lazy_rendering: bool,
+    input_since_draw: bool,
+    frames_drawn: u64,
+    font_manager: Option<FontManager>,
+    touch_tracker: TouchTracker,
+    pan_position: [f64; 2],
+    gamepad: GamepadNavigator,
+    debug_key: Option<Key>,
+    scroll_unit: ScrollUnit,
 }
 
+/// What a `Pushrod` draws into: its window, or, when it is headless, a `DrawRecorder` that
+/// stands in for the window, along with the rates that the window would be run at.
+enum Surface {
+    Window(Box<PistonWindow>),
+    Headless {
+        recorder: DrawRecorder,
+        settings: EventSettings,
+    },
+}
+
+impl Surface {
+    /// Retrieves the window, which a headless `Pushrod` does not have.
+    fn get_window(&mut self) -> &mut PistonWindow {
+        match self {
+            Surface::Window(window) => window,
+            Surface::Headless { .. } => panic!("A headless Pushrod has no window"),
+        }
+    }
+}
+
+/// The event handler used by `Pushrod::run_without_handler`, which ignores every event.
+struct NoEventHandler {}
+
+impl PushrodCallbackEvents for NoEventHandler {
+    fn handle_event(&mut self, _event: CallbackEvent, _widget_store: &mut WidgetStore) {}
+}
+
+/// Default number of milliseconds that the mouse must rest over a `Widget` before its tooltip is
+/// shown.
+pub const DEFAULT_TOOLTIP_DELAY: u64 = 700;
+
+/// Number of milliseconds that the mouse must rest over a `Widget` before its tooltip is shown,
+/// after a tooltip has just been shown for another `Widget`.
+pub const TOOLTIP_BROWSE_DELAY: u64 = 100;
+
 /// Pushrod implementation.  Create a `Pushrod::new( OpenGL )` object to create a new
 /// main loop.  Only one of these should be set for the entire application runtime.
 ///
@@ -44,61 +126,1559 @@ pub struct Pushrod {
 impl Pushrod {
     /// Pushrod Object Constructor.  Takes in a single OpenGL configuration type.
     pub fn new(window: PistonWindow) -> Self {
+        let window_size = window.size();
+        let scale_factor = if window_size.width > 0.0 {
+            window.draw_size().width / window_size.width
+        } else {
+            1.0
+        };
+        let font_manager = FontManager::new(window.factory.clone());
+
+        Self::build(
+            Surface::Window(Box::new(window)),
+            window_size.width as i32,
+            window_size.height as i32,
+            scale_factor,
+            Some(font_manager),
+            &|| Box::new(SystemClock),
+        )
+    }
+
+    /// Creates the window described by `settings`, and a `Pushrod` that runs it, in the same way
+    /// as `new`.  Returns a `PushrodError::WindowCreation` if the window cannot be created, such
+    /// as when no OpenGL context is available, rather than panicking.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use piston_window::*;
+    /// # use pushrod::core::main::*;
+    /// # fn main() {
+    ///    let settings = WindowSettings::new("Pushrod", [640, 480]).exit_on_esc(true);
+    ///
+    ///    match Pushrod::try_new(&settings) {
+    ///        Ok(mut pushrod) => pushrod.run_without_handler(),
+    ///        Err(error) => eprintln!("{}", error),
+    ///    }
+    /// # }
+    /// ```
+    pub fn try_new(settings: &WindowSettings) -> Result<Self, PushrodError> {
+        let window: PistonWindow = settings
+            .build()
+            .map_err(|error| PushrodError::WindowCreation(error.to_string()))?;
+
+        Ok(Self::new(window))
+    }
+
+    /// Creates a `Pushrod` without a window, for tests, which draws into a `DrawRecorder` of
+    /// `width` by `height` points instead, and times clicks, tooltips, touches, and gamepad
+    /// repeats with `clock`.  Events are sent to it with `handle_window_event`, as the
+    /// `TestHarness` does, since there is no window to read them from: `run` returns at once.
+    /// Nothing can be loaded onto the graphics card without a window, so `get_factory`,
+    /// `load_layout`, `enable_tooltips`, and `get_font_manager` panic, `set_dialog_font` returns
+    /// an error, and `capture_screenshot` and `capture_widget` return `None`.
+    pub fn headless(width: i32, height: i32, clock: ManualClock) -> Self {
+        Self::build(
+            Surface::Headless {
+                recorder: DrawRecorder::new(width, height),
+                settings: EventSettings::new(),
+            },
+            width,
+            height,
+            1.0,
+            None,
+            &|| Box::new(clock.clone()),
+        )
+    }
+
+    fn build(
+        surface: Surface,
+        width: i32,
+        height: i32,
+        scale_factor: f64,
+        font_manager: Option<FontManager>,
+        new_clock: &dyn Fn() -> Box<dyn Clock>,
+    ) -> Self {
+        let mut widget_store = WidgetStore::new();
+
+        widget_store
+            .get_widget_for_id(WidgetId::ROOT)
+            .borrow_mut()
+            .set_size(width, height);
+        widget_store.set_scale_factor(scale_factor);
+
+        let mut tooltip_timer = TimerWidget::with_clock(new_clock());
+
+        tooltip_timer.set_one_shot(true);
+        tooltip_timer.set_enabled(false);
+
         Self {
-            window,
-            widget_store: RefCell::new(WidgetStore::new()),
+            surface,
+            widget_store: RefCell::new(widget_store),
+            click_counter: ClickCounter::with_clock(new_clock()),
+            tooltip_id: WidgetId::INVALID,
+            tooltip_delay: DEFAULT_TOOLTIP_DELAY,
+            tooltip_timer,
+            tooltip_browsing: false,
+            pending_resize: None,
+            cursor: CursorStyle::Default,
+            last_widget_id: WidgetId::INVALID,
+            previous_mouse_position: make_origin_point(),
+            button_map: HashMap::new(),
+            passed_press_ids: Vec::new(),
+            modifiers: KeyModifiers::default(),
+            on_close_requested: None,
+            on_focus_changed: None,
+            on_frame: None,
+            on_raw_event: None,
+            on_error: None,
+            lazy_rendering: false,
+            input_since_draw: false,
+            frames_drawn: 0,
+            font_manager,
+            touch_tracker: TouchTracker::with_clock(new_clock()),
+            pan_position: [0.0, 0.0],
+            gamepad: GamepadNavigator::with_clock(new_clock()),
+            debug_key: if cfg!(debug_assertions) {
+                Some(DEBUG_OVERLAY_KEY)
+            } else {
+                None
+            },
+            scroll_unit: ScrollUnit::Lines,
+        }
+    }
+
+    /// Retrieves the size of the window in points.  A headless `Pushrod` is the size of its
+    /// top level `Widget`.
+    fn get_window_size(&self) -> piston_window::Size {
+        match &self.surface {
+            Surface::Window(window) => window.size(),
+            Surface::Headless { .. } => {
+                let size = self
+                    .widget_store
+                    .borrow_mut()
+                    .get_widget_for_id(WidgetId::ROOT)
+                    .borrow_mut()
+                    .get_size();
+
+                piston_window::Size {
+                    width: f64::from(size.w),
+                    height: f64::from(size.h),
+                }
+            }
+        }
+    }
+
+    /// Indicates whether this `Pushrod` was created with `headless`, and has no window.
+    pub fn is_headless(&self) -> bool {
+        matches!(self.surface, Surface::Headless { .. })
+    }
+
+    /// Retrieves the `DrawRecorder` that a headless `Pushrod` draws into, holding what was drawn
+    /// in the last frame, or `None` if it has a window.
+    pub fn get_draw_recorder(&self) -> Option<&DrawRecorder> {
+        match &self.surface {
+            Surface::Window(_) => None,
+            Surface::Headless { recorder, .. } => Some(recorder),
+        }
+    }
+
+    /// Sets the `Theme` that `Widget`s use for any color or font size that they have not been
+    /// configured with.  The theme can be changed at any time, and all `Widget`s are redrawn in
+    /// the new theme on the next frame.  See `WidgetStore::set_theme`.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.widget_store.borrow_mut().set_theme(theme);
+    }
+
+    /// Retrieves the `Theme` that is used by this window.
+    pub fn get_theme(&self) -> Theme {
+        self.widget_store.borrow().get_theme()
+    }
+
+    /// Sets the maximum number of frames that are drawn per second.  This can be changed at any
+    /// time, including while the run loop is running.
+    pub fn set_max_fps(&mut self, fps: u64) {
+        match &mut self.surface {
+            Surface::Window(window) => window.set_max_fps(fps),
+            Surface::Headless { settings, .. } => settings.max_fps = fps,
         }
     }
 
+    /// Retrieves the maximum number of frames that are drawn per second.
+    pub fn get_max_fps(&self) -> u64 {
+        match &self.surface {
+            Surface::Window(window) => window.get_event_settings().max_fps,
+            Surface::Headless { settings, .. } => settings.max_fps,
+        }
+    }
+
+    /// Sets the number of updates per second, which is how often `Widget::update` is called, and
+    /// therefore how often timers and animations are ticked.  Setting this to `0` disables
+    /// updates.  This can be changed at any time, including while the run loop is running.
+    pub fn set_ups(&mut self, ups: u64) {
+        match &mut self.surface {
+            Surface::Window(window) => window.set_ups(ups),
+            Surface::Headless { settings, .. } => settings.ups = ups,
+        }
+    }
+
+    /// Retrieves the number of updates per second.
+    pub fn get_ups(&self) -> u64 {
+        match &self.surface {
+            Surface::Window(window) => window.get_event_settings().ups,
+            Surface::Headless { settings, .. } => settings.ups,
+        }
+    }
+
+    /// Turns lazy rendering on or off.  With lazy rendering on, a frame is only drawn when a
+    /// `Widget` needs to be redrawn (see `WidgetStore::needs_redraw`), or when an input event has
+    /// been received since the last frame was drawn.  Updates are still sent at the rate set by
+    /// `set_ups`, so timers and animations keep running, and draw their changes as they are made.
+    pub fn set_lazy_rendering(&mut self, lazy: bool) {
+        self.lazy_rendering = lazy;
+
+        // Frames that are skipped must not be swapped onto the screen, so buffers are swapped
+        // by `handle_draw` instead.
+        if let Surface::Window(window) = &mut self.surface {
+            window.set_swap_buffers(!lazy);
+        }
+        self.input_since_draw = true;
+    }
+
+    /// Indicates whether lazy rendering is turned on.
+    pub fn is_lazy_rendering(&self) -> bool {
+        self.lazy_rendering
+    }
+
+    /// Returns the number of frames that have been drawn since the window was created.  Frames
+    /// that were skipped by lazy rendering are not counted.
+    pub fn get_frames_drawn(&self) -> u64 {
+        self.frames_drawn
+    }
+
+    /// Sets the maximum number of milliseconds between clicks for them to be counted as a
+    /// double (or triple, and so on) click.  Defaults to `DEFAULT_CLICK_INTERVAL`.
+    pub fn set_click_interval(&mut self, interval: u64) {
+        self.click_counter.set_interval(interval);
+    }
+
+    /// Sets the maximum number of pixels the mouse may move between a button press and release,
+    /// or between clicks, for them to still be counted as a click.  Defaults to
+    /// `DEFAULT_CLICK_DISTANCE`.
+    pub fn set_click_distance(&mut self, distance: i32) {
+        self.click_counter.set_distance(distance);
+    }
+
+    /// Enables tooltips, which are shown when the mouse rests over a `Widget` that has tooltip
+    /// text set with `Widget::set_tooltip`.  Tooltips are drawn with the specified font (filename
+    /// in the `assets` directory) and font size.  A tooltip is hidden as soon as the mouse moves,
+    /// or a mouse button is pressed.  Once a tooltip has been shown, moving to another `Widget`
+    /// shows its tooltip after only a short delay.  Calling this again has no effect.
+    pub fn enable_tooltips(&mut self, font_name: &str, font_size: u32) {
+        if self.tooltip_id.is_valid() {
+            return;
+        }
+
+        let tooltip = TooltipWidget::new(
+            &mut self.surface.get_window().factory,
+            font_name.to_string(),
+            font_size,
+        );
+
+        self.tooltip_id = self.add_widget("_PushrodTooltip", Box::new(tooltip));
+    }
+
+    /// Sets the number of milliseconds that the mouse must rest over a `Widget` before its
+    /// tooltip is shown.  Defaults to `DEFAULT_TOOLTIP_DELAY`.
+    pub fn set_tooltip_delay(&mut self, delay: u64) {
+        self.tooltip_delay = delay;
+    }
+
+    /// Shows the tooltip of the `Widget` with the specified ID near the mouse pointer.  Returns
+    /// `false` if tooltips are not enabled, or the `Widget` has no tooltip.
+    fn show_tooltip(&mut self, widget_id: WidgetId, mouse_point: &Point) -> bool {
+        if !self.tooltip_id.is_valid() || !widget_id.is_valid() {
+            return false;
+        }
+
+        let window_size = self.get_window_size();
+        let widget_store = &mut self.widget_store.borrow_mut();
+        let text = match widget_store
+            .get_widget_for_id(widget_id)
+            .borrow_mut()
+            .get_tooltip()
+        {
+            Some(text) => text,
+            None => return false,
+        };
+        let window_size = crate::core::point::Size {
+            w: window_size.width as i32,
+            h: window_size.height as i32,
+        };
+
+        {
+            let tooltip = &mut widget_store.get_widget_for_id(self.tooltip_id).borrow_mut();
+
+            tooltip.set_tooltip(&text);
+
+            let origin = get_tooltip_origin(mouse_point, &tooltip.get_size(), &window_size);
+
+            tooltip.set_origin(origin.x, origin.y);
+            tooltip.set_visible(true);
+        }
+
+        widget_store.bring_to_front(self.tooltip_id);
+        true
+    }
+
+    /// Hides the tooltip, and stops waiting to show one.  Returns `true` if the tooltip was
+    /// visible.
+    fn hide_tooltip(&mut self) -> bool {
+        self.tooltip_timer.set_enabled(false);
+
+        if !self.tooltip_id.is_valid() {
+            return false;
+        }
+
+        let widget_store = &mut self.widget_store.borrow_mut();
+        let tooltip = &mut widget_store.get_widget_for_id(self.tooltip_id).borrow_mut();
+
+        if tooltip.is_visible() {
+            tooltip.set_visible(false);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Hides the tooltip when the mouse moves, and starts waiting to show the tooltip of the
+    /// `Widget` that the mouse is over.  If a tooltip was visible, the wait is shortened, so the
+    /// user can move between `Widget`s and read their tooltips without lingering over each one.
+    fn restart_tooltip(&mut self, widget_id: WidgetId) {
+        if !self.tooltip_id.is_valid() {
+            return;
+        }
+
+        if self.hide_tooltip() {
+            self.tooltip_browsing = true;
+        }
+
+        let has_tooltip = widget_id.is_valid()
+            && self
+                .widget_store
+                .borrow_mut()
+                .get_widget_for_id(widget_id)
+                .borrow_mut()
+                .get_tooltip()
+                .is_some();
+
+        if !has_tooltip {
+            self.tooltip_browsing = false;
+            return;
+        }
+
+        self.tooltip_timer.set_timeout(if self.tooltip_browsing {
+            TOOLTIP_BROWSE_DELAY
+        } else {
+            self.tooltip_delay
+        });
+        self.tooltip_timer.reset();
+    }
+
     /// Retrieves the window `GfxFactory` factory object for graphics textures.
     pub fn get_factory(&mut self) -> &mut GfxFactory {
-        &mut self.window.factory
+        &mut self.surface.get_window().factory
     }
 
     /// Helper method that adds a `Widget` to the `WidgetStore`, returning the ID of the `Widget`
     /// after it has been added.
-    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> i32 {
+    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> WidgetId {
         self.widget_store.borrow_mut().add_widget(name, widget)
     }
 
     /// Helper method that adds a `Widget` to the `WidgetStore`, specifying the `parent_id` as the
     /// parent of which to add this object to.  Returns the new ID of the `Widget` after it has
     /// been added.
-    pub fn add_widget_to_parent(&mut self, name: &str, widget: Box<dyn Widget>, parent_id: i32) -> i32 {
+    pub fn add_widget_to_parent(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+        parent_id: WidgetId,
+    ) -> WidgetId {
         self.widget_store
             .borrow_mut()
             .add_widget_to_parent(name, widget, parent_id)
     }
 
+    /// Helper method that creates the `Widget`s described by a TOML layout using the `loader`,
+    /// and adds them to the `WidgetStore` under their names.  Returns the IDs of the new
+    /// `Widget`s, in the order in which they appear in the layout.  See `LayoutLoader::load`.
+    pub fn load_layout(
+        &mut self,
+        loader: &mut LayoutLoader,
+        source: &str,
+    ) -> Result<Vec<WidgetId>, LayoutError> {
+        loader.load(
+            source,
+            &mut self.surface.get_window().factory,
+            &mut self.widget_store.borrow_mut(),
+        )
+    }
+
+    /// Helper method that adds a `Widget` with a unique name to the `WidgetStore`, returning
+    /// the ID of the `Widget`, or an error if the name is already in use.  See
+    /// `WidgetStore::add_widget_named`.
+    pub fn add_widget_named(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+    ) -> Result<WidgetId, WidgetStoreError> {
+        self.widget_store.borrow_mut().add_widget_named(name, widget)
+    }
+
+    /// Helper method that adds a `Widget` with a unique name to the parent specified by the
+    /// `parent_id`, returning the ID of the `Widget`, or an error if the name is already in use.
+    pub fn add_widget_named_to_parent(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+        parent_id: WidgetId,
+    ) -> Result<WidgetId, WidgetStoreError> {
+        self.widget_store
+            .borrow_mut()
+            .add_widget_named_to_parent(name, widget, parent_id)
+    }
+
     fn handle_draw(&mut self, event: &Event) {
         let widgets = &mut self.widget_store.borrow_mut();
 
-        self.window.draw_2d(event, |c, g| widgets.draw(0, c, g));
+        if self.lazy_rendering && !self.input_since_draw && !widgets.needs_redraw() {
+            return;
+        }
+
+        match &mut self.surface {
+            Surface::Window(window) => {
+                // Cached widgets are drawn into their textures first, as nothing else can be
+                // drawn while the window is being drawn to.
+                window.window.make_current();
+                widgets.update_render_cache(window);
+
+                window.draw_2d(event, |c, g| {
+                    widgets.draw(WidgetId::ROOT, c, &mut DrawTarget::Window(g))
+                });
+
+                if self.lazy_rendering {
+                    Window::swap_buffers(&mut **window);
+                }
+            }
+
+            // Without a window, there is nothing to cache into, so every widget is drawn, and
+            // the recorder only keeps the latest frame.
+            Surface::Headless { recorder, .. } => {
+                let root_size = widgets
+                    .get_widget_for_id(WidgetId::ROOT)
+                    .borrow_mut()
+                    .get_size();
+
+                recorder.clear();
+                recorder.set_size(root_size.w, root_size.h);
+                widgets.draw(
+                    WidgetId::ROOT,
+                    recorder.get_context(),
+                    &mut DrawTarget::Recorder(recorder),
+                );
+            }
+        }
+
+        self.input_since_draw = false;
+        self.frames_drawn += 1;
+    }
+
+    /// Draws every visible `Widget` into an offscreen framebuffer the size of the window, and
+    /// returns the result as an image, in device pixels.  All of the `Widget`s are drawn, whether
+    /// or not they have been invalidated, and what is on the screen is left as it was.  Returns
+    /// `None` if the framebuffer could not be created.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use piston_window::*;
+    /// # use pushrod::core::main::*;
+    /// # use pushrod::widget::box_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let window: PistonWindow = WindowSettings::new("Capture", [400, 300]).build().unwrap();
+    ///    let mut pushrod = Pushrod::new(window);
+    ///    let mut box_widget = BoxWidget::new();
+    ///
+    ///    box_widget.set_origin(50, 50);
+    ///    box_widget.set_size(100, 100);
+    ///    box_widget.set_color([0.0, 0.5, 1.0, 1.0]);
+    ///
+    ///    let box_id = pushrod.add_widget("Box", Box::new(box_widget));
+    ///
+    ///    pushrod.capture_screenshot().unwrap().save("window.png").unwrap();
+    ///    pushrod.capture_widget(box_id).unwrap().save("box.png").unwrap();
+    /// # }
+    /// ```
+    pub fn capture_screenshot(&mut self) -> Option<RgbaImage> {
+        self.capture_widget(WidgetId::ROOT)
+    }
+
+    /// Draws a `Widget`, and all of its visible children, into an offscreen framebuffer the size
+    /// of the `Widget`, and returns the result as an image, in device pixels.  `Widget`s that
+    /// are not children of the `Widget`, but overlap it, are left out.  This is useful for
+    /// images of single `Widget`s, for documentation.  Returns `None` if the `Widget` has no
+    /// size, or the framebuffer could not be created.
+    pub fn capture_widget(&mut self, widget_id: WidgetId) -> Option<RgbaImage> {
+        let widgets = &mut self.widget_store.borrow_mut();
+
+        if !widgets.is_widget_id_valid(widget_id) {
+            return None;
+        }
+
+        let size = widgets.get_widget_for_id(widget_id).borrow_mut().get_size();
+        let scale = widgets.get_scale_factor();
+        let pixel_size = [
+            (f64::from(size.w) * scale).ceil() as u32,
+            (f64::from(size.h) * scale).ceil() as u32,
+        ];
+
+        let window = match &mut self.surface {
+            Surface::Window(window) => window,
+            Surface::Headless { .. } => return None,
+        };
+
+        window.window.make_current();
+        widgets.update_render_cache(window);
+
+        screenshot::capture(window, &size, pixel_size, |c, g| {
+            widgets.draw_for_capture(widget_id, c, &mut DrawTarget::Window(g))
+        })
+    }
+
+    /// Removes a `Widget`, and all of its children, once the current event has been handled.  See
+    /// `WidgetStore::remove_widget`.
+    pub fn remove_widget(&mut self, widget_id: WidgetId) {
+        self.widget_store.borrow_mut().remove_widget(widget_id);
+    }
+
+    /// Removes the `Widget` with the specified name, and all of its children, once the current
+    /// event has been handled.
+    pub fn remove_widget_by_name(&mut self, name: &str) {
+        self.widget_store.borrow_mut().remove_widget_by_name(name);
+    }
+
+    /// Disables or enables a `Widget`.  See `WidgetStore::set_widget_disabled`.
+    pub fn set_widget_disabled(&mut self, widget_id: WidgetId, disabled: bool) {
+        self.widget_store
+            .borrow_mut()
+            .set_widget_disabled(widget_id, disabled);
+    }
+
+    /// Invalidates all `Widget`s, forcing the entire window to be redrawn in the next frame.  This
+    /// happens automatically when the window is resized.
+    pub fn invalidate_all(&mut self) {
+        self.widget_store.borrow_mut().invalidate_all_widgets();
+    }
+
+    /// Sets the z-order of a `Widget` among its siblings.  See `WidgetStore::set_z_order`.
+    pub fn set_z_order(&mut self, widget_id: WidgetId, z_order: i32) {
+        self.widget_store
+            .borrow_mut()
+            .set_z_order(widget_id, z_order);
+    }
+
+    /// Moves a `Widget` above all of its siblings, so it is drawn on top, and receives mouse
+    /// events first.
+    pub fn bring_to_front(&mut self, widget_id: WidgetId) {
+        self.widget_store.borrow_mut().bring_to_front(widget_id);
+    }
+
+    /// Moves a `Widget` below all of its siblings.
+    pub fn send_to_back(&mut self, widget_id: WidgetId) {
+        self.widget_store.borrow_mut().send_to_back(widget_id);
+    }
+
+    /// Gives keyboard focus to the `Widget` with the specified ID, or clears the focus if the ID is
+    /// `WidgetId::INVALID`.  Key events are only sent to the focused `Widget`.  Clicking on a
+    /// `Widget` also gives it focus.  See `WidgetStore::set_focused_widget`.
+    pub fn set_focused_widget(&mut self, widget_id: WidgetId) {
+        self.widget_store.borrow_mut().set_focused_widget(widget_id);
+    }
+
+    /// Moves the focus to the next focusable `Widget`.  This is what the Tab key does.  See
+    /// `WidgetStore::focus_next`.
+    pub fn focus_next(&mut self) {
+        self.widget_store.borrow_mut().focus_next();
+    }
+
+    /// Moves the focus to the previous focusable `Widget`.  This is what Shift+Tab does.  See
+    /// `WidgetStore::focus_previous`.
+    pub fn focus_previous(&mut self) {
+        self.widget_store.borrow_mut().focus_previous();
+    }
+
+    /// Shows `dialog` as a modal dialog above the rest of the window, which receives no events
+    /// until the dialog is closed, and returns the ID of the dialog.  See
+    /// `WidgetStore::show_modal`.
+    pub fn show_modal(&mut self, dialog: DialogWidget) -> WidgetId {
+        self.widget_store.borrow_mut().show_modal(dialog)
+    }
+
+    /// Closes the topmost modal dialog with `result`.  See `WidgetStore::close_modal`.
+    pub fn close_modal(&mut self, result: Option<&str>) {
+        self.widget_store.borrow_mut().close_modal(result);
+    }
+
+    /// Retrieves the `FontManager`, which loads fonts, hands them to `Widget`s, and measures text.
+    pub fn get_font_manager(&mut self) -> &mut FontManager {
+        self.font_manager
+            .as_mut()
+            .expect("A headless Pushrod has no font manager")
+    }
+
+    /// Loads the font named `font_name`, and draws ready-made dialogs, such as `MessageBox`es,
+    /// in it.  Returns an error if the font could not be loaded, or if this `Pushrod` is headless,
+    /// as fonts cannot be loaded without a window.  See `WidgetStore::set_dialog_font`.
+    pub fn set_dialog_font(&mut self, font_name: &str) -> Result<(), PushrodError> {
+        let window = match &mut self.surface {
+            Surface::Window(window) => window,
+            Surface::Headless { .. } => {
+                return Err(PushrodError::InvalidConfiguration(String::from(
+                    "A headless Pushrod cannot load fonts",
+                )))
+            }
+        };
+        let font = load_font(&mut window.factory, font_name)?;
+
+        self.widget_store.borrow_mut().set_dialog_font(font);
+        Ok(())
+    }
+
+    /// Turns the debug overlay on or off: an outline around every `Widget`, labelled with its ID
+    /// and name, a highlight over the `Widget` under the mouse, and a flash over each `Widget`
+    /// that is redrawn.  See `DebugOverlay`.  The labels are drawn in the default font, which a
+    /// headless `Pushrod` cannot load, so it only draws the outlines.
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        let mut widget_store = self.widget_store.borrow_mut();
+
+        if enabled && !widget_store.get_debug_overlay().has_font() {
+            if let Surface::Window(window) = &mut self.surface {
+                match load_font(&mut window.factory, DEFAULT_FONT_NAME) {
+                    Ok(font) => widget_store.get_debug_overlay().set_font(font),
+                    Err(error) => report_error(error),
+                }
+            }
+        }
+
+        widget_store.set_debug_overlay(enabled);
+    }
+
+    /// Indicates whether the debug overlay is drawn.
+    pub fn is_debug_overlay_enabled(&self) -> bool {
+        self.widget_store.borrow().is_debug_overlay_enabled()
+    }
+
+    /// Sets the unit that the window reports scroll distances in.  The window backend reports the
+    /// notches of a mouse wheel and the precise scrolls of a touchpad alike, so they cannot be
+    /// told apart: scrolls are taken to be in `ScrollUnit::Lines` by default, and an application
+    /// that is used with a touchpad, whose scrolls are reported in pixels, can set
+    /// `ScrollUnit::Pixels` instead.  Distances keep their fractions either way.
+    pub fn set_scroll_unit(&mut self, unit: ScrollUnit) {
+        self.scroll_unit = unit;
+    }
+
+    /// Retrieves the unit that the window reports scroll distances in.
+    pub fn get_scroll_unit(&self) -> ScrollUnit {
+        self.scroll_unit
+    }
+
+    /// Sets the key that turns the debug overlay on and off, or prints the tree of `Widget`s
+    /// with `print_widget_tree` when it is pressed with Shift.  The key is not passed on to the
+    /// `Widget`s.  Defaults to `DEBUG_OVERLAY_KEY` in debug builds, and to `None`, for no key, in
+    /// release builds.
+    pub fn set_debug_key(&mut self, key: Option<Key>) {
+        self.debug_key = key;
+    }
+
+    /// Writes the tree of `Widget`s to standard error, with the ID, name, origin, and size of
+    /// each.  See `WidgetStore::get_widget_tree`.
+    pub fn print_widget_tree(&mut self) {
+        eprint!("{}", self.widget_store.borrow_mut().get_widget_tree());
+    }
+
+    /// Describes the visible `Widget`s to assistive tools, as a tree of `AccessibleNode`s.  See
+    /// `WidgetStore::get_accessibility_tree`.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use piston_window::Key;
+    /// # use pushrod::core::accessibility::*;
+    /// # use pushrod::core::test_harness::*;
+    /// # use pushrod::event::event::*;
+    /// # use pushrod::widget::slider_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut harness = TestHarness::new(400, 300);
+    ///    let mut slider = SliderWidget::new();
+    ///    let values = Rc::new(RefCell::new(Vec::new()));
+    ///    let values_clone = values.clone();
+    ///
+    ///    slider.set_origin(20, 20);
+    ///    slider.set_size(200, 24);
+    ///    slider.set_range(0.0, 10.0, 1.0);
+    ///    slider.set_accessible_name("Volume");
+    ///
+    ///    let slider_id = harness.add_widget("Volume", Box::new(slider));
+    ///
+    ///    harness.get_pushrod().set_accessibility_events(true);
+    ///    harness.get_widget_store().subscribe(
+    ///        MASK_EVENT_ACCESSIBILITY,
+    ///        Box::new(move |event, _| {
+    ///            if let PushrodEvent::AccessibilityChanged { change, .. } = event {
+    ///                values_clone.borrow_mut().push(change.clone());
+    ///            }
+    ///        }),
+    ///    );
+    ///
+    ///    harness.get_pushrod().set_focused_widget(slider_id);
+    ///    harness.type_key(Key::Right);
+    ///    harness.draw();
+    ///
+    ///    assert_eq!(
+    ///        *values.borrow(),
+    ///        vec![
+    ///            AccessibleChange::Focused(true),
+    ///            AccessibleChange::Value(Some(String::from("1"))),
+    ///        ]
+    ///    );
+    ///
+    ///    let tree = harness.get_pushrod().get_accessibility_tree();
+    ///    let toml = toml::to_string(&tree).unwrap();
+    ///
+    ///    assert!(toml.contains("role = \"Slider\""));
+    ///    assert!(toml.contains("name = \"Volume\""));
+    ///    assert!(toml.contains("value = \"1\""));
+    /// # }
+    /// ```
+    pub fn get_accessibility_tree(&mut self) -> AccessibleNode {
+        self.widget_store.borrow_mut().get_accessibility_tree()
+    }
+
+    /// Turns on or off posting a `PushrodEvent::AccessibilityChanged` event whenever the
+    /// accessible state of a `Widget` changes.  See `WidgetStore::set_accessibility_events`.
+    pub fn set_accessibility_events(&mut self, enabled: bool) {
+        self.widget_store
+            .borrow_mut()
+            .set_accessibility_events(enabled);
+    }
+
+    /// Handles a press or release of the debug key, returning whether the event was one.
+    fn handle_debug_key(&mut self, event: &Event) -> bool {
+        let args = match event.button_args() {
+            Some(args) => args,
+            None => return false,
+        };
+
+        match self.debug_key {
+            Some(key) if args.button == Button::Keyboard(key) => (),
+            _ => return false,
+        }
+
+        if args.state == ButtonState::Press {
+            if self.modifiers.shift {
+                self.print_widget_tree();
+            } else {
+                let enabled = !self.is_debug_overlay_enabled();
+
+                self.set_debug_overlay(enabled);
+            }
+        }
+
+        true
+    }
+
+    /// Sets the callback that is called when the window is asked to close, either with its
+    /// close button, or with `request_close`.  If the callback returns `false`, the close is
+    /// cancelled and the window keeps running, so the application can first ask to save changes.
+    pub fn on_close_requested(&mut self, callback: CloseRequestedCallback) {
+        self.on_close_requested = Some(callback);
+    }
+
+    /// Sets the callback that is called when the window gains (`true`) or loses (`false`) focus,
+    /// before the `WindowFocused` event is sent, so the application can pause and resume work.
+    ///
+    /// The window backend does not report when the window is minimized or restored, so there is
+    /// no callback for it.
+    pub fn on_focus_changed(&mut self, callback: FocusChangedCallback) {
+        self.on_focus_changed = Some(callback);
+    }
+
+    /// Sets the callback that is called once for every frame, after the events posted to the
+    /// event bus have been delivered, and before the frame is drawn.  This is the place for work
+    /// that has to happen once per frame, such as moving an animation along.  With lazy
+    /// rendering, the callback is still called on frames that are skipped, so that it can
+    /// invalidate the `Widget`s that it changes.
+    pub fn on_frame(&mut self, callback: FrameCallback) {
+        self.on_frame = Some(callback);
+    }
+
+    /// Sets the callback that is called with every event from the window, before `Pushrod`
+    /// handles it, so that the application can react to events that `Pushrod` does not handle
+    /// itself, such as file drops or controller input.
+    pub fn on_raw_event(&mut self, callback: RawEventCallback) {
+        self.on_raw_event = Some(callback);
+    }
+
+    /// Sets the callback that is called with each error that is reported while the run loop is
+    /// running, such as an image that cannot be loaded when it is first drawn, or a cached
+    /// texture that cannot be created again once the window has been resized.  These errors are
+    /// reported with `error::report_error`, and handed to the callback once the event that was
+    /// being handled is done with, so one failure does not take the whole application down.
+    /// Without a callback, errors are written to standard error.
+    pub fn on_error(&mut self, callback: ErrorCallback) {
+        self.on_error = Some(callback);
+    }
+
+    /// Asks for the window to be closed, going through the same confirmation as the close
+    /// button.  See `WidgetStore::request_close`.
+    pub fn request_close(&mut self) {
+        self.widget_store.borrow_mut().request_close();
+    }
+
+    /// Hands each error that has been reported since the last event to the error callback, or
+    /// writes it to standard error if there is no callback.
+    fn dispatch_errors(&mut self) {
+        for error in take_reported_errors() {
+            match &mut self.on_error {
+                Some(callback) => callback(&error, &mut self.widget_store.borrow_mut()),
+                None => eprintln!("{}", error),
+            }
+        }
+    }
+
+    /// Calls the close requested callback, returning whether or not the window may close.
+    fn confirm_close(&mut self) -> bool {
+        match &mut self.on_close_requested {
+            Some(callback) => callback(&mut self.widget_store.borrow_mut()),
+            None => true,
+        }
+    }
+
+    /// Releases the mouse when the window loses focus: the hovered widget is exited, widgets
+    /// with a button pressed receive the release, and any drag or mouse capture is ended.
+    fn release_mouse(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        last_widget_id: &mut WidgetId,
+        button_map: &mut HashMap<WidgetId, HashSet<Button>>,
+        mouse_point: &Point,
+    ) {
+        if self.widget_store.borrow().is_dragging() {
+            self.widget_store.borrow_mut().cancel_drag();
+            self.dispatch_pending_events(event_handler);
+        }
+
+        for (widget_id, button_set) in button_map.drain() {
+            for button in button_set {
+                let local_point = self
+                    .widget_store
+                    .borrow_mut()
+                    .get_widget_local_point(widget_id, mouse_point.clone());
+
+                self.handle_event(
+                    widget_id,
+                    event_handler,
+                    CallbackEvent::MouseButtonUpOutside {
+                        widget_id,
+                        button,
+                        point: local_point,
+                    },
+                );
+            }
+        }
+
+        self.click_counter.reset();
+        self.widget_store.borrow_mut().release_capture();
+        self.passed_press_ids.clear();
+
+        if last_widget_id.is_valid() {
+            self.handle_event(
+                *last_widget_id,
+                event_handler,
+                CallbackEvent::MouseExited {
+                    widget_id: *last_widget_id,
+                },
+            );
+            *last_widget_id = WidgetId::INVALID;
+        }
+
+        self.hide_tooltip();
+        self.tooltip_browsing = false;
+    }
+
+    /// Sets the distance, in points, that a touch can move from where it started and still be a
+    /// tap.  A touch that is dragged further pans the scrollable `Widget` under it, if there is
+    /// one, and the `Widget` that it started on receives the release of the mouse button outside
+    /// of itself, so that a button that is touched while scrolling is not clicked.
+    pub fn set_touch_slop(&mut self, slop: f64) {
+        self.touch_tracker.set_slop(slop);
+    }
+
+    /// Retrieves the distance that a touch can move and still be a tap.  Defaults to
+    /// `DEFAULT_TOUCH_SLOP`.
+    pub fn get_touch_slop(&self) -> f64 {
+        self.touch_tracker.get_slop()
+    }
+
+    /// Sets the buttons and axes of a gamepad that move the focus and activate `Widget`s.
+    /// Gamepads are only reported by window backends that support them, such as SDL2.
+    pub fn set_gamepad_mapping(&mut self, mapping: GamepadMapping) {
+        self.gamepad.set_mapping(mapping);
+    }
+
+    /// Retrieves the buttons and axes of a gamepad that are used.  Defaults to the SDL game
+    /// controller layout: see `GamepadMapping`.
+    pub fn get_gamepad_mapping(&self) -> &GamepadMapping {
+        self.gamepad.get_mapping()
+    }
+
+    /// Reads a gamepad button, hat, or axis event, returning what it asks to do, if anything.
+    fn read_gamepad_event(&mut self, event: &Event) -> Option<GamepadAction> {
+        match event {
+            Event::Input(Input::Button(ButtonArgs {
+                state,
+                button: Button::Controller(ControllerButton { button, .. }),
+                ..
+            })) => self.gamepad.button(*button, *state),
+
+            Event::Input(Input::Button(ButtonArgs {
+                state,
+                button: Button::Hat(ControllerHat { state: hat, .. }),
+                ..
+            })) => match state {
+                ButtonState::Press => self.gamepad.hat(*hat),
+                ButtonState::Release => self.gamepad.hat(HatState::Centered),
+            },
+
+            Event::Input(Input::Move(Motion::ControllerAxis(args))) => {
+                self.gamepad.axis(args.axis, args.position)
+            }
+
+            Event::Loop(Loop::Update(_)) => self.gamepad.update(),
+
+            _ => None,
+        }
+    }
+
+    /// Carries out a gamepad action, showing the focus indicator.  A move presses and releases
+    /// the arrow key in the focused `Widget` if it handles that key, and otherwise moves the
+    /// focus to the nearest `Widget` in that direction.  Activating presses `Enter`, and going
+    /// back presses `Escape`, which closes the topmost popup if nothing else handles it.
+    fn handle_gamepad(
+        &mut self,
+        action: GamepadAction,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        self.widget_store
+            .borrow_mut()
+            .set_focus_indicator_visible(true);
+
+        match action {
+            GamepadAction::Move(direction) => {
+                let key = direction.get_key();
+                let focused_widget_id = self.widget_store.borrow().get_focused_widget();
+                let consumes_key = focused_widget_id.is_valid()
+                    && self
+                        .widget_store
+                        .borrow_mut()
+                        .get_widget_for_id(focused_widget_id)
+                        .borrow_mut()
+                        .consumes_arrow_key(key);
+
+                if consumes_key {
+                    self.send_gamepad_key(key, ButtonState::Press, event_handler);
+                    self.send_gamepad_key(key, ButtonState::Release, event_handler);
+                } else {
+                    self.widget_store
+                        .borrow_mut()
+                        .focus_in_direction(direction);
+                    self.dispatch_pending_events(event_handler);
+                }
+            }
+
+            GamepadAction::Activate(state) => {
+                self.send_gamepad_key(Key::Return, state, event_handler);
+            }
+
+            GamepadAction::Back(ButtonState::Press) => {
+                let popups = self.widget_store.borrow().get_open_popups();
+
+                self.send_gamepad_key(Key::Escape, ButtonState::Press, event_handler);
+
+                if let Some(popup_id) = popups.last() {
+                    if self.widget_store.borrow().get_open_popups() == popups {
+                        self.widget_store.borrow_mut().close_popup(*popup_id);
+                        self.dispatch_pending_events(event_handler);
+                    }
+                }
+            }
+
+            GamepadAction::Back(state) => {
+                self.send_gamepad_key(Key::Escape, state, event_handler);
+            }
+        }
+    }
+
+    /// Presses or releases `key`, by handling the same event as the keyboard would generate.
+    fn send_gamepad_key(
+        &mut self,
+        key: Key,
+        state: ButtonState,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        let event = Event::Input(Input::Button(ButtonArgs {
+            state,
+            button: Button::Keyboard(key),
+            scancode: None,
+        }));
+
+        self.handle_window_event(&event, event_handler);
+    }
+
+    /// Delivers a touch event.  Touches that start on a `Widget` that handles multi-touch are
+    /// sent to it as touch events.  Otherwise, one touch at a time is delivered as the left
+    /// mouse button, until it is dragged past the slop over a scrollable `Widget`, which it then
+    /// pans, and flings when it is lifted.  See `Widget::set_multi_touch`.
+    fn handle_touch(&mut self, args: &TouchArgs, event_handler: &mut dyn PushrodCallbackEvents) {
+        let window_size = self.get_window_size();
+        let position = [args.x * window_size.width, args.y * window_size.height];
+        let point = make_point_f64(position[0], position[1]);
+        let touch_id = args.id;
+
+        if args.touch == Touch::Start {
+            let widget_id = self
+                .widget_store
+                .borrow_mut()
+                .get_widget_id_for_point(point.clone());
+            let multi_touch = widget_id.is_valid()
+                && self
+                    .widget_store
+                    .borrow_mut()
+                    .get_widget_for_id(widget_id)
+                    .borrow_mut()
+                    .is_multi_touch();
+
+            if multi_touch {
+                self.touch_tracker
+                    .start_touch(touch_id, widget_id, TouchMode::MultiTouch, position);
+                self.send_touch_event(widget_id, touch_id, &point, args.touch, event_handler);
+            } else if !self.touch_tracker.has_mouse_touch() {
+                self.touch_tracker
+                    .start_touch(touch_id, widget_id, TouchMode::Mouse, position);
+                self.send_touch_as_mouse(position, Some(ButtonState::Press), event_handler);
+            }
+
+            return;
+        }
+
+        // Only a move changes the speed of a touch, so that it can be flung when it is lifted.
+        let delta = if args.touch == Touch::Move {
+            self.touch_tracker.move_touch(touch_id, position)
+        } else {
+            Some([0.0, 0.0])
+        };
+        let touch = match args.touch {
+            Touch::Move => self.touch_tracker.get_touch(touch_id).cloned(),
+            _ => self.touch_tracker.end_touch(touch_id),
+        };
+        let (delta, touch) = match (delta, touch) {
+            (Some(delta), Some(touch)) => (delta, touch),
+            _ => return,
+        };
+
+        match (touch.mode, args.touch) {
+            (TouchMode::MultiTouch, _) => {
+                self.send_touch_event(touch.widget_id, touch_id, &point, args.touch, event_handler);
+            }
+
+            (TouchMode::Mouse, Touch::Move) => {
+                let pan_id = if self.touch_tracker.is_past_slop(touch_id) {
+                    self.get_pan_target(touch.widget_id)
+                } else {
+                    WidgetId::INVALID
+                };
+
+                if !pan_id.is_valid() {
+                    self.send_touch_as_mouse(position, None, event_handler);
+                } else {
+                    // The drag becomes a pan, so the press is released without a click.
+                    self.cancel_mouse(event_handler);
+                    self.touch_tracker
+                        .set_touch_mode(touch_id, TouchMode::Panning(pan_id));
+                    self.begin_pan(pan_id);
+                    self.pan_widget(
+                        pan_id,
+                        [position[0] - touch.start[0], position[1] - touch.start[1]],
+                    );
+                }
+            }
+
+            (TouchMode::Mouse, Touch::Cancel) => self.cancel_mouse(event_handler),
+
+            (TouchMode::Mouse, _) => {
+                self.send_touch_as_mouse(position, Some(ButtonState::Release), event_handler);
+            }
+
+            (TouchMode::Panning(pan_id), Touch::Move) => {
+                self.pan_widget(pan_id, delta);
+            }
+
+            (TouchMode::Panning(pan_id), Touch::End) => {
+                self.touch_tracker.start_fling(pan_id, touch.velocity);
+            }
+
+            (TouchMode::Panning(_), _) => (),
+        }
+    }
+
+    /// Sends the touch `touch_id` of the `Widget` with the ID `widget_id`, which handles
+    /// multi-touch, to it, at `point` in the window.
+    fn send_touch_event(
+        &mut self,
+        widget_id: WidgetId,
+        touch_id: i64,
+        point: &Point,
+        touch: Touch,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        let point = self
+            .widget_store
+            .borrow_mut()
+            .get_widget_local_point(widget_id, point.clone());
+        let event = match touch {
+            Touch::Start => CallbackEvent::TouchStarted {
+                widget_id,
+                touch_id,
+                point,
+            },
+            Touch::Move => CallbackEvent::TouchMoved {
+                widget_id,
+                touch_id,
+                point,
+            },
+            Touch::End => CallbackEvent::TouchEnded {
+                widget_id,
+                touch_id,
+                point,
+            },
+            Touch::Cancel => CallbackEvent::TouchCancelled {
+                widget_id,
+                touch_id,
+            },
+        };
+
+        self.handle_event(widget_id, event_handler, event);
+    }
+
+    /// Moves the mouse to `position`, and then presses or releases the left mouse button, by
+    /// handling the same events as the mouse would generate.
+    fn send_touch_as_mouse(
+        &mut self,
+        position: [f64; 2],
+        state: Option<ButtonState>,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        let cursor = Event::Input(Input::Move(Motion::MouseCursor(position[0], position[1])));
+
+        self.handle_window_event(&cursor, event_handler);
+
+        if let Some(state) = state {
+            let button = Event::Input(Input::Button(ButtonArgs {
+                state,
+                button: Button::Mouse(MouseButton::Left),
+                scancode: None,
+            }));
+
+            self.handle_window_event(&button, event_handler);
+        }
+    }
+
+    /// Releases the mouse without a click, as `release_mouse` does when the window loses focus.
+    fn cancel_mouse(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        let mut last_widget_id = self.last_widget_id;
+        let mut button_map = std::mem::take(&mut self.button_map);
+        let mouse_point = self.previous_mouse_position.clone();
+
+        self.release_mouse(
+            event_handler,
+            &mut last_widget_id,
+            &mut button_map,
+            &mouse_point,
+        );
+        self.last_widget_id = last_widget_id;
+        self.button_map = button_map;
+    }
+
+    /// Cancels all of the touches, and stops any fling, when the window loses focus.  Touches
+    /// that are delivered as the mouse are released with the mouse.
+    fn cancel_touches(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        for (touch_id, touch) in self.touch_tracker.cancel_touches() {
+            if touch.mode == TouchMode::MultiTouch {
+                self.handle_event(
+                    touch.widget_id,
+                    event_handler,
+                    CallbackEvent::TouchCancelled {
+                        widget_id: touch.widget_id,
+                        touch_id,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns the ID of the `Widget` that a touch dragged across the `Widget` with the ID
+    /// `widget_id` pans: the first of the `Widget` and its parents that is scrollable, or
+    /// `WidgetId::INVALID` if none of them is.
+    fn get_pan_target(&mut self, widget_id: WidgetId) -> WidgetId {
+        let mut current_id = widget_id;
+
+        while current_id > WidgetId::ROOT {
+            if self
+                .widget_store
+                .borrow_mut()
+                .get_widget_for_id(current_id)
+                .borrow_mut()
+                .is_scrollable()
+            {
+                return current_id;
+            }
+
+            current_id = self.widget_store.borrow_mut().get_parent_of(current_id);
+        }
+
+        WidgetId::INVALID
+    }
+
+    /// Starts panning the `Widget` with the ID `widget_id` from where it is scrolled to.
+    fn begin_pan(&mut self, widget_id: WidgetId) {
+        let offset = self
+            .widget_store
+            .borrow_mut()
+            .get_widget_for_id(widget_id)
+            .borrow_mut()
+            .get_scroll_offset();
+
+        self.pan_position = [f64::from(offset.x), f64::from(offset.y)];
+    }
+
+    /// Drags the content of the `Widget` with the ID `widget_id` by `delta`, keeping track of
+    /// the fractions of a point that it moves between events.  Returns whether the content
+    /// could move along either axis that it was dragged along, rather than being stopped by its
+    /// edges.
+    fn pan_widget(&mut self, widget_id: WidgetId, delta: [f64; 2]) -> bool {
+        if !self.widget_store.borrow().is_widget_id_valid(widget_id) {
+            return false;
+        }
+
+        let mut widget_store = self.widget_store.borrow_mut();
+        let mut widget = widget_store.get_widget_for_id(widget_id).borrow_mut();
+        let target = [
+            self.pan_position[0] - delta[0],
+            self.pan_position[1] - delta[1],
+        ];
+
+        widget.scroll_to(target[0].round() as i32, target[1].round() as i32);
+
+        let offset = widget.get_scroll_offset();
+        let offset = [f64::from(offset.x), f64::from(offset.y)];
+        let mut moved = false;
+
+        for axis in 0..2 {
+            // Content that reaches its edge stays there, rather than keeping the overshoot.
+            if offset[axis] == target[axis].round() {
+                self.pan_position[axis] = target[axis];
+                moved |= delta[axis] != 0.0;
+            } else {
+                self.pan_position[axis] = offset[axis];
+            }
+        }
+
+        moved
+    }
+
+    /// Releases the mouse capture, if a `Widget` has captured the mouse.  See
+    /// `WidgetStore::set_mouse_capture`.
+    pub fn release_capture(&mut self) {
+        self.widget_store.borrow_mut().release_capture();
+    }
+
+    /// Starts dragging `payload` from the `Widget` with the ID `source_id`, optionally drawing a
+    /// `ghost` `Widget` under the mouse pointer.  See `WidgetStore::begin_drag`.
+    pub fn begin_drag(
+        &mut self,
+        source_id: WidgetId,
+        payload: Box<dyn Any>,
+        ghost: Option<Box<dyn Widget>>,
+    ) -> bool {
+        self.widget_store
+            .borrow_mut()
+            .begin_drag(source_id, payload, ghost)
+    }
+
+    /// Dispatches events that have been generated by the `WidgetStore`, such as focus changes,
+    /// until no more are pending.
+    fn dispatch_pending_events(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        loop {
+            let pending_events = self.widget_store.borrow_mut().take_pending_events();
+
+            if pending_events.is_empty() {
+                break;
+            }
+
+            for (widget_id, event) in pending_events {
+                self.handle_event(widget_id, event_handler, event);
+            }
+        }
+    }
+
+    /// Returns the number of device pixels per point of the window: 2.0 on a typical high density
+    /// display, and 1.0 otherwise.  `Widget`s are positioned and sized in points, and mouse
+    /// positions are reported in points, so applications rarely need this.  It is updated before
+    /// every frame, and `CallbackEvent::ScaleFactorChanged` is sent when it changes.
+    pub fn get_scale_factor(&self) -> f64 {
+        self.widget_store.borrow().get_scale_factor()
+    }
+
+    /// Updates the scale factor of the window from the ratio between its size in device pixels and
+    /// in points.  If it has changed, every `Widget` is told about it, the `ScaleFactorChanged`
+    /// event is sent to the application and then to every `Widget`, and all `Widget`s are
+    /// invalidated.
+    fn update_scale_factor(
+        &mut self,
+        args: &RenderArgs,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        if args.width <= 0.0 {
+            return;
+        }
+
+        let scale_factor = f64::from(args.draw_width) / args.width;
+
+        if !self.widget_store.borrow_mut().set_scale_factor(scale_factor) {
+            return;
+        }
+
+        let event = CallbackEvent::ScaleFactorChanged { scale_factor };
+
+        event_handler.handle_event(event.clone(), &mut self.widget_store.borrow_mut());
+
+        let injected_events = self.widget_store.borrow_mut().broadcast_event(event);
+
+        for injected_event in injected_events {
+            event_handler.handle_event(injected_event, &mut self.widget_store.borrow_mut());
+        }
+
+        self.widget_store.borrow_mut().invalidate_all_widgets();
+    }
+
+    /// Dispatches the last `WindowResized` event that was received, if any.  The base widget is
+    /// resized to the window, the event is sent to the application and then to every `Widget`,
+    /// and all `Widget`s are invalidated, as the whole window needs to be redrawn.
+    fn dispatch_pending_resize(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        let size = match self.pending_resize.take() {
+            Some(size) => size,
+            None => return,
+        };
+        let event = CallbackEvent::WindowResized { size: size.clone() };
+
+        self.widget_store
+            .borrow_mut()
+            .get_widget_for_id(WidgetId::ROOT)
+            .borrow_mut()
+            .set_size(size.w, size.h);
+
+        event_handler.handle_event(event.clone(), &mut self.widget_store.borrow_mut());
+
+        let injected_events = self.widget_store.borrow_mut().broadcast_event(event);
+
+        for injected_event in injected_events {
+            event_handler.handle_event(injected_event, &mut self.widget_store.borrow_mut());
+        }
+
+        self.widget_store.borrow_mut().invalidate_all_widgets();
+    }
+
+    /// Shows the mouse cursor of the `Widget` with the specified ID, or the default cursor if the
+    /// ID is `WidgetId::INVALID`, or the `Widget` is hidden or disabled.  The window's cursor is
+    /// only changed if it is different.
+    fn update_cursor(&mut self, widget_id: WidgetId) {
+        let widget_store = &mut self.widget_store.borrow_mut();
+        let cursor = if !widget_id.is_valid()
+            || !widget_store.is_widget_visible(widget_id)
+            || !widget_store.is_widget_enabled(widget_id)
+        {
+            CursorStyle::Default
+        } else {
+            widget_store
+                .get_widget_for_id(widget_id)
+                .borrow_mut()
+                .get_cursor()
+        };
+
+        if cursor == self.cursor {
+            return;
+        }
+
+        self.cursor = cursor;
+        let window = match &mut self.surface {
+            Surface::Window(window) => window,
+            Surface::Headless { .. } => return,
+        };
+
+        window.window.window.set_cursor(match cursor {
+            CursorStyle::Default => MouseCursor::Default,
+            CursorStyle::Arrow => MouseCursor::Arrow,
+            CursorStyle::Text => MouseCursor::Text,
+            CursorStyle::Hand => MouseCursor::Hand,
+            CursorStyle::Crosshair => MouseCursor::Crosshair,
+            CursorStyle::Move => MouseCursor::Move,
+            CursorStyle::Wait => MouseCursor::Wait,
+            CursorStyle::NotAllowed => MouseCursor::NotAllowed,
+            CursorStyle::ResizeHorizontal => MouseCursor::EwResize,
+            CursorStyle::ResizeVertical => MouseCursor::NsResize,
+        });
     }
 
     fn handle_event(
         &mut self,
-        widget_id: i32,
+        widget_id: WidgetId,
         event_handler: &mut PushrodCallbackEvents,
         event: CallbackEvent,
     ) {
-        if widget_id == -1 {
+        if !widget_id.is_valid() {
             return;
         }
 
-        let injectable_event = self
+        let injectable_event = self
+            .widget_store
+            .borrow_mut()
+            .handle_event(widget_id, event.clone());
+
+        event_handler.handle_event(event.clone(), &mut self.widget_store.borrow_mut());
+        self.handle_injected_event(event_handler, injectable_event);
+    }
+
+    /// Sends the event that a `Widget` produced while handling an event to the application.
+    fn handle_injected_event(
+        &mut self,
+        event_handler: &mut dyn PushrodCallbackEvents,
+        injectable_event: Option<CallbackEvent>,
+    ) {
+        // Clicks are also posted to the event bus, for subscribers that prefer it to callbacks.
+        if let Some(CallbackEvent::WidgetClicked { widget_id, .. }) = &injectable_event {
+            self.widget_store
+                .borrow_mut()
+                .post_event(PushrodEvent::Clicked {
+                    widget_id: *widget_id,
+                });
+        }
+
+        match injectable_event {
+            Some(new_event) => {
+                event_handler.handle_event(new_event.clone(), &mut self.widget_store.borrow_mut())
+            }
+            None => (),
+        }
+    }
+
+    /// Offers an input event to each of the `widget_ids` in turn, until one of them handles it;
+    /// see `EventResult`.  `make_event` builds the event for each `Widget`.  The application
+    /// receives the event of the `Widget` that handled it, unless it was consumed, or the event
+    /// of the first `Widget` if none of them handled it.  Returns the ID of the `Widget` that
+    /// handled the event, or the first ID if none did, or `WidgetId::INVALID` if there are no IDs.
+    fn propagate_event<F>(
+        &mut self,
+        widget_ids: &[WidgetId],
+        event_handler: &mut dyn PushrodCallbackEvents,
+        make_event: F,
+    ) -> WidgetId
+    where
+        F: Fn(&mut WidgetStore, WidgetId) -> CallbackEvent,
+    {
+        for &widget_id in widget_ids {
+            let event = make_event(&mut self.widget_store.borrow_mut(), widget_id);
+            let (result, injectable_event) = self
+                .widget_store
+                .borrow_mut()
+                .handle_input_event(widget_id, event.clone());
+
+            match result {
+                EventResult::NotHandled => {
+                    self.handle_injected_event(event_handler, injectable_event);
+                }
+                EventResult::Handled => {
+                    event_handler.handle_event(event, &mut self.widget_store.borrow_mut());
+                    self.handle_injected_event(event_handler, injectable_event);
+                    return widget_id;
+                }
+                EventResult::Consumed => {
+                    self.handle_injected_event(event_handler, injectable_event);
+                    return widget_id;
+                }
+            }
+        }
+
+        match widget_ids.first() {
+            Some(&widget_id) => {
+                let event = make_event(&mut self.widget_store.borrow_mut(), widget_id);
+
+                event_handler.handle_event(event, &mut self.widget_store.borrow_mut());
+                widget_id
+            }
+            None => WidgetId::INVALID,
+        }
+    }
+
+    /// Returns the focused widget, followed by each of its parents, which is the order in which
+    /// keyboard events are offered to them.
+    fn get_focus_chain(&mut self, focused_widget_id: WidgetId) -> Vec<WidgetId> {
+        let mut widget_ids = vec![focused_widget_id];
+        let mut parent_id = self
             .widget_store
             .borrow_mut()
-            .handle_event(widget_id, event.clone());
+            .get_parent_of(focused_widget_id);
 
-        event_handler.handle_event(event.clone(), &mut self.widget_store.borrow_mut());
+        while parent_id != WidgetId::ROOT {
+            widget_ids.push(parent_id);
+            parent_id = self.widget_store.borrow_mut().get_parent_of(parent_id);
+        }
 
-        match injectable_event {
-            Some(new_event) => {
-                event_handler.handle_event(new_event.clone(), &mut self.widget_store.borrow_mut())
-            }
-            None => (),
+        widget_ids
+    }
+
+    /// Returns the IDs of the `Widget`s that a mouse button press or scroll at `point` is offered
+    /// to, in order: the hovered `Widget`, followed by the `Widget`s under it.  Only the hovered
+    /// `Widget` is included while it has captured the mouse.
+    fn get_mouse_event_ids(&mut self, hovered_id: WidgetId, point: &Point) -> Vec<WidgetId> {
+        if !hovered_id.is_valid() {
+            return Vec::new();
+        }
+
+        let mut widget_ids = vec![hovered_id];
+
+        if !self.widget_store.borrow().get_mouse_capture().is_valid() {
+            widget_ids.extend(
+                self.widget_store
+                    .borrow_mut()
+                    .get_widget_ids_for_point(point.clone())
+                    .into_iter()
+                    .filter(|widget_id| *widget_id != hovered_id),
+            );
         }
+
+        widget_ids
     }
 
     /// This is the main run loop that is called to process all UI events.  This loop is responsible
@@ -107,142 +1687,445 @@ impl Pushrod {
     ///
     /// The run loop handles events in the following order:
     ///
+    /// - The debug key, which turns the debug overlay on and off, and is not passed on (see
+    ///   `set_debug_key`)
+    /// - The raw event callback (see `on_raw_event`)
     /// - Mouse events
     ///   - Movement events
-    ///   - Button events
-    ///   - Scroll button events
+    ///   - Button events, including clicks synthesized from button presses and releases.  A
+    ///     button press or scroll is offered to the widgets under the mouse, topmost first, until
+    ///     one of them handles it (see `Widget::handle_input_event`)
+    ///   - Mouse capture: a widget that a button is pressed in receives all mouse events until the
+    ///     buttons are released
+    ///   - Scroll events, along both axes, which are turned sideways while Shift is held down
+    ///   - Drag-and-drop events, which replace the hover and button events while a drag is active
+    /// - Touch events, which are sent to widgets that handle multi-touch, and otherwise replayed
+    ///   as mouse events, one touch at a time, or pan the scrollable widget that they are dragged
+    ///   across (see `Widget::set_multi_touch`)
+    /// - Gamepad events, which move the focus to the nearest widget in a direction, or replay the
+    ///   arrow keys, Enter, and Escape, showing the focus indicator (see `GamepadNavigator`)
+    /// - Keyboard and text input events, which are sent to the focused widget, and then to each
+    ///   of its parents until one of them handles the event
+    /// - Focus change events
+    /// - Window focus events, which release the mouse when the window loses focus
     /// - Custom events are then dispatched to any registered event listeners
+    /// - Window resize events, which are coalesced, and dispatched before the next update or draw
+    /// - Update events, which are passed to all widgets whether or not the window is drawn
+    /// - Tooltips, which are shown once the mouse has rested over a widget for long enough
+    /// - Removal of widgets that were removed while handling the event
+    /// - Layout of the children of layout widgets (see `WidgetStore::apply_layouts`)
+    /// - Configuration change events, sent once for each widget whose configuration was changed
+    /// - Delivery of the events posted to the event bus since the last frame
+    /// - The frame callback (see `on_frame`)
+    /// - Layout of the children of layout widgets again, in case the frame callback changed them
     /// - Draw loop
-    ///   - Draw only widgets whose states have become invalidated
+    ///   - With lazy rendering, skip the frame if nothing has changed (see `set_lazy_rendering`)
+    ///   - Draw only widgets whose states have become invalidated, and the widgets they overlap
     ///   - Swap display buffers if required
+    /// - Errors reported while handling the event, which are sent to the error callback (see
+    ///   `on_error`)
     ///
-    /// This event is handled window-by-window.  Once a window has processed all of its pending
-    /// events, the next window is then processed.  No particular window takes precidence - any
-    /// window that has events to process gets handled in order.
-    pub fn run(&mut self, event_handler: &mut PushrodCallbackEvents) {
-        let mut last_widget_id = -1;
-        let mut previous_mouse_position: Point = make_origin_point();
-        let mut button_map: HashMap<i32, HashSet<Button>> = HashMap::new();
-
-        while let Some(ref event) = &self.window.next() {
-            event.mouse_cursor(|x, y| {
-                let mouse_point = make_point_f64(x, y);
-
-                if mouse_point.x != previous_mouse_position.x
-                    || mouse_point.y != previous_mouse_position.y
-                {
-                    previous_mouse_position = mouse_point.clone();
-
-                    let current_widget_id = self
-                        .widget_store
-                        .borrow_mut()
-                        .get_widget_id_for_point(mouse_point.clone());
-                    let current_parent_for_widget = self
-                        .widget_store
+    /// The loop returns once the window has been closed.  Only this window's events are handled:
+    /// to run several windows at once, add them to a `WindowManager`, which handles each window's
+    /// events in turn.
+    pub fn run(&mut self, event_handler: &mut dyn PushrodCallbackEvents) {
+        while let Some(event) = self.next_event() {
+            self.handle_window_event(&event, event_handler);
+        }
+    }
+
+    /// Runs the main loop in the same way as `run`, for applications that have no need for an
+    /// event handler, as they only use `Widget` callbacks, the event bus, and the `on_frame` and
+    /// `on_raw_event` callbacks.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use piston_window::*;
+    /// # use pushrod::core::main::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let window: PistonWindow = WindowSettings::new("Frames", [400, 300]).build().unwrap();
+    ///    let mut pushrod = Pushrod::new(window);
+    ///    let mut frames = 0;
+    ///
+    ///    pushrod.on_frame(Box::new(move |_widget_store| frames += 1));
+    ///    pushrod.on_raw_event(Box::new(|event, _widget_store| {
+    ///        if let Some(text) = event.text_args() {
+    ///            eprintln!("Text entered: {}", text);
+    ///        }
+    ///    }));
+    ///
+    ///    pushrod.run_without_handler();
+    /// # }
+    /// ```
+    pub fn run_without_handler(&mut self) {
+        self.run(&mut NoEventHandler {});
+    }
+
+    /// Waits for the next event from this window, returning `None` once the window has been
+    /// closed.  This is used together with `handle_window_event` to drive several windows from
+    /// one loop, as the `WindowManager` does.  Before the window closes, whether from its close
+    /// button or from `request_close`, the close requested callback is consulted, and the window
+    /// keeps running if it returns `false`.  A headless `Pushrod` has no events to wait for, and
+    /// always returns `None`.
+    pub fn next_event(&mut self) -> Option<Event> {
+        if self.is_headless() {
+            return None;
+        }
+
+        loop {
+            if self.widget_store.borrow_mut().take_close_request() && self.confirm_close() {
+                self.surface.get_window().set_should_close(true);
+                return None;
+            }
+
+            match self.surface.get_window().next() {
+                Some(event) => return Some(event),
+                None => {
+                    if self.confirm_close() {
+                        return None;
+                    }
+
+                    self.surface.get_window().set_should_close(false);
+                }
+            }
+        }
+    }
+
+    /// Handles a single event from this window, in the order described by `run`, sending the
+    /// resulting callbacks to the `event_handler`.  The state of the mouse and keyboard is kept
+    /// between calls.
+    pub fn handle_window_event(
+        &mut self,
+        event: &Event,
+        event_handler: &mut dyn PushrodCallbackEvents,
+    ) {
+        if self.handle_debug_key(event) {
+            self.input_since_draw = true;
+            self.dispatch_errors();
+            return;
+        }
+
+        let mut last_widget_id = self.last_widget_id;
+        let mut previous_mouse_position = self.previous_mouse_position.clone();
+        let mut button_map = std::mem::take(&mut self.button_map);
+        let mut modifiers = self.modifiers;
+
+        if let Event::Input(_) = event {
+            self.input_since_draw = true;
+        }
+
+        if let Some(callback) = &mut self.on_raw_event {
+            callback(event, &mut self.widget_store.borrow_mut());
+        }
+
+        // Each window may have its own theme, so its theme is made current before its widgets
+        // are used.
+        set_current_theme(self.widget_store.borrow().get_theme());
+
+        // The focus indicator is shown while a gamepad is used, until the mouse is used again.
+        if let Some(Button::Mouse(_)) = event.press_args() {
+            self.widget_store
+                .borrow_mut()
+                .set_focus_indicator_visible(false);
+        }
+
+        event.mouse_cursor(|x, y| {
+            let mouse_point = make_point_f64(x, y);
+
+            if mouse_point.x != previous_mouse_position.x
+                || mouse_point.y != previous_mouse_position.y
+            {
+                previous_mouse_position = mouse_point.clone();
+
+                // While dragging, drag events are sent instead of the normal hover events.
+                if self.widget_store.borrow().is_dragging() {
+                    self.widget_store.borrow_mut().drag_to(mouse_point.clone());
+                    self.dispatch_pending_events(event_handler);
+                    return;
+                }
+
+                // A widget that has captured the mouse keeps receiving the mouse move
+                // events, even once the mouse has left it.
+                let captured_widget_id = self.widget_store.borrow().get_mouse_capture();
+                let current_widget_id = if captured_widget_id.is_valid() {
+                    captured_widget_id
+                } else {
+                    self.widget_store
                         .borrow_mut()
-                        .get_parent_of(current_widget_id);
+                        .get_widget_id_for_point(mouse_point.clone())
+                };
+                let current_parent_for_widget = self
+                    .widget_store
+                    .borrow_mut()
+                    .get_parent_of(current_widget_id);
 
-                    // Handles the mouse move callback.
-                    if current_widget_id != -1 {
+                if current_widget_id != last_widget_id {
+                    if last_widget_id.is_valid() {
                         self.handle_event(
-                            current_widget_id,
+                            last_widget_id,
                             event_handler,
-                            CallbackEvent::MouseMoved {
-                                widget_id: current_widget_id,
-                                point: mouse_point.clone(),
+                            CallbackEvent::MouseExited {
+                                widget_id: last_widget_id,
                             },
                         );
                     }
 
-                    if current_widget_id != last_widget_id {
-                        if last_widget_id != -1 {
-                            self.handle_event(
-                                last_widget_id,
-                                event_handler,
-                                CallbackEvent::MouseExited {
-                                    widget_id: last_widget_id,
-                                },
-                            );
-                        }
-
-                        last_widget_id = current_widget_id;
-
-                        if last_widget_id != -1 {
-                            self.handle_event(
-                                last_widget_id,
-                                event_handler,
-                                CallbackEvent::MouseEntered {
-                                    widget_id: last_widget_id,
-                                },
-                            );
-                        }
+                    last_widget_id = current_widget_id;
 
-                        eprintln!(
-                            "Widget IDs: current={} parent={} children={:?}",
-                            current_widget_id,
-                            current_parent_for_widget,
-                            self.widget_store
-                                .borrow_mut()
-                                .get_children_of(current_widget_id)
+                    if last_widget_id.is_valid() {
+                        self.handle_event(
+                            last_widget_id,
+                            event_handler,
+                            CallbackEvent::MouseEntered {
+                                widget_id: last_widget_id,
+                            },
                         );
                     }
+
+                    eprintln!(
+                        "Widget IDs: current={} parent={} children={:?}",
+                        current_widget_id,
+                        current_parent_for_widget,
+                        self.widget_store
+                            .borrow_mut()
+                            .get_children_of(current_widget_id)
+                    );
+                }
+
+                // Handles the mouse move callback after any enter event, so that a widget
+                // always knows where the mouse is once it has been entered.
+                if current_widget_id.is_valid() {
+                    let local_point = self
+                        .widget_store
+                        .borrow_mut()
+                        .get_widget_local_point(current_widget_id, mouse_point.clone());
+
+                    self.handle_event(
+                        current_widget_id,
+                        event_handler,
+                        CallbackEvent::MouseMoved {
+                            widget_id: current_widget_id,
+                            point: local_point,
+                        },
+                    );
+                }
+
+                self.restart_tooltip(current_widget_id);
+            }
+        });
+
+        event.mouse_scroll(|x, y| {
+            let delta = ScrollDelta::new(x, y, self.scroll_unit);
+
+            // Shift turns the mouse wheel sideways, for every widget alike.
+            let delta = if modifiers.shift {
+                delta.to_horizontal()
+            } else {
+                delta
+            };
+            let widget_ids = self.get_mouse_event_ids(last_widget_id, &previous_mouse_position);
+
+            self.propagate_event(&widget_ids, event_handler, |_, widget_id| {
+                CallbackEvent::MouseScrolled {
+                    widget_id,
+                    delta,
+                    modifiers,
                 }
             });
+        });
+
+        event.button(|args| {
+            // Keyboard and controller buttons are handled separately.
+            if !matches!(args.button, Button::Mouse(_)) {
+                return;
+            }
+
+            // Releasing the mouse button drops whatever is being dragged.  No other button
+            // events are sent until the drag is over.
+            if self.widget_store.borrow().is_dragging() {
+                if args.state == ButtonState::Release {
+                    self.widget_store
+                        .borrow_mut()
+                        .end_drag(previous_mouse_position.clone());
+                    self.dispatch_pending_events(event_handler);
+
+                    button_map.clear();
+                    self.click_counter.reset();
 
-            event.mouse_scroll(|x, y| {
-                let mouse_point = make_point_f64(x, y);
+                    // The mouse is once again over whichever widget it was dropped on.
+                    last_widget_id = self
+                        .widget_store
+                        .borrow_mut()
+                        .get_widget_id_for_point(previous_mouse_position.clone());
 
-                if last_widget_id != -1 {
                     self.handle_event(
                         last_widget_id,
                         event_handler,
-                        CallbackEvent::MouseScrolled {
+                        CallbackEvent::MouseEntered {
                             widget_id: last_widget_id,
-                            point: mouse_point.clone(),
                         },
                     );
                 }
-            });
 
-            event.button(|args| match args.state {
+                return;
+            }
+
+            match args.state {
                 ButtonState::Press => {
+                    self.hide_tooltip();
+                    self.tooltip_browsing = false;
+
+                    // A press outside of the open popups only closes them.
+                    if self
+                        .widget_store
+                        .borrow_mut()
+                        .close_popups_outside(previous_mouse_position.clone())
+                    {
+                        self.dispatch_pending_events(event_handler);
+                        return;
+                    }
+
+                    let widget_ids =
+                        self.get_mouse_event_ids(last_widget_id, &previous_mouse_position);
+
+                    self.widget_store
+                        .borrow_mut()
+                        .set_focused_widget(last_widget_id);
+                    self.dispatch_pending_events(event_handler);
+
+                    if last_widget_id.is_valid() {
+                        self.widget_store
+                            .borrow_mut()
+                            .set_mouse_capture(last_widget_id);
+                    }
+
+                    let mouse_point = previous_mouse_position.clone();
+                    let handled_id =
+                        self.propagate_event(&widget_ids, event_handler, |store, widget_id| {
+                            CallbackEvent::MouseButtonDown {
+                                widget_id,
+                                button: args.button,
+                                point: store.get_widget_local_point(widget_id, mouse_point.clone()),
+                            }
+                        });
+
+                    // A press that was passed on to a widget underneath makes that widget the
+                    // one the mouse is over, until the buttons are released, so that it
+                    // receives the rest of the click.
+                    if handled_id != last_widget_id {
+                        self.handle_event(
+                            last_widget_id,
+                            event_handler,
+                            CallbackEvent::MouseExited {
+                                widget_id: last_widget_id,
+                            },
+                        );
+
+                        self.passed_press_ids.extend(
+                            widget_ids
+                                .iter()
+                                .take_while(|widget_id| **widget_id != handled_id),
+                        );
+                        last_widget_id = handled_id;
+
+                        self.widget_store
+                            .borrow_mut()
+                            .set_focused_widget(last_widget_id);
+                        self.widget_store
+                            .borrow_mut()
+                            .set_mouse_capture(last_widget_id);
+                        self.dispatch_pending_events(event_handler);
+
+                        self.handle_event(
+                            last_widget_id,
+                            event_handler,
+                            CallbackEvent::MouseEntered {
+                                widget_id: last_widget_id,
+                            },
+                        );
+                    }
+
                     button_map
                         .entry(last_widget_id)
                         .or_insert(HashSet::new())
                         .insert(args.button);
 
-                    self.handle_event(
+                    self.click_counter.press(
                         last_widget_id,
-                        event_handler,
-                        CallbackEvent::MouseButtonDown {
-                            widget_id: last_widget_id,
-                            button: args.button,
-                        },
+                        args.button,
+                        previous_mouse_position.clone(),
                     );
                 }
                 ButtonState::Release => {
+                    // The widget under the mouse may not be the hovered widget, if the
+                    // mouse is captured.  Widgets that passed the press on are skipped, so
+                    // that the release reaches the widget that handled the press.
+                    let widget_ids = self
+                        .widget_store
+                        .borrow_mut()
+                        .get_widget_ids_for_point(previous_mouse_position.clone());
+                    let hover_widget_id = widget_ids.first().copied().unwrap_or(WidgetId::INVALID);
+                    let release_widget_id = widget_ids
+                        .into_iter()
+                        .find(|widget_id| !self.passed_press_ids.contains(widget_id))
+                        .unwrap_or(WidgetId::INVALID);
+                    let click_count = self.click_counter.release(
+                        release_widget_id,
+                        args.button,
+                        previous_mouse_position.clone(),
+                    );
                     let button_set = button_map.entry(last_widget_id).or_insert(HashSet::new());
 
-                    if button_set.contains(&args.button) {
+                    if button_set.contains(&args.button) && release_widget_id == last_widget_id
+                    {
                         button_set.remove(&args.button);
 
+                        let local_point =
+                            self.widget_store.borrow_mut().get_widget_local_point(
+                                last_widget_id,
+                                previous_mouse_position.clone(),
+                            );
+
                         self.handle_event(
                             last_widget_id,
                             event_handler,
                             CallbackEvent::MouseButtonUpInside {
                                 widget_id: last_widget_id,
                                 button: args.button,
+                                point: local_point.clone(),
                             },
                         );
+
+                        if let Some(click_count) = click_count {
+                            self.handle_event(
+                                last_widget_id,
+                                event_handler,
+                                CallbackEvent::MouseClicked {
+                                    widget_id: last_widget_id,
+                                    button: args.button,
+                                    point: local_point,
+                                    click_count,
+                                    modifiers,
+                                },
+                            );
+                        }
                     } else {
                         for (widget_id, button_set) in button_map.iter_mut() {
                             if button_set.contains(&args.button) {
+                                let local_point =
+                                    self.widget_store.borrow_mut().get_widget_local_point(
+                                        *widget_id,
+                                        previous_mouse_position.clone(),
+                                    );
+
                                 self.handle_event(
                                     *widget_id,
                                     event_handler,
                                     CallbackEvent::MouseButtonUpOutside {
                                         widget_id: *widget_id,
                                         button: args.button,
+                                        point: local_point,
                                     },
                                 );
 
@@ -250,58 +2133,284 @@ impl Pushrod {
                             }
                         }
                     }
+
+                    // Once all of its buttons are released, the widget lets go of the
+                    // mouse, and the mouse is matched to the widget it is over again.
+                    let captured_widget_id = self.widget_store.borrow().get_mouse_capture();
+
+                    if captured_widget_id.is_valid()
+                        && button_map
+                            .get(&captured_widget_id)
+                            .is_none_or(|button_set| button_set.is_empty())
+                    {
+                        self.widget_store.borrow_mut().release_capture();
+                        self.passed_press_ids.clear();
+
+                        if hover_widget_id != last_widget_id {
+                            self.handle_event(
+                                last_widget_id,
+                                event_handler,
+                                CallbackEvent::MouseExited {
+                                    widget_id: last_widget_id,
+                                },
+                            );
+
+                            last_widget_id = hover_widget_id;
+
+                            self.handle_event(
+                                last_widget_id,
+                                event_handler,
+                                CallbackEvent::MouseEntered {
+                                    widget_id: last_widget_id,
+                                },
+                            );
+                        }
+                    }
                 }
-            });
+            }
+        });
 
-            event.resize(|w, h| {
-                event_handler.handle_event(
-                    CallbackEvent::WindowResized {
-                        size: crate::core::point::Size {
-                            w: w as i32,
-                            h: h as i32,
-                        },
-                    },
-                    &mut self.widget_store.borrow_mut(),
-                );
+        // Resizes are only dispatched on the next update or draw, so a stream of resize
+        // events is sent as a single event with the final size.
+        event.resize(|w, h| {
+            self.pending_resize = Some(crate::core::point::Size {
+                w: w as i32,
+                h: h as i32,
             });
+        });
 
-            event.focus(|focused| {
-                self.handle_event(
-                    last_widget_id,
+        event.focus(|focused| {
+            if !focused {
+                self.release_mouse(
                     event_handler,
-                    CallbackEvent::WindowFocused { flag: focused },
+                    &mut last_widget_id,
+                    &mut button_map,
+                    &previous_mouse_position,
                 );
-            });
+                self.cancel_touches(event_handler);
+            }
 
-            match event {
-                Event::Input(Input::Button(ButtonArgs {
-                    state,
-                    button: Button::Keyboard(key),
-                    scancode: _,
-                })) => {
-                    self.handle_event(
-                        last_widget_id,
-                        event_handler,
+            if let Some(callback) = &mut self.on_focus_changed {
+                callback(focused, &mut self.widget_store.borrow_mut());
+            }
+
+            let event = CallbackEvent::WindowFocused { flag: focused };
+
+            event_handler.handle_event(event.clone(), &mut self.widget_store.borrow_mut());
+
+            let injected_events = self.widget_store.borrow_mut().broadcast_event(event);
+
+            for injected_event in injected_events {
+                event_handler.handle_event(injected_event, &mut self.widget_store.borrow_mut());
+            }
+        });
+
+        match event {
+            Event::Input(Input::Button(ButtonArgs {
+                state,
+                button: Button::Keyboard(key),
+                scancode: _,
+            })) => {
+                modifiers.update(*key, *state);
+
+                let focused_widget_id = self.widget_store.borrow().get_focused_widget();
+                let focus_consumes_tab = focused_widget_id.is_valid()
+                    && self
+                        .widget_store
+                        .borrow_mut()
+                        .get_widget_for_id(focused_widget_id)
+                        .borrow_mut()
+                        .consumes_tab();
+
+                // Tab and Shift+Tab move the focus, unless the focused widget handles Tab
+                // itself.  Neither the press nor the release is delivered.
+                // Escape cancels a drag, and is not delivered.
+                if *key == Key::Escape && self.widget_store.borrow().is_dragging() {
+                    if *state == ButtonState::Press {
+                        self.widget_store.borrow_mut().cancel_drag();
+                    }
+                } else if *key == Key::Tab && !focus_consumes_tab {
+                    if *state == ButtonState::Press {
+                        if modifiers.shift {
+                            self.widget_store.borrow_mut().focus_previous();
+                        } else {
+                            self.widget_store.borrow_mut().focus_next();
+                        }
+                    }
+                } else if !focused_widget_id.is_valid() {
+                    event_handler.handle_event(
                         CallbackEvent::KeyPressed {
-                            widget_id: last_widget_id,
+                            widget_id: focused_widget_id,
+                            key: *key,
+                            state: *state,
+                            modifiers,
+                        },
+                        &mut self.widget_store.borrow_mut(),
+                    );
+                } else {
+                    // The key bubbles up from the focused widget through its parents, until
+                    // one of them handles it.
+                    let widget_ids = self.get_focus_chain(focused_widget_id);
+
+                    self.propagate_event(&widget_ids, event_handler, |_, widget_id| {
+                        CallbackEvent::KeyPressed {
+                            widget_id,
                             key: *key,
                             state: *state,
+                            modifiers,
+                        }
+                    });
+                }
+            }
+            Event::Input(Input::Text(text)) => {
+                let focused_widget_id = self.widget_store.borrow().get_focused_widget();
+
+                if !focused_widget_id.is_valid() {
+                    event_handler.handle_event(
+                        CallbackEvent::TextInput {
+                            widget_id: focused_widget_id,
+                            text: text.clone(),
                         },
+                        &mut self.widget_store.borrow_mut(),
                     );
+                } else {
+                    let widget_ids = self.get_focus_chain(focused_widget_id);
+
+                    self.propagate_event(&widget_ids, event_handler, |_, widget_id| {
+                        CallbackEvent::TextInput {
+                            widget_id,
+                            text: text.clone(),
+                        }
+                    });
                 }
-                _ => {}
-            };
+            }
+            _ => {}
+        };
 
-            event.resize(|_, _| {
-                self.widget_store.borrow_mut().invalidate_all_widgets();
-            });
+        self.dispatch_pending_events(event_handler);
 
-            // FPS loop handling
+        event.update(|args| {
+            self.dispatch_pending_resize(event_handler);
+            self.widget_store
+                .borrow_mut()
+                .update((args.dt * 1_000.0) as u64);
 
-            event.render(|_| {
-                self.handle_draw(&event);
-                self.widget_store.borrow_mut().invalidate_all_widgets();
-            });
+            // Flung content keeps moving until it slows to a stop, or reaches its edge.
+            if let Some((widget_id, delta)) =
+                self.touch_tracker.update_fling((args.dt * 1_000.0) as u64)
+            {
+                if !self.pan_widget(widget_id, delta) {
+                    self.touch_tracker.stop_fling();
+                }
+            }
+
+            self.tooltip_timer.tick();
+
+            if self.tooltip_timer.get_fire_count() > 0 {
+                self.tooltip_timer.reset();
+                self.tooltip_timer.set_enabled(false);
+                self.show_tooltip(last_widget_id, &previous_mouse_position);
+            }
+        });
+
+        // Widgets are only removed once the event has been handled, so no widget is in use.
+        let removed_ids = self.widget_store.borrow_mut().apply_pending_removals();
+
+        if !removed_ids.is_empty() {
+            // The removed widget is gone, so only the application is told that the mouse
+            // has left it.
+            if removed_ids.contains(&last_widget_id) {
+                event_handler.handle_event(
+                    CallbackEvent::MouseExited {
+                        widget_id: last_widget_id,
+                    },
+                    &mut self.widget_store.borrow_mut(),
+                );
+                last_widget_id = WidgetId::INVALID;
+                self.hide_tooltip();
+            }
+
+            button_map.retain(|widget_id, _| !removed_ids.contains(widget_id));
+            self.click_counter.reset();
+        }
+
+        // A hovered widget that becomes disabled no longer has the mouse over it.
+        if last_widget_id.is_valid()
+            && !self
+                .widget_store
+                .borrow_mut()
+                .is_widget_enabled(last_widget_id)
+        {
+            self.handle_event(
+                last_widget_id,
+                event_handler,
+                CallbackEvent::MouseExited {
+                    widget_id: last_widget_id,
+                },
+            );
+            last_widget_id = WidgetId::INVALID;
+            self.hide_tooltip();
+        }
+
+        self.widget_store.borrow_mut().apply_layouts();
+        self.widget_store.borrow_mut().queue_config_changes();
+        self.dispatch_pending_events(event_handler);
+
+        // A drag takes over the mouse, so the widget that was hovered no longer has it.
+        if last_widget_id.is_valid() && self.widget_store.borrow().is_dragging() {
+            self.handle_event(
+                last_widget_id,
+                event_handler,
+                CallbackEvent::MouseExited {
+                    widget_id: last_widget_id,
+                },
+            );
+            last_widget_id = WidgetId::INVALID;
+            self.hide_tooltip();
+        }
+
+        // The cursor is only updated once all of the events have been handled, so it always
+        // matches the widget that the mouse ended up over.
+        self.update_cursor(last_widget_id);
+        self.widget_store
+            .borrow_mut()
+            .get_debug_overlay()
+            .set_hovered_widget(last_widget_id);
+
+        // FPS loop handling
+
+        event.render(|args| {
+            self.update_scale_factor(args, event_handler);
+            self.dispatch_pending_resize(event_handler);
+            self.widget_store.borrow_mut().deliver_posted_events();
+
+            if let Some(callback) = &mut self.on_frame {
+                callback(&mut self.widget_store.borrow_mut());
+            }
+
+            self.widget_store.borrow_mut().apply_layouts();
+
+            self.handle_draw(event);
+        });
+
+        self.last_widget_id = last_widget_id;
+        self.previous_mouse_position = previous_mouse_position;
+        self.button_map = button_map;
+        self.modifiers = modifiers;
+
+        // Touches are handled last, as they replay mouse events through this same function.
+        if let Some(args) = event.touch_args() {
+            self.handle_touch(&args, event_handler);
+        }
+
+        // Gamepad actions replay keyboard events in the same way, and held directions repeat on
+        // each update.
+        if let Some(action) = self.read_gamepad_event(event) {
+            self.handle_gamepad(action, event_handler);
         }
+
+        // Errors reported while the event was handled, such as a texture that could not be
+        // created, are handed to the application once it is done with.
+        self.dispatch_errors();
     }
 }
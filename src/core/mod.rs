@@ -25,6 +25,70 @@ pub mod point;
 /// `Pushrod` object that is created contains its own set of `Widget` objects, stored here.
 pub mod widget_store;
 
+/// Identifies a `Widget` in a `WidgetStore`, with a reserved ID for the top level `Widget`, and
+/// one for no `Widget` at all.
+pub mod widget_id;
+
+/// Errors that are returned by fallible constructors and loaders, or reported while the run
+/// loop is running.
+pub mod error;
+
+/// Describes `Widget`s to assistive tools, such as screen readers: their roles, names, values,
+/// and states, as a tree that can be serialized.
+pub mod accessibility;
+
 /// This is a per-widget callback store that is used to call closures when an event is
 /// triggered.
 pub mod callbacks;
+
+/// Monotonic time sources used by time-based `Widget`s, such as the `TimerWidget`.  A
+/// manually-advanced clock is also provided for testing.
+pub mod clock;
+
+/// Synthesizes clicks, double clicks, and multiple clicks from mouse button presses and
+/// releases, used by the `Pushrod` run loop.
+pub mod click_counter;
+
+/// Loads `Widget`s from a TOML layout description, and adds them to a `WidgetStore` under
+/// their names.
+pub mod layout;
+
+/// Runs several `Pushrod` windows in one application, each with its own `WidgetStore` and event
+/// handler.
+pub mod window_manager;
+
+/// Keeps offscreen textures of the `Widget`s that are set to be cached, so that they only have
+/// to be drawn again when they change.
+pub mod render_cache;
+
+/// Tracks the touches on a touch screen by finger, telling taps from drags, and flinging the
+/// content of scrollable `Widget`s once a drag is let go.
+pub mod touch;
+
+/// Moves the focus between `Widget`s with the directional pad or stick of a gamepad, to the
+/// nearest `Widget` in that direction, and activates them with its buttons.
+pub mod gamepad;
+
+/// Holds the text that is cut, copied, and pasted by `Widget`s, in the clipboard of the system,
+/// or in the application itself when there is no system clipboard.
+pub mod clipboard;
+
+/// Draws `Widget`s into an offscreen framebuffer, and reads the pixels back into an image, for
+/// screenshots and visual regression tests.
+pub mod screenshot;
+
+/// Draws `Widget`s into the window, or records what they draw, so that they can be tested
+/// without a window.
+pub mod draw_target;
+
+/// Draws the bounds, IDs, and names of `Widget`s over the window, and flashes the `Widget`s
+/// that are redrawn, for debugging layouts.
+pub mod debug_overlay;
+
+/// Runs `Widget`s in a `Pushrod` without a window, sending them mouse and keyboard events,
+/// moving time on, and recording what they draw, for tests on machines without a display.
+pub mod test_harness;
+
+/// Keeps the bounds of objects in a grid, so that the objects under a point can be found without
+/// looking at every object, for finding the `Widget` under the mouse.
+pub mod spatial_index;
@@ -12,9 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::{Add, Sub};
+
+use serde::Serialize;
+
 /// Structure identifying a point on the screen by X and Y coordinates.  X and Y coordinates
 /// are represented from the upper left-hand corner of the base object.
-#[derive(Clone, Debug)]
+///
+/// Points can be added to and subtracted from each other, such as to turn a point in window
+/// coordinates into one that is relative to the origin of a `Widget`, and converted to and from
+/// the `[f64; 2]` positions used by Piston.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # fn main() {
+///    let origin = make_point_i32(100, 50);
+///    let mouse = make_point_i32(130, 55);
+///
+///    assert_eq!(mouse.clone() - origin.clone(), make_point_i32(30, 5));
+///    assert_eq!(origin.clone() + make_point_i32(30, 5), mouse);
+///    assert_eq!(origin.offset(-10, 10), make_point_i32(90, 60));
+///    assert_eq!(origin.scale(1.5), make_point_i32(150, 75));
+///
+///    let position: [f64; 2] = mouse.clone().into();
+///
+///    assert_eq!(position, [130.0, 55.0]);
+///    assert_eq!(Point::from([130.9, 55.2]), mouse);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -23,12 +50,177 @@ pub struct Point {
 /// Structure identifying a size of an object by W (width) and H (height), respectively.
 /// Other systems may use "width" and "height" as nomenclature, however, we wanted to keep
 /// naming consistent.
-#[derive(Clone, Debug)]
+///
+/// Sizes can be added to each other, such as to grow a `Widget` by its border, scaled, and
+/// clamped between a smallest and a largest size.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # fn main() {
+///    let size = Size { w: 300, h: 40 };
+///
+///    assert_eq!(size.clone() + Size { w: 4, h: 4 }, Size { w: 304, h: 44 });
+///    assert_eq!(size.scale(2.0), Size { w: 600, h: 80 });
+///    assert_eq!(
+///        size.clamp(&Size { w: 50, h: 50 }, &Size { w: 200, h: 200 }),
+///        Size { w: 200, h: 50 }
+///    );
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct Size {
     pub w: i32,
     pub h: i32,
 }
 
+/// Implementation of the `Point`.
+impl Point {
+    /// Returns this point, moved `dx` pixels to the right, and `dy` pixels down.
+    pub fn offset(&self, dx: i32, dy: i32) -> Point {
+        Point {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    /// Returns this point, with both coordinates multiplied by `factor`, and rounded to the
+    /// nearest pixel.
+    pub fn scale(&self, factor: f64) -> Point {
+        Point {
+            x: (f64::from(self.x) * factor).round() as i32,
+            y: (f64::from(self.y) * factor).round() as i32,
+        }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        self.offset(other.x, other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        self.offset(-other.x, -other.y)
+    }
+}
+
+/// Converts a Piston position to a `Point`, dropping the fractions of a pixel, in the same way
+/// as `make_point_f64`.
+impl From<[f64; 2]> for Point {
+    fn from(position: [f64; 2]) -> Point {
+        make_point_f64(position[0], position[1])
+    }
+}
+
+/// Converts a `Point` to a Piston position.
+impl From<Point> for [f64; 2] {
+    fn from(point: Point) -> [f64; 2] {
+        [f64::from(point.x), f64::from(point.y)]
+    }
+}
+
+/// Implementation of the `Size`.
+impl Size {
+    /// Returns this size, with the width and height multiplied by `factor`, and rounded to the
+    /// nearest pixel.
+    pub fn scale(&self, factor: f64) -> Size {
+        Size {
+            w: (f64::from(self.w) * factor).round() as i32,
+            h: (f64::from(self.h) * factor).round() as i32,
+        }
+    }
+
+    /// Returns this size, with the width and height each kept between the width and height of
+    /// `min` and `max`.  If `min` is larger than `max`, `min` wins.
+    pub fn clamp(&self, min: &Size, max: &Size) -> Size {
+        Size {
+            w: self.w.min(max.w).max(min.w),
+            h: self.h.min(max.h).max(min.h),
+        }
+    }
+}
+
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, other: Size) -> Size {
+        Size {
+            w: self.w + other.w,
+            h: self.h + other.h,
+        }
+    }
+}
+
+/// Converts a Piston size, such as the size of a window, to a `Size`, dropping the fractions of
+/// a pixel.
+impl From<[f64; 2]> for Size {
+    fn from(size: [f64; 2]) -> Size {
+        Size {
+            w: size[0] as i32,
+            h: size[1] as i32,
+        }
+    }
+}
+
+/// Converts a `Size` to a Piston size.
+impl From<Size> for [f64; 2] {
+    fn from(size: Size) -> [f64; 2] {
+        [f64::from(size.w), f64::from(size.h)]
+    }
+}
+
+/// Indicates whether `point` is inside of the rectangle of `size` at `origin`.  This is the rule
+/// that all hit-testing uses: the left and top edges are inside of the rectangle, and the right
+/// and bottom edges are not, so a `Widget` that is 100 pixels wide covers exactly 100 pixels,
+/// and the pixel where one `Widget` ends and the next begins belongs to only one of them.  A
+/// rectangle without a width or height contains no points.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # fn main() {
+///    let origin = make_point_i32(10, 20);
+///    let size = Size { w: 100, h: 50 };
+///
+///    // The first and last pixels of each edge are inside.
+///    assert!(point_in_rect(&make_point_i32(10, 20), &origin, &size));
+///    assert!(point_in_rect(&make_point_i32(109, 20), &origin, &size));
+///    assert!(point_in_rect(&make_point_i32(10, 69), &origin, &size));
+///    assert!(point_in_rect(&make_point_i32(109, 69), &origin, &size));
+///
+///    // The pixels just outside of each edge are not.
+///    assert!(!point_in_rect(&make_point_i32(9, 20), &origin, &size));
+///    assert!(!point_in_rect(&make_point_i32(10, 19), &origin, &size));
+///    assert!(!point_in_rect(&make_point_i32(110, 20), &origin, &size));
+///    assert!(!point_in_rect(&make_point_i32(10, 70), &origin, &size));
+///    assert!(!point_in_rect(&make_point_i32(110, 70), &origin, &size));
+///
+///    // An empty rectangle contains nothing, not even its origin.
+///    assert!(!point_in_rect(&origin, &origin, &make_unsized()));
+///
+///    // Rectangles as `[x, y, w, h]` follow the same rule.
+///    assert!(point_in_bounds(&make_point_i32(109, 69), &[10, 20, 100, 50]));
+///    assert!(!point_in_bounds(&make_point_i32(110, 69), &[10, 20, 100, 50]));
+/// # }
+/// ```
+pub fn point_in_rect(point: &Point, origin: &Point, size: &Size) -> bool {
+    point.x >= origin.x
+        && point.x < origin.x + size.w
+        && point.y >= origin.y
+        && point.y < origin.y + size.h
+}
+
+/// Indicates whether `point` is inside of the rectangle `[x, y, w, h]`.  See `point_in_rect`.
+pub fn point_in_bounds(point: &Point, bounds: &[i32; 4]) -> bool {
+    Rect::from(*bounds).contains(point)
+}
+
 /// Convenience method to create a new `Point`.
 pub fn make_point_i32(x: i32, y: i32) -> Point {
     Point { x, y }
@@ -69,3 +261,269 @@ pub fn make_point_f64(x: f64, y: f64) -> Point {
 pub fn make_unsized() -> Size {
     Size { w: 0, h: 0 }
 }
+
+/// Structure identifying the space taken away from each side of a rectangle, in pixels.  This is
+/// used for the padding and margin of a `Widget`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Insets {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+/// Implementation of the `Insets`.
+impl Insets {
+    /// Returns the rectangle `[x, y, w, h]` that remains of an object of `size` once these insets
+    /// are taken away from each side, relative to the upper left-hand corner of the object.  The
+    /// width and height are never negative.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # fn main() {
+    ///    let insets = make_insets(1, 2, 3, 4);
+    ///
+    ///    assert_eq!(insets.inset_size(&Size { w: 100, h: 50 }), [4, 1, 94, 46]);
+    ///    assert_eq!(insets.inset_size(&Size { w: 4, h: 2 }), [4, 1, 0, 0]);
+    ///    assert_eq!(make_uniform_insets(2).grow(1), make_uniform_insets(3));
+    /// # }
+    /// ```
+    pub fn inset_size(&self, size: &Size) -> [i32; 4] {
+        [
+            self.left,
+            self.top,
+            (size.w - self.left - self.right).max(0),
+            (size.h - self.top - self.bottom).max(0),
+        ]
+    }
+
+    /// Returns these insets, grown by `amount` pixels on each side.
+    pub fn grow(&self, amount: i32) -> Insets {
+        Insets {
+            top: self.top + amount,
+            right: self.right + amount,
+            bottom: self.bottom + amount,
+            left: self.left + amount,
+        }
+    }
+}
+
+/// Convenience method to create new `Insets`, in the order top, right, bottom, left.
+pub fn make_insets(top: i32, right: i32, bottom: i32, left: i32) -> Insets {
+    Insets {
+        top,
+        right,
+        bottom,
+        left,
+    }
+}
+
+/// Convenience method to create `Insets` that are the same on all four sides.
+pub fn make_uniform_insets(inset: i32) -> Insets {
+    make_insets(inset, inset, inset, inset)
+}
+
+/// Structure identifying a rectangle on the screen by the `Point` of its upper left-hand corner
+/// and its `Size`.  A rectangle covers the pixels from its origin up to, but not including, its
+/// right and bottom edges, following the same rule as `point_in_rect`.  A rectangle without a
+/// width or height is empty: it contains no points, and intersects nothing.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # fn main() {
+///    let panel = make_rect(0, 0, 100, 100);
+///    let button = make_rect(80, 90, 40, 20);
+///
+///    assert!(panel.contains(&make_point_i32(99, 99)));
+///    assert!(!panel.contains(&make_point_i32(100, 99)));
+///    assert!(panel.intersects(&button));
+///    assert_eq!(panel.intersection(&button), Some(make_rect(80, 90, 20, 10)));
+///    assert_eq!(panel.union(&button), make_rect(0, 0, 120, 110));
+///    assert_eq!(
+///        button.translate(&make_point_i32(-80, 10)),
+///        make_rect(0, 100, 40, 20)
+///    );
+///
+///    // Rectangles that only touch do not overlap.
+///    assert!(!panel.intersects(&make_rect(100, 0, 10, 10)));
+///    assert_eq!(panel.intersection(&make_rect(0, 100, 10, 10)), None);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Rect {
+    pub origin: Point,
+    pub size: Size,
+}
+
+/// Implementation of the `Rect`.
+impl Rect {
+    /// Returns the X coordinate just past the right edge of this rectangle.
+    pub fn get_right(&self) -> i32 {
+        self.origin.x + self.size.w
+    }
+
+    /// Returns the Y coordinate just past the bottom edge of this rectangle.
+    pub fn get_bottom(&self) -> i32 {
+        self.origin.y + self.size.h
+    }
+
+    /// Indicates whether this rectangle has no width or no height, so that it covers no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.size.w <= 0 || self.size.h <= 0
+    }
+
+    /// Indicates whether `point` is inside of this rectangle.  See `point_in_rect`.
+    pub fn contains(&self, point: &Point) -> bool {
+        point_in_rect(point, &self.origin, &self.size)
+    }
+
+    /// Indicates whether this rectangle and `other` have any pixels in common.  Empty rectangles
+    /// intersect nothing, and rectangles that only share an edge do not intersect.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the area that this rectangle and `other` have in common, or `None` if they do not
+    /// overlap.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # fn main() {
+    ///    let rect = make_rect(10, 10, 50, 50);
+    ///
+    ///    assert_eq!(rect.intersection(&rect), Some(rect.clone()));
+    ///    assert_eq!(
+    ///        rect.intersection(&make_rect(0, 0, 20, 100)),
+    ///        Some(make_rect(10, 10, 10, 50))
+    ///    );
+    ///    assert_eq!(rect.intersection(&make_rect(59, 59, 10, 10)), Some(make_rect(59, 59, 1, 1)));
+    ///    assert_eq!(rect.intersection(&make_rect(60, 59, 10, 10)), None);
+    ///    assert_eq!(rect.intersection(&make_rect(20, 20, 0, 10)), None);
+    ///    assert_eq!(rect.intersection(&make_rect(20, 20, -5, 10)), None);
+    /// # }
+    /// ```
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+
+        let x1 = self.origin.x.max(other.origin.x);
+        let y1 = self.origin.y.max(other.origin.y);
+        let x2 = self.get_right().min(other.get_right());
+        let y2 = self.get_bottom().min(other.get_bottom());
+
+        if x2 > x1 && y2 > y1 {
+            Some(make_rect(x1, y1, x2 - x1, y2 - y1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle that contains both this rectangle and `other`.  Empty
+    /// rectangles cover nothing, so they do not add to the union: the union of a rectangle and an
+    /// empty one is the rectangle itself.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # fn main() {
+    ///    let rect = make_rect(10, 10, 50, 50);
+    ///
+    ///    assert_eq!(rect.union(&make_rect(100, 0, 10, 10)), make_rect(10, 0, 100, 60));
+    ///    assert_eq!(rect.union(&make_rect(0, 0, 0, 0)), rect);
+    ///    assert_eq!(make_rect(500, 500, 0, 10).union(&rect), rect);
+    /// # }
+    /// ```
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return self.clone();
+        }
+
+        if self.is_empty() {
+            return other.clone();
+        }
+
+        let x1 = self.origin.x.min(other.origin.x);
+        let y1 = self.origin.y.min(other.origin.y);
+        let x2 = self.get_right().max(other.get_right());
+        let y2 = self.get_bottom().max(other.get_bottom());
+
+        make_rect(x1, y1, x2 - x1, y2 - y1)
+    }
+
+    /// Returns the rectangle that remains of this one once `insets` are taken away from each
+    /// side.  The width and height are never negative.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # fn main() {
+    ///    let rect = make_rect(10, 10, 50, 20);
+    ///
+    ///    assert_eq!(rect.inset(&make_insets(1, 2, 3, 4)), make_rect(14, 11, 44, 16));
+    ///    assert_eq!(rect.inset(&make_uniform_insets(15)), make_rect(25, 25, 20, 0));
+    ///    assert!(rect.inset(&make_uniform_insets(15)).is_empty());
+    /// # }
+    /// ```
+    pub fn inset(&self, insets: &Insets) -> Rect {
+        let [x, y, w, h] = insets.inset_size(&self.size);
+
+        make_rect(self.origin.x + x, self.origin.y + y, w, h)
+    }
+
+    /// Returns this rectangle, grown by `insets` on each side, such as to include a border or a
+    /// shadow that is drawn around it.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # fn main() {
+    ///    let rect = make_rect(10, 10, 50, 20);
+    ///
+    ///    assert_eq!(rect.outset(&make_insets(1, 2, 3, 4)), make_rect(6, 9, 56, 24));
+    ///    assert_eq!(rect.outset(&make_uniform_insets(0)), rect);
+    /// # }
+    /// ```
+    pub fn outset(&self, insets: &Insets) -> Rect {
+        make_rect(
+            self.origin.x - insets.left,
+            self.origin.y - insets.top,
+            self.size.w + insets.left + insets.right,
+            self.size.h + insets.top + insets.bottom,
+        )
+    }
+
+    /// Returns this rectangle, moved by the X and Y coordinates of `offset`.
+    pub fn translate(&self, offset: &Point) -> Rect {
+        Rect {
+            origin: self.origin.offset(offset.x, offset.y),
+            size: self.size.clone(),
+        }
+    }
+}
+
+/// Converts a rectangle `[x, y, w, h]`, as used by `Widget::get_viewport_rect` and
+/// `Widget::invalidate_area`, to a `Rect`.
+impl From<[i32; 4]> for Rect {
+    fn from(bounds: [i32; 4]) -> Rect {
+        make_rect(bounds[0], bounds[1], bounds[2], bounds[3])
+    }
+}
+
+/// Converts a `Rect` to a rectangle `[x, y, w, h]`.
+impl From<Rect> for [i32; 4] {
+    fn from(rect: Rect) -> [i32; 4] {
+        [rect.origin.x, rect.origin.y, rect.size.w, rect.size.h]
+    }
+}
+
+/// Convenience method to create a `Rect` from its X and Y coordinates, width, and height.
+pub fn make_rect(x: i32, y: i32, w: i32, h: i32) -> Rect {
+    Rect {
+        origin: Point { x, y },
+        size: Size { w, h },
+    }
+}
@@ -0,0 +1,189 @@
+// Render Cache
+// Offscreen textures that cached widgets are drawn into, and copied from on every frame.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use gfx::format::{DepthStencil, Srgba8};
+use gfx::handle::{DepthStencilView, RenderTargetView};
+use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
+use gfx::Factory;
+use gfx_device_gl::Resources;
+use piston_window::*;
+
+use crate::core::error::*;
+use crate::core::widget_id::*;
+
+/// An offscreen texture that a cached `Widget` has been drawn into, along with the views that
+/// are used to draw into it.
+struct CachedTexture {
+    texture: G2dTexture,
+    render_target: RenderTargetView<Resources, Srgba8>,
+    depth_stencil: DepthStencilView<Resources, DepthStencil>,
+    pixel_size: [u32; 2],
+    stale: bool,
+}
+
+/// This is the `RenderCache`, which stores an offscreen texture for each `Widget` that is set to
+/// be cached with `Widget::set_cached`.  A cached `Widget` is drawn into its texture when it is
+/// invalidated, and the texture is copied to the screen instead of drawing the `Widget` on
+/// every frame.  Each `WidgetStore` owns a `RenderCache`, and keeps it up to date as `Widget`s
+/// are changed, resized, and removed.
+///
+/// Textures are kept by `Widget` ID, and sized in device pixels, so that they stay sharp on
+/// high density displays.  A texture is created again whenever the `Widget`'s size, or the
+/// number of device pixels per point, changes.
+#[derive(Default)]
+pub struct RenderCache {
+    textures: HashMap<WidgetId, CachedTexture>,
+}
+
+/// Implementation of the `RenderCache`.
+impl RenderCache {
+    /// Creates a new, empty `RenderCache`.
+    pub fn new() -> Self {
+        Self {
+            textures: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of textures that are currently kept in the cache.
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Indicates whether the cache has no textures.
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+
+    /// Indicates whether the texture for a `Widget` has to be drawn before it can be used: it
+    /// does not exist, has the wrong size, or has been marked stale with `mark_stale`.
+    pub fn needs_render(&self, widget_id: WidgetId, pixel_size: [u32; 2]) -> bool {
+        match self.textures.get(&widget_id) {
+            Some(cached) => cached.stale || cached.pixel_size != pixel_size,
+            None => true,
+        }
+    }
+
+    /// Marks the texture for a `Widget` as stale, so that the `Widget` is drawn into it again on
+    /// the next frame, even if the `Widget` has not been invalidated.
+    pub fn mark_stale(&mut self, widget_id: WidgetId) {
+        if let Some(cached) = self.textures.get_mut(&widget_id) {
+            cached.stale = true;
+        }
+    }
+
+    /// Marks all of the textures in the cache as stale.
+    pub fn mark_all_stale(&mut self) {
+        for cached in self.textures.values_mut() {
+            cached.stale = true;
+        }
+    }
+
+    /// Frees the texture for a `Widget`, if it has one.
+    pub fn remove(&mut self, widget_id: WidgetId) {
+        self.textures.remove(&widget_id);
+    }
+
+    /// Frees all of the textures in the cache.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+    }
+
+    /// Retrieves the texture that a `Widget` was last drawn into, if it has one.
+    pub fn get_texture(&self, widget_id: WidgetId) -> Option<&G2dTexture> {
+        self.textures.get(&widget_id).map(|cached| &cached.texture)
+    }
+
+    /// Draws into the texture for a `Widget`, which is created first if it does not exist yet,
+    /// or has the wrong size.  The texture is cleared before `draw_fn` is called, with a
+    /// `Context` whose coordinates are in points, from the top left of the `Widget`.  `size` is
+    /// the size of the `Widget` in points, and `pixel_size` its size in device pixels.
+    pub fn render<F>(
+        &mut self,
+        window: &mut PistonWindow,
+        widget_id: WidgetId,
+        size: &crate::core::point::Size,
+        pixel_size: [u32; 2],
+        draw_fn: F,
+    ) where
+        F: FnOnce(Context, &mut G2d),
+    {
+        let recreate = match self.textures.get(&widget_id) {
+            Some(cached) => cached.pixel_size != pixel_size,
+            None => true,
+        };
+
+        if recreate {
+            match Self::create_texture(&mut window.factory, pixel_size) {
+                Some(cached) => {
+                    self.textures.insert(widget_id, cached);
+                }
+                None => {
+                    report_error(PushrodError::Rendering(format!(
+                        "cannot create a {}x{} render cache texture for widget {}",
+                        pixel_size[0], pixel_size[1], widget_id
+                    )));
+                    self.textures.remove(&widget_id);
+                    return;
+                }
+            }
+        }
+
+        let cached = self.textures.get_mut(&widget_id).unwrap();
+        let viewport = Viewport {
+            rect: [0, 0, pixel_size[0] as i32, pixel_size[1] as i32],
+            draw_size: pixel_size,
+            window_size: [f64::from(size.w), f64::from(size.h)],
+        };
+
+        window.g2d.draw(
+            &mut window.encoder,
+            &cached.render_target,
+            &cached.depth_stencil,
+            viewport,
+            |c, g| {
+                clear([0.0; 4], g);
+                draw_fn(c, g);
+            },
+        );
+
+        cached.stale = false;
+    }
+
+    fn create_texture(factory: &mut GfxFactory, pixel_size: [u32; 2]) -> Option<CachedTexture> {
+        let width = pixel_size[0] as u16;
+        let height = pixel_size[1] as u16;
+        let (surface, view, render_target) =
+            factory.create_render_target::<Srgba8>(width, height).ok()?;
+        let depth_stencil = factory
+            .create_depth_stencil_view_only::<DepthStencil>(width, height)
+            .ok()?;
+        let sampler =
+            factory.create_sampler(SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp));
+
+        Some(CachedTexture {
+            texture: Texture {
+                surface,
+                sampler,
+                view,
+            },
+            render_target,
+            depth_stencil,
+            pixel_size,
+            stale: false,
+        })
+    }
+}
@@ -0,0 +1,110 @@
+// Screenshot
+// Draws widgets into an offscreen framebuffer, and reads the pixels back into an image.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ::image::RgbaImage;
+use gfx::format::{DepthStencil, Formatted, Srgba8};
+use gfx::memory::{Bind, Typed, Usage};
+use gfx::texture::{AaMode, Kind};
+use gfx::traits::FactoryExt;
+use gfx::Factory;
+use piston_window::*;
+
+/// Draws into an offscreen framebuffer, and returns what was drawn as an image with the top
+/// row first.  The framebuffer is cleared to transparent before `draw_fn` is called, with a
+/// `Context` whose coordinates are in points.  `size` is the size of the image in points, and
+/// `pixel_size` its size in device pixels, which is the size of the image that is returned.
+/// Returns `None` if the framebuffer could not be created or read back.
+///
+/// This is used by `Pushrod::capture_screenshot` and `Pushrod::capture_widget`, which draw the
+/// `Widget`s into the framebuffer.
+pub fn capture<F>(
+    window: &mut PistonWindow,
+    size: &crate::core::point::Size,
+    pixel_size: [u32; 2],
+    draw_fn: F,
+) -> Option<RgbaImage>
+where
+    F: FnOnce(Context, &mut G2d),
+{
+    let width = pixel_size[0] as u16;
+    let height = pixel_size[1] as u16;
+
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let texture = window
+        .factory
+        .create_texture::<<Srgba8 as Formatted>::Surface>(
+            Kind::D2(width, height, AaMode::Single),
+            1,
+            Bind::RENDER_TARGET | Bind::TRANSFER_SRC,
+            Usage::Data,
+            Some(<Srgba8 as Formatted>::get_format().1),
+        )
+        .ok()?;
+    let render_target = window
+        .factory
+        .view_texture_as_render_target::<Srgba8>(&texture, 0, None)
+        .ok()?;
+    let depth_stencil = window
+        .factory
+        .create_depth_stencil_view_only::<DepthStencil>(width, height)
+        .ok()?;
+    let download = window
+        .factory
+        .create_download_buffer::<[u8; 4]>(usize::from(width) * usize::from(height))
+        .ok()?;
+    let viewport = Viewport {
+        rect: [0, 0, i32::from(width), i32::from(height)],
+        draw_size: pixel_size,
+        window_size: [f64::from(size.w), f64::from(size.h)],
+    };
+
+    window.window.make_current();
+    window.g2d.draw(
+        &mut window.encoder,
+        &render_target,
+        &depth_stencil,
+        viewport,
+        |c, g| {
+            clear([0.0; 4], g);
+            draw_fn(c, g);
+        },
+    );
+
+    let image_info = texture
+        .get_info()
+        .to_raw_image_info(<Srgba8 as Formatted>::get_format().1, 0);
+
+    window
+        .encoder
+        .copy_texture_to_buffer_raw(texture.raw(), None, image_info, download.raw(), 0)
+        .ok()?;
+    window.encoder.flush(&mut window.device);
+
+    let pixels = window.factory.read_mapping(&download).ok()?;
+    let row_length = usize::from(width);
+    let mut data = Vec::with_capacity(pixels.len() * 4);
+
+    // Framebuffers are stored bottom row first, so the rows are flipped as they are copied.
+    for row in pixels.chunks(row_length).rev() {
+        for pixel in row {
+            data.extend_from_slice(pixel);
+        }
+    }
+
+    RgbaImage::from_raw(u32::from(width), u32::from(height), data)
+}
@@ -0,0 +1,212 @@
+// Spatial Index
+// Finds the objects under a point without looking at every object
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::core::point::{Point, Rect};
+
+/// The width and height, in points, of each cell of a `SpatialIndex`, unless another size is
+/// given.
+pub const DEFAULT_CELL_SIZE: i32 = 64;
+
+/// The most cells that an object is stored in.  Objects that cover more cells than this, such
+/// as the content of a large scrolled area, are kept in a separate list, which is checked on
+/// every query, so that they do not fill the grid.
+const MAX_CELLS_PER_ENTRY: i64 = 256;
+
+/// This is the `SpatialIndex`, a uniform grid of square cells, each of which lists the objects
+/// whose bounds overlap it.  Finding the objects under a point only looks at the objects in the
+/// cell that the point is in, rather than at every object.  Objects are added, moved, and
+/// removed one at a time, by an ID of any type, such as a number or a `WidgetId`.  The
+/// `WidgetStore` keeps a `SpatialIndex` of the children of each `Widget`, to find the `Widget`
+/// under the mouse.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::spatial_index::*;
+/// # fn main() {
+///    let mut index = SpatialIndex::new();
+///
+///    index.insert(1, make_rect(0, 0, 100, 100));
+///    index.insert(2, make_rect(50, 50, 100, 100));
+///    index.insert(3, make_rect(1000, 1000, 10, 10));
+///
+///    assert_eq!(index.query_point(&make_point_i32(75, 75)), vec![1, 2]);
+///    assert_eq!(index.query_point(&make_point_i32(10, 10)), vec![1]);
+///    assert_eq!(index.query_point(&make_point_i32(1005, 1005)), vec![3]);
+///    assert!(index.query_point(&make_point_i32(500, 500)).is_empty());
+///
+///    // Moving an object moves it to the cells of its new bounds.
+///    index.insert(3, make_rect(0, 0, 10, 10));
+///    assert_eq!(index.query_point(&make_point_i32(5, 5)), vec![1, 3]);
+///    assert!(index.query_point(&make_point_i32(1005, 1005)).is_empty());
+///
+///    assert_eq!(index.remove(1), Some(make_rect(0, 0, 100, 100)));
+///    assert_eq!(index.query_point(&make_point_i32(5, 5)), vec![3]);
+///    assert_eq!(index.len(), 2);
+///
+///    // An object that covers a lot of cells is found anywhere in its bounds.
+///    index.insert(4, make_rect(0, 0, 100_000, 100_000));
+///    assert_eq!(index.query_point(&make_point_i32(99_000, 99_000)), vec![4]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<T = i32> {
+    cell_size: i32,
+    cells: HashMap<(i32, i32), Vec<T>>,
+    large: Vec<T>,
+    entries: HashMap<T, Rect>,
+}
+
+/// Implementation of the `SpatialIndex`.
+impl<T: Copy + Eq + Hash + Ord> SpatialIndex<T> {
+    /// Creates a new, empty `SpatialIndex` with cells of `DEFAULT_CELL_SIZE`.
+    pub fn new() -> Self {
+        Self::with_cell_size(DEFAULT_CELL_SIZE)
+    }
+
+    /// Creates a new, empty `SpatialIndex` with cells of `cell_size` points, which is at least 1.
+    pub fn with_cell_size(cell_size: i32) -> Self {
+        Self {
+            cell_size: cell_size.max(1),
+            cells: HashMap::new(),
+            large: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Adds the object `id` with `bounds`, or moves it to `bounds` if it was already added.
+    /// Objects with an empty size are kept, but contain no points.
+    pub fn insert(&mut self, id: T, bounds: Rect) {
+        if self.entries.get(&id) == Some(&bounds) {
+            return;
+        }
+
+        self.remove(id);
+
+        if !bounds.is_empty() {
+            match self.get_cell_range(&bounds) {
+                Some((columns, rows)) => {
+                    for row in rows.0..=rows.1 {
+                        for column in columns.0..=columns.1 {
+                            self.cells.entry((column, row)).or_default().push(id);
+                        }
+                    }
+                }
+                None => self.large.push(id),
+            }
+        }
+
+        self.entries.insert(id, bounds);
+    }
+
+    /// Removes the object `id`, returning the bounds that it had, or `None` if it was not added.
+    pub fn remove(&mut self, id: T) -> Option<Rect> {
+        let bounds = self.entries.remove(&id)?;
+
+        if !bounds.is_empty() {
+            match self.get_cell_range(&bounds) {
+                Some((columns, rows)) => {
+                    for row in rows.0..=rows.1 {
+                        for column in columns.0..=columns.1 {
+                            let cell = (column, row);
+
+                            if let Some(ids) = self.cells.get_mut(&cell) {
+                                ids.retain(|x| *x != id);
+
+                                if ids.is_empty() {
+                                    self.cells.remove(&cell);
+                                }
+                            }
+                        }
+                    }
+                }
+                None => self.large.retain(|x| *x != id),
+            }
+        }
+
+        Some(bounds)
+    }
+
+    /// Retrieves the bounds of the object `id`, or `None` if it was not added.
+    pub fn get_bounds(&self, id: T) -> Option<&Rect> {
+        self.entries.get(&id)
+    }
+
+    /// Returns the IDs of the objects whose bounds contain `point`, in ascending order.
+    pub fn query_point(&self, point: &Point) -> Vec<T> {
+        let cell = (
+            point.x.div_euclid(self.cell_size),
+            point.y.div_euclid(self.cell_size),
+        );
+        let mut ids: Vec<T> = self
+            .cells
+            .get(&cell)
+            .into_iter()
+            .flatten()
+            .chain(self.large.iter())
+            .copied()
+            .filter(|id| self.entries[id].contains(point))
+            .collect();
+
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the number of objects in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether there are no objects in the index.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all of the objects.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.large.clear();
+        self.entries.clear();
+    }
+
+    /// Returns the first and last column, and the first and last row, of the cells that
+    /// `bounds` overlaps, or `None` if it overlaps more than `MAX_CELLS_PER_ENTRY` cells.
+    fn get_cell_range(&self, bounds: &Rect) -> Option<((i32, i32), (i32, i32))> {
+        let columns = (
+            bounds.origin.x.div_euclid(self.cell_size),
+            (bounds.get_right() - 1).div_euclid(self.cell_size),
+        );
+        let rows = (
+            bounds.origin.y.div_euclid(self.cell_size),
+            (bounds.get_bottom() - 1).div_euclid(self.cell_size),
+        );
+        let count = (i64::from(columns.1 - columns.0) + 1) * (i64::from(rows.1 - rows.0) + 1);
+
+        if count > MAX_CELLS_PER_ENTRY {
+            None
+        } else {
+            Some((columns, rows))
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash + Ord> Default for SpatialIndex<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
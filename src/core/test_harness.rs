@@ -0,0 +1,309 @@
+// Test Harness
+// Runs widgets without a window, for tests that send events and check what happened
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::{RefCell, RefMut};
+use std::rc::Rc;
+
+use piston_window::*;
+
+use crate::core::callbacks::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::core::main::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::widget::*;
+
+/// The event handler of a `TestHarness`, which keeps every callback event that is sent to the
+/// application.
+struct RecordingEventHandler {
+    events: Vec<CallbackEvent>,
+}
+
+impl PushrodCallbackEvents for RecordingEventHandler {
+    fn handle_event(&mut self, event: CallbackEvent, _widget_store: &mut WidgetStore) {
+        self.events.push(event);
+    }
+}
+
+/// This is the `TestHarness`, which runs `Widget`s in a headless `Pushrod`, without a window or
+/// an OpenGL context, so that they can be tested on machines without a display.  Mouse and
+/// keyboard events are sent to it at window coordinates, and are handled by the same code that
+/// handles the events of a window, so `Widget`s are hovered, focused, clicked, and dragged just
+/// as they would be.  Time only moves on when the harness is advanced, so timers, animations,
+/// double clicks, and tooltips are tested without waiting.  Frames are drawn into a
+/// `DrawRecorder`, to check what was drawn, and where.
+///
+/// `Widget`s that load fonts or images need a window, so they cannot be created for the harness.
+///
+/// Example:
+/// ```
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # use piston_window::*;
+/// # use pushrod::core::callbacks::*;
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::test_harness::*;
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::slider_widget::*;
+/// # use pushrod::widget::timer_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut harness = TestHarness::new(400, 300);
+///    let value = Rc::new(Cell::new(0.0));
+///    let fired = Rc::new(Cell::new(0));
+///    let mut slider = SliderWidget::new();
+///    let mut panel = BoxWidget::new();
+///    let mut timer = TimerWidget::with_clock(harness.get_clock());
+///
+///    slider.set_origin(100, 20);
+///    slider.set_size(200, 20);
+///    slider.set_focusable(true);
+///
+///    let slider_value = value.clone();
+///
+///    slider.on_value_changed(Box::new(move |value| slider_value.set(value)));
+///
+///    let slider_id = harness.add_widget("Slider", Box::new(slider));
+///
+///    panel.set_origin(20, 100);
+///    panel.set_size(50, 50);
+///    panel.set_color([0.0, 0.0, 1.0, 1.0]);
+///    harness.add_widget("Panel", Box::new(panel));
+///
+///    let timer_fired = fired.clone();
+///
+///    timer.set_timeout(500);
+///    timer.on_timeout(Box::new(move |_| timer_fired.set(timer_fired.get() + 1)));
+///    harness.add_widget("Timer", Box::new(timer));
+///
+///    // Clicking the middle of the slider moves it to the middle, and focuses it.
+///    harness.click(200, 30);
+///    assert!((value.get() - 0.5).abs() < 0.05);
+///    assert_eq!(harness.get_widget_store().get_focused_widget(), slider_id);
+///
+///    // The focused slider takes the keyboard.
+///    harness.type_key(Key::End);
+///    assert_eq!(value.get(), 1.0);
+///
+///    // Time only moves on when the harness is advanced.
+///    harness.advance(400);
+///    assert_eq!(fired.get(), 0);
+///    harness.advance(100);
+///    assert_eq!(fired.get(), 1);
+///
+///    // Each frame is recorded, rather than drawn.
+///    let frame = harness.draw();
+///
+///    assert!(frame
+///        .get_commands_at(&make_point_i32(40, 120))
+///        .iter()
+///        .any(|command| command.get_color() == [0.0, 0.0, 1.0, 1.0]));
+///
+///    // The events that reached the application are kept, from the oldest.
+///    assert!(harness.take_events().iter().any(|event| match event {
+///        CallbackEvent::MouseEntered { widget_id } => *widget_id == slider_id,
+///        _ => false,
+///    }));
+/// # }
+/// ```
+pub struct TestHarness {
+    pushrod: Pushrod,
+    clock: ManualClock,
+    event_handler: RecordingEventHandler,
+    errors: Rc<RefCell<Vec<PushrodError>>>,
+}
+
+/// Implementation of the `TestHarness`.
+impl TestHarness {
+    /// Creates a new `TestHarness`, with a headless `Pushrod` whose window is `width` by `height`
+    /// points.  Errors that are reported while the harness runs are kept, rather than written
+    /// to standard error.
+    pub fn new(width: i32, height: i32) -> Self {
+        let clock = ManualClock::new();
+        let mut pushrod = Pushrod::headless(width, height, clock.clone());
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let reported_errors = errors.clone();
+
+        pushrod.on_error(Box::new(move |error, _widget_store| {
+            reported_errors.borrow_mut().push(error.clone())
+        }));
+
+        Self {
+            pushrod,
+            clock,
+            event_handler: RecordingEventHandler { events: Vec::new() },
+            errors,
+        }
+    }
+
+    /// Retrieves the headless `Pushrod` that the harness runs.
+    pub fn get_pushrod(&mut self) -> &mut Pushrod {
+        &mut self.pushrod
+    }
+
+    /// Borrows the `WidgetStore` of the harness, to check the state of its `Widget`s.  The
+    /// borrow has to be dropped before the next event is sent.
+    pub fn get_widget_store(&self) -> RefMut<'_, WidgetStore> {
+        self.pushrod.widget_store.borrow_mut()
+    }
+
+    /// Returns a clock for time-based `Widget`s, such as a `TimerWidget` created with
+    /// `TimerWidget::with_clock`, which keeps the time of the harness.
+    pub fn get_clock(&self) -> Box<dyn Clock> {
+        Box::new(self.clock.clone())
+    }
+
+    /// Adds a `Widget` to the top level `Widget`, returning its ID.
+    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> WidgetId {
+        self.pushrod.add_widget(name, widget)
+    }
+
+    /// Adds a `Widget` to the `Widget` with the ID `parent_id`, returning its ID.
+    pub fn add_widget_to_parent(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+        parent_id: WidgetId,
+    ) -> WidgetId {
+        self.pushrod.add_widget_to_parent(name, widget, parent_id)
+    }
+
+    /// Handles `event` as though it came from the window.
+    pub fn send_event(&mut self, event: Event) {
+        self.pushrod
+            .handle_window_event(&event, &mut self.event_handler);
+    }
+
+    /// Moves the mouse to `x` and `y`, in points from the top left of the window.
+    pub fn mouse_move(&mut self, x: i32, y: i32) {
+        self.send_event(Event::Input(Input::Move(Motion::MouseCursor(
+            f64::from(x),
+            f64::from(y),
+        ))));
+    }
+
+    /// Presses a mouse button where the mouse is.
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.send_button(Button::Mouse(button), ButtonState::Press);
+    }
+
+    /// Releases a mouse button where the mouse is.
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.send_button(Button::Mouse(button), ButtonState::Release);
+    }
+
+    /// Moves the mouse to `x` and `y`, and clicks the left mouse button there.
+    pub fn click(&mut self, x: i32, y: i32) {
+        self.mouse_move(x, y);
+        self.mouse_down(MouseButton::Left);
+        self.mouse_up(MouseButton::Left);
+    }
+
+    /// Presses the left mouse button at `from`, moves the mouse to `to`, and releases it there.
+    pub fn drag(&mut self, from: (i32, i32), to: (i32, i32)) {
+        self.mouse_move(from.0, from.1);
+        self.mouse_down(MouseButton::Left);
+        self.mouse_move(to.0, to.1);
+        self.mouse_up(MouseButton::Left);
+    }
+
+    /// Scrolls the mouse wheel by `dx` and `dy` ticks where the mouse is.
+    pub fn scroll(&mut self, dx: f64, dy: f64) {
+        self.send_event(Event::Input(Input::Move(Motion::MouseScroll(dx, dy))));
+    }
+
+    /// Presses a key.
+    pub fn key_down(&mut self, key: Key) {
+        self.send_button(Button::Keyboard(key), ButtonState::Press);
+    }
+
+    /// Releases a key.
+    pub fn key_up(&mut self, key: Key) {
+        self.send_button(Button::Keyboard(key), ButtonState::Release);
+    }
+
+    /// Presses and releases a key.
+    pub fn type_key(&mut self, key: Key) {
+        self.key_down(key);
+        self.key_up(key);
+    }
+
+    /// Types `text`, as the text that a keyboard or input method enters.
+    pub fn type_text(&mut self, text: &str) {
+        self.send_event(Event::Input(Input::Text(text.to_string())));
+    }
+
+    /// Resizes the window to `width` by `height` points.  The new size is applied on the next
+    /// update or frame.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.send_event(Event::Input(Input::Resize(
+            f64::from(width),
+            f64::from(height),
+        )));
+    }
+
+    /// Moves time on by `ms` milliseconds, and sends an update, which ticks timers and
+    /// animations, and shows tooltips that have waited long enough.
+    pub fn advance(&mut self, ms: u64) {
+        self.clock.advance(ms);
+        self.send_event(Event::Loop(Loop::Update(UpdateArgs {
+            dt: ms as f64 / 1_000.0,
+        })));
+    }
+
+    /// Draws a frame, and returns the recorder that it was drawn into.  Every visible `Widget` is
+    /// drawn, whether or not it has been invalidated, so the recorder holds the whole window.
+    pub fn draw(&mut self) -> &DrawRecorder {
+        let size = self
+            .get_widget_store()
+            .get_widget_for_id(WidgetId::ROOT)
+            .borrow_mut()
+            .get_size();
+
+        self.get_widget_store().invalidate_all_widgets();
+        self.send_event(Event::Loop(Loop::Render(RenderArgs {
+            ext_dt: 0.0,
+            width: f64::from(size.w),
+            height: f64::from(size.h),
+            draw_width: size.w as u32,
+            draw_height: size.h as u32,
+        })));
+
+        self.pushrod.get_draw_recorder().unwrap()
+    }
+
+    /// Returns the callback events that have reached the application since the last call, from
+    /// the oldest.  `Widget`s handle most events themselves, so these are mostly the events that
+    /// no `Widget` handled, and the events that `Widget`s send back.
+    pub fn take_events(&mut self) -> Vec<CallbackEvent> {
+        std::mem::take(&mut self.event_handler.events)
+    }
+
+    /// Returns the errors that have been reported since the last call, from the oldest, such as
+    /// text that could not be drawn.  See `Pushrod::on_error`.
+    pub fn take_errors(&mut self) -> Vec<PushrodError> {
+        std::mem::take(&mut *self.errors.borrow_mut())
+    }
+
+    fn send_button(&mut self, button: Button, state: ButtonState) {
+        self.send_event(Event::Input(Input::Button(ButtonArgs {
+            state,
+            button,
+            scancode: None,
+        })));
+    }
+}
@@ -0,0 +1,302 @@
+// Touch Tracking
+// Tracks each finger on a touch screen, telling taps from drags, and flinging scrolled content
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::core::clock::*;
+use crate::core::widget_id::*;
+
+/// The distance, in points, that a touch can move from where it started and still be a tap.
+/// A touch that moves further is a drag, which pans the scrollable `Widget` under it.
+pub const DEFAULT_TOUCH_SLOP: f64 = 8.0;
+
+/// The number of milliseconds that it takes a fling to slow down to about a third of its speed.
+pub const FLING_TIME_CONSTANT_MS: f64 = 325.0;
+
+/// The slowest speed, in points per millisecond, at which a fling keeps moving.
+const MIN_FLING_SPEED: f64 = 0.02;
+
+/// The number of milliseconds that a touch can rest before it is lifted, and still fling.
+const FLING_IDLE_MS: u64 = 100;
+
+/// How much of the speed of a touch is taken from its latest movement, rather than from its
+/// earlier movements, which smooths out uneven touch events.
+const VELOCITY_SMOOTHING: f64 = 0.8;
+
+/// This `enum` specifies how a touch is delivered to the `Widget`s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchMode {
+    /// The touch is delivered as touch events to the `Widget` that it started on, which handles
+    /// multi-touch.  See `Widget::set_multi_touch`.
+    MultiTouch,
+
+    /// The touch is delivered as the left mouse button, pressed where the touch started, moved
+    /// with the touch, and released where it ended.  Only one touch is delivered this way at a
+    /// time.
+    Mouse,
+
+    /// The touch was dragged past the slop, and pans the scrollable `Widget` with this ID.
+    Panning(WidgetId),
+}
+
+/// A touch that is being tracked by a `TouchTracker`.  Positions are in points, from the top
+/// left of the window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackedTouch {
+    /// The ID of the `Widget` that the touch started on.
+    pub widget_id: WidgetId,
+
+    /// How the touch is delivered.
+    pub mode: TouchMode,
+
+    /// Where the touch started.
+    pub start: [f64; 2],
+
+    /// Where the touch is now.
+    pub position: [f64; 2],
+
+    /// The speed of the touch along each axis, in points per millisecond.
+    pub velocity: [f64; 2],
+
+    moved_at: Instant,
+}
+
+/// This is the `TouchTracker`, which keeps track of every touch on a touch screen by its ID,
+/// so that simultaneous touches on different `Widget`s are kept apart.  It tells a tap from a
+/// drag by whether the touch stays within the slop of where it started, and keeps the speed of
+/// each touch, so that a scrollable `Widget` that is dragged can be flung when the touch is
+/// lifted, slowing down on its own until it stops.  `Pushrod` uses a `TouchTracker` to deliver
+/// touch events.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::clock::*;
+/// # use pushrod::core::touch::*;
+/// # use pushrod::core::widget_id::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let mut tracker = TouchTracker::with_clock(Box::new(clock.clone()));
+///
+///    // A touch that stays within the slop is a tap.
+///    tracker.start_touch(1, WidgetId::from(5), TouchMode::Mouse, [100.0, 100.0]);
+///    tracker.move_touch(1, [104.0, 103.0]);
+///    assert!(tracker.has_mouse_touch());
+///    assert!(!tracker.is_past_slop(1));
+///
+///    // Another finger is tracked separately.
+///    tracker.start_touch(2, WidgetId::from(8), TouchMode::MultiTouch, [300.0, 100.0]);
+///    assert_eq!(tracker.get_touch(2).unwrap().widget_id, WidgetId::from(8));
+///    assert_eq!(tracker.end_touch(2).unwrap().mode, TouchMode::MultiTouch);
+///
+///    // Dragging the first touch quickly past the slop, and lifting it, flings its widget.
+///    clock.advance(10);
+///    assert_eq!(tracker.move_touch(1, [104.0, 83.0]), Some([0.0, -20.0]));
+///    assert!(tracker.is_past_slop(1));
+///
+///    tracker.set_touch_mode(1, TouchMode::Panning(WidgetId::from(3)));
+///
+///    let touch = tracker.end_touch(1).unwrap();
+///
+///    assert!(touch.velocity[1] < -1.0);
+///    tracker.start_fling(WidgetId::from(3), touch.velocity);
+///
+///    // The fling moves the content on in the same direction, slowing down as it goes.
+///    let (widget_id, delta) = tracker.update_fling(16).unwrap();
+///
+///    assert_eq!(widget_id, WidgetId::from(3));
+///    assert!(delta[1] < -15.0 && delta[1] > touch.velocity[1] * 16.0);
+///    assert!(tracker.update_fling(5000).is_some());
+///    assert!(tracker.update_fling(16).is_none());
+/// # }
+/// ```
+pub struct TouchTracker {
+    touches: HashMap<i64, TrackedTouch>,
+    slop: f64,
+    fling: Option<(WidgetId, [f64; 2])>,
+    clock: Box<dyn Clock>,
+}
+
+/// Implementation of the `TouchTracker`.
+impl TouchTracker {
+    /// Creates a new `TouchTracker` without any touches, which times touches with the system
+    /// clock.
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Creates a new `TouchTracker` without any touches, which times touches with `clock`.
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            touches: HashMap::new(),
+            slop: DEFAULT_TOUCH_SLOP,
+            fling: None,
+            clock,
+        }
+    }
+
+    /// Sets the distance, in points, that a touch can move and still be a tap.
+    pub fn set_slop(&mut self, slop: f64) {
+        self.slop = slop;
+    }
+
+    /// Retrieves the distance that a touch can move and still be a tap.  Defaults to
+    /// `DEFAULT_TOUCH_SLOP`.
+    pub fn get_slop(&self) -> f64 {
+        self.slop
+    }
+
+    /// Starts tracking the touch `touch_id`, which started at `position` on the `Widget` with
+    /// the ID `widget_id`.  Any fling is stopped, as a touch catches the content that it lands
+    /// on.
+    pub fn start_touch(
+        &mut self,
+        touch_id: i64,
+        widget_id: WidgetId,
+        mode: TouchMode,
+        position: [f64; 2],
+    ) {
+        self.fling = None;
+        self.touches.insert(
+            touch_id,
+            TrackedTouch {
+                widget_id,
+                mode,
+                start: position,
+                position,
+                velocity: [0.0, 0.0],
+                moved_at: self.clock.now(),
+            },
+        );
+    }
+
+    /// Retrieves the touch `touch_id`, if it is being tracked.
+    pub fn get_touch(&self, touch_id: i64) -> Option<&TrackedTouch> {
+        self.touches.get(&touch_id)
+    }
+
+    /// Indicates whether a touch is being delivered as the mouse, or was, and is now panning.
+    pub fn has_mouse_touch(&self) -> bool {
+        self.touches
+            .values()
+            .any(|touch| touch.mode != TouchMode::MultiTouch)
+    }
+
+    /// Changes how the touch `touch_id` is delivered.
+    pub fn set_touch_mode(&mut self, touch_id: i64, mode: TouchMode) {
+        if let Some(touch) = self.touches.get_mut(&touch_id) {
+            touch.mode = mode;
+        }
+    }
+
+    /// Moves the touch `touch_id` to `position`, updating its speed, and returns the distance
+    /// that it moved, or `None` if the touch is not being tracked.
+    pub fn move_touch(&mut self, touch_id: i64, position: [f64; 2]) -> Option<[f64; 2]> {
+        let now = self.clock.now();
+        let touch = self.touches.get_mut(&touch_id)?;
+        let delta = [
+            position[0] - touch.position[0],
+            position[1] - touch.position[1],
+        ];
+        let elapsed_ms = now
+            .saturating_duration_since(touch.moved_at)
+            .as_millis()
+            .max(1) as f64;
+
+        for (velocity, distance) in touch.velocity.iter_mut().zip(delta.iter()) {
+            *velocity =
+                VELOCITY_SMOOTHING * distance / elapsed_ms + (1.0 - VELOCITY_SMOOTHING) * *velocity;
+        }
+
+        touch.position = position;
+        touch.moved_at = now;
+        Some(delta)
+    }
+
+    /// Indicates whether the touch `touch_id` has moved further than the slop from where it
+    /// started, which makes it a drag rather than a tap.
+    pub fn is_past_slop(&self, touch_id: i64) -> bool {
+        self.touches.get(&touch_id).is_some_and(|touch| {
+            let dx = touch.position[0] - touch.start[0];
+            let dy = touch.position[1] - touch.start[1];
+
+            dx.hypot(dy) > self.slop
+        })
+    }
+
+    /// Stops tracking the touch `touch_id`, returning it as it was when it was lifted.  A touch
+    /// that rested before it was lifted has no speed.
+    pub fn end_touch(&mut self, touch_id: i64) -> Option<TrackedTouch> {
+        let mut touch = self.touches.remove(&touch_id)?;
+
+        if self.clock.elapsed_ms(touch.moved_at) > FLING_IDLE_MS {
+            touch.velocity = [0.0, 0.0];
+        }
+
+        Some(touch)
+    }
+
+    /// Stops tracking all touches, returning them, such as when the window loses focus.
+    pub fn cancel_touches(&mut self) -> Vec<(i64, TrackedTouch)> {
+        self.fling = None;
+        self.touches.drain().collect()
+    }
+
+    /// Flings the scrollable `Widget` with the ID `widget_id` at `velocity`, in points per
+    /// millisecond, in the direction that its content is dragged.  A fling that is too slow to
+    /// move is not started.
+    pub fn start_fling(&mut self, widget_id: WidgetId, velocity: [f64; 2]) {
+        if velocity[0].hypot(velocity[1]) >= MIN_FLING_SPEED {
+            self.fling = Some((widget_id, velocity));
+        }
+    }
+
+    /// Stops the fling, if there is one.
+    pub fn stop_fling(&mut self) {
+        self.fling = None;
+    }
+
+    /// Indicates whether a `Widget` is being flung.
+    pub fn is_flinging(&self) -> bool {
+        self.fling.is_some()
+    }
+
+    /// Moves the fling on by `delta_ms` milliseconds, slowing it down, and returns the ID of the
+    /// `Widget` that is being flung, with the distance that its content is dragged.  Returns
+    /// `None` once the fling has stopped.
+    pub fn update_fling(&mut self, delta_ms: u64) -> Option<(WidgetId, [f64; 2])> {
+        let (widget_id, velocity) = self.fling?;
+        let decay = (-(delta_ms as f64) / FLING_TIME_CONSTANT_MS).exp();
+        let distance = [
+            velocity[0] * FLING_TIME_CONSTANT_MS * (1.0 - decay),
+            velocity[1] * FLING_TIME_CONSTANT_MS * (1.0 - decay),
+        ];
+        let velocity = [velocity[0] * decay, velocity[1] * decay];
+
+        self.fling = if velocity[0].hypot(velocity[1]) >= MIN_FLING_SPEED {
+            Some((widget_id, velocity))
+        } else {
+            None
+        };
+
+        Some((widget_id, distance))
+    }
+}
+
+impl Default for TouchTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,100 @@
+// Widget ID
+// Identifies a widget in a widget store
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// This is the `WidgetId`, which identifies a `Widget` in a `WidgetStore`.  IDs are given out by
+/// the store when `Widget`s are added to it, starting from the top level `Widget`, which is
+/// `WidgetId::ROOT`.  The ID of a `Widget` never changes, but once the `Widget` is removed, its ID
+/// is given to a `Widget` that is added later, so the IDs of removed `Widget`s should not be kept.
+/// `WidgetId::INVALID` refers to no `Widget` at all, such as the focused `Widget` while nothing has
+/// the focus.
+///
+/// IDs can be converted to and from `i32`, for code that still keeps them as numbers.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::widget_id::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let widget_id = widget_store.add_widget("Canvas", Box::new(CanvasWidget::new()));
+///
+///    assert!(widget_id.is_valid());
+///    assert_eq!(widget_store.get_parent_of(widget_id), WidgetId::ROOT);
+///    assert_eq!(widget_store.get_focused_widget(), WidgetId::INVALID);
+///    assert_eq!(i32::from(widget_id), 1);
+///    assert_eq!(WidgetId::from(1), widget_id);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct WidgetId(i32);
+
+/// Implementation of the `WidgetId`.
+impl WidgetId {
+    /// The ID of the top level `Widget` of a `WidgetStore`, which other `Widget`s are added to.
+    pub const ROOT: WidgetId = WidgetId(0);
+
+    /// An ID that refers to no `Widget`.
+    pub const INVALID: WidgetId = WidgetId(-1);
+
+    /// Indicates whether the ID can refer to a `Widget`, as every ID but `INVALID` can.  Whether
+    /// a `Widget` with this ID is in a store is checked with `WidgetStore::is_widget_id_valid`.
+    pub fn is_valid(self) -> bool {
+        self.0 >= 0
+    }
+
+    /// Indicates whether this is the ID of the top level `Widget`.
+    pub fn is_root(self) -> bool {
+        self == Self::ROOT
+    }
+
+    /// Returns the ID as an index into the `Widget`s of a `WidgetStore`.
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Returns the ID of the `Widget` at an index into the `Widget`s of a `WidgetStore`.
+    pub(crate) fn from_index(index: usize) -> Self {
+        WidgetId(index as i32)
+    }
+}
+
+impl Default for WidgetId {
+    fn default() -> Self {
+        Self::INVALID
+    }
+}
+
+impl From<i32> for WidgetId {
+    fn from(id: i32) -> Self {
+        WidgetId(id)
+    }
+}
+
+impl From<WidgetId> for i32 {
+    fn from(id: WidgetId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for WidgetId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
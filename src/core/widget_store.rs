@@ -15,12 +15,46 @@
 
 use piston_window::*;
 
+use std::any::Any;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
 
-use crate::core::callbacks::CallbackEvent;
+use crate::core::accessibility::*;
+use crate::core::callbacks::{
+    CallbackEvent, DeferredCallback, DragPayload, EventResult, ModalClosedCallback,
+    RadioSelectionCallback,
+};
+use crate::core::debug_overlay::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::core::gamepad::{find_focus_in_direction, FocusDirection};
 use crate::core::point::*;
+use crate::core::render_cache::*;
+use crate::core::spatial_index::SpatialIndex;
+use crate::core::widget_id::*;
+use crate::event::event::*;
+use crate::event::event_bus::*;
+use crate::widget::config::{
+    get_geometry_generation, resolve_dimensions, CheckState, CheckedState, Collapsed,
+    RadioGroupId, SelectedTab, TabTitles,
+};
+use crate::widget::dialog_widget::{DialogCloser, DialogWidget, ModalScrimWidget};
+use crate::widget::font_cache::SharedGlyphs;
+use crate::widget::layout_widget::{make_grid_cell, GridCell, LayoutChild};
+use crate::widget::shape::{
+    point_in_rounded_rect, stroke_border, stroke_rounded_rect, BorderStyle, Shadow,
+};
+use crate::widget::theme::*;
 use crate::widget::widget::*;
 
+/// The space, in points, between a focused `Widget` and the focus indicator around it.
+const FOCUS_INDICATOR_GAP: i32 = 2;
+
+/// The thickness, in points, of the focus indicator.
+const FOCUS_INDICATOR_THICKNESS: i32 = 2;
+
 /// This is a container object, used for storing the `Widget` trait object, and the parent
 /// relationship for the added `Widget`.  Only the `widget` is public.  `Widget` objects do not
 /// need to have a child relationship, only parent objects are traversed.  A parent object of 0, or
@@ -34,10 +68,91 @@ pub struct WidgetContainer {
     widget_name: String,
 
     /// This `Widget`'s assigned ID.  These IDs are auto-assigned.
-    widget_id: i32,
+    widget_id: WidgetId,
 
     /// The parent ID.
-    parent_id: i32,
+    parent_id: WidgetId,
+
+    /// The IDs of the children of the `Widget`, in the order in which they were added.
+    children: Vec<WidgetId>,
+
+    /// Indicates that the `Widget` has been removed from the store.  Removed `Widget`s are dropped,
+    /// and replaced by a hidden placeholder, so that the IDs of other `Widget`s do not change.  The
+    /// slot of the placeholder is given to the next `Widget` that is added.
+    removed: bool,
+
+    /// The area of the window the `Widget` could draw in when it was last drawn, or `None` if it
+    /// is not currently on the screen.  When the `Widget` moves, resizes, or
+    /// is hidden, this area is repainted by the `Widget`s underneath.
+    drawn_bounds: Option<Rect>,
+
+    /// The stacking order of the `Widget` among its siblings.  `Widget`s with a higher z-order
+    /// are drawn above, and receive mouse events before, those with a lower z-order.
+    z_order: i32,
+}
+
+/// Errors that can be returned by the `WidgetStore`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WidgetStoreError {
+    /// A `Widget` with this name already exists in the store.
+    DuplicateName(String),
+
+    /// A cell of a `GridLayoutWidget` that a `Widget` was placed in is already taken by another
+    /// `Widget`.
+    CellOccupied { row: u32, column: u32 },
+}
+
+impl fmt::Display for WidgetStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WidgetStoreError::DuplicateName(name) => {
+                write!(f, "A widget named '{}' already exists", name)
+            }
+            WidgetStoreError::CellOccupied { row, column } => write!(
+                f,
+                "The grid cell at row {}, column {} is already taken",
+                row, column
+            ),
+        }
+    }
+}
+
+impl Error for WidgetStoreError {}
+
+/// The state of a drag-and-drop operation that is in progress.
+struct DragState {
+    /// The ID of the `Widget` that the drag was started from.
+    source_id: WidgetId,
+
+    /// The data being dragged.
+    payload: DragPayload,
+
+    /// The ID of the `Widget` that the mouse is over, or `WidgetId::INVALID` if the mouse has not
+    /// moved yet.
+    target_id: WidgetId,
+
+    /// The ID of the `Widget` that is drawn under the mouse pointer during the drag, or
+    /// `WidgetId::INVALID`.
+    ghost_id: WidgetId,
+}
+
+/// A modal dialog that is open.
+struct ModalState {
+    /// The ID of the `DialogWidget`.
+    dialog_id: WidgetId,
+
+    /// The ID of the `ModalScrimWidget` behind the dialog.
+    scrim_id: WidgetId,
+
+    /// The ID of the `Widget` that had the focus before the dialog was shown, or
+    /// `WidgetId::INVALID`.
+    previous_focus_id: WidgetId,
+
+    /// The handle that the dialog is asked to close with.
+    closer: DialogCloser,
+
+    /// The closure that is called with the result of the dialog once it has been closed.
+    on_closed: Option<ModalClosedCallback>,
 }
 
 /// This is the `WidgetStore`, which is used to store `Widget` objects for a `Pushrod`
@@ -45,6 +160,92 @@ pub struct WidgetContainer {
 pub struct WidgetStore {
     /// A vector list of `WidgetContainer` objects.
     pub widgets: Vec<WidgetContainer>,
+
+    /// The indexes of the slots in `widgets` that hold the placeholders of removed `Widget`s,
+    /// which are reused for the next `Widget`s that are added.
+    free_slots: Vec<usize>,
+
+    /// The areas of the window that removed `Widget`s were drawn in, which are repainted by the
+    /// `Widget`s underneath in the next frame.
+    removed_bounds: Vec<Rect>,
+
+    /// The ID of the `Widget` that has keyboard focus, or `WidgetId::INVALID` if no `Widget` has
+    /// focus.
+    focused_widget_id: WidgetId,
+
+    /// `Widget`s and the areas of them that were painted in the last frame.  These are painted
+    /// again in the next frame, so that both buffers of a double-buffered window are up to date.
+    repaint_next_frame: Vec<(WidgetId, Rect)>,
+
+    /// The number of `Widget`s that were drawn in the last frame.
+    draw_count: u32,
+
+    /// IDs of `Widget`s that are waiting to be removed.
+    pending_removals: Vec<WidgetId>,
+
+    /// Events generated by the store, along with the ID of the `Widget` they are addressed to,
+    /// waiting to be dispatched by the `Pushrod` run loop.
+    pending_events: Vec<(WidgetId, CallbackEvent)>,
+
+    /// The drag-and-drop operation that is in progress, if any.
+    drag: Option<DragState>,
+
+    /// The ID of the `Widget` that holds the ghost of every drag, which is hidden between drags,
+    /// or `WidgetId::INVALID` if no drag has had a ghost yet.
+    drag_ghost_id: WidgetId,
+
+    /// The ID of the `Widget` that has captured the mouse, or `WidgetId::INVALID` if the mouse is
+    /// not captured.
+    captured_widget_id: WidgetId,
+
+    /// The `Theme` that is used by the `Widget`s in this store.
+    theme: Theme,
+
+    /// Indicates that the application has asked for the window to be closed.
+    close_requested: bool,
+
+    /// Offscreen textures of the `Widget`s that are set to be cached.
+    render_cache: RenderCache,
+
+    /// The number of device pixels per point of the window.
+    scale_factor: f64,
+
+    /// Events posted by `Widget`s and the application, and their subscribers.
+    event_bus: EventBus,
+
+    /// The ID of the last `Widget` that was selected in each radio group, or `WidgetId::INVALID` if
+    /// it was removed.
+    radio_selections: HashMap<String, WidgetId>,
+
+    /// Closures that are called when the selection of a radio group changes, by group name.
+    radio_callbacks: HashMap<String, RadioSelectionCallback>,
+
+    /// The IDs of the open popups, in the order in which they were opened.
+    popups: Vec<WidgetId>,
+
+    /// The open modal dialogs, in the order in which they were shown.
+    modals: Vec<ModalState>,
+
+    /// The font in which ready-made dialogs, such as `MessageBox`es, are drawn.
+    dialog_font: Option<SharedGlyphs>,
+
+    /// Indicates that the focus indicator is drawn around the focused `Widget`.
+    focus_indicator_visible: bool,
+
+    /// Draws the bounds, IDs, and names of the `Widget`s over them, when it is enabled.
+    debug_overlay: DebugOverlay,
+
+    /// Remembers the accessible state of the `Widget`s, while changes to it are posted as
+    /// events.
+    accessibility: Option<AccessibilityTracker>,
+
+    /// The bounds of the children of each `Widget`, relative to it, by the ID of the `Widget`,
+    /// for finding the `Widget` under a point.
+    hit_index: HashMap<WidgetId, SpatialIndex<WidgetId>>,
+
+    /// The geometry generation that the `hit_index` was last brought up to date with.  See
+    /// `get_geometry_generation`.
+    hit_index_generation: Option<u64>,
 }
 
 /// Implementation of the `WidgetStore`.
@@ -55,15 +256,286 @@ impl WidgetStore {
         let mut base_widget = CanvasWidget::new();
 
         base_widget.set_size(800, 600);
+        base_widget.config().take_changed_keys();
         widgets_list.push(WidgetContainer {
             widget: RefCell::new(Box::new(base_widget)),
             widget_name: String::from("_WidgetStoreBase"),
-            widget_id: 0,
-            parent_id: 0,
+            widget_id: WidgetId::ROOT,
+            parent_id: WidgetId::ROOT,
+            children: vec![WidgetId::ROOT],
+            removed: false,
+            drawn_bounds: None,
+            z_order: 0,
         });
 
         Self {
             widgets: widgets_list,
+            free_slots: Vec::new(),
+            removed_bounds: Vec::new(),
+            focused_widget_id: WidgetId::INVALID,
+            repaint_next_frame: Vec::new(),
+            draw_count: 0,
+            pending_removals: Vec::new(),
+            pending_events: Vec::new(),
+            drag: None,
+            drag_ghost_id: WidgetId::INVALID,
+            captured_widget_id: WidgetId::INVALID,
+            theme: get_current_theme(),
+            close_requested: false,
+            render_cache: RenderCache::new(),
+            scale_factor: 1.0,
+            event_bus: EventBus::new(),
+            radio_selections: HashMap::new(),
+            radio_callbacks: HashMap::new(),
+            popups: Vec::new(),
+            modals: Vec::new(),
+            dialog_font: None,
+            focus_indicator_visible: false,
+            debug_overlay: DebugOverlay::new(),
+            accessibility: None,
+            hit_index: HashMap::new(),
+            hit_index_generation: None,
+        }
+    }
+
+    /// Sets the `Theme` that `Widget`s use for any color or font size that they have not been
+    /// configured with.  This can be done while the application is running, for instance from
+    /// a keyboard shortcut: `Widget::theme_changed` is called for every `Widget`, which
+    /// invalidates them all, so the whole window is redrawn in the new theme in a single frame.
+    /// Colors that were set on a `Widget` are kept.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::box_widget::*;
+    /// # use pushrod::widget::theme::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut red_box = BoxWidget::new();
+    ///
+    ///    red_box.set_color([1.0, 0.0, 0.0, 1.0]);
+    ///
+    ///    let plain_id = widget_store.add_widget("Plain", Box::new(BoxWidget::new()));
+    ///    let red_id = widget_store.add_widget("Red", Box::new(red_box));
+    ///
+    ///    widget_store.get_widget_for_id(plain_id).borrow_mut().clear_invalidate();
+    ///    widget_store.set_theme(Theme::dark());
+    ///
+    ///    {
+    ///        let mut plain = widget_store.get_widget_for_id(plain_id).borrow_mut();
+    ///
+    ///        assert!(plain.is_invalidated());
+    ///        assert_eq!(plain.get_color(), Theme::dark().background_color);
+    ///    }
+    ///
+    ///    let red_color = widget_store.get_widget_for_id(red_id).borrow_mut().get_color();
+    ///    assert_eq!(red_color, [1.0, 0.0, 0.0, 1.0]);
+    /// # }
+    /// ```
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        set_current_theme(theme);
+
+        self.widgets
+            .iter()
+            .filter(|container| !container.removed)
+            .for_each(|container| container.widget.borrow_mut().theme_changed(&theme));
+    }
+
+    /// Retrieves the `Theme` that is used by the `Widget`s in this store.
+    pub fn get_theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// Posts an event to the event bus, to be delivered to its subscribers, and to the `Widget`s
+    /// whose event mask matches it, on the next frame.  See `EventBus`.
+    pub fn post_event(&mut self, event: PushrodEvent) {
+        self.event_bus.post(event);
+    }
+
+    /// Adds a closure that is called with every event posted to the event bus that matches the
+    /// `mask`, such as `MASK_EVENT_CLICKED`, or `MASK_EVENT_ALL` for every event.
+    pub fn subscribe(&mut self, mask: EventMask, callback: EventBusCallback) {
+        self.event_bus.subscribe(mask, callback);
+    }
+
+    /// Adds an `EventListener`, which receives every event posted to the event bus that matches
+    /// its `event_mask`.
+    pub fn add_event_listener(&mut self, listener: Box<dyn EventListener>) {
+        self.event_bus.add_listener(listener);
+    }
+
+    /// Returns an `EventSender`, which posts events to the event bus from any thread.  See
+    /// `EventSender`.
+    pub fn get_event_sender(&mut self) -> EventSender {
+        self.event_bus.get_sender()
+    }
+
+    /// Indicates whether any events are waiting on the event bus to be delivered, including any
+    /// that were sent from other threads.
+    pub fn has_posted_events(&mut self) -> bool {
+        self.event_bus.receive_sent_events();
+        self.event_bus.has_posted_events()
+    }
+
+    /// Collects the events posted by `Widget`s (see `Widget::take_posted_events`), and those sent
+    /// from other threads (see `EventSender`), then delivers every event waiting on the event bus,
+    /// in the order they were posted.  Each event is sent
+    /// to the subscribed closures, then to the `EventListener`s, and then to the `Widget`s, in
+    /// order of ID, each only if its event mask matches.  Events that are posted while this is
+    /// running are delivered the next time it is called.  This is called by the `Pushrod` run
+    /// loop once per frame, before drawing.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::event::event::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let clicks = Rc::new(Cell::new(0));
+    ///    let clicks_clone = clicks.clone();
+    ///
+    ///    widget_store.subscribe(MASK_EVENT_CLICKED, Box::new(move |event, widget_store| {
+    ///        if let PushrodEvent::Clicked { widget_id } = event {
+    ///            clicks_clone.set(clicks_clone.get() + 1);
+    ///
+    ///            // Events posted while delivering are delivered on the next frame.
+    ///            widget_store.post_event(PushrodEvent::Clicked { widget_id: *widget_id });
+    ///        }
+    ///    }));
+    ///
+    ///    widget_store.post_event(PushrodEvent::Clicked { widget_id: WidgetId::from(1) });
+    ///    widget_store.post_event(PushrodEvent::TimerTriggered { widget_id: WidgetId::from(2) });
+    ///
+    ///    widget_store.deliver_posted_events();
+    ///    assert_eq!(clicks.get(), 1);
+    ///    assert!(widget_store.has_posted_events());
+    ///
+    ///    widget_store.deliver_posted_events();
+    ///    assert_eq!(clicks.get(), 2);
+    /// # }
+    /// ```
+    pub fn deliver_posted_events(&mut self) {
+        for event in self.track_accessibility() {
+            self.event_bus.post(event);
+        }
+
+        for (id, container) in self.widgets.iter().enumerate() {
+            if container.removed {
+                continue;
+            }
+
+            for event in container
+                .widget
+                .borrow_mut()
+                .take_posted_events(WidgetId::from_index(id))
+            {
+                self.event_bus.post(event);
+            }
+        }
+
+        self.event_bus.receive_sent_events();
+
+        let events = self.event_bus.take_posted_events();
+
+        if events.is_empty() {
+            return;
+        }
+
+        let mut subscribers = self.event_bus.take_subscribers();
+
+        for event in &events {
+            let mask = event.get_mask();
+
+            for (subscriber_mask, callback) in subscribers.iter_mut() {
+                if *subscriber_mask & mask != 0 {
+                    callback(event, self);
+                }
+            }
+
+            self.event_bus.notify_listeners(event);
+
+            for container in self.widgets.iter().filter(|x| !x.removed) {
+                let mut widget = container.widget.borrow_mut();
+
+                if widget.event_mask() & mask != 0 {
+                    widget.handle_pushrod_event(event);
+                }
+            }
+        }
+
+        self.event_bus.restore_subscribers(subscribers);
+    }
+
+    /// Sets the number of device pixels per point of the window, returning `true` if it changed.
+    /// `Widget`s are laid out in points, so their origins and sizes do not change, but
+    /// `Widget::scale_factor_changed` is called for every `Widget`, so text and images can be
+    /// rasterized at the new resolution.  This is called by the `Pushrod` run loop whenever the
+    /// window's scale factor changes, such as when it is moved to a display with a different
+    /// density, before `CallbackEvent::ScaleFactorChanged` is sent.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(10, 10);
+    ///    panel.set_size(100, 50);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///
+    ///    assert!(widget_store.set_scale_factor(2.0));
+    ///    assert!(!widget_store.set_scale_factor(2.0));
+    ///    assert_eq!(widget_store.get_scale_factor(), 2.0);
+    ///
+    ///    // The widget keeps its size in points, and is redrawn at the new resolution.
+    ///    let panel = widget_store.get_widget_for_id(panel_id);
+    ///
+    ///    assert_eq!(panel.borrow_mut().get_size().w, 100);
+    ///    assert!(panel.borrow_mut().is_invalidated());
+    /// # }
+    /// ```
+    pub fn set_scale_factor(&mut self, scale_factor: f64) -> bool {
+        if scale_factor <= 0.0 || (scale_factor - self.scale_factor).abs() < f64::EPSILON {
+            return false;
+        }
+
+        self.scale_factor = scale_factor;
+
+        self.widgets
+            .iter()
+            .filter(|container| !container.removed)
+            .for_each(|container| {
+                container
+                    .widget
+                    .borrow_mut()
+                    .scale_factor_changed(scale_factor)
+            });
+
+        true
+    }
+
+    /// Retrieves the number of device pixels per point of the window.  Defaults to 1.0.
+    pub fn get_scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Tells a `Widget` that is being added about the scale factor of the window, if it is not the
+    /// default, so that it does not start out rasterized at the wrong resolution.
+    fn apply_scale_factor(&self, widget: &mut Box<dyn Widget>) {
+        if (self.scale_factor - 1.0).abs() >= f64::EPSILON {
+            widget.scale_factor_changed(self.scale_factor);
         }
     }
 
@@ -87,169 +559,3987 @@ impl WidgetStore {
     }
 
     /// Adds a UI `Widget` to this window.  `Widget` objects that are added using this method will
-    /// be part of the base widget (`id = 0`), and will be force-redrawn when the parent is
+    /// be part of the base widget (`WidgetId::ROOT`), and will be force-redrawn when the parent is
     /// invalidated.
     ///
     /// After adding a widget, the ID of the widget is returned.
-    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> i32 {
-        let widget_size = self.widgets.len() as i32;
-
-        self.widgets.push(WidgetContainer {
-            widget: RefCell::new(widget),
-            widget_name: String::from(name),
-            widget_id: widget_size,
-            parent_id: 0,
-        });
-
-        widget_size
+    pub fn add_widget(&mut self, name: &str, widget: Box<dyn Widget>) -> WidgetId {
+        self.add_widget_to_parent(name, widget, WidgetId::ROOT)
     }
 
     /// Adds a UI `Widget` to the parent of a window, specified by the `parent_id`.  The `parent_id`
-    /// must be an object that already exists in the stack.
+    /// must be an object that already exists in the stack.  The origin of the `Widget` is relative
+    /// to the origin of its parent.  If the `parent_id` is not valid, or its `Widget` is about to
+    /// be removed, the `Widget` is added to the base widget (`WidgetId::ROOT`) instead.
     ///
     /// After adding a widget, the ID of the widget is returned.
-    pub fn add_widget_to_parent(&mut self, name: &str, widget: Box<dyn Widget>, parent_id: i32) -> i32 {
-        // TODO Validate parent_id
-        let widget_size = self.widgets.len() as i32;
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///    let child_id =
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///    let orphan_id = widget_store.add_widget_to_parent(
+    ///        "Orphan",
+    ///        Box::new(CanvasWidget::new()),
+    ///        WidgetId::INVALID,
+    ///    );
+    ///
+    ///    assert_eq!(widget_store.get_parent_of(child_id), panel_id);
+    ///    assert_eq!(widget_store.get_parent_of(orphan_id), WidgetId::ROOT);
+    /// # }
+    /// ```
+    pub fn add_widget_to_parent(
+        &mut self,
+        name: &str,
+        mut widget: Box<dyn Widget>,
+        parent_id: WidgetId,
+    ) -> WidgetId {
+        let parent_id =
+            if self.is_widget_id_valid(parent_id) && !self.pending_removals.contains(&parent_id) {
+                parent_id
+            } else {
+                WidgetId::ROOT
+            };
+        let free_slot = self.free_slots.pop();
+        let widget_size = WidgetId::from_index(free_slot.unwrap_or(self.widgets.len()));
+
+        widget.invalidate();
+        widget.config().take_changed_keys();
+        widget.config().take_geometry_changed();
+        self.apply_scale_factor(&mut widget);
+        self.hit_index
+            .entry(parent_id)
+            .or_default()
+            .insert(widget_size, widget.get_bounds());
+        self.widgets[parent_id.index()].children.push(widget_size);
 
-        self.widgets.push(WidgetContainer {
+        let container = WidgetContainer {
             widget: RefCell::new(widget),
             widget_name: String::from(name),
             widget_id: widget_size,
             parent_id,
-        });
+            children: Vec::new(),
+            removed: false,
+            drawn_bounds: None,
+            z_order: 0,
+        };
+
+        match free_slot {
+            Some(index) => self.widgets[index] = container,
+            None => self.widgets.push(container),
+        }
 
         widget_size
     }
 
-    /// Retrieves the parent of the widget requested.  Parent of 0 or -1 will always return 0.
-    pub fn get_parent_of(&mut self, widget_id: i32) -> i32 {
-        if widget_id <= 0 {
-            0
-        } else {
-            self.widgets[widget_id as usize].parent_id
+    /// Removes a `Widget`, along with all of its children.  Removal is deferred until the `Pushrod`
+    /// run loop has finished handling the current event, so it is safe to remove a `Widget` while
+    /// it is handling an event, including from its own callback.  Once removed, the `Widget` is
+    /// dropped, loses focus and hover state, and the area it covered is repainted.  The IDs of
+    /// other `Widget`s are not affected, and the IDs of the removed `Widget`s are given to the next
+    /// `Widget`s that are added.  Removing the base widget (`WidgetId::ROOT`) does nothing.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///    let child_id =
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///    let other_id = widget_store.add_widget("Other", Box::new(CanvasWidget::new()));
+    ///
+    ///    widget_store.remove_widget(panel_id);
+    ///    assert!(widget_store.is_widget_id_valid(panel_id));
+    ///
+    ///    assert_eq!(widget_store.apply_pending_removals(), vec![panel_id, child_id]);
+    ///    assert!(!widget_store.is_widget_id_valid(panel_id));
+    ///    assert!(!widget_store.is_widget_id_valid(child_id));
+    ///    assert_eq!(widget_store.get_children_of(WidgetId::ROOT), vec![WidgetId::ROOT, other_id]);
+    /// # }
+    /// ```
+    pub fn remove_widget(&mut self, widget_id: WidgetId) {
+        if widget_id > WidgetId::ROOT && !self.pending_removals.contains(&widget_id) {
+            self.pending_removals.push(widget_id);
         }
     }
 
-    /// Retrieves a list of all of the child IDs that list the `parent_id` as its parent.  This
-    /// can be used recursively to determine the widget ownership tree, or the redraw order in which
-    /// repaint should take place.
-    pub fn get_children_of(&self, parent_id: i32) -> Vec<i32> {
-        self.widgets
+    /// Removes the `Widget` with the specified name, along with all of its children.  Does nothing
+    /// if no `Widget` has that name.  See `remove_widget`.
+    pub fn remove_widget_by_name(&mut self, name: &str) {
+        if let Some(widget_id) = self.get_widget_id_by_name(name) {
+            self.remove_widget(widget_id);
+        }
+    }
+
+    /// Indicates whether or not the ID refers to a `Widget` in the store that has not been
+    /// removed.
+    pub fn is_widget_id_valid(&self, widget_id: WidgetId) -> bool {
+        widget_id >= WidgetId::ROOT
+            && widget_id.index() < self.widgets.len()
+            && !self.widgets[widget_id.index()].removed
+    }
+
+    /// Removes the `Widget`s that were requested by `remove_widget`, along with their children,
+    /// and returns the IDs of all of the `Widget`s that were removed.  This is called by the
+    /// `Pushrod` run loop once it has finished handling an event.  The slots of the removed
+    /// `Widget`s are reused by the `Widget`s that are added next, so the store does not grow when
+    /// `Widget`s are added and removed over and over.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let other_id = widget_store.add_widget("Other", Box::new(CanvasWidget::new()));
+    ///
+    ///    for _ in 0..100 {
+    ///        let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///        widget_store.remove_widget(panel_id);
+    ///        widget_store.apply_pending_removals();
+    ///    }
+    ///
+    ///    assert_eq!(widget_store.widgets.len(), 4);
+    ///    assert_eq!(widget_store.get_children_of(WidgetId::ROOT), vec![WidgetId::ROOT, other_id]);
+    ///
+    ///    let reused_id = widget_store.add_widget("Reused", Box::new(CanvasWidget::new()));
+    ///
+    ///    assert_eq!(widget_store.widgets.len(), 4);
+    ///    assert_eq!(widget_store.get_widget_id_by_name("Reused"), Some(reused_id));
+    /// # }
+    /// ```
+    pub fn apply_pending_removals(&mut self) -> Vec<WidgetId> {
+        let mut removed_ids = Vec::new();
+
+        for widget_id in std::mem::take(&mut self.pending_removals) {
+            if !self.is_widget_id_valid(widget_id) {
+                continue;
+            }
+
+            let parent_id = self.get_parent_of(widget_id);
+            let first_removed = removed_ids.len();
+
+            removed_ids.push(widget_id);
+
+            let mut pos = first_removed;
+
+            while pos < removed_ids.len() {
+                let current_id = removed_ids[pos];
+
+                removed_ids.extend(
+                    self.get_children_of(current_id)
+                        .into_iter()
+                        .filter(|x| *x != current_id),
+                );
+                pos += 1;
+            }
+
+            for removed_id in &removed_ids[first_removed..] {
+                self.render_cache.remove(*removed_id);
+                self.hit_index.remove(removed_id);
+
+                if let Some(tracker) = self.accessibility.as_mut() {
+                    tracker.forget(*removed_id);
+                }
+
+                let removed_parent_id = self.widgets[removed_id.index()].parent_id;
+
+                self.widgets[removed_parent_id.index()]
+                    .children
+                    .retain(|x| x != removed_id);
+
+                let container = &mut self.widgets[removed_id.index()];
+
+                if let Some(index) = self.hit_index.get_mut(&container.parent_id) {
+                    index.remove(*removed_id);
+                }
+                let mut placeholder = CanvasWidget::new();
+
+                placeholder.set_visible(false);
+                container.widget = RefCell::new(Box::new(placeholder));
+                container.widget_name = String::new();
+                container.children.clear();
+                container.removed = true;
+                container.z_order = 0;
+                self.removed_bounds.extend(container.drawn_bounds.take());
+                self.free_slots.push(removed_id.index());
+            }
+
+            if self.is_widget_id_valid(parent_id) {
+                self.invalidate_widget_and_children(parent_id);
+            }
+        }
+
+        if removed_ids.contains(&self.focused_widget_id) {
+            self.focused_widget_id = WidgetId::INVALID;
+        }
+
+        if removed_ids.contains(&self.captured_widget_id) {
+            self.captured_widget_id = WidgetId::INVALID;
+        }
+
+        // The IDs of removed widgets are given to widgets added later, so none of them may be
+        // kept.
+        for modal in self.modals.iter_mut() {
+            if removed_ids.contains(&modal.previous_focus_id) {
+                modal.previous_focus_id = WidgetId::INVALID;
+            }
+        }
+
+        self.repaint_next_frame
+            .retain(|(widget_id, _)| !removed_ids.contains(widget_id));
+        self.popups.retain(|widget_id| !removed_ids.contains(widget_id));
+
+        // A modal dialog that was removed is closed, with no result.
+        if let Some(index) = self.modals.iter().position(|modal| {
+            removed_ids.contains(&modal.dialog_id) || removed_ids.contains(&modal.scrim_id)
+        }) {
+            self.close_modals_from(index, None);
+        }
+
+        self.pending_events
+            .retain(|(widget_id, _)| !removed_ids.contains(widget_id));
+
+        if removed_ids.contains(&self.drag_ghost_id) {
+            self.drag_ghost_id = WidgetId::INVALID;
+        }
+
+        self.cancel_removed_drag(&removed_ids);
+
+        let emptied_groups: Vec<String> = self
+            .radio_selections
             .iter()
-            .filter(|x| x.parent_id == parent_id)
-            .map(|x| x.widget_id)
-            .collect()
+            .filter(|(_, widget_id)| removed_ids.contains(widget_id))
+            .map(|(group, _)| group.clone())
+            .collect();
+
+        for group in emptied_groups {
+            self.radio_selections
+                .insert(group.clone(), WidgetId::INVALID);
+
+            if let Some(callback) = self.radio_callbacks.get_mut(&group) {
+                callback(WidgetId::INVALID);
+            }
+        }
+
+        removed_ids
+    }
+
+    /// Adds a UI `Widget` to this window with a unique name, which can be used to look up the
+    /// `Widget` later, instead of its ID.  Returns the ID of the `Widget`, or an error if a
+    /// `Widget` with the same name already exists.  The name of a removed `Widget` can be reused.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let ok_id = widget_store
+    ///        .add_widget_named("ok_button", Box::new(CanvasWidget::new()))
+    ///        .unwrap();
+    ///
+    ///    assert_eq!(widget_store.get_widget_id_by_name("ok_button"), Some(ok_id));
+    ///    assert_eq!(widget_store.get_widget_id_by_name("cancel_button"), None);
+    ///    assert_eq!(
+    ///        widget_store.add_widget_named("ok_button", Box::new(CanvasWidget::new())),
+    ///        Err(WidgetStoreError::DuplicateName(String::from("ok_button")))
+    ///    );
+    ///
+    ///    widget_store.get_widget_by_name("ok_button").unwrap().set_size(80, 24);
+    ///
+    ///    widget_store.remove_widget(ok_id);
+    ///    widget_store.apply_pending_removals();
+    ///    assert!(widget_store
+    ///        .add_widget_named("ok_button", Box::new(CanvasWidget::new()))
+    ///        .is_ok());
+    /// # }
+    /// ```
+    pub fn add_widget_named(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+    ) -> Result<WidgetId, WidgetStoreError> {
+        self.add_widget_named_to_parent(name, widget, WidgetId::ROOT)
     }
 
-    /// Retrieves a `PushrodWidget` ID for a specified `Point`.  If no ID could be found,
-    /// defaults to a -1.
-    pub fn get_widget_id_for_point(&mut self, point: Point) -> i32 {
-        let mut found_id = -1;
+    /// Adds a UI `Widget` with a unique name to the parent specified by the `parent_id`.  Returns
+    /// the ID of the `Widget`, or an error if a `Widget` with the same name already exists.
+    pub fn add_widget_named_to_parent(
+        &mut self,
+        name: &str,
+        widget: Box<dyn Widget>,
+        parent_id: WidgetId,
+    ) -> Result<WidgetId, WidgetStoreError> {
+        if self.get_widget_id_by_name(name).is_some() {
+            return Err(WidgetStoreError::DuplicateName(String::from(name)));
+        }
+
+        Ok(self.add_widget_to_parent(name, widget, parent_id))
+    }
 
-        for (pos, obj) in self.widgets.iter_mut().enumerate() {
-            let widget_point = &obj.widget.borrow_mut().get_origin();
-            let widget_size: crate::core::point::Size = obj.widget.borrow_mut().get_size();
+    /// Adds a UI `Widget` to a `GridLayoutWidget`, in the cell, or block of cells, given by
+    /// `cell`.  Returns the ID of the `Widget`, or an error with the first cell, from the top
+    /// left, that is already taken by another child of the grid.  See the `GridLayoutWidget` for
+    /// an example.
+    pub fn add_widget_to_grid(
+        &mut self,
+        name: &str,
+        mut widget: Box<dyn Widget>,
+        grid_id: WidgetId,
+        cell: GridCell,
+    ) -> Result<WidgetId, WidgetStoreError> {
+        let cell = cell.with_span(cell.row_span, cell.column_span);
+        let taken: Vec<GridCell> = self
+            .get_children_of(grid_id)
+            .into_iter()
+            .filter_map(|child_id| {
+                self.widgets[child_id.index()]
+                    .widget
+                    .borrow_mut()
+                    .get_grid_cell()
+            })
+            .filter(|other| other.overlaps(&cell))
+            .collect();
 
-            // Skip over item widgets that have a width and height of 0.
-            if widget_size.w > 0 && widget_size.h > 0 {
-                if point.x >= widget_point.x
-                    && point.x <= widget_point.x + widget_size.w
-                    && point.y >= widget_point.y
-                    && point.y <= widget_point.y + widget_size.h
+        for row in cell.row..cell.row + cell.row_span {
+            for column in cell.column..cell.column + cell.column_span {
+                if taken
+                    .iter()
+                    .any(|other| other.overlaps(&make_grid_cell(row, column)))
                 {
-                    found_id = pos as i32;
+                    return Err(WidgetStoreError::CellOccupied { row, column });
                 }
             }
         }
 
-        found_id
+        widget.set_grid_cell(cell);
+        Ok(self.add_widget_to_parent(name, widget, grid_id))
     }
 
-    /// Returns the name of the widget by its ID.
-    pub fn get_name_for_widget_id(&mut self, widget_id: i32) -> &str {
-        self.widgets[widget_id as usize]
-            .widget_name
-            .as_str()
+    /// Retrieves the ID of the `Widget` with the specified name, or `None` if no `Widget` has that
+    /// name.
+    pub fn get_widget_id_by_name(&self, name: &str) -> Option<WidgetId> {
+        self.widgets
+            .iter()
+            .find(|x| !x.removed && x.widget_name == name)
+            .map(|x| x.widget_id)
     }
 
-    /// Handles event messages, returning an event if provided by the `Widget`.
-    pub fn handle_event(&mut self, widget_id: i32, event: CallbackEvent) -> Option<CallbackEvent> {
-        self.widgets[widget_id as usize]
-            .widget
-            .borrow_mut()
-            .handle_event(event)
+    /// Retrieves the `Widget` with the specified name, or `None` if no `Widget` has that name.
+    /// Unlike `get_widget_for_name`, this does not fall back to the base widget.
+    pub fn get_widget_by_name(&mut self, name: &str) -> Option<&mut dyn Widget> {
+        let widget_id = self.get_widget_id_by_name(name)?;
+
+        Some(self.widgets[widget_id.index()].widget.get_mut().as_mut())
     }
 
-    pub fn set_color(&mut self, widget_id: i32, color: types::Color) {
-        self.widgets[widget_id as usize]
-            .widget
-            .borrow_mut()
-            .set_color(color);
+    /// Retrieves the parent of the widget requested.  The parent of `WidgetId::ROOT`, or of an ID
+    /// that is not valid, such as `WidgetId::INVALID`, is always `WidgetId::ROOT`.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///    let child_id =
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///
+    ///    assert_eq!(widget_store.get_parent_of(child_id), panel_id);
+    ///    assert_eq!(widget_store.get_parent_of(WidgetId::INVALID), WidgetId::ROOT);
+    ///    assert_eq!(widget_store.get_parent_of(WidgetId::from(100)), WidgetId::ROOT);
+    /// # }
+    /// ```
+    pub fn get_parent_of(&mut self, widget_id: WidgetId) -> WidgetId {
+        if widget_id == WidgetId::ROOT || !self.is_widget_id_valid(widget_id) {
+            WidgetId::ROOT
+        } else {
+            self.widgets[widget_id.index()].parent_id
+        }
     }
 
-    /// Recursive draw object: paints objects in order of appearance on the screen.  This does not
-    /// account for object depth, but it is implied that objects' parents are displayed in stacking
-    /// order.  Therefore, the parent is drawn first, then sibling, and other siblings.  This draw
-    /// function is used by the `Pushrod` main loop, and is meant to be called in a `draw_2d`
-    /// closure.
-    pub fn draw(&mut self, widget_id: i32, c: Context, g: &mut G2d) {
-        let parents_of_widget = self.get_children_of(widget_id);
+    /// Retrieves a list of all of the child IDs that list the `parent_id` as its parent.  This
+    /// can be used recursively to determine the widget ownership tree, or the redraw order in which
+    /// repaint should take place.
+    pub fn get_children_of(&self, parent_id: WidgetId) -> Vec<WidgetId> {
+        if self.is_widget_id_valid(parent_id) {
+            self.widgets[parent_id.index()].children.clone()
+        } else {
+            Vec::new()
+        }
+    }
 
-        if parents_of_widget.len() == 0 {
+    /// Sets the z-order of a `Widget`, which controls its stacking order among the other children
+    /// of its parent.  Children are drawn in ascending z-order, so the `Widget` with the highest
+    /// z-order is drawn on top, and receives mouse events first.  `Widget`s with the same z-order
+    /// are stacked in the order in which they were added.  The default z-order is 0.  Does
+    /// nothing if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut overlay = CanvasWidget::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    overlay.set_origin(10, 10);
+    ///    overlay.set_size(100, 100);
+    ///    panel.set_origin(10, 10);
+    ///    panel.set_size(100, 100);
+    ///
+    ///    let overlay_id = widget_store.add_widget("Overlay", Box::new(overlay));
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(50, 50)), panel_id);
+    ///
+    ///    widget_store.set_z_order(overlay_id, 10);
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(50, 50)), overlay_id);
+    ///    assert_eq!(widget_store.get_draw_order(), vec![WidgetId::ROOT, panel_id, overlay_id]);
+    /// # }
+    /// ```
+    pub fn set_z_order(&mut self, widget_id: WidgetId, z_order: i32) {
+        if !self.is_widget_id_valid(widget_id) {
             return;
         }
 
-        for pos in 0..parents_of_widget.len() {
-            c.reset();
+        self.widgets[widget_id.index()].z_order = z_order;
 
-            let paint_id = parents_of_widget[pos];
-            let paint_widget = &mut self.widgets[paint_id as usize];
+        let parent_id = self.get_parent_of(widget_id);
 
-            if &paint_widget.widget.borrow_mut().is_invalidated() == &true {
-                let origin: Point = paint_widget.widget.borrow_mut().get_origin().clone();
-                let size: crate::core::point::Size =
-                    paint_widget.widget.borrow_mut().get_size().clone();
+        self.invalidate_widget_and_children(parent_id);
+    }
 
-                let new_context: Context = Context {
-                    viewport: c.viewport,
-                    view: c.view,
-                    transform: c.transform.trans(origin.x as f64, origin.y as f64),
-                    draw_state: c.draw_state,
-                };
+    /// Retrieves the z-order of a `Widget`, or 0 if the ID is not valid.
+    pub fn get_z_order(&self, widget_id: WidgetId) -> i32 {
+        if !self.is_widget_id_valid(widget_id) {
+            return 0;
+        }
 
-                let clip: DrawState = c.draw_state.scissor([
-                    origin.x as u32 * 2,
-                    origin.y as u32 * 2,
-                    size.w as u32 * 2,
-                    size.h as u32 * 2,
-                ]);
+        self.widgets[widget_id.index()].z_order
+    }
 
-                &paint_widget.widget.borrow_mut().draw(new_context, g, &clip);
-            }
+    /// Moves a `Widget` above all of its siblings.  Does nothing if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let back_id = widget_store.add_widget("Back", Box::new(CanvasWidget::new()));
+    ///    let front_id = widget_store.add_widget("Front", Box::new(CanvasWidget::new()));
+    ///
+    ///    widget_store.bring_to_front(back_id);
+    ///    assert_eq!(widget_store.get_draw_order(), vec![WidgetId::ROOT, front_id, back_id]);
+    ///
+    ///    widget_store.bring_to_front(WidgetId::INVALID);
+    ///    widget_store.bring_to_front(WidgetId::from(100));
+    ///    assert_eq!(widget_store.get_draw_order(), vec![WidgetId::ROOT, front_id, back_id]);
+    /// # }
+    /// ```
+    pub fn bring_to_front(&mut self, widget_id: WidgetId) {
+        if !self.is_widget_id_valid(widget_id) {
+            return;
+        }
 
-            if parents_of_widget[pos] != widget_id {
-                self.draw(paint_id, c, g);
-            }
+        let z_order = self
+            .get_siblings_of(widget_id)
+            .iter()
+            .map(|x| self.widgets[x.index()].z_order)
+            .max();
+
+        match z_order {
+            Some(z) if z >= self.get_z_order(widget_id) => self.set_z_order(widget_id, z + 1),
+            _ => (),
         }
     }
 
-    /// Retrieves a widget by the name when the widget was added.  To get the very top-level
-    /// widget, refer to `_WidgetStoreBase`.
-    pub fn get_widget_for_name(&mut self, name: &str) -> &RefCell<Box<dyn Widget>> {
-        let widget_id = match self.widgets
-            .iter_mut()
-            .find(|x| x.widget_name == String::from(name)) {
+    /// Moves a `Widget` below all of its siblings.  Does nothing if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let back_id = widget_store.add_widget("Back", Box::new(CanvasWidget::new()));
+    ///    let front_id = widget_store.add_widget("Front", Box::new(CanvasWidget::new()));
+    ///
+    ///    widget_store.send_to_back(front_id);
+    ///    assert_eq!(widget_store.get_z_order(front_id), -1);
+    ///
+    ///    widget_store.send_to_back(WidgetId::INVALID);
+    ///    widget_store.send_to_back(WidgetId::from(100));
+    ///    assert_eq!(widget_store.get_z_order(back_id), 0);
+    /// # }
+    /// ```
+    pub fn send_to_back(&mut self, widget_id: WidgetId) {
+        if !self.is_widget_id_valid(widget_id) {
+            return;
+        }
+
+        let z_order = self
+            .get_siblings_of(widget_id)
+            .iter()
+            .map(|x| self.widgets[x.index()].z_order)
+            .min();
+
+        match z_order {
+            Some(z) if z <= self.get_z_order(widget_id) => self.set_z_order(widget_id, z - 1),
+            _ => (),
+        }
+    }
+
+    /// Returns the IDs of the other children of a `Widget`'s parent.
+    fn get_siblings_of(&self, widget_id: WidgetId) -> Vec<WidgetId> {
+        let parent_id = self.widgets[widget_id.index()].parent_id;
+
+        self.get_children_of(parent_id)
+            .into_iter()
+            .filter(|x| *x != widget_id && *x != parent_id)
+            .collect()
+    }
+
+    /// Returns the children of a `Widget` in the order in which they are drawn: ascending z-order,
+    /// with ties kept in the order in which they were added.
+    fn get_children_in_z_order(&self, parent_id: WidgetId) -> Vec<WidgetId> {
+        let mut children: Vec<WidgetId> = self
+            .get_children_of(parent_id)
+            .into_iter()
+            .filter(|x| *x != parent_id)
+            .collect();
+
+        children.sort_by_key(|x| self.widgets[x.index()].z_order);
+        children
+    }
+
+    /// Returns the IDs of all `Widget`s in the order in which they are drawn, starting with the
+    /// base widget (`WidgetId::ROOT`).  Each `Widget` is followed by its children, in z-order.
+    pub fn get_draw_order(&self) -> Vec<WidgetId> {
+        let mut draw_order = vec![WidgetId::ROOT];
+
+        self.append_draw_order(WidgetId::ROOT, &mut draw_order);
+        draw_order
+    }
+
+    /// Describes the tree of `Widget`s, one `Widget` to a line, each followed by its children,
+    /// indented, in the order in which they were added: its ID, its name, its origin relative to
+    /// its parent, and its size.  `Widget`s that are not visible are marked as hidden.  This is
+    /// printed by `Pushrod::print_widget_tree`, for debugging layouts.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut button = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(10, 20);
+    ///    panel.set_size(200, 100);
+    ///    button.set_origin(5, 5);
+    ///    button.set_size(80, 24);
+    ///    button.set_visible(false);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    widget_store.add_widget_to_parent("OK", Box::new(button), panel_id);
+    ///
+    ///    assert_eq!(
+    ///        widget_store.get_widget_tree(),
+    ///        "0 '_WidgetStoreBase' at (0, 0), 800x600\n\
+    ///         \x20 1 'Panel' at (10, 20), 200x100\n\
+    ///         \x20   2 'OK' at (5, 5), 80x24, hidden\n"
+    ///    );
+    /// # }
+    /// ```
+    pub fn get_widget_tree(&mut self) -> String {
+        let mut tree = String::new();
+
+        self.append_widget_tree(WidgetId::ROOT, 0, &mut tree);
+        tree
+    }
+
+    fn append_widget_tree(&mut self, widget_id: WidgetId, depth: usize, tree: &mut String) {
+        let (origin, size) = {
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+            (widget.get_origin(), widget.get_size())
+        };
+        let hidden = if self.is_widget_visible(widget_id) {
+            ""
+        } else {
+            ", hidden"
+        };
+
+        tree.push_str(&format!(
+            "{}{} '{}' at ({}, {}), {}x{}{}\n",
+            "  ".repeat(depth),
+            widget_id,
+            self.widgets[widget_id.index()].widget_name,
+            origin.x,
+            origin.y,
+            size.w,
+            size.h,
+            hidden
+        ));
+
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id {
+                self.append_widget_tree(child_id, depth + 1, tree);
+            }
+        }
+    }
+
+    fn append_draw_order(&self, widget_id: WidgetId, draw_order: &mut Vec<WidgetId>) {
+        for child_id in self.get_children_in_z_order(widget_id) {
+            draw_order.push(child_id);
+            self.append_draw_order(child_id, draw_order);
+        }
+    }
+
+    /// Indicates whether or not a `Widget` is visible on the screen: the `Widget` and all of its
+    /// parents must be visible, and none of its parents may be `Collapsed`.  Returns `false` if
+    /// the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///    let child_id =
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///
+    ///    widget_store.get_widget_for_id(panel_id).borrow_mut().config().set(Collapsed);
+    ///    assert!(widget_store.is_widget_visible(panel_id));
+    ///    assert!(!widget_store.is_widget_visible(child_id));
+    ///    assert!(!widget_store.is_widget_visible(WidgetId::INVALID));
+    ///    assert!(!widget_store.is_widget_visible(WidgetId::from(100)));
+    /// # }
+    /// ```
+    pub fn is_widget_visible(&mut self, widget_id: WidgetId) -> bool {
+        if !self.is_widget_id_valid(widget_id) {
+            return false;
+        }
+
+        let mut current_id = widget_id;
+
+        loop {
+            if !self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .is_visible()
+            {
+                return false;
+            }
+
+            if current_id == WidgetId::ROOT {
+                return true;
+            }
+
+            current_id = self.get_parent_of(current_id);
+
+            if self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .config()
+                .contains_key::<Collapsed>()
+            {
+                return false;
+            }
+        }
+    }
+
+    /// Retrieves the opacity that a `Widget` is drawn with: its own opacity, multiplied by the
+    /// opacity of all of its parents.  Returns 1.0 if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut child = CanvasWidget::new();
+    ///
+    ///    panel.set_opacity(0.5);
+    ///    child.set_opacity(0.5);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let child_id = widget_store.add_widget_to_parent("Child", Box::new(child), panel_id);
+    ///
+    ///    assert_eq!(widget_store.get_effective_opacity(panel_id), 0.5);
+    ///    assert_eq!(widget_store.get_effective_opacity(child_id), 0.25);
+    ///    assert_eq!(widget_store.get_effective_opacity(WidgetId::INVALID), 1.0);
+    ///    assert_eq!(widget_store.get_effective_opacity(WidgetId::from(100)), 1.0);
+    /// # }
+    /// ```
+    pub fn get_effective_opacity(&mut self, widget_id: WidgetId) -> f32 {
+        let mut current_id = widget_id;
+        let mut opacity = 1.0;
+
+        if !self.is_widget_id_valid(widget_id) {
+            return opacity;
+        }
+
+        loop {
+            opacity *= self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .get_opacity();
+
+            if current_id == WidgetId::ROOT {
+                return opacity;
+            }
+
+            current_id = self.get_parent_of(current_id);
+        }
+    }
+
+    /// Shows or hides the `Widget` with the specified ID.  The parent of the `Widget`, and all of
+    /// the parent's children, are invalidated, so that the area the `Widget` covers is repainted.
+    /// If the `Widget` being hidden has keyboard focus, the focus is cleared.  Does nothing if the
+    /// ID is not valid.
+    pub fn set_widget_visible(&mut self, widget_id: WidgetId, visible: bool) {
+        if !self.is_widget_id_valid(widget_id) {
+            return;
+        }
+
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .set_visible(visible);
+
+        let parent_id = self.get_parent_of(widget_id);
+
+        self.invalidate_widget_and_children(parent_id);
+
+        if !visible
+            && self.focused_widget_id.is_valid()
+            && !self.is_widget_visible(self.focused_widget_id)
+        {
+            self.set_focused_widget(WidgetId::INVALID);
+        }
+
+        if !visible
+            && self.captured_widget_id.is_valid()
+            && !self.is_widget_visible(self.captured_widget_id)
+        {
+            self.release_capture();
+        }
+    }
+
+    /// Indicates whether or not a `Widget` is enabled: the `Widget` and all of its parents must
+    /// not be disabled, and no modal dialog may be keeping the `Widget` from receiving events;
+    /// see `show_modal`.  Returns `false` if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(CanvasWidget::new()));
+    ///    let child_id =
+    ///        widget_store.add_widget_to_parent("Child", Box::new(CanvasWidget::new()), panel_id);
+    ///
+    ///    widget_store.set_widget_disabled(panel_id, true);
+    ///    assert!(!widget_store.is_widget_enabled(child_id));
+    ///    assert!(widget_store.is_widget_enabled(WidgetId::ROOT));
+    ///    assert!(!widget_store.is_widget_enabled(WidgetId::INVALID));
+    ///    assert!(!widget_store.is_widget_enabled(WidgetId::from(100)));
+    /// # }
+    /// ```
+    pub fn is_widget_enabled(&mut self, widget_id: WidgetId) -> bool {
+        if !self.is_widget_id_valid(widget_id) || self.is_blocked_by_modal(widget_id) {
+            return false;
+        }
+
+        let mut current_id = widget_id;
+
+        loop {
+            if self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .is_disabled()
+            {
+                return false;
+            }
+
+            if current_id == WidgetId::ROOT {
+                return true;
+            }
+
+            current_id = self.get_parent_of(current_id);
+        }
+    }
+
+    /// Indicates whether the topmost modal dialog keeps a `Widget` from receiving events: a
+    /// dialog is open, and the `Widget` is not the dialog, its scrim, an open popup, or one of
+    /// their children.
+    fn is_blocked_by_modal(&mut self, widget_id: WidgetId) -> bool {
+        let (dialog_id, scrim_id) = match self.modals.last() {
+            Some(modal) => (modal.dialog_id, modal.scrim_id),
+            None => return false,
+        };
+        let mut current_id = widget_id;
+
+        while current_id > WidgetId::ROOT {
+            if current_id == dialog_id
+                || current_id == scrim_id
+                || self.popups.contains(&current_id)
+            {
+                return false;
+            }
+
+            current_id = self.get_parent_of(current_id);
+        }
+
+        true
+    }
+
+    /// Disables or enables the `Widget` with the specified ID, and sends it a `DisabledChanged`
+    /// event if its state changed.  If the `Widget` being disabled, or one of its children, has
+    /// keyboard focus, the focus is cleared.  The `Pushrod` run loop sends a `MouseExited` event
+    /// to a hovered `Widget` that becomes disabled.  Does nothing if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut button = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(10, 10);
+    ///    panel.set_size(100, 100);
+    ///    button.set_size(50, 20);
+    ///    button.set_focusable(true);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let button_id = widget_store.add_widget_to_parent("Button", Box::new(button), panel_id);
+    ///
+    ///    widget_store.set_widget_disabled(button_id, true);
+    ///    assert!(!widget_store.is_widget_enabled(button_id));
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(20, 20)), panel_id);
+    ///    assert!(widget_store.get_focus_ring().is_empty());
+    /// # }
+    /// ```
+    pub fn set_widget_disabled(&mut self, widget_id: WidgetId, disabled: bool) {
+        if !self.is_widget_id_valid(widget_id) {
+            return;
+        }
+
+        let was_disabled = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .is_disabled();
+
+        if was_disabled == disabled {
+            return;
+        }
+
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .set_disabled(disabled);
+
+        if disabled
+            && self.focused_widget_id.is_valid()
+            && !self.is_widget_enabled(self.focused_widget_id)
+        {
+            self.set_focused_widget(WidgetId::INVALID);
+        }
+
+        if disabled
+            && self.captured_widget_id.is_valid()
+            && !self.is_widget_enabled(self.captured_widget_id)
+        {
+            self.release_capture();
+        }
+
+        self.pending_events.push((
+            widget_id,
+            CallbackEvent::DisabledChanged {
+                widget_id,
+                disabled,
+            },
+        ));
+    }
+
+    /// Invalidates a `Widget`, and all of its children, recursively.
+    pub fn invalidate_widget_and_children(&mut self, widget_id: WidgetId) {
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .invalidate();
+
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id {
+                self.invalidate_widget_and_children(child_id);
+            }
+        }
+    }
+
+    /// Retrieves a `PushrodWidget` ID for a specified `Point`.  If no ID could be found, defaults
+    /// to `WidgetId::INVALID`.  The topmost `Widget` in the draw order is matched.  Hidden and
+    /// disabled `Widget`s, and `Widget`s that let mouse events pass through, are skipped, so the
+    /// point is matched to the `Widget` underneath.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut bottom = CanvasWidget::new();
+    ///    let mut top = CanvasWidget::new();
+    ///
+    ///    bottom.set_origin(10, 10);
+    ///    bottom.set_size(100, 100);
+    ///    top.set_origin(10, 10);
+    ///    top.set_size(100, 100);
+    ///
+    ///    let bottom_id = widget_store.add_widget("Bottom", Box::new(bottom));
+    ///    let top_id = widget_store.add_widget("Top", Box::new(top));
+    ///
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(50, 50)), top_id);
+    ///
+    ///    widget_store.set_widget_visible(top_id, false);
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(50, 50)), bottom_id);
+    ///
+    ///    widget_store.set_widget_visible(top_id, true);
+    ///    widget_store.get_widget_by_name("Top").unwrap().set_pass_through(true);
+    ///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(50, 50)), bottom_id);
+    /// # }
+    /// ```
+    pub fn get_widget_id_for_point(&mut self, point: Point) -> WidgetId {
+        self.get_widget_ids_for_point(point)
+            .first()
+            .copied()
+            .unwrap_or(WidgetId::INVALID)
+    }
+
+    /// Retrieves the IDs of all of the `Widget`s under a point in the window, topmost first, which
+    /// is the order in which mouse events are offered to them.  `Widget`s that are hidden,
+    /// disabled, or let mouse events pass through are skipped, as for `get_widget_id_for_point`.
+    /// The children of each `Widget` are kept in a `SpatialIndex`, so only the `Widget`s under
+    /// the point, and their children, are looked at, however many `Widget`s there are.  The
+    /// index is brought up to date whenever the origin or size of a `Widget` has changed since
+    /// the last call.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut bottom = CanvasWidget::new();
+    ///    let mut top = CanvasWidget::new();
+    ///
+    ///    bottom.set_origin(10, 10);
+    ///    bottom.set_size(100, 100);
+    ///    top.set_origin(50, 50);
+    ///    top.set_size(100, 100);
+    ///
+    ///    let bottom_id = widget_store.add_widget("Bottom", Box::new(bottom));
+    ///    let top_id = widget_store.add_widget("Top", Box::new(top));
+    ///
+    ///    assert_eq!(
+    ///        widget_store.get_widget_ids_for_point(make_point_i32(75, 75)),
+    ///        vec![top_id, bottom_id, WidgetId::ROOT]
+    ///    );
+    ///    assert_eq!(
+    ///        widget_store.get_widget_ids_for_point(make_point_i32(20, 20)),
+    ///        vec![bottom_id, WidgetId::ROOT]
+    ///    );
+    /// # }
+    /// ```
+    pub fn get_widget_ids_for_point(&mut self, point: Point) -> Vec<WidgetId> {
+        let mut found_ids = Vec::new();
+
+        self.sync_hit_index();
+
+        let content_point = point.clone() - self.get_content_origin(WidgetId::ROOT);
+
+        self.append_widget_ids_for_point(WidgetId::ROOT, &point, &content_point, &mut found_ids);
+        found_ids
+    }
+
+    /// Retrieves the same IDs as `get_widget_ids_for_point`, by looking at every `Widget` in draw
+    /// order, without the spatial index.  This is much slower once there are many `Widget`s, and
+    /// is kept to check the index against, and to benchmark it.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(20, 20);
+    ///    panel.set_size(400, 400);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    // A grid of cells, which overlap their neighbors by a point.
+    ///    let mut cell_ids = Vec::new();
+    ///
+    ///    for row in 0..10 {
+    ///        for column in 0..10 {
+    ///            let mut cell = CanvasWidget::new();
+    ///
+    ///            cell.set_origin(column * 40, row * 40);
+    ///            cell.set_size(41, 41);
+    ///            let cell_id = widget_store.add_widget_to_parent("Cell", Box::new(cell), panel_id);
+    ///
+    ///            cell_ids.push(cell_id);
+    ///        }
+    ///    }
+    ///
+    ///    // The panel is moved after the cells were added, which moves the cells with it.
+    ///    widget_store.get_widget_for_id(panel_id).borrow_mut().set_origin(0, 0);
+    ///
+    ///    for point in &[(0, 0), (40, 40), (55, 123), (399, 399), (400, 400), (500, 10)] {
+    ///        let point = make_point_i32(point.0, point.1);
+    ///
+    ///        assert_eq!(
+    ///            widget_store.get_widget_ids_for_point(point.clone()),
+    ///            widget_store.scan_widget_ids_for_point(point)
+    ///        );
+    ///    }
+    ///
+    ///    assert_eq!(
+    ///        widget_store.get_widget_ids_for_point(make_point_i32(40, 40)),
+    ///        vec![
+    ///            cell_ids[11],
+    ///            cell_ids[10],
+    ///            cell_ids[1],
+    ///            cell_ids[0],
+    ///            panel_id,
+    ///            WidgetId::ROOT
+    ///        ]
+    ///    );
+    /// # }
+    /// ```
+    pub fn scan_widget_ids_for_point(&mut self, point: Point) -> Vec<WidgetId> {
+        let mut found_ids = Vec::new();
+
+        for pos in self.get_draw_order() {
+            if !self.is_widget_visible(pos)
+                || !self.is_widget_enabled(pos)
+                || self.widgets[pos.index()]
+                    .widget
+                    .borrow_mut()
+                    .is_pass_through()
+            {
+                continue;
+            }
+
+            // Item widgets that have a width and height of 0 contain no points, so they are
+            // skipped over.
+            if self.is_point_on_widget(pos, &point) {
+                // Parts of a widget that are clipped away by its parents, such as children
+                // scrolled out of view, cannot be clicked.
+                let visible = match self.get_clip_bounds(pos) {
+                    Some(clip) => clip.contains(&point),
+                    None => pos.is_root(),
+                };
+
+                if visible {
+                    found_ids.push(pos);
+                }
+            }
+        }
+
+        found_ids.reverse();
+        found_ids
+    }
+
+    /// Appends the IDs of the `Widget`s under `point`, in window coordinates, among the children
+    /// of `widget_id` and their children, topmost first, followed by `widget_id` itself, if it is
+    /// under the point.  `content_point` is the point relative to the content of `widget_id`,
+    /// which the origins of its children are relative to.  The children of a `Widget` can only
+    /// be seen in its viewport, so they are only looked at if the point is in it.
+    fn append_widget_ids_for_point(
+        &mut self,
+        widget_id: WidgetId,
+        point: &Point,
+        content_point: &Point,
+        found_ids: &mut Vec<WidgetId>,
+    ) {
+        let mut child_ids = match self.hit_index.get(&widget_id) {
+            Some(index) => index.query_point(content_point),
+            None => Vec::new(),
+        };
+
+        // Children are drawn in ascending z-order, and then in the order in which they were
+        // added, so the last of them is on top.
+        child_ids.retain(|x| *x != widget_id && self.is_widget_id_valid(*x));
+        child_ids.sort_by_key(|x| self.widgets[x.index()].z_order);
+
+        for child_id in child_ids.into_iter().rev() {
+            // The children of a hidden widget are hidden along with it.
+            if !self.is_widget_visible(child_id) {
+                continue;
+            }
+
+            if self.get_viewport_bounds(child_id).contains(point) {
+                let child_point = content_point.clone() - self.get_content_origin(child_id);
+
+                self.append_widget_ids_for_point(child_id, point, &child_point, found_ids);
+            }
+
+            if self.is_hit_by_point(child_id, point) {
+                found_ids.push(child_id);
+            }
+        }
+
+        if widget_id == WidgetId::ROOT && self.is_hit_by_point(WidgetId::ROOT, point) {
+            found_ids.push(WidgetId::ROOT);
+        }
+    }
+
+    /// Indicates whether `point`, in window coordinates, hits a `Widget`: it is visible, enabled,
+    /// does not let mouse events pass through, and the point is on a part of it that can be seen.
+    fn is_hit_by_point(&mut self, widget_id: WidgetId, point: &Point) -> bool {
+        if !self.is_widget_visible(widget_id)
+            || !self.is_widget_enabled(widget_id)
+            || self.widgets[widget_id.index()]
+                .widget
+                .borrow_mut()
+                .is_pass_through()
+            || !self.is_point_on_widget(widget_id, point)
+        {
+            return false;
+        }
+
+        match self.get_clip_bounds(widget_id) {
+            Some(clip) => clip.contains(point),
+            None => widget_id == WidgetId::ROOT,
+        }
+    }
+
+    /// Returns the origin of a `Widget`, relative to its parent, less the distance by which it
+    /// scrolls its children: the point of its parent that the origins of its children are
+    /// relative to.  See `Widget::get_scroll_offset`.
+    fn get_content_origin(&mut self, widget_id: WidgetId) -> Point {
+        let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+        widget.get_origin() - widget.get_scroll_offset()
+    }
+
+    /// Brings the bounds of the `Widget`s that have moved or resized since the last time up to
+    /// date in the `hit_index`, if the origin or size of any `Widget` has changed.
+    fn sync_hit_index(&mut self) {
+        let generation = get_geometry_generation();
+
+        if self.hit_index_generation == Some(generation) {
+            return;
+        }
+
+        for container in self.widgets.iter().skip(1).filter(|x| !x.removed) {
+            let mut widget = container.widget.borrow_mut();
+
+            if !widget.config().take_geometry_changed() {
+                continue;
+            }
+
+            let bounds = widget.get_bounds();
+
+            self.hit_index
+                .entry(container.parent_id)
+                .or_default()
+                .insert(container.widget_id, bounds);
+        }
+
+        self.hit_index_generation = Some(generation);
+    }
+
+    /// Retrieves the origin of a `Widget` in window coordinates.  A `Widget`'s origin is relative
+    /// to its parent, so this adds up the origins of the `Widget` and all of its parents.  Moving a
+    /// parent therefore moves all of its children with it.  The origin of an ID that is not
+    /// valid is (0, 0).
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut child = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(100, 50);
+    ///    child.set_origin(10, 20);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let child_id = widget_store.add_widget_to_parent("Child", Box::new(child), panel_id);
+    ///
+    ///    assert_eq!(widget_store.get_absolute_origin(child_id).x, 110);
+    ///    assert_eq!(widget_store.get_absolute_origin(child_id).y, 70);
+    ///
+    ///    widget_store.get_widget_for_id(panel_id).borrow_mut().set_origin(200, 50);
+    ///    assert_eq!(widget_store.get_absolute_origin(child_id).x, 210);
+    ///    assert_eq!(widget_store.get_children_of(panel_id), vec![child_id]);
+    ///    assert_eq!(widget_store.get_absolute_origin(WidgetId::INVALID).x, 0);
+    ///    assert_eq!(widget_store.get_absolute_origin(WidgetId::from(100)).y, 0);
+    /// # }
+    /// ```
+    pub fn get_absolute_origin(&mut self, widget_id: WidgetId) -> Point {
+        let mut origin = make_origin_point();
+        let mut current_id = widget_id;
+
+        if !self.is_widget_id_valid(widget_id) {
+            return origin;
+        }
+
+        loop {
+            let widget_origin = self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .get_origin();
+
+            origin.x += widget_origin.x;
+            origin.y += widget_origin.y;
+
+            if current_id == WidgetId::ROOT {
+                return origin;
+            }
+
+            current_id = self.get_parent_of(current_id);
+
+            let scroll_offset = self.widgets[current_id.index()]
+                .widget
+                .borrow_mut()
+                .get_scroll_offset();
+
+            origin.x -= scroll_offset.x;
+            origin.y -= scroll_offset.y;
+        }
+    }
+
+    /// Translates a `Point` in window coordinates to a `Point` relative to the origin of the
+    /// specified `Widget`.  The resulting point may be negative, or outside of the `Widget`'s size,
+    /// if the original point lies outside of the `Widget`.  If the ID is not valid, the point is
+    /// returned as it is.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(100, 50);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let point = make_point_i32(120, 60);
+    ///
+    ///    assert_eq!(
+    ///        widget_store.get_widget_local_point(panel_id, point.clone()),
+    ///        make_point_i32(20, 10)
+    ///    );
+    ///    assert_eq!(
+    ///        widget_store.get_widget_local_point(WidgetId::INVALID, point.clone()),
+    ///        point
+    ///    );
+    ///    assert_eq!(
+    ///        widget_store.get_widget_local_point(WidgetId::from(100), point.clone()),
+    ///        point
+    ///    );
+    /// # }
+    /// ```
+    pub fn get_widget_local_point(&mut self, widget_id: WidgetId, point: Point) -> Point {
+        if !self.is_widget_id_valid(widget_id) {
+            return point;
+        }
+
+        let origin = self.get_absolute_origin(widget_id);
+
+        make_point_i32(point.x - origin.x, point.y - origin.y)
+    }
+
+    /// Scrolls each parent of a `Widget` that scrolls its children, such as a
+    /// `ScrollContainerWidget`, as little as possible to bring the `Widget` into view.  A
+    /// `Widget` that is larger than the viewport of its parent is scrolled to its top left
+    /// corner.  This is useful for things like scrolling to the selected row of a list.  Does
+    /// nothing if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::scroll_container_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut container = ScrollContainerWidget::new();
+    ///    let mut row = CanvasWidget::new();
+    ///
+    ///    container.set_size(100, 100);
+    ///    container.set_content_size(90, 1000);
+    ///    row.set_origin(0, 500);
+    ///    row.set_size(100, 20);
+    ///
+    ///    let container_id = widget_store.add_widget("Container", Box::new(container));
+    ///    let row_id = widget_store.add_widget_to_parent("Row", Box::new(row), container_id);
+    ///
+    ///    widget_store.ensure_visible(row_id);
+    ///    assert_eq!(widget_store.get_absolute_origin(row_id).y, 80);
+    ///
+    ///    // A widget that is already in view is left where it is.
+    ///    widget_store.ensure_visible(row_id);
+    ///    assert_eq!(widget_store.get_absolute_origin(row_id).y, 80);
+    ///
+    ///    widget_store.ensure_visible(WidgetId::INVALID);
+    ///    widget_store.ensure_visible(WidgetId::from(100));
+    ///    assert_eq!(widget_store.get_absolute_origin(row_id).y, 80);
+    /// # }
+    /// ```
+    pub fn ensure_visible(&mut self, widget_id: WidgetId) {
+        let mut current_id = widget_id;
+
+        if !self.is_widget_id_valid(widget_id) {
+            return;
+        }
+
+        while current_id > WidgetId::ROOT {
+            current_id = self.get_parent_of(current_id);
+
+            let bounds = self.get_bounds(widget_id);
+            let parent_origin = self.get_absolute_origin(current_id);
+            let mut parent = self.widgets[current_id.index()].widget.borrow_mut();
+            let offset = parent.get_scroll_offset();
+            let [view_x, view_y, view_w, view_h] = parent.get_viewport_rect();
+
+            // The bounds of the widget in the coordinates of the content of the parent.
+            let x = bounds.origin.x - parent_origin.x + offset.x - view_x;
+            let y = bounds.origin.y - parent_origin.y + offset.y - view_y;
+
+            parent.scroll_to(
+                scroll_into_view(offset.x, x, bounds.size.w, view_w),
+                scroll_into_view(offset.y, y, bounds.size.h, view_h),
+            );
+        }
+    }
+
+    /// Retrieves the ID of the `Widget` that has keyboard focus, or `WidgetId::INVALID` if no
+    /// `Widget` has focus.
+    pub fn get_focused_widget(&self) -> WidgetId {
+        self.focused_widget_id
+    }
+
+    /// Gives keyboard focus to the `Widget` with the specified ID.  The previously focused `Widget`
+    /// is sent a `LostFocus` event, followed by a `GotFocus` event to the newly focused `Widget`.
+    /// These events are dispatched by the `Pushrod` run loop.  Specifying `WidgetId::INVALID`, the
+    /// base widget (`WidgetId::ROOT`), an ID that does not exist or has been removed, or a disabled
+    /// `Widget` clears the focus.  While a modal dialog is open, the focus is kept inside of it
+    /// instead: see `show_modal`.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let widget_id = widget_store.add_widget("Canvas", Box::new(CanvasWidget::new()));
+    ///
+    ///    widget_store.set_focused_widget(widget_id);
+    ///    assert_eq!(widget_store.get_focused_widget(), widget_id);
+    ///
+    ///    widget_store.set_focused_widget(WidgetId::ROOT);
+    ///    assert_eq!(widget_store.get_focused_widget(), WidgetId::INVALID);
+    ///    assert_eq!(widget_store.take_pending_events().len(), 2);
+    /// # }
+    /// ```
+    pub fn set_focused_widget(&mut self, widget_id: WidgetId) {
+        let widget_id = if widget_id > WidgetId::ROOT
+            && self.is_widget_id_valid(widget_id)
+            && self.is_widget_enabled(widget_id)
+        {
+            widget_id
+        } else {
+            WidgetId::INVALID
+        };
+
+        // The topmost modal dialog keeps the focus when it would otherwise be cleared, or given
+        // to its scrim.
+        let widget_id = match self.modals.last() {
+            Some(modal) if !widget_id.is_valid() || widget_id == modal.scrim_id => modal.dialog_id,
+            _ => widget_id,
+        };
+
+        if widget_id == self.focused_widget_id {
+            return;
+        }
+
+        let previous_widget_id = self.focused_widget_id;
+
+        // The focus indicator moves with the focus, from one widget to the other.
+        if self.focus_indicator_visible {
+            self.invalidate_focused_widget();
+        }
+
+        self.focused_widget_id = widget_id;
+
+        if self.focus_indicator_visible {
+            self.invalidate_focused_widget();
+        }
+
+        if previous_widget_id.is_valid() {
+            self.pending_events.push((
+                previous_widget_id,
+                CallbackEvent::LostFocus {
+                    widget_id: previous_widget_id,
+                },
+            ));
+        }
+
+        if widget_id.is_valid() {
+            self.pending_events
+                .push((widget_id, CallbackEvent::GotFocus { widget_id }));
+        }
+    }
+
+    /// Returns the focus ring: the IDs of all visible, enabled `Widget`s that can receive focus
+    /// using Tab and Shift+Tab, in the order in which they were added.
+    pub fn get_focus_ring(&mut self) -> Vec<WidgetId> {
+        self.widgets
+            .iter()
+            .skip(1)
+            .filter(|x| x.widget.borrow_mut().is_focusable())
+            .map(|x| x.widget_id)
+            .collect::<Vec<WidgetId>>()
+            .into_iter()
+            .filter(|x| self.is_widget_visible(*x) && self.is_widget_enabled(*x))
+            .collect()
+    }
+
+    /// Moves the focus to the next `Widget` in the focus ring, wrapping around to the first after
+    /// the last.  If no `Widget` in the ring has focus, the first one receives it.  Does nothing if
+    /// the focus ring is empty.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut ids = Vec::new();
+    ///
+    ///    for name in &["First", "Skipped", "Last"] {
+    ///        let mut widget = CanvasWidget::new();
+    ///
+    ///        widget.set_focusable(*name != "Skipped");
+    ///        ids.push(widget_store.add_widget(name, Box::new(widget)));
+    ///    }
+    ///
+    ///    widget_store.focus_next();
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[0]);
+    ///
+    ///    widget_store.focus_next();
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[2]);
+    ///
+    ///    widget_store.focus_next();
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[0]);
+    ///
+    ///    widget_store.focus_previous();
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[2]);
+    /// # }
+    /// ```
+    pub fn focus_next(&mut self) {
+        self.move_focus(true);
+    }
+
+    /// Moves the focus to the previous `Widget` in the focus ring, wrapping around to the last
+    /// before the first.  If no `Widget` in the ring has focus, the last one receives it.  Does
+    /// nothing if the focus ring is empty.
+    pub fn focus_previous(&mut self) {
+        self.move_focus(false);
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let ring = self.get_focus_ring();
+
+        if ring.is_empty() {
+            return;
+        }
+
+        let len = ring.len();
+        let next = match ring.iter().position(|x| *x == self.focused_widget_id) {
+            Some(pos) if forward => (pos + 1) % len,
+            Some(pos) => (pos + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+
+        self.set_focused_widget(ring[next]);
+    }
+
+    /// Moves the focus to the nearest `Widget` in the focus ring in `direction` from the focused
+    /// `Widget`, by their bounds, as a gamepad does, scrolling it into view.  If no `Widget` in
+    /// the ring has focus, the first one receives it.  Returns `false` if there is no `Widget`
+    /// in that direction, in which case the focus stays where it is.  See
+    /// `find_focus_in_direction`.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::gamepad::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut ids = Vec::new();
+    ///
+    ///    // A grid of two rows of two buttons.
+    ///    for (name, x, y) in &[("A", 0, 0), ("B", 100, 0), ("C", 0, 100), ("D", 100, 100)] {
+    ///        let mut widget = CanvasWidget::new();
+    ///
+    ///        widget.set_origin(*x, *y);
+    ///        widget.set_size(80, 80);
+    ///        widget.set_focusable(true);
+    ///        ids.push(widget_store.add_widget(name, Box::new(widget)));
+    ///    }
+    ///
+    ///    assert!(widget_store.focus_in_direction(FocusDirection::Down));
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[0]);
+    ///
+    ///    assert!(widget_store.focus_in_direction(FocusDirection::Down));
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[2]);
+    ///
+    ///    assert!(widget_store.focus_in_direction(FocusDirection::Right));
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[3]);
+    ///
+    ///    assert!(!widget_store.focus_in_direction(FocusDirection::Right));
+    ///    assert_eq!(widget_store.get_focused_widget(), ids[3]);
+    /// # }
+    /// ```
+    pub fn focus_in_direction(&mut self, direction: FocusDirection) -> bool {
+        let ring = self.get_focus_ring();
+
+        if !ring.contains(&self.focused_widget_id) {
+            return match ring.first() {
+                Some(first) => {
+                    self.set_focused_widget(*first);
+                    self.ensure_visible(*first);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        let from = self.get_bounds(self.focused_widget_id);
+        let candidates: Vec<(WidgetId, Rect)> = ring
+            .into_iter()
+            .map(|widget_id| (widget_id, self.get_bounds(widget_id)))
+            .collect();
+
+        match find_focus_in_direction(&from, &candidates, direction) {
+            Some(widget_id) => {
+                self.set_focused_widget(widget_id);
+                self.ensure_visible(widget_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets whether the focus indicator is drawn around the focused `Widget`, in the accent
+    /// color of the `Theme`.  The `Pushrod` run loop shows it once a gamepad is used, as there is
+    /// no mouse pointer to show where the user is, and hides it once the mouse is used again.
+    pub fn set_focus_indicator_visible(&mut self, visible: bool) {
+        if visible != self.focus_indicator_visible {
+            self.focus_indicator_visible = visible;
+            self.invalidate_focused_widget();
+        }
+    }
+
+    /// Indicates whether the focus indicator is drawn around the focused `Widget`.
+    pub fn is_focus_indicator_visible(&self) -> bool {
+        self.focus_indicator_visible
+    }
+
+    /// Turns the debug overlay on or off, and redraws the whole window.  See `DebugOverlay`.
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        if enabled != self.debug_overlay.is_enabled() {
+            self.debug_overlay.set_enabled(enabled);
+            self.invalidate_all_widgets();
+        }
+    }
+
+    /// Indicates whether the debug overlay is drawn over the `Widget`s.
+    pub fn is_debug_overlay_enabled(&self) -> bool {
+        self.debug_overlay.is_enabled()
+    }
+
+    /// Retrieves the debug overlay, to set the font of its labels, or to see which `Widget`s it
+    /// highlights.
+    pub fn get_debug_overlay(&mut self) -> &mut DebugOverlay {
+        &mut self.debug_overlay
+    }
+
+    /// Describes the visible `Widget`s to assistive tools, as a tree of `AccessibleNode`s from the
+    /// top level `Widget`, whose role is `AccessibleRole::Window`.  Each node holds the role,
+    /// name, value, and states of a `Widget`, and its bounds in window coordinates, followed by
+    /// its visible children, in the order in which they were added.  The tree can be serialized,
+    /// to hand it to a process that bridges it to the accessibility API of the platform.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::accessibility::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::slider_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut slider = SliderWidget::new();
+    ///    let mut hidden = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(10, 20);
+    ///    panel.set_size(200, 100);
+    ///    panel.set_accessible_role(AccessibleRole::Group);
+    ///    panel.set_accessible_name("Volume");
+    ///    slider.set_origin(5, 5);
+    ///    slider.set_size(150, 20);
+    ///    slider.set_range(0.0, 10.0, 1.0);
+    ///    slider.set_value(4.0);
+    ///    hidden.set_visible(false);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let slider_id = widget_store.add_widget_to_parent("Slider", Box::new(slider), panel_id);
+    ///
+    ///    widget_store.add_widget_to_parent("Hidden", Box::new(hidden), panel_id);
+    ///    widget_store.set_focused_widget(slider_id);
+    ///
+    ///    let tree = widget_store.get_accessibility_tree();
+    ///    let slider = tree.find(slider_id).unwrap();
+    ///
+    ///    assert_eq!(tree.role, AccessibleRole::Window);
+    ///    assert_eq!(tree.children[0].name, "Volume");
+    ///    assert_eq!(tree.children[0].children.len(), 1);
+    ///    assert_eq!(slider.role, AccessibleRole::Slider);
+    ///    assert_eq!(slider.value, Some(String::from("4")));
+    ///    assert!(slider.focusable && slider.focused);
+    ///    assert_eq!((slider.bounds.origin.x, slider.bounds.origin.y), (15, 25));
+    /// # }
+    /// ```
+    pub fn get_accessibility_tree(&mut self) -> AccessibleNode {
+        self.get_accessible_node(WidgetId::ROOT)
+    }
+
+    fn get_accessible_node(&mut self, widget_id: WidgetId) -> AccessibleNode {
+        let mut node = self.describe_widget(widget_id);
+
+        for child_id in self.get_children_of(widget_id) {
+            if child_id != widget_id && self.is_widget_visible(child_id) {
+                node.children.push(self.get_accessible_node(child_id));
+            }
+        }
+
+        node
+    }
+
+    /// Describes a `Widget` to assistive tools, without its children.
+    fn describe_widget(&mut self, widget_id: WidgetId) -> AccessibleNode {
+        let bounds = self.get_bounds(widget_id);
+        let disabled = !self.is_widget_enabled(widget_id);
+        let container = &self.widgets[widget_id.index()];
+        let mut widget = container.widget.borrow_mut();
+        let role = if widget_id.is_root() {
+            AccessibleRole::Window
+        } else {
+            widget.get_accessible_role()
+        };
+
+        AccessibleNode {
+            id: widget_id,
+            widget_name: container.widget_name.clone(),
+            role,
+            name: widget.get_accessible_name(),
+            value: widget.get_accessible_value(),
+            checked: widget.get_accessible_checked(),
+            focusable: widget.is_focusable(),
+            focused: widget_id == self.focused_widget_id,
+            disabled,
+            bounds,
+            children: Vec::new(),
+        }
+    }
+
+    /// Turns on or off posting a `PushrodEvent::AccessibilityChanged` event whenever the
+    /// accessible name, value, or check state of a `Widget` changes, or a `Widget` gets or loses
+    /// the focus, so that a bridge to assistive tools can keep up with the window.  Changes are
+    /// looked for once per frame, before the posted events are delivered, by describing every
+    /// `Widget`, so this is off by default.  Changes to the focus are posted first.  Subscribe to
+    /// them with `MASK_EVENT_ACCESSIBILITY`.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::accessibility::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::event::event::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut checkbox = CanvasWidget::new();
+    ///    let changes = Rc::new(RefCell::new(Vec::new()));
+    ///    let changes_clone = changes.clone();
+    ///
+    ///    checkbox.set_accessible_role(AccessibleRole::CheckBox);
+    ///    checkbox.set_focusable(true);
+    ///
+    ///    let checkbox_id = widget_store.add_widget("Checkbox", Box::new(checkbox));
+    ///
+    ///    widget_store.subscribe(MASK_EVENT_ACCESSIBILITY, Box::new(move |event, _| {
+    ///        if let PushrodEvent::AccessibilityChanged { widget_id, change } = event {
+    ///            changes_clone.borrow_mut().push((*widget_id, change.clone()));
+    ///        }
+    ///    }));
+    ///    widget_store.set_accessibility_events(true);
+    ///
+    ///    widget_store
+    ///        .get_widget_for_id(checkbox_id)
+    ///        .borrow_mut()
+    ///        .config()
+    ///        .set(CheckedState(CheckState::Checked));
+    ///    widget_store.set_focused_widget(checkbox_id);
+    ///    widget_store.deliver_posted_events();
+    ///
+    ///    assert_eq!(
+    ///        *changes.borrow(),
+    ///        vec![
+    ///            (checkbox_id, AccessibleChange::Focused(true)),
+    ///            (checkbox_id, AccessibleChange::Checked(Some(CheckState::Checked))),
+    ///        ]
+    ///    );
+    /// # }
+    /// ```
+    pub fn set_accessibility_events(&mut self, enabled: bool) {
+        if enabled == self.accessibility.is_some() {
+            return;
+        }
+
+        if enabled {
+            self.accessibility = Some(AccessibilityTracker::new(self.focused_widget_id));
+
+            // The state that the `Widget`s start with is not a change.
+            self.track_accessibility();
+        } else {
+            self.accessibility = None;
+        }
+    }
+
+    /// Indicates whether changes to the accessible state of `Widget`s are posted as events.
+    pub fn is_accessibility_events_enabled(&self) -> bool {
+        self.accessibility.is_some()
+    }
+
+    /// Describes every `Widget` that has not been removed, and returns the events for what
+    /// changed about them since the last time, if changes are being posted.
+    fn track_accessibility(&mut self) -> Vec<PushrodEvent> {
+        let mut tracker = match self.accessibility.take() {
+            Some(tracker) => tracker,
+            None => return Vec::new(),
+        };
+        let mut events = Vec::new();
+
+        for (widget_id, change) in tracker.track_focus(self.focused_widget_id) {
+            events.push(PushrodEvent::AccessibilityChanged { widget_id, change });
+        }
+
+        for index in 1..self.widgets.len() {
+            let widget_id = WidgetId::from_index(index);
+
+            if self.widgets[index].removed {
+                tracker.forget(widget_id);
+                continue;
+            }
+
+            let node = self.describe_widget(widget_id);
+
+            for change in tracker.track(&node) {
+                events.push(PushrodEvent::AccessibilityChanged { widget_id, change });
+            }
+        }
+
+        self.accessibility = Some(tracker);
+        events
+    }
+
+    /// Invalidates the focused `Widget`, if there is one, so that the focus indicator around it
+    /// is drawn, or the area it covered is repainted.
+    fn invalidate_focused_widget(&mut self) {
+        if self.focused_widget_id.is_valid() && self.is_widget_id_valid(self.focused_widget_id) {
+            self.widgets[self.focused_widget_id.index()]
+                .widget
+                .borrow_mut()
+                .invalidate();
+        }
+    }
+
+    /// Captures the mouse for the `Widget` with the specified ID.  While the mouse is captured,
+    /// the `Pushrod` run loop sends all mouse move and button events to that `Widget`, wherever
+    /// the mouse is, and no `MouseEntered` or `MouseExited` events are sent.  The run loop
+    /// captures the mouse for a `Widget` when a mouse button is pressed in it, and releases it once
+    /// all of the buttons are released.  The capture is also released if the `Widget` is removed,
+    /// hidden, or disabled.  Returns `false` if the ID is invalid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let slider_id = widget_store.add_widget("Slider", Box::new(CanvasWidget::new()));
+    ///
+    ///    assert!(widget_store.set_mouse_capture(slider_id));
+    ///    assert_eq!(widget_store.get_mouse_capture(), slider_id);
+    ///
+    ///    widget_store.set_widget_disabled(slider_id, true);
+    ///    assert_eq!(widget_store.get_mouse_capture(), WidgetId::INVALID);
+    /// # }
+    /// ```
+    pub fn set_mouse_capture(&mut self, widget_id: WidgetId) -> bool {
+        if !self.is_widget_id_valid(widget_id) {
+            return false;
+        }
+
+        self.captured_widget_id = widget_id;
+        true
+    }
+
+    /// Retrieves the ID of the `Widget` that has captured the mouse, or `WidgetId::INVALID` if the
+    /// mouse is not captured.
+    pub fn get_mouse_capture(&self) -> WidgetId {
+        self.captured_widget_id
+    }
+
+    /// Releases the mouse capture, so the mouse is matched to whichever `Widget` it is over again
+    /// when it next moves.
+    pub fn release_capture(&mut self) {
+        self.captured_widget_id = WidgetId::INVALID;
+    }
+
+    /// Opens the `Widget` with the specified ID as a popup, such as a menu: it is shown, and
+    /// brought in front of its siblings, so a popup that is added to the base widget floats
+    /// above all other `Widget`s.  Popups are stacked in the order in which they are opened, so
+    /// that a submenu is closed along with the menu that opened it.  While any popup is open, a
+    /// mouse button press outside of all of them closes them instead of being delivered; see
+    /// `close_popups_outside`.  Opening a popup that is already open does nothing.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut menu = CanvasWidget::new();
+    ///    let mut submenu = CanvasWidget::new();
+    ///
+    ///    menu.set_origin(10, 10);
+    ///    menu.set_size(100, 100);
+    ///    submenu.set_origin(110, 10);
+    ///    submenu.set_size(100, 100);
+    ///
+    ///    let menu_id = widget_store.add_widget("Menu", Box::new(menu));
+    ///    let submenu_id = widget_store.add_widget("Submenu", Box::new(submenu));
+    ///
+    ///    widget_store.open_popup(menu_id);
+    ///    widget_store.open_popup(submenu_id);
+    ///    assert_eq!(widget_store.get_open_popups(), vec![menu_id, submenu_id]);
+    ///
+    ///    // A press inside of a popup is delivered as usual.
+    ///    assert!(!widget_store.close_popups_outside(make_point_i32(150, 50)));
+    ///
+    ///    // Closing the menu closes its submenu, too.
+    ///    widget_store.close_popup(menu_id);
+    ///    assert!(widget_store.get_open_popups().is_empty());
+    ///    assert!(!widget_store.is_widget_visible(submenu_id));
+    /// # }
+    /// ```
+    pub fn open_popup(&mut self, widget_id: WidgetId) {
+        if widget_id <= WidgetId::ROOT
+            || !self.is_widget_id_valid(widget_id)
+            || self.popups.contains(&widget_id)
+        {
+            return;
+        }
+
+        self.set_widget_visible(widget_id, true);
+        self.bring_to_front(widget_id);
+        self.popups.push(widget_id);
+    }
+
+    /// Closes the popup with the specified ID, along with every popup that was opened after it.
+    /// Each popup is hidden, and sent `CallbackEvent::PopupClosed`, the last one opened first.
+    /// Does nothing if the `Widget` is not an open popup.
+    pub fn close_popup(&mut self, widget_id: WidgetId) {
+        let index = match self.popups.iter().position(|popup_id| *popup_id == widget_id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        for popup_id in self.popups.split_off(index).into_iter().rev() {
+            self.set_widget_visible(popup_id, false);
+            self.pending_events.push((
+                popup_id,
+                CallbackEvent::PopupClosed {
+                    widget_id: popup_id,
+                },
+            ));
+        }
+    }
+
+    /// Closes all of the open popups.  See `close_popup`.
+    pub fn close_all_popups(&mut self) {
+        if let Some(widget_id) = self.popups.first().copied() {
+            self.close_popup(widget_id);
+        }
+    }
+
+    /// Retrieves the IDs of the open popups, in the order in which they were opened.
+    pub fn get_open_popups(&self) -> Vec<WidgetId> {
+        self.popups.clone()
+    }
+
+    /// Closes all of the open popups if `point` is not inside of any of them, or of their
+    /// children, returning whether they were closed.  The `Pushrod` run loop calls this when a
+    /// mouse button is pressed, and drops the press if the popups were closed, so that clicking
+    /// away from a menu only closes it.
+    pub fn close_popups_outside(&mut self, point: Point) -> bool {
+        if self.popups.is_empty() {
+            return false;
+        }
+
+        let inside_popup = self
+            .get_widget_ids_for_point(point)
+            .into_iter()
+            .any(|widget_id| {
+                let mut current_id = widget_id;
+
+                while current_id > WidgetId::ROOT {
+                    if self.popups.contains(&current_id) {
+                        return true;
+                    }
+
+                    current_id = self.get_parent_of(current_id);
+                }
+
+                false
+            });
+
+        if !inside_popup {
+            self.close_all_popups();
+        }
+
+        !inside_popup
+    }
+
+    /// Shows `dialog` as a modal dialog, centered in the window, and returns its ID, so that its
+    /// contents can be added to it as children.  Any open popups are closed, and the mouse is
+    /// released.  A `ModalScrimWidget` is added behind the dialog, covering the window, and
+    /// both are brought in front of all other `Widget`s.  Until the dialog is closed, every
+    /// `Widget` outside of it is treated as disabled (see `is_widget_enabled`), so that it
+    /// receives no mouse or keyboard events, and cannot be focused.  Popups that are opened from
+    /// the dialog, such as the list of a `DropdownWidget`, are not blocked.  The dialog receives
+    /// the focus, which stays on it or its children while it is open.
+    ///
+    /// The dialog is closed with `close_modal`, or by its `DialogCloser` (see
+    /// `DialogWidget::get_closer`), once the event that asked for it has been handled.  A dialog
+    /// that is shown while another one is open is stacked on top of it, and closing a dialog
+    /// closes the dialogs stacked on top of it first, with no result.  Each closed dialog is
+    /// removed, along with its scrim and children, the focus is given back to the `Widget` that
+    /// had it before the dialog was shown, and its `on_closed` callback is called.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::dialog_widget::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn confirm(widget_store: &mut WidgetStore, font: SharedGlyphs) {
+    ///    let mut button = CanvasWidget::new();
+    ///
+    ///    button.set_focusable(true);
+    ///
+    ///    let button_id = widget_store.add_widget("Button", Box::new(button));
+    ///
+    ///    widget_store.set_focused_widget(button_id);
+    ///
+    ///    let dialog_id = widget_store.show_modal(DialogWidget::from_font(font, "Sure?".into()));
+    ///
+    ///    assert_eq!(widget_store.get_open_modals(), vec![dialog_id]);
+    ///    assert_eq!(widget_store.get_focused_widget(), dialog_id);
+    ///    assert!(!widget_store.is_widget_enabled(button_id));
+    ///    assert_eq!(widget_store.get_focus_ring(), vec![dialog_id]);
+    ///
+    ///    widget_store.close_modal(Some("yes"));
+    ///    assert!(widget_store.get_open_modals().is_empty());
+    ///    assert!(widget_store.is_widget_enabled(button_id));
+    ///    assert_eq!(widget_store.get_focused_widget(), button_id);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn show_modal(&mut self, mut dialog: DialogWidget) -> WidgetId {
+        self.close_all_popups();
+        self.release_capture();
+
+        let window_size = self.widgets[0].widget.borrow_mut().get_size();
+        let closer = dialog.get_closer();
+        let scrim_closer = if dialog.is_close_on_scrim_click() {
+            Some(closer.clone())
+        } else {
+            None
+        };
+        let mut scrim = ModalScrimWidget::new(dialog.get_scrim_color(), scrim_closer);
+        let on_closed = dialog.take_on_closed();
+
+        scrim.set_size(window_size.w, window_size.h);
+        dialog.center_in(&window_size);
+
+        let scrim_id = self.add_widget("_ModalScrim", Box::new(scrim));
+        let dialog_id = self.add_widget("_ModalDialog", Box::new(dialog));
+
+        self.bring_to_front(scrim_id);
+        self.bring_to_front(dialog_id);
+        self.modals.push(ModalState {
+            dialog_id,
+            scrim_id,
+            previous_focus_id: self.focused_widget_id,
+            closer,
+            on_closed,
+        });
+        self.set_focused_widget(dialog_id);
+
+        dialog_id
+    }
+
+    /// Closes the topmost modal dialog with `result`, such as the ID of the button that closed
+    /// it, or `None` if it was dismissed.  Does nothing if no dialog is open.  See `show_modal`.
+    pub fn close_modal(&mut self, result: Option<&str>) {
+        if !self.modals.is_empty() {
+            let index = self.modals.len() - 1;
+
+            self.close_modals_from(index, result.map(String::from));
+        }
+    }
+
+    /// Retrieves the IDs of the open modal dialogs, in the order in which they were shown.
+    pub fn get_open_modals(&self) -> Vec<WidgetId> {
+        self.modals.iter().map(|modal| modal.dialog_id).collect()
+    }
+
+    /// Closes the modal dialog at `index` in the stack with `result`, after closing the dialogs
+    /// that were shown after it with no result.
+    fn close_modals_from(&mut self, index: usize, result: Option<String>) {
+        let closed = self.modals.split_off(index);
+
+        for (position, modal) in closed.into_iter().enumerate().rev() {
+            let modal_result = if position == 0 { result.clone() } else { None };
+
+            for widget_id in &[modal.dialog_id, modal.scrim_id] {
+                if self.is_widget_id_valid(*widget_id) {
+                    self.set_widget_visible(*widget_id, false);
+                    self.remove_widget(*widget_id);
+                }
+            }
+
+            self.set_focused_widget(modal.previous_focus_id);
+
+            if let Some(mut callback) = modal.on_closed {
+                callback(modal_result, self);
+            }
+        }
+    }
+
+    /// Closes the modal dialogs that have been asked to close by their `DialogCloser`s.
+    fn sync_modals(&mut self) {
+        while let Some((index, result)) =
+            self.modals.iter().enumerate().find_map(|(index, modal)| {
+                modal.closer.take_request().map(|result| (index, result))
+            })
+        {
+            self.close_modals_from(index, result);
+        }
+    }
+
+    /// Asks for the window to be closed, as if its close button had been pressed.  The close goes
+    /// through the same confirmation as the close button, so it is cancelled if the callback set
+    /// with `Pushrod::on_close_requested` returns `false`.  The request is handled by the
+    /// `Pushrod` run loop before it waits for the next event.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///
+    ///    widget_store.request_close();
+    ///    assert!(widget_store.take_close_request());
+    ///    assert!(!widget_store.take_close_request());
+    /// # }
+    /// ```
+    pub fn request_close(&mut self) {
+        self.close_requested = true;
+    }
+
+    /// Sets the font in which ready-made dialogs, such as `MessageBox`es, are drawn.  The font
+    /// can be loaded with `load_font`, or `Pushrod::set_dialog_font` can be used instead.
+    pub fn set_dialog_font(&mut self, font: SharedGlyphs) {
+        self.dialog_font = Some(font);
+    }
+
+    /// Retrieves the font set with `set_dialog_font`, if any.
+    pub fn get_dialog_font(&self) -> Option<SharedGlyphs> {
+        self.dialog_font.clone()
+    }
+
+    /// Indicates whether or not the window has been asked to close with `request_close`, and
+    /// clears the request.
+    pub fn take_close_request(&mut self) -> bool {
+        std::mem::take(&mut self.close_requested)
+    }
+
+    /// Starts dragging `payload` from the `Widget` with the ID `source_id`.  This is usually
+    /// called in response to a `MouseButtonDown` event.  While the drag is active, the `Pushrod`
+    /// run loop sends `DragEntered`, `DragExited` and `DragMoved` events instead of the normal
+    /// mouse hover events.  Releasing the mouse button calls `end_drag`, and pressing Escape calls
+    /// `cancel_drag`.
+    ///
+    /// If a `ghost` `Widget` is given, it is drawn centered on the mouse pointer for the duration
+    /// of the drag, and lets mouse events pass through.  Every drag puts its ghost in the same
+    /// `Widget` slot of the store, named `_DragGhost`, which is hidden when the drag ends.
+    ///
+    /// If the `Widget` the drag was started from, or the ghost, is removed during the drag, the
+    /// drag is cancelled, and the `DragCancelled` event, with the payload, is sent to the nearest
+    /// parent of the `Widget` the drag was started from that was not removed.
+    ///
+    /// Returns `false`, and does nothing, if a drag is already in progress, or the ID is invalid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut source = CanvasWidget::new();
+    ///    let mut target = CanvasWidget::new();
+    ///
+    ///    source.set_origin(0, 0);
+    ///    source.set_size(100, 100);
+    ///    target.set_origin(200, 0);
+    ///    target.set_size(100, 100);
+    ///
+    ///    let source_id = widget_store.add_widget("Source", Box::new(source));
+    ///    let target_id = widget_store.add_widget("Target", Box::new(target));
+    ///
+    ///    assert!(widget_store.begin_drag(source_id, Box::new(42u32), None));
+    ///    assert!(!widget_store.begin_drag(source_id, Box::new(7u32), None));
+    ///
+    ///    widget_store.drag_to(make_point_i32(250, 50));
+    ///    assert_eq!(widget_store.get_drag_target(), target_id);
+    ///
+    ///    widget_store.end_drag(make_point_i32(250, 50));
+    ///    assert!(!widget_store.is_dragging());
+    ///
+    ///    match widget_store.take_pending_events().pop() {
+    ///        Some((id, CallbackEvent::Dropped { source_id: from, point, payload, .. })) => {
+    ///            assert_eq!(id, target_id);
+    ///            assert_eq!(from, source_id);
+    ///            assert_eq!((point.x, point.y), (50, 50));
+    ///            assert_eq!(*payload.take_as::<u32>().unwrap(), 42);
+    ///        }
+    ///        _ => panic!("Expected a Dropped event"),
+    ///    }
+    /// # }
+    /// ```
+    ///
+    /// Reusing the ghost, and removing the `Widget` that a drag was started from:
+    /// ```
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let list_id = widget_store.add_widget("List", Box::new(CanvasWidget::new()));
+    ///    let row_id =
+    ///        widget_store.add_widget_to_parent("Row", Box::new(CanvasWidget::new()), list_id);
+    ///
+    ///    widget_store.begin_drag(row_id, Box::new(3u32), Some(Box::new(CanvasWidget::new())));
+    ///    widget_store.drag_to(make_point_i32(50, 50));
+    ///
+    ///    let ghost_id = widget_store.get_widget_id_by_name("_DragGhost").unwrap();
+    ///
+    ///    assert!(widget_store.is_widget_visible(ghost_id));
+    ///    widget_store.cancel_drag();
+    ///    assert!(!widget_store.is_widget_visible(ghost_id));
+    ///
+    ///    // The next drag puts its ghost in the same place.
+    ///    widget_store.take_pending_events();
+    ///    widget_store.begin_drag(row_id, Box::new(5u32), Some(Box::new(CanvasWidget::new())));
+    ///    assert_eq!(widget_store.get_widget_id_by_name("_DragGhost"), Some(ghost_id));
+    ///
+    ///    // Removing the row cancels the drag, and returns the payload to the list.
+    ///    widget_store.remove_widget(row_id);
+    ///    widget_store.apply_pending_removals();
+    ///    assert!(!widget_store.is_dragging());
+    ///
+    ///    match widget_store.take_pending_events().pop() {
+    ///        Some((id, CallbackEvent::DragCancelled { payload, .. })) => {
+    ///            assert_eq!(id, list_id);
+    ///            assert_eq!(*payload.take_as::<u32>().unwrap(), 5);
+    ///        }
+    ///        _ => panic!("Expected a DragCancelled event"),
+    ///    }
+    /// # }
+    /// ```
+    pub fn begin_drag(
+        &mut self,
+        source_id: WidgetId,
+        payload: Box<dyn Any>,
+        ghost: Option<Box<dyn Widget>>,
+    ) -> bool {
+        if self.drag.is_some() || !self.is_widget_id_valid(source_id) {
+            return false;
+        }
+
+        let ghost_id = match ghost {
+            Some(mut ghost) => {
+                ghost.set_pass_through(true);
+                ghost.set_visible(false);
+                self.set_drag_ghost(ghost)
+            }
+            None => WidgetId::INVALID,
+        };
+
+        self.release_capture();
+        self.drag = Some(DragState {
+            source_id,
+            payload: DragPayload::new(payload),
+            target_id: WidgetId::INVALID,
+            ghost_id,
+        });
+
+        true
+    }
+
+    /// Puts `ghost` in the slot of the drag ghost, which is added the first time that a drag has
+    /// a ghost, and reused by every drag after it, and moves it above all of the other `Widget`s.
+    /// Returns the ID of the slot.
+    fn set_drag_ghost(&mut self, mut ghost: Box<dyn Widget>) -> WidgetId {
+        let ghost_id = self.drag_ghost_id;
+
+        if self.is_widget_id_valid(ghost_id) {
+            ghost.invalidate();
+            ghost.config().take_changed_keys();
+            ghost.config().take_geometry_changed();
+            self.apply_scale_factor(&mut ghost);
+            self.render_cache.remove(ghost_id);
+            self.hit_index
+                .entry(self.widgets[ghost_id.index()].parent_id)
+                .or_default()
+                .insert(ghost_id, ghost.get_bounds());
+            self.widgets[ghost_id.index()].widget = RefCell::new(ghost);
+        } else {
+            self.drag_ghost_id = self.add_widget("_DragGhost", ghost);
+        }
+
+        self.bring_to_front(self.drag_ghost_id);
+        self.drag_ghost_id
+    }
+
+    /// Hides the ghost of the drag that ended, if it had one, until the next drag.
+    fn hide_drag_ghost(&mut self, ghost_id: WidgetId) {
+        if ghost_id.is_valid() {
+            self.set_widget_visible(ghost_id, false);
+        }
+    }
+
+    /// Cancels the current drag if the `Widget` it was started from, or its ghost, is one of the
+    /// `removed_ids`.  The `DragCancelled` event goes to the nearest parent of the `Widget` the
+    /// drag was started from that was not removed, so that the payload is not lost.  A drag
+    /// target that was removed is forgotten.
+    fn cancel_removed_drag(&mut self, removed_ids: &[WidgetId]) {
+        let drag = match &mut self.drag {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        if removed_ids.contains(&drag.target_id) {
+            drag.target_id = WidgetId::INVALID;
+        }
+
+        if removed_ids.contains(&drag.ghost_id) {
+            drag.ghost_id = WidgetId::INVALID;
+        } else if !removed_ids.contains(&drag.source_id) {
+            return;
+        }
+
+        let mut receiver_id = drag.source_id;
+
+        while removed_ids.contains(&receiver_id) {
+            receiver_id = self.widgets[receiver_id.index()].parent_id;
+        }
+
+        self.cancel_drag_to(receiver_id);
+    }
+
+    /// Indicates whether or not a drag-and-drop operation is in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Retrieves the ID of the `Widget` that the current drag was started from, or
+    /// `WidgetId::INVALID` if no drag is in progress.
+    pub fn get_drag_source(&self) -> WidgetId {
+        self.drag
+            .as_ref()
+            .map(|drag| drag.source_id)
+            .unwrap_or(WidgetId::INVALID)
+    }
+
+    /// Retrieves the ID of the `Widget` that the mouse is over during the current drag, or
+    /// `WidgetId::INVALID` if no drag is in progress.
+    pub fn get_drag_target(&self) -> WidgetId {
+        self.drag
+            .as_ref()
+            .map(|drag| drag.target_id)
+            .unwrap_or(WidgetId::INVALID)
+    }
+
+    /// Moves the current drag to `point`, in window coordinates.  Queues `DragExited` and
+    /// `DragEntered` events if the mouse moved to another `Widget`, followed by a `DragMoved`
+    /// event for the `Widget` the mouse is over, and moves the drag ghost, if there is one.
+    pub fn drag_to(&mut self, point: Point) {
+        let (source_id, target_id, ghost_id) = match &self.drag {
+            Some(drag) => (drag.source_id, drag.target_id, drag.ghost_id),
+            None => return,
+        };
+
+        if ghost_id.is_valid() {
+            let ghost = &mut self.widgets[ghost_id.index()].widget.borrow_mut();
+            let size = ghost.get_size();
+
+            ghost.set_origin(point.x - size.w / 2, point.y - size.h / 2);
+            ghost.set_visible(true);
+        }
+
+        let current_id = self.get_widget_id_for_point(point.clone());
+
+        if current_id != target_id {
+            if target_id.is_valid() {
+                self.pending_events.push((
+                    target_id,
+                    CallbackEvent::DragExited {
+                        widget_id: target_id,
+                        source_id,
+                    },
+                ));
+            }
+
+            if current_id.is_valid() {
+                self.pending_events.push((
+                    current_id,
+                    CallbackEvent::DragEntered {
+                        widget_id: current_id,
+                        source_id,
+                    },
+                ));
+            }
+
+            if let Some(drag) = &mut self.drag {
+                drag.target_id = current_id;
+            }
+        }
+
+        if current_id.is_valid() {
+            let local_point = self.get_widget_local_point(current_id, point);
+
+            self.pending_events.push((
+                current_id,
+                CallbackEvent::DragMoved {
+                    widget_id: current_id,
+                    source_id,
+                    point: local_point,
+                },
+            ));
+        }
+    }
+
+    /// Drops the current drag at `point`, in window coordinates.  If the point is over a `Widget`
+    /// other than the base widget, a `Dropped` event is queued for that `Widget`.  Otherwise, the
+    /// drag is cancelled, as with `cancel_drag`.  The drag ghost, if any, is hidden.
+    pub fn end_drag(&mut self, point: Point) {
+        self.drag_to(point.clone());
+
+        let target_id = self.get_drag_target();
+
+        if target_id <= WidgetId::ROOT || !self.is_widget_id_valid(target_id) {
+            self.cancel_drag();
+            return;
+        }
+
+        let drag = match self.drag.take() {
+            Some(drag) => drag,
+            None => return,
+        };
+        let local_point = self.get_widget_local_point(target_id, point);
+
+        self.hide_drag_ghost(drag.ghost_id);
+        self.pending_events.push((
+            target_id,
+            CallbackEvent::Dropped {
+                widget_id: target_id,
+                source_id: drag.source_id,
+                point: local_point,
+                payload: drag.payload,
+            },
+        ));
+    }
+
+    /// Cancels the current drag.  A `DragExited` event is queued for the `Widget` the mouse is
+    /// over, followed by a `DragCancelled` event for the `Widget` the drag was started from,
+    /// which returns the payload to it.  The drag ghost, if any, is hidden.
+    pub fn cancel_drag(&mut self) {
+        let source_id = self.get_drag_source();
+
+        self.cancel_drag_to(source_id);
+    }
+
+    /// Cancels the current drag, as with `cancel_drag`, but sends the `DragCancelled` event to
+    /// `receiver_id`.
+    fn cancel_drag_to(&mut self, receiver_id: WidgetId) {
+        let drag = match self.drag.take() {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        self.hide_drag_ghost(drag.ghost_id);
+
+        if drag.target_id.is_valid() {
+            self.pending_events.push((
+                drag.target_id,
+                CallbackEvent::DragExited {
+                    widget_id: drag.target_id,
+                    source_id: drag.source_id,
+                },
+            ));
+        }
+
+        self.pending_events.push((
+            receiver_id,
+            CallbackEvent::DragCancelled {
+                widget_id: receiver_id,
+                payload: drag.payload,
+            },
+        ));
+    }
+
+    /// Lays out the children of every `Widget` that is a layout manager, such as the
+    /// `HorizontalLayoutWidget`, with `Widget::layout_children`, and moves every other `Widget`
+    /// that has percentage dimensions or anchors to where they place it in its parent (see
+    /// `Widget::set_size_dimensions` and `Widget::set_anchor`).
+    /// Parents are laid out before their children, so layouts and anchors can be nested.  Only
+    /// the origins and sizes that have changed are set, so this is cheap to call when nothing
+    /// has changed.  The `Pushrod` run loop calls this after every event, and before every
+    /// frame.  See the `HorizontalLayoutWidget` for an example.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut status = CanvasWidget::new();
+    ///
+    ///    widget_store.get_widget_for_id(WidgetId::ROOT).borrow_mut().set_size(400, 300);
+    ///    status.set_size(0, 20);
+    ///    status.set_anchor(AnchorEdge::Left, 0);
+    ///    status.set_anchor(AnchorEdge::Right, 0);
+    ///    status.set_anchor(AnchorEdge::Bottom, 0);
+    ///
+    ///    let status_id = widget_store.add_widget("Status", Box::new(status));
+    ///
+    ///    widget_store.apply_layouts();
+    ///    assert_eq!(widget_store.get_widget_for_id(status_id).borrow_mut().get_origin().y, 280);
+    ///
+    ///    // The window is resized.
+    ///    widget_store.get_widget_for_id(WidgetId::ROOT).borrow_mut().set_size(600, 400);
+    ///    widget_store.apply_layouts();
+    ///
+    ///    let status = widget_store.get_widget_for_id(status_id);
+    ///
+    ///    assert_eq!(status.borrow_mut().get_origin().y, 380);
+    ///    assert_eq!(status.borrow_mut().get_size().w, 600);
+    /// # }
+    /// ```
+    pub fn apply_layouts(&mut self) {
+        let mut laid_out: HashSet<WidgetId> = HashSet::new();
+
+        for id in self.get_draw_order() {
+            if id != WidgetId::ROOT && !laid_out.contains(&id) {
+                let parent_id = self.get_parent_of(id);
+                let parent_size = self.widgets[parent_id.index()]
+                    .widget
+                    .borrow_mut()
+                    .get_size();
+                let mut widget = self.widgets[id.index()].widget.borrow_mut();
+                let origin_dimensions = widget.get_origin_dimensions();
+                let size_dimensions = widget.get_size_dimensions();
+                let anchors = widget.get_anchors();
+
+                // Percentages are resolved first, so that anchors win over them.
+                if origin_dimensions.is_some() || size_dimensions.is_some() || !anchors.is_empty()
+                {
+                    let origin = widget.get_origin();
+                    let size = widget.get_size();
+                    let bounds = resolve_dimensions(
+                        origin_dimensions,
+                        size_dimensions,
+                        &parent_size,
+                        [origin.x, origin.y, size.w, size.h],
+                    );
+
+                    set_widget_bounds(&mut **widget, anchors.resolve(&parent_size, bounds));
+                }
+            }
+
+            let children: Vec<LayoutChild> = self
+                .get_children_of(id)
+                .into_iter()
+                .filter_map(|child_id| {
+                    let mut child = self.widgets[child_id.index()].widget.borrow_mut();
+
+                    if !child.is_visible() {
+                        return None;
+                    }
+
+                    Some(LayoutChild {
+                        widget_id: child_id,
+                        preferred_size: child.get_preferred_size(),
+                        margin: child.get_margin(),
+                        weight: child.get_layout_weight(),
+                        grid_cell: child.get_grid_cell(),
+                    })
+                })
+                .collect();
+
+            if children.is_empty() {
+                continue;
+            }
+
+            let bounds = self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .layout_children(&children);
+
+            for (child, bounds) in children.iter().zip(bounds) {
+                let mut widget = self.widgets[child.widget_id.index()].widget.borrow_mut();
+
+                set_widget_bounds(&mut **widget, bounds);
+                laid_out.insert(child.widget_id);
+            }
+        }
+    }
+
+    /// Queues a `ConfigChanged` event for each `Widget` whose configuration was changed since
+    /// the last call, listing the keys that changed.  Changes made before a `Widget` is added
+    /// are not reported.  This is called by the `Pushrod` run loop once per event.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut canvas = CanvasWidget::new();
+    ///
+    ///    canvas.set_color([1.0, 0.0, 0.0, 1.0]);
+    ///
+    ///    let canvas_id = widget_store.add_widget("Canvas", Box::new(canvas));
+    ///
+    ///    widget_store.queue_config_changes();
+    ///    assert!(widget_store.take_pending_events().is_empty());
+    ///
+    ///    widget_store.get_widget_for_id(canvas_id).borrow_mut().set_origin(10, 10);
+    ///    widget_store.get_widget_for_id(canvas_id).borrow_mut().set_origin(20, 20);
+    ///    widget_store.queue_config_changes();
+    ///
+    ///    match widget_store.take_pending_events().pop() {
+    ///        Some((widget_id, CallbackEvent::ConfigChanged { keys, .. })) => {
+    ///            assert_eq!(widget_id, canvas_id);
+    ///            assert_eq!(keys, vec!["Origin"]);
+    ///        }
+    ///        _ => panic!("Expected a ConfigChanged event"),
+    ///    }
+    /// # }
+    /// ```
+    pub fn queue_config_changes(&mut self) {
+        for container in self.widgets.iter().filter(|container| !container.removed) {
+            let keys = container.widget.borrow_mut().config().take_changed_keys();
+
+            if !keys.is_empty() {
+                self.pending_events.push((
+                    container.widget_id,
+                    CallbackEvent::ConfigChanged {
+                        widget_id: container.widget_id,
+                        keys,
+                    },
+                ));
+            }
+        }
+    }
+
+    /// Removes and returns the events that are waiting to be dispatched, in the order in which
+    /// they were generated, along with the ID of the `Widget` each is addressed to.
+    pub fn take_pending_events(&mut self) -> Vec<(WidgetId, CallbackEvent)> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// Returns the name of the widget by its ID.
+    pub fn get_name_for_widget_id(&mut self, widget_id: WidgetId) -> &str {
+        self.widgets[widget_id.index()].widget_name.as_str()
+    }
+
+    /// Handles event messages, returning an event if provided by the `Widget`.
+    pub fn handle_event(
+        &mut self,
+        widget_id: WidgetId,
+        event: CallbackEvent,
+    ) -> Option<CallbackEvent> {
+        let result = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .handle_event(event);
+
+        self.sync_radio_group(widget_id);
+        self.sync_tab_pages(widget_id);
+        self.release_hidden_focus();
+        self.sync_modals();
+        result
+    }
+
+    /// Handles a mouse button, scroll, key, or text event for a `Widget` with the `Widget`'s
+    /// `handle_input_event` method, returning whether the event should propagate any further,
+    /// and the event that the `Widget` produced in response, if any.  The text of a text event is
+    /// given to the `Widget`'s `text_input` method first.
+    pub fn handle_input_event(
+        &mut self,
+        widget_id: WidgetId,
+        event: CallbackEvent,
+    ) -> (EventResult, Option<CallbackEvent>) {
+        let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+        if let CallbackEvent::TextInput { widget_id, text } = &event {
+            widget.text_input(*widget_id, text);
+        }
+
+        let result = widget.handle_input_event(event);
+
+        drop(widget);
+
+        self.sync_radio_group(widget_id);
+        self.sync_tab_pages(widget_id);
+        self.release_hidden_focus();
+        self.sync_modals();
+        result
+    }
+
+    /// Sets the closure that is called with the ID of the newly selected `Widget` whenever the
+    /// selection of the radio group `group` changes, or with `WidgetId::INVALID` when the selected
+    /// `Widget` is removed.  The closure is called once per change, however many `Widget`s are
+    /// unchecked.
+    pub fn on_radio_selection_changed(&mut self, group: &str, callback: RadioSelectionCallback) {
+        self.radio_callbacks.insert(group.to_string(), callback);
+    }
+
+    /// Retrieves the IDs of the `Widget`s in the radio group `group`, in order of ID.  `Widget`s
+    /// are added to a radio group by setting their `RadioGroupId`.
+    pub fn get_radio_group_members(&self, group: &str) -> Vec<WidgetId> {
+        (1..self.widgets.len())
+            .map(WidgetId::from_index)
+            .filter(|widget_id| {
+                self.is_widget_id_valid(*widget_id)
+                    && self.get_radio_group(*widget_id).as_deref() == Some(group)
+            })
+            .collect()
+    }
+
+    /// Retrieves the ID of the checked `Widget` in the radio group `group`, or `WidgetId::INVALID`
+    /// if none of them is checked.
+    pub fn get_selected_radio_button(&self, group: &str) -> WidgetId {
+        self.get_radio_group_members(group)
+            .into_iter()
+            .find(|widget_id| self.is_radio_button_checked(*widget_id))
+            .unwrap_or(WidgetId::INVALID)
+    }
+
+    /// Selects the `Widget` with the specified ID in its radio group: it is checked, and every
+    /// other `Widget` in the group is unchecked, and they are all invalidated so that they are
+    /// redrawn.  Does nothing if the `Widget` does not belong to a radio group.  A radio group
+    /// whose selected `Widget` is removed is left without a selection.
+    ///
+    /// Clicking a `RadioButtonWidget` selects it in the same way.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut ids = Vec::new();
+    ///
+    ///    for name in &["Small", "Medium", "Large"] {
+    ///        let mut radio_button = CanvasWidget::new();
+    ///
+    ///        radio_button.config().set(RadioGroupId("size".to_string()));
+    ///        ids.push(widget_store.add_widget(name, Box::new(radio_button)));
+    ///    }
+    ///
+    ///    let selected = Rc::new(Cell::new(WidgetId::INVALID));
+    ///    let selected_clone = selected.clone();
+    ///
+    ///    widget_store.on_radio_selection_changed(
+    ///        "size",
+    ///        Box::new(move |widget_id| selected_clone.set(widget_id)),
+    ///    );
+    ///
+    ///    widget_store.select_radio_button(ids[0]);
+    ///    widget_store.select_radio_button(ids[1]);
+    ///    assert_eq!(widget_store.get_selected_radio_button("size"), ids[1]);
+    ///    assert_eq!(selected.get(), ids[1]);
+    ///
+    ///    // Removing the selected radio button leaves the group without a selection.
+    ///    widget_store.remove_widget(ids[1]);
+    ///    widget_store.apply_pending_removals();
+    ///    assert_eq!(widget_store.get_selected_radio_button("size"), WidgetId::INVALID);
+    ///    assert_eq!(selected.get(), WidgetId::INVALID);
+    /// # }
+    /// ```
+    pub fn select_radio_button(&mut self, widget_id: WidgetId) {
+        if !self.is_widget_id_valid(widget_id) || self.get_radio_group(widget_id).is_none() {
+            return;
+        }
+
+        {
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+            widget.config().set(CheckedState(CheckState::Checked));
+            widget.invalidate();
+        }
+
+        self.sync_radio_group(widget_id);
+    }
+
+    fn get_radio_group(&self, widget_id: WidgetId) -> Option<String> {
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .config()
+            .get::<RadioGroupId>()
+            .map(|group| group.0.clone())
+    }
+
+    fn is_radio_button_checked(&self, widget_id: WidgetId) -> bool {
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .config()
+            .get::<CheckedState>()
+            .map(|state| state.0 == CheckState::Checked)
+            .unwrap_or(false)
+    }
+
+    /// Called after a `Widget` handles an event: if the `Widget` belongs to a radio group, and
+    /// has just been checked, the other `Widget`s in the group are unchecked, and the radio
+    /// group's callback is called.
+    fn sync_radio_group(&mut self, widget_id: WidgetId) {
+        let group = match self.get_radio_group(widget_id) {
+            Some(group) => group,
+            None => return,
+        };
+
+        if !self.is_radio_button_checked(widget_id)
+            || self.radio_selections.get(&group) == Some(&widget_id)
+        {
+            return;
+        }
+
+        for member_id in self.get_radio_group_members(&group) {
+            if member_id != widget_id && self.is_radio_button_checked(member_id) {
+                let mut widget = self.widgets[member_id.index()].widget.borrow_mut();
+
+                widget.config().set(CheckedState(CheckState::Unchecked));
+                widget.invalidate();
+            }
+        }
+
+        self.radio_selections.insert(group.clone(), widget_id);
+
+        if let Some(callback) = self.radio_callbacks.get_mut(&group) {
+            callback(widget_id);
+        }
+    }
+
+    /// Adds `page` to the `Widget` with the ID `tab_widget_id`, such as a `TabWidget`, as the
+    /// page of a new tab with the title `title`, after the other tabs.  The page is added as a
+    /// child with the name `name`, and is only visible while its tab is selected.  The first tab
+    /// that is added is selected.  Returns the ID of the page.  If `tab_widget_id` is not valid,
+    /// the page is dropped, a `PushrodError::InvalidConfiguration` is reported (see
+    /// `error::report_error`), and `WidgetId::INVALID` is returned.
+    ///
+    /// The pages of a tab widget are its children, in the order in which they were added, and
+    /// their titles are kept in its `TabTitles`; `remove_tab` removes both.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let tabs_id = widget_store.add_widget("Tabs", Box::new(CanvasWidget::new()));
+    ///    let general_id = widget_store.add_tab(
+    ///        tabs_id,
+    ///        "General",
+    ///        "GeneralPage",
+    ///        Box::new(CanvasWidget::new()),
+    ///    );
+    ///    let advanced_id = widget_store.add_tab(
+    ///        tabs_id,
+    ///        "Advanced",
+    ///        "AdvancedPage",
+    ///        Box::new(CanvasWidget::new()),
+    ///    );
+    ///    let about_id =
+    ///        widget_store.add_tab(tabs_id, "About", "AboutPage", Box::new(CanvasWidget::new()));
+    ///
+    ///    assert_eq!(widget_store.get_selected_tab(tabs_id), Some(0));
+    ///    assert!(widget_store.is_widget_visible(general_id));
+    ///    assert!(!widget_store.is_widget_visible(advanced_id));
+    ///
+    ///    widget_store.select_tab(tabs_id, 1);
+    ///    assert!(!widget_store.is_widget_visible(general_id));
+    ///    assert!(widget_store.is_widget_visible(advanced_id));
+    ///
+    ///    // Removing the selected tab selects the tab that takes its place.
+    ///    widget_store.remove_tab(tabs_id, 1);
+    ///    widget_store.apply_pending_removals();
+    ///    assert_eq!(widget_store.get_tab_pages(tabs_id), vec![general_id, about_id]);
+    ///    assert_eq!(widget_store.get_selected_tab(tabs_id), Some(1));
+    ///    assert!(widget_store.is_widget_visible(about_id));
+    /// # }
+    /// ```
+    ///
+    /// Adding a tab to a `Widget` that does not exist:
+    /// ```
+    /// # use pushrod::core::error::*;
+    /// # use pushrod::core::test_harness::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut harness = TestHarness::new(400, 300);
+    ///    let page_id = harness.get_widget_store().add_tab(
+    ///        WidgetId::from(100),
+    ///        "General",
+    ///        "GeneralPage",
+    ///        Box::new(CanvasWidget::new()),
+    ///    );
+    ///
+    ///    assert_eq!(page_id, WidgetId::INVALID);
+    ///    assert_eq!(harness.get_widget_store().get_widget_id_by_name("GeneralPage"), None);
+    ///
+    ///    harness.advance(10);
+    ///    assert_eq!(
+    ///        harness.take_errors(),
+    ///        vec![PushrodError::InvalidConfiguration(String::from(
+    ///            "Cannot add the tab 'General' to widget 100, which does not exist"
+    ///        ))]
+    ///    );
+    /// # }
+    /// ```
+    pub fn add_tab(
+        &mut self,
+        tab_widget_id: WidgetId,
+        title: &str,
+        name: &str,
+        page: Box<dyn Widget>,
+    ) -> WidgetId {
+        if !self.is_widget_id_valid(tab_widget_id) {
+            report_error(PushrodError::InvalidConfiguration(format!(
+                "Cannot add the tab '{}' to widget {}, which does not exist",
+                title, tab_widget_id
+            )));
+            return WidgetId::INVALID;
+        }
+
+        let page_id = self.add_widget_to_parent(name, page, tab_widget_id);
+
+        {
+            let mut widget = self.widgets[tab_widget_id.index()].widget.borrow_mut();
+            let mut titles = widget
+                .config()
+                .get::<TabTitles>()
+                .map(|titles| titles.0.clone())
+                .unwrap_or_default();
+
+            titles.push(title.to_string());
+            widget.config().set(TabTitles(titles));
+
+            if widget.config().get::<SelectedTab>().is_none() {
+                widget.config().set(SelectedTab(0));
+            }
+
+            widget.invalidate();
+        }
+
+        self.sync_tab_pages(tab_widget_id);
+        page_id
+    }
+
+    /// Removes the tab at `index` from the `Widget` with the ID `tab_widget_id`, along with its
+    /// page; see `remove_widget`.  If the selected tab is removed, the tab that takes its place
+    /// is selected, or the one before it if it was the last tab.  Does nothing if there is no
+    /// tab at `index`.
+    pub fn remove_tab(&mut self, tab_widget_id: WidgetId, index: usize) {
+        let page_id = match self.get_tab_pages(tab_widget_id).get(index) {
+            Some(page_id) => *page_id,
+            None => return,
+        };
+
+        self.remove_widget(page_id);
+
+        {
+            let mut widget = self.widgets[tab_widget_id.index()].widget.borrow_mut();
+            let mut titles = widget
+                .config()
+                .get::<TabTitles>()
+                .map(|titles| titles.0.clone())
+                .unwrap_or_default();
+            let selected = widget.config().get_or(SelectedTab(0)).0;
+
+            if index < titles.len() {
+                titles.remove(index);
+            }
+
+            if titles.is_empty() {
+                widget.config().remove::<SelectedTab>();
+            } else if index < selected || selected >= titles.len() {
+                widget.config().set(SelectedTab(selected - 1));
+            }
+
+            widget.config().set(TabTitles(titles));
+            widget.invalidate();
+        }
+
+        self.sync_tab_pages(tab_widget_id);
+    }
+
+    /// Selects the tab at `index` of the `Widget` with the ID `tab_widget_id`, showing its page,
+    /// and hiding the others.  Does nothing if there is no tab at `index`.
+    pub fn select_tab(&mut self, tab_widget_id: WidgetId, index: usize) {
+        if index >= self.get_tab_pages(tab_widget_id).len() {
+            return;
+        }
+
+        {
+            let mut widget = self.widgets[tab_widget_id.index()].widget.borrow_mut();
+
+            widget.config().set(SelectedTab(index));
+            widget.invalidate();
+        }
+
+        self.sync_tab_pages(tab_widget_id);
+    }
+
+    /// Retrieves the index of the selected tab of the `Widget` with the ID `tab_widget_id`, or
+    /// `None` if it has no tabs, or if the ID is not valid.
+    pub fn get_selected_tab(&self, tab_widget_id: WidgetId) -> Option<usize> {
+        if !self.is_widget_id_valid(tab_widget_id) {
+            return None;
+        }
+
+        self.widgets[tab_widget_id.index()]
+            .widget
+            .borrow_mut()
+            .config()
+            .get::<SelectedTab>()
+            .map(|selected| selected.0)
+    }
+
+    /// Retrieves the IDs of the pages of the `Widget` with the ID `tab_widget_id`, in the order
+    /// of its tabs.  Pages that are about to be removed are left out.
+    pub fn get_tab_pages(&self, tab_widget_id: WidgetId) -> Vec<WidgetId> {
+        self.get_children_of(tab_widget_id)
+            .into_iter()
+            .filter(|page_id| {
+                *page_id != WidgetId::ROOT && !self.pending_removals.contains(page_id)
+            })
+            .collect()
+    }
+
+    /// Called after a `Widget` handles an event: if the `Widget` has tabs, the page of its
+    /// selected tab is shown, and its other pages are hidden.
+    fn sync_tab_pages(&mut self, widget_id: WidgetId) {
+        let selected = {
+            let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+            if widget.config().get::<TabTitles>().is_none() {
+                return;
+            }
+
+            widget.config().get::<SelectedTab>().map(|selected| selected.0)
+        };
+
+        for (index, page_id) in self.get_tab_pages(widget_id).into_iter().enumerate() {
+            let visible = Some(index) == selected;
+
+            if self.widgets[page_id.index()]
+                .widget
+                .borrow_mut()
+                .is_visible()
+                != visible
+            {
+                self.set_widget_visible(page_id, visible);
+            }
+        }
+    }
+
+    /// Called after a `Widget` handles an event: if the `Widget` that has keyboard focus, or that
+    /// has captured the mouse, has been hidden by a parent that was collapsed, the focus is
+    /// cleared, or the capture is released.
+    fn release_hidden_focus(&mut self) {
+        if self.focused_widget_id.is_valid() && !self.is_widget_visible(self.focused_widget_id) {
+            self.set_focused_widget(WidgetId::INVALID);
+        }
+
+        if self.captured_widget_id.is_valid() && !self.is_widget_visible(self.captured_widget_id) {
+            self.release_capture();
+        }
+    }
+
+    /// Sends an event to every `Widget`, in order of ID, returning the events that the `Widget`s
+    /// produced in response.  Removed `Widget`s are skipped.
+    pub fn broadcast_event(&mut self, event: CallbackEvent) -> Vec<CallbackEvent> {
+        self.widgets
+            .iter_mut()
+            .filter(|x| !x.removed)
+            .filter_map(|x| x.widget.borrow_mut().handle_event(event.clone()))
+            .collect()
+    }
+
+    pub fn set_color(&mut self, widget_id: WidgetId, color: types::Color) {
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .set_color(color);
+    }
+
+    /// Calls `update` on every `Widget` in the store, regardless of invalidation.  This is called
+    /// by the `Pushrod` main loop for every update event.  After all `Widget`s have been updated,
+    /// any `WidgetStoreCallback`s queued by the `Widget`s are called in order, with access to this
+    /// store.
+    pub fn update(&mut self, delta_ms: u64) {
+        self.widgets
+            .iter_mut()
+            .for_each(|x| x.widget.borrow_mut().update(delta_ms));
+
+        self.run_deferred_callbacks();
+        self.sync_modals();
+    }
+
+    /// Collects the `WidgetStoreCallback`s that have been queued by all `Widget`s, and calls them.
+    /// No `Widget` is borrowed while the callbacks are called.
+    pub fn run_deferred_callbacks(&mut self) {
+        let deferred: Vec<DeferredCallback> = self
+            .widgets
+            .iter()
+            .flat_map(|x| x.widget.borrow_mut().take_deferred_callbacks())
+            .collect();
+
+        for callback in deferred {
+            (callback.borrow_mut())(self);
+        }
+    }
+
+    /// Returns the number of `Widget`s that were drawn by the last call to `draw`.  A static
+    /// screen draws no `Widget`s at all.
+    pub fn get_draw_count(&self) -> u32 {
+        self.draw_count
+    }
+
+    /// Indicates whether the next call to `draw` would paint anything: a visible `Widget` has been
+    /// invalidated, a `Widget` has disappeared from where it was last drawn, or the last frame
+    /// still has to be painted into the other display buffer.  This is used by the lazy rendering
+    /// mode of `Pushrod` to skip frames that would not change the screen.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(0, 0);
+    ///    panel.set_size(100, 100);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    assert!(widget_store.needs_redraw());
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    assert!(widget_store.needs_redraw());
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    assert!(!widget_store.needs_redraw());
+    ///
+    ///    widget_store.get_widget_for_id(panel_id).borrow_mut().set_color([1.0, 0.0, 0.0, 1.0]);
+    ///    assert!(widget_store.needs_redraw());
+    /// # }
+    /// ```
+    pub fn needs_redraw(&mut self) -> bool {
+        if !self.repaint_next_frame.is_empty()
+            || !self.removed_bounds.is_empty()
+            || (self.debug_overlay.is_enabled() && self.debug_overlay.has_flashes())
+        {
+            return true;
+        }
+
+        for id in (0..self.widgets.len()).map(WidgetId::from_index) {
+            if !self.is_widget_visible(id) {
+                if self.widgets[id.index()].drawn_bounds.is_some() {
+                    return true;
+                }
+
+                continue;
+            }
+
+            if self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .is_invalidated()
+                && self.get_clip_bounds(id).is_some()
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Indicates whether `point`, in window coordinates, is on a `Widget`: inside of its bounds,
+    /// and, if it leaves its rounded corners out of hit-testing, not in one of them.  See
+    /// `Widget::set_rounded_hit_test`.
+    fn is_point_on_widget(&mut self, widget_id: WidgetId, point: &Point) -> bool {
+        let bounds = self.get_bounds(widget_id);
+
+        if !bounds.contains(point) {
+            return false;
+        }
+
+        let mut widget = self.widgets[widget_id.index()].widget.borrow_mut();
+
+        !widget.is_rounded_hit_test()
+            || point_in_rounded_rect(
+                &(point.clone() - bounds.origin),
+                &bounds.size,
+                widget.get_corner_radius(),
+            )
+    }
+
+    /// Returns the bounds of a `Widget` in window coordinates.
+    fn get_bounds(&mut self, widget_id: WidgetId) -> Rect {
+        let origin = self.get_absolute_origin(widget_id);
+        let size = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .get_size();
+
+        Rect { origin, size }
+    }
+
+    /// Returns how far the drop shadow of a `Widget` reaches outside of its bounds, on each side,
+    /// or the focus indicator around it, if that reaches further.
+    fn get_shadow_extent(&mut self, widget_id: WidgetId) -> Insets {
+        let extent = match self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .get_shadow()
+        {
+            Some(shadow) => shadow.get_extent(),
+            None => Insets::default(),
+        };
+
+        if self.focus_indicator_visible && widget_id == self.focused_widget_id {
+            let ring = FOCUS_INDICATOR_GAP + FOCUS_INDICATOR_THICKNESS;
+
+            make_insets(
+                extent.top.max(ring),
+                extent.right.max(ring),
+                extent.bottom.max(ring),
+                extent.left.max(ring),
+            )
+        } else {
+            extent
+        }
+    }
+
+    /// Indicates whether the `Widget`s underneath a `Widget` show through it, because it is
+    /// translucent, or casts a translucent shadow.  Whatever shows through has to be painted
+    /// again before such a `Widget` is, or its translucent parts would build up each time.
+    fn is_see_through(&mut self, widget_id: WidgetId) -> bool {
+        self.get_effective_opacity(widget_id) < 1.0
+            || self.widgets[widget_id.index()]
+                .widget
+                .borrow_mut()
+                .get_shadow()
+                .is_some()
+    }
+
+    /// Returns the area of the window in which the children of a `Widget` can be seen: its
+    /// viewport (see `Widget::get_viewport_rect`), in window coordinates.
+    fn get_viewport_bounds(&mut self, widget_id: WidgetId) -> Rect {
+        let origin = self.get_absolute_origin(widget_id);
+        let viewport = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .get_viewport_rect();
+
+        Rect::from(viewport).translate(&origin)
+    }
+
+    /// Returns the area of the window that a `Widget` is allowed to draw in: its bounds, grown to
+    /// include its drop shadow, if it has one, clipped to the viewport of each of its parents.  A
+    /// `Widget` that is not clipped (see `Widget::set_clipped`) may draw anywhere within its
+    /// parents, or anywhere in the window if it has no parent.  Returns `None` if there is no
+    /// area left to draw in, or if the ID is not valid.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut child = CanvasWidget::new();
+    ///    let mut outside = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(100, 100);
+    ///    panel.set_size(100, 100);
+    ///    child.set_origin(50, 50);
+    ///    child.set_size(100, 100);
+    ///    outside.set_origin(150, 0);
+    ///    outside.set_size(10, 10);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///    let child_id = widget_store.add_widget_to_parent("Child", Box::new(child), panel_id);
+    ///    let outside_id = widget_store.add_widget_to_parent("Outside", Box::new(outside), panel_id);
+    ///
+    ///    assert_eq!(widget_store.get_clip_bounds(child_id), Some(make_rect(150, 150, 50, 50)));
+    ///    assert_eq!(widget_store.get_clip_bounds(outside_id), None);
+    ///    assert_eq!(widget_store.get_clip_bounds(WidgetId::INVALID), None);
+    ///    assert_eq!(widget_store.get_clip_bounds(WidgetId::from(100)), None);
+    /// # }
+    /// ```
+    pub fn get_clip_bounds(&mut self, widget_id: WidgetId) -> Option<Rect> {
+        if !self.is_widget_id_valid(widget_id) {
+            return None;
+        }
+
+        let mut clip = if self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .is_clipped()
+        {
+            let extent = self.get_shadow_extent(widget_id);
+
+            self.get_bounds(widget_id).outset(&extent)
+        } else {
+            self.get_bounds(WidgetId::ROOT)
+        };
+        let mut current_id = widget_id;
+
+        while current_id != WidgetId::ROOT {
+            current_id = self.get_parent_of(current_id);
+
+            if current_id != WidgetId::ROOT {
+                clip = clip.intersection(&self.get_viewport_bounds(current_id))?;
+            }
+        }
+
+        if clip.is_empty() {
+            None
+        } else {
+            Some(clip)
+        }
+    }
+
+    /// Determines which `Widget`s need to be painted in the next frame, starting from `widget_id`
+    /// and its children, and returns them in draw order, along with the area of each that needs
+    /// painting, in window coordinates.  The invalidation flag of each
+    /// `Widget` that is returned is cleared.
+    ///
+    /// The following are painted:
+    ///
+    /// - Invalidated `Widget`s
+    /// - The areas of `Widget`s that are covered by a `Widget` painted before them, drawn above
+    /// - The areas of `Widget`s underneath a `Widget` that moved, resized, or was hidden
+    /// - Anything painted in the previous frame, so both buffers of the window are up to date
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///    let mut small = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(0, 0);
+    ///    panel.set_size(100, 100);
+    ///    small.set_origin(200, 200);
+    ///    small.set_size(20, 20);
+    ///
+    ///    widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    let small_id = widget_store.add_widget("Small", Box::new(small));
+    ///
+    ///    // Everything is painted in the first two frames, then nothing while the screen is static.
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT).len(), 3);
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT).len(), 3);
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT).len(), 0);
+    ///
+    ///    // Changing one widget only paints that widget.
+    ///    let small_paint = vec![(small_id, make_rect(200, 200, 20, 20))];
+    ///
+    ///    widget_store.get_widget_for_id(small_id).borrow_mut().set_color([1.0, 0.0, 0.0, 1.0]);
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT), small_paint);
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT), small_paint);
+    ///    assert_eq!(widget_store.prepare_draw(WidgetId::ROOT).len(), 0);
+    /// # }
+    /// ```
+    pub fn prepare_draw(&mut self, widget_id: WidgetId) -> Vec<(WidgetId, Rect)> {
+        let mut draw_order = vec![widget_id];
+        let mut damage: Vec<Rect> = Vec::new();
+        let mut paint_list: Vec<(WidgetId, Rect)> = Vec::new();
+
+        self.append_draw_order(widget_id, &mut draw_order);
+
+        // Areas that were left behind by widgets that moved, resized, disappeared, or were removed.
+        damage.append(&mut self.removed_bounds);
+
+        for id in (0..self.widgets.len()).map(WidgetId::from_index) {
+            let drawn_bounds = match self.widgets[id.index()].drawn_bounds.clone() {
+                Some(drawn_bounds) => drawn_bounds,
+                None => continue,
+            };
+
+            if !self.is_widget_visible(id) {
+                damage.push(drawn_bounds);
+                self.widgets[id.index()].drawn_bounds = None;
+            } else if self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .is_invalidated()
+                && (self.get_clip_bounds(id).as_ref() != Some(&drawn_bounds)
+                    || self.is_see_through(id))
+            {
+                // Whatever shows through a translucent widget, or its shadow, has to be repainted
+                // underneath it.
+                damage.push(drawn_bounds);
+            }
+        }
+
+        for id in draw_order {
+            if !self.is_widget_visible(id) {
+                continue;
+            }
+
+            if self.get_effective_opacity(id) <= 0.0 {
+                // Nothing of a fully transparent widget, or of its children, can be seen.
+                self.widgets[id.index()]
+                    .widget
+                    .borrow_mut()
+                    .set_invalidated(false);
+                self.widgets[id.index()].drawn_bounds = None;
+                continue;
+            }
+
+            let bounds = match self.get_clip_bounds(id) {
+                Some(bounds) => bounds,
+                None => {
+                    // Nothing of the widget can be seen, so there is nothing to draw.
+                    self.widgets[id.index()]
+                        .widget
+                        .borrow_mut()
+                        .set_invalidated(false);
+                    self.widgets[id.index()].drawn_bounds = None;
+                    continue;
+                }
+            };
+
+            if self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .is_invalidated()
+            {
+                match self.get_invalidated_bounds(id, &bounds) {
+                    Some(area) => {
+                        paint_list.push((id, area.clone()));
+                        damage.push(area);
+                    }
+                    None => self.widgets[id.index()]
+                        .widget
+                        .borrow_mut()
+                        .set_invalidated(false),
+                }
+
+                continue;
+            }
+
+            let covered = damage
+                .iter()
+                .filter_map(|area| bounds.intersection(area))
+                .fold(None, |acc: Option<Rect>, area| match acc {
+                    Some(acc) => Some(acc.union(&area)),
+                    None => Some(area),
+                });
+
+            if let Some(covered) = covered {
+                paint_list.push((id, covered.clone()));
+                damage.push(covered);
+            }
+        }
+
+        // Anything painted in the last frame is painted again, in its place in the draw order.
+        let carried = std::mem::replace(&mut self.repaint_next_frame, paint_list.clone());
+
+        for (id, area) in carried {
+            if !self.is_widget_id_valid(id) || !self.is_widget_visible(id) {
+                continue;
+            }
+
+            match paint_list.iter_mut().find(|(paint_id, _)| *paint_id == id) {
+                Some(entry) => entry.1 = entry.1.union(&area),
+                None => paint_list.push((id, area)),
+            }
+        }
+
+        let full_order = self.get_draw_order();
+
+        paint_list.sort_by_key(|(id, _)| full_order.iter().position(|x| x == id));
+
+        for (id, _) in &paint_list {
+            let bounds = self.get_clip_bounds(*id);
+            let container = &mut self.widgets[id.index()];
+
+            container.widget.borrow_mut().set_invalidated(false);
+            container.drawn_bounds = bounds;
+        }
+
+        paint_list
+    }
+
+    /// Returns the part of the visible `bounds` of an invalidated `Widget` that has to be painted,
+    /// in window coordinates: only the area set with `Widget::invalidate_area`, if any, or else
+    /// all of `bounds`.  Anything underneath a translucent `Widget`, or one that casts a shadow, is
+    /// repainted as a whole, so such a `Widget` is always painted as a whole.  Returns `None` if
+    /// the invalidated area cannot be seen.
+    fn get_invalidated_bounds(&mut self, widget_id: WidgetId, bounds: &Rect) -> Option<Rect> {
+        let area = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .get_invalidated_area();
+
+        match area {
+            Some(area) if !self.is_see_through(widget_id) => {
+                let origin = self.get_absolute_origin(widget_id);
+
+                bounds.intersection(&Rect::from(area).translate(&origin))
+            }
+            _ => Some(bounds.clone()),
+        }
+    }
+
+    /// Draws every `Widget` that is set to be cached (see `Widget::set_cached`) into its offscreen
+    /// texture, if it has been invalidated or resized since it was last drawn, or if its texture
+    /// has been refreshed with `refresh_render_cache`.  Textures are sized in device pixels, using
+    /// the scale factor of the window (see `set_scale_factor`), and are created again when that
+    /// changes.  Textures of `Widget`s that are no longer cached are freed.  This is called by the
+    /// `Pushrod` main loop before each frame is drawn, and must be called outside of a `draw_2d`
+    /// closure.
+    pub fn update_render_cache(&mut self, window: &mut PistonWindow) {
+        let pixel_scale = self.scale_factor;
+
+        for id in (0..self.widgets.len()).map(WidgetId::from_index) {
+            if self.widgets[id.index()].removed
+                || !self.widgets[id.index()].widget.borrow_mut().is_cached()
+            {
+                self.render_cache.remove(id);
+                continue;
+            }
+
+            if !self.is_widget_visible(id) {
+                continue;
+            }
+
+            let size = self.widgets[id.index()].widget.borrow_mut().get_size();
+
+            if size.w <= 0 || size.h <= 0 {
+                self.render_cache.remove(id);
+                continue;
+            }
+
+            let pixel_size = [
+                (f64::from(size.w) * pixel_scale).ceil() as u32,
+                (f64::from(size.h) * pixel_scale).ceil() as u32,
+            ];
+            let widget = &self.widgets[id.index()].widget;
+
+            if !widget.borrow_mut().is_invalidated()
+                && !self.render_cache.needs_render(id, pixel_size)
+            {
+                continue;
+            }
+
+            self.render_cache.render(window, id, &size, pixel_size, |c, g| {
+                set_draw_opacity(1.0);
+                widget.borrow_mut().draw(c, &mut DrawTarget::Window(g), &c.draw_state);
+            });
+
+            // Drawing clears the invalidated state, which is set again so that the new texture is
+            // copied to the screen.
+            widget.borrow_mut().set_invalidated(true);
+        }
+    }
+
+    /// Forces a cached `Widget` to be drawn into its offscreen texture again on the next frame,
+    /// for cases where what it draws has changed without the `Widget` being invalidated.  Does
+    /// nothing for a `Widget` that is not cached.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut panel = CanvasWidget::new();
+    ///
+    ///    panel.set_origin(0, 0);
+    ///    panel.set_size(100, 100);
+    ///    panel.set_cached(true);
+    ///
+    ///    let panel_id = widget_store.add_widget("Panel", Box::new(panel));
+    ///
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    assert!(!widget_store.needs_redraw());
+    ///
+    ///    widget_store.refresh_render_cache(panel_id);
+    ///    assert!(widget_store.needs_redraw());
+    /// # }
+    /// ```
+    pub fn refresh_render_cache(&mut self, widget_id: WidgetId) {
+        if !self.is_widget_id_valid(widget_id)
+            || !self.widgets[widget_id.index()]
+                .widget
+                .borrow_mut()
+                .is_cached()
+        {
+            return;
+        }
+
+        self.render_cache.mark_stale(widget_id);
+        self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .invalidate();
+    }
+
+    /// Forces every cached `Widget` to be drawn into its offscreen texture again on the next
+    /// frame.
+    pub fn refresh_all_render_caches(&mut self) {
+        for id in (0..self.widgets.len()).map(WidgetId::from_index) {
+            self.refresh_render_cache(id);
+        }
+    }
+
+    /// Retrieves the `RenderCache` that holds the offscreen textures of cached `Widget`s.
+    pub fn get_render_cache(&self) -> &RenderCache {
+        &self.render_cache
+    }
+
+    /// Draw object: paints objects in order of appearance on the screen.  Objects' parents are
+    /// displayed in stacking order.  Therefore, the parent is drawn first, then its children in
+    /// ascending z-order (see `set_z_order`), each followed by its own children.  Only the
+    /// `Widget`s that need to be painted are drawn, clipped to the area that needs painting, which
+    /// never extends outside of the `Widget`'s clip bounds; see `prepare_draw` and
+    /// `get_clip_bounds`.  Each `Widget` is drawn with its effective opacity set as the draw
+    /// opacity; see `get_effective_opacity` and `apply_opacity`.  This draw function is used by
+    /// the `Pushrod` main loop, and is meant to be called in a `draw_2d` closure.
+    ///
+    /// When the debug overlay is enabled, the `Widget`s that have been invalidated are flashed,
+    /// and every `Widget` is drawn, so that the overlay can be drawn over all of them afterward.
+    pub fn draw(&mut self, widget_id: WidgetId, c: Context, g: &mut DrawTarget) {
+        let debug_overlay = self.debug_overlay.is_enabled();
+
+        if debug_overlay {
+            self.flash_invalidated_widgets();
+            self.invalidate_all_widgets();
+        }
+
+        let paint_list = self.prepare_draw(widget_id);
+
+        self.draw_count = paint_list.len() as u32;
+
+        for (paint_id, area) in &paint_list {
+            self.paint_widget(*paint_id, area.clone(), &make_origin_point(), c, g);
+        }
+
+        set_draw_opacity(1.0);
+        self.draw_focus_indicator(&paint_list, c, g);
+
+        if debug_overlay {
+            self.draw_debug_overlay(c, g);
+        }
+    }
+
+    /// Flashes every visible `Widget` that has been invalidated since the last frame in the
+    /// debug overlay.
+    fn flash_invalidated_widgets(&mut self) {
+        for id in (1..self.widgets.len()).map(WidgetId::from_index) {
+            if !self.widgets[id.index()].removed
+                && self.widgets[id.index()]
+                    .widget
+                    .borrow_mut()
+                    .is_invalidated()
+                && self.is_widget_visible(id)
+            {
+                self.debug_overlay.flash(id);
+            }
+        }
+    }
+
+    /// Draws the debug overlay over every visible `Widget` but the top level `Widget`.
+    fn draw_debug_overlay(&mut self, c: Context, g: &mut DrawTarget) {
+        let mut widgets = Vec::new();
+
+        for id in self.get_draw_order() {
+            if id != WidgetId::ROOT && self.is_widget_visible(id) {
+                widgets.push((id, self.get_bounds(id)));
+            }
+        }
+
+        let containers = &self.widgets;
+        let widgets: Vec<(WidgetId, &str, Rect)> = widgets
+            .into_iter()
+            .map(|(id, bounds)| (id, containers[id.index()].widget_name.as_str(), bounds))
+            .collect();
+
+        self.debug_overlay.draw(&widgets, self.scale_factor, c, g);
+    }
+
+    /// Draws the focus indicator around the focused `Widget`, if it is shown, on top of
+    /// everything else, wherever it was painted over in this frame.
+    fn draw_focus_indicator(
+        &mut self,
+        paint_list: &[(WidgetId, Rect)],
+        c: Context,
+        g: &mut DrawTarget,
+    ) {
+        let widget_id = self.focused_widget_id;
+
+        if !self.focus_indicator_visible
+            || !widget_id.is_valid()
+            || !self.is_widget_visible(widget_id)
+        {
+            return;
+        }
+
+        let clip_bounds = match self.get_clip_bounds(widget_id) {
+            Some(clip_bounds) => clip_bounds,
+            None => return,
+        };
+        let area = paint_list
+            .iter()
+            .filter_map(|(_, area)| clip_bounds.intersection(area))
+            .fold(None, |acc: Option<Rect>, area| match acc {
+                Some(acc) => Some(acc.union(&area)),
+                None => Some(area),
+            });
+        let area = match area {
+            Some(area) => area,
+            None => return,
+        };
+
+        let outset = FOCUS_INDICATOR_GAP + FOCUS_INDICATOR_THICKNESS;
+        let bounds = self
+            .get_bounds(widget_id)
+            .outset(&make_uniform_insets(outset));
+        let rect = [
+            f64::from(bounds.origin.x),
+            f64::from(bounds.origin.y),
+            f64::from(bounds.size.w),
+            f64::from(bounds.size.h),
+        ];
+        let radius = self.widgets[widget_id.index()]
+            .widget
+            .borrow_mut()
+            .get_corner_radius();
+        let color = self.theme.accent_color;
+        let clip = c.draw_state.scissor(scale_bounds(&area, self.scale_factor));
+
+        if radius > 0.0 {
+            stroke_rounded_rect(
+                color,
+                rect,
+                radius + f64::from(outset),
+                f64::from(FOCUS_INDICATOR_THICKNESS),
+                &clip,
+                c.transform,
+                g,
+            );
+        } else {
+            stroke_border(
+                color,
+                rect,
+                &make_uniform_insets(FOCUS_INDICATOR_THICKNESS),
+                BorderStyle::Solid,
+                &clip,
+                c.transform,
+                g,
+            );
+        }
+    }
+
+    /// Draws a `Widget` at its place in the window, clipped to `area`, from its cached texture if
+    /// it has one.  Its drop shadow, if it has one, is drawn underneath it first.  The `Widget`
+    /// itself is still clipped to its own bounds, even though `area` may include its shadow.
+    /// `area` is in window coordinates, and `offset` moves it, along with the bounds of the
+    /// `Widget`, into the coordinates of what is drawn to, which is moved by the same amount
+    /// in `c`.
+    fn paint_widget(
+        &mut self,
+        paint_id: WidgetId,
+        area: Rect,
+        offset: &Point,
+        c: Context,
+        g: &mut DrawTarget,
+    ) {
+        let origin: Point = self.get_absolute_origin(paint_id);
+        let new_context: Context = Context {
+            viewport: c.viewport,
+            view: c.view,
+            transform: c.transform.trans(origin.x as f64, origin.y as f64),
+            draw_state: c.draw_state,
+        };
+
+        let area = area.translate(offset);
+        let bounds = self.get_bounds(paint_id).translate(offset);
+
+        // The scissor is set in device pixels, while the area is in points.
+        let clip: DrawState = c.draw_state.scissor(scale_bounds(&area, self.scale_factor));
+
+        set_draw_opacity(self.get_effective_opacity(paint_id));
+
+        let mut widget = self.widgets[paint_id.index()].widget.borrow_mut();
+
+        if let Some(shadow) = widget.get_shadow() {
+            let shadow = Shadow {
+                color: apply_opacity(shadow.color),
+                ..shadow
+            };
+
+            shadow.draw(
+                &bounds.size,
+                widget.get_corner_radius(),
+                &clip,
+                new_context.transform,
+                g,
+            );
+        }
+
+        let clip = if widget.is_clipped() {
+            match area.intersection(&bounds) {
+                Some(content) => c.draw_state.scissor(scale_bounds(&content, self.scale_factor)),
+                None => return,
+            }
+        } else {
+            clip
+        };
+
+        match self.render_cache.get_texture(paint_id) {
+            Some(texture) if widget.is_cached() => {
+                let size = widget.get_size();
+                let (texture_w, texture_h) = texture.get_size();
+                let (texture_w, texture_h) = (f64::from(texture_w), f64::from(texture_h));
+
+                // Render targets are stored bottom row first, so the texture is flipped as it
+                // is copied.
+                Image::new_color(apply_opacity([1.0; 4]))
+                    .src_rect([0.0, texture_h, texture_w, -texture_h])
+                    .rect([0.0, 0.0, f64::from(size.w), f64::from(size.h)])
+                    .draw(texture, &clip, new_context.transform, g);
+            }
+
+            _ => widget.draw(new_context, g, &clip),
+        }
+    }
+
+    /// Draws a `Widget`, and all of its visible children, whether or not they have been
+    /// invalidated, for a screenshot.  The `Context` is moved so that the top left of the
+    /// `Widget` is at its origin.  The invalidated state of the `Widget`s is left as it was, so
+    /// the next frame on the screen is drawn as if nothing had happened.  See
+    /// `Pushrod::capture_widget`.
+    pub fn draw_for_capture(&mut self, widget_id: WidgetId, c: Context, g: &mut DrawTarget) {
+        let origin = self.get_absolute_origin(widget_id);
+        let capture_context = c.trans(-f64::from(origin.x), -f64::from(origin.y));
+        let offset = make_point_i32(-origin.x, -origin.y);
+        let mut draw_order = vec![widget_id];
+
+        self.append_draw_order(widget_id, &mut draw_order);
+
+        for id in draw_order {
+            if !self.is_widget_visible(id) || self.get_effective_opacity(id) <= 0.0 {
+                continue;
+            }
+
+            let bounds = match self.get_clip_bounds(id) {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            let invalidated = self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .is_invalidated();
+
+            self.paint_widget(id, bounds, &offset, capture_context, g);
+            self.widgets[id.index()]
+                .widget
+                .borrow_mut()
+                .set_invalidated(invalidated);
+        }
+
+        set_draw_opacity(1.0);
+    }
+
+    /// Retrieves a widget by the name when the widget was added.  To get the very top-level
+    /// widget, refer to `_WidgetStoreBase`.
+    pub fn get_widget_for_name(&mut self, name: &str) -> &RefCell<Box<dyn Widget>> {
+        let widget_id = match self.widgets
+            .iter_mut()
+            .find(|x| !x.removed && x.widget_name == String::from(name)) {
             Some(x) => x.widget_id,
-            None => 0,
+            None => WidgetId::ROOT,
         };
 
         self.get_widget_for_id(widget_id)
     }
 
     /// Retrieves a reference to the `Box`ed `Widget` object by its ID.  To get the very top-level
-    /// widget, specify ID 0.
-    pub fn get_widget_for_id(&mut self, id: i32) -> &RefCell<Box<dyn Widget>> {
-        &self.widgets[id as usize].widget
+    /// widget, specify `WidgetId::ROOT`.
+    pub fn get_widget_for_id(&mut self, id: WidgetId) -> &RefCell<Box<dyn Widget>> {
+        &self.widgets[id.index()].widget
+    }
+}
+
+/// Returns the scroll offset, nearest to `offset`, at which the span from `start` to
+/// `start + length` fits in a viewport of `view_length`, or starts at its beginning if it does not.
+fn scroll_into_view(offset: i32, start: i32, length: i32, view_length: i32) -> i32 {
+    if start < offset || length > view_length {
+        start
+    } else if start + length > offset + view_length {
+        start + length - view_length
+    } else {
+        offset
+    }
+}
+
+/// Sets the origin and size of a `Widget` to `bounds`, leaving whichever of them has not changed
+/// alone, so that the `Widget` is not invalidated for nothing.
+fn set_widget_bounds(widget: &mut dyn Widget, bounds: [i32; 4]) {
+    let origin = widget.get_origin();
+    let size = widget.get_size();
+
+    if origin.x != bounds[0] || origin.y != bounds[1] {
+        widget.set_origin(bounds[0], bounds[1]);
+    }
+
+    if size.w != bounds[2] || size.h != bounds[3] {
+        widget.set_size(bounds[2], bounds[3]);
     }
 }
+
+/// Converts bounds in points to device pixels (X, Y, width, height), using the number of device
+/// pixels per point in `scale`.  The result covers every pixel that the bounds touch.
+fn scale_bounds(bounds: &Rect, scale: f64) -> [u32; 4] {
+    let x1 = (f64::from(bounds.origin.x.max(0)) * scale).floor();
+    let y1 = (f64::from(bounds.origin.y.max(0)) * scale).floor();
+    let x2 = (f64::from(bounds.get_right().max(0)) * scale).ceil();
+    let y2 = (f64::from(bounds.get_bottom().max(0)) * scale).ceil();
+
+    [x1 as u32, y1 as u32, (x2 - x1) as u32, (y2 - y1) as u32]
+}
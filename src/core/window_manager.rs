@@ -0,0 +1,106 @@
+// Window Manager
+// Runs several Pushrod windows from a single event loop.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::callbacks::*;
+use crate::core::main::*;
+
+/// A window that is run by the `WindowManager`, along with the handler for its events.
+struct ManagedWindow {
+    pushrod: Pushrod,
+    event_handler: Box<dyn PushrodCallbackEvents>,
+}
+
+/// This is the `WindowManager`, which runs several `Pushrod` windows in one application, such as
+/// a main window and a tool palette.  Each window keeps its own `WidgetStore`, so `Widget` IDs
+/// and names are scoped to the window that they were added to, and each window has its own
+/// `PushrodCallbackEvents` handler, which only receives the events of that window.
+///
+/// Windows are identified by the ID that is returned when they are added.  Closing a window
+/// removes only that window: the others keep running, and `run` returns once every window has
+/// been closed.
+pub struct WindowManager {
+    windows: Vec<Option<ManagedWindow>>,
+}
+
+/// Implementation of the `WindowManager`.
+impl WindowManager {
+    /// Creates a new `WindowManager` with no windows.
+    pub fn new() -> Self {
+        Self {
+            windows: Vec::new(),
+        }
+    }
+
+    /// Adds a window, along with the handler that receives its events.  Returns the ID of the
+    /// window, which is never reused, even once the window has been closed.
+    pub fn add_window(
+        &mut self,
+        pushrod: Pushrod,
+        event_handler: Box<dyn PushrodCallbackEvents>,
+    ) -> usize {
+        self.windows.push(Some(ManagedWindow {
+            pushrod,
+            event_handler,
+        }));
+
+        self.windows.len() - 1
+    }
+
+    /// Retrieves the window with the specified ID, or `None` if it has been closed.
+    pub fn get_window(&mut self, window_id: usize) -> Option<&mut Pushrod> {
+        self.windows
+            .get_mut(window_id)?
+            .as_mut()
+            .map(|window| &mut window.pushrod)
+    }
+
+    /// Retrieves the number of windows that are still open.
+    pub fn get_window_count(&self) -> usize {
+        self.windows.iter().filter(|window| window.is_some()).count()
+    }
+
+    /// Runs all of the windows until every one of them has been closed.  Each window's events are
+    /// handled in turn, one event at a time, so that no window holds up the others.  The events
+    /// of a window are dispatched to its own `WidgetStore` and event handler, exactly as
+    /// `Pushrod::run` would.  A window that is closed is dropped, along with its `Widget`s.
+    pub fn run(&mut self) {
+        while self.get_window_count() > 0 {
+            for slot in self.windows.iter_mut() {
+                let closed = match slot {
+                    Some(window) => match window.pushrod.next_event() {
+                        Some(event) => {
+                            window
+                                .pushrod
+                                .handle_window_event(&event, window.event_handler.as_mut());
+                            false
+                        }
+                        None => true,
+                    },
+                    None => false,
+                };
+
+                if closed {
+                    *slot = None;
+                }
+            }
+        }
+    }
+}
+
+impl Default for WindowManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -16,8 +16,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::core::accessibility::AccessibleChange;
 use crate::core::point::Point;
+use crate::core::widget_id::*;
 use piston_window::*;
+use std::any::Any;
+use std::sync::Arc;
 
 /// Pushrod Event Mask type (`u32`).  Used to define an event mask.  Event masks are hexadecimal masks
 /// identifying which events to listen for, in binary order.  When defining a new event that
@@ -42,8 +46,27 @@ pub const MASK_EVENT_MOUSE_SCROLL: EventMask = 0x00000008;
 /// All mouse events mask.
 pub const MASK_EVENT_MOUSE_ALL: EventMask = 0x0000000F;
 
+/// Widget clicked event mask.
+pub const MASK_EVENT_CLICKED: EventMask = 0x00000010;
+
+/// Widget value changed event mask.
+pub const MASK_EVENT_VALUE_CHANGED: EventMask = 0x00000020;
+
+/// Timer triggered event mask.
+pub const MASK_EVENT_TIMER: EventMask = 0x00000040;
+
+/// Application-defined event mask.
+pub const MASK_EVENT_CUSTOM: EventMask = 0x00000080;
+
+/// All widget and application events mask.
+pub const MASK_EVENT_WIDGET_ALL: EventMask = 0x000000F0;
+
+/// Accessibility change event mask.  See `WidgetStore::set_accessibility_events`.
+pub const MASK_EVENT_ACCESSIBILITY: EventMask = 0x00000100;
+
 /// All events mask.  (Use this carefully)
-pub const MASK_EVENT_ALL: EventMask = MASK_EVENT_MOUSE_ALL;
+pub const MASK_EVENT_ALL: EventMask =
+    MASK_EVENT_MOUSE_ALL | MASK_EVENT_WIDGET_ALL | MASK_EVENT_ACCESSIBILITY;
 
 /// Enumeration types for different `PushrodEvents`, translated from the underlying OS.
 #[derive(Clone)]
@@ -61,6 +84,62 @@ pub enum PushrodEvent {
     /// positive direction indicates movement to the right, where negative is to the left.
     /// Y movement in a positive direction indicates downward movement, negative is upward.
     MouseScrollEvent { point: Point },
+
+    /// A `Widget` was clicked, such as a `PushButtonWidget`.
+    Clicked { widget_id: WidgetId },
+
+    /// The value of a `Widget` changed, such as the state of a `ToggleButtonWidget`, which is 1.0
+    /// when selected, and 0.0 otherwise.
+    ValueChanged { widget_id: WidgetId, value: f64 },
+
+    /// A `TimerWidget` that posts its events fired.  See `TimerWidget::set_post_events`.
+    TimerTriggered { widget_id: WidgetId },
+
+    /// The accessible name, value, check state, or focus of a `Widget` changed.  These are only
+    /// posted once they are turned on with `WidgetStore::set_accessibility_events`.
+    AccessibilityChanged {
+        widget_id: WidgetId,
+        change: AccessibleChange,
+    },
+
+    /// An application-defined event, identified by its `name`, with any payload.  The payload
+    /// can be read back with `payload.downcast_ref::<T>()`.
+    Custom {
+        name: String,
+        payload: Arc<dyn Any + Send + Sync>,
+    },
+}
+
+/// Implementation of the `PushrodEvent`.
+impl PushrodEvent {
+    /// Returns the event mask that matches this event, which is used to decide which subscribers
+    /// and `EventListener`s receive it.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::event::event::*;
+    /// # fn main() {
+    ///    let event = PushrodEvent::TimerTriggered { widget_id: WidgetId::from(3) };
+    ///
+    ///    assert_eq!(event.get_mask(), MASK_EVENT_TIMER);
+    ///    assert_ne!(event.get_mask() & MASK_EVENT_WIDGET_ALL, 0);
+    ///    assert_eq!(event.get_mask() & MASK_EVENT_MOUSE_ALL, 0);
+    /// # }
+    /// ```
+    pub fn get_mask(&self) -> EventMask {
+        match self {
+            PushrodEvent::MouseEvent { .. } => MASK_EVENT_MOUSE_MOVED,
+            PushrodEvent::MouseDownEvent { .. } => MASK_EVENT_MOUSE_DOWN,
+            PushrodEvent::MouseUpEvent { .. } => MASK_EVENT_MOUSE_UP,
+            PushrodEvent::MouseScrollEvent { .. } => MASK_EVENT_MOUSE_SCROLL,
+            PushrodEvent::Clicked { .. } => MASK_EVENT_CLICKED,
+            PushrodEvent::ValueChanged { .. } => MASK_EVENT_VALUE_CHANGED,
+            PushrodEvent::TimerTriggered { .. } => MASK_EVENT_TIMER,
+            PushrodEvent::AccessibilityChanged { .. } => MASK_EVENT_ACCESSIBILITY,
+            PushrodEvent::Custom { .. } => MASK_EVENT_CUSTOM,
+        }
+    }
 }
 
 /// Implement this trait to register for system-wide events.  Only implement this if you plan
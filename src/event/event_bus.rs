@@ -0,0 +1,206 @@
+// Event Bus
+// Queues events posted by widgets and the application, and delivers them to subscribers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+
+use crate::core::widget_store::*;
+use crate::event::event::*;
+
+/// Default number of events that can be waiting in the channel of an `EventSender` before
+/// sending blocks.  See `EventBus::set_channel_capacity`.
+pub const DEFAULT_EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Closure type that is called with every `PushrodEvent` that matches the event mask it was
+/// subscribed with, along with the `WidgetStore`, so that it can change any `Widget`.
+pub type EventBusCallback = Box<dyn FnMut(&PushrodEvent, &mut WidgetStore)>;
+
+/// This is the `EventSender`, a handle that posts `PushrodEvent`s to an `EventBus` from any
+/// thread, such as a background thread that has finished loading data.  It is created with
+/// `WidgetStore::get_event_sender`, and can be cloned for as many threads as needed.  Events
+/// that are sent are delivered on the UI thread, in the order they were sent, on the next frame.
+///
+/// The channel behind the sender is bounded, so a thread that produces events faster than the UI
+/// can deliver them cannot use up all of the memory: once the channel is full, `send` waits until
+/// there is room, and `try_send` fails, returning the event.
+///
+/// Example:
+/// ```
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::event::event::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let sender = widget_store.get_event_sender();
+///
+///    thread::spawn(move || {
+///        for percent in 1..=3u16 {
+///            sender.send(PushrodEvent::Custom {
+///                name: "Loaded".to_string(),
+///                payload: Arc::new(percent),
+///            });
+///        }
+///    })
+///    .join()
+///    .unwrap();
+///
+///    widget_store.subscribe(MASK_EVENT_CUSTOM, Box::new(|event, _widget_store| {
+///        if let PushrodEvent::Custom { payload, .. } = event {
+///            eprintln!("Loaded {}%", payload.downcast_ref::<u16>().unwrap());
+///        }
+///    }));
+///
+///    assert!(widget_store.has_posted_events());
+///    widget_store.deliver_posted_events();
+///    assert!(!widget_store.has_posted_events());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EventSender {
+    sender: SyncSender<PushrodEvent>,
+}
+
+/// Implementation of the `EventSender`.
+impl EventSender {
+    /// Sends an event, waiting for room in the channel if it is full.  Returns `false` if the
+    /// `EventBus` that the sender belongs to no longer exists, such as when its window has been
+    /// closed, so that the sending thread can stop.
+    pub fn send(&self, event: PushrodEvent) -> bool {
+        self.sender.send(event).is_ok()
+    }
+
+    /// Sends an event if there is room in the channel, returning the event as an error if the
+    /// channel is full, or the `EventBus` no longer exists.
+    pub fn try_send(&self, event: PushrodEvent) -> Result<(), PushrodEvent> {
+        self.sender.try_send(event).map_err(|error| match error {
+            TrySendError::Full(event) | TrySendError::Disconnected(event) => event,
+        })
+    }
+}
+
+/// This is the `EventBus`, which lets `Widget`s and application code communicate through
+/// `PushrodEvent`s, without having to share handles to each other.  Each `WidgetStore` owns an
+/// `EventBus`, so events are posted with `WidgetStore::post_event`, and subscribers are added
+/// with `WidgetStore::subscribe` and `WidgetStore::add_event_listener`.  `Widget`s receive the
+/// events that match their `Widget::event_mask` in `Widget::handle_pushrod_event`.
+///
+/// Posted events are queued, and delivered in the order they were posted by the `Pushrod` run
+/// loop at the start of the next frame (see `WidgetStore::deliver_posted_events`).  An event that
+/// is posted while events are being delivered is delivered on the frame after, so a subscriber
+/// is never called from inside of another subscriber.  Events sent from other threads with an
+/// `EventSender` are delivered the same way, after the events posted on the UI thread.
+pub struct EventBus {
+    queue: Vec<PushrodEvent>,
+    subscribers: Vec<(EventMask, EventBusCallback)>,
+    listeners: Vec<Box<dyn EventListener>>,
+    channel_capacity: usize,
+    channel: Option<(SyncSender<PushrodEvent>, Receiver<PushrodEvent>)>,
+}
+
+/// Implementation of the `EventBus`.
+impl EventBus {
+    /// Creates a new `EventBus` with no events and no subscribers.
+    pub fn new() -> Self {
+        Self {
+            queue: Vec::new(),
+            subscribers: Vec::new(),
+            listeners: Vec::new(),
+            channel_capacity: DEFAULT_EVENT_CHANNEL_CAPACITY,
+            channel: None,
+        }
+    }
+
+    /// Queues an event, to be delivered on the next frame.
+    pub fn post(&mut self, event: PushrodEvent) {
+        self.queue.push(event);
+    }
+
+    /// Returns an `EventSender` that posts events to this bus from any thread.  The channel is
+    /// created the first time this is called, and every sender shares it.
+    pub fn get_sender(&mut self) -> EventSender {
+        let capacity = self.channel_capacity;
+        let (sender, _) = self.channel.get_or_insert_with(|| sync_channel(capacity));
+
+        EventSender {
+            sender: sender.clone(),
+        }
+    }
+
+    /// Sets the number of events that can be waiting in the channel of the `EventSender`s before
+    /// sending blocks.  Defaults to `DEFAULT_EVENT_CHANNEL_CAPACITY`.  This only takes effect if it
+    /// is set before the first call to `get_sender`.
+    pub fn set_channel_capacity(&mut self, capacity: usize) {
+        self.channel_capacity = capacity;
+    }
+
+    /// Moves the events that were sent from other threads, in the order they were sent, to the
+    /// end of the queue of posted events.
+    pub fn receive_sent_events(&mut self) {
+        if let Some((_, receiver)) = &self.channel {
+            self.queue.extend(receiver.try_iter());
+        }
+    }
+
+    /// Indicates whether any events are waiting to be delivered.  Events that were sent from other
+    /// threads are only counted once they have been received with `receive_sent_events`.
+    pub fn has_posted_events(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Returns, and clears, the events that are waiting to be delivered, in the order they were
+    /// posted.
+    pub fn take_posted_events(&mut self) -> Vec<PushrodEvent> {
+        std::mem::take(&mut self.queue)
+    }
+
+    /// Adds a closure that is called for every event that matches the `mask`.
+    pub fn subscribe(&mut self, mask: EventMask, callback: EventBusCallback) {
+        self.subscribers.push((mask, callback));
+    }
+
+    /// Adds an `EventListener`, which receives every event that matches its `event_mask`.
+    pub fn add_listener(&mut self, listener: Box<dyn EventListener>) {
+        self.listeners.push(listener);
+    }
+
+    /// Returns, and clears, the subscribed closures, so that they can be called with the
+    /// `WidgetStore` that owns this bus.  They are put back with `restore_subscribers`.
+    pub fn take_subscribers(&mut self) -> Vec<(EventMask, EventBusCallback)> {
+        std::mem::take(&mut self.subscribers)
+    }
+
+    /// Puts back the closures returned by `take_subscribers`, ahead of any that were subscribed
+    /// while they were taken, so that subscribers are always called in the order they were added.
+    pub fn restore_subscribers(&mut self, mut subscribers: Vec<(EventMask, EventBusCallback)>) {
+        subscribers.append(&mut self.subscribers);
+        self.subscribers = subscribers;
+    }
+
+    /// Sends an event to every `EventListener` whose event mask matches it.
+    pub fn notify_listeners(&self, event: &PushrodEvent) {
+        let mask = event.get_mask();
+
+        self.listeners
+            .iter()
+            .filter(|listener| listener.event_mask() & mask != 0)
+            .for_each(|listener| listener.handle_event(event));
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -14,3 +14,7 @@
 
 /// Event definitions, used to detect when UI-specific events occur, and defining callbacks.
 pub mod event;
+
+/// Queues `PushrodEvent`s that are posted by `Widget`s and the application, and delivers them to
+/// their subscribers on the next frame.
+pub mod event_bus;
@@ -23,6 +23,11 @@
 //! [dependencies]
 //! piston_window = "^0.89.0"
 //! find_folder = "^0.3.0"
+//! glutin = "^0.19"
+//! serde = { version = "1.0", features = ["derive"] }
+//! toml = "^0.5"
+//! gfx = "^0.17"
+//! gfx_device_gl = "^0.15"
 //! ```
 //!
 //! To use the crate in your project, add the following dependencies:
@@ -33,9 +38,10 @@
 //!
 //! # Core Components
 //! `pushrod::core` is the _core_ library components, representing the main run loop, the callback
-//! store mechanism, and the widget store.
+//! store mechanism, the widget store, and the layout loader.
 //!
-//! `pushrod::event` is the event system, in the works for 0.2.x.
+//! `pushrod::event` is the event system, containing `PushrodEvent`s, event masks, and the event
+//! bus that `Widget`s and applications use to send events to each other.
 //!
 //! `pushrod::widget` is the core `Widget` library.
 //!
@@ -58,6 +64,15 @@
 //! - Mouse button click down inside the scope of a `Widget`
 //! - Mouse button click release inside and outside of a `Widget`
 //!
+//! Every callback is a closure that can mutate its own state: the callback types are all boxed
+//! `FnMut` closures, such as `TimeoutCallback`.  `Widget`s can also be generic over the type of
+//! their callback, defaulting to the boxed type, as the `TimerWidget` is.  Such a `Widget` stores
+//! its closure unboxed, and the type is only erased once, for the whole `Widget`, when it is
+//! added to the `WidgetStore` as a `Box<dyn Widget>`.  Until then, its closure may borrow local
+//! state.  The boxed form has an alias, such as `DynTimerWidget`, for storing `Widget`s whose
+//! callbacks are set or replaced later.  Callbacks that are given the `WidgetStore` are never
+//! called while it is iterating over its `Widget`s: they are queued, and called afterward.
+//!
 //! # Widgets
 //! The following `Widget` objects are provided:
 //!
@@ -72,8 +87,15 @@
 //! which can be downloaded from Google, so long as they are free/open source.  One has been
 //! included with the source distribution in the `assets` directory.
 //!
-//! `TimerWidget` provides a rudimentary timer, which increases ticks based on the screen
-//! refresh.  After a timer has expired, a callback is triggered, allowing an action to occur.
+//! `TimerWidget` provides a rudimentary timer, which increases ticks based on the run loop's
+//! update events.  After a timer has expired, a callback is triggered, allowing an action to occur.
+//!
+//! `AnimationWidget` interpolates a value between a start and end value over a period of time,
+//! using an easing function, and triggers a callback with the value on each update.  This can be
+//! used to animate other `Widget`s, such as moving or fading them.
+//!
+//! `CountdownWidget` draws the number of whole seconds remaining in a countdown, using the same
+//! timing as the `TimerWidget`.  After the countdown reaches zero, a callback is triggered.
 //!
 //! `ImageWidget` draws an image on the screen.  `png` format has been tested, but `jpg` and `gif`
 //! formats should also work, as they are part of the Piston library.
@@ -83,12 +105,15 @@
 //! click is detected (with the left mouse button) inside the bounds of the widget (ie. it
 //! overrides the mouse button click inside event.)
 //!
-//! `ToggleButtonWidget` operates the same as a `PushButtonWidget`, but offers the ability to toggle
-//! selected state.  `on_selected` is the callback that is triggered with the selected state
-//! returned.
+//! `ToggleButtonWidget` operates the same as a `ButtonWidget`, but latches its selected state
+//! until it is clicked again.  `on_toggle` is the callback that is triggered with the selected
+//! state returned.  Toggle buttons in the same exclusive group behave like radio buttons.
 //!
 //! `ProgressWidget` draws a progress bar on the screen, with a `BoxWidget` as its base.  The
 //! color of the progress bar and its background can be changed at any time.
+//!
+//! `TooltipWidget` draws a line of text in a bordered box.  It is managed by `Pushrod`, which
+//! shows it near the mouse pointer when the mouse rests over a `Widget` that has a tooltip.
 
 /// Main module containing the run loop for the UI components, containers for windows and
 /// `Widget` trait objects, and so on.  Contains the core elements required to build
@@ -109,8 +134,11 @@ pub mod event;
 /// - Box Widget (for drawing a plain background with a box and a colored border)
 /// - Text Widget (for drawing text)
 /// - Timer Widget (for performing timer operations)
+/// - Animation Widget (for interpolating values over time)
+/// - Countdown Widget (for displaying a countdown)
 /// - Image Widget (for drawing images)
 /// - Push Button Widget (for creating an interactive button that can be clicked)
 /// - Toggle Button Widget (for creating an on/off button that can be toggled)
 /// - Progress Widget (for displaying progress of an operation)
+/// - Tooltip Widget (for describing a `Widget` when the mouse rests over it)
 pub mod widget;
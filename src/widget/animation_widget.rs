@@ -0,0 +1,267 @@
+// Animation Widget
+// Time-based widget that interpolates a value over a period of time.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::time::{Duration, Instant};
+
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the interpolated value on every update of an animation.
+pub type AnimationCallback = Box<dyn FnMut(f64)>;
+
+/// Closure type that is called when an animation completes.
+pub type AnimationCompleteCallback = Box<dyn FnMut()>;
+
+/// This `enum` specifies the easing function used to interpolate between the start and end
+/// values of an `AnimationWidget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// Constant speed from start to end.
+    Linear,
+
+    /// Starts slowly, and accelerates towards the end.
+    EaseIn,
+
+    /// Starts quickly, and decelerates towards the end.
+    EaseOut,
+
+    /// Starts slowly, accelerates through the middle, and decelerates towards the end.
+    EaseInOut,
+}
+
+/// Implementation of the easing functions.
+impl Easing {
+    /// Applies the easing function to a progress value between 0.0 and 1.0, returning the eased
+    /// progress.  Progress values outside of that range are clamped.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::animation_widget::*;
+    /// # fn main() {
+    ///    assert_eq!(Easing::Linear.apply(0.25), 0.25);
+    ///    assert_eq!(Easing::EaseIn.apply(0.5), 0.25);
+    ///    assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+    ///    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    ///    assert_eq!(Easing::EaseInOut.apply(2.0), 1.0);
+    /// # }
+    /// ```
+    pub fn apply(self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+        }
+    }
+}
+
+/// This is the `AnimationWidget`.  Like the `TimerWidget`, it is invisible, and is driven by the
+/// run loop's update events.  On every update, it interpolates between a start and end value
+/// over a duration, and calls the `on_value` closure with the result.  When the duration has
+/// passed, the end value is reported one last time, and the `on_complete` closure is called.
+///
+/// Example usage:
+/// ```
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # use pushrod::core::clock::*;
+/// # use pushrod::widget::animation_widget::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let value = Rc::new(Cell::new(0.0));
+///    let value_clone = value.clone();
+///    let mut animation = AnimationWidget::with_clock(0.0, 400.0, 2000, Box::new(clock.clone()));
+///
+///    animation.on_value(Box::new(move |x| value_clone.set(x)));
+///
+///    clock.advance(500);
+///    animation.tick();
+///    assert_eq!(value.get(), 100.0);
+///
+///    // Reversing mid-way heads back towards the start value from the current position.
+///    animation.reverse();
+///    clock.advance(250);
+///    animation.tick();
+///    assert_eq!(value.get(), 50.0);
+///
+///    clock.advance(250);
+///    animation.tick();
+///    assert_eq!(value.get(), 0.0);
+///    assert!(!animation.is_running());
+/// # }
+/// ```
+pub struct AnimationWidget {
+    config: Configurable,
+    clock: Box<dyn Clock>,
+    started: Instant,
+    start_value: f64,
+    end_value: f64,
+    duration: u64,
+    easing: Easing,
+    running: bool,
+    on_value: Option<AnimationCallback>,
+    on_complete: Option<AnimationCompleteCallback>,
+}
+
+/// Implementation of the constructor for the `AnimationWidget`.  Animation widgets are not
+/// accessible on the screen, so they have an origin of 0x0 and width of 0x0.  Upon
+/// instantiation, the animation is running.
+impl AnimationWidget {
+    /// Constructor, creates a new `AnimationWidget` that interpolates from `start_value` to
+    /// `end_value` over `duration` milliseconds, using linear easing.
+    pub fn new(start_value: f64, end_value: f64, duration: u64) -> Self {
+        Self::with_clock(start_value, end_value, duration, Box::new(SystemClock))
+    }
+
+    /// Constructor, creates a new `AnimationWidget` that measures time using the specified
+    /// `Clock`.  This is mainly useful for testing, by supplying a `ManualClock`.
+    pub fn with_clock(start_value: f64, end_value: f64, duration: u64, clock: Box<dyn Clock>) -> Self {
+        let started = clock.now();
+
+        Self {
+            config: Configurable::new(),
+            clock,
+            started,
+            start_value,
+            end_value,
+            duration,
+            easing: Easing::Linear,
+            running: true,
+            on_value: None,
+            on_complete: None,
+        }
+    }
+
+    /// Sets the easing function used for interpolation.
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    /// Sets the closure function that receives the interpolated value on every update.
+    pub fn on_value(&mut self, callback: AnimationCallback) {
+        self.on_value = Some(callback);
+    }
+
+    /// Sets the closure function that is called when the animation completes.
+    pub fn on_complete(&mut self, callback: AnimationCompleteCallback) {
+        self.on_complete = Some(callback);
+    }
+
+    /// Indicates whether or not the animation is still running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the progress of the animation between 0.0 and 1.0, before easing is applied.
+    pub fn get_progress(&self) -> f64 {
+        if !self.running || self.duration == 0 {
+            return 1.0;
+        }
+
+        (self.clock.elapsed_ms(self.started) as f64 / self.duration as f64).min(1.0)
+    }
+
+    /// Returns the current interpolated value of the animation.
+    pub fn get_value(&self) -> f64 {
+        self.start_value + (self.end_value - self.start_value) * self.easing.apply(self.get_progress())
+    }
+
+    /// Restarts the animation from the start value.
+    pub fn restart(&mut self) {
+        self.started = self.clock.now();
+        self.running = true;
+    }
+
+    /// Reverses the direction of the animation, swapping the start and end values.  A running
+    /// animation continues from its current point in time towards the new end value.  A completed
+    /// animation restarts, running back to where it came from.
+    pub fn reverse(&mut self) {
+        let remaining = if self.running {
+            self.duration
+                .saturating_sub(self.clock.elapsed_ms(self.started))
+        } else {
+            0
+        };
+        let now = self.clock.now();
+
+        std::mem::swap(&mut self.start_value, &mut self.end_value);
+        self.started = now
+            .checked_sub(Duration::from_millis(remaining))
+            .unwrap_or(now);
+        self.running = true;
+    }
+
+    /// Called to calculate the current value, and pass it to the `on_value` function.  When the
+    /// animation reaches its end, the animation stops, and `on_complete` is called.
+    pub fn tick(&mut self) {
+        if !self.running {
+            return;
+        }
+
+        let progress = self.get_progress();
+        let value = self.get_value();
+
+        if let Some(callback) = &mut self.on_value {
+            callback(value);
+        }
+
+        if progress >= 1.0 {
+            self.running = false;
+
+            if let Some(callback) = &mut self.on_complete {
+                callback();
+            }
+        }
+    }
+}
+
+/// Implementation of the `AnimationWidget` object with the `Widget` traits implemented.
+impl Widget for AnimationWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Origin is always set to X/Y at points 0x0.
+    fn get_origin(&mut self) -> Point {
+        make_origin_point()
+    }
+
+    /// Size is always unsized, as animations are invisible.
+    fn get_size(&mut self) -> crate::core::point::Size {
+        make_unsized()
+    }
+
+    /// Calls the animation `tick()` function on every update of the run loop.
+    fn update(&mut self, _delta_ms: u64) {
+        self.tick();
+    }
+
+    /// Does not draw anything, as animations are invisible.
+    fn draw(&mut self, _context: Context, _graphics: &mut DrawTarget, _clip: &DrawState) {
+        self.clear_invalidate();
+    }
+}
@@ -15,8 +15,13 @@
 
 use piston_window::*;
 
+use crate::core::draw_target::*;
 use crate::core::point::*;
+use crate::widget::builder::*;
 use crate::widget::config::*;
+use crate::widget::gradient::*;
+use crate::widget::shape::{stroke_border, stroke_rounded_rect, BorderStyle};
+use crate::widget::theme::*;
 use crate::widget::widget::*;
 
 /// This is the `BoxWidget`, which contains a top-level widget for display, overriding the
@@ -56,6 +61,11 @@ impl BoxWidget {
         }
     }
 
+    /// Creates a `WidgetBuilder` for a new `BoxWidget`, which needs a size unless it is hidden.
+    pub fn builder() -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::new())
+    }
+
     /// Sets the border color for this widget.
     pub fn set_border_color(&mut self, color: types::Color) {
         self.config().set(BorderColor(color));
@@ -63,11 +73,10 @@ impl BoxWidget {
     }
 
     /// Retrieves the border color of this widget.
-    /// Defaults to black color `[0.0, 0.0, 0.0, 1.0]` if not set.
+    /// Defaults to the border color of the current `Theme` if not set.
     pub fn get_border_color(&mut self) -> types::Color {
         self.config()
-            .get::<BorderColor>()
-            .unwrap_or(&BorderColor([0.0, 0.0, 0.0, 1.0]))
+            .get_or(BorderColor(get_current_theme().border_color))
             .0
     }
 
@@ -81,8 +90,7 @@ impl BoxWidget {
     /// Defaults to 1 if not set.
     pub fn get_border_thickness(&mut self) -> u8 {
         self.config()
-            .get::<BorderWidth>()
-            .unwrap_or(&BorderWidth(1))
+            .get_or(BorderWidth(1))
             .0
     }
 
@@ -92,13 +100,71 @@ impl BoxWidget {
         self.set_border_thickness(thickness);
     }
 
+    /// Sets the thickness of each side of the border separately, overriding the border thickness,
+    /// such as to draw only a divider line along the bottom with `make_insets(0, 0, 1, 0)`.
+    pub fn set_border_sides(&mut self, sides: Insets) {
+        self.config().set(BorderSides(sides));
+        self.invalidate();
+    }
+
+    /// Retrieves the thickness of each side of the border of this widget.
+    /// Defaults to the border thickness on every side if not set.
+    pub fn get_border_sides(&mut self) -> Insets {
+        match self.config().get::<BorderSides>() {
+            Some(sides) => sides.0,
+            None => make_uniform_insets(i32::from(self.get_border_thickness())),
+        }
+    }
+
+    /// Sets the style in which the border is stroked: solid, dashed, or dotted.
+    pub fn set_border_style(&mut self, style: BorderStyle) {
+        self.config().set(BorderStroke(style));
+        self.invalidate();
+    }
+
+    /// Retrieves the style in which the border is stroked.
+    /// Defaults to `BorderStyle::Solid` if not set.
+    pub fn get_border_style(&mut self) -> BorderStyle {
+        self.config().get_or(BorderStroke(BorderStyle::Solid)).0
+    }
+
     /// Function to draw a box for the point and size of this box.  Automatically draws the border
     /// along with the width of the border.  This is automatically determined by the origin, so the
-    /// box is automatically drawn for the bounds of the `Widget`.
-    fn draw_box(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    /// box is automatically drawn for the bounds of the `Widget`.  A border with a thickness for
+    /// each side, or that is dashed or dotted, is drawn with `stroke_border`, and always has
+    /// square corners.
+    fn draw_box(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         let size: crate::core::point::Size = self.get_size();
         let border: f64 = self.get_border_thickness() as f64;
-        let color: types::Color = self.get_border_color();
+        let color: types::Color = apply_opacity(self.get_border_color());
+        let radius = self.get_corner_radius();
+        let style = self.get_border_style();
+
+        if self.config().contains_key::<BorderSides>() || style != BorderStyle::Solid {
+            stroke_border(
+                color,
+                [0.0, 0.0, size.w as f64, size.h as f64],
+                &self.get_border_sides(),
+                style,
+                clip,
+                c.transform,
+                g,
+            );
+            return;
+        }
+
+        if radius > 0.0 {
+            stroke_rounded_rect(
+                color,
+                [0.0, 0.0, size.w as f64, size.h as f64],
+                radius,
+                border,
+                clip,
+                c.transform,
+                g,
+            );
+            return;
+        }
 
         // Upper left to upper right
         Line::new(color, border).draw(
@@ -144,6 +210,41 @@ impl BoxWidget {
     }
 }
 
+impl Buildable for BoxWidget {}
+
+/// Implementation of the `WidgetBuilder` settings of the `BoxWidget`.
+impl WidgetBuilder<BoxWidget> {
+    /// Sets both the color and the thickness of the border.
+    pub fn border(mut self, color: types::Color, thickness: u8) -> Self {
+        self.widget().set_border(color, thickness);
+        self
+    }
+
+    /// Sets the color of the border.
+    pub fn border_color(mut self, color: types::Color) -> Self {
+        self.widget().set_border_color(color);
+        self
+    }
+
+    /// Sets the thickness of the border.
+    pub fn border_thickness(mut self, thickness: u8) -> Self {
+        self.widget().set_border_thickness(thickness);
+        self
+    }
+
+    /// Sets the thickness of each side of the border separately.
+    pub fn border_sides(mut self, sides: Insets) -> Self {
+        self.widget().set_border_sides(sides);
+        self
+    }
+
+    /// Sets the style in which the border is stroked.
+    pub fn border_style(mut self, style: BorderStyle) -> Self {
+        self.widget().set_border_style(style);
+        self
+    }
+}
+
 /// Implementation of the `BoxWidget` object with the `Widget` traits implemented.
 /// This implementation is similar to the `CanvasWidget`, but incorporates a drawable box inside
 /// the widget.  Base widget is the `CanvasWidget`.
@@ -179,16 +280,57 @@ impl Widget for BoxWidget {
     }
 
     /// Retrieves the color of this widget.
-    /// Defaults to white color `[1.0; 4]` if not set.
+    /// Defaults to the background color of the current `Theme` if not set.
     fn get_color(&mut self) -> types::Color {
         self.base_widget.get_color()
     }
 
+    /// Sets the corner radius for this widget and the base widget.  Invalidates the widget
+    /// afterward.
+    fn set_corner_radius(&mut self, radius: f64) {
+        self.config().set(CornerRadius(radius));
+        self.base_widget.set_corner_radius(radius);
+        self.invalidate();
+    }
+
+    /// Sets the gradient for this widget and the base widget.  Invalidates the widget afterward.
+    fn set_gradient(&mut self, color: types::Color, direction: GradientDirection) {
+        self.config().set(GradientColor(color));
+        self.config().set(GradientOrientation(direction));
+        self.base_widget.set_gradient(color, direction);
+        self.invalidate();
+    }
+
+    /// Removes the gradient from this widget and the base widget.  Invalidates the widget
+    /// afterward.
+    fn clear_gradient(&mut self) {
+        self.config().remove::<GradientColor>();
+        self.config().remove::<GradientOrientation>();
+        self.base_widget.clear_gradient();
+        self.invalidate();
+    }
+
+    /// Retrieves the rectangle inside of which the contents of this widget are drawn: the size
+    /// of the widget, inset by the thickness of each side of the border, and then by the padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+        let sides = self.get_border_sides();
+        let padding = self.get_padding();
+
+        make_insets(
+            sides.top + padding.top,
+            sides.right + padding.right,
+            sides.bottom + padding.bottom,
+            sides.left + padding.left,
+        )
+        .inset_size(&size)
+    }
+
     /// Draws the contents of the widget in this order:
     ///
     /// - Base widget first
     /// - Box graphic for the specified width
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
         self.base_widget.draw(c, g, &clip);
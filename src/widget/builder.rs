@@ -0,0 +1,402 @@
+// Widget Builder
+// Builds a widget from a chain of settings, checking that the result makes sense
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use piston_window::types;
+
+use crate::core::point::Insets;
+use crate::core::widget_id::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+/// Errors that can be returned when a `WidgetBuilder` builds its `Widget`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// A visible `Widget` was given no area to draw in, and no preferred size or dimensions from
+    /// which a layout could size it.
+    ZeroSize { width: i32, height: i32 },
+
+    /// A `TimerWidget` was built without a timeout, so it would fire on every update.
+    MissingTimeout,
+
+    /// A `Widget` that draws text was given a font size of 0.
+    ZeroFontSize,
+
+    /// `pushrod_ui!` was given two `Widget`s with the same name.
+    DuplicateName(String),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::ZeroSize { width, height } => write!(
+                f,
+                "A visible widget cannot have a size of {}x{}",
+                width, height
+            ),
+            BuildError::MissingTimeout => write!(f, "A timer needs a timeout"),
+            BuildError::ZeroFontSize => write!(f, "Text cannot have a font size of 0"),
+            BuildError::DuplicateName(name) => {
+                write!(f, "More than one widget is named '{}'", name)
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// A `Widget` that can be built by a `WidgetBuilder`.  `validate` is called by `build`, once
+/// every setting has been applied, and by default checks that a visible `Widget` has a size.
+pub trait Buildable: Widget + Sized {
+    /// Checks the settings of the `Widget`, returning a `BuildError` if they make no sense.
+    fn validate(&mut self) -> Result<(), BuildError> {
+        validate_size(self)
+    }
+}
+
+/// Checks that `widget` has a size to draw in, if it is visible.  A `Widget` with a preferred
+/// size, or a size in `Dimension`s, is sized by its parent, so it does not need one yet.
+pub fn validate_size<W: Widget>(widget: &mut W) -> Result<(), BuildError> {
+    let size = widget.get_size();
+
+    if widget.is_visible()
+        && (size.w <= 0 || size.h <= 0)
+        && !widget.config().contains_key::<PreferredSize>()
+        && !widget.config().contains_key::<SizeDimensions>()
+    {
+        return Err(BuildError::ZeroSize {
+            width: size.w,
+            height: size.h,
+        });
+    }
+
+    Ok(())
+}
+
+/// This is the `WidgetBuilder`, which builds a `Widget` from a chain of settings, as an
+/// alternative to calling its setters one at a time.  Each setting is applied to the `Widget`
+/// as it is given, with the same setter that would be called by hand, so a built `Widget` is no
+/// different from one that was set up manually.  `build` checks the result, so that a `Widget`
+/// that could never work, such as a visible box with no size, is caught where it is created.
+///
+/// Builders are created by the `builder` function of each `Widget` that has one, such as
+/// `BoxWidget::builder()`, which also adds settings of its own.  Settings that have no builder
+/// function can be applied with `with`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::builder::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut box_widget = BoxWidget::builder()
+///        .origin(20, 20)
+///        .size(100, 50)
+///        .color([0.0, 0.0, 1.0, 1.0])
+///        .border([0.0, 0.0, 0.0, 1.0], 2)
+///        .tooltip("A blue box")
+///        .build()
+///        .unwrap();
+///
+///    assert_eq!(box_widget.get_color(), [0.0, 0.0, 1.0, 1.0]);
+///    assert_eq!(box_widget.get_border_thickness(), 2);
+///
+///    // A visible box has to have a size, or a preferred size for a layout to give it.
+///    assert_eq!(
+///        BoxWidget::builder().origin(20, 20).build().err(),
+///        Some(BuildError::ZeroSize { width: 0, height: 0 })
+///    );
+///    assert!(BoxWidget::builder().preferred_size(100, 50).build().is_ok());
+/// # }
+/// ```
+pub struct WidgetBuilder<W: Buildable> {
+    widget: W,
+}
+
+/// Implementation of the `WidgetBuilder`, with the settings that every `Widget` has.
+impl<W: Buildable> WidgetBuilder<W> {
+    /// Creates a new `WidgetBuilder` that applies its settings to `widget`.
+    pub fn new(widget: W) -> Self {
+        Self { widget }
+    }
+
+    /// Sets the origin of the `Widget`, relative to its parent.
+    pub fn origin(mut self, x: i32, y: i32) -> Self {
+        self.widget.set_origin(x, y);
+        self
+    }
+
+    /// Sets the size of the `Widget`.
+    pub fn size(mut self, w: i32, h: i32) -> Self {
+        self.widget.set_size(w, h);
+        self
+    }
+
+    /// Sets the size that layouts give the `Widget`.
+    pub fn preferred_size(mut self, w: i32, h: i32) -> Self {
+        self.widget.set_preferred_size(w, h);
+        self
+    }
+
+    /// Sets the main color of the `Widget`.
+    pub fn color(mut self, color: types::Color) -> Self {
+        self.widget.set_color(color);
+        self
+    }
+
+    /// Sets the radius of the rounded corners of the `Widget`.
+    pub fn corner_radius(mut self, radius: f64) -> Self {
+        self.widget.set_corner_radius(radius);
+        self
+    }
+
+    /// Sets the opacity of the `Widget`, between 0.0 and 1.0.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.widget.set_opacity(opacity);
+        self
+    }
+
+    /// Sets the padding between the edges of the `Widget` and its content.
+    pub fn padding(mut self, padding: Insets) -> Self {
+        self.widget.set_padding(padding);
+        self
+    }
+
+    /// Hides the `Widget`.  A hidden `Widget` does not need a size.
+    pub fn hidden(mut self) -> Self {
+        self.widget.set_visible(false);
+        self
+    }
+
+    /// Disables the `Widget`.
+    pub fn disabled(mut self) -> Self {
+        self.widget.set_disabled(true);
+        self
+    }
+
+    /// Lets the `Widget` take the keyboard focus.
+    pub fn focusable(mut self) -> Self {
+        self.widget.set_focusable(true);
+        self
+    }
+
+    /// Sets the mouse cursor that is shown over the `Widget`.
+    pub fn cursor(mut self, cursor: CursorStyle) -> Self {
+        self.widget.set_cursor(cursor);
+        self
+    }
+
+    /// Sets the tooltip text of the `Widget`.
+    pub fn tooltip(mut self, text: &str) -> Self {
+        self.widget.set_tooltip(text);
+        self
+    }
+
+    /// Applies any other setting to the `Widget`, by calling `f` with it.
+    pub fn with<F: FnOnce(&mut W)>(mut self, f: F) -> Self {
+        f(&mut self.widget);
+        self
+    }
+
+    /// Checks the settings, and returns the `Widget`, or the `BuildError` that makes it unusable.
+    pub fn build(mut self) -> Result<W, BuildError> {
+        self.widget.validate()?;
+        Ok(self.widget)
+    }
+
+    /// The `Widget` that the settings are applied to, for the settings of each type of `Widget`.
+    pub(crate) fn widget(&mut self) -> &mut W {
+        &mut self.widget
+    }
+}
+
+/// The IDs of the `Widget`s that were added by `pushrod_ui!`, by their names.
+pub type WidgetIds = HashMap<&'static str, WidgetId>;
+
+/// Builds a tree of `Widget`s, and adds them to a `Pushrod` or a `WidgetStore`, so that the
+/// structure of a user interface can be read from its code.  Each `Widget` is given as its name,
+/// followed by its `WidgetBuilder`, and optionally by its children, in braces after `=>`:
+///
+/// ```text
+/// pushrod_ui!(target, {
+///     "Name": Widget::builder().setting(value) => {
+///         "ChildName": Widget::builder().setting(value),
+///     },
+/// })
+/// ```
+///
+/// Every `Widget` is built first, and only once all of them have been built are they added to
+/// their parents with `add_widget_to_parent`, in the order in which they are written, so
+/// children are drawn above their parents, and later siblings above earlier ones.  Top level
+/// `Widget`s are added to the top level `Widget`.  The macro evaluates to a `Result` with the
+/// `WidgetIds` of every `Widget` by name, for attaching callbacks afterward, or the first
+/// `BuildError`, in which case none of the `Widget`s are added.  Each name may only be used
+/// once, or `BuildError::DuplicateName` is returned.
+///
+/// The builder expressions are ordinary Rust, so an unknown `Widget` type, or a setting that its
+/// builder does not have, is reported by the compiler at that expression, as it would be in any
+/// other code.
+///
+/// The `add_box_widgets` function of the `simple` example was a sequence of builders, each
+/// followed by an `add_widget_to_parent` call with the ID of its parent:
+///
+/// ```text
+/// let box_widget = BoxWidget::builder()
+///     .origin(250, 80)
+///     .size(200, 200)
+///     .build()
+///     .unwrap_or_else(|error| panic!("Failed to build BoxWidget1: {}", error));
+/// let box_widget_id = pushrod.add_widget("BoxWidget1", Box::new(box_widget));
+/// let text_widget = TextWidget::builder(font.clone())
+///     .origin(15, 20)
+///     .size(170, 32)
+///     .text("Left")
+///     .build()
+///     .unwrap_or_else(|error| panic!("Failed to build LeftJustifiedText: {}", error));
+///
+/// pushrod.add_widget_to_parent("LeftJustifiedText", Box::new(text_widget), box_widget_id);
+/// ```
+///
+/// With `pushrod_ui!`, the nesting shows which `Widget` each one is placed in:
+///
+/// ```text
+/// pushrod_ui!(pushrod, {
+///     "BoxWidget1": BoxWidget::builder().origin(250, 80).size(200, 200) => {
+///         "LeftJustifiedText": TextWidget::builder(font.clone())
+///             .origin(15, 20)
+///             .size(170, 32)
+///             .text("Left"),
+///     },
+/// })
+/// .unwrap_or_else(|error| panic!("Failed to build the boxes: {}", error));
+/// ```
+///
+/// Example:
+/// ```
+/// # use pushrod::core::widget_id::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::pushrod_ui;
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::builder::*;
+/// # use pushrod::widget::timer_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let ids = pushrod_ui!(widget_store, {
+///        "Panel": BoxWidget::builder().origin(20, 20).size(200, 100) => {
+///            "Left": BoxWidget::builder().origin(10, 10).size(80, 80),
+///            "Right": BoxWidget::builder().origin(110, 10).size(80, 80) => {
+///                "Dot": CanvasWidget::builder().origin(30, 30).size(20, 20),
+///            },
+///        },
+///        "Blink": TimerWidget::builder().timeout_ms(500),
+///    })
+///    .unwrap();
+///
+///    assert_eq!(ids.len(), 5);
+///    assert_eq!(widget_store.get_parent_of(ids["Right"]), ids["Panel"]);
+///    assert_eq!(widget_store.get_parent_of(ids["Dot"]), ids["Right"]);
+///    assert_eq!(widget_store.get_parent_of(ids["Blink"]), WidgetId::ROOT);
+///
+///    // A builder that fails keeps all of the widgets from being added, even those before it.
+///    let result = pushrod_ui!(widget_store, {
+///        "Before": BoxWidget::builder().size(10, 10),
+///        "Empty": BoxWidget::builder(),
+///        "Never": BoxWidget::builder().size(10, 10),
+///    });
+///
+///    assert_eq!(result.err(), Some(BuildError::ZeroSize { width: 0, height: 0 }));
+///    assert!(widget_store.get_widget_by_name("Before").is_none());
+///    assert!(widget_store.get_widget_by_name("Never").is_none());
+///
+///    // So does a name that is used twice.
+///    let result = pushrod_ui!(widget_store, {
+///        "Box": BoxWidget::builder().size(10, 10) => {
+///            "Box": BoxWidget::builder().size(5, 5),
+///        },
+///    });
+///
+///    assert_eq!(result.err(), Some(BuildError::DuplicateName(String::from("Box"))));
+///    assert!(widget_store.get_widget_by_name("Box").is_none());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pushrod_ui {
+    (@nodes $nodes:ident, $parent:expr; ) => {};
+
+    (@nodes $nodes:ident, $parent:expr;
+        $name:literal : $builder:expr $(=> { $($children:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        if $nodes.iter().any(|(name, _, _)| *name == $name) {
+            return Err($crate::widget::builder::BuildError::DuplicateName($name.to_string()));
+        }
+
+        let widget: Box<dyn $crate::widget::widget::Widget> = Box::new($builder.build()?);
+        let index = $nodes.len();
+
+        $nodes.push(($name, widget, $parent));
+        $( $crate::pushrod_ui!(@nodes $nodes, Some(index); $($children)*); )?
+        $( $crate::pushrod_ui!(@nodes $nodes, $parent; $($rest)*); )?
+    };
+
+    (@nodes $nodes:ident, $parent:expr; $($unexpected:tt)*) => {
+        compile_error!(concat!(
+            "Expected a widget as `\"Name\": Widget::builder()...`, optionally followed by its ",
+            "children as `=> { ... }`, and separated by commas, but found: ",
+            stringify!($($unexpected)*)
+        ));
+    };
+
+    ($target:expr, { $($nodes:tt)* }) => {
+        (|| -> ::std::result::Result<
+            $crate::widget::builder::WidgetIds,
+            $crate::widget::builder::BuildError,
+        > {
+            // Each `Widget` is built before any is added, with the index of its parent.
+            let mut nodes: Vec<(
+                &'static str,
+                Box<dyn $crate::widget::widget::Widget>,
+                Option<usize>,
+            )> = Vec::new();
+            let mut ids = $crate::widget::builder::WidgetIds::new();
+            let mut widget_ids = Vec::new();
+
+            $crate::pushrod_ui!(@nodes nodes, None; $($nodes)*);
+
+            for (name, widget, parent) in nodes {
+                let parent_id = parent.map_or($crate::core::widget_id::WidgetId::ROOT, |index| {
+                    widget_ids[index]
+                });
+                let widget_id = $target.add_widget_to_parent(name, widget, parent_id);
+
+                widget_ids.push(widget_id);
+                ids.insert(name, widget_id);
+            }
+
+            Ok(ids)
+        })()
+    };
+
+    ($($unexpected:tt)*) => {
+        compile_error!(concat!(
+            "Expected `pushrod_ui!(target, { widgets })`, where the target is a `Pushrod` or a ",
+            "`WidgetStore`, but found: ",
+            stringify!($($unexpected)*)
+        ));
+    };
+}
@@ -0,0 +1,547 @@
+// Button Widget
+// Extensible widget for the widget library - handles a clickable button with hover and pressed
+// states.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::box_widget::*;
+use crate::widget::builder::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called when a `ButtonWidget` is clicked, with the ID of the button.
+pub type ClickCallback = Box<dyn FnMut(WidgetId)>;
+
+/// How much of the accent color of the current `Theme` is mixed into the color of a button while
+/// the mouse is over it.
+const HOVER_ACCENT_AMOUNT: f32 = 0.2;
+
+/// This is the `ButtonWidget`, a button that draws a box with a border and a centered text
+/// label, and calls its `on_click` callback when it is clicked: when the left mouse button is
+/// both pressed and released inside of it, or when `Space` or `Enter` is pressed and released
+/// while it has the keyboard focus.  Like other buttons, it also sends a `WidgetClicked` event.
+///
+/// The button is drawn differently while the mouse is over it, and while it is pressed, and its
+/// label is drawn in the disabled color of the current `Theme` while it is disabled, when it
+/// does not respond to the mouse or keyboard.  The colors and font size default to those of the
+/// current `Theme`.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::button_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Button", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut button = ButtonWidget::new(
+///        pushrod.get_factory(),
+///        "OpenSans-Regular.ttf".to_string(),
+///        "Save".to_string(),
+///    );
+///
+///    button.set_origin(20, 20);
+///    button.set_size(120, 32);
+///    button.on_click(Box::new(|widget_id| eprintln!("Button {} clicked", widget_id)));
+///    pushrod.add_widget("SaveButton", Box::new(button));
+/// # }
+/// ```
+pub struct ButtonWidget {
+    config: Configurable,
+    base_widget: BoxWidget,
+    text_widget: TextWidget,
+    hovered: bool,
+    mouse_pressed: bool,
+    key_pressed: bool,
+    latched: bool,
+    on_click: Option<ClickCallback>,
+}
+
+/// Implementation of the constructor for the `ButtonWidget`.
+impl ButtonWidget {
+    /// Creates a new button with a `text` label, drawn with the font `font_name` from the
+    /// `assets` directory, in the font size of the current `Theme`.  The button can be focused,
+    /// so that it can be clicked from the keyboard.
+    pub fn new(factory: &mut GfxFactory, font_name: String, text: String) -> Self {
+        Self::with_font_size(factory, font_name, text, get_current_theme().font_size)
+    }
+
+    /// Creates a new button with a `text` label, drawn with the font `font_name` from the
+    /// `assets` directory, in `font_size`.  Panics if the font cannot be loaded; use
+    /// `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn with_font_size(
+        factory: &mut GfxFactory,
+        font_name: String,
+        text: String,
+        font_size: u32,
+    ) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, text, font_size)
+    }
+
+    /// Creates a new button with a `text` label, drawn in a font from the font cache, in
+    /// `font_size`.
+    pub fn from_font(font: SharedGlyphs, text: String, font_size: u32) -> Self {
+        let mut widget = Self {
+            config: Configurable::new(),
+            base_widget: BoxWidget::new(),
+            text_widget: TextWidget::from_font(font, text, font_size, TextJustify::Center),
+            hovered: false,
+            mouse_pressed: false,
+            key_pressed: false,
+            latched: false,
+            on_click: None,
+        };
+
+        widget.base_widget.set_border_thickness(1);
+        widget.set_focusable(true);
+        widget.update_text_padding();
+        widget
+    }
+
+    /// Creates a `WidgetBuilder` for a new button with no label, drawn in a font from the font
+    /// cache, in the font size of the current `Theme`.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use piston_window::*;
+    /// # use pushrod::core::main::*;
+    /// # use pushrod::widget::button_widget::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # fn main() {
+    ///    let window: PistonWindow = WindowSettings::new("Button", [400, 300]).build().unwrap();
+    ///    let mut pushrod = Pushrod::new(window);
+    ///    let font = load_font(pushrod.get_factory(), "OpenSans-Regular.ttf").unwrap();
+    ///    let button = ButtonWidget::builder(font)
+    ///        .origin(20, 20)
+    ///        .size(120, 32)
+    ///        .text("Save")
+    ///        .on_click(|widget_id| eprintln!("Button {} clicked", widget_id))
+    ///        .build()
+    ///        .unwrap();
+    ///
+    ///    pushrod.add_widget("SaveButton", Box::new(button));
+    /// # }
+    /// ```
+    pub fn builder(font: SharedGlyphs) -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::from_font(
+            font,
+            String::new(),
+            get_current_theme().font_size,
+        ))
+    }
+
+    /// Sets the closure that is called with the ID of the button when it is clicked.
+    pub fn on_click(&mut self, callback: ClickCallback) {
+        self.on_click = Some(callback);
+    }
+
+    /// Sets the text label of the button.
+    pub fn set_text(&mut self, text: String) {
+        self.text_widget.set_text(text);
+        self.invalidate();
+    }
+
+    /// Sets the font size of the text label of the button.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.text_widget.set_font_size(font_size);
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the text label of the button.
+    pub fn get_font_size(&self) -> u32 {
+        self.text_widget.get_font_size()
+    }
+
+    /// Sets the justification of the text label of the button.  Defaults to
+    /// `TextJustify::Center`.
+    pub fn set_justify(&mut self, justify: TextJustify) {
+        self.text_widget.set_justify(justify);
+        self.invalidate();
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.update_visual_state();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Sets the border color for this widget.
+    pub fn set_border_color(&mut self, color: types::Color) {
+        self.base_widget.set_border_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the border color of this widget.
+    /// Defaults to the border color of the current `Theme` if not set.
+    pub fn get_border_color(&mut self) -> types::Color {
+        self.base_widget.get_border_color()
+    }
+
+    /// Sets the thickness of the border for this widget.
+    pub fn set_border_thickness(&mut self, thickness: u8) {
+        self.base_widget.set_border_thickness(thickness);
+        self.update_text_padding();
+        self.invalidate();
+    }
+
+    /// Retrieves the border thickness of this widget.
+    /// Defaults to 1.
+    pub fn get_border_thickness(&mut self) -> u8 {
+        self.base_widget.get_border_thickness()
+    }
+
+    /// Helper function that sets both the color of the border and the thickness at the same time.
+    pub fn set_border(&mut self, color: types::Color, thickness: u8) {
+        self.set_border_color(color);
+        self.set_border_thickness(thickness);
+    }
+
+    /// Indicates whether the mouse is over the button.
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// Indicates whether the button is being pressed, with the mouse or the keyboard.
+    pub fn is_pressed(&self) -> bool {
+        self.mouse_pressed || self.key_pressed
+    }
+
+    /// Latches the button, so that it is drawn as pressed until it is unlatched, whether or
+    /// not it is being pressed, as a selected `ToggleButtonWidget` is.
+    pub fn set_latched(&mut self, latched: bool) {
+        if latched != self.latched {
+            self.latched = latched;
+            self.update_visual_state();
+        }
+    }
+
+    /// Indicates whether the button is latched.  Defaults to `false`.
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Sets the colors of the box and the text to match the state of the button, and invalidates
+    /// the widget.
+    fn update_visual_state(&mut self) {
+        let theme = get_current_theme();
+        let color = self.get_color();
+        let text_color = self.get_text_color();
+
+        if self.is_pressed() || self.latched {
+            self.base_widget.set_color(theme.accent_color);
+            self.text_widget.set_text_color(theme.background_color);
+        } else if self.hovered {
+            let mut hover_color = color;
+
+            for (channel, accent) in hover_color
+                .iter_mut()
+                .zip(theme.accent_color.iter())
+                .take(3)
+            {
+                *channel += (accent - *channel) * HOVER_ACCENT_AMOUNT;
+            }
+
+            self.base_widget.set_color(hover_color);
+            self.text_widget.set_text_color(text_color);
+        } else {
+            self.base_widget.set_color(color);
+            self.text_widget.set_text_color(text_color);
+        }
+
+        self.invalidate();
+    }
+
+    /// Calls the `on_click` callback, and returns the `WidgetClicked` event for the button.
+    fn click(&mut self, widget_id: WidgetId, button: Button) -> Option<CallbackEvent> {
+        if let Some(callback) = &mut self.on_click {
+            callback(widget_id);
+        }
+
+        Some(CallbackEvent::WidgetClicked { widget_id, button })
+    }
+
+    /// Insets the text by the border and the padding, so that it is drawn inside of both.
+    fn update_text_padding(&mut self) {
+        let border = self.base_widget.get_border_thickness() as i32;
+        let padding = self.get_padding().grow(border);
+
+        self.text_widget.set_padding(padding);
+    }
+}
+
+/// A `ButtonWidget` also needs a font size.
+impl Buildable for ButtonWidget {
+    fn validate(&mut self) -> Result<(), BuildError> {
+        if self.get_font_size() == 0 {
+            return Err(BuildError::ZeroFontSize);
+        }
+
+        validate_size(self)
+    }
+}
+
+/// Implementation of the `WidgetBuilder` settings of the `ButtonWidget`.
+impl WidgetBuilder<ButtonWidget> {
+    /// Sets the text label of the button.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.widget().set_text(text.into());
+        self
+    }
+
+    /// Sets the color of the text label.
+    pub fn text_color(mut self, color: types::Color) -> Self {
+        self.widget().set_text_color(color);
+        self
+    }
+
+    /// Sets the font size of the text label.
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.widget().set_font_size(font_size);
+        self
+    }
+
+    /// Sets the justification of the text label.
+    pub fn justify(mut self, justify: TextJustify) -> Self {
+        self.widget().set_justify(justify);
+        self
+    }
+
+    /// Sets both the color and the thickness of the border.
+    pub fn border(mut self, color: types::Color, thickness: u8) -> Self {
+        self.widget().set_border(color, thickness);
+        self
+    }
+
+    /// Sets the closure that is called with the ID of the button when it is clicked.
+    pub fn on_click<F: FnMut(WidgetId) + 'static>(mut self, callback: F) -> Self {
+        self.widget().on_click(Box::new(callback));
+        self
+    }
+}
+
+/// Implementation of the `ButtonWidget` object with the `Widget` traits implemented.
+/// The base widget is a `BoxWidget`, which overlays a `TextWidget` over the top.  This `Widget`
+/// tracks the mouse and the keyboard internally, and calls its `on_click` callback when it is
+/// clicked.
+impl Widget for ButtonWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Button
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text_widget.get_text().to_string()
+    }
+
+    /// Sets the `Point` of origin for this widget and the base widget, given the X and Y
+    /// coordinates.  Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.base_widget.set_origin(x, y);
+        self.text_widget.set_origin(x, y);
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and the base widget, given width and height.
+    /// Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.base_widget.set_size(w, h);
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the padding between the border of this widget and its text.  Invalidates the widget
+    /// afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.update_text_padding();
+        self.invalidate();
+    }
+
+    /// Retrieves the rectangle inside of which the text is drawn: the size of the widget, inset
+    /// by the thickness of the border, and then by the padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        self.text_widget.get_content_rect()
+    }
+
+    /// Sets the color for this widget.  Invalidates the widget afterward.
+    fn set_color(&mut self, color: types::Color) {
+        self.config().set(MainColor(color));
+        self.update_visual_state();
+    }
+
+    /// Retrieves the color of this widget, which is drawn while the button is neither hovered
+    /// nor pressed.  Defaults to the background color of the current `Theme` if not set.
+    fn get_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(MainColor(get_current_theme().background_color))
+            .0
+    }
+
+    /// Enables or disables this widget.  A disabled button forgets that it was hovered or
+    /// pressed, and its text is drawn in the disabled color of the current `Theme`.  Invalidates
+    /// the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.hovered = false;
+        self.mouse_pressed = false;
+        self.key_pressed = false;
+        self.text_widget.set_disabled(disabled);
+        self.update_visual_state();
+    }
+
+    /// Passes keys other than `Space` and `Enter` on to the parents of the button, such as
+    /// `Escape` to the dialog that the button is in.  All other events are handled by
+    /// `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::KeyPressed { key, .. } if key != Key::Space && key != Key::Return => {
+                (EventResult::NotHandled, None)
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::MouseEntered { .. } => {
+                self.hovered = true;
+                self.update_visual_state();
+            }
+
+            CallbackEvent::MouseExited { .. } => {
+                self.hovered = false;
+                self.update_visual_state();
+            }
+
+            CallbackEvent::MouseButtonDown { button, .. } if button == left_button => {
+                self.mouse_pressed = true;
+                self.update_visual_state();
+            }
+
+            CallbackEvent::MouseButtonUpInside {
+                widget_id, button, ..
+            } if button == left_button => {
+                let was_pressed = self.mouse_pressed;
+
+                self.mouse_pressed = false;
+                self.update_visual_state();
+
+                if was_pressed {
+                    return self.click(widget_id, button);
+                }
+            }
+
+            CallbackEvent::MouseButtonUpOutside { button, .. } if button == left_button => {
+                self.mouse_pressed = false;
+                self.update_visual_state();
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key,
+                state,
+                ..
+            } if key == Key::Space || key == Key::Return => match state {
+                ButtonState::Press => {
+                    self.key_pressed = true;
+                    self.update_visual_state();
+                }
+                ButtonState::Release => {
+                    if self.key_pressed {
+                        self.key_pressed = false;
+                        self.update_visual_state();
+
+                        return self.click(widget_id, Button::Keyboard(key));
+                    }
+                }
+            },
+
+            CallbackEvent::LostFocus { .. } => {
+                self.key_pressed = false;
+                self.update_visual_state();
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the base widget and text, and redraws the button in the
+    /// colors of the new theme.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.base_widget.theme_changed(theme);
+        self.text_widget.theme_changed(theme);
+        self.update_visual_state();
+    }
+
+    /// Sets the corner radius for this widget and its box.  Invalidates the widget afterward.
+    fn set_corner_radius(&mut self, radius: f64) {
+        self.config().set(CornerRadius(radius));
+        self.base_widget.set_corner_radius(radius);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the box of the button, and then its text.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.base_widget.draw(c, g, clip);
+        self.text_widget.draw(c, g, clip);
+
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,344 @@
+// Checkbox Widget
+// Extensible widget for the widget library - handles a checkbox with a text label.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the new checked state of a `CheckboxWidget` when it changes.
+pub type ToggleCallback = Box<dyn FnMut(bool)>;
+
+/// The width and height of the box of a `CheckboxWidget`, in pixels.
+pub const CHECKBOX_BOX_SIZE: i32 = 16;
+
+/// The number of pixels between the box of a `CheckboxWidget` and its label.
+const CHECKBOX_LABEL_SPACING: i32 = 6;
+
+/// This is the `CheckboxWidget`, which draws a small box followed by a text label.  Clicking
+/// anywhere on the box or the label, or pressing `Space` while it is focused, toggles whether it
+/// is checked, and calls its `on_toggle` callback with the new state.  A checked box is drawn
+/// with a check mark, and an indeterminate box, which is neither checked nor unchecked, with a
+/// bar.
+///
+/// The state is stored in the `CheckedState` config key, so that it can also be set and read
+/// from code.  A disabled checkbox is drawn in the disabled color of the current `Theme`, and
+/// cannot be toggled by the user.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::checkbox_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Checkbox", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut checkbox = CheckboxWidget::new(
+///        pushrod.get_factory(),
+///        "OpenSans-Regular.ttf".to_string(),
+///        "Remember me".to_string(),
+///    );
+///
+///    checkbox.set_origin(20, 20);
+///    checkbox.set_size(200, 24);
+///    checkbox.on_toggle(Box::new(|checked| eprintln!("Checked: {}", checked)));
+///    pushrod.add_widget("RememberMe", Box::new(checkbox));
+/// # }
+/// ```
+pub struct CheckboxWidget {
+    config: Configurable,
+    text_widget: TextWidget,
+    on_toggle: Option<ToggleCallback>,
+}
+
+/// Implementation of the constructor for the `CheckboxWidget`.
+impl CheckboxWidget {
+    /// Creates a new unchecked checkbox with a `text` label, drawn with the font `font_name` from
+    /// the `assets` directory, in the font size of the current `Theme`.  The checkbox can be
+    /// focused, so that it can be toggled from the keyboard.
+    pub fn new(factory: &mut GfxFactory, font_name: String, text: String) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: TextWidget::new(factory, font_name, text, font_size, TextJustify::Left),
+            on_toggle: None,
+        };
+
+        widget.set_focusable(true);
+        widget.update_text_padding();
+        widget
+    }
+
+    /// Sets the closure that is called with the new checked state when it changes.
+    pub fn on_toggle(&mut self, callback: ToggleCallback) {
+        self.on_toggle = Some(callback);
+    }
+
+    /// Sets the text label of the checkbox.
+    pub fn set_text(&mut self, text: String) {
+        self.text_widget.set_text(text);
+        self.invalidate();
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.text_widget.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.text_widget.get_text_color()
+    }
+
+    /// Checks or unchecks the checkbox.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.set_check_state(if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        });
+    }
+
+    /// Indicates whether the checkbox is checked.  An indeterminate checkbox is not checked.
+    pub fn is_checked(&mut self) -> bool {
+        self.get_check_state() == CheckState::Checked
+    }
+
+    /// Sets the state of the checkbox.  If the state changed, the `on_toggle` callback is called
+    /// with whether the checkbox is now checked, and the widget is invalidated.
+    pub fn set_check_state(&mut self, state: CheckState) {
+        if state == self.get_check_state() {
+            return;
+        }
+
+        self.config().set(CheckedState(state));
+
+        if let Some(callback) = &mut self.on_toggle {
+            callback(state == CheckState::Checked);
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the state of the checkbox.  Defaults to `CheckState::Unchecked`.
+    pub fn get_check_state(&mut self) -> CheckState {
+        self.config().get_or(CheckedState(CheckState::Unchecked)).0
+    }
+
+    /// Returns the rectangle of the box, centered vertically at the left of the content
+    /// rectangle.
+    fn get_box_rect(&mut self) -> [f64; 4] {
+        let [x, y, _, h] = self.get_content_rect();
+        let box_y = y + (h - CHECKBOX_BOX_SIZE) / 2;
+        let size = f64::from(CHECKBOX_BOX_SIZE);
+
+        [f64::from(x), f64::from(box_y), size, size]
+    }
+
+    /// Insets the text by the padding, and by the box and the spacing next to it, so that the
+    /// label is drawn to the right of the box.
+    fn update_text_padding(&mut self) {
+        let mut padding = self.get_padding();
+
+        padding.left += CHECKBOX_BOX_SIZE + CHECKBOX_LABEL_SPACING;
+        self.text_widget.set_padding(padding);
+    }
+}
+
+/// Implementation of the `CheckboxWidget` object with the `Widget` traits implemented.
+/// The label is a `TextWidget`, which is drawn next to the box.  This `Widget` responds to the
+/// mouse and the keyboard internally, and calls its `on_toggle` callback when it is toggled.
+impl Widget for CheckboxWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::CheckBox
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text_widget.get_text().to_string()
+    }
+
+    fn get_accessible_checked(&mut self) -> Option<CheckState> {
+        Some(self.get_check_state())
+    }
+
+    /// Sets the `Point` of origin for this widget and the label, given the X and Y coordinates.
+    /// Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.text_widget.set_origin(x, y);
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and the label, given width and height.  Invalidates the
+    /// widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the padding around the box and the label.  Invalidates the widget afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.update_text_padding();
+        self.invalidate();
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the size
+    /// of the box and the label next to it, plus the padding, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => {
+                let text_size = self.text_widget.get_preferred_size();
+
+                crate::core::point::Size {
+                    w: text_size.w,
+                    h: text_size.h.max(CHECKBOX_BOX_SIZE),
+                }
+            }
+        }
+    }
+
+    /// Enables or disables this widget.  A disabled checkbox is drawn in the disabled color of
+    /// the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseButtonUpInside {
+                widget_id, button, ..
+            } if button == Button::Mouse(MouseButton::Left) => {
+                let state = self.get_check_state().toggled();
+
+                self.set_check_state(state);
+
+                return Some(CallbackEvent::WidgetClicked { widget_id, button });
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key: Key::Space,
+                state: ButtonState::Release,
+                ..
+            } => {
+                let state = self.get_check_state().toggled();
+
+                self.set_check_state(state);
+
+                return Some(CallbackEvent::WidgetClicked {
+                    widget_id,
+                    button: Button::Keyboard(Key::Space),
+                });
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the label, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the label, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the box, the check mark or bar inside of it, and then the label.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let [x, y, w, h] = self.get_box_rect();
+        let (border_color, mark_color) = if self.is_disabled() {
+            (theme.disabled_color, theme.disabled_color)
+        } else {
+            (theme.border_color, theme.accent_color)
+        };
+
+        Rectangle::new(apply_opacity(self.get_color())).draw([x, y, w, h], clip, c.transform, g);
+        Rectangle::new_border(apply_opacity(border_color), 0.5).draw(
+            [x + 0.5, y + 0.5, w - 1.0, h - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        match self.get_check_state() {
+            CheckState::Checked => {
+                let line = Line::new_round(apply_opacity(mark_color), 1.0);
+
+                line.draw(
+                    [x + w * 0.2, y + h * 0.5, x + w * 0.42, y + h * 0.72],
+                    clip,
+                    c.transform,
+                    g,
+                );
+                line.draw(
+                    [x + w * 0.42, y + h * 0.72, x + w * 0.8, y + h * 0.28],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+            CheckState::Indeterminate => {
+                Rectangle::new(apply_opacity(mark_color)).draw(
+                    [x + w * 0.25, y + h * 0.42, w * 0.5, h * 0.16],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+            CheckState::Unchecked => (),
+        }
+
+        self.text_widget.draw(c, g, clip);
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
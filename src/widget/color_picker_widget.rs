@@ -0,0 +1,862 @@
+// Color Picker Widget
+// Extensible widget for the widget library - handles choosing a color with the mouse.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ::image::{Rgba, RgbaImage};
+use piston_window::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::{point_in_bounds, Point, Size};
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::dropdown_widget::get_dropdown_list_placement;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the new color of a `ColorPickerWidget`.
+pub type ColorChangedCallback = Box<dyn FnMut(types::Color)>;
+
+/// The space around the parts of a `ColorPickerWidget`, and between them, in pixels.
+pub const COLOR_PICKER_PADDING: i32 = 6;
+
+/// The width of the hue and alpha strips of a `ColorPickerWidget`, in pixels.
+pub const COLOR_PICKER_STRIP_WIDTH: i32 = 16;
+
+/// The height of the preview swatch of a `ColorPickerWidget`, in pixels.
+pub const COLOR_PICKER_SWATCH_HEIGHT: i32 = 24;
+
+/// The default size of the picker that a compact `ColorPickerWidget` opens, in pixels.
+pub const DEFAULT_COLOR_PICKER_POPUP_SIZE: (i32, i32) = (240, 200);
+
+/// The width and height of the texture of the saturation and value square, which is stretched
+/// to the size of the square.
+const SATURATION_VALUE_TEXTURE_SIZE: u32 = 64;
+
+/// The height of the texture of the hue strip.
+const HUE_TEXTURE_HEIGHT: u32 = 180;
+
+/// The size of the squares of the checkerboard that is drawn behind translucent colors.
+const CHECKER_SIZE: i32 = 6;
+
+static NEXT_COLOR_PICKER_POPUP_NUMBER: AtomicUsize = AtomicUsize::new(1);
+
+/// The parts of a `ColorPickerWidget`, as placed by `get_color_picker_layout`.  Each part is
+/// the rectangle `[x, y, w, h]`, relative to the picker.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorPickerLayout {
+    /// The square that chooses the saturation, from left to right, and the value, from bottom
+    /// to top.
+    pub square: [i32; 4],
+
+    /// The strip that chooses the hue, from top to bottom.
+    pub hue: [i32; 4],
+
+    /// The strip that chooses the alpha, from opaque at the top to transparent at the bottom,
+    /// if it is shown.
+    pub alpha: Option<[i32; 4]>,
+
+    /// The preview of the current color.
+    pub swatch: [i32; 4],
+}
+
+/// Places the parts of a `ColorPickerWidget` of `size`: the swatch along the bottom, the hue
+/// strip, and the alpha strip if `show_alpha` is set, on the right, and the saturation and
+/// value square in the rest of the picker.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::Size;
+/// # use pushrod::widget::color_picker_widget::*;
+/// # fn main() {
+///    let layout = get_color_picker_layout(&Size { w: 240, h: 200 }, true);
+///
+///    assert_eq!(layout.square, [6, 6, 184, 158]);
+///    assert_eq!(layout.hue, [196, 6, 16, 158]);
+///    assert_eq!(layout.alpha, Some([218, 6, 16, 158]));
+///    assert_eq!(layout.swatch, [6, 170, 228, 24]);
+///
+///    let layout = get_color_picker_layout(&Size { w: 240, h: 200 }, false);
+///
+///    assert_eq!(layout.square, [6, 6, 206, 158]);
+///    assert_eq!(layout.alpha, None);
+/// # }
+/// ```
+pub fn get_color_picker_layout(size: &Size, show_alpha: bool) -> ColorPickerLayout {
+    let padding = COLOR_PICKER_PADDING;
+    let strip_w = COLOR_PICKER_STRIP_WIDTH;
+    let strips = if show_alpha { 2 } else { 1 };
+    let top_h = (size.h - padding * 3 - COLOR_PICKER_SWATCH_HEIGHT).max(0);
+    let square_w = (size.w - padding * 2 - (strip_w + padding) * strips).max(0);
+    let hue_x = padding * 2 + square_w;
+
+    ColorPickerLayout {
+        square: [padding, padding, square_w, top_h],
+        hue: [hue_x, padding, strip_w, top_h],
+        alpha: if show_alpha {
+            Some([hue_x + strip_w + padding, padding, strip_w, top_h])
+        } else {
+            None
+        },
+        swatch: [
+            padding,
+            padding * 2 + top_h,
+            (size.w - padding * 2).max(0),
+            COLOR_PICKER_SWATCH_HEIGHT,
+        ],
+    }
+}
+
+/// Returns where `point` is across and down `rect`, each from 0.0 to 1.0.  Points outside of
+/// the rectangle are clamped to its edges.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::color_picker_widget::*;
+/// # fn main() {
+///    let rect = [10, 10, 100, 50];
+///
+///    assert_eq!(get_fraction_in_rect(&rect, &make_point_i32(60, 35)), (0.5, 0.5));
+///    assert_eq!(get_fraction_in_rect(&rect, &make_point_i32(-20, 500)), (0.0, 1.0));
+/// # }
+/// ```
+pub fn get_fraction_in_rect(rect: &[i32; 4], point: &Point) -> (f32, f32) {
+    let fraction = |position: i32, start: i32, length: i32| {
+        if length <= 0 {
+            0.0
+        } else {
+            ((position - start) as f32 / length as f32).clamp(0.0, 1.0)
+        }
+    };
+
+    (
+        fraction(point.x, rect[0], rect[2]),
+        fraction(point.y, rect[1], rect[3]),
+    )
+}
+
+/// Converts a color channel from 0.0 to 1.0 to a byte.
+fn to_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Creates the pixels of the saturation and value square for `hue`.
+fn make_saturation_value_image(hue: f32) -> RgbaImage {
+    let last = (SATURATION_VALUE_TEXTURE_SIZE - 1) as f32;
+
+    RgbaImage::from_fn(
+        SATURATION_VALUE_TEXTURE_SIZE,
+        SATURATION_VALUE_TEXTURE_SIZE,
+        |x, y| {
+            let [r, g, b] = hsv_to_rgb([hue, x as f32 / last, 1.0 - y as f32 / last]);
+
+            Rgba([to_byte(r), to_byte(g), to_byte(b), 255])
+        },
+    )
+}
+
+/// Creates the pixels of the hue strip, from a hue of 0 at the top to 360 at the bottom.
+fn make_hue_image() -> RgbaImage {
+    let last = (HUE_TEXTURE_HEIGHT - 1) as f32;
+
+    RgbaImage::from_fn(1, HUE_TEXTURE_HEIGHT, |_, y| {
+        let [r, g, b] = hsv_to_rgb([360.0 * y as f32 / last, 1.0, 1.0]);
+
+        Rgba([to_byte(r), to_byte(g), to_byte(b), 255])
+    })
+}
+
+/// Creates the pixels of the alpha strip, from opaque white at the top to transparent at the
+/// bottom.  The strip is tinted with the current color when it is drawn.
+fn make_alpha_image() -> RgbaImage {
+    let last = (SATURATION_VALUE_TEXTURE_SIZE - 1) as f32;
+
+    RgbaImage::from_fn(1, SATURATION_VALUE_TEXTURE_SIZE, |_, y| {
+        Rgba([255, 255, 255, to_byte(1.0 - y as f32 / last)])
+    })
+}
+
+/// Draws a checkerboard in `rect`, so that the translucent colors drawn over it can be seen.
+fn draw_checkerboard(rect: [i32; 4], c: Context, g: &mut DrawTarget, clip: &DrawState) {
+    let [x, y, w, h] = rect;
+
+    Rectangle::new(apply_opacity([1.0; 4])).draw(
+        [f64::from(x), f64::from(y), f64::from(w), f64::from(h)],
+        clip,
+        c.transform,
+        g,
+    );
+
+    let dark = Rectangle::new(apply_opacity([0.8, 0.8, 0.8, 1.0]));
+
+    for row in 0..(h + CHECKER_SIZE - 1) / CHECKER_SIZE {
+        for column in 0..(w + CHECKER_SIZE - 1) / CHECKER_SIZE {
+            if (row + column) % 2 == 0 {
+                continue;
+            }
+
+            let (cell_x, cell_y) = (column * CHECKER_SIZE, row * CHECKER_SIZE);
+
+            dark.draw(
+                [
+                    f64::from(x + cell_x),
+                    f64::from(y + cell_y),
+                    f64::from(CHECKER_SIZE.min(w - cell_x)),
+                    f64::from(CHECKER_SIZE.min(h - cell_y)),
+                ],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+    }
+}
+
+/// Draws a one pixel border around `rect`.
+fn draw_outline(
+    rect: [i32; 4],
+    color: types::Color,
+    c: Context,
+    g: &mut DrawTarget,
+    clip: &DrawState,
+) {
+    let [x, y, w, h] = rect;
+
+    Rectangle::new_border(apply_opacity(color), 0.5).draw(
+        [
+            f64::from(x) + 0.5,
+            f64::from(y) + 0.5,
+            f64::from(w - 1),
+            f64::from(h - 1),
+        ],
+        clip,
+        c.transform,
+        g,
+    );
+}
+
+/// Queues `callback` to be called with the `WidgetStore` once the current update is over.
+fn queue_callback<F>(deferred: &mut Vec<DeferredCallback>, callback: F)
+where
+    F: FnMut(&mut WidgetStore) + 'static,
+{
+    deferred.push(Rc::new(RefCell::new(Box::new(callback))));
+}
+
+/// The part of a `ColorPickerWidget` that is being dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PickerPart {
+    Square,
+    Hue,
+    Alpha,
+}
+
+/// The state that a compact `ColorPickerWidget` shares with the picker that it opens.
+#[derive(Default)]
+struct PopupState {
+    /// The color that was picked in the popup, which the compact picker takes on its next
+    /// update.
+    picked: Option<types::Color>,
+
+    /// Whether or not the popup is open.
+    open: bool,
+}
+
+type SharedPopupState = Rc<RefCell<PopupState>>;
+
+/// This is the `ColorPickerWidget`, which chooses a color with the mouse: the hue is picked from
+/// a strip of hues, the saturation and value from a square that shows every shade of that hue,
+/// and, optionally, the alpha from a strip that fades the color out.  A swatch along the bottom
+/// shows the color.  Pressing the mouse button on the square or a strip picks the color under
+/// the mouse, and dragging keeps picking colors, even outside of the picker, where the position
+/// is clamped to the edge of the part that is being dragged.
+///
+/// The color of the picker is its `get_color`, and is changed with `set_color`, like the color
+/// of any other `Widget`, so it can also be changed through the `WidgetStore`.  The
+/// `on_color_changed` callback is called whenever the mouse picks a new color, including
+/// while dragging.
+///
+/// In compact mode, the picker only draws the swatch, and opens a full picker as a popup below
+/// it when it is clicked, or when `Space` or `Enter` is pressed while it is focused.  The popup
+/// is closed by clicking outside of it, or by pressing `Escape` or `Enter`, and the compact
+/// picker takes on every color that is picked in it.
+///
+/// The gradients are drawn from textures, which are created the first time the picker is
+/// drawn.  The texture of the square is only created again when the hue changes.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::color_picker_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Colors", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut picker = ColorPickerWidget::new(pushrod.get_factory());
+///    let mut compact_picker = ColorPickerWidget::new(pushrod.get_factory());
+///
+///    picker.set_origin(20, 20);
+///    picker.set_size(240, 200);
+///    picker.set_show_alpha(true);
+///    picker.set_color([0.2, 0.4, 0.8, 1.0]);
+///    picker.on_color_changed(Box::new(|color| eprintln!("Picked {:?}", color)));
+///
+///    compact_picker.set_origin(280, 20);
+///    compact_picker.set_size(60, 24);
+///    compact_picker.set_compact(true);
+///
+///    pushrod.add_widget("Picker", Box::new(picker));
+///    pushrod.add_widget("CompactPicker", Box::new(compact_picker));
+///    pushrod.run_without_handler();
+/// # }
+/// ```
+pub struct ColorPickerWidget {
+    config: Configurable,
+    factory: GfxFactory,
+    hsv: [f32; 3],
+    alpha: f32,
+    show_alpha: bool,
+    compact: bool,
+    popup_size: (i32, i32),
+    dragging: Option<PickerPart>,
+    square_texture: Option<(f32, G2dTexture)>,
+    hue_texture: Option<G2dTexture>,
+    alpha_texture: Option<G2dTexture>,
+    on_color_changed: Option<ColorChangedCallback>,
+    widget_id: WidgetId,
+    owner_id: WidgetId,
+    popup_state: SharedPopupState,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `ColorPickerWidget`.
+impl ColorPickerWidget {
+    /// Creates a new color picker, with an opaque white color, requiring the current
+    /// `PistonWindow`'s factory object, which the textures of the gradients are created with.
+    pub fn new(factory: &mut GfxFactory) -> Self {
+        let mut widget = Self {
+            config: Configurable::new(),
+            factory: factory.clone(),
+            hsv: [0.0, 0.0, 1.0],
+            alpha: 1.0,
+            show_alpha: false,
+            compact: false,
+            popup_size: DEFAULT_COLOR_PICKER_POPUP_SIZE,
+            dragging: None,
+            square_texture: None,
+            hue_texture: None,
+            alpha_texture: None,
+            on_color_changed: None,
+            widget_id: WidgetId::INVALID,
+            owner_id: WidgetId::INVALID,
+            popup_state: Rc::new(RefCell::new(PopupState::default())),
+            deferred: Vec::new(),
+        };
+
+        widget.set_focusable(true);
+        widget
+    }
+
+    /// Sets the closure function that is called with the new color whenever the mouse picks a
+    /// color.  It is not called when the color is changed with `set_color`.
+    pub fn on_color_changed(&mut self, callback: ColorChangedCallback) {
+        self.on_color_changed = Some(callback);
+    }
+
+    /// Sets the color from its hue, in degrees, its saturation and value, and its alpha, each
+    /// from 0.0 to 1.0.  Keeping the hue separately means that it is not lost when picking a
+    /// gray.  Invalidates the widget afterward.
+    pub fn set_hsva(&mut self, hue: f32, saturation: f32, value: f32, alpha: f32) {
+        self.hsv = [
+            hue.rem_euclid(360.0),
+            saturation.clamp(0.0, 1.0),
+            value.clamp(0.0, 1.0),
+        ];
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self.invalidate();
+    }
+
+    /// Retrieves the hue, saturation, value, and alpha of the color.
+    pub fn get_hsva(&self) -> [f32; 4] {
+        [self.hsv[0], self.hsv[1], self.hsv[2], self.alpha]
+    }
+
+    /// Sets whether or not the alpha strip is shown.  Without it, picking a color keeps its
+    /// alpha.
+    pub fn set_show_alpha(&mut self, show_alpha: bool) {
+        self.show_alpha = show_alpha;
+        self.invalidate();
+    }
+
+    /// Indicates whether or not the alpha strip is shown.  Defaults to `false`.
+    pub fn is_showing_alpha(&self) -> bool {
+        self.show_alpha
+    }
+
+    /// Sets whether or not the picker is compact, only drawing the swatch, and opening a full
+    /// picker as a popup when it is clicked.
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+        self.dragging = None;
+        self.invalidate();
+    }
+
+    /// Indicates whether or not the picker is compact.  Defaults to `false`.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets the size of the picker that a compact picker opens.
+    pub fn set_popup_size(&mut self, w: i32, h: i32) {
+        self.popup_size = (w, h);
+    }
+
+    /// Retrieves the size of the picker that a compact picker opens.  Defaults to
+    /// `DEFAULT_COLOR_PICKER_POPUP_SIZE`.
+    pub fn get_popup_size(&self) -> (i32, i32) {
+        self.popup_size
+    }
+
+    /// Indicates whether or not the popup of a compact picker is open.
+    pub fn is_open(&self) -> bool {
+        self.compact && self.popup_state.borrow().open
+    }
+
+    /// Picks the color under `point` in the part that is being dragged, calling the
+    /// `on_color_changed` callback if the color changed.
+    fn pick(&mut self, point: &Point) {
+        let part = match self.dragging {
+            Some(part) => part,
+            None => return,
+        };
+        let layout = get_color_picker_layout(&self.get_size(), self.show_alpha);
+        let [hue, saturation, value] = self.hsv;
+        let mut alpha = self.alpha;
+        let (hue, saturation, value) = match part {
+            PickerPart::Square => {
+                let (x, y) = get_fraction_in_rect(&layout.square, point);
+
+                (hue, x, 1.0 - y)
+            }
+            PickerPart::Hue => {
+                let (_, y) = get_fraction_in_rect(&layout.hue, point);
+
+                (y * 360.0, saturation, value)
+            }
+            PickerPart::Alpha => {
+                if let Some(rect) = &layout.alpha {
+                    alpha = 1.0 - get_fraction_in_rect(rect, point).1;
+                }
+
+                (hue, saturation, value)
+            }
+        };
+
+        if [hue, saturation, value, alpha] == self.get_hsva() {
+            return;
+        }
+
+        // The hue is not wrapped around, so that a hue of 360 keeps the marker at the bottom.
+        self.hsv = [hue, saturation, value];
+        self.alpha = alpha;
+        self.invalidate();
+        self.color_changed();
+    }
+
+    /// Calls the `on_color_changed` callback with the current color.
+    fn color_changed(&mut self) {
+        let color = self.get_color();
+
+        if let Some(callback) = &mut self.on_color_changed {
+            callback(color);
+        }
+    }
+
+    /// Returns the part of the picker under `point`, if any.
+    fn get_part_at(&mut self, point: &Point) -> Option<PickerPart> {
+        let layout = get_color_picker_layout(&self.get_size(), self.show_alpha);
+        let inside = |rect: &[i32; 4]| point_in_bounds(point, rect);
+
+        if inside(&layout.square) {
+            Some(PickerPart::Square)
+        } else if inside(&layout.hue) {
+            Some(PickerPart::Hue)
+        } else if layout.alpha.as_ref().is_some_and(inside) {
+            Some(PickerPart::Alpha)
+        } else {
+            None
+        }
+    }
+
+    /// Opens a full picker as a popup below a compact picker, once the current update is over.
+    /// Does nothing if it is already open.
+    fn open_popup(&mut self) {
+        if !self.compact || self.is_open() || self.widget_id <= WidgetId::ROOT {
+            return;
+        }
+
+        let mut popup = ColorPickerWidget::new(&mut self.factory);
+        let popup_state = self.popup_state.clone();
+        let owner_id = self.widget_id;
+        let size = self.get_size();
+        let (popup_w, popup_h) = self.popup_size;
+        let [hue, saturation, value] = self.hsv;
+
+        popup.set_hsva(hue, saturation, value, self.alpha);
+        popup.set_show_alpha(self.show_alpha);
+        popup.owner_id = owner_id;
+        popup.popup_state = popup_state.clone();
+        popup.on_color_changed(Box::new({
+            let popup_state = popup_state.clone();
+
+            move |color| popup_state.borrow_mut().picked = Some(color)
+        }));
+
+        let mut popup = Some(popup);
+
+        popup_state.borrow_mut().open = true;
+        queue_callback(&mut self.deferred, move |widget_store| {
+            let mut popup = match popup.take() {
+                Some(popup) => popup,
+                None => return,
+            };
+            let origin = widget_store.get_absolute_origin(owner_id);
+            let window_h = widget_store
+                .get_widget_for_id(WidgetId::ROOT)
+                .borrow_mut()
+                .get_size()
+                .h;
+            let (popup_y, popup_h) =
+                get_dropdown_list_placement(origin.y, size.h, popup_h, window_h);
+            let number = NEXT_COLOR_PICKER_POPUP_NUMBER.fetch_add(1, Ordering::Relaxed);
+
+            popup.set_origin(origin.x, popup_y);
+            popup.set_size(popup_w, popup_h);
+
+            let popup_id =
+                widget_store.add_widget(&format!("_PushrodColorPicker{}", number), Box::new(popup));
+
+            widget_store.open_popup(popup_id);
+            widget_store.set_focused_widget(popup_id);
+        });
+        self.invalidate();
+    }
+
+    /// Closes this picker, if it was opened as a popup.
+    fn close_popup(&mut self) {
+        let widget_id = self.widget_id;
+
+        if self.owner_id > WidgetId::ROOT && widget_id > WidgetId::ROOT {
+            queue_callback(&mut self.deferred, move |widget_store| {
+                widget_store.close_popup(widget_id)
+            });
+        }
+    }
+
+    /// Draws a swatch of the current color in `rect`, over a checkerboard if it is translucent.
+    fn draw_swatch(&mut self, rect: [i32; 4], c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let color = self.get_color();
+        let [x, y, w, h] = rect;
+
+        if color[3] < 1.0 {
+            draw_checkerboard(rect, c, g, clip);
+        }
+
+        Rectangle::new(apply_opacity(color)).draw(
+            [f64::from(x), f64::from(y), f64::from(w), f64::from(h)],
+            clip,
+            c.transform,
+            g,
+        );
+        draw_outline(rect, get_current_theme().border_color, c, g, clip);
+    }
+
+    /// Creates the textures of the gradients that do not exist yet, and the texture of the
+    /// square again if the hue has changed since it was created.
+    fn update_textures(&mut self) {
+        let hue = self.hsv[0];
+        let settings = TextureSettings::new();
+
+        if self
+            .square_texture
+            .as_ref()
+            .map(|(texture_hue, _)| *texture_hue)
+            != Some(hue)
+        {
+            self.square_texture = Texture::from_image(
+                &mut self.factory,
+                &make_saturation_value_image(hue),
+                &settings,
+            )
+            .ok()
+            .map(|texture| (hue, texture));
+        }
+
+        if self.hue_texture.is_none() {
+            self.hue_texture =
+                Texture::from_image(&mut self.factory, &make_hue_image(), &settings).ok();
+        }
+
+        if self.show_alpha && self.alpha_texture.is_none() {
+            self.alpha_texture =
+                Texture::from_image(&mut self.factory, &make_alpha_image(), &settings).ok();
+        }
+    }
+}
+
+/// Implementation of the `ColorPickerWidget` object with the `Widget` traits implemented.
+impl Widget for ColorPickerWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Sets the color of the picker, keeping the current hue if the color is a gray.
+    /// Invalidates the widget afterward.
+    fn set_color(&mut self, color: types::Color) {
+        let [hue, saturation, value] = rgb_to_hsv([color[0], color[1], color[2]]);
+        let hue = if saturation > 0.0 && value > 0.0 {
+            hue
+        } else {
+            self.hsv[0]
+        };
+
+        self.set_hsva(hue, saturation, value, color[3]);
+    }
+
+    /// Retrieves the color of the picker.
+    fn get_color(&mut self) -> types::Color {
+        let [r, g, b] = hsv_to_rgb(self.hsv);
+
+        [r, g, b, self.alpha]
+    }
+
+    /// Enables or disables this widget, which stops any drag that is in progress.  Invalidates
+    /// the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+            self.dragging = None;
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.invalidate();
+    }
+
+    /// Takes on the color that was picked in the popup of a compact picker, calling the
+    /// `on_color_changed` callback.
+    fn update(&mut self, _delta_ms: u64) {
+        if self.owner_id > WidgetId::ROOT {
+            return;
+        }
+
+        let picked = self.popup_state.borrow_mut().picked.take();
+
+        if let Some(color) = picked {
+            self.set_color(color);
+            self.color_changed();
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::MouseEntered { widget_id }
+            | CallbackEvent::MouseMoved { widget_id, .. }
+            | CallbackEvent::GotFocus { widget_id } => {
+                self.widget_id = widget_id;
+
+                if let CallbackEvent::MouseMoved { point, .. } = event {
+                    self.pick(&point);
+                }
+            }
+
+            CallbackEvent::MouseButtonDown {
+                widget_id,
+                button,
+                point,
+            } if button == left_button => {
+                self.widget_id = widget_id;
+
+                if self.compact {
+                    self.open_popup();
+                } else {
+                    self.dragging = self.get_part_at(&point);
+                    self.pick(&point);
+                }
+            }
+
+            CallbackEvent::MouseButtonUpInside { button, .. }
+            | CallbackEvent::MouseButtonUpOutside { button, .. }
+                if button == left_button =>
+            {
+                self.dragging = None;
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key,
+                state: ButtonState::Press,
+                ..
+            } => {
+                self.widget_id = widget_id;
+
+                match key {
+                    Key::Space | Key::Return if self.compact => self.open_popup(),
+                    Key::Escape | Key::Return => self.close_popup(),
+                    _ => (),
+                }
+            }
+
+            CallbackEvent::LostFocus { .. } => self.dragging = None,
+
+            CallbackEvent::PopupClosed { widget_id } => {
+                let owner_id = self.owner_id;
+
+                self.popup_state.borrow_mut().open = false;
+                queue_callback(&mut self.deferred, move |widget_store| {
+                    widget_store.remove_widget(widget_id);
+                    widget_store.set_focused_widget(owner_id);
+                });
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Draws the swatch of a compact picker, or the background, the square, the strips with a
+    /// marker at the current color, and the swatch of a full picker.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+
+        if self.compact {
+            self.draw_swatch([0, 0, size.w, size.h], c, g, clip);
+
+            // Then clear invalidation.
+            self.clear_invalidate();
+            return;
+        }
+
+        let layout = get_color_picker_layout(&size, self.show_alpha);
+        let [hue, saturation, value] = self.hsv;
+        let to_rect = |rect: [i32; 4]| {
+            [
+                f64::from(rect[0]),
+                f64::from(rect[1]),
+                f64::from(rect[2]),
+                f64::from(rect[3]),
+            ]
+        };
+
+        Rectangle::new(apply_opacity(theme.background_color)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+        self.update_textures();
+
+        let image = Image::new().color(apply_opacity([1.0; 4]));
+
+        if let Some((_, texture)) = &self.square_texture {
+            image
+                .rect(to_rect(layout.square))
+                .draw(texture, clip, c.transform, g);
+        }
+
+        if let Some(texture) = &self.hue_texture {
+            image
+                .rect(to_rect(layout.hue))
+                .draw(texture, clip, c.transform, g);
+        }
+
+        if let Some(rect) = layout.alpha {
+            draw_checkerboard(rect, c, g, clip);
+
+            if let Some(texture) = &self.alpha_texture {
+                let [r, g_channel, b] = hsv_to_rgb(self.hsv);
+
+                Image::new()
+                    .color(apply_opacity([r, g_channel, b, 1.0]))
+                    .rect(to_rect(rect))
+                    .draw(texture, clip, c.transform, g);
+            }
+        }
+
+        // The markers are drawn in black or white, whichever stands out from the color.
+        let marker_color = if value > 0.5 && saturation < 0.5 {
+            [0.0, 0.0, 0.0, 1.0]
+        } else {
+            [1.0; 4]
+        };
+        let [x, y, w, h] = layout.square;
+        let marker_x = f64::from(x) + f64::from(saturation) * f64::from(w);
+        let marker_y = f64::from(y) + f64::from(1.0 - value) * f64::from(h);
+
+        Ellipse::new_border(apply_opacity(marker_color), 1.0).draw(
+            [marker_x - 4.0, marker_y - 4.0, 8.0, 8.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let mut strip_markers = vec![(layout.hue, hue / 360.0)];
+
+        if let Some(rect) = layout.alpha {
+            strip_markers.push((rect, 1.0 - self.alpha));
+        }
+
+        for ([x, y, w, h], fraction) in strip_markers {
+            let marker_y = f64::from(y) + f64::from(fraction) * f64::from(h);
+
+            Rectangle::new_border(apply_opacity(theme.text_color), 1.0).draw(
+                [f64::from(x) - 1.0, marker_y - 2.0, f64::from(w) + 2.0, 4.0],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        for rect in &[layout.square, layout.hue] {
+            draw_outline(*rect, theme.border_color, c, g, clip);
+        }
+
+        if let Some(rect) = layout.alpha {
+            draw_outline(rect, theme.border_color, c, g, clip);
+        }
+
+        self.draw_swatch(layout.swatch, c, g, clip);
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -14,9 +14,16 @@
 // limitations under the License.
 
 use piston_window::types::Color;
+use serde::Serialize;
+use std::cell::Cell;
 
+use crate::core::accessibility::AccessibleRole;
+use crate::core::point::Insets;
 use crate::core::point::Point;
 use crate::core::point::Size;
+use crate::widget::gradient::GradientDirection;
+use crate::widget::layout_widget::GridCell;
+use crate::widget::shape::BorderStyle;
 
 /// Powerful macro that automatically creates a configuration object from a specified struct.
 /// Each struct has its own getter, setter, removal of a key (by its value), and checking to see
@@ -30,12 +37,16 @@ macro_rules! impl_configurable {
             use super::*;
 
             pub trait ConfigKeyInner: Sized {
+                fn name() -> &'static str;
                 fn field(config: &Configurable) -> &Option<Self>;
                 fn field_mut(config: &mut Configurable) -> &mut Option<Self>;
             }
 
             $(
             impl ConfigKeyInner for $name {
+                fn name() -> &'static str {
+                    stringify!($name)
+                }
                 fn field(config: &Configurable) -> &Option<Self> {
                     &config.$field
                 }
@@ -51,6 +62,8 @@ macro_rules! impl_configurable {
         #[derive(Default)]
         pub struct Configurable {
             $( $field: Option<$name>, )*
+            changed_keys: Vec<&'static str>,
+            geometry_changed: bool,
         }
     }
 }
@@ -59,6 +72,12 @@ macro_rules! impl_configurable {
 #[derive(Clone, Debug)]
 pub struct Invalidate;
 
+/// The area of a `Widget`, in its own coordinates (X, Y, width, height), that needs to be
+/// redrawn, when only part of it has been invalidated.  Setting any other configuration value
+/// invalidates the whole `Widget`, and removes this area.  See `Widget::invalidate_area`.
+#[derive(Clone, Debug)]
+pub struct InvalidatedArea(pub [i32; 4]);
+
 /// Origin `Point` at which a `Widget` exists on the display window.
 #[derive(Clone, Debug)]
 pub struct Origin(pub Point);
@@ -79,6 +98,16 @@ pub struct BorderColor(pub Color);
 #[derive(Clone, Debug)]
 pub struct BorderWidth(pub u8);
 
+/// Thickness (in pixels) of each side of the border of a `BoxWidget`, overriding its
+/// `BorderWidth`.  Sides with a thickness of 0 are not drawn.
+#[derive(Clone, Debug)]
+pub struct BorderSides(pub Insets);
+
+/// Style in which the border of a `BoxWidget`, or a `GroupBoxWidget`, is stroked.  Defaults to
+/// `BorderStyle::Solid` if not set.
+#[derive(Clone, Debug)]
+pub struct BorderStroke(pub BorderStyle);
+
 /// `Color` of text to be displayed in a `TextWidget`.
 #[derive(Clone, Debug)]
 pub struct TextColor(pub Color);
@@ -87,18 +116,541 @@ pub struct TextColor(pub Color);
 #[derive(Clone, Debug)]
 pub struct SecondaryColor(pub Color);
 
+/// `Color` that the fill of a `Widget` blends into from its main color, along the direction of
+/// its `GradientOrientation`.  Without this, the fill is a single color.  See
+/// `Widget::set_gradient`.
+#[derive(Clone, Debug)]
+pub struct GradientColor(pub Color);
+
+/// Direction in which the fill of a `Widget` blends from its main color to its `GradientColor`.
+/// Defaults to `GradientDirection::Vertical` if not set.
+#[derive(Clone, Debug)]
+pub struct GradientOrientation(pub GradientDirection);
+
+/// Radius, in pixels, of the rounded corners of the background and border of a `Widget`.  Radii
+/// that are larger than half of the width or height of the `Widget` are reduced to fit.  Defaults
+/// to 0.0, for square corners.
+#[derive(Clone, Debug)]
+pub struct CornerRadius(pub f64);
+
+/// Existence of this object indicates that the corners of a `Widget` that are cut away by its
+/// `CornerRadius` are not part of it for mouse events, which reach the `Widget`s underneath
+/// instead.  Without this, the whole rectangle of the `Widget` is hit-tested, which is cheaper.
+#[derive(Clone, Debug)]
+pub struct RoundedHitTest;
+
+/// `Color` of the drop shadow that is drawn underneath a `Widget`.  Without this, the `Widget`
+/// casts no shadow.  See `Widget::set_shadow`.
+#[derive(Clone, Debug)]
+pub struct ShadowColor(pub Color);
+
+/// Distance, in pixels, that the drop shadow of a `Widget` is moved from the `Widget`.
+/// Defaults to no offset if not set.
+#[derive(Clone, Debug)]
+pub struct ShadowOffset(pub Point);
+
+/// Number of pixels over which the edge of the drop shadow of a `Widget` fades out.
+/// Defaults to 0.0, for a hard edge, if not set.
+#[derive(Clone, Debug)]
+pub struct ShadowBlur(pub f64);
+
+/// Existence of this object indicates that a `Widget` is hidden: it is not drawn, and does not
+/// receive mouse events or keyboard focus.  `Widget`s are visible by default.
+#[derive(Clone, Debug)]
+pub struct Hidden;
+
+/// Existence of this object indicates that a `Widget` is not clipped to its own bounds when it
+/// is drawn, so it can intentionally draw outside of them, for things like shadows.  It is still
+/// clipped to the bounds of its parents.
+#[derive(Clone, Debug)]
+pub struct Unclipped;
+
+/// Existence of this object indicates that a `Widget` is disabled: it does not receive mouse or
+/// keyboard events, or keyboard focus.  `Widget`s are enabled by default.
+#[derive(Clone, Debug)]
+pub struct Disabled;
+
+/// Existence of this object indicates that a `Widget` can receive keyboard focus using Tab and
+/// Shift+Tab.
+#[derive(Clone, Debug)]
+pub struct Focusable;
+
+/// Existence of this object indicates that a `Widget` handles the Tab key itself while it has
+/// focus, so the run loop will not use it to move the focus to another `Widget`.
+#[derive(Clone, Debug)]
+pub struct ConsumesTab;
+
+/// The arrow keys that a `Widget` handles itself while it has focus: the first is whether it
+/// handles left and right, and the second is whether it handles up and down.  A gamepad moves
+/// the focus away from such a `Widget` only in the other directions.
+#[derive(Clone, Debug)]
+pub struct ConsumesArrows(pub bool, pub bool);
+
+/// Opacity of a `Widget`, between 0.0 (fully transparent) and 1.0 (fully opaque).  The opacity of
+/// a `Widget` is multiplied by the opacity of its parents when it is drawn.
+#[derive(Clone, Debug)]
+pub struct Opacity(pub f32);
+
+/// This `enum` specifies the mouse cursor that is shown while the mouse is over a `Widget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CursorStyle {
+    /// The platform's default cursor.
+    Default,
+
+    /// An arrow.
+    Arrow,
+
+    /// An I-beam, indicating text that can be selected or edited.
+    Text,
+
+    /// A pointing hand, indicating something that can be clicked.
+    Hand,
+
+    /// A crosshair, for precise selection.
+    Crosshair,
+
+    /// Indicates something that can be moved.
+    Move,
+
+    /// Indicates that the application is busy.
+    Wait,
+
+    /// Indicates that something cannot be done.
+    NotAllowed,
+
+    /// Indicates something that can be resized horizontally.
+    ResizeHorizontal,
+
+    /// Indicates something that can be resized vertically.
+    ResizeVertical,
+}
+
+/// Number of milliseconds after which a `TimerWidget`, or any other timed `Widget`, fires.
+#[derive(Clone, Debug)]
+pub struct Timeout(pub u64);
+
+/// Mouse cursor that is shown while the mouse is over a `Widget`.
+#[derive(Clone, Debug)]
+pub struct Cursor(pub CursorStyle);
+
+/// Text of the tooltip that is shown when the mouse rests over a `Widget`.
+#[derive(Clone, Debug)]
+pub struct TooltipText(pub String);
+
+/// Role of a `Widget` that assistive tools are told, instead of the role of its type.  See
+/// `Widget::set_accessible_role`.
+#[derive(Clone, Debug)]
+pub struct AccessibilityRole(pub AccessibleRole);
+
+/// Name of a `Widget` that assistive tools read out, instead of its text.  See
+/// `Widget::set_accessible_name`.
+#[derive(Clone, Debug)]
+pub struct AccessibilityName(pub String);
+
+/// Existence of this object indicates that mouse events pass through a `Widget` to whatever is
+/// underneath it, so it can never be hovered or clicked.  Its children are not affected.
+#[derive(Clone, Debug)]
+pub struct PassThrough;
+
+/// Existence of this object indicates that a `Widget` handles multi-touch: the touches that
+/// start on it are delivered as touch events, one for each finger, rather than as the mouse.
+/// See `Widget::set_multi_touch`.
+#[derive(Clone, Debug)]
+pub struct MultiTouch;
+
+/// Space between the edges of a `Widget` and its contents.  Borders are drawn at the outer edge
+/// of a `Widget`, and its contents are drawn inside of the padding.
+#[derive(Clone, Debug)]
+pub struct Padding(pub Insets);
+
+/// Space that is kept clear around the outside of a `Widget`, used by layout managers to space
+/// out neighboring `Widget`s.  It is not part of the `Widget`'s bounds.
+#[derive(Clone, Debug)]
+pub struct Margin(pub Insets);
+
+/// Existence of this object indicates that a `Widget` is drawn into an offscreen texture when it
+/// is invalidated, and that the texture is copied to the screen instead of drawing the `Widget`
+/// on every frame.  See `Widget::set_cached`.
+#[derive(Clone, Debug)]
+pub struct Cached;
+
+/// Size that a `Widget` would like to be given by layout managers, such as the
+/// `HorizontalLayoutWidget`.  See `Widget::set_preferred_size`.
+#[derive(Clone, Debug)]
+pub struct PreferredSize(pub Size);
+
+/// Share of the space left over in a layout that a `Widget` is stretched to fill, relative to the
+/// weights of the other `Widget`s in the layout.  See `Widget::set_layout_weight`.
+#[derive(Clone, Debug)]
+pub struct LayoutWeight(pub u32);
+
+/// The cell, or block of cells, of a `GridLayoutWidget` that a `Widget` is placed in.  See
+/// `Widget::set_grid_cell`.
+#[derive(Clone, Debug)]
+pub struct GridPlacement(pub GridCell);
+
+/// Distance in pixels between the left edge of a `Widget` and the left edge of its parent,
+/// which the `Widget` is kept at as the parent is resized.  See `Widget::set_anchor`.
+#[derive(Clone, Debug)]
+pub struct AnchorLeft(pub i32);
+
+/// Distance in pixels between the top edge of a `Widget` and the top edge of its parent.
+#[derive(Clone, Debug)]
+pub struct AnchorTop(pub i32);
+
+/// Distance in pixels between the right edge of a `Widget` and the right edge of its parent.
+#[derive(Clone, Debug)]
+pub struct AnchorRight(pub i32);
+
+/// Distance in pixels between the bottom edge of a `Widget` and the bottom edge of its parent.
+#[derive(Clone, Debug)]
+pub struct AnchorBottom(pub i32);
+
+/// This `enum` specifies an edge of a `Widget` that can be anchored to the same edge of its
+/// parent.  See `Widget::set_anchor`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnchorEdge {
+    Left,
+    Top,
+    Right,
+    Bottom,
+}
+
+/// The anchors of a `Widget`: the distance that each edge is kept from the same edge of the
+/// parent, or `None` for edges that are not anchored.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Anchors {
+    pub left: Option<i32>,
+    pub top: Option<i32>,
+    pub right: Option<i32>,
+    pub bottom: Option<i32>,
+}
+
+/// Implementation of the `Anchors`.
+impl Anchors {
+    /// Indicates whether no edge is anchored.
+    pub fn is_empty(&self) -> bool {
+        self.left.is_none() && self.top.is_none() && self.right.is_none() && self.bottom.is_none()
+    }
+
+    /// Returns the bounds `[x, y, w, h]` of a `Widget` with these anchors, relative to its parent,
+    /// given the size of the parent and the current `bounds` of the `Widget`.  Anchors win over
+    /// the origin and size that were set: an edge that is anchored moves the `Widget` so that
+    /// the edge is at its distance from the parent, and when opposite edges are both anchored,
+    /// the `Widget` is stretched between them, ignoring its size.  The size is never negative.
+    /// The origin and size along a direction without anchors are kept.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::widget::config::*;
+    /// # fn main() {
+    ///    let parent = Size { w: 400, h: 300 };
+    ///    let bounds = [20, 20, 100, 50];
+    ///
+    ///    // Pinned to the bottom right-hand corner, with a 10 pixel inset.
+    ///    let corner = Anchors {
+    ///        right: Some(10),
+    ///        bottom: Some(10),
+    ///        ..Anchors::default()
+    ///    };
+    ///
+    ///    assert_eq!(corner.resolve(&parent, bounds), [290, 240, 100, 50]);
+    ///
+    ///    // Stretched across the top, replacing the origin and width that were set.
+    ///    let header = Anchors {
+    ///        left: Some(0),
+    ///        top: Some(0),
+    ///        right: Some(0),
+    ///        bottom: None,
+    ///    };
+    ///
+    ///    assert_eq!(header.resolve(&parent, bounds), [0, 0, 400, 50]);
+    ///
+    ///    // A left anchor wins over the origin, and keeps the width.
+    ///    let left = Anchors {
+    ///        left: Some(5),
+    ///        ..Anchors::default()
+    ///    };
+    ///
+    ///    assert_eq!(left.resolve(&parent, bounds), [5, 20, 100, 50]);
+    ///
+    ///    // Opposite anchors that do not fit leave no room, rather than a negative size.
+    ///    let squeezed = Anchors {
+    ///        top: Some(200),
+    ///        bottom: Some(200),
+    ///        ..Anchors::default()
+    ///    };
+    ///
+    ///    assert_eq!(squeezed.resolve(&parent, bounds), [20, 200, 100, 0]);
+    ///    assert_eq!(Anchors::default().resolve(&parent, bounds), bounds);
+    /// # }
+    /// ```
+    pub fn resolve(&self, parent_size: &Size, bounds: [i32; 4]) -> [i32; 4] {
+        let (x, w) =
+            resolve_anchor_span(self.left, self.right, parent_size.w, bounds[0], bounds[2]);
+        let (y, h) =
+            resolve_anchor_span(self.top, self.bottom, parent_size.h, bounds[1], bounds[3]);
+
+        [x, y, w, h]
+    }
+}
+
+/// Resolves the anchors of one direction of a `Widget`, returning its origin and length.
+fn resolve_anchor_span(
+    start: Option<i32>,
+    end: Option<i32>,
+    parent_length: i32,
+    origin: i32,
+    length: i32,
+) -> (i32, i32) {
+    match (start, end) {
+        (Some(start), Some(end)) => (start, (parent_length - start - end).max(0)),
+        (Some(start), None) => (start, length),
+        (None, Some(end)) => (parent_length - end - length, length),
+        (None, None) => (origin, length),
+    }
+}
+
+/// This `enum` specifies the state of a `Widget` that can be checked, such as a
+/// `CheckboxWidget`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum CheckState {
+    /// Not checked.
+    Unchecked,
+
+    /// Checked.
+    Checked,
+
+    /// Neither checked nor unchecked, such as a checkbox for a group of options of which only
+    /// some are checked.
+    Indeterminate,
+}
+
+/// Implementation of the `CheckState`.
+impl CheckState {
+    /// Returns the state that a click changes this state to: checked states become unchecked,
+    /// and unchecked and indeterminate states become checked.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::config::*;
+    /// # fn main() {
+    ///    assert_eq!(CheckState::Unchecked.toggled(), CheckState::Checked);
+    ///    assert_eq!(CheckState::Checked.toggled(), CheckState::Unchecked);
+    ///    assert_eq!(CheckState::Indeterminate.toggled(), CheckState::Checked);
+    /// # }
+    /// ```
+    pub fn toggled(self) -> CheckState {
+        match self {
+            CheckState::Checked => CheckState::Unchecked,
+            CheckState::Unchecked | CheckState::Indeterminate => CheckState::Checked,
+        }
+    }
+}
+
+/// Check state of a `Widget` that can be checked, such as a `CheckboxWidget`.
+#[derive(Clone, Debug)]
+pub struct CheckedState(pub CheckState);
+
+/// Name of the radio group that a `Widget`, such as a `RadioButtonWidget`, belongs to.  Only one
+/// `Widget` in each radio group is checked at a time.  See `WidgetStore::select_radio_button`.
+#[derive(Clone, Debug)]
+pub struct RadioGroupId(pub String);
+
+/// Smallest value of a `Widget` that edits a number, such as a `SliderWidget`.
+#[derive(Clone, Debug)]
+pub struct MinValue(pub f64);
+
+/// Largest value of a `Widget` that edits a number, such as a `SliderWidget`.
+#[derive(Clone, Debug)]
+pub struct MaxValue(pub f64);
+
+/// Step between the values of a `Widget` that edits a number, such as a `SliderWidget`, which
+/// snaps its value to a multiple of the step above its smallest value.  A step of 0 allows any
+/// value.
+#[derive(Clone, Debug)]
+pub struct StepValue(pub f64);
+
+/// Titles of the tabs of a `Widget` that shows one of its children at a time, such as a
+/// `TabWidget`.  Each title belongs to the child with the same index.  See
+/// `WidgetStore::add_tab`.
+#[derive(Clone, Debug)]
+pub struct TabTitles(pub Vec<String>);
+
+/// Index of the tab, and so of the child, that a `Widget` with `TabTitles` shows.
+#[derive(Clone, Debug)]
+pub struct SelectedTab(pub usize);
+
+/// Existence of this object indicates that the children of a `Widget`, such as a collapsed
+/// `GroupBoxWidget`, are hidden, without changing whether each child is visible.
+#[derive(Clone, Debug)]
+pub struct Collapsed;
+
+/// This `enum` specifies the direction in which a `Widget`, such as a `ProgressBarWidget`, is
+/// laid out or filled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orientation {
+    /// From left to right.
+    Horizontal,
+
+    /// From bottom to top.
+    Vertical,
+}
+
+/// This `enum` specifies a width, height, or position along one direction, either as a number
+/// of pixels, or as a percentage of the same dimension of the parent, or of the window for
+/// `Widget`s that have no parent.  See `Widget::set_size_dimensions`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dimension {
+    /// A number of pixels.
+    Pixels(u32),
+
+    /// A percentage of the parent, where `100.0` is the whole width or height.
+    Percent(f32),
+}
+
+/// The width and height of a `Widget`, as `Dimension`s that are resolved into its size whenever
+/// its parent is resized.  See `Widget::set_size_dimensions`.
+#[derive(Clone, Debug)]
+pub struct SizeDimensions(pub Dimension, pub Dimension);
+
+/// The X and Y position of a `Widget` in its parent, as `Dimension`s that are resolved into its
+/// origin whenever its parent is resized.  See `Widget::set_origin_dimensions`.
+#[derive(Clone, Debug)]
+pub struct OriginDimensions(pub Dimension, pub Dimension);
+
+/// Returns the bounds `[x, y, w, h]` of a `Widget`, relative to its parent, given the size of
+/// the parent, the `origin` and `size` dimensions of the `Widget`, if it has any, and its current
+/// `bounds`, which are used along the directions that have no dimensions.  Percentages are
+/// resolved by rounding the positions of the edges, rather than the size, so `Widget`s that are
+/// placed next to each other by percentages meet without a gap, or an overlap, whatever the size
+/// of the parent.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::config::*;
+/// # fn main() {
+///    let parent = Size { w: 401, h: 300 };
+///    let half = (Dimension::Percent(50.0), Dimension::Pixels(100));
+///    let left = resolve_dimensions(None, Some(half), &parent, [0, 0, 10, 10]);
+///    let right = resolve_dimensions(
+///        Some((Dimension::Percent(50.0), Dimension::Pixels(0))),
+///        Some(half),
+///        &parent,
+///        [0, 0, 10, 10],
+///    );
+///
+///    assert_eq!(left, [0, 0, 201, 100]);
+///    assert_eq!(right, [201, 0, 200, 100]);
+///    assert_eq!(left[2] + right[2], parent.w);
+/// # }
+/// ```
+pub fn resolve_dimensions(
+    origin: Option<(Dimension, Dimension)>,
+    size: Option<(Dimension, Dimension)>,
+    parent_size: &Size,
+    bounds: [i32; 4],
+) -> [i32; 4] {
+    let (x, w) = resolve_dimension_span(
+        origin.map(|origin| origin.0),
+        size.map(|size| size.0),
+        parent_size.w,
+        bounds[0],
+        bounds[2],
+    );
+    let (y, h) = resolve_dimension_span(
+        origin.map(|origin| origin.1),
+        size.map(|size| size.1),
+        parent_size.h,
+        bounds[1],
+        bounds[3],
+    );
+
+    [x, y, w, h]
+}
+
+/// Resolves the position and length of one direction of a `Widget`.
+fn resolve_dimension_span(
+    origin: Option<Dimension>,
+    length: Option<Dimension>,
+    parent_length: i32,
+    current_origin: i32,
+    current_length: i32,
+) -> (i32, i32) {
+    let percent_of_parent = |percent: f32| f64::from(percent) / 100.0 * f64::from(parent_length);
+    let start = match origin {
+        Some(Dimension::Pixels(pixels)) => f64::from(pixels),
+        Some(Dimension::Percent(percent)) => percent_of_parent(percent),
+        None => f64::from(current_origin),
+    };
+    let end = match length {
+        Some(Dimension::Pixels(pixels)) => start.round() + f64::from(pixels),
+        Some(Dimension::Percent(percent)) => start + percent_of_parent(percent),
+        None => start.round() + f64::from(current_length),
+    };
+    let x = start.round() as i32;
+
+    (x, (end.round() as i32 - x).max(0))
+}
+
 /// This macro implements the availability of configuration items.  The first value is the name
 /// of the `struct` that the configuration object applies, and the second value is the name of the
 /// private inner trait that is responsible for setting and getting values for that `struct`
 impl_configurable! {
     Invalidate => invalidate,
+    InvalidatedArea => invalidated_area,
     Origin => origin,
     BodySize => body_size,
     MainColor => main_color,
     BorderColor => border_color,
     BorderWidth => border_width,
+    BorderSides => border_sides,
+    BorderStroke => border_stroke,
     TextColor => text_color,
     SecondaryColor => secondary_color,
+    GradientColor => gradient_color,
+    GradientOrientation => gradient_orientation,
+    CornerRadius => corner_radius,
+    RoundedHitTest => rounded_hit_test,
+    MultiTouch => multi_touch,
+    ShadowColor => shadow_color,
+    ShadowOffset => shadow_offset,
+    ShadowBlur => shadow_blur,
+    Hidden => hidden,
+    Unclipped => unclipped,
+    Disabled => disabled,
+    Focusable => focusable,
+    ConsumesTab => consumes_tab,
+    ConsumesArrows => consumes_arrows,
+    Opacity => opacity,
+    Timeout => timeout,
+    Cursor => cursor,
+    TooltipText => tooltip_text,
+    AccessibilityRole => accessibility_role,
+    AccessibilityName => accessibility_name,
+    PassThrough => pass_through,
+    Padding => padding,
+    Margin => margin,
+    Cached => cached,
+    PreferredSize => preferred_size,
+    LayoutWeight => layout_weight,
+    GridPlacement => grid_placement,
+    AnchorLeft => anchor_left,
+    AnchorTop => anchor_top,
+    AnchorRight => anchor_right,
+    AnchorBottom => anchor_bottom,
+    SizeDimensions => size_dimensions,
+    OriginDimensions => origin_dimensions,
+    CheckedState => checked_state,
+    RadioGroupId => radio_group_id,
+    MinValue => min_value,
+    MaxValue => max_value,
+    StepValue => step_value,
+    TabTitles => tab_titles,
+    SelectedTab => selected_tab,
+    Collapsed => collapsed,
 }
 
 /// Implementation of the default `Configurable` object.
@@ -119,6 +671,13 @@ impl_configurable! {
 ///
 ///   // Or you can use declared types with ::<> as such:
 ///   let body_size = &config.get::<BodySize>().unwrap().0;
+///
+///   // Values that have not been set can fall back to a default value of the same type:
+///   let main_color = config.get_or(MainColor([1.0; 4])).0;
+///
+///   assert_eq!(main_origin.0.y, 100);
+///   assert_eq!(body_size.w, 150);
+///   assert_eq!(main_color, [1.0; 4]);
 /// }
 /// ```
 impl Configurable {
@@ -126,19 +685,154 @@ impl Configurable {
         Self::default()
     }
 
+    /// Sets a configuration value.  Setting any value also marks the configuration as
+    /// `Invalidate`d, so the `Widget` that owns it is redrawn, and records the key as changed.
+    /// Setting any value other than an `InvalidatedArea` invalidates the whole `Widget`.
     pub fn set<T: ConfigKey>(&mut self, value: T) {
+        if T::name() != <InvalidatedArea as private::ConfigKeyInner>::name() {
+            self.invalidated_area = None;
+        }
+
+        let geometry = self.get_geometry();
+
         *T::field_mut(self) = Some(value);
+        self.invalidate = Some(Invalidate);
+        self.record_change::<T>(geometry);
     }
 
     pub fn get<T: ConfigKey>(&self) -> Option<&T> {
         T::field(self).as_ref()
     }
 
+    /// Retrieves a copy of a configuration value, or `default` if the value has not been set.
+    pub fn get_or<T: ConfigKey + Clone>(&self, default: T) -> T {
+        self.get::<T>().cloned().unwrap_or(default)
+    }
+
+    /// Removes a configuration value.  If the value was set, the key is recorded as changed.
     pub fn remove<T: ConfigKey>(&mut self) {
-        *T::field_mut(self) = None;
+        let geometry = self.get_geometry();
+
+        if T::field_mut(self).take().is_some() {
+            self.record_change::<T>(geometry);
+        }
     }
 
     pub fn contains_key<T: ConfigKey>(&self) -> bool {
         T::field(self).is_some()
     }
+
+    /// Retrieves the name of a configuration key, which is the name of its `struct`.
+    pub fn key_name<T: ConfigKey>() -> &'static str {
+        T::name()
+    }
+
+    /// Removes and returns the names of the keys that have been set or removed since the last
+    /// call, in the order in which they first changed.  Each key is only listed once, no matter
+    /// how many times it changed.  `Invalidate` and `InvalidatedArea` are never listed.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::core::point::Point;
+    /// # fn main() {
+    ///    let mut config = Configurable::new();
+    ///
+    ///    config.set(MainColor([1.0, 0.0, 0.0, 1.0]));
+    ///    config.set(Origin(Point { x: 10, y: 10 }));
+    ///    config.set(MainColor([0.0, 1.0, 0.0, 1.0]));
+    ///    config.remove::<Invalidate>();
+    ///
+    ///    assert_eq!(config.take_changed_keys(), vec!["MainColor", "Origin"]);
+    ///    assert!(config.take_changed_keys().is_empty());
+    ///    assert_eq!(Configurable::key_name::<BodySize>(), "BodySize");
+    /// # }
+    /// ```
+    pub fn take_changed_keys(&mut self) -> Vec<&'static str> {
+        std::mem::take(&mut self.changed_keys)
+    }
+
+    /// Indicates whether the `Origin` or `BodySize` has been set to a different value, or removed,
+    /// since the last call, and clears the indication.  The `WidgetStore` uses this to only look
+    /// at the bounds of the `Widget`s that have moved or resized.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::core::point::Point;
+    /// # fn main() {
+    ///    let mut config = Configurable::new();
+    ///
+    ///    config.set(Origin(Point { x: 10, y: 10 }));
+    ///    assert!(config.take_geometry_changed());
+    ///    assert!(!config.take_geometry_changed());
+    ///
+    ///    config.set(Origin(Point { x: 10, y: 10 }));
+    ///    config.set(MainColor([1.0, 0.0, 0.0, 1.0]));
+    ///    assert!(!config.take_geometry_changed());
+    ///
+    ///    config.remove::<Origin>();
+    ///    assert!(config.take_geometry_changed());
+    /// # }
+    /// ```
+    pub fn take_geometry_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.geometry_changed, false)
+    }
+
+    /// Retrieves a copy of the `Origin` and `BodySize`, to tell whether a change moved or resized
+    /// the `Widget`.
+    fn get_geometry(&self) -> (Option<Point>, Option<Size>) {
+        (
+            self.origin.as_ref().map(|origin| origin.0.clone()),
+            self.body_size.as_ref().map(|body_size| body_size.0.clone()),
+        )
+    }
+
+    fn record_change<T: ConfigKey>(&mut self, geometry: (Option<Point>, Option<Size>)) {
+        let name = T::name();
+
+        if self.get_geometry() != geometry {
+            self.geometry_changed = true;
+            GEOMETRY_GENERATION.with(|generation| generation.set(generation.get().wrapping_add(1)));
+        }
+
+        if name != <Invalidate as private::ConfigKeyInner>::name()
+            && name != <InvalidatedArea as private::ConfigKeyInner>::name()
+            && !self.changed_keys.contains(&name)
+        {
+            self.changed_keys.push(name);
+        }
+    }
+}
+
+thread_local! {
+    static GEOMETRY_GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Retrieves a number that changes whenever the `Origin` or `BodySize` of any `Configurable` on
+/// this thread is set to a different value, or removed.  The `WidgetStore` compares it with the number it last saw, to
+/// know when the bounds of its `Widget`s have to be looked at again.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::config::*;
+/// # use pushrod::core::point::Point;
+/// # fn main() {
+///    let mut config = Configurable::new();
+///    let generation = get_geometry_generation();
+///
+///    config.set(MainColor([1.0, 0.0, 0.0, 1.0]));
+///    assert_eq!(get_geometry_generation(), generation);
+///
+///    config.set(Origin(Point { x: 10, y: 10 }));
+///    assert_ne!(get_geometry_generation(), generation);
+///
+///    let generation = get_geometry_generation();
+///
+///    config.set(Origin(Point { x: 10, y: 10 }));
+///    assert_eq!(get_geometry_generation(), generation);
+/// # }
+/// ```
+pub fn get_geometry_generation() -> u64 {
+    GEOMETRY_GENERATION.with(|generation| generation.get())
 }
@@ -0,0 +1,245 @@
+// Countdown Widget
+// Displays the number of seconds remaining in a countdown, triggering a callback at zero.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::text_widget::*;
+use crate::widget::timer_widget::*;
+use crate::widget::widget::*;
+
+/// Closure type that converts the number of whole seconds remaining into the text to display.
+pub type CountdownFormatCallback = Box<dyn FnMut(u64) -> String>;
+
+/// Closure type that is called when a countdown reaches zero.
+pub type CountdownFinishedCallback = Box<dyn FnMut()>;
+
+/// Converts a number of milliseconds into the number of whole seconds to display in a countdown.
+/// Partial seconds are rounded up, so a countdown only shows 0 once it has finished.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::countdown_widget::*;
+/// # fn main() {
+///    assert_eq!(round_up_seconds(5000), 5);
+///    assert_eq!(round_up_seconds(4001), 5);
+///    assert_eq!(round_up_seconds(1), 1);
+///    assert_eq!(round_up_seconds(0), 0);
+/// # }
+/// ```
+pub fn round_up_seconds(ms: u64) -> u64 {
+    ms.div_ceil(1_000)
+}
+
+/// This is the `CountdownWidget`, which draws the number of seconds remaining in a countdown
+/// within its bounds, and calls the `on_finished` closure when the countdown reaches zero.  The
+/// countdown is timed using a `TimerWidget`, so it is driven by the run loop's update events,
+/// and can be paused and resumed.
+///
+/// Example usage:
+/// IN PROGRESS
+pub struct CountdownWidget {
+    config: Configurable,
+    base_widget: CanvasWidget,
+    text_widget: TextWidget,
+    timer: TimerWidget,
+    displayed_seconds: u64,
+    finished: bool,
+    format: CountdownFormatCallback,
+    on_finished: Option<CountdownFinishedCallback>,
+}
+
+/// Implementation of the constructor for the `CountdownWidget`.
+impl CountdownWidget {
+    /// Creates a new `CountdownWidget` object, requiring the current `PistonWindow`'s factory
+    /// object, the name of the font (filename in the `assets` directory), the font size, the
+    /// desired text justification, and the duration of the countdown in milliseconds.  The
+    /// countdown starts immediately.
+    pub fn new(
+        factory: &mut GfxFactory,
+        font_name: String,
+        font_size: u32,
+        justify: TextJustify,
+        duration: u64,
+    ) -> Self {
+        let mut format: CountdownFormatCallback = Box::new(|seconds| format!("{}", seconds));
+        let displayed_seconds = round_up_seconds(duration);
+        let mut text_widget = TextWidget::new(
+            factory,
+            font_name,
+            format(displayed_seconds),
+            font_size,
+            justify,
+        );
+        let mut timer = TimerWidget::new();
+
+        text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
+        timer.set_timeout(duration);
+        timer.set_one_shot(true);
+
+        Self {
+            config: Configurable::new(),
+            base_widget: CanvasWidget::new(),
+            text_widget,
+            timer,
+            displayed_seconds,
+            finished: false,
+            format,
+            on_finished: None,
+        }
+    }
+
+    /// Sets the closure used to convert the number of whole seconds remaining into the text
+    /// that is displayed.  Defaults to the number of seconds.
+    pub fn set_format(&mut self, format: CountdownFormatCallback) {
+        self.format = format;
+        self.refresh_text();
+    }
+
+    /// Sets the closure function that is called when the countdown reaches zero.
+    pub fn on_finished(&mut self, callback: CountdownFinishedCallback) {
+        self.on_finished = Some(callback);
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.text_widget.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.text_widget.get_text_color()
+    }
+
+    /// Returns the number of milliseconds remaining in the countdown.
+    pub fn get_remaining_ms(&self) -> u64 {
+        if self.finished {
+            0
+        } else {
+            self.timer.get_remaining_ms()
+        }
+    }
+
+    /// Returns the number of whole seconds remaining in the countdown, rounded up.
+    pub fn get_remaining_seconds(&self) -> u64 {
+        round_up_seconds(self.get_remaining_ms())
+    }
+
+    /// Indicates whether or not the countdown has reached zero.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Pauses the countdown.
+    pub fn pause(&mut self) {
+        self.timer.pause();
+    }
+
+    /// Resumes a paused countdown from where it left off.
+    pub fn resume(&mut self) {
+        self.timer.resume();
+    }
+
+    /// Restarts the countdown from its full duration.
+    pub fn restart(&mut self) {
+        self.timer.reset();
+        self.finished = false;
+        self.refresh_text();
+    }
+
+    /// Updates the displayed text if the number of whole seconds remaining has changed.
+    fn refresh_text(&mut self) {
+        let seconds = self.get_remaining_seconds();
+
+        self.displayed_seconds = seconds;
+        self.text_widget.set_text((self.format)(seconds));
+        self.invalidate();
+    }
+}
+
+/// Implementation of the `CountdownWidget` object with the `Widget` traits implemented.
+/// The base widget is a `CanvasWidget`, which paints the background, with a `TextWidget` drawn
+/// over the top.
+impl Widget for CountdownWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Sets the `Point` of origin for this widget and the text widget, given the X and Y
+    /// coordinates.  Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.base_widget.set_origin(x, y);
+        self.text_widget.set_origin(x, y);
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and the text widget, given width and height.  Invalidates
+    /// the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.base_widget.set_size(w, h);
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the background color for this widget.  Invalidates the widget afterward.
+    fn set_color(&mut self, color: types::Color) {
+        self.config().set(MainColor(color));
+        self.base_widget.set_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the background color of this widget.
+    fn get_color(&mut self) -> types::Color {
+        self.base_widget.get_color()
+    }
+
+    /// Ticks the countdown timer, updating the displayed text when the number of whole seconds
+    /// remaining changes, and calling `on_finished` when the countdown reaches zero.
+    fn update(&mut self, delta_ms: u64) {
+        if self.finished {
+            return;
+        }
+
+        self.timer.update(delta_ms);
+
+        if self.timer.get_fire_count() > 0 {
+            self.finished = true;
+            self.refresh_text();
+
+            if let Some(callback) = &mut self.on_finished {
+                callback();
+            }
+        } else if self.get_remaining_seconds() != self.displayed_seconds {
+            self.refresh_text();
+        }
+    }
+
+    /// Draws the contents of the widget in this order:
+    ///
+    /// - Base widget first
+    /// - Text showing the time remaining
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.base_widget.draw(c, g, clip);
+        self.text_widget.draw(c, g, clip);
+
+        self.clear_invalidate();
+    }
+}
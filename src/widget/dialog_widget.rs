@@ -0,0 +1,548 @@
+// Dialog Widget
+// Extensible widget for the widget library - handles modal dialogs over a dimmed window.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The color of the scrim that dims the window behind a modal `DialogWidget`, unless another
+/// color is set with `DialogWidget::set_scrim_color`.  Its alpha is the opacity of the scrim.
+pub const DEFAULT_SCRIM_COLOR: types::Color = [0.0, 0.0, 0.0, 0.4];
+
+/// The space between the title of a `DialogWidget` and the edges of its title bar, in pixels.
+const DIALOG_TITLE_PADDING: i32 = 6;
+
+/// Returns the origin that centers a dialog that is `dialog_w` by `dialog_h` pixels in a window
+/// that is `window_w` by `window_h` pixels.  A dialog that is larger than the window is kept at
+/// its top left corner, so that its title can always be seen.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::dialog_widget::*;
+/// # fn main() {
+///    assert_eq!(get_dialog_origin(800, 600, 300, 200), (250, 200));
+///
+///    // A dialog that does not fit is moved to the top left corner of the window.
+///    assert_eq!(get_dialog_origin(400, 300, 500, 200), (0, 50));
+/// # }
+/// ```
+pub fn get_dialog_origin(window_w: i32, window_h: i32, dialog_w: i32, dialog_h: i32) -> (i32, i32) {
+    (
+        ((window_w - dialog_w) / 2).max(0),
+        ((window_h - dialog_h) / 2).max(0),
+    )
+}
+
+/// This is the `DialogCloser`, a handle that closes the modal `DialogWidget` that it was taken
+/// from with `DialogWidget::get_closer`, with a result.  It can be cloned into the callbacks of
+/// the buttons of the dialog, which have no access to the `WidgetStore`: the dialog is closed
+/// as soon as the `WidgetStore` has finished handling the current event.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::dialog_widget::*;
+/// # fn main() {
+///    let closer = DialogCloser::new();
+///    let ok_closer = closer.clone();
+///
+///    assert_eq!(closer.take_request(), None);
+///
+///    ok_closer.close(Some("ok"));
+///    assert_eq!(closer.take_request(), Some(Some(String::from("ok"))));
+///    assert_eq!(closer.take_request(), None);
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct DialogCloser {
+    request: Rc<RefCell<Option<Option<String>>>>,
+}
+
+/// Implementation of the `DialogCloser`.
+impl DialogCloser {
+    /// Creates a new handle that has not been asked to close its dialog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks for the dialog to be closed with `result`, such as the ID of the button that closed
+    /// it, or `None` if it was dismissed.  Only the last request before the dialog is closed
+    /// counts.
+    pub fn close(&self, result: Option<&str>) {
+        *self.request.borrow_mut() = Some(result.map(String::from));
+    }
+
+    /// Returns, and clears, the result that the dialog was last asked to close with, or `None`
+    /// if it has not been asked to close.  This is used by the `WidgetStore`.
+    pub fn take_request(&self) -> Option<Option<String>> {
+        self.request.borrow_mut().take()
+    }
+}
+
+/// This is the `DialogWidget`, a panel with a title bar that is shown as a modal dialog with
+/// `WidgetStore::show_modal`, or `Pushrod::show_modal`.  The dialog floats above all other
+/// `Widget`s, centered in the window, and the rest of the window is dimmed by a translucent
+/// scrim, which keeps all mouse and keyboard events from reaching the `Widget`s outside of the
+/// dialog until it is closed.  While the dialog is open, Tab and Shift+Tab only move the focus
+/// among the dialog and its children.
+///
+/// The contents of the dialog, such as its message, buttons and inputs, are added to it as
+/// children once it has been shown, using the ID returned by `show_modal`.  They are placed
+/// below the title bar; see `get_content_rect`.  A button closes the dialog by calling
+/// `close` on a `DialogCloser` taken from the dialog with `get_closer`, with a result that
+/// identifies the button.  `Escape` closes the dialog with no result, unless this is turned off
+/// with `set_close_on_escape`, and a click on the scrim can be made to do the same with
+/// `set_close_on_scrim_click`.  Once the dialog has been closed, it is removed from the
+/// `WidgetStore`, along with its children, the focus returns to where it was before the dialog
+/// was shown, and the `on_closed` callback is called with the result.
+///
+/// A dialog can be shown from another, such as to confirm a choice: dialogs are stacked in the
+/// order in which they are shown, only the topmost one receives events, and closing a dialog
+/// also closes the dialogs shown from it.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::dialog_widget::*;
+/// # use pushrod::widget::font_cache::*;
+/// # use pushrod::widget::button_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn confirm_quit(widget_store: &mut WidgetStore, factory: &mut GfxFactory) {
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let font = get_font(&font_name).unwrap();
+///    let mut dialog = DialogWidget::from_font(font, "Quit".to_string());
+///    let closer = dialog.get_closer();
+///
+///    dialog.set_size(300, 120);
+///    dialog.on_closed(Box::new(|result, widget_store| {
+///        if result.as_deref() == Some("quit") {
+///            widget_store.request_close();
+///        }
+///    }));
+///
+///    let dialog_id = widget_store.show_modal(dialog);
+///    let mut quit_button = ButtonWidget::new(factory, font_name, "Quit".to_string());
+///
+///    quit_button.set_origin(190, 70);
+///    quit_button.set_size(100, 32);
+///    quit_button.on_click(Box::new(move |_| closer.close(Some("quit"))));
+///    widget_store.add_widget_to_parent("QuitButton", Box::new(quit_button), dialog_id);
+/// # }
+/// # fn main() {}
+/// ```
+pub struct DialogWidget {
+    config: Configurable,
+    text_widget: Box<TextWidget>,
+    closer: DialogCloser,
+    close_on_escape: bool,
+    close_on_scrim_click: bool,
+    default_result: Option<String>,
+    scrim_color: types::Color,
+    on_closed: Option<ModalClosedCallback>,
+}
+
+/// Implementation of the constructor for the `DialogWidget`.
+impl DialogWidget {
+    /// Creates a new dialog with a `title`, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  Panics if the font cannot be
+    /// loaded; use `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String, title: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, title)
+    }
+
+    /// Creates a new dialog with a `title` that is drawn in a font from the font cache.  The
+    /// dialog closes on `Escape`, but not on a click on the scrim.  The dialog itself can be
+    /// focused, so that it receives `Escape` when none of its children has the focus.
+    pub fn from_font(font: SharedGlyphs, title: String) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget = TextWidget::from_font(font, title, font_size, TextJustify::Left);
+
+        text_widget.set_ellipsize(true);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: Box::new(text_widget),
+            closer: DialogCloser::new(),
+            close_on_escape: true,
+            close_on_scrim_click: false,
+            default_result: None,
+            scrim_color: DEFAULT_SCRIM_COLOR,
+            on_closed: None,
+        };
+
+        widget.set_focusable(true);
+        widget
+    }
+
+    /// Sets the closure that is called with the result of the dialog, and the `WidgetStore`,
+    /// once the dialog has been closed.  The result is `None` if the dialog was dismissed with
+    /// `Escape`, a click on the scrim, or by closing the dialog that it was shown from.
+    pub fn on_closed(&mut self, callback: ModalClosedCallback) {
+        self.on_closed = Some(callback);
+    }
+
+    /// Takes the closure set with `on_closed`, if any.  This is used by the `WidgetStore` when
+    /// the dialog is shown.
+    pub fn take_on_closed(&mut self) -> Option<ModalClosedCallback> {
+        self.on_closed.take()
+    }
+
+    /// Returns a handle that closes this dialog with a result.
+    pub fn get_closer(&self) -> DialogCloser {
+        self.closer.clone()
+    }
+
+    /// Sets the title.  Invalidates the widget afterward.
+    pub fn set_title(&mut self, title: &str) {
+        self.text_widget.set_text(title);
+        self.invalidate();
+    }
+
+    /// Retrieves the title.
+    pub fn get_title(&self) -> &str {
+        self.text_widget.get_text()
+    }
+
+    /// Sets the font size of the title.  Invalidates the widget afterward.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.text_widget.set_font_size(font_size);
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the title.  Defaults to the font size of the current `Theme`.
+    pub fn get_font_size(&self) -> u32 {
+        self.text_widget.get_font_size()
+    }
+
+    /// Sets the color of the title for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the title for this `Widget`.
+    /// Defaults to the background color of the current `Theme` if not set, as the title is drawn
+    /// on the accent color.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().background_color))
+            .0
+    }
+
+    /// Sets the border color for this widget.
+    pub fn set_border_color(&mut self, color: types::Color) {
+        self.config().set(BorderColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the border color of this widget.
+    /// Defaults to the border color of the current `Theme` if not set.
+    pub fn get_border_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(BorderColor(get_current_theme().border_color))
+            .0
+    }
+
+    /// Sets whether `Escape` closes the dialog, with no result.
+    pub fn set_close_on_escape(&mut self, close_on_escape: bool) {
+        self.close_on_escape = close_on_escape;
+    }
+
+    /// Indicates whether `Escape` closes the dialog.  Defaults to `true`.
+    pub fn is_close_on_escape(&self) -> bool {
+        self.close_on_escape
+    }
+
+    /// Sets the result that the dialog is closed with when `Enter` is pressed, and not handled by
+    /// the focused child of the dialog, such as a button, which handles `Enter` itself.  `None`
+    /// leaves `Enter` to the application.
+    pub fn set_default_result(&mut self, result: Option<&str>) {
+        self.default_result = result.map(String::from);
+    }
+
+    /// Retrieves the result that the dialog is closed with when `Enter` is pressed.  Defaults to
+    /// `None`.
+    pub fn get_default_result(&self) -> Option<&str> {
+        self.default_result.as_deref()
+    }
+
+    /// Sets whether a click on the scrim outside of the dialog closes it, with no result.  This
+    /// only has an effect before the dialog is shown.
+    pub fn set_close_on_scrim_click(&mut self, close_on_scrim_click: bool) {
+        self.close_on_scrim_click = close_on_scrim_click;
+    }
+
+    /// Indicates whether a click on the scrim closes the dialog.  Defaults to `false`.
+    pub fn is_close_on_scrim_click(&self) -> bool {
+        self.close_on_scrim_click
+    }
+
+    /// Sets the color of the scrim that dims the window behind the dialog, where the alpha of the
+    /// color is the opacity of the scrim.  This only has an effect before the dialog is shown.
+    pub fn set_scrim_color(&mut self, color: types::Color) {
+        self.scrim_color = color;
+    }
+
+    /// Retrieves the color of the scrim.  Defaults to `DEFAULT_SCRIM_COLOR`.
+    pub fn get_scrim_color(&self) -> types::Color {
+        self.scrim_color
+    }
+
+    /// Moves the dialog to the center of a window of the size `size`.
+    pub fn center_in(&mut self, size: &crate::core::point::Size) {
+        let dialog_size = self.get_size();
+        let (x, y) = get_dialog_origin(size.w, size.h, dialog_size.w, dialog_size.h);
+
+        self.set_origin(x, y);
+    }
+
+    /// Returns the height of the title bar at the top of the dialog.
+    fn get_title_height(&self) -> i32 {
+        (f64::from(self.text_widget.get_font_size()) * DEFAULT_LINE_SPACING).ceil() as i32
+            + DIALOG_TITLE_PADDING * 2
+    }
+}
+
+/// Implementation of the `DialogWidget` object with the `Widget` traits implemented.  The title
+/// is drawn by a `TextWidget`.
+impl Widget for DialogWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Dialog
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.get_title().to_string()
+    }
+
+    /// Retrieves the rectangle inside of which the children should be placed: the size of the
+    /// widget, inset by its one pixel border, and the title bar at the top, and then by the
+    /// padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+        let title_h = self.get_title_height();
+        let padding = self.get_padding();
+
+        make_insets(
+            title_h + padding.top,
+            1 + padding.right,
+            1 + padding.bottom,
+            1 + padding.left,
+        )
+        .inset_size(&size)
+    }
+
+    /// Keeps the mouse wheel from reaching the `Widget`s underneath the dialog, and closes the
+    /// dialog when `Escape` is pressed, if it closes on `Escape`, or when `Enter` is pressed, if
+    /// it has a default result.  All other events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { .. } => (EventResult::Consumed, None),
+
+            CallbackEvent::KeyPressed {
+                key: Key::Escape,
+                state,
+                ..
+            } if self.close_on_escape => {
+                if state == ButtonState::Press {
+                    self.closer.close(None);
+                }
+
+                (EventResult::Consumed, None)
+            }
+
+            CallbackEvent::KeyPressed {
+                key: Key::Return,
+                state,
+                ..
+            } if self.default_result.is_some() => {
+                if state == ButtonState::Press {
+                    self.closer.close(self.default_result.as_deref());
+                }
+
+                (EventResult::Consumed, None)
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    /// Stays centered in the window when it is resized.
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if let CallbackEvent::WindowResized { size } = event {
+            self.center_in(&size);
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the title, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the title, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, the title bar in the accent color of the current `Theme`, with the
+    /// title in it, and then the border.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let title_h = self.get_title_height();
+        let text_color = self.get_text_color();
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, w, h],
+            clip,
+            c.transform,
+            g,
+        );
+        Rectangle::new(apply_opacity(theme.accent_color)).draw(
+            [0.0, 0.0, w, f64::from(title_h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        self.text_widget.set_size(
+            (size.w - DIALOG_TITLE_PADDING * 2).max(0),
+            title_h - DIALOG_TITLE_PADDING * 2,
+        );
+        self.text_widget.set_text_color(text_color);
+        self.text_widget.draw(
+            c.trans(
+                f64::from(DIALOG_TITLE_PADDING),
+                f64::from(DIALOG_TITLE_PADDING),
+            ),
+            g,
+            clip,
+        );
+
+        let border_color = Rectangle::new(apply_opacity(self.get_border_color()));
+
+        for rect in &[
+            [0.0, 0.0, w, 1.0],
+            [0.0, h - 1.0, w, 1.0],
+            [0.0, 0.0, 1.0, h],
+            [w - 1.0, 0.0, 1.0, h],
+        ] {
+            border_color.draw(*rect, clip, c.transform, g);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+/// This is the `ModalScrimWidget`, which covers the whole window behind a modal `DialogWidget`,
+/// dimming it, and keeping mouse events from reaching the `Widget`s underneath.  It is created
+/// by `WidgetStore::show_modal`, and removed along with its dialog, and resizes itself along
+/// with the window.
+pub struct ModalScrimWidget {
+    config: Configurable,
+    closer: Option<DialogCloser>,
+}
+
+/// Implementation of the constructor for the `ModalScrimWidget`.
+impl ModalScrimWidget {
+    /// Creates a new scrim that is drawn in `color`, where the alpha of the color is the
+    /// opacity of the scrim.  A click on the scrim closes the dialog of `closer` with no result,
+    /// if it is given.
+    pub fn new(color: types::Color, closer: Option<DialogCloser>) -> Self {
+        let mut widget = Self {
+            config: Configurable::new(),
+            closer,
+        };
+
+        // The scrim is drawn opaque, and faded with its opacity, so that whatever is underneath
+        // is painted again each time the scrim is.
+        widget.set_color([color[0], color[1], color[2], 1.0]);
+        widget.set_opacity(color[3]);
+        widget
+    }
+}
+
+/// Implementation of the `ModalScrimWidget` object with the `Widget` traits implemented.
+impl Widget for ModalScrimWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Keeps mouse button presses and the mouse wheel from reaching the `Widget`s underneath.
+    /// All other events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseButtonDown { .. } | CallbackEvent::MouseScrolled { .. } => {
+                (EventResult::Consumed, None)
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        match event {
+            CallbackEvent::MouseClicked {
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } => {
+                if let Some(closer) = &self.closer {
+                    closer.close(None);
+                }
+            }
+
+            CallbackEvent::WindowResized { size } => self.set_size(size.w, size.h),
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let size = self.get_size();
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,869 @@
+// Dropdown Widget
+// Extensible widget for the widget library - handles a field that picks an item from a list.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::list_widget::*;
+use crate::widget::shape::{stroke_border, BorderStyle};
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the index and the text of the newly selected item of a
+/// `DropdownWidget`.
+pub type DropdownCallback = Box<dyn FnMut(usize, &str)>;
+
+/// The default number of items that the list of a `DropdownWidget` shows before it scrolls.
+pub const DEFAULT_DROPDOWN_VISIBLE_ROWS: usize = 8;
+
+/// The number of pixels between the left edge of a `DropdownWidget` and its text.
+const DROPDOWN_TEXT_PADDING: i32 = 6;
+
+/// The width of the space for the arrow at the right of a `DropdownWidget`, in pixels.
+const DROPDOWN_ARROW_SPACE: i32 = 20;
+
+/// How far the background of a hovered `DropdownWidget` is blended toward the accent color of
+/// the current `Theme`.
+const DROPDOWN_HOVER_AMOUNT: f32 = 0.2;
+
+/// The number that is given to the name of the next list that is opened, so that every list
+/// has a unique name in the `WidgetStore`.
+static NEXT_DROPDOWN_LIST_NUMBER: AtomicUsize = AtomicUsize::new(1);
+
+/// Returns the top and the height of the list of a `DropdownWidget` whose field is `field_h`
+/// pixels high at the height `field_y` of a window that is `window_h` pixels high.  The list
+/// is `list_h` pixels high, and opens below the field if it fits there.  Otherwise, it opens on
+/// whichever side has more room, and is shortened to fit.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::dropdown_widget::*;
+/// # fn main() {
+///    // There is room below the field.
+///    assert_eq!(get_dropdown_list_placement(20, 28, 100, 400), (48, 100));
+///
+///    // There is more room above the field than below it.
+///    assert_eq!(get_dropdown_list_placement(330, 28, 100, 400), (230, 100));
+///
+///    // Neither side has room for the whole list.
+///    assert_eq!(get_dropdown_list_placement(50, 28, 300, 200), (78, 122));
+/// # }
+/// ```
+pub fn get_dropdown_list_placement(
+    field_y: i32,
+    field_h: i32,
+    list_h: i32,
+    window_h: i32,
+) -> (i32, i32) {
+    let below = (window_h - field_y - field_h).max(0);
+    let above = field_y.max(0);
+
+    if list_h <= below || below >= above {
+        (field_y + field_h, list_h.min(below))
+    } else {
+        let list_h = list_h.min(above);
+
+        (field_y - list_h, list_h)
+    }
+}
+
+/// Returns the index of the first item after `current` whose text starts with `letter`,
+/// ignoring case, wrapping around to the first item.  If no item is current, the search starts
+/// at the first item.  Returns `None` if no item starts with `letter`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::dropdown_widget::*;
+/// # fn main() {
+///    let items: Vec<String> = vec!["Apple", "banana", "Blueberry", "Cherry"]
+///        .into_iter()
+///        .map(String::from)
+///        .collect();
+///
+///    assert_eq!(find_item_starting_with(&items, 'b', None), Some(1));
+///    assert_eq!(find_item_starting_with(&items, 'B', Some(1)), Some(2));
+///    assert_eq!(find_item_starting_with(&items, 'b', Some(2)), Some(1));
+///    assert_eq!(find_item_starting_with(&items, 'z', Some(0)), None);
+/// # }
+/// ```
+pub fn find_item_starting_with(
+    items: &[String],
+    letter: char,
+    current: Option<usize>,
+) -> Option<usize> {
+    let start = current.map(|current| current + 1).unwrap_or(0);
+    let letter: Vec<char> = letter.to_lowercase().collect();
+
+    (0..items.len())
+        .map(|offset| (start + offset) % items.len())
+        .find(|index| {
+            items[*index]
+                .chars()
+                .next()
+                .map(|first| first.to_lowercase().eq(letter.iter().copied()))
+                .unwrap_or(false)
+        })
+}
+
+/// The state that a `DropdownWidget` shares with its list while the list is open.
+#[derive(Default)]
+struct DropdownState {
+    /// The item that was chosen from the list, which the `DropdownWidget` selects on its next
+    /// update.
+    chosen: Option<usize>,
+
+    /// The ID of the open list, or `WidgetId::INVALID` until it has been added to the
+    /// `WidgetStore`.
+    open_list: Option<WidgetId>,
+}
+
+type SharedDropdownState = Rc<RefCell<DropdownState>>;
+
+/// Queues `callback` to be called with the `WidgetStore` once the current update is over.
+fn queue_callback<F>(deferred: &mut Vec<DeferredCallback>, callback: F)
+where
+    F: FnMut(&mut WidgetStore) + 'static,
+{
+    deferred.push(Rc::new(RefCell::new(Box::new(callback))));
+}
+
+/// This is the `DropdownListWidget`, the floating list of items that a `DropdownWidget` opens
+/// as a popup.  It draws the items with a `ListWidget`, and chooses the item that is clicked,
+/// or that is current when `Enter` or `Space` is pressed, closing itself afterward.  The
+/// arrow keys move through the items, typing a letter jumps to the next item that starts with
+/// it, and `Escape` closes the list without choosing an item.
+///
+/// Lists are created and removed by their `DropdownWidget`, so they have no public
+/// constructor.
+pub struct DropdownListWidget {
+    config: Configurable,
+    list: Box<ListWidget>,
+    dropdown_id: WidgetId,
+    widget_id: WidgetId,
+    state: SharedDropdownState,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `DropdownListWidget`.
+impl DropdownListWidget {
+    fn new(
+        font: SharedGlyphs,
+        items: Vec<String>,
+        selected: Option<usize>,
+        dropdown_id: WidgetId,
+        state: SharedDropdownState,
+    ) -> Self {
+        let mut list = ListWidget::from_font(font, items);
+
+        list.set_selected_row(selected);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            list: Box::new(list),
+            dropdown_id,
+            widget_id: WidgetId::INVALID,
+            state,
+            deferred: Vec::new(),
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget
+    }
+
+    /// Chooses `row` for the `DropdownWidget`, and closes the list.
+    fn choose(&mut self, row: usize) {
+        self.state.borrow_mut().chosen = Some(row);
+        self.close();
+    }
+
+    /// Closes the list.
+    fn close(&mut self) {
+        let widget_id = self.widget_id;
+
+        queue_callback(&mut self.deferred, move |widget_store| {
+            widget_store.close_popup(widget_id)
+        });
+    }
+
+    /// Passes `event` on to the list, invalidating this widget if it needs to be redrawn.
+    fn forward_event(&mut self, event: CallbackEvent) {
+        self.list.handle_event(event);
+
+        if self.list.is_invalidated() {
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `DropdownListWidget` object with the `Widget` traits implemented.
+impl Widget for DropdownListWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::List
+    }
+
+    /// Sets the `Size` for this widget and the list, given width and height, and scrolls the
+    /// selected item into view.  Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.list.set_size(w, h);
+
+        if let Some(row) = self.list.get_selected_row() {
+            self.list.ensure_row_visible(row);
+        }
+
+        self.invalidate();
+    }
+
+    /// Scrolls the list in response to the mouse wheel.  All other events are handled by
+    /// `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { .. } => {
+                self.list.handle_input_event(event);
+
+                if self.list.is_invalidated() {
+                    self.invalidate();
+                }
+
+                (EventResult::Consumed, None)
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        match event {
+            CallbackEvent::MouseMoved { widget_id, .. } => {
+                self.widget_id = widget_id;
+                self.forward_event(event);
+            }
+
+            CallbackEvent::GotFocus { widget_id } => self.widget_id = widget_id,
+
+            CallbackEvent::MouseClicked {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                ref point,
+                ..
+            } => {
+                self.widget_id = widget_id;
+
+                if let Some(row) = self.list.get_row_at(point.y) {
+                    self.choose(row);
+                }
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key,
+                state: ButtonState::Press,
+                ..
+            } => {
+                self.widget_id = widget_id;
+
+                match key {
+                    Key::Return | Key::Space => match self.list.get_selected_row() {
+                        Some(row) => self.choose(row),
+                        None => self.close(),
+                    },
+                    Key::Escape => self.close(),
+                    _ => self.forward_event(event),
+                }
+            }
+
+            CallbackEvent::TextInput { ref text, .. } => {
+                let current = self.list.get_selected_row();
+                let found = text
+                    .chars()
+                    .find(|c| c.is_alphanumeric())
+                    .and_then(|letter| {
+                        find_item_starting_with(self.list.get_items(), letter, current)
+                    });
+
+                if found.is_some() {
+                    self.list.set_selected_row(found);
+                    self.invalidate();
+                }
+            }
+
+            CallbackEvent::PopupClosed { widget_id } => {
+                let dropdown_id = self.dropdown_id;
+
+                self.state.borrow_mut().open_list = None;
+                queue_callback(&mut self.deferred, move |widget_store| {
+                    widget_store.remove_widget(widget_id);
+                    widget_store.set_focused_widget(dropdown_id);
+                });
+            }
+
+            _ => self.forward_event(event),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the list, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.list.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the list, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.list.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the list, and then a border around it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let size = self.get_size();
+
+        self.list.draw(c, g, clip);
+        Rectangle::new_border(apply_opacity(get_current_theme().border_color), 0.5).draw(
+            [0.5, 0.5, f64::from(size.w) - 1.0, f64::from(size.h) - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+/// This is the `DropdownWidget`, a field that shows the selected item of a list of items, or a
+/// placeholder while no item is selected.  Clicking the field, or pressing `Enter`, `Space`, or
+/// `Alt`+`Down` while it is focused, opens a `DropdownListWidget` of the items below the field,
+/// or above it if there is more room there.  The list is a popup, so it is drawn above every
+/// other `Widget`, keeps the keyboard focus while it is open, and is closed by choosing an item,
+/// or by pressing the mouse outside of it.
+///
+/// While the list is closed, the `Up` and `Down` keys select the previous and next items, and
+/// typing a letter selects the next item that starts with it.  The `on_selection_changed`
+/// callback is called with the index and the text of the item every time the selection
+/// changes.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::dropdown_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Dropdown", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let items = vec!["Small".to_string(), "Medium".to_string(), "Large".to_string()];
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut dropdown = DropdownWidget::new(pushrod.get_factory(), font_name, items);
+///
+///    dropdown.set_origin(20, 20);
+///    dropdown.set_size(160, 28);
+///    dropdown.set_placeholder("Pick a size");
+///    dropdown.on_selection_changed(Box::new(|index, item| {
+///        eprintln!("Selected {}: {}", index, item)
+///    }));
+///    pushrod.add_widget("SizeDropdown", Box::new(dropdown));
+/// # }
+/// ```
+pub struct DropdownWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    text_widget: Box<TextWidget>,
+    items: Vec<String>,
+    selected: Option<usize>,
+    placeholder: String,
+    visible_rows: usize,
+    hovered: bool,
+    focused: bool,
+    shown_open: bool,
+    widget_id: WidgetId,
+    state: SharedDropdownState,
+    deferred: Vec<DeferredCallback>,
+    on_selection_changed: Option<DropdownCallback>,
+}
+
+/// Implementation of the constructor for the `DropdownWidget`.
+impl DropdownWidget {
+    /// Creates a new dropdown of `items`, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  No item is selected.  Panics if the
+    /// font cannot be loaded; use `font_cache::load_font` and `from_font` to handle the error
+    /// instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String, items: Vec<String>) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, items)
+    }
+
+    /// Creates a new dropdown of `items` that draws its text, and the text of its list, in a
+    /// font from the font cache.  The dropdown can be focused, so that it can be used from the
+    /// keyboard.
+    pub fn from_font(font: SharedGlyphs, items: Vec<String>) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font.clone(), String::new(), font_size, TextJustify::Left);
+
+        text_widget.set_padding(make_insets(0, 0, 0, DROPDOWN_TEXT_PADDING));
+        text_widget.set_ellipsize(true);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            font,
+            text_widget: Box::new(text_widget),
+            items,
+            selected: None,
+            placeholder: String::new(),
+            visible_rows: DEFAULT_DROPDOWN_VISIBLE_ROWS,
+            hovered: false,
+            focused: false,
+            shown_open: false,
+            widget_id: WidgetId::INVALID,
+            state: Rc::new(RefCell::new(DropdownState::default())),
+            deferred: Vec::new(),
+            on_selection_changed: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget.set_cursor(CursorStyle::Hand);
+        widget
+    }
+
+    /// Sets the closure that is called with the index and the text of the selected item every
+    /// time the selection changes.
+    pub fn on_selection_changed(&mut self, callback: DropdownCallback) {
+        self.on_selection_changed = Some(callback);
+    }
+
+    /// Replaces all of the items, closing the list if it is open.  The selected item stays
+    /// selected if it is one of the new items, and otherwise, the selection is cleared.  The
+    /// `on_selection_changed` callback is not called.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::dropdown_widget::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # fn replace_items(font: SharedGlyphs) {
+    ///    let items = vec!["Red".to_string(), "Green".to_string(), "Blue".to_string()];
+    ///    let mut dropdown = DropdownWidget::from_font(font, items);
+    ///
+    ///    dropdown.set_selected(Some(2));
+    ///    dropdown.set_items(vec!["Blue".to_string(), "Yellow".to_string()]);
+    ///    assert_eq!(dropdown.get_selected(), Some(0));
+    ///
+    ///    dropdown.set_items(vec!["Cyan".to_string()]);
+    ///    assert_eq!(dropdown.get_selected(), None);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn set_items(&mut self, items: Vec<String>) {
+        let selected_item = self.get_selected_item().map(String::from);
+
+        self.selected = selected_item
+            .and_then(|selected_item| items.iter().position(|item| *item == selected_item));
+        self.items = items;
+        self.close_list();
+        self.invalidate();
+    }
+
+    /// Retrieves the items.
+    pub fn get_items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Selects the item at `index`, or clears the selection if `index` is `None`.  An index
+    /// past the end of the items clears the selection.  If an item was selected, and it is not
+    /// the item that was selected before, the `on_selection_changed` callback is called.
+    /// Invalidates the widget if the selection changed.
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        let index = index.filter(|index| *index < self.items.len());
+
+        if index == self.selected {
+            return;
+        }
+
+        self.selected = index;
+
+        if let Some(index) = index {
+            if let Some(callback) = &mut self.on_selection_changed {
+                callback(index, &self.items[index]);
+            }
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the index of the selected item, or `None` if no item is selected.
+    pub fn get_selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Retrieves the text of the selected item, or `None` if no item is selected.
+    pub fn get_selected_item(&self) -> Option<&str> {
+        self.selected.map(|index| self.items[index].as_str())
+    }
+
+    /// Sets the text that is shown while no item is selected.  Invalidates the widget
+    /// afterward.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+        self.invalidate();
+    }
+
+    /// Retrieves the text that is shown while no item is selected.  Defaults to no text.
+    pub fn get_placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Sets the number of items that the list shows before it scrolls.
+    pub fn set_visible_rows(&mut self, visible_rows: usize) {
+        self.visible_rows = visible_rows.max(1);
+    }
+
+    /// Retrieves the number of items that the list shows before it scrolls.  Defaults to
+    /// `DEFAULT_DROPDOWN_VISIBLE_ROWS`.
+    pub fn get_visible_rows(&self) -> usize {
+        self.visible_rows
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Indicates whether the list of items is open.
+    pub fn is_open(&self) -> bool {
+        self.state.borrow().open_list.is_some()
+    }
+
+    /// Opens the list of items as a popup, with the keyboard focus, once the current update is
+    /// over.  Does nothing if there are no items, or if the list is already open.
+    fn open_list(&mut self) {
+        if self.items.is_empty() || self.is_open() || self.widget_id <= WidgetId::ROOT {
+            return;
+        }
+
+        let row_h = DEFAULT_LIST_ROW_HEIGHT;
+        let list_h = row_h * self.items.len().min(self.visible_rows) as i32 + 2;
+        let size = self.get_size();
+        let dropdown_id = self.widget_id;
+        let state = self.state.clone();
+        let mut list = Some(DropdownListWidget::new(
+            self.font.clone(),
+            self.items.clone(),
+            self.selected,
+            dropdown_id,
+            state.clone(),
+        ));
+
+        state.borrow_mut().open_list = Some(WidgetId::INVALID);
+        queue_callback(&mut self.deferred, move |widget_store| {
+            let mut list = match list.take() {
+                Some(list) => list,
+                None => return,
+            };
+            let origin = widget_store.get_absolute_origin(dropdown_id);
+            let window_h = widget_store
+                .get_widget_for_id(WidgetId::ROOT)
+                .borrow_mut()
+                .get_size()
+                .h;
+            let (list_y, list_h) = get_dropdown_list_placement(origin.y, size.h, list_h, window_h);
+            let number = NEXT_DROPDOWN_LIST_NUMBER.fetch_add(1, Ordering::Relaxed);
+
+            list.set_origin(origin.x, list_y);
+            list.set_size(size.w, list_h.max(row_h));
+
+            let list_id =
+                widget_store.add_widget(&format!("_PushrodDropdownList{}", number), Box::new(list));
+
+            state.borrow_mut().open_list = Some(list_id);
+            widget_store.open_popup(list_id);
+            widget_store.set_focused_widget(list_id);
+        });
+        self.invalidate();
+    }
+
+    /// Closes the list of items, if it is open.
+    fn close_list(&mut self) {
+        if let Some(list_id) = self.state.borrow().open_list {
+            if list_id > WidgetId::ROOT {
+                queue_callback(&mut self.deferred, move |widget_store| {
+                    widget_store.close_popup(list_id)
+                });
+            }
+        }
+    }
+
+    /// Moves the selection by `rows`, selecting the first item if none was selected.
+    fn move_selection(&mut self, rows: i32) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let index = match self.selected {
+            Some(current) => (current as i32 + rows).clamp(0, self.items.len() as i32 - 1),
+            None => 0,
+        } as usize;
+
+        self.set_selected(Some(index));
+    }
+}
+
+/// Implementation of the `DropdownWidget` object with the `Widget` traits implemented.  The
+/// text of the field is drawn with a `TextWidget`.
+impl Widget for DropdownWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::ComboBox
+    }
+
+    /// The value is the selected item.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        self.get_selected_item().map(String::from)
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget
+            .set_size((w - DROPDOWN_ARROW_SPACE).max(0), h);
+        self.invalidate();
+    }
+
+    /// Enables or disables this widget.  A disabled dropdown is drawn in the disabled color of
+    /// the current `Theme`, and closes its list.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+            self.close_list();
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Selects the item that was chosen from the list, and redraws the field when the list
+    /// opens or closes.
+    fn update(&mut self, _delta_ms: u64) {
+        let chosen = self.state.borrow_mut().chosen.take();
+
+        if chosen.is_some() {
+            self.set_selected(chosen);
+        }
+
+        let open = self.is_open();
+
+        if open != self.shown_open {
+            self.shown_open = open;
+            self.invalidate();
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseEntered { widget_id }
+            | CallbackEvent::MouseMoved { widget_id, .. } => {
+                self.widget_id = widget_id;
+
+                if !self.hovered {
+                    self.hovered = true;
+                    self.invalidate();
+                }
+            }
+
+            CallbackEvent::MouseExited { .. } => {
+                self.hovered = false;
+                self.invalidate();
+            }
+
+            CallbackEvent::GotFocus { widget_id } => {
+                self.widget_id = widget_id;
+                self.focused = true;
+                self.invalidate();
+            }
+
+            CallbackEvent::LostFocus { .. } => {
+                self.focused = false;
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonDown {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } => {
+                self.widget_id = widget_id;
+                self.open_list();
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key,
+                state: ButtonState::Press,
+                modifiers,
+            } => {
+                self.widget_id = widget_id;
+
+                match key {
+                    Key::Return | Key::Space => self.open_list(),
+                    Key::Down if modifiers.alt => self.open_list(),
+                    Key::Up => self.move_selection(-1),
+                    Key::Down => self.move_selection(1),
+                    _ => (),
+                }
+            }
+
+            CallbackEvent::TextInput { ref text, .. } => {
+                if let Some(letter) = text.chars().find(|c| c.is_alphanumeric()) {
+                    let found = find_item_starting_with(&self.items, letter, self.selected);
+
+                    if found.is_some() {
+                        self.set_selected(found);
+                    }
+                }
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, which is blended toward the accent color of the current `Theme`
+    /// while the mouse is over the field, the selected item or the placeholder, the arrow, and
+    /// the border, which is drawn in the accent color while the field is focused or open.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let disabled = self.is_disabled();
+        let open = self.is_open();
+        let mut background = self.get_color();
+
+        if (self.hovered || open) && !disabled {
+            for (channel, accent) in background.iter_mut().zip(theme.accent_color.iter()).take(3) {
+                *channel += (accent - *channel) * DROPDOWN_HOVER_AMOUNT;
+            }
+        }
+
+        Rectangle::new(apply_opacity(background)).draw([0.0, 0.0, w, h], clip, c.transform, g);
+
+        let (text, text_color) = match self.get_selected_item() {
+            Some(item) if !disabled => (item.to_string(), self.get_text_color()),
+            Some(item) => (item.to_string(), theme.disabled_color),
+            None => (self.placeholder.clone(), theme.disabled_color),
+        };
+
+        self.text_widget.set_text(text);
+        self.text_widget.set_text_color(text_color);
+        self.text_widget.draw(c, g, clip);
+
+        let arrow_color = if disabled {
+            theme.disabled_color
+        } else {
+            self.get_text_color()
+        };
+        let (arrow_x, arrow_y) = (w - f64::from(DROPDOWN_ARROW_SPACE) / 2.0, h / 2.0);
+
+        Polygon::new(apply_opacity(arrow_color)).draw(
+            &[
+                [arrow_x - 4.0, arrow_y - 2.0],
+                [arrow_x + 4.0, arrow_y - 2.0],
+                [arrow_x, arrow_y + 2.0],
+            ],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let border_color = if (self.focused || open) && !disabled {
+            theme.accent_color
+        } else {
+            theme.border_color
+        };
+
+        stroke_border(
+            apply_opacity(border_color),
+            [0.0, 0.0, w, h],
+            &make_uniform_insets(1),
+            BorderStyle::Solid,
+            clip,
+            c.transform,
+            g,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,272 @@
+// Font Cache
+// Loads fonts once, and shares them between all of the widgets that draw text in them.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use piston_window::*;
+
+use crate::core::error::PushrodError;
+use crate::core::point::Size;
+use crate::widget::text_widget::{measure_text, DEFAULT_LINE_SPACING};
+
+/// The name of the font that is bundled with Pushrod, and used when no other font is chosen.  It
+/// is loaded from the `assets` directory if it is there, or else from the copy that is built into
+/// the library, so it can always be loaded, wherever the application is run from.
+pub const DEFAULT_FONT_NAME: &str = "OpenSans-Regular.ttf";
+
+/// The built-in copy of the default font.
+const DEFAULT_FONT_BYTES: &[u8] = include_bytes!("../../assets/OpenSans-Regular.ttf");
+
+/// A font loaded into the font cache, along with the glyphs that have been rasterized from it.
+/// Every `Widget` that draws text in the same font shares the same `SharedGlyphs`, so each glyph
+/// is only rasterized once for each font size.
+pub type SharedGlyphs = Rc<RefCell<Glyphs>>;
+
+thread_local! {
+    static FONT_CACHE: RefCell<HashMap<String, SharedGlyphs>> = RefCell::new(HashMap::new());
+}
+
+/// Retrieves the font `font_name` from the font cache, loading it if it has not been loaded yet.
+/// `font_name` is either the path to a TTF file, or the filename of a font in the `assets`
+/// directory.  Returns a `PushrodError::ResourceLoading` if the font cannot be found or loaded.
+/// The default font, `DEFAULT_FONT_NAME`, is loaded from the copy built into the library if it
+/// cannot be found.
+pub fn load_font(factory: &mut GfxFactory, font_name: &str) -> Result<SharedGlyphs, PushrodError> {
+    if let Some(glyphs) = get_font(font_name) {
+        return Ok(glyphs);
+    }
+
+    let glyphs = find_font(font_name).and_then(|path| {
+        Glyphs::new(&path, factory.clone(), TextureSettings::new()).map_err(|error| {
+            PushrodError::ResourceLoading {
+                name: path.display().to_string(),
+                reason: error.to_string(),
+            }
+        })
+    });
+
+    match glyphs {
+        Ok(glyphs) => Ok(add_font(font_name, glyphs)),
+        Err(_) if font_name == DEFAULT_FONT_NAME => {
+            load_font_bytes(factory, font_name, DEFAULT_FONT_BYTES)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Adds the TTF font data in `bytes` to the font cache under the name `font_name`, so that it can
+/// be drawn by `Widget`s that are given the same name, such as a font embedded in the
+/// application with `include_bytes!`.  If a font was already loaded under the name, it is
+/// returned instead.  Returns a `PushrodError::ResourceLoading` if the data is not a valid font.
+pub fn load_font_bytes(
+    factory: &mut GfxFactory,
+    font_name: &str,
+    bytes: &'static [u8],
+) -> Result<SharedGlyphs, PushrodError> {
+    if let Some(glyphs) = get_font(font_name) {
+        return Ok(glyphs);
+    }
+
+    let glyphs =
+        Glyphs::from_bytes(bytes, factory.clone(), TextureSettings::new()).map_err(|_| {
+            PushrodError::ResourceLoading {
+                name: font_name.to_string(),
+                reason: String::from("invalid font data"),
+            }
+        })?;
+
+    Ok(add_font(font_name, glyphs))
+}
+
+/// Retrieves the font `font_name` if it has already been loaded into the font cache.
+pub fn get_font(font_name: &str) -> Option<SharedGlyphs> {
+    FONT_CACHE.with(|cache| cache.borrow().get(font_name).cloned())
+}
+
+fn add_font(font_name: &str, glyphs: Glyphs) -> SharedGlyphs {
+    let glyphs = Rc::new(RefCell::new(glyphs));
+
+    FONT_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .insert(font_name.to_string(), glyphs.clone())
+    });
+
+    glyphs
+}
+
+/// Resolves `font_name` to the path of a TTF file, looking in the `assets` directory if it is
+/// not the path of an existing file.
+fn find_font(font_name: &str) -> Result<PathBuf, PushrodError> {
+    let path = Path::new(font_name);
+
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let assets = find_folder::Search::ParentsThenKids(3, 3)
+        .for_folder("assets")
+        .map_err(|_| PushrodError::ResourceLoading {
+            name: font_name.to_string(),
+            reason: String::from("no assets directory"),
+        })?;
+
+    Ok(assets.join(font_name))
+}
+
+/// Identifies a font that has been loaded by a `FontManager`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontId(usize);
+
+/// This is the `FontManager`, which is owned by `Pushrod`, and keeps track of the fonts that the
+/// application has loaded, by their `FontId`.  Fonts are loaded through the font cache, so a
+/// font that is loaded by the `FontManager` is shared with every `Widget` that is created with
+/// the same font name, and the glyphs of each font are only rasterized once for each font size.
+/// Text can be measured with `measure_text`, so that `Widget`s and layouts can be sized to fit
+/// their text before anything is drawn.
+///
+/// Example:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::font_cache::*;
+/// # use pushrod::widget::text_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Pushrod", [640, 480])
+///        .build()
+///        .unwrap_or_else(|error| panic!("Failed to build PistonWindow: {}", error));
+///    let mut pushrod = Pushrod::new(window);
+///    let fonts = pushrod.get_font_manager();
+///    let font = match fonts.load_font("fonts/Heading.ttf") {
+///        Ok(font) => font,
+///        Err(error) => {
+///            eprintln!("{}, using the default font", error);
+///            fonts.default_font()
+///        }
+///    };
+///    let size = fonts.measure_text(font, 24, "Welcome!");
+///    let glyphs = fonts.get_font(font).unwrap();
+///    let mut title = TextWidget::from_font(glyphs, "Welcome!".to_string(), 24, TextJustify::Left);
+///
+///    title.set_size(size.w, size.h);
+/// # }
+/// ```
+pub struct FontManager {
+    factory: GfxFactory,
+    fonts: Vec<SharedGlyphs>,
+    font_ids: HashMap<String, FontId>,
+    default_font: Option<FontId>,
+}
+
+/// Implementation of the `FontManager`.
+impl FontManager {
+    /// Creates a new `FontManager`, which loads fonts with `factory`.
+    pub fn new(factory: GfxFactory) -> Self {
+        Self {
+            factory,
+            fonts: Vec::new(),
+            font_ids: HashMap::new(),
+            default_font: None,
+        }
+    }
+
+    /// Loads the font `font_name`, which is either the path to a TTF file, or the filename of a
+    /// font in the `assets` directory, and returns its `FontId`.  Loading the same font again
+    /// returns the same `FontId`.  Returns an error if the font cannot be found or loaded, so that
+    /// the application can fall back to another font, such as `default_font`.
+    pub fn load_font(&mut self, font_name: &str) -> Result<FontId, PushrodError> {
+        if let Some(font_id) = self.get_font_id(font_name) {
+            return Ok(font_id);
+        }
+
+        let glyphs = load_font(&mut self.factory, font_name)?;
+
+        Ok(self.add_font(font_name, glyphs))
+    }
+
+    /// Loads the TTF font data in `bytes` under the name `font_name`, such as a font embedded in
+    /// the application with `include_bytes!`, and returns its `FontId`.  Returns an error if the
+    /// data is not a valid font.
+    pub fn load_font_bytes(
+        &mut self,
+        font_name: &str,
+        bytes: &'static [u8],
+    ) -> Result<FontId, PushrodError> {
+        if let Some(font_id) = self.get_font_id(font_name) {
+            return Ok(font_id);
+        }
+
+        let glyphs = load_font_bytes(&mut self.factory, font_name, bytes)?;
+
+        Ok(self.add_font(font_name, glyphs))
+    }
+
+    /// Returns the `FontId` of the default font, loading it the first time.  This is the font
+    /// set with `set_default_font`, or else the font that is bundled with Pushrod,
+    /// `DEFAULT_FONT_NAME`, which can always be loaded.
+    pub fn default_font(&mut self) -> FontId {
+        if let Some(font_id) = self.default_font {
+            return font_id;
+        }
+
+        let font_id = self
+            .load_font(DEFAULT_FONT_NAME)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        self.default_font = Some(font_id);
+        font_id
+    }
+
+    /// Sets the font that `default_font` returns.
+    pub fn set_default_font(&mut self, font_id: FontId) {
+        self.default_font = Some(font_id);
+    }
+
+    /// Retrieves the `FontId` of the font `font_name`, if it has been loaded by this manager.
+    pub fn get_font_id(&self, font_name: &str) -> Option<FontId> {
+        self.font_ids.get(font_name).cloned()
+    }
+
+    /// Retrieves the glyphs of a font, to hand to the `from_font` constructor of a `Widget` that
+    /// draws text, or `None` if the `FontId` did not come from this manager.
+    pub fn get_font(&self, font_id: FontId) -> Option<SharedGlyphs> {
+        self.fonts.get(font_id.0).cloned()
+    }
+
+    /// Measures `text` when drawn in the font `font_id` at `font_size`, in the same way as a
+    /// `TextWidget` with the default line spacing: the width of the widest line, and the height
+    /// of all of the lines.  Returns an empty size if the font has not been loaded by this
+    /// manager.
+    pub fn measure_text(&mut self, font_id: FontId, font_size: u32, text: &str) -> Size {
+        let line_height = (f64::from(font_size) * DEFAULT_LINE_SPACING).round() as i32;
+
+        match self.fonts.get(font_id.0) {
+            Some(glyphs) => measure_text(&mut glyphs.borrow_mut(), font_size, line_height, text),
+            None => Size { w: 0, h: 0 },
+        }
+    }
+
+    fn add_font(&mut self, font_name: &str, glyphs: SharedGlyphs) -> FontId {
+        let font_id = FontId(self.fonts.len());
+
+        self.fonts.push(glyphs);
+        self.font_ids.insert(font_name.to_string(), font_id);
+        font_id
+    }
+}
@@ -0,0 +1,287 @@
+// Gradient Fills
+// Linear gradients that fill the bounds of a widget, blending between two or more colors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::draw_target::*;
+use crate::widget::widget::apply_opacity;
+
+/// The largest number of bands that a gradient is drawn with.  This is as many shades as there
+/// are in an 8-bit color channel, so more bands could not be told apart.
+const MAX_GRADIENT_BANDS: usize = 256;
+
+/// This `enum` specifies the direction in which a gradient blends from its first color to its
+/// last.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientDirection {
+    /// From the left edge to the right edge.
+    Horizontal,
+
+    /// From the top edge to the bottom edge.
+    Vertical,
+
+    /// Along an angle, in degrees clockwise from left to right, so that `0.0` is the same as
+    /// `Horizontal`, `90.0` is the same as `Vertical`, and `45.0` blends from the upper left-hand
+    /// corner to the lower right-hand corner.
+    Angle(f64),
+}
+
+/// Implementation of the `GradientDirection`.
+impl GradientDirection {
+    /// Returns the unit vector, in window coordinates, along which the gradient blends.
+    pub fn get_vector(self) -> [f64; 2] {
+        match self {
+            GradientDirection::Horizontal => [1.0, 0.0],
+            GradientDirection::Vertical => [0.0, 1.0],
+            GradientDirection::Angle(degrees) => {
+                let radians = degrees.to_radians();
+
+                [radians.cos(), radians.sin()]
+            }
+        }
+    }
+}
+
+/// A color at a position along a gradient, between 0.0 (the start) and 1.0 (the end).
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientStop {
+    pub position: f64,
+    pub color: types::Color,
+}
+
+/// A linear gradient, which blends between its stops along its direction.  The stops are kept in
+/// order of their position.  Before the first stop, the gradient has the color of the first stop,
+/// and after the last stop, the color of the last.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::gradient::*;
+/// # fn main() {
+///    let gradient = Gradient::new(
+///        GradientDirection::Vertical,
+///        [1.0, 0.0, 0.0, 1.0],
+///        [0.0, 0.0, 1.0, 1.0],
+///    );
+///
+///    assert_eq!(gradient.get_color_at(0.0), [1.0, 0.0, 0.0, 1.0]);
+///    assert_eq!(gradient.get_color_at(0.5), [0.5, 0.0, 0.5, 1.0]);
+///    assert_eq!(gradient.get_color_at(1.0), [0.0, 0.0, 1.0, 1.0]);
+///    assert_eq!(gradient.get_color_at(2.0), [0.0, 0.0, 1.0, 1.0]);
+///
+///    let with_middle = gradient.with_stop(0.5, [1.0; 4]);
+///
+///    assert_eq!(with_middle.get_color_at(0.5), [1.0; 4]);
+///    assert_eq!(with_middle.get_color_at(0.75), [0.5, 0.5, 1.0, 1.0]);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub direction: GradientDirection,
+    pub stops: Vec<GradientStop>,
+}
+
+/// Implementation of the `Gradient`.
+impl Gradient {
+    /// Creates a new two-stop gradient, blending from `start` to `end` along `direction`.
+    pub fn new(direction: GradientDirection, start: types::Color, end: types::Color) -> Self {
+        Self {
+            direction,
+            stops: vec![
+                GradientStop {
+                    position: 0.0,
+                    color: start,
+                },
+                GradientStop {
+                    position: 1.0,
+                    color: end,
+                },
+            ],
+        }
+    }
+
+    /// Returns this gradient with another stop of `color` at `position`, keeping the stops in
+    /// order.
+    pub fn with_stop(mut self, position: f64, color: types::Color) -> Self {
+        let index = self
+            .stops
+            .iter()
+            .position(|stop| stop.position > position)
+            .unwrap_or(self.stops.len());
+
+        self.stops.insert(index, GradientStop { position, color });
+        self
+    }
+
+    /// Returns the color of the gradient at `position`, between 0.0 and 1.0, blending between
+    /// the stops on either side of it.  A gradient without stops is transparent.
+    pub fn get_color_at(&self, position: f64) -> types::Color {
+        let after = match self.stops.iter().position(|stop| stop.position > position) {
+            Some(0) => return self.stops[0].color,
+            Some(after) => after,
+            None => return self.stops.last().map_or([0.0; 4], |stop| stop.color),
+        };
+        let (from, to) = (&self.stops[after - 1], &self.stops[after]);
+        let amount = ((position - from.position) / (to.position - from.position)) as f32;
+        let mut color = from.color;
+
+        for (channel, target) in color.iter_mut().zip(to.color.iter()) {
+            *channel += (target - *channel) * amount;
+        }
+
+        color
+    }
+
+    /// Returns the bands that the gradient is drawn with over an area of `w` by `h` pixels, each
+    /// with its color and the corners of its polygon, relative to the upper left-hand corner of
+    /// the area.  The bands are the slices of the area across the direction of the gradient,
+    /// about one pixel wide each, up to a limit of 256, so that the gradient looks smooth at any
+    /// size.  An area without a width or height has no bands.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::gradient::*;
+    /// # fn main() {
+    ///    let horizontal = Gradient::new(GradientDirection::Horizontal, [0.0; 4], [1.0; 4]);
+    ///    let bands = horizontal.get_bands(100.0, 20.0);
+    ///
+    ///    assert_eq!(bands.len(), 100);
+    ///    assert_eq!(bands[0].0, [0.0; 4]);
+    ///    assert_eq!(bands[99].0, [1.0; 4]);
+    ///    assert_eq!(bands[0].1, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 20.0], [0.0, 20.0]]);
+    ///
+    ///    // Bands are never less than a pixel wide, and there are never more than 256 of them.
+    ///    assert_eq!(horizontal.get_bands(1000.0, 20.0).len(), 256);
+    ///    assert_eq!(horizontal.get_bands(0.5, 20.0).len(), 1);
+    ///    assert!(horizontal.get_bands(0.0, 20.0).is_empty());
+    ///
+    ///    // Diagonal bands start and end in the corners of the area, and the band in the middle
+    ///    // passes through the other two corners.
+    ///    let diagonal = Gradient::new(GradientDirection::Angle(45.0), [0.0; 4], [1.0; 4]);
+    ///    let bands = diagonal.get_bands(10.0, 10.0);
+    ///
+    ///    assert_eq!(bands.len(), 15);
+    ///    assert_eq!(bands[0].1.len(), 3);
+    ///    assert_eq!(bands[7].1.len(), 6);
+    ///    assert_eq!(bands[14].1.len(), 3);
+    /// # }
+    /// ```
+    pub fn get_bands(&self, w: f64, h: f64) -> Vec<(types::Color, Vec<[f64; 2]>)> {
+        if w <= 0.0 || h <= 0.0 {
+            return Vec::new();
+        }
+
+        self.get_polygon_bands(&[[0.0, 0.0], [w, 0.0], [w, h], [0.0, h]])
+    }
+
+    /// Returns the bands that the gradient is drawn with over the convex `polygon`, such as a
+    /// rectangle with rounded corners, in the same way as `get_bands`.  The gradient runs from
+    /// one side of the polygon to the other, and each band is the part of the polygon that it
+    /// crosses.
+    pub fn get_polygon_bands(&self, polygon: &[[f64; 2]]) -> Vec<(types::Color, Vec<[f64; 2]>)> {
+        if polygon.len() < 3 || self.stops.is_empty() {
+            return Vec::new();
+        }
+
+        let [dx, dy] = self.direction.get_vector();
+        let project = |point: &[f64; 2]| point[0] * dx + point[1] * dy;
+        let start = polygon.iter().map(project).fold(f64::INFINITY, f64::min);
+        let end = polygon
+            .iter()
+            .map(project)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let length = end - start;
+        let count = (length.ceil() as usize).clamp(1, MAX_GRADIENT_BANDS);
+        let mut bands = Vec::with_capacity(count);
+
+        for band in 0..count {
+            let from = start + length * band as f64 / count as f64;
+            let to = start + length * (band + 1) as f64 / count as f64;
+            let band_polygon = clip_polygon(polygon, |point| project(point) - from);
+            let band_polygon = clip_polygon(&band_polygon, |point| to - project(point));
+            let position = if count > 1 {
+                band as f64 / (count - 1) as f64
+            } else {
+                0.0
+            };
+
+            if band_polygon.len() >= 3 {
+                bands.push((self.get_color_at(position), band_polygon));
+            }
+        }
+
+        bands
+    }
+
+    /// Draws the gradient over `rect` (X, Y, width, height), using the `DrawState` to clip it,
+    /// and the transform of the `Context` to place it.  The colors of the gradient are drawn with
+    /// the current draw opacity; see `apply_opacity`.
+    pub fn draw(
+        &self,
+        rect: [f64; 4],
+        clip: &DrawState,
+        transform: math::Matrix2d,
+        g: &mut DrawTarget,
+    ) {
+        let transform = transform.trans(rect[0], rect[1]);
+
+        for (color, polygon) in self.get_bands(rect[2], rect[3]) {
+            Polygon::new(apply_opacity(color)).draw(&polygon, clip, transform, g);
+        }
+    }
+
+    /// Draws the gradient over the convex `polygon`, in the same way as `draw`.
+    pub fn draw_polygon(
+        &self,
+        polygon: &[[f64; 2]],
+        clip: &DrawState,
+        transform: math::Matrix2d,
+        g: &mut DrawTarget,
+    ) {
+        for (color, band) in self.get_polygon_bands(polygon) {
+            Polygon::new(apply_opacity(color)).draw(&band, clip, transform, g);
+        }
+    }
+}
+
+/// Returns the part of the convex `polygon` where `distance` is not negative, for a `distance`
+/// that changes linearly across the polygon, such as the distance from a line.  Corners are kept
+/// in order, and new corners are added where the edges of the polygon cross the line.
+fn clip_polygon<F>(polygon: &[[f64; 2]], distance: F) -> Vec<[f64; 2]>
+where
+    F: Fn(&[f64; 2]) -> f64,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (index, point) in polygon.iter().enumerate() {
+        let next = &polygon[(index + 1) % polygon.len()];
+        let (point_distance, next_distance) = (distance(point), distance(next));
+
+        if point_distance >= 0.0 {
+            clipped.push(*point);
+        }
+
+        // An edge that crosses the line is cut where it crosses.
+        if (point_distance >= 0.0) != (next_distance >= 0.0) {
+            let amount = point_distance / (point_distance - next_distance);
+
+            clipped.push([
+                point[0] + (next[0] - point[0]) * amount,
+                point[1] + (next[1] - point[1]) * amount,
+            ]);
+        }
+    }
+
+    clipped
+}
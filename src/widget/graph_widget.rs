@@ -0,0 +1,584 @@
+// Graph Widget
+// Extensible widget for the widget library - handles plotting of scrolling time-series data.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::core::accessibility::*;
+use crate::core::draw_target::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Default number of samples that each series of a `GraphWidget` keeps.
+pub const DEFAULT_GRAPH_CAPACITY: usize = 60;
+
+/// Default number of spaces between the horizontal grid lines of a `GraphWidget`.
+pub const DEFAULT_GRAPH_GRID_LINES: u32 = 4;
+
+/// The space around the plot of a `GraphWidget`, and between its axis labels and the plot, in
+/// pixels.
+pub const GRAPH_PADDING: i32 = 4;
+
+struct GraphSeries {
+    color: types::Color,
+    samples: VecDeque<f64>,
+}
+
+struct GraphData {
+    series: Vec<GraphSeries>,
+    capacity: usize,
+    changed: bool,
+}
+
+/// This is a handle to the samples of a `GraphWidget`, taken from the graph with `get_samples`.
+/// A handle can be cloned, and moved into a callback, such as the timeout function of a
+/// `TimerWidget`, so that samples can be pushed to the graph after it has been added to the
+/// `WidgetStore`.  The graph redraws itself on the next update after new samples have arrived.
+///
+/// Each series keeps at most the capacity of the graph of samples, dropping the oldest sample
+/// when a new one is pushed.  A sample that is not a number, such as `f64::NAN`, is kept as a
+/// gap in the line.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::graph_widget::*;
+/// # fn main() {
+///    let samples = GraphSamples::new();
+///    let series = samples.add_series([1.0, 0.0, 0.0, 1.0]);
+///
+///    assert_eq!(samples.get_value_range(), None);
+///
+///    samples.set_capacity(3);
+///
+///    for value in &[1.0, 5.0, f64::NAN, 2.0] {
+///        samples.push_sample(series, *value);
+///    }
+///
+///    assert_eq!(samples.get_series_samples(series).len(), 3);
+///    assert_eq!(samples.get_series_samples(series)[0], 5.0);
+///    assert_eq!(samples.get_value_range(), Some((2.0, 5.0)));
+///
+///    // Samples for a series that does not exist are ignored.
+///    samples.push_sample(7, 100.0);
+///    assert_eq!(samples.get_value_range(), Some((2.0, 5.0)));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GraphSamples {
+    data: Rc<RefCell<GraphData>>,
+}
+
+/// Implementation of the `GraphSamples`.
+impl GraphSamples {
+    /// Creates a new set of samples, without any series.
+    pub fn new() -> Self {
+        Self {
+            data: Rc::new(RefCell::new(GraphData {
+                series: Vec::new(),
+                capacity: DEFAULT_GRAPH_CAPACITY,
+                changed: false,
+            })),
+        }
+    }
+
+    /// Adds a series of samples that is drawn in `color`, and returns its index.
+    pub fn add_series(&self, color: types::Color) -> usize {
+        let mut data = self.data.borrow_mut();
+
+        data.series.push(GraphSeries {
+            color,
+            samples: VecDeque::new(),
+        });
+        data.changed = true;
+        data.series.len() - 1
+    }
+
+    /// Returns the number of series.
+    pub fn get_series_count(&self) -> usize {
+        self.data.borrow().series.len()
+    }
+
+    /// Changes the color of `series`.
+    pub fn set_series_color(&self, series: usize, color: types::Color) {
+        let mut data = self.data.borrow_mut();
+
+        if let Some(entry) = data.series.get_mut(series) {
+            entry.color = color;
+            data.changed = true;
+        }
+    }
+
+    /// Retrieves the color of `series`, if it exists.
+    pub fn get_series_color(&self, series: usize) -> Option<types::Color> {
+        self.data
+            .borrow()
+            .series
+            .get(series)
+            .map(|entry| entry.color)
+    }
+
+    /// Sets the number of samples that each series keeps, dropping the oldest samples of any
+    /// series that has more.  Values lower than 2 are treated as 2.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut data = self.data.borrow_mut();
+        let capacity = capacity.max(2);
+
+        data.capacity = capacity;
+
+        for entry in data.series.iter_mut() {
+            while entry.samples.len() > capacity {
+                entry.samples.pop_front();
+            }
+        }
+
+        data.changed = true;
+    }
+
+    /// Retrieves the number of samples that each series keeps.
+    pub fn get_capacity(&self) -> usize {
+        self.data.borrow().capacity
+    }
+
+    /// Adds `value` as the newest sample of `series`, dropping its oldest sample if it is full.
+    /// Samples for a series that does not exist are ignored.
+    pub fn push_sample(&self, series: usize, value: f64) {
+        let mut data = self.data.borrow_mut();
+        let capacity = data.capacity;
+
+        if let Some(entry) = data.series.get_mut(series) {
+            if entry.samples.len() >= capacity {
+                entry.samples.pop_front();
+            }
+
+            entry.samples.push_back(value);
+            data.changed = true;
+        }
+    }
+
+    /// Removes all of the samples of every series, keeping the series.
+    pub fn clear(&self) {
+        let mut data = self.data.borrow_mut();
+
+        for entry in data.series.iter_mut() {
+            entry.samples.clear();
+        }
+
+        data.changed = true;
+    }
+
+    /// Returns the samples of `series`, from oldest to newest.
+    pub fn get_series_samples(&self, series: usize) -> Vec<f64> {
+        self.data
+            .borrow()
+            .series
+            .get(series)
+            .map(|entry| entry.samples.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the smallest and largest sample of all series, skipping samples that are not
+    /// finite numbers, or `None` if there are no such samples.
+    pub fn get_value_range(&self) -> Option<(f64, f64)> {
+        self.data
+            .borrow()
+            .series
+            .iter()
+            .flat_map(|entry| entry.samples.iter())
+            .filter(|value| value.is_finite())
+            .fold(None, |range, value| match range {
+                None => Some((*value, *value)),
+                Some((min, max)) => Some((min.min(*value), max.max(*value))),
+            })
+    }
+
+    /// Indicates whether or not the samples have changed since the last time this was called.
+    fn take_changed(&self) -> bool {
+        std::mem::take(&mut self.data.borrow_mut().changed)
+    }
+}
+
+impl Default for GraphSamples {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the points at which the `samples` of a series are drawn in the rectangle
+/// `[x, y, w, h]`, from oldest to newest, for a graph that keeps `capacity` samples and shows
+/// the values from `min` to `max`.  The newest sample is drawn at the right edge, and each older
+/// sample one step further to the left.  Samples that are not finite numbers have no point.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::graph_widget::*;
+/// # fn main() {
+///    let samples = [0.0, f64::NAN, 10.0];
+///    let points = get_graph_points(&samples, 5, (0.0, 10.0), [0.0, 0.0, 100.0, 50.0]);
+///
+///    assert_eq!(points, vec![Some([50.0, 50.0]), None, Some([100.0, 0.0])]);
+/// # }
+/// ```
+pub fn get_graph_points(
+    samples: &[f64],
+    capacity: usize,
+    (min, max): (f64, f64),
+    [x, y, w, h]: [f64; 4],
+) -> Vec<Option<[f64; 2]>> {
+    let capacity = capacity.max(samples.len()).max(2);
+    let step = w / (capacity - 1) as f64;
+    let first = (capacity - samples.len()) as f64;
+    let span = if max > min { max - min } else { 1.0 };
+
+    samples
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            if value.is_finite() {
+                let fraction = ((value - min) / span).clamp(0.0, 1.0);
+
+                Some([x + (first + index as f64) * step, y + h - fraction * h])
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// This is the `GraphWidget`, which plots one or more series of samples, such as the number of
+/// frames drawn per second, as lines that scroll to the left as new samples are pushed.  Each
+/// series has its own color, and keeps the capacity of the graph of samples.  The values from
+/// the bottom to the top of the graph are either the fixed range set with `set_y_range`, or the
+/// range of the samples that are shown.  Horizontal grid lines are drawn across the graph,
+/// labelled with their values on the left.
+///
+/// Samples are pushed with `push_sample`, or, once the graph has been added to the
+/// `WidgetStore`, through a `GraphSamples` handle taken from it with `get_samples`.  The graph
+/// is only redrawn when new samples have arrived.
+///
+/// Example usage, plotting the number of updates of the run loop every second, as pushed by a
+/// `TimerWidget`:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::graph_widget::*;
+/// # use pushrod::widget::timer_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Graph", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut graph = GraphWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///    let samples = graph.get_samples();
+///    let ups = graph.add_series([0.0, 0.5, 1.0, 1.0]);
+///    let updates = Rc::new(Cell::new(0));
+///    let updates_clone = updates.clone();
+///    let mut update_counter = TimerWidget::new();
+///    let mut sample_timer = TimerWidget::new();
+///
+///    graph.set_origin(20, 20);
+///    graph.set_size(360, 260);
+///
+///    // A timer without a timeout fires on every update.
+///    update_counter.on_timeout(Box::new(move |_| updates_clone.set(updates_clone.get() + 1)));
+///    sample_timer.set_timeout(1000);
+///    sample_timer.on_timeout(Box::new(move |_| {
+///        samples.push_sample(ups, f64::from(updates.replace(0)));
+///    }));
+///
+///    pushrod.add_widget("Graph", Box::new(graph));
+///    pushrod.add_widget("UpdateCounter", Box::new(update_counter));
+///    pushrod.add_widget("SampleTimer", Box::new(sample_timer));
+///    pushrod.run_without_handler();
+/// # }
+/// ```
+pub struct GraphWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    text_widget: Box<TextWidget>,
+    samples: GraphSamples,
+    y_range: Option<(f64, f64)>,
+    grid_lines: u32,
+    show_labels: bool,
+}
+
+/// Implementation of the constructor for the `GraphWidget`.
+impl GraphWidget {
+    /// Creates a new graph without any series, labelled with the font `font_name` from the
+    /// `assets` directory.  Panics if the font cannot be loaded; use `font_cache::load_font` and
+    /// `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs)
+    }
+
+    /// Creates a new graph without any series, labelled with a font from the font cache.
+    pub fn from_font(font: SharedGlyphs) -> Self {
+        let font_size = get_current_theme().font_size;
+
+        Self {
+            config: Configurable::new(),
+            font: font.clone(),
+            text_widget: Box::new(TextWidget::from_font(
+                font,
+                String::new(),
+                font_size,
+                TextJustify::Right,
+            )),
+            samples: GraphSamples::new(),
+            y_range: None,
+            grid_lines: DEFAULT_GRAPH_GRID_LINES,
+            show_labels: true,
+        }
+    }
+
+    /// Returns a handle to the samples of the graph, which can be used to push samples to it
+    /// after it has been added to the `WidgetStore`.
+    pub fn get_samples(&self) -> GraphSamples {
+        self.samples.clone()
+    }
+
+    /// Adds a series of samples that is drawn in `color`, and returns its index.
+    pub fn add_series(&mut self, color: types::Color) -> usize {
+        self.samples.add_series(color)
+    }
+
+    /// Adds `value` as the newest sample of `series`, dropping its oldest sample if it is full,
+    /// and invalidates the widget.  See `GraphSamples::push_sample`.
+    pub fn push_sample(&mut self, series: usize, value: f64) {
+        self.samples.push_sample(series, value);
+        self.invalidate();
+    }
+
+    /// Sets the number of samples that each series keeps, which is also the number of samples
+    /// that fit across the graph.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.samples.set_capacity(capacity);
+        self.invalidate();
+    }
+
+    /// Retrieves the number of samples that each series keeps.  Defaults to 60.
+    pub fn get_capacity(&self) -> usize {
+        self.samples.get_capacity()
+    }
+
+    /// Sets the values shown at the bottom and the top of the graph, or `None` to scale the
+    /// graph to the smallest and largest sample that is shown.  Samples outside of a fixed range
+    /// are drawn at its edge.
+    pub fn set_y_range(&mut self, y_range: Option<(f64, f64)>) {
+        self.y_range = y_range;
+        self.invalidate();
+    }
+
+    /// Retrieves the fixed range of values shown by the graph, if any.  Defaults to `None`.
+    pub fn get_y_range(&self) -> Option<(f64, f64)> {
+        self.y_range
+    }
+
+    /// Sets the number of spaces between the horizontal grid lines.  With 0, no grid lines are
+    /// drawn, and only the bottom and the top of the graph are labelled.
+    pub fn set_grid_lines(&mut self, grid_lines: u32) {
+        self.grid_lines = grid_lines;
+        self.invalidate();
+    }
+
+    /// Retrieves the number of spaces between the horizontal grid lines.  Defaults to 4.
+    pub fn get_grid_lines(&self) -> u32 {
+        self.grid_lines
+    }
+
+    /// Sets whether or not the grid lines are labelled with their values.
+    pub fn set_show_labels(&mut self, show_labels: bool) {
+        self.show_labels = show_labels;
+        self.invalidate();
+    }
+
+    /// Indicates whether or not the grid lines are labelled with their values.  Defaults to
+    /// `true`.
+    pub fn is_showing_labels(&self) -> bool {
+        self.show_labels
+    }
+
+    /// Sets the color of the labels.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the labels, which defaults to the text color of the current
+    /// `Theme`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Returns the range of values that is shown from the bottom to the top of the graph.  A
+    /// range without any height is widened, so that its samples are drawn across the middle.
+    fn get_shown_range(&self) -> (f64, f64) {
+        let (min, max) = self
+            .y_range
+            .or_else(|| self.samples.get_value_range())
+            .unwrap_or((0.0, 1.0));
+
+        if max > min {
+            (min, max)
+        } else {
+            (min - 1.0, min + 1.0)
+        }
+    }
+}
+
+/// Formats `value` as a label for a graph that shows a range of values that is `span` high.
+fn format_graph_value(value: f64, span: f64) -> String {
+    if span >= 10.0 {
+        format!("{:.0}", value)
+    } else if span >= 1.0 {
+        format!("{:.1}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// Implementation of the `GraphWidget` object with the `Widget` traits implemented.
+impl Widget for GraphWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Image
+    }
+
+    /// Invalidates the widget if samples have been pushed through a `GraphSamples` handle
+    /// since the last update.
+    fn update(&mut self, _delta_ms: u64) {
+        if self.samples.take_changed() {
+            self.invalidate();
+        }
+    }
+
+    /// Passes the new `Theme` on to the labels, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the labels, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, the grid lines and their labels, and then the line of each series,
+    /// leaving gaps where samples are not numbers.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (min, max) = self.get_shown_range();
+        let span = max - min;
+        let grid_lines = self.grid_lines.max(1);
+        let labels: Vec<String> = (0..=grid_lines)
+            .map(|index| {
+                format_graph_value(min + span * f64::from(index) / f64::from(grid_lines), span)
+            })
+            .collect();
+        let font_size = self.text_widget.get_font_size();
+        let label_h = (f64::from(font_size) * DEFAULT_LINE_SPACING).ceil() as i32;
+        let label_w = if self.show_labels {
+            let mut glyphs = self.font.borrow_mut();
+
+            labels
+                .iter()
+                .map(|label| text_width(&mut glyphs, font_size, label))
+                .max()
+                .unwrap_or(0)
+                + GRAPH_PADDING
+        } else {
+            0
+        };
+        let vertical_padding = if self.show_labels {
+            GRAPH_PADDING.max(label_h / 2)
+        } else {
+            GRAPH_PADDING
+        };
+        let plot = [
+            f64::from(GRAPH_PADDING + label_w),
+            f64::from(vertical_padding),
+            f64::from((size.w - GRAPH_PADDING * 2 - label_w).max(0)),
+            f64::from((size.h - vertical_padding * 2).max(0)),
+        ];
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let grid_color = apply_opacity(theme.border_color);
+        let text_color = self.get_text_color();
+
+        for (index, label) in labels.iter().enumerate() {
+            let y = plot[1] + plot[3] - plot[3] * index as f64 / f64::from(grid_lines);
+
+            if self.grid_lines > 0 {
+                Line::new(grid_color, 0.5).draw(
+                    [plot[0], y, plot[0] + plot[2], y],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            if self.show_labels {
+                self.text_widget.set_text(label.clone());
+                self.text_widget.set_size(label_w - GRAPH_PADDING, label_h);
+                self.text_widget.set_text_color(text_color);
+                self.text_widget.draw(
+                    c.trans(
+                        f64::from(GRAPH_PADDING),
+                        (y - f64::from(label_h) / 2.0).round(),
+                    ),
+                    g,
+                    clip,
+                );
+            }
+        }
+
+        let capacity = self.samples.get_capacity();
+
+        for series in 0..self.samples.get_series_count() {
+            let color = apply_opacity(self.samples.get_series_color(series).unwrap_or([0.0; 4]));
+            let samples = self.samples.get_series_samples(series);
+            let points = get_graph_points(&samples, capacity, (min, max), plot);
+
+            for pair in points.windows(2) {
+                if let [Some([x1, y1]), Some([x2, y2])] = pair {
+                    Line::new(color, 1.0).draw([*x1, *y1, *x2, *y2], clip, c.transform, g);
+                }
+            }
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,1049 @@
+// Grapheme Clusters
+// Splits text into the characters that a reader sees, for moving the caret and deleting text
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use self::GraphemeClass::*;
+
+/// The kinds of `char` that decide where one grapheme cluster ends and the next begins.  These
+/// are the values of the `Grapheme_Cluster_Break` property of Unicode, with `Extend` split by the
+/// `Indic_Conjunct_Break` property, and with the `Extended_Pictographic` `char`s and the
+/// consonants that join into Indic conjuncts picked out of `Other`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GraphemeClass {
+    CarriageReturn,
+    LineFeed,
+    Control,
+    Prepend,
+    Extend,
+    IndicExtend,
+    IndicLinker,
+    SpacingMark,
+    ZeroWidthJoiner,
+    RegionalIndicator,
+    Pictographic,
+    IndicConsonant,
+    HangulL,
+    HangulV,
+    HangulT,
+    HangulLV,
+    HangulLVT,
+    Other,
+}
+
+/// Returns the class of `c`.  Precomposed Hangul syllables are told apart by arithmetic, and
+/// every other `char` is looked up in `GRAPHEME_CLASSES`.
+fn get_grapheme_class(c: char) -> GraphemeClass {
+    let code = c as u32;
+
+    if (0xac00..=0xd7a3).contains(&code) {
+        return if (code - 0xac00).is_multiple_of(28) {
+            HangulLV
+        } else {
+            HangulLVT
+        };
+    }
+
+    let found = GRAPHEME_CLASSES.binary_search_by(|&(start, end, _)| {
+        if end < code {
+            Ordering::Less
+        } else if start > code {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    });
+
+    match found {
+        Ok(index) => GRAPHEME_CLASSES[index].2,
+        Err(_) => Other,
+    }
+}
+
+/// Returns the byte index of every boundary between the grapheme clusters of `text`, from 0 to
+/// the length of the text, so that a text of `n` clusters has `n + 1` boundaries.  A grapheme
+/// cluster is what a reader sees as one character, even when it is made of several `char`s,
+/// such as a letter with combining accents, a syllable of an Indic script with its vowel signs,
+/// an emoji with a skin tone, a sequence of emoji joined into one, a flag, or a Hangul syllable
+/// typed as separate jamo.  These are the extended grapheme clusters of Unicode 17.0, as
+/// described in Unicode Standard Annex #29.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::grapheme::*;
+/// # fn main() {
+///    assert_eq!(get_grapheme_boundaries(""), vec![0]);
+///    assert_eq!(get_grapheme_boundaries("abc"), vec![0, 1, 2, 3]);
+///
+///    // An "e" with a combining acute accent, and a line break of two `char`s.
+///    assert_eq!(get_grapheme_boundaries("e\u{301}x\r\n"), vec![0, 3, 4, 6]);
+///
+///    // A thumbs up with a skin tone, the family emoji, and two flags.
+///    assert_eq!(get_grapheme_boundaries("\u{1f44d}\u{1f3fd}"), vec![0, 8]);
+///    assert_eq!(
+///        get_grapheme_boundaries("\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}"),
+///        vec![0, 18]
+///    );
+///    assert_eq!(
+///        get_grapheme_boundaries("\u{1f1ef}\u{1f1f5}\u{1f1eb}\u{1f1f7}"),
+///        vec![0, 8, 16]
+///    );
+///
+///    // Hangul syllables, precomposed and as jamo, and Japanese.
+///    assert_eq!(get_grapheme_boundaries("한\u{1112}\u{1161}\u{11ab}"), vec![0, 3, 12]);
+///    assert_eq!(get_grapheme_boundaries("日本"), vec![0, 3, 6]);
+///
+///    // Tamil "தமிழ்", Telugu "తెలుగు", and Malayalam "മലയാളം", with their vowel signs.
+///    assert_eq!(get_grapheme_count("தமிழ்"), 3);
+///    assert_eq!(get_grapheme_count("తెలుగు"), 3);
+///    assert_eq!(get_grapheme_count("മലയാളം"), 4);
+///
+///    // The Devanagari conjunct "क्ष", joined by a virama.
+///    assert_eq!(get_grapheme_boundaries("\u{915}\u{94d}\u{937}"), vec![0, 9]);
+/// # }
+/// ```
+pub fn get_grapheme_boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries = vec![0];
+    let mut previous: Option<GraphemeClass> = None;
+    let mut emoji_run = false;
+    let mut joins_emoji = false;
+    let mut conjunct: Option<bool> = None;
+    let mut regional_run = 0;
+
+    for (index, c) in text.char_indices() {
+        let class = get_grapheme_class(c);
+
+        if let Some(previous) = previous {
+            let linked_conjunct = conjunct == Some(true);
+
+            if is_grapheme_break(previous, class, joins_emoji, linked_conjunct, regional_run) {
+                boundaries.push(index);
+            }
+        }
+
+        joins_emoji = class == ZeroWidthJoiner && emoji_run;
+        emoji_run = match class {
+            Pictographic => true,
+            Extend | IndicExtend | IndicLinker => emoji_run,
+            _ => false,
+        };
+        conjunct = match class {
+            IndicConsonant => Some(false),
+            IndicLinker => conjunct.map(|_| true),
+            IndicExtend | ZeroWidthJoiner => conjunct,
+            _ => None,
+        };
+        regional_run = if class == RegionalIndicator {
+            regional_run + 1
+        } else {
+            0
+        };
+        previous = Some(class);
+    }
+
+    if !text.is_empty() {
+        boundaries.push(text.len());
+    }
+
+    boundaries
+}
+
+/// Returns the number of grapheme clusters in `text`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::grapheme::*;
+/// # fn main() {
+///    assert_eq!(get_grapheme_count("Caf\u{e9}"), 4);
+///    assert_eq!(get_grapheme_count("Cafe\u{301}"), 4);
+/// # }
+/// ```
+pub fn get_grapheme_count(text: &str) -> usize {
+    get_grapheme_boundaries(text).len() - 1
+}
+
+/// Decides whether there is a boundary between a `char` of class `previous` and one of class
+/// `next`.  `joins_emoji` is whether `previous` is a zero width joiner that follows an emoji and
+/// any modifiers, `linked_conjunct` is whether `previous` ends an Indic consonant followed by a
+/// virama, which joins it to the next consonant, and `regional_run` is the number of regional
+/// indicators in a row up to `previous`, which pair up into flags.
+fn is_grapheme_break(
+    previous: GraphemeClass,
+    next: GraphemeClass,
+    joins_emoji: bool,
+    linked_conjunct: bool,
+    regional_run: usize,
+) -> bool {
+    match (previous, next) {
+        (CarriageReturn, LineFeed) => false,
+        (CarriageReturn, _) | (LineFeed, _) | (Control, _) => true,
+        (_, CarriageReturn) | (_, LineFeed) | (_, Control) => true,
+        (HangulL, HangulL) | (HangulL, HangulV) | (HangulL, HangulLV) | (HangulL, HangulLVT) => {
+            false
+        }
+        (HangulLV, HangulV) | (HangulLV, HangulT) | (HangulV, HangulV) | (HangulV, HangulT) => {
+            false
+        }
+        (HangulLVT, HangulT) | (HangulT, HangulT) => false,
+        (_, Extend) | (_, IndicExtend) | (_, IndicLinker) | (_, ZeroWidthJoiner) => false,
+        (_, SpacingMark) | (Prepend, _) => false,
+        (_, IndicConsonant) if linked_conjunct => false,
+        (ZeroWidthJoiner, Pictographic) => !joins_emoji,
+        (RegionalIndicator, RegionalIndicator) => regional_run.is_multiple_of(2),
+        _ => true,
+    }
+}
+
+/// The class of every `char` that is not `Other`, as ranges of code points, from the lowest,
+/// except for the precomposed Hangul syllables.  Generated by `scripts/gen_grapheme_table.py`
+/// from the Unicode Character Database 17.0.0: `GraphemeBreakProperty.txt`, the
+/// `Extended_Pictographic` property of `emoji-data.txt`, and the `Indic_Conjunct_Break`
+/// property of `DerivedCoreProperties.txt`.  Run the script again to update the table to a
+/// newer version of Unicode, rather than editing it by hand.
+const GRAPHEME_CLASSES: &[(u32, u32, GraphemeClass)] = &[
+    (0x0000, 0x0009, Control),
+    (0x000a, 0x000a, LineFeed),
+    (0x000b, 0x000c, Control),
+    (0x000d, 0x000d, CarriageReturn),
+    (0x000e, 0x001f, Control),
+    (0x007f, 0x009f, Control),
+    (0x00a9, 0x00a9, Pictographic),
+    (0x00ad, 0x00ad, Control),
+    (0x00ae, 0x00ae, Pictographic),
+    (0x0300, 0x036f, IndicExtend),
+    (0x0483, 0x0489, IndicExtend),
+    (0x0591, 0x05bd, IndicExtend),
+    (0x05bf, 0x05bf, IndicExtend),
+    (0x05c1, 0x05c2, IndicExtend),
+    (0x05c4, 0x05c5, IndicExtend),
+    (0x05c7, 0x05c7, IndicExtend),
+    (0x0600, 0x0605, Prepend),
+    (0x0610, 0x061a, IndicExtend),
+    (0x061c, 0x061c, Control),
+    (0x064b, 0x065f, IndicExtend),
+    (0x0670, 0x0670, IndicExtend),
+    (0x06d6, 0x06dc, IndicExtend),
+    (0x06dd, 0x06dd, Prepend),
+    (0x06df, 0x06e4, IndicExtend),
+    (0x06e7, 0x06e8, IndicExtend),
+    (0x06ea, 0x06ed, IndicExtend),
+    (0x070f, 0x070f, Prepend),
+    (0x0711, 0x0711, IndicExtend),
+    (0x0730, 0x074a, IndicExtend),
+    (0x07a6, 0x07b0, IndicExtend),
+    (0x07eb, 0x07f3, IndicExtend),
+    (0x07fd, 0x07fd, IndicExtend),
+    (0x0816, 0x0819, IndicExtend),
+    (0x081b, 0x0823, IndicExtend),
+    (0x0825, 0x0827, IndicExtend),
+    (0x0829, 0x082d, IndicExtend),
+    (0x0859, 0x085b, IndicExtend),
+    (0x0890, 0x0891, Prepend),
+    (0x0897, 0x089f, IndicExtend),
+    (0x08ca, 0x08e1, IndicExtend),
+    (0x08e2, 0x08e2, Prepend),
+    (0x08e3, 0x0902, IndicExtend),
+    (0x0903, 0x0903, SpacingMark),
+    (0x0915, 0x0939, IndicConsonant),
+    (0x093a, 0x093a, IndicExtend),
+    (0x093b, 0x093b, SpacingMark),
+    (0x093c, 0x093c, IndicExtend),
+    (0x093e, 0x0940, SpacingMark),
+    (0x0941, 0x0948, IndicExtend),
+    (0x0949, 0x094c, SpacingMark),
+    (0x094d, 0x094d, IndicLinker),
+    (0x094e, 0x094f, SpacingMark),
+    (0x0951, 0x0957, IndicExtend),
+    (0x0958, 0x095f, IndicConsonant),
+    (0x0962, 0x0963, IndicExtend),
+    (0x0978, 0x097f, IndicConsonant),
+    (0x0981, 0x0981, IndicExtend),
+    (0x0982, 0x0983, SpacingMark),
+    (0x0995, 0x09a8, IndicConsonant),
+    (0x09aa, 0x09b0, IndicConsonant),
+    (0x09b2, 0x09b2, IndicConsonant),
+    (0x09b6, 0x09b9, IndicConsonant),
+    (0x09bc, 0x09bc, IndicExtend),
+    (0x09be, 0x09be, IndicExtend),
+    (0x09bf, 0x09c0, SpacingMark),
+    (0x09c1, 0x09c4, IndicExtend),
+    (0x09c7, 0x09c8, SpacingMark),
+    (0x09cb, 0x09cc, SpacingMark),
+    (0x09cd, 0x09cd, IndicLinker),
+    (0x09d7, 0x09d7, IndicExtend),
+    (0x09dc, 0x09dd, IndicConsonant),
+    (0x09df, 0x09df, IndicConsonant),
+    (0x09e2, 0x09e3, IndicExtend),
+    (0x09f0, 0x09f1, IndicConsonant),
+    (0x09fe, 0x09fe, IndicExtend),
+    (0x0a01, 0x0a02, IndicExtend),
+    (0x0a03, 0x0a03, SpacingMark),
+    (0x0a3c, 0x0a3c, IndicExtend),
+    (0x0a3e, 0x0a40, SpacingMark),
+    (0x0a41, 0x0a42, IndicExtend),
+    (0x0a47, 0x0a48, IndicExtend),
+    (0x0a4b, 0x0a4d, IndicExtend),
+    (0x0a51, 0x0a51, IndicExtend),
+    (0x0a70, 0x0a71, IndicExtend),
+    (0x0a75, 0x0a75, IndicExtend),
+    (0x0a81, 0x0a82, IndicExtend),
+    (0x0a83, 0x0a83, SpacingMark),
+    (0x0a95, 0x0aa8, IndicConsonant),
+    (0x0aaa, 0x0ab0, IndicConsonant),
+    (0x0ab2, 0x0ab3, IndicConsonant),
+    (0x0ab5, 0x0ab9, IndicConsonant),
+    (0x0abc, 0x0abc, IndicExtend),
+    (0x0abe, 0x0ac0, SpacingMark),
+    (0x0ac1, 0x0ac5, IndicExtend),
+    (0x0ac7, 0x0ac8, IndicExtend),
+    (0x0ac9, 0x0ac9, SpacingMark),
+    (0x0acb, 0x0acc, SpacingMark),
+    (0x0acd, 0x0acd, IndicLinker),
+    (0x0ae2, 0x0ae3, IndicExtend),
+    (0x0af9, 0x0af9, IndicConsonant),
+    (0x0afa, 0x0aff, IndicExtend),
+    (0x0b01, 0x0b01, IndicExtend),
+    (0x0b02, 0x0b03, SpacingMark),
+    (0x0b15, 0x0b28, IndicConsonant),
+    (0x0b2a, 0x0b30, IndicConsonant),
+    (0x0b32, 0x0b33, IndicConsonant),
+    (0x0b35, 0x0b39, IndicConsonant),
+    (0x0b3c, 0x0b3c, IndicExtend),
+    (0x0b3e, 0x0b3f, IndicExtend),
+    (0x0b40, 0x0b40, SpacingMark),
+    (0x0b41, 0x0b44, IndicExtend),
+    (0x0b47, 0x0b48, SpacingMark),
+    (0x0b4b, 0x0b4c, SpacingMark),
+    (0x0b4d, 0x0b4d, IndicLinker),
+    (0x0b55, 0x0b57, IndicExtend),
+    (0x0b5c, 0x0b5d, IndicConsonant),
+    (0x0b5f, 0x0b5f, IndicConsonant),
+    (0x0b62, 0x0b63, IndicExtend),
+    (0x0b71, 0x0b71, IndicConsonant),
+    (0x0b82, 0x0b82, IndicExtend),
+    (0x0bbe, 0x0bbe, IndicExtend),
+    (0x0bbf, 0x0bbf, SpacingMark),
+    (0x0bc0, 0x0bc0, IndicExtend),
+    (0x0bc1, 0x0bc2, SpacingMark),
+    (0x0bc6, 0x0bc8, SpacingMark),
+    (0x0bca, 0x0bcc, SpacingMark),
+    (0x0bcd, 0x0bcd, IndicExtend),
+    (0x0bd7, 0x0bd7, IndicExtend),
+    (0x0c00, 0x0c00, IndicExtend),
+    (0x0c01, 0x0c03, SpacingMark),
+    (0x0c04, 0x0c04, IndicExtend),
+    (0x0c15, 0x0c28, IndicConsonant),
+    (0x0c2a, 0x0c39, IndicConsonant),
+    (0x0c3c, 0x0c3c, IndicExtend),
+    (0x0c3e, 0x0c40, IndicExtend),
+    (0x0c41, 0x0c44, SpacingMark),
+    (0x0c46, 0x0c48, IndicExtend),
+    (0x0c4a, 0x0c4c, IndicExtend),
+    (0x0c4d, 0x0c4d, IndicLinker),
+    (0x0c55, 0x0c56, IndicExtend),
+    (0x0c58, 0x0c5a, IndicConsonant),
+    (0x0c62, 0x0c63, IndicExtend),
+    (0x0c81, 0x0c81, IndicExtend),
+    (0x0c82, 0x0c83, SpacingMark),
+    (0x0cbc, 0x0cbc, IndicExtend),
+    (0x0cbe, 0x0cbe, SpacingMark),
+    (0x0cbf, 0x0cc0, IndicExtend),
+    (0x0cc1, 0x0cc1, SpacingMark),
+    (0x0cc2, 0x0cc2, IndicExtend),
+    (0x0cc3, 0x0cc4, SpacingMark),
+    (0x0cc6, 0x0cc8, IndicExtend),
+    (0x0cca, 0x0ccd, IndicExtend),
+    (0x0cd5, 0x0cd6, IndicExtend),
+    (0x0ce2, 0x0ce3, IndicExtend),
+    (0x0cf3, 0x0cf3, SpacingMark),
+    (0x0d00, 0x0d01, IndicExtend),
+    (0x0d02, 0x0d03, SpacingMark),
+    (0x0d15, 0x0d3a, IndicConsonant),
+    (0x0d3b, 0x0d3c, IndicExtend),
+    (0x0d3e, 0x0d3e, IndicExtend),
+    (0x0d3f, 0x0d40, SpacingMark),
+    (0x0d41, 0x0d44, IndicExtend),
+    (0x0d46, 0x0d48, SpacingMark),
+    (0x0d4a, 0x0d4c, SpacingMark),
+    (0x0d4d, 0x0d4d, IndicLinker),
+    (0x0d4e, 0x0d4e, Prepend),
+    (0x0d57, 0x0d57, IndicExtend),
+    (0x0d62, 0x0d63, IndicExtend),
+    (0x0d81, 0x0d81, IndicExtend),
+    (0x0d82, 0x0d83, SpacingMark),
+    (0x0dca, 0x0dca, IndicExtend),
+    (0x0dcf, 0x0dcf, IndicExtend),
+    (0x0dd0, 0x0dd1, SpacingMark),
+    (0x0dd2, 0x0dd4, IndicExtend),
+    (0x0dd6, 0x0dd6, IndicExtend),
+    (0x0dd8, 0x0dde, SpacingMark),
+    (0x0ddf, 0x0ddf, IndicExtend),
+    (0x0df2, 0x0df3, SpacingMark),
+    (0x0e31, 0x0e31, IndicExtend),
+    (0x0e33, 0x0e33, SpacingMark),
+    (0x0e34, 0x0e3a, IndicExtend),
+    (0x0e47, 0x0e4e, IndicExtend),
+    (0x0eb1, 0x0eb1, IndicExtend),
+    (0x0eb3, 0x0eb3, SpacingMark),
+    (0x0eb4, 0x0ebc, IndicExtend),
+    (0x0ec8, 0x0ece, IndicExtend),
+    (0x0f18, 0x0f19, IndicExtend),
+    (0x0f35, 0x0f35, IndicExtend),
+    (0x0f37, 0x0f37, IndicExtend),
+    (0x0f39, 0x0f39, IndicExtend),
+    (0x0f3e, 0x0f3f, SpacingMark),
+    (0x0f71, 0x0f7e, IndicExtend),
+    (0x0f7f, 0x0f7f, SpacingMark),
+    (0x0f80, 0x0f84, IndicExtend),
+    (0x0f86, 0x0f87, IndicExtend),
+    (0x0f8d, 0x0f97, IndicExtend),
+    (0x0f99, 0x0fbc, IndicExtend),
+    (0x0fc6, 0x0fc6, IndicExtend),
+    (0x1000, 0x102a, IndicConsonant),
+    (0x102d, 0x1030, IndicExtend),
+    (0x1031, 0x1031, SpacingMark),
+    (0x1032, 0x1037, IndicExtend),
+    (0x1039, 0x1039, IndicLinker),
+    (0x103a, 0x103a, IndicExtend),
+    (0x103b, 0x103c, SpacingMark),
+    (0x103d, 0x103e, IndicExtend),
+    (0x103f, 0x103f, IndicConsonant),
+    (0x1050, 0x1055, IndicConsonant),
+    (0x1056, 0x1057, SpacingMark),
+    (0x1058, 0x1059, IndicExtend),
+    (0x105a, 0x105d, IndicConsonant),
+    (0x105e, 0x1060, IndicExtend),
+    (0x1061, 0x1061, IndicConsonant),
+    (0x1065, 0x1066, IndicConsonant),
+    (0x106e, 0x1070, IndicConsonant),
+    (0x1071, 0x1074, IndicExtend),
+    (0x1075, 0x1081, IndicConsonant),
+    (0x1082, 0x1082, IndicExtend),
+    (0x1084, 0x1084, SpacingMark),
+    (0x1085, 0x1086, IndicExtend),
+    (0x108d, 0x108d, IndicExtend),
+    (0x108e, 0x108e, IndicConsonant),
+    (0x109d, 0x109d, IndicExtend),
+    (0x1100, 0x115f, HangulL),
+    (0x1160, 0x11a7, HangulV),
+    (0x11a8, 0x11ff, HangulT),
+    (0x135d, 0x135f, IndicExtend),
+    (0x1712, 0x1715, IndicExtend),
+    (0x1732, 0x1734, IndicExtend),
+    (0x1752, 0x1753, IndicExtend),
+    (0x1772, 0x1773, IndicExtend),
+    (0x1780, 0x17b3, IndicConsonant),
+    (0x17b4, 0x17b5, IndicExtend),
+    (0x17b6, 0x17b6, SpacingMark),
+    (0x17b7, 0x17bd, IndicExtend),
+    (0x17be, 0x17c5, SpacingMark),
+    (0x17c6, 0x17c6, IndicExtend),
+    (0x17c7, 0x17c8, SpacingMark),
+    (0x17c9, 0x17d1, IndicExtend),
+    (0x17d2, 0x17d2, IndicLinker),
+    (0x17d3, 0x17d3, IndicExtend),
+    (0x17dd, 0x17dd, IndicExtend),
+    (0x180b, 0x180d, IndicExtend),
+    (0x180e, 0x180e, Control),
+    (0x180f, 0x180f, IndicExtend),
+    (0x1885, 0x1886, IndicExtend),
+    (0x18a9, 0x18a9, IndicExtend),
+    (0x1920, 0x1922, IndicExtend),
+    (0x1923, 0x1926, SpacingMark),
+    (0x1927, 0x1928, IndicExtend),
+    (0x1929, 0x192b, SpacingMark),
+    (0x1930, 0x1931, SpacingMark),
+    (0x1932, 0x1932, IndicExtend),
+    (0x1933, 0x1938, SpacingMark),
+    (0x1939, 0x193b, IndicExtend),
+    (0x1a17, 0x1a18, IndicExtend),
+    (0x1a19, 0x1a1a, SpacingMark),
+    (0x1a1b, 0x1a1b, IndicExtend),
+    (0x1a20, 0x1a54, IndicConsonant),
+    (0x1a55, 0x1a55, SpacingMark),
+    (0x1a56, 0x1a56, IndicExtend),
+    (0x1a57, 0x1a57, SpacingMark),
+    (0x1a58, 0x1a5e, IndicExtend),
+    (0x1a60, 0x1a60, IndicLinker),
+    (0x1a62, 0x1a62, IndicExtend),
+    (0x1a65, 0x1a6c, IndicExtend),
+    (0x1a6d, 0x1a72, SpacingMark),
+    (0x1a73, 0x1a7c, IndicExtend),
+    (0x1a7f, 0x1a7f, IndicExtend),
+    (0x1ab0, 0x1add, IndicExtend),
+    (0x1ae0, 0x1aeb, IndicExtend),
+    (0x1b00, 0x1b03, IndicExtend),
+    (0x1b04, 0x1b04, SpacingMark),
+    (0x1b0b, 0x1b0c, IndicConsonant),
+    (0x1b13, 0x1b33, IndicConsonant),
+    (0x1b34, 0x1b3d, IndicExtend),
+    (0x1b3e, 0x1b41, SpacingMark),
+    (0x1b42, 0x1b43, IndicExtend),
+    (0x1b44, 0x1b44, IndicLinker),
+    (0x1b45, 0x1b4c, IndicConsonant),
+    (0x1b6b, 0x1b73, IndicExtend),
+    (0x1b80, 0x1b81, IndicExtend),
+    (0x1b82, 0x1b82, SpacingMark),
+    (0x1b83, 0x1ba0, IndicConsonant),
+    (0x1ba1, 0x1ba1, SpacingMark),
+    (0x1ba2, 0x1ba5, IndicExtend),
+    (0x1ba6, 0x1ba7, SpacingMark),
+    (0x1ba8, 0x1baa, IndicExtend),
+    (0x1bab, 0x1bab, IndicLinker),
+    (0x1bac, 0x1bad, IndicExtend),
+    (0x1bae, 0x1baf, IndicConsonant),
+    (0x1bbb, 0x1bbd, IndicConsonant),
+    (0x1be6, 0x1be6, IndicExtend),
+    (0x1be7, 0x1be7, SpacingMark),
+    (0x1be8, 0x1be9, IndicExtend),
+    (0x1bea, 0x1bec, SpacingMark),
+    (0x1bed, 0x1bed, IndicExtend),
+    (0x1bee, 0x1bee, SpacingMark),
+    (0x1bef, 0x1bf3, IndicExtend),
+    (0x1c24, 0x1c2b, SpacingMark),
+    (0x1c2c, 0x1c33, IndicExtend),
+    (0x1c34, 0x1c35, SpacingMark),
+    (0x1c36, 0x1c37, IndicExtend),
+    (0x1cd0, 0x1cd2, IndicExtend),
+    (0x1cd4, 0x1ce0, IndicExtend),
+    (0x1ce1, 0x1ce1, SpacingMark),
+    (0x1ce2, 0x1ce8, IndicExtend),
+    (0x1ced, 0x1ced, IndicExtend),
+    (0x1cf4, 0x1cf4, IndicExtend),
+    (0x1cf7, 0x1cf7, SpacingMark),
+    (0x1cf8, 0x1cf9, IndicExtend),
+    (0x1dc0, 0x1dff, IndicExtend),
+    (0x200b, 0x200b, Control),
+    (0x200c, 0x200c, Extend),
+    (0x200d, 0x200d, ZeroWidthJoiner),
+    (0x200e, 0x200f, Control),
+    (0x2028, 0x202e, Control),
+    (0x203c, 0x203c, Pictographic),
+    (0x2049, 0x2049, Pictographic),
+    (0x2060, 0x206f, Control),
+    (0x20d0, 0x20f0, IndicExtend),
+    (0x2122, 0x2122, Pictographic),
+    (0x2139, 0x2139, Pictographic),
+    (0x2194, 0x2199, Pictographic),
+    (0x21a9, 0x21aa, Pictographic),
+    (0x231a, 0x231b, Pictographic),
+    (0x2328, 0x2328, Pictographic),
+    (0x23cf, 0x23cf, Pictographic),
+    (0x23e9, 0x23f3, Pictographic),
+    (0x23f8, 0x23fa, Pictographic),
+    (0x24c2, 0x24c2, Pictographic),
+    (0x25aa, 0x25ab, Pictographic),
+    (0x25b6, 0x25b6, Pictographic),
+    (0x25c0, 0x25c0, Pictographic),
+    (0x25fb, 0x25fe, Pictographic),
+    (0x2600, 0x2604, Pictographic),
+    (0x260e, 0x260e, Pictographic),
+    (0x2611, 0x2611, Pictographic),
+    (0x2614, 0x2615, Pictographic),
+    (0x2618, 0x2618, Pictographic),
+    (0x261d, 0x261d, Pictographic),
+    (0x2620, 0x2620, Pictographic),
+    (0x2622, 0x2623, Pictographic),
+    (0x2626, 0x2626, Pictographic),
+    (0x262a, 0x262a, Pictographic),
+    (0x262e, 0x262f, Pictographic),
+    (0x2638, 0x263a, Pictographic),
+    (0x2640, 0x2640, Pictographic),
+    (0x2642, 0x2642, Pictographic),
+    (0x2648, 0x2653, Pictographic),
+    (0x265f, 0x2660, Pictographic),
+    (0x2663, 0x2663, Pictographic),
+    (0x2665, 0x2666, Pictographic),
+    (0x2668, 0x2668, Pictographic),
+    (0x267b, 0x267b, Pictographic),
+    (0x267e, 0x267f, Pictographic),
+    (0x2692, 0x2697, Pictographic),
+    (0x2699, 0x2699, Pictographic),
+    (0x269b, 0x269c, Pictographic),
+    (0x26a0, 0x26a1, Pictographic),
+    (0x26a7, 0x26a7, Pictographic),
+    (0x26aa, 0x26ab, Pictographic),
+    (0x26b0, 0x26b1, Pictographic),
+    (0x26bd, 0x26be, Pictographic),
+    (0x26c4, 0x26c5, Pictographic),
+    (0x26c8, 0x26c8, Pictographic),
+    (0x26ce, 0x26cf, Pictographic),
+    (0x26d1, 0x26d1, Pictographic),
+    (0x26d3, 0x26d4, Pictographic),
+    (0x26e9, 0x26ea, Pictographic),
+    (0x26f0, 0x26f5, Pictographic),
+    (0x26f7, 0x26fa, Pictographic),
+    (0x26fd, 0x26fd, Pictographic),
+    (0x2702, 0x2702, Pictographic),
+    (0x2705, 0x2705, Pictographic),
+    (0x2708, 0x270d, Pictographic),
+    (0x270f, 0x270f, Pictographic),
+    (0x2712, 0x2712, Pictographic),
+    (0x2714, 0x2714, Pictographic),
+    (0x2716, 0x2716, Pictographic),
+    (0x271d, 0x271d, Pictographic),
+    (0x2721, 0x2721, Pictographic),
+    (0x2728, 0x2728, Pictographic),
+    (0x2733, 0x2734, Pictographic),
+    (0x2744, 0x2744, Pictographic),
+    (0x2747, 0x2747, Pictographic),
+    (0x274c, 0x274c, Pictographic),
+    (0x274e, 0x274e, Pictographic),
+    (0x2753, 0x2755, Pictographic),
+    (0x2757, 0x2757, Pictographic),
+    (0x2763, 0x2764, Pictographic),
+    (0x2795, 0x2797, Pictographic),
+    (0x27a1, 0x27a1, Pictographic),
+    (0x27b0, 0x27b0, Pictographic),
+    (0x27bf, 0x27bf, Pictographic),
+    (0x2934, 0x2935, Pictographic),
+    (0x2b05, 0x2b07, Pictographic),
+    (0x2b1b, 0x2b1c, Pictographic),
+    (0x2b50, 0x2b50, Pictographic),
+    (0x2b55, 0x2b55, Pictographic),
+    (0x2cef, 0x2cf1, IndicExtend),
+    (0x2d7f, 0x2d7f, IndicExtend),
+    (0x2de0, 0x2dff, IndicExtend),
+    (0x302a, 0x302f, IndicExtend),
+    (0x3030, 0x3030, Pictographic),
+    (0x303d, 0x303d, Pictographic),
+    (0x3099, 0x309a, IndicExtend),
+    (0x3297, 0x3297, Pictographic),
+    (0x3299, 0x3299, Pictographic),
+    (0xa66f, 0xa672, IndicExtend),
+    (0xa674, 0xa67d, IndicExtend),
+    (0xa69e, 0xa69f, IndicExtend),
+    (0xa6f0, 0xa6f1, IndicExtend),
+    (0xa802, 0xa802, IndicExtend),
+    (0xa806, 0xa806, IndicExtend),
+    (0xa80b, 0xa80b, IndicExtend),
+    (0xa823, 0xa824, SpacingMark),
+    (0xa825, 0xa826, IndicExtend),
+    (0xa827, 0xa827, SpacingMark),
+    (0xa82c, 0xa82c, IndicExtend),
+    (0xa880, 0xa881, SpacingMark),
+    (0xa8b4, 0xa8c3, SpacingMark),
+    (0xa8c4, 0xa8c5, IndicExtend),
+    (0xa8e0, 0xa8f1, IndicExtend),
+    (0xa8ff, 0xa8ff, IndicExtend),
+    (0xa926, 0xa92d, IndicExtend),
+    (0xa947, 0xa951, IndicExtend),
+    (0xa952, 0xa952, SpacingMark),
+    (0xa953, 0xa953, IndicExtend),
+    (0xa960, 0xa97c, HangulL),
+    (0xa980, 0xa982, IndicExtend),
+    (0xa983, 0xa983, SpacingMark),
+    (0xa989, 0xa98b, IndicConsonant),
+    (0xa98f, 0xa9b2, IndicConsonant),
+    (0xa9b3, 0xa9b3, IndicExtend),
+    (0xa9b4, 0xa9b5, SpacingMark),
+    (0xa9b6, 0xa9b9, IndicExtend),
+    (0xa9ba, 0xa9bb, SpacingMark),
+    (0xa9bc, 0xa9bd, IndicExtend),
+    (0xa9be, 0xa9bf, SpacingMark),
+    (0xa9c0, 0xa9c0, IndicLinker),
+    (0xa9e0, 0xa9e4, IndicConsonant),
+    (0xa9e5, 0xa9e5, IndicExtend),
+    (0xa9e7, 0xa9ef, IndicConsonant),
+    (0xa9fa, 0xa9fe, IndicConsonant),
+    (0xaa29, 0xaa2e, IndicExtend),
+    (0xaa2f, 0xaa30, SpacingMark),
+    (0xaa31, 0xaa32, IndicExtend),
+    (0xaa33, 0xaa34, SpacingMark),
+    (0xaa35, 0xaa36, IndicExtend),
+    (0xaa43, 0xaa43, IndicExtend),
+    (0xaa4c, 0xaa4c, IndicExtend),
+    (0xaa4d, 0xaa4d, SpacingMark),
+    (0xaa60, 0xaa6f, IndicConsonant),
+    (0xaa71, 0xaa73, IndicConsonant),
+    (0xaa7a, 0xaa7a, IndicConsonant),
+    (0xaa7c, 0xaa7c, IndicExtend),
+    (0xaa7e, 0xaa7f, IndicConsonant),
+    (0xaab0, 0xaab0, IndicExtend),
+    (0xaab2, 0xaab4, IndicExtend),
+    (0xaab7, 0xaab8, IndicExtend),
+    (0xaabe, 0xaabf, IndicExtend),
+    (0xaac1, 0xaac1, IndicExtend),
+    (0xaae0, 0xaaea, IndicConsonant),
+    (0xaaeb, 0xaaeb, SpacingMark),
+    (0xaaec, 0xaaed, IndicExtend),
+    (0xaaee, 0xaaef, SpacingMark),
+    (0xaaf5, 0xaaf5, SpacingMark),
+    (0xaaf6, 0xaaf6, IndicLinker),
+    (0xabc0, 0xabda, IndicConsonant),
+    (0xabe3, 0xabe4, SpacingMark),
+    (0xabe5, 0xabe5, IndicExtend),
+    (0xabe6, 0xabe7, SpacingMark),
+    (0xabe8, 0xabe8, IndicExtend),
+    (0xabe9, 0xabea, SpacingMark),
+    (0xabec, 0xabec, SpacingMark),
+    (0xabed, 0xabed, IndicExtend),
+    (0xd7b0, 0xd7c6, HangulV),
+    (0xd7cb, 0xd7fb, HangulT),
+    (0xfb1e, 0xfb1e, IndicExtend),
+    (0xfe00, 0xfe0f, IndicExtend),
+    (0xfe20, 0xfe2f, IndicExtend),
+    (0xfeff, 0xfeff, Control),
+    (0xff9e, 0xff9f, IndicExtend),
+    (0xfff0, 0xfffb, Control),
+    (0x101fd, 0x101fd, IndicExtend),
+    (0x102e0, 0x102e0, IndicExtend),
+    (0x10376, 0x1037a, IndicExtend),
+    (0x10a00, 0x10a00, IndicConsonant),
+    (0x10a01, 0x10a03, IndicExtend),
+    (0x10a05, 0x10a06, IndicExtend),
+    (0x10a0c, 0x10a0f, IndicExtend),
+    (0x10a10, 0x10a13, IndicConsonant),
+    (0x10a15, 0x10a17, IndicConsonant),
+    (0x10a19, 0x10a35, IndicConsonant),
+    (0x10a38, 0x10a3a, IndicExtend),
+    (0x10a3f, 0x10a3f, IndicLinker),
+    (0x10ae5, 0x10ae6, IndicExtend),
+    (0x10d24, 0x10d27, IndicExtend),
+    (0x10d69, 0x10d6d, IndicExtend),
+    (0x10eab, 0x10eac, IndicExtend),
+    (0x10efa, 0x10eff, IndicExtend),
+    (0x10f46, 0x10f50, IndicExtend),
+    (0x10f82, 0x10f85, IndicExtend),
+    (0x11000, 0x11000, SpacingMark),
+    (0x11001, 0x11001, IndicExtend),
+    (0x11002, 0x11002, SpacingMark),
+    (0x11038, 0x11046, IndicExtend),
+    (0x11070, 0x11070, IndicExtend),
+    (0x11073, 0x11074, IndicExtend),
+    (0x1107f, 0x11081, IndicExtend),
+    (0x11082, 0x11082, SpacingMark),
+    (0x110b0, 0x110b2, SpacingMark),
+    (0x110b3, 0x110b6, IndicExtend),
+    (0x110b7, 0x110b8, SpacingMark),
+    (0x110b9, 0x110ba, IndicExtend),
+    (0x110bd, 0x110bd, Prepend),
+    (0x110c2, 0x110c2, IndicExtend),
+    (0x110cd, 0x110cd, Prepend),
+    (0x11100, 0x11102, IndicExtend),
+    (0x11103, 0x11126, IndicConsonant),
+    (0x11127, 0x1112b, IndicExtend),
+    (0x1112c, 0x1112c, SpacingMark),
+    (0x1112d, 0x11132, IndicExtend),
+    (0x11133, 0x11133, IndicLinker),
+    (0x11134, 0x11134, IndicExtend),
+    (0x11144, 0x11144, IndicConsonant),
+    (0x11145, 0x11146, SpacingMark),
+    (0x11147, 0x11147, IndicConsonant),
+    (0x11173, 0x11173, IndicExtend),
+    (0x11180, 0x11181, IndicExtend),
+    (0x11182, 0x11182, SpacingMark),
+    (0x111b3, 0x111b5, SpacingMark),
+    (0x111b6, 0x111be, IndicExtend),
+    (0x111bf, 0x111bf, SpacingMark),
+    (0x111c0, 0x111c0, IndicExtend),
+    (0x111c2, 0x111c3, Prepend),
+    (0x111c9, 0x111cc, IndicExtend),
+    (0x111ce, 0x111ce, SpacingMark),
+    (0x111cf, 0x111cf, IndicExtend),
+    (0x1122c, 0x1122e, SpacingMark),
+    (0x1122f, 0x11231, IndicExtend),
+    (0x11232, 0x11233, SpacingMark),
+    (0x11234, 0x11237, IndicExtend),
+    (0x1123e, 0x1123e, IndicExtend),
+    (0x11241, 0x11241, IndicExtend),
+    (0x112df, 0x112df, IndicExtend),
+    (0x112e0, 0x112e2, SpacingMark),
+    (0x112e3, 0x112ea, IndicExtend),
+    (0x11300, 0x11301, IndicExtend),
+    (0x11302, 0x11303, SpacingMark),
+    (0x1133b, 0x1133c, IndicExtend),
+    (0x1133e, 0x1133e, IndicExtend),
+    (0x1133f, 0x1133f, SpacingMark),
+    (0x11340, 0x11340, IndicExtend),
+    (0x11341, 0x11344, SpacingMark),
+    (0x11347, 0x11348, SpacingMark),
+    (0x1134b, 0x1134c, SpacingMark),
+    (0x1134d, 0x1134d, IndicExtend),
+    (0x11357, 0x11357, IndicExtend),
+    (0x11362, 0x11363, SpacingMark),
+    (0x11366, 0x1136c, IndicExtend),
+    (0x11370, 0x11374, IndicExtend),
+    (0x11380, 0x11389, IndicConsonant),
+    (0x1138b, 0x1138b, IndicConsonant),
+    (0x1138e, 0x1138e, IndicConsonant),
+    (0x11390, 0x113b5, IndicConsonant),
+    (0x113b8, 0x113b8, IndicExtend),
+    (0x113b9, 0x113ba, SpacingMark),
+    (0x113bb, 0x113c0, IndicExtend),
+    (0x113c2, 0x113c2, IndicExtend),
+    (0x113c5, 0x113c5, IndicExtend),
+    (0x113c7, 0x113c9, IndicExtend),
+    (0x113ca, 0x113ca, SpacingMark),
+    (0x113cc, 0x113cd, SpacingMark),
+    (0x113ce, 0x113cf, IndicExtend),
+    (0x113d0, 0x113d0, IndicLinker),
+    (0x113d1, 0x113d1, Prepend),
+    (0x113d2, 0x113d2, IndicExtend),
+    (0x113e1, 0x113e2, IndicExtend),
+    (0x11435, 0x11437, SpacingMark),
+    (0x11438, 0x1143f, IndicExtend),
+    (0x11440, 0x11441, SpacingMark),
+    (0x11442, 0x11444, IndicExtend),
+    (0x11445, 0x11445, SpacingMark),
+    (0x11446, 0x11446, IndicExtend),
+    (0x1145e, 0x1145e, IndicExtend),
+    (0x114b0, 0x114b0, IndicExtend),
+    (0x114b1, 0x114b2, SpacingMark),
+    (0x114b3, 0x114b8, IndicExtend),
+    (0x114b9, 0x114b9, SpacingMark),
+    (0x114ba, 0x114ba, IndicExtend),
+    (0x114bb, 0x114bc, SpacingMark),
+    (0x114bd, 0x114bd, IndicExtend),
+    (0x114be, 0x114be, SpacingMark),
+    (0x114bf, 0x114c0, IndicExtend),
+    (0x114c1, 0x114c1, SpacingMark),
+    (0x114c2, 0x114c3, IndicExtend),
+    (0x115af, 0x115af, IndicExtend),
+    (0x115b0, 0x115b1, SpacingMark),
+    (0x115b2, 0x115b5, IndicExtend),
+    (0x115b8, 0x115bb, SpacingMark),
+    (0x115bc, 0x115bd, IndicExtend),
+    (0x115be, 0x115be, SpacingMark),
+    (0x115bf, 0x115c0, IndicExtend),
+    (0x115dc, 0x115dd, IndicExtend),
+    (0x11630, 0x11632, SpacingMark),
+    (0x11633, 0x1163a, IndicExtend),
+    (0x1163b, 0x1163c, SpacingMark),
+    (0x1163d, 0x1163d, IndicExtend),
+    (0x1163e, 0x1163e, SpacingMark),
+    (0x1163f, 0x11640, IndicExtend),
+    (0x116ab, 0x116ab, IndicExtend),
+    (0x116ac, 0x116ac, SpacingMark),
+    (0x116ad, 0x116ad, IndicExtend),
+    (0x116ae, 0x116af, SpacingMark),
+    (0x116b0, 0x116b7, IndicExtend),
+    (0x1171d, 0x1171d, IndicExtend),
+    (0x1171e, 0x1171e, SpacingMark),
+    (0x1171f, 0x1171f, IndicExtend),
+    (0x11722, 0x11725, IndicExtend),
+    (0x11726, 0x11726, SpacingMark),
+    (0x11727, 0x1172b, IndicExtend),
+    (0x1182c, 0x1182e, SpacingMark),
+    (0x1182f, 0x11837, IndicExtend),
+    (0x11838, 0x11838, SpacingMark),
+    (0x11839, 0x1183a, IndicExtend),
+    (0x11900, 0x11906, IndicConsonant),
+    (0x11909, 0x11909, IndicConsonant),
+    (0x1190c, 0x11913, IndicConsonant),
+    (0x11915, 0x11916, IndicConsonant),
+    (0x11918, 0x1192f, IndicConsonant),
+    (0x11930, 0x11930, IndicExtend),
+    (0x11931, 0x11935, SpacingMark),
+    (0x11937, 0x11938, SpacingMark),
+    (0x1193b, 0x1193d, IndicExtend),
+    (0x1193e, 0x1193e, IndicLinker),
+    (0x1193f, 0x1193f, Prepend),
+    (0x11940, 0x11940, SpacingMark),
+    (0x11941, 0x11941, Prepend),
+    (0x11942, 0x11942, SpacingMark),
+    (0x11943, 0x11943, IndicExtend),
+    (0x119d1, 0x119d3, SpacingMark),
+    (0x119d4, 0x119d7, IndicExtend),
+    (0x119da, 0x119db, IndicExtend),
+    (0x119dc, 0x119df, SpacingMark),
+    (0x119e0, 0x119e0, IndicExtend),
+    (0x119e4, 0x119e4, SpacingMark),
+    (0x11a00, 0x11a00, IndicConsonant),
+    (0x11a01, 0x11a0a, IndicExtend),
+    (0x11a0b, 0x11a32, IndicConsonant),
+    (0x11a33, 0x11a38, IndicExtend),
+    (0x11a39, 0x11a39, SpacingMark),
+    (0x11a3b, 0x11a3e, IndicExtend),
+    (0x11a47, 0x11a47, IndicLinker),
+    (0x11a50, 0x11a50, IndicConsonant),
+    (0x11a51, 0x11a56, IndicExtend),
+    (0x11a57, 0x11a58, SpacingMark),
+    (0x11a59, 0x11a5b, IndicExtend),
+    (0x11a5c, 0x11a83, IndicConsonant),
+    (0x11a84, 0x11a89, Prepend),
+    (0x11a8a, 0x11a96, IndicExtend),
+    (0x11a97, 0x11a97, SpacingMark),
+    (0x11a98, 0x11a98, IndicExtend),
+    (0x11a99, 0x11a99, IndicLinker),
+    (0x11b60, 0x11b60, IndicExtend),
+    (0x11b61, 0x11b61, SpacingMark),
+    (0x11b62, 0x11b64, IndicExtend),
+    (0x11b65, 0x11b65, SpacingMark),
+    (0x11b66, 0x11b66, IndicExtend),
+    (0x11b67, 0x11b67, SpacingMark),
+    (0x11c2f, 0x11c2f, SpacingMark),
+    (0x11c30, 0x11c36, IndicExtend),
+    (0x11c38, 0x11c3d, IndicExtend),
+    (0x11c3e, 0x11c3e, SpacingMark),
+    (0x11c3f, 0x11c3f, IndicExtend),
+    (0x11c92, 0x11ca7, IndicExtend),
+    (0x11ca9, 0x11ca9, SpacingMark),
+    (0x11caa, 0x11cb0, IndicExtend),
+    (0x11cb1, 0x11cb1, SpacingMark),
+    (0x11cb2, 0x11cb3, IndicExtend),
+    (0x11cb4, 0x11cb4, SpacingMark),
+    (0x11cb5, 0x11cb6, IndicExtend),
+    (0x11d31, 0x11d36, IndicExtend),
+    (0x11d3a, 0x11d3a, IndicExtend),
+    (0x11d3c, 0x11d3d, IndicExtend),
+    (0x11d3f, 0x11d45, IndicExtend),
+    (0x11d46, 0x11d46, Prepend),
+    (0x11d47, 0x11d47, IndicExtend),
+    (0x11d8a, 0x11d8e, SpacingMark),
+    (0x11d90, 0x11d91, IndicExtend),
+    (0x11d93, 0x11d94, SpacingMark),
+    (0x11d95, 0x11d95, IndicExtend),
+    (0x11d96, 0x11d96, SpacingMark),
+    (0x11d97, 0x11d97, IndicExtend),
+    (0x11ef3, 0x11ef4, IndicExtend),
+    (0x11ef5, 0x11ef6, SpacingMark),
+    (0x11f00, 0x11f01, IndicExtend),
+    (0x11f02, 0x11f02, Prepend),
+    (0x11f03, 0x11f03, SpacingMark),
+    (0x11f04, 0x11f10, IndicConsonant),
+    (0x11f12, 0x11f33, IndicConsonant),
+    (0x11f34, 0x11f35, SpacingMark),
+    (0x11f36, 0x11f3a, IndicExtend),
+    (0x11f3e, 0x11f3f, SpacingMark),
+    (0x11f40, 0x11f41, IndicExtend),
+    (0x11f42, 0x11f42, IndicLinker),
+    (0x11f5a, 0x11f5a, IndicExtend),
+    (0x13430, 0x1343f, Control),
+    (0x13440, 0x13440, IndicExtend),
+    (0x13447, 0x13455, IndicExtend),
+    (0x1611e, 0x16129, IndicExtend),
+    (0x1612a, 0x1612c, SpacingMark),
+    (0x1612d, 0x1612f, IndicExtend),
+    (0x16af0, 0x16af4, IndicExtend),
+    (0x16b30, 0x16b36, IndicExtend),
+    (0x16d63, 0x16d63, HangulV),
+    (0x16d67, 0x16d6a, HangulV),
+    (0x16f4f, 0x16f4f, IndicExtend),
+    (0x16f51, 0x16f87, SpacingMark),
+    (0x16f8f, 0x16f92, IndicExtend),
+    (0x16fe4, 0x16fe4, IndicExtend),
+    (0x16ff0, 0x16ff1, IndicExtend),
+    (0x1bc9d, 0x1bc9e, IndicExtend),
+    (0x1bca0, 0x1bca3, Control),
+    (0x1cf00, 0x1cf2d, IndicExtend),
+    (0x1cf30, 0x1cf46, IndicExtend),
+    (0x1d165, 0x1d169, IndicExtend),
+    (0x1d16d, 0x1d172, IndicExtend),
+    (0x1d173, 0x1d17a, Control),
+    (0x1d17b, 0x1d182, IndicExtend),
+    (0x1d185, 0x1d18b, IndicExtend),
+    (0x1d1aa, 0x1d1ad, IndicExtend),
+    (0x1d242, 0x1d244, IndicExtend),
+    (0x1da00, 0x1da36, IndicExtend),
+    (0x1da3b, 0x1da6c, IndicExtend),
+    (0x1da75, 0x1da75, IndicExtend),
+    (0x1da84, 0x1da84, IndicExtend),
+    (0x1da9b, 0x1da9f, IndicExtend),
+    (0x1daa1, 0x1daaf, IndicExtend),
+    (0x1e000, 0x1e006, IndicExtend),
+    (0x1e008, 0x1e018, IndicExtend),
+    (0x1e01b, 0x1e021, IndicExtend),
+    (0x1e023, 0x1e024, IndicExtend),
+    (0x1e026, 0x1e02a, IndicExtend),
+    (0x1e08f, 0x1e08f, IndicExtend),
+    (0x1e130, 0x1e136, IndicExtend),
+    (0x1e2ae, 0x1e2ae, IndicExtend),
+    (0x1e2ec, 0x1e2ef, IndicExtend),
+    (0x1e4ec, 0x1e4ef, IndicExtend),
+    (0x1e5ee, 0x1e5ef, IndicExtend),
+    (0x1e6e3, 0x1e6e3, IndicExtend),
+    (0x1e6e6, 0x1e6e6, IndicExtend),
+    (0x1e6ee, 0x1e6ef, IndicExtend),
+    (0x1e6f5, 0x1e6f5, IndicExtend),
+    (0x1e8d0, 0x1e8d6, IndicExtend),
+    (0x1e944, 0x1e94a, IndicExtend),
+    (0x1f004, 0x1f004, Pictographic),
+    (0x1f02c, 0x1f02f, Pictographic),
+    (0x1f094, 0x1f09f, Pictographic),
+    (0x1f0af, 0x1f0b0, Pictographic),
+    (0x1f0c0, 0x1f0c0, Pictographic),
+    (0x1f0cf, 0x1f0d0, Pictographic),
+    (0x1f0f6, 0x1f0ff, Pictographic),
+    (0x1f170, 0x1f171, Pictographic),
+    (0x1f17e, 0x1f17f, Pictographic),
+    (0x1f18e, 0x1f18e, Pictographic),
+    (0x1f191, 0x1f19a, Pictographic),
+    (0x1f1ae, 0x1f1e5, Pictographic),
+    (0x1f1e6, 0x1f1ff, RegionalIndicator),
+    (0x1f201, 0x1f20f, Pictographic),
+    (0x1f21a, 0x1f21a, Pictographic),
+    (0x1f22f, 0x1f22f, Pictographic),
+    (0x1f232, 0x1f23a, Pictographic),
+    (0x1f23c, 0x1f23f, Pictographic),
+    (0x1f249, 0x1f25f, Pictographic),
+    (0x1f266, 0x1f321, Pictographic),
+    (0x1f324, 0x1f393, Pictographic),
+    (0x1f396, 0x1f397, Pictographic),
+    (0x1f399, 0x1f39b, Pictographic),
+    (0x1f39e, 0x1f3f0, Pictographic),
+    (0x1f3f3, 0x1f3f5, Pictographic),
+    (0x1f3f7, 0x1f3fa, Pictographic),
+    (0x1f3fb, 0x1f3ff, IndicExtend),
+    (0x1f400, 0x1f4fd, Pictographic),
+    (0x1f4ff, 0x1f53d, Pictographic),
+    (0x1f549, 0x1f54e, Pictographic),
+    (0x1f550, 0x1f567, Pictographic),
+    (0x1f56f, 0x1f570, Pictographic),
+    (0x1f573, 0x1f57a, Pictographic),
+    (0x1f587, 0x1f587, Pictographic),
+    (0x1f58a, 0x1f58d, Pictographic),
+    (0x1f590, 0x1f590, Pictographic),
+    (0x1f595, 0x1f596, Pictographic),
+    (0x1f5a4, 0x1f5a5, Pictographic),
+    (0x1f5a8, 0x1f5a8, Pictographic),
+    (0x1f5b1, 0x1f5b2, Pictographic),
+    (0x1f5bc, 0x1f5bc, Pictographic),
+    (0x1f5c2, 0x1f5c4, Pictographic),
+    (0x1f5d1, 0x1f5d3, Pictographic),
+    (0x1f5dc, 0x1f5de, Pictographic),
+    (0x1f5e1, 0x1f5e1, Pictographic),
+    (0x1f5e3, 0x1f5e3, Pictographic),
+    (0x1f5e8, 0x1f5e8, Pictographic),
+    (0x1f5ef, 0x1f5ef, Pictographic),
+    (0x1f5f3, 0x1f5f3, Pictographic),
+    (0x1f5fa, 0x1f64f, Pictographic),
+    (0x1f680, 0x1f6c5, Pictographic),
+    (0x1f6cb, 0x1f6d2, Pictographic),
+    (0x1f6d5, 0x1f6e5, Pictographic),
+    (0x1f6e9, 0x1f6e9, Pictographic),
+    (0x1f6eb, 0x1f6f0, Pictographic),
+    (0x1f6f3, 0x1f6ff, Pictographic),
+    (0x1f7da, 0x1f7ff, Pictographic),
+    (0x1f80c, 0x1f80f, Pictographic),
+    (0x1f848, 0x1f84f, Pictographic),
+    (0x1f85a, 0x1f85f, Pictographic),
+    (0x1f888, 0x1f88f, Pictographic),
+    (0x1f8ae, 0x1f8af, Pictographic),
+    (0x1f8bc, 0x1f8bf, Pictographic),
+    (0x1f8c2, 0x1f8cf, Pictographic),
+    (0x1f8d9, 0x1f8ff, Pictographic),
+    (0x1f90c, 0x1f93a, Pictographic),
+    (0x1f93c, 0x1f945, Pictographic),
+    (0x1f947, 0x1f9ff, Pictographic),
+    (0x1fa58, 0x1fa5f, Pictographic),
+    (0x1fa6e, 0x1faff, Pictographic),
+    (0x1fc00, 0x1fffd, Pictographic),
+    (0xe0000, 0xe001f, Control),
+    (0xe0020, 0xe007f, IndicExtend),
+    (0xe0080, 0xe00ff, Control),
+    (0xe0100, 0xe01ef, IndicExtend),
+    (0xe01f0, 0xe0fff, Control),
+];
@@ -0,0 +1,552 @@
+// Group Box Widget
+// Extensible widget for the widget library - handles a titled frame around a group of widgets.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::shape::{get_dash_rects, BorderStyle};
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the new collapsed state of a `GroupBoxWidget`.
+pub type CollapsedCallback = Box<dyn FnMut(bool)>;
+
+/// The smallest distance between the title of a `GroupBoxWidget` and its left or right edge, in
+/// pixels.
+pub const GROUP_BOX_TITLE_INDENT: i32 = 10;
+
+/// The space that is left between the title of a `GroupBoxWidget` and its top border, on
+/// either side of the title, in pixels.
+const GROUP_BOX_TITLE_GAP: i32 = 4;
+
+/// The width of the arrow in front of the title of a collapsible `GroupBoxWidget`, in pixels.
+const GROUP_BOX_ARROW_SPACE: i32 = 14;
+
+/// Returns the left edge of a title that is `title_w` pixels wide, in the top border of a
+/// `GroupBoxWidget` that is `box_w` pixels wide, justified by `justify`.  The title is kept at
+/// least `GROUP_BOX_TITLE_INDENT` pixels from the left edge.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::group_box_widget::*;
+/// # use pushrod::widget::text_widget::*;
+/// # fn main() {
+///    assert_eq!(get_group_box_title_x(200, 50, TextJustify::Left), 10);
+///    assert_eq!(get_group_box_title_x(200, 50, TextJustify::Center), 75);
+///    assert_eq!(get_group_box_title_x(200, 50, TextJustify::Right), 140);
+///
+///    // A title that is too wide for the box starts at the indent.
+///    assert_eq!(get_group_box_title_x(60, 80, TextJustify::Center), 10);
+/// # }
+/// ```
+pub fn get_group_box_title_x(box_w: i32, title_w: i32, justify: TextJustify) -> i32 {
+    let x = match justify {
+        TextJustify::Left => GROUP_BOX_TITLE_INDENT,
+        TextJustify::Center => (box_w - title_w) / 2,
+        TextJustify::Right => box_w - GROUP_BOX_TITLE_INDENT - title_w,
+    };
+
+    x.max(GROUP_BOX_TITLE_INDENT)
+}
+
+/// This is the `GroupBoxWidget`, which draws a border around a group of related `Widget`s, with
+/// a title that interrupts the top border.  The `Widget`s are added as its children, with
+/// `WidgetStore::add_widget_to_parent`, and are positioned relative to it.  `get_content_rect`
+/// returns the area inside of the border, the title, and the padding, in which the children
+/// should be placed.
+///
+/// A group box is only a frame, so mouse events pass through it, to its children, or to
+/// whatever is underneath its empty interior.  The border is drawn in the `BorderColor` and
+/// `BorderWidth` config keys, which default to the border color of the current `Theme`, and a
+/// thickness of 1.  The background is only filled once a color has been set with `set_color`.
+///
+/// A collapsible group box draws an arrow in front of its title, and clicking the title
+/// collapses it, hiding its children and shrinking it to the height of the title, or expands
+/// it again.  Only the title of a collapsible group box receives mouse events.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::checkbox_widget::*;
+/// # use pushrod::widget::group_box_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Group Box", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut group_box =
+///        GroupBoxWidget::new(pushrod.get_factory(), font_name.clone(), "Options".to_string());
+///
+///    group_box.set_origin(20, 20);
+///    group_box.set_size(200, 100);
+///    group_box.set_collapsible(true);
+///
+///    let [x, y, _, _] = group_box.get_content_rect();
+///    let mut checkbox =
+///        CheckboxWidget::new(pushrod.get_factory(), font_name, "Wrap lines".to_string());
+///
+///    checkbox.set_origin(x, y);
+///    checkbox.set_size(160, 24);
+///
+///    let group_id = pushrod.add_widget("Options", Box::new(group_box));
+///
+///    pushrod
+///        .widget_store
+///        .borrow_mut()
+///        .add_widget_to_parent("WrapLines", Box::new(checkbox), group_id);
+/// # }
+/// ```
+pub struct GroupBoxWidget {
+    config: Configurable,
+    text_widget: Box<TextWidget>,
+    justify: TextJustify,
+    collapsible: bool,
+    expanded_h: i32,
+    title_hovered: bool,
+    on_collapsed: Option<CollapsedCallback>,
+}
+
+/// Implementation of the constructor for the `GroupBoxWidget`.
+impl GroupBoxWidget {
+    /// Creates a new group box with a `title`, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  Panics if the font cannot be
+    /// loaded; use `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String, title: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, title)
+    }
+
+    /// Creates a new group box with a `title` that is drawn in a font from the font cache.  The
+    /// title is left-justified, and the group box is not collapsible.
+    pub fn from_font(font: SharedGlyphs, title: String) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: Box::new(TextWidget::from_font(
+                font,
+                title,
+                font_size,
+                TextJustify::Left,
+            )),
+            justify: TextJustify::Left,
+            collapsible: false,
+            expanded_h: 0,
+            title_hovered: false,
+            on_collapsed: None,
+        };
+
+        widget.set_pass_through(true);
+        widget
+    }
+
+    /// Sets the closure that is called with the new collapsed state when it changes.
+    pub fn on_collapsed(&mut self, callback: CollapsedCallback) {
+        self.on_collapsed = Some(callback);
+    }
+
+    /// Sets the title.  Invalidates the widget afterward.
+    pub fn set_title(&mut self, title: &str) {
+        self.text_widget.set_text(title);
+        self.invalidate();
+    }
+
+    /// Retrieves the title.
+    pub fn get_title(&self) -> &str {
+        self.text_widget.get_text()
+    }
+
+    /// Sets where the title is drawn in the top border.  Invalidates the widget afterward.
+    pub fn set_title_justify(&mut self, justify: TextJustify) {
+        self.justify = justify;
+        self.invalidate();
+    }
+
+    /// Retrieves where the title is drawn in the top border.  Defaults to `TextJustify::Left`.
+    pub fn get_title_justify(&self) -> TextJustify {
+        self.justify
+    }
+
+    /// Sets the font size of the title.  Invalidates the widget afterward.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.text_widget.set_font_size(font_size);
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the title.  Defaults to the font size of the current `Theme`.
+    pub fn get_font_size(&self) -> u32 {
+        self.text_widget.get_font_size()
+    }
+
+    /// Sets the color of the title for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.text_widget.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the title for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Sets the border color for this widget.
+    pub fn set_border_color(&mut self, color: types::Color) {
+        self.config().set(BorderColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the border color of this widget.
+    /// Defaults to the border color of the current `Theme` if not set.
+    pub fn get_border_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(BorderColor(get_current_theme().border_color))
+            .0
+    }
+
+    /// Sets the thickness of the border for this widget.
+    pub fn set_border_thickness(&mut self, thickness: u8) {
+        self.config().set(BorderWidth(thickness));
+        self.invalidate();
+    }
+
+    /// Retrieves the border thickness of this widget.
+    /// Defaults to 1 if not set.
+    pub fn get_border_thickness(&mut self) -> u8 {
+        self.config().get_or(BorderWidth(1)).0
+    }
+
+    /// Helper function that sets both the color of the border and the thickness at the same time.
+    pub fn set_border(&mut self, color: types::Color, thickness: u8) {
+        self.set_border_color(color);
+        self.set_border_thickness(thickness);
+    }
+
+    /// Sets the style in which the border is stroked: solid, dashed, or dotted.
+    pub fn set_border_style(&mut self, style: BorderStyle) {
+        self.config().set(BorderStroke(style));
+        self.invalidate();
+    }
+
+    /// Retrieves the style in which the border is stroked.
+    /// Defaults to `BorderStyle::Solid` if not set.
+    pub fn get_border_style(&mut self) -> BorderStyle {
+        self.config().get_or(BorderStroke(BorderStyle::Solid)).0
+    }
+
+    /// Makes the group box collapsible by clicking its title, or not.  A group box that is no
+    /// longer collapsible is expanded, and lets all mouse events pass through it again.
+    pub fn set_collapsible(&mut self, collapsible: bool) {
+        self.collapsible = collapsible;
+        self.set_pass_through(!collapsible);
+
+        if !collapsible {
+            self.set_collapsed(false);
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether the group box can be collapsed by clicking its title.  Defaults to
+    /// `false`.
+    pub fn is_collapsible(&self) -> bool {
+        self.collapsible
+    }
+
+    /// Collapses the group box, hiding its children and shrinking it to the height of its
+    /// title, or expands it to the height that it had before.  The children are hidden with the
+    /// `Collapsed` config key, so the children that were hidden before the group box was
+    /// collapsed stay hidden when it is expanded.  If the state changed, the `on_collapsed`
+    /// callback is called, and the widget is invalidated.
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        if collapsed == self.is_collapsed() {
+            return;
+        }
+
+        let size = self.get_size();
+
+        if collapsed {
+            let title_h = self.get_title_height();
+
+            self.expanded_h = size.h;
+            self.config().set(Collapsed);
+            self.set_size(size.w, title_h);
+        } else {
+            let expanded_h = self.expanded_h;
+
+            self.config().remove::<Collapsed>();
+            self.set_size(size.w, expanded_h);
+        }
+
+        if let Some(callback) = &mut self.on_collapsed {
+            callback(collapsed);
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether the group box is collapsed.  Defaults to `false`.
+    pub fn is_collapsed(&mut self) -> bool {
+        self.config().contains_key::<Collapsed>()
+    }
+
+    /// Returns the height of the band at the top of the widget in which the title is drawn.
+    fn get_title_height(&self) -> i32 {
+        (f64::from(self.text_widget.get_font_size()) * DEFAULT_LINE_SPACING).ceil() as i32
+    }
+
+    /// Returns the left edge and the width of the title, including the arrow of a collapsible
+    /// group box.
+    fn get_title_bounds(&mut self) -> (i32, i32) {
+        let arrow_w = if self.collapsible {
+            GROUP_BOX_ARROW_SPACE
+        } else {
+            0
+        };
+        let box_w = self.get_size().w;
+        let title_w = (self.text_widget.get_text_width() + arrow_w)
+            .min(box_w - GROUP_BOX_TITLE_INDENT * 2)
+            .max(0);
+
+        (get_group_box_title_x(box_w, title_w, self.justify), title_w)
+    }
+
+    /// Indicates whether `point` is over the title of the widget.
+    fn is_title_at(&mut self, point: &Point) -> bool {
+        let (title_x, title_w) = self.get_title_bounds();
+
+        point.x >= title_x - GROUP_BOX_TITLE_GAP
+            && point.x < title_x + title_w + GROUP_BOX_TITLE_GAP
+            && point.y >= 0
+            && point.y < self.get_title_height()
+    }
+}
+
+/// Implementation of the `GroupBoxWidget` object with the `Widget` traits implemented.  The
+/// title is drawn by a `TextWidget`.
+impl Widget for GroupBoxWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Group
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.get_title().to_string()
+    }
+
+    /// Retrieves the rectangle inside of which the children should be placed: the size of the
+    /// widget, inset by the thickness of the border, and the height of the title at the top,
+    /// and then by the padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+        let border = self.get_border_thickness() as i32;
+        let title_h = self.get_title_height().max(border);
+        let padding = self.get_padding();
+
+        make_insets(
+            title_h + padding.top,
+            border + padding.right,
+            border + padding.bottom,
+            border + padding.left,
+        )
+        .inset_size(&size)
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  A collapsed group box
+    /// is as high as its title.  Otherwise, this defaults to the current size of the widget if
+    /// not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        let mut preferred_size = match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => self.get_size(),
+        };
+
+        if self.is_collapsed() {
+            preferred_size.h = self.get_title_height();
+        }
+
+        preferred_size
+    }
+
+    /// Enables or disables this widget.  The title of a disabled group box is drawn in the
+    /// disabled color of the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Shows a pointing hand over the title of a collapsible group box.
+    fn get_cursor(&mut self) -> CursorStyle {
+        if self.collapsible && self.title_hovered {
+            CursorStyle::Hand
+        } else {
+            self.config()
+                .get::<Cursor>()
+                .map(|cursor| cursor.0)
+                .unwrap_or(CursorStyle::Default)
+        }
+    }
+
+    /// Passes mouse button presses that are not on the title, and the mouse wheel, on to the
+    /// widget underneath.  All other events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseButtonDown { ref point, .. } if !self.is_title_at(point) => {
+                (EventResult::NotHandled, None)
+            }
+
+            CallbackEvent::MouseScrolled { .. } => (EventResult::NotHandled, None),
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                self.title_hovered = self.is_title_at(&point);
+            }
+
+            CallbackEvent::MouseExited { .. } => self.title_hovered = false,
+
+            CallbackEvent::MouseClicked {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } if self.collapsible && self.is_title_at(&point) => {
+                let collapsed = self.is_collapsed();
+
+                self.set_collapsed(!collapsed);
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the title, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the title, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, if a color has been set, the border, with a gap in the top border
+    /// for the title, and then the title, with the arrow of a collapsible group box in front of
+    /// it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let border = f64::from(self.get_border_thickness());
+        let title_h = self.get_title_height();
+        let top = (f64::from(title_h) / 2.0).floor();
+        let (title_x, title_w) = self.get_title_bounds();
+        let (gap_start, gap_end) = if title_w > 0 {
+            (
+                f64::from(title_x - GROUP_BOX_TITLE_GAP),
+                f64::from(title_x + title_w + GROUP_BOX_TITLE_GAP),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        if let Some(color) = self.config().get::<MainColor>().map(|color| color.0) {
+            Rectangle::new(apply_opacity(color)).draw([0.0, 0.0, w, h], clip, c.transform, g);
+        }
+
+        let border_color = Rectangle::new(apply_opacity(self.get_border_color()));
+        let style = self.get_border_style();
+        let edges = [
+            ([0.0, top, gap_start, border], true),
+            ([gap_end, top, w - gap_end, border], true),
+            ([0.0, top, border, h - top], false),
+            ([w - border, top, border, h - top], false),
+            ([0.0, h - border, w, border], true),
+        ];
+
+        for (edge, horizontal) in &edges {
+            for rect in get_dash_rects(*edge, *horizontal, style) {
+                border_color.draw(rect, clip, c.transform, g);
+            }
+        }
+
+        let text_color = if self.is_disabled() {
+            theme.disabled_color
+        } else {
+            self.get_text_color()
+        };
+        let mut text_x = title_x;
+
+        if self.collapsible {
+            let (arrow_x, arrow_y) = (f64::from(title_x) + 4.0, f64::from(title_h) / 2.0);
+            let points = if self.is_collapsed() {
+                [
+                    [arrow_x, arrow_y - 4.0],
+                    [arrow_x + 4.0, arrow_y],
+                    [arrow_x, arrow_y + 4.0],
+                ]
+            } else {
+                [
+                    [arrow_x - 2.0, arrow_y - 2.0],
+                    [arrow_x + 6.0, arrow_y - 2.0],
+                    [arrow_x + 2.0, arrow_y + 2.0],
+                ]
+            };
+
+            Polygon::new(apply_opacity(text_color)).draw(&points, clip, c.transform, g);
+            text_x += GROUP_BOX_ARROW_SPACE;
+        }
+
+        self.text_widget
+            .set_size((title_x + title_w - text_x).max(0), title_h);
+        self.text_widget.set_ellipsize(true);
+        self.text_widget.set_text_color(text_color);
+        self.text_widget
+            .draw(c.trans(f64::from(text_x), 0.0), g, clip);
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
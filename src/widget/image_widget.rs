@@ -13,49 +13,372 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::{Path, PathBuf};
+
+use ::image::RgbaImage;
 use piston_window::*;
 
+use crate::core::accessibility::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
 use crate::widget::config::*;
+use crate::widget::theme::*;
 use crate::widget::widget::*;
 
-/// This is the `ImageWidget`, which draws an image on the screen.  The image is loaded into
-/// heap memory (using a `Box`).  This way, larger image objects can be loaded.
+/// This `enum` specifies how an `ImageWidget` fits its image into its bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageScaling {
+    /// The image is drawn at its own size from the upper left-hand corner of the widget, and is
+    /// clipped to the bounds of the widget.
+    Original,
+
+    /// The image is stretched to fill the bounds of the widget.
+    Stretch,
+
+    /// The image is scaled to the largest size that fits into the widget without changing its
+    /// aspect ratio, and is centered, leaving empty space on two of its sides.
+    Fit,
+}
+
+/// This is the `ImageWidget`, which draws an image on the screen, either from a file, or from
+/// the contents of an image file in memory, in `png`, `jpg`, or `gif` format.  The image is
+/// decoded when it is first needed, and the texture for it is only created the first time the
+/// widget is drawn, when the window exists.  The image is drawn according to its
+/// `ImageScaling`, which stretches it to the size of the widget by default.
+///
+/// An image that cannot be loaded does not panic: the widget draws a crossed out box in its
+/// place instead, and `get_error` returns the reason.  Use `from_path` or `from_bytes` to find
+/// out whether the image can be loaded when the widget is created.
+///
+/// On high density displays, a higher resolution version of the image is used if one exists
+/// next to it, named after the scale factor: `rust-512x512@2x.jpg` is used instead of
+/// `rust-512x512.jpg` on a display with a scale factor of 2.  The image is always drawn at its
+/// size in points.
 ///
 /// Example usage:
-/// IN PROGRESS
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::image_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Image", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut image_widget = ImageWidget::from_path(pushrod.get_factory(), "rust-512x512.jpg")
+///        .unwrap_or_else(|error| panic!("{}", error));
+///
+///    image_widget.set_origin(20, 20);
+///    image_widget.set_size(360, 260);
+///    image_widget.set_scaling(ImageScaling::Fit);
+///    pushrod.add_widget("Image", Box::new(image_widget));
+/// # }
+/// ```
 pub struct ImageWidget {
     config: Configurable,
-    image: Box<G2dTexture>,
+    factory: GfxFactory,
+    image_name: Option<String>,
+    pixels: Option<RgbaImage>,
+    image: Option<G2dTexture>,
     image_size: crate::core::point::Size,
+    image_scale: f64,
+    scale_factor: f64,
+    scaling: ImageScaling,
+    error: Option<PushrodError>,
+}
+
+/// Returns the name of the version of an image that is meant for a display with the specified
+/// scale factor, such as `icon@2x.png` for `icon.png` at a scale factor of 2, or `None` if the
+/// normal version of the image should be used.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::image_widget::*;
+/// # fn main() {
+///    assert_eq!(get_scaled_image_name("icon.png", 1.0), None);
+///    assert_eq!(get_scaled_image_name("icon.png", 2.0), Some("icon@2x.png".to_string()));
+///    assert_eq!(get_scaled_image_name("icon.png", 1.75), Some("icon@2x.png".to_string()));
+///    assert_eq!(get_scaled_image_name("icon", 3.0), Some("icon@3x".to_string()));
+/// # }
+/// ```
+pub fn get_scaled_image_name(image_name: &str, scale_factor: f64) -> Option<String> {
+    let scale = scale_factor.round() as u32;
+
+    if scale < 2 {
+        return None;
+    }
+
+    Some(match image_name.rfind('.') {
+        Some(pos) => format!("{}@{}x{}", &image_name[..pos], scale, &image_name[pos..]),
+        None => format!("{}@{}x", image_name, scale),
+    })
+}
+
+/// Returns the rectangle `[x, y, w, h]` in which an image of `image_size` points is drawn inside
+/// of bounds of `size`, relative to the upper left-hand corner of the bounds.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::Size;
+/// # use pushrod::widget::image_widget::*;
+/// # fn main() {
+///    let image_size = Size { w: 200, h: 100 };
+///    let size = Size { w: 100, h: 100 };
+///
+///    let rect = |scaling| get_image_rect(scaling, &image_size, &size);
+///
+///    assert_eq!(rect(ImageScaling::Original), [0.0, 0.0, 200.0, 100.0]);
+///    assert_eq!(rect(ImageScaling::Stretch), [0.0, 0.0, 100.0, 100.0]);
+///    assert_eq!(rect(ImageScaling::Fit), [0.0, 25.0, 100.0, 50.0]);
+/// # }
+/// ```
+pub fn get_image_rect(
+    scaling: ImageScaling,
+    image_size: &crate::core::point::Size,
+    size: &crate::core::point::Size,
+) -> [f64; 4] {
+    let (image_w, image_h) = (f64::from(image_size.w), f64::from(image_size.h));
+    let (w, h) = (f64::from(size.w), f64::from(size.h));
+
+    match scaling {
+        ImageScaling::Original => [0.0, 0.0, image_w, image_h],
+        ImageScaling::Stretch => [0.0, 0.0, w, h],
+        ImageScaling::Fit => {
+            if image_w <= 0.0 || image_h <= 0.0 {
+                return [0.0, 0.0, 0.0, 0.0];
+            }
+
+            let scale = (w / image_w).min(h / image_h);
+            let (fit_w, fit_h) = (image_w * scale, image_h * scale);
+
+            [(w - fit_w) / 2.0, (h - fit_h) / 2.0, fit_w, fit_h]
+        }
+    }
 }
 
 /// Implementation of the constructor for the `ImageWidget`.  Creates a new image object to be
-/// displayed on the screen, given the image filename.
+/// displayed on the screen, given the image filename or contents.
 impl ImageWidget {
     /// Creates a new `ImageWidget` object, requiring the current `PistonWindow`'s factory object
-    /// (which can be cloned), and the name of the image to load.  The image should be in the
-    /// project's local `assets` directory at the top level.
+    /// (which can be cloned), and the name of the image to load: either the path to an image
+    /// file, or the filename of an image in the project's local `assets` directory at the top
+    /// level.  The image is loaded when the widget is first drawn.
     pub fn new(factory: &mut GfxFactory, image_name: String) -> Self {
-        let assets = find_folder::Search::ParentsThenKids(3, 3)
-            .for_folder("assets")
-            .unwrap();
-        let texture = Texture::from_path(
-            factory,
-            &assets.join(image_name),
-            Flip::None,
-            &TextureSettings::new(),
-        )
-        .unwrap();
+        let mut widget = Self::empty(factory);
+
+        widget.image_name = Some(image_name);
+        widget
+    }
+
+    /// Creates a new `ImageWidget` object that draws the image `image_name`, as with `new`,
+    /// loading the image right away.  Returns a `PushrodError::ResourceLoading` if the image
+    /// cannot be found or decoded.
+    pub fn from_path(factory: &mut GfxFactory, image_name: &str) -> Result<Self, PushrodError> {
+        let mut widget = Self::new(factory, image_name.to_string());
+
+        widget.decode_image()?;
+        Ok(widget)
+    }
+
+    /// Creates a new `ImageWidget` object that draws an image from the contents of an image
+    /// file, such as an image embedded in the application with `include_bytes!`.  Returns a
+    /// `PushrodError::ResourceLoading` if the image cannot be decoded.
+    pub fn from_bytes(factory: &mut GfxFactory, bytes: &[u8]) -> Result<Self, PushrodError> {
+        let mut widget = Self::empty(factory);
+
+        widget.set_image_bytes(bytes)?;
+        Ok(widget)
+    }
 
+    fn empty(factory: &mut GfxFactory) -> Self {
         Self {
             config: Configurable::new(),
-            image: Box::new(texture.clone()),
-            image_size: crate::core::point::Size {
-                w: texture.clone().get_size().0 as i32,
-                h: texture.clone().get_size().1 as i32,
+            factory: factory.clone(),
+            image_name: None,
+            pixels: None,
+            image: None,
+            image_size: crate::core::point::Size { w: 0, h: 0 },
+            image_scale: 1.0,
+            scale_factor: 1.0,
+            scaling: ImageScaling::Stretch,
+            error: None,
+        }
+    }
+
+    /// Changes the image to `image_name`, which is loaded the next time the widget is drawn.
+    pub fn set_image_name(&mut self, image_name: String) {
+        self.image_name = Some(image_name);
+        self.reset_image();
+        self.invalidate();
+    }
+
+    /// Retrieves the name of the image, or `None` if the image was loaded from memory.
+    pub fn get_image_name(&self) -> Option<&str> {
+        self.image_name.as_deref()
+    }
+
+    /// Changes the image to one decoded from the contents of an image file.  Returns an error,
+    /// and keeps the current image, if the image cannot be decoded.
+    pub fn set_image_bytes(&mut self, bytes: &[u8]) -> Result<(), PushrodError> {
+        let pixels = ::image::load_from_memory(bytes)
+            .map_err(decode_error)?
+            .to_rgba();
+
+        self.image_name = None;
+        self.reset_image();
+        self.image_size = crate::core::point::Size {
+            w: pixels.width() as i32,
+            h: pixels.height() as i32,
+        };
+        self.image_scale = 1.0;
+        self.pixels = Some(pixels);
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Sets how the image is fit into the bounds of the widget.
+    pub fn set_scaling(&mut self, scaling: ImageScaling) {
+        self.scaling = scaling;
+        self.invalidate();
+    }
+
+    /// Retrieves how the image is fit into the bounds of the widget.  Defaults to
+    /// `ImageScaling::Stretch`.
+    pub fn get_scaling(&self) -> ImageScaling {
+        self.scaling
+    }
+
+    /// Retrieves the size of the image in points, or a size of 0 if it has not been loaded yet.
+    pub fn get_image_size(&self) -> crate::core::point::Size {
+        crate::core::point::Size {
+            w: (f64::from(self.image_size.w) / self.image_scale).round() as i32,
+            h: (f64::from(self.image_size.h) / self.image_scale).round() as i32,
+        }
+    }
+
+    /// Retrieves the reason that the image could not be loaded, if it could not.  The error is
+    /// also reported to `Pushrod::on_error` when the image is first drawn.
+    pub fn get_error(&self) -> Option<&PushrodError> {
+        self.error.as_ref()
+    }
+
+    fn reset_image(&mut self) {
+        self.pixels = None;
+        self.image = None;
+        self.image_size = crate::core::point::Size { w: 0, h: 0 };
+        self.error = None;
+    }
+
+    /// Decodes the image file, picking the version of the image for the current scale factor
+    /// if there is one, if it has not been decoded or uploaded already.
+    fn decode_image(&mut self) -> Result<(), PushrodError> {
+        if self.pixels.is_some() || self.image.is_some() {
+            return Ok(());
+        }
+
+        let image_name = match &self.image_name {
+            Some(image_name) => image_name.clone(),
+            None => return Err(no_image_error()),
+        };
+        let path = find_image(&image_name)?;
+        let (path, image_scale) = match get_scaled_image_name(&image_name, self.scale_factor) {
+            Some(scaled_name) => match find_image(&scaled_name) {
+                Ok(scaled_path) if scaled_path.exists() => (scaled_path, self.scale_factor.round()),
+                _ => (path, 1.0),
             },
+            None => (path, 1.0),
+        };
+        let pixels = ::image::open(&path)
+            .map_err(|error| PushrodError::ResourceLoading {
+                name: path.display().to_string(),
+                reason: error.to_string(),
+            })?
+            .to_rgba();
+
+        self.image_size = crate::core::point::Size {
+            w: pixels.width() as i32,
+            h: pixels.height() as i32,
+        };
+        self.image_scale = image_scale;
+        self.pixels = Some(pixels);
+        Ok(())
+    }
+
+    /// Creates the texture for the image the first time it is needed, remembering the error if
+    /// the image cannot be loaded, so that it is only tried once.
+    fn load_texture(&mut self) {
+        if self.image.is_some() || self.error.is_some() {
+            return;
         }
+
+        let result = self.decode_image().and_then(|_| match self.pixels.take() {
+            Some(pixels) => {
+                Texture::from_image(&mut self.factory, &pixels, &TextureSettings::new()).map_err(
+                    |error| {
+                        PushrodError::Rendering(format!(
+                            "cannot create a texture for the image: {:?}",
+                            error
+                        ))
+                    },
+                )
+            }
+            None => Err(no_image_error()),
+        });
+
+        match result {
+            Ok(texture) => self.image = Some(texture),
+            Err(error) => {
+                report_error(error.clone());
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+/// Draws a crossed out box of `size` in place of an image that could not be loaded.
+pub(crate) fn draw_broken_image(
+    size: &crate::core::point::Size,
+    c: Context,
+    g: &mut DrawTarget,
+    clip: &DrawState,
+) {
+    let (w, h) = (f64::from(size.w), f64::from(size.h));
+    let color = apply_opacity(get_current_theme().disabled_color);
+
+    Rectangle::new_border(color, 0.5).draw([0.5, 0.5, w - 1.0, h - 1.0], clip, c.transform, g);
+    Line::new(color, 0.5).draw([0.0, 0.0, w, h], clip, c.transform, g);
+    Line::new(color, 0.5).draw([0.0, h, w, 0.0], clip, c.transform, g);
+}
+
+/// Resolves `image_name` to the path of an image file, looking in the `assets` directory if it
+/// is not the path of an existing file.
+pub(crate) fn find_image(image_name: &str) -> Result<PathBuf, PushrodError> {
+    let path = Path::new(image_name);
+
+    if path.is_file() {
+        return Ok(path.to_path_buf());
     }
+
+    let assets = find_folder::Search::ParentsThenKids(3, 3)
+        .for_folder("assets")
+        .map_err(|_| PushrodError::ResourceLoading {
+            name: image_name.to_string(),
+            reason: String::from("no assets directory"),
+        })?;
+
+    Ok(assets.join(image_name))
+}
+
+/// The error for an image that could not be decoded from the contents of an image file.
+pub(crate) fn decode_error(error: ::image::ImageError) -> PushrodError {
+    PushrodError::ResourceLoading {
+        name: String::from("image data"),
+        reason: error.to_string(),
+    }
+}
+
+/// The error for an image `Widget` that was not given an image to draw.
+pub(crate) fn no_image_error() -> PushrodError {
+    PushrodError::InvalidConfiguration(String::from("No image was given"))
 }
 
 /// Implementation of the `ImageWidget` object.  Draws an image on the screen based on the
@@ -65,17 +388,41 @@ impl Widget for ImageWidget {
         &mut self.config
     }
 
-    /// Draws the contents of the widget.
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Image
+    }
+
+    /// Draws the contents of the widget, creating the texture for the image the first time.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.load_texture();
+
         let size = self.get_size();
-        let transform = c.transform.scale(
-            size.w as f64 / self.image_size.w as f64,
-            size.h as f64 / self.image_size.h as f64,
-        );
+        let image_size = self.get_image_size();
 
-        Image::new().draw(&*self.image, clip, transform, g);
+        match &self.image {
+            Some(texture) => {
+                Image::new()
+                    .color(apply_opacity([1.0; 4]))
+                    .rect(get_image_rect(self.scaling, &image_size, &size))
+                    .draw(texture, clip, c.transform, g);
+            }
+            None => draw_broken_image(&size, c, g, clip),
+        }
 
         // Then clear invalidation.
         self.clear_invalidate();
     }
+
+    /// Loads the version of the image for the new scale factor, if there is one, or the normal
+    /// version otherwise, the next time the widget is drawn.  Images loaded from memory are
+    /// kept.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+
+        if self.image_name.is_some() {
+            self.reset_image();
+        }
+
+        self.invalidate();
+    }
 }
@@ -0,0 +1,780 @@
+// Layout Widgets
+// Containers that place their children in a row or a column.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+/// Default number of pixels between neighboring children of a layout.
+pub const DEFAULT_LAYOUT_SPACING: i32 = 0;
+
+/// The layout settings of one child of a layout manager, which are passed to
+/// `Widget::layout_children`.
+#[derive(Clone, Debug)]
+pub struct LayoutChild {
+    /// The ID of the child `Widget`.
+    pub widget_id: WidgetId,
+
+    /// The size that the child would like to be given; see `Widget::set_preferred_size`.
+    pub preferred_size: Size,
+
+    /// The space to keep clear around the outside of the child; see `Widget::set_margin`.
+    pub margin: Insets,
+
+    /// The share of the space left over that the child is stretched to fill; see
+    /// `Widget::set_layout_weight`.
+    pub weight: u32,
+
+    /// The cell of a grid that the child is placed in, if any; see `Widget::set_grid_cell`.
+    pub grid_cell: Option<GridCell>,
+}
+
+/// The direction in which a box layout places its children.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LayoutDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// Swaps the horizontal and vertical parts of the bounds `[x, y, w, h]`.
+fn transpose_bounds(bounds: [i32; 4]) -> [i32; 4] {
+    [bounds[1], bounds[0], bounds[3], bounds[2]]
+}
+
+/// Places the `children` in sequence inside of the `content` rectangle, from left to right,
+/// or from top to bottom.  Children with a weight of 0 keep their preferred size along the
+/// direction of the layout, and the space that is left is shared out among the others by weight.
+/// Every child is stretched to fill the layout in the other direction.
+fn layout_box(
+    direction: LayoutDirection,
+    content: [i32; 4],
+    spacing: i32,
+    children: &[LayoutChild],
+) -> Vec<[i32; 4]> {
+    // Vertical layouts are laid out as horizontal layouts, with the axes swapped.
+    let transpose = direction == LayoutDirection::Vertical;
+    let content = if transpose {
+        transpose_bounds(content)
+    } else {
+        content
+    };
+    let items: Vec<(i32, i32, i32, i32, i32, u32)> = children
+        .iter()
+        .map(|child| {
+            let margin = child.margin;
+            let (before, after, top, bottom, preferred) = if transpose {
+                (
+                    margin.top,
+                    margin.bottom,
+                    margin.left,
+                    margin.right,
+                    child.preferred_size.h,
+                )
+            } else {
+                (
+                    margin.left,
+                    margin.right,
+                    margin.top,
+                    margin.bottom,
+                    child.preferred_size.w,
+                )
+            };
+
+            (before, after, top, bottom, preferred.max(0), child.weight)
+        })
+        .collect();
+
+    let spacing_total = spacing * (items.len() as i32 - 1).max(0);
+    let fixed_total: i32 = items
+        .iter()
+        .map(|(before, after, _, _, preferred, weight)| {
+            before + after + if *weight == 0 { *preferred } else { 0 }
+        })
+        .sum();
+    let remaining = i64::from((content[2] - fixed_total - spacing_total).max(0));
+    let total_weight: i64 = items.iter().map(|item| i64::from(item.5)).sum();
+    let mut weight_so_far: i64 = 0;
+    let mut x = content[0];
+
+    items
+        .iter()
+        .map(|(before, after, top, bottom, preferred, weight)| {
+            let length = if *weight == 0 {
+                *preferred
+            } else {
+                // The shares are taken from the running total, so that rounding never leaves a
+                // gap at the end of the layout.
+                let start = remaining * weight_so_far / total_weight;
+
+                weight_so_far += i64::from(*weight);
+                (remaining * weight_so_far / total_weight - start) as i32
+            };
+            let bounds = [
+                x + before,
+                content[1] + top,
+                length,
+                (content[3] - top - bottom).max(0),
+            ];
+
+            x += before + length + after + spacing;
+
+            if transpose {
+                transpose_bounds(bounds)
+            } else {
+                bounds
+            }
+        })
+        .collect()
+}
+
+/// This is the `HorizontalLayoutWidget`, a container that places its children in a row, from
+/// left to right, in the order in which they were added, with `spacing` pixels between them.
+/// Children are placed inside of the padding of the layout, and keep their margins clear.  Each
+/// child keeps its preferred width, unless it has a layout weight, in which case it is stretched
+/// to fill its share of the width that is left.  Every child is stretched to the height of the
+/// layout.  Hidden children take up no space.
+///
+/// The origins and sizes of the children are set by `WidgetStore::apply_layouts`, which the
+/// `Pushrod` run loop calls after every event, and before every frame, so the children are
+/// laid out again whenever they are added or removed, or the layout or a child is resized.
+///
+/// Example usage:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::layout_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let mut layout = HorizontalLayoutWidget::new();
+///    let mut label = CanvasWidget::new();
+///    let mut field = CanvasWidget::new();
+///    let mut button = CanvasWidget::new();
+///
+///    layout.set_size(300, 40);
+///    layout.set_spacing(10);
+///    layout.set_padding(make_uniform_insets(5));
+///    label.set_preferred_size(60, 20);
+///    field.set_layout_weight(1);
+///    button.set_preferred_size(80, 20);
+///
+///    let layout_id = widget_store.add_widget("Row", Box::new(layout));
+///    widget_store.add_widget_to_parent("Label", Box::new(label), layout_id);
+///    let field_id = widget_store.add_widget_to_parent("Field", Box::new(field), layout_id);
+///    let button_id = widget_store.add_widget_to_parent("Button", Box::new(button), layout_id);
+///
+///    widget_store.apply_layouts();
+///
+///    let field = widget_store.get_widget_for_id(field_id);
+///
+///    assert_eq!(field.borrow_mut().get_origin().x, 75);
+///    assert_eq!(field.borrow_mut().get_size().w, 130);
+///    assert_eq!(field.borrow_mut().get_size().h, 30);
+///    assert_eq!(widget_store.get_widget_for_id(button_id).borrow_mut().get_origin().x, 215);
+/// # }
+/// ```
+pub struct HorizontalLayoutWidget {
+    config: Configurable,
+    spacing: i32,
+}
+
+/// Implementation of the constructor for the `HorizontalLayoutWidget`.
+impl HorizontalLayoutWidget {
+    pub fn new() -> Self {
+        Self {
+            config: Configurable::new(),
+            spacing: DEFAULT_LAYOUT_SPACING,
+        }
+    }
+
+    /// Sets the number of pixels between neighboring children.
+    pub fn set_spacing(&mut self, spacing: i32) {
+        self.spacing = spacing;
+    }
+
+    /// Retrieves the number of pixels between neighboring children.
+    pub fn get_spacing(&self) -> i32 {
+        self.spacing
+    }
+}
+
+impl Default for HorizontalLayoutWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `HorizontalLayoutWidget` object with the `Widget` traits implemented.
+/// The layout draws its background like a `CanvasWidget`.
+impl Widget for HorizontalLayoutWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn layout_children(&mut self, children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        layout_box(
+            LayoutDirection::Horizontal,
+            self.get_content_rect(),
+            self.spacing,
+            children,
+        )
+    }
+}
+
+/// This is the `VerticalLayoutWidget`, a container that places its children in a column, from
+/// top to bottom, in the order in which they were added.  It works in the same way as the
+/// `HorizontalLayoutWidget`, with the preferred heights of the children used along the column,
+/// and every child stretched to the width of the layout.
+///
+/// Example usage:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::layout_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let mut layout = VerticalLayoutWidget::new();
+///    let mut header = CanvasWidget::new();
+///    let mut body = CanvasWidget::new();
+///    let mut footer = CanvasWidget::new();
+///
+///    layout.set_size(200, 300);
+///    header.set_preferred_size(0, 40);
+///    body.set_layout_weight(1);
+///    footer.set_preferred_size(0, 20);
+///    footer.set_margin(make_insets(5, 10, 5, 10));
+///
+///    let layout_id = widget_store.add_widget("Column", Box::new(layout));
+///    widget_store.add_widget_to_parent("Header", Box::new(header), layout_id);
+///    let body_id = widget_store.add_widget_to_parent("Body", Box::new(body), layout_id);
+///    let footer_id = widget_store.add_widget_to_parent("Footer", Box::new(footer), layout_id);
+///
+///    widget_store.apply_layouts();
+///
+///    assert_eq!(widget_store.get_widget_for_id(body_id).borrow_mut().get_size().h, 230);
+///
+///    let footer = widget_store.get_widget_for_id(footer_id);
+///
+///    assert_eq!(footer.borrow_mut().get_origin().y, 275);
+///    assert_eq!(footer.borrow_mut().get_origin().x, 10);
+///    assert_eq!(footer.borrow_mut().get_size().w, 180);
+/// # }
+/// ```
+pub struct VerticalLayoutWidget {
+    config: Configurable,
+    spacing: i32,
+}
+
+/// Implementation of the constructor for the `VerticalLayoutWidget`.
+impl VerticalLayoutWidget {
+    pub fn new() -> Self {
+        Self {
+            config: Configurable::new(),
+            spacing: DEFAULT_LAYOUT_SPACING,
+        }
+    }
+
+    /// Sets the number of pixels between neighboring children.
+    pub fn set_spacing(&mut self, spacing: i32) {
+        self.spacing = spacing;
+    }
+
+    /// Retrieves the number of pixels between neighboring children.
+    pub fn get_spacing(&self) -> i32 {
+        self.spacing
+    }
+}
+
+impl Default for VerticalLayoutWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `VerticalLayoutWidget` object with the `Widget` traits implemented.
+/// The layout draws its background like a `CanvasWidget`.
+impl Widget for VerticalLayoutWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn layout_children(&mut self, children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        layout_box(
+            LayoutDirection::Vertical,
+            self.get_content_rect(),
+            self.spacing,
+            children,
+        )
+    }
+}
+
+/// How a child of a `GridLayoutWidget` is placed inside of its cell, along one direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CellAlignment {
+    /// The child keeps its preferred size, at the left or top of the cell.
+    Start,
+
+    /// The child keeps its preferred size, in the middle of the cell.
+    Center,
+
+    /// The child keeps its preferred size, at the right or bottom of the cell.
+    End,
+
+    /// The child fills the cell.  This is the default.
+    Stretch,
+}
+
+/// The cell, or block of cells, of a `GridLayoutWidget` that a child is placed in, along with
+/// how the child is aligned inside of it.  Rows and columns start at 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridCell {
+    pub row: u32,
+    pub column: u32,
+    pub row_span: u32,
+    pub column_span: u32,
+    pub horizontal_alignment: CellAlignment,
+    pub vertical_alignment: CellAlignment,
+}
+
+/// Implementation of the `GridCell`.
+impl GridCell {
+    /// Returns a copy of this cell that spans `row_span` rows and `column_span` columns, each at
+    /// least 1.
+    pub fn with_span(self, row_span: u32, column_span: u32) -> Self {
+        Self {
+            row_span: row_span.max(1),
+            column_span: column_span.max(1),
+            ..self
+        }
+    }
+
+    /// Returns a copy of this cell with the horizontal and vertical alignments of the child.
+    pub fn with_alignment(self, horizontal: CellAlignment, vertical: CellAlignment) -> Self {
+        Self {
+            horizontal_alignment: horizontal,
+            vertical_alignment: vertical,
+            ..self
+        }
+    }
+
+    /// Indicates whether this block of cells and `other` share any cells.
+    pub fn overlaps(&self, other: &GridCell) -> bool {
+        self.row < other.row + other.row_span
+            && other.row < self.row + self.row_span
+            && self.column < other.column + other.column_span
+            && other.column < self.column + self.column_span
+    }
+}
+
+/// Creates a `GridCell` for a single cell at `row` and `column`, which the child fills.
+pub fn make_grid_cell(row: u32, column: u32) -> GridCell {
+    GridCell {
+        row,
+        column,
+        row_span: 1,
+        column_span: 1,
+        horizontal_alignment: CellAlignment::Stretch,
+        vertical_alignment: CellAlignment::Stretch,
+    }
+}
+
+/// How the width of a column, or the height of a row, of a `GridLayoutWidget` is chosen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridTrack {
+    /// Sized to fit the preferred sizes, and margins, of the children in it.  This is the
+    /// default for rows and columns that are not specified.
+    Auto,
+
+    /// A fixed number of pixels.
+    Fixed(i32),
+
+    /// A percentage of the content width or height of the grid, once the spacing has been
+    /// taken away.
+    Percent(f64),
+}
+
+/// A child of a grid, as one span along one direction: the first track, the number of tracks,
+/// and the number of pixels the child needs, including its margins.
+struct TrackSpan {
+    start: usize,
+    span: usize,
+    required: i32,
+}
+
+/// Computes the size of each track along one direction of a grid.  Fixed and percentage
+/// tracks are sized first.  Automatic tracks are sized to fit the children that lie within a
+/// single track, and then grown to fit the children that span several tracks, with the extra
+/// space shared out in proportion to the sizes of the spanned automatic tracks.
+fn compute_track_sizes(
+    specs: &[GridTrack],
+    count: usize,
+    available: i32,
+    spacing: i32,
+    spans: &[TrackSpan],
+) -> Vec<i32> {
+    let spec_for = |track: usize| specs.get(track).copied().unwrap_or(GridTrack::Auto);
+    let available = (available - spacing * (count as i32 - 1).max(0)).max(0);
+    let mut sizes: Vec<i32> = (0..count)
+        .map(|track| match spec_for(track) {
+            GridTrack::Auto => 0,
+            GridTrack::Fixed(size) => size.max(0),
+            GridTrack::Percent(percent) => (f64::from(available) * percent / 100.0).round() as i32,
+        })
+        .collect();
+
+    for item in spans.iter().filter(|item| item.span == 1) {
+        if spec_for(item.start) == GridTrack::Auto {
+            sizes[item.start] = sizes[item.start].max(item.required);
+        }
+    }
+
+    for item in spans.iter().filter(|item| item.span > 1) {
+        let tracks = item.start..item.start + item.span;
+        let current: i32 = tracks.clone().map(|track| sizes[track]).sum::<i32>()
+            + spacing * (item.span as i32 - 1);
+        let extra = item.required - current;
+        let auto_tracks: Vec<usize> = tracks
+            .filter(|track| spec_for(*track) == GridTrack::Auto)
+            .collect();
+
+        if extra <= 0 || auto_tracks.is_empty() {
+            continue;
+        }
+
+        let auto_total: i64 = auto_tracks
+            .iter()
+            .map(|track| i64::from(sizes[*track]))
+            .sum();
+        let mut share_so_far: i64 = 0;
+        let mut given_so_far: i64 = 0;
+
+        for (index, track) in auto_tracks.iter().enumerate() {
+            // Tracks that are still empty share the extra space evenly.
+            share_so_far += if auto_total > 0 {
+                i64::from(sizes[*track])
+            } else {
+                1
+            };
+
+            let total = if auto_total > 0 {
+                auto_total
+            } else {
+                auto_tracks.len() as i64
+            };
+            let given = if index == auto_tracks.len() - 1 {
+                i64::from(extra)
+            } else {
+                i64::from(extra) * share_so_far / total
+            };
+
+            sizes[*track] += (given - given_so_far) as i32;
+            given_so_far = given;
+        }
+    }
+
+    sizes
+}
+
+/// Returns the offset and length of a child of `preferred` pixels inside of a span of a grid
+/// that starts at `start` and is `length` pixels long, aligned with `alignment`.
+fn align_in_cell(alignment: CellAlignment, start: i32, length: i32, preferred: i32) -> (i32, i32) {
+    let preferred = preferred.max(0).min(length);
+
+    match alignment {
+        CellAlignment::Start => (start, preferred),
+        CellAlignment::Center => (start + (length - preferred) / 2, preferred),
+        CellAlignment::End => (start + length - preferred, preferred),
+        CellAlignment::Stretch => (start, length),
+    }
+}
+
+/// This is the `GridLayoutWidget`, a container that places its children in the cells of a grid
+/// of rows and columns, such as a form with labels in the first column and fields in the second.
+/// Each child is given a cell, or a block of cells, with `Widget::set_grid_cell`, most easily by
+/// adding it with `WidgetStore::add_widget_to_grid`, which refuses to place a child in a cell
+/// that is already taken.  Children without a cell are placed in the free cells that are left,
+/// from left to right, and top to bottom.
+///
+/// The size of each column and row is either fixed, a percentage of the size of the grid, or
+/// sized to fit its children (see `GridTrack`), with `spacing` pixels between columns and rows.
+/// A child that spans several columns or rows widens the automatically sized ones that it spans,
+/// in proportion to their sizes, if it does not fit.  Children are placed inside of the padding
+/// of the grid, keep their margins clear, and are aligned in their cells by their `GridCell`.
+/// Hidden children take up no space.  Like the other layouts, the children are placed by
+/// `WidgetStore::apply_layouts`.
+///
+/// Example usage:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::layout_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let mut grid = GridLayoutWidget::new();
+///    let mut name_label = CanvasWidget::new();
+///    let mut name_field = CanvasWidget::new();
+///    let mut notes_field = CanvasWidget::new();
+///
+///    grid.set_size(400, 200);
+///    grid.set_spacing(10);
+///    grid.set_columns(vec![GridTrack::Auto, GridTrack::Percent(75.0)]);
+///    name_label.set_preferred_size(80, 24);
+///    name_field.set_preferred_size(100, 24);
+///    notes_field.set_preferred_size(100, 40);
+///
+///    let grid_id = widget_store.add_widget("Form", Box::new(grid));
+///    let cell = make_grid_cell(0, 0).with_alignment(CellAlignment::End, CellAlignment::Center);
+///    let label_id = widget_store
+///        .add_widget_to_grid("NameLabel", Box::new(name_label), grid_id, cell)
+///        .unwrap();
+///    let field_id = widget_store
+///        .add_widget_to_grid("NameField", Box::new(name_field), grid_id, make_grid_cell(0, 1))
+///        .unwrap();
+///    let notes_id = widget_store
+///        .add_widget_to_grid(
+///            "Notes",
+///            Box::new(notes_field),
+///            grid_id,
+///            make_grid_cell(1, 0).with_span(1, 2),
+///        )
+///        .unwrap();
+///
+///    assert_eq!(
+///        widget_store.add_widget_to_grid(
+///            "Overlap",
+///            Box::new(CanvasWidget::new()),
+///            grid_id,
+///            make_grid_cell(1, 1),
+///        ),
+///        Err(WidgetStoreError::CellOccupied { row: 1, column: 1 })
+///    );
+///
+///    widget_store.apply_layouts();
+///
+///    let field = widget_store.get_widget_for_id(field_id);
+///
+///    assert_eq!(field.borrow_mut().get_origin().x, 90);
+///    assert_eq!(field.borrow_mut().get_size().w, 293);
+///
+///    let notes = widget_store.get_widget_for_id(notes_id);
+///
+///    assert_eq!(notes.borrow_mut().get_origin().y, 34);
+///    assert_eq!(notes.borrow_mut().get_size().w, 383);
+///    assert_eq!(widget_store.get_widget_for_id(label_id).borrow_mut().get_origin().x, 0);
+/// # }
+/// ```
+pub struct GridLayoutWidget {
+    config: Configurable,
+    columns: Vec<GridTrack>,
+    rows: Vec<GridTrack>,
+    column_spacing: i32,
+    row_spacing: i32,
+}
+
+/// Implementation of the constructor for the `GridLayoutWidget`.
+impl GridLayoutWidget {
+    pub fn new() -> Self {
+        Self {
+            config: Configurable::new(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_spacing: DEFAULT_LAYOUT_SPACING,
+            row_spacing: DEFAULT_LAYOUT_SPACING,
+        }
+    }
+
+    /// Sets how the width of each column is chosen, from the first column.  Columns that are
+    /// not specified, but that children are placed in, are sized automatically.
+    pub fn set_columns(&mut self, columns: Vec<GridTrack>) {
+        self.columns = columns;
+    }
+
+    /// Retrieves how the width of each column is chosen.
+    pub fn get_columns(&self) -> &[GridTrack] {
+        &self.columns
+    }
+
+    /// Sets how the height of each row is chosen, from the first row.  Rows that are not
+    /// specified, but that children are placed in, are sized automatically.
+    pub fn set_rows(&mut self, rows: Vec<GridTrack>) {
+        self.rows = rows;
+    }
+
+    /// Retrieves how the height of each row is chosen.
+    pub fn get_rows(&self) -> &[GridTrack] {
+        &self.rows
+    }
+
+    /// Sets the number of pixels between neighboring columns and between neighboring rows.
+    pub fn set_spacing(&mut self, spacing: i32) {
+        self.column_spacing = spacing;
+        self.row_spacing = spacing;
+    }
+
+    /// Sets the number of pixels between neighboring columns.
+    pub fn set_column_spacing(&mut self, spacing: i32) {
+        self.column_spacing = spacing;
+    }
+
+    /// Retrieves the number of pixels between neighboring columns.
+    pub fn get_column_spacing(&self) -> i32 {
+        self.column_spacing
+    }
+
+    /// Sets the number of pixels between neighboring rows.
+    pub fn set_row_spacing(&mut self, spacing: i32) {
+        self.row_spacing = spacing;
+    }
+
+    /// Retrieves the number of pixels between neighboring rows.
+    pub fn get_row_spacing(&self) -> i32 {
+        self.row_spacing
+    }
+
+    /// Returns the cell of each child: its own, or the next free cell for children without one.
+    fn place_children(&self, children: &[LayoutChild]) -> Vec<GridCell> {
+        let column_count = self.columns.len().max(1) as u32;
+        let mut taken: HashSet<(u32, u32)> = HashSet::new();
+
+        for cell in children.iter().filter_map(|child| child.grid_cell) {
+            for row in cell.row..cell.row + cell.row_span {
+                for column in cell.column..cell.column + cell.column_span {
+                    taken.insert((row, column));
+                }
+            }
+        }
+
+        let mut next_free = 0;
+
+        children
+            .iter()
+            .map(|child| match child.grid_cell {
+                Some(cell) => cell,
+                None => {
+                    while taken.contains(&(next_free / column_count, next_free % column_count)) {
+                        next_free += 1;
+                    }
+
+                    let cell = make_grid_cell(next_free / column_count, next_free % column_count);
+
+                    taken.insert((cell.row, cell.column));
+                    cell
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for GridLayoutWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `GridLayoutWidget` object with the `Widget` traits implemented.
+/// The layout draws its background like a `CanvasWidget`.
+impl Widget for GridLayoutWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn layout_children(&mut self, children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        let content = self.get_content_rect();
+        let cells = self.place_children(children);
+        let column_count = cells
+            .iter()
+            .map(|cell| (cell.column + cell.column_span) as usize)
+            .max()
+            .unwrap_or(0)
+            .max(self.columns.len());
+        let row_count = cells
+            .iter()
+            .map(|cell| (cell.row + cell.row_span) as usize)
+            .max()
+            .unwrap_or(0)
+            .max(self.rows.len());
+        let column_spans: Vec<TrackSpan> = children
+            .iter()
+            .zip(&cells)
+            .map(|(child, cell)| TrackSpan {
+                start: cell.column as usize,
+                span: cell.column_span as usize,
+                required: child.preferred_size.w + child.margin.left + child.margin.right,
+            })
+            .collect();
+        let row_spans: Vec<TrackSpan> = children
+            .iter()
+            .zip(&cells)
+            .map(|(child, cell)| TrackSpan {
+                start: cell.row as usize,
+                span: cell.row_span as usize,
+                required: child.preferred_size.h + child.margin.top + child.margin.bottom,
+            })
+            .collect();
+        let column_widths = compute_track_sizes(
+            &self.columns,
+            column_count,
+            content[2],
+            self.column_spacing,
+            &column_spans,
+        );
+        let row_heights = compute_track_sizes(
+            &self.rows,
+            row_count,
+            content[3],
+            self.row_spacing,
+            &row_spans,
+        );
+
+        // The start of each track, followed by the end of the last one.
+        let track_starts = |sizes: &[i32], start: i32, spacing: i32| -> Vec<i32> {
+            let mut starts = vec![start];
+
+            for size in sizes {
+                starts.push(starts.last().unwrap() + size + spacing);
+            }
+
+            starts
+        };
+        let column_starts = track_starts(&column_widths, content[0], self.column_spacing);
+        let row_starts = track_starts(&row_heights, content[1], self.row_spacing);
+
+        children
+            .iter()
+            .zip(&cells)
+            .map(|(child, cell)| {
+                let first_column = cell.column as usize;
+                let last_column = first_column + cell.column_span as usize;
+                let first_row = cell.row as usize;
+                let last_row = first_row + cell.row_span as usize;
+                let margin = child.margin;
+                let x = column_starts[first_column] + margin.left;
+                let y = row_starts[first_row] + margin.top;
+                let w =
+                    (column_starts[last_column] - self.column_spacing - margin.right - x).max(0);
+                let h = (row_starts[last_row] - self.row_spacing - margin.bottom - y).max(0);
+                let (x, w) = align_in_cell(cell.horizontal_alignment, x, w, child.preferred_size.w);
+                let (y, h) = align_in_cell(cell.vertical_alignment, y, h, child.preferred_size.h);
+
+                [x, y, w, h]
+            })
+            .collect()
+    }
+}
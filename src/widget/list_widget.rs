@@ -0,0 +1,703 @@
+// List Widget
+// Extensible widget for the widget library - handles a list of selectable rows of text.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the current row of a `ListWidget` when its selection
+/// changes, or `None` if no row is selected.
+pub type SelectionCallback = Box<dyn FnMut(Option<usize>)>;
+
+/// Closure type that is called with the row of a `ListWidget` that was activated.
+pub type ItemCallback = Box<dyn FnMut(usize)>;
+
+/// The default height of the rows of a `ListWidget`, in pixels.
+pub const DEFAULT_LIST_ROW_HEIGHT: i32 = 24;
+
+/// The number of pixels between the left edge of a row of a `ListWidget` and its text.
+const LIST_TEXT_INDENT: i32 = 4;
+
+/// How far the background of a hovered row is blended toward the accent color of the current
+/// `Theme`.
+const LIST_HOVER_AMOUNT: f32 = 0.2;
+
+/// This is the `ListSelection`, which keeps track of the selected rows of a list: the current
+/// row, which was selected last, all of the selected rows, and the anchor from which a range of
+/// rows is selected.  The selection is kept consistent as rows are inserted and removed, so that
+/// the same items stay selected.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::list_widget::*;
+/// # fn main() {
+///    let mut selection = ListSelection::new();
+///
+///    selection.select(2);
+///    selection.extend_to(4);
+///    assert_eq!(selection.get_rows(), &[2, 3, 4]);
+///    assert_eq!(selection.get_current(), Some(4));
+///
+///    selection.toggle(3);
+///    assert_eq!(selection.get_rows(), &[2, 4]);
+///
+///    // Rows move along with the items they belong to.
+///    selection.row_inserted(0);
+///    assert_eq!(selection.get_rows(), &[3, 5]);
+///
+///    selection.row_removed(5);
+///    assert_eq!(selection.get_rows(), &[3]);
+///    assert_eq!(selection.get_current(), Some(3));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListSelection {
+    rows: Vec<usize>,
+    current: Option<usize>,
+    anchor: Option<usize>,
+}
+
+/// Implementation of the `ListSelection`.
+impl ListSelection {
+    /// Creates a new selection, in which no rows are selected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects only `row`, which becomes the current row, and the anchor of ranges.
+    pub fn select(&mut self, row: usize) {
+        self.rows = vec![row];
+        self.current = Some(row);
+        self.anchor = Some(row);
+    }
+
+    /// Adds `row` to the selection if it is not selected, or removes it if it is.  The row
+    /// becomes the anchor of ranges.
+    pub fn toggle(&mut self, row: usize) {
+        match self.rows.binary_search(&row) {
+            Ok(index) => {
+                self.rows.remove(index);
+                self.current = self.rows.last().copied();
+            }
+            Err(index) => {
+                self.rows.insert(index, row);
+                self.current = Some(row);
+            }
+        }
+
+        self.anchor = Some(row);
+    }
+
+    /// Selects the rows from the anchor to `row`, which becomes the current row.  Selects only
+    /// `row` if there is no anchor.
+    pub fn extend_to(&mut self, row: usize) {
+        let anchor = match self.anchor {
+            Some(anchor) => anchor,
+            None => return self.select(row),
+        };
+
+        self.rows = (anchor.min(row)..=anchor.max(row)).collect();
+        self.current = Some(row);
+    }
+
+    /// Deselects all of the rows.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Indicates whether `row` is selected.
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.rows.binary_search(&row).is_ok()
+    }
+
+    /// Retrieves the row that was selected last, or `None` if no row is selected.
+    pub fn get_current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Retrieves all of the selected rows, in ascending order.
+    pub fn get_rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    /// Moves the selected rows at or after `row` down by one, for a row that was inserted at
+    /// `row`.
+    pub fn row_inserted(&mut self, row: usize) {
+        let shift = |selected: usize| {
+            if selected >= row {
+                selected + 1
+            } else {
+                selected
+            }
+        };
+
+        self.rows = self.rows.iter().map(|selected| shift(*selected)).collect();
+        self.current = self.current.map(shift);
+        self.anchor = self.anchor.map(shift);
+    }
+
+    /// Deselects `row`, and moves the selected rows after it up by one, for a row that was
+    /// removed.  If the current row was removed, the last remaining selected row becomes the
+    /// current row.
+    pub fn row_removed(&mut self, row: usize) {
+        let shift = |selected: usize| {
+            if selected > row {
+                selected - 1
+            } else {
+                selected
+            }
+        };
+
+        self.rows = self
+            .rows
+            .iter()
+            .filter(|selected| **selected != row)
+            .map(|selected| shift(*selected))
+            .collect();
+        self.current = match self.current {
+            Some(current) if current != row => Some(shift(current)),
+            _ => self.rows.last().copied(),
+        };
+        self.anchor = match self.anchor {
+            Some(anchor) if anchor != row => Some(shift(anchor)),
+            _ => self.current,
+        };
+    }
+}
+
+/// This is the `ListWidget`, which displays a list of text items as rows of the same height.
+/// Clicking a row selects it, and the row under the mouse is highlighted.  When multiple
+/// selection is turned on, `Ctrl`-clicking a row adds it to or removes it from the selection,
+/// and `Shift`-clicking selects the range of rows from the row that was clicked before.  The
+/// `Up` and `Down` keys move the selection while the list is focused.
+///
+/// The `on_selection_changed` callback is called with the current row every time the selection
+/// changes, and `on_item_activated` when a row is double-clicked, or `Enter` is pressed.
+///
+/// The list scrolls its rows itself, with the mouse wheel, and to keep the current row in view
+/// when it is moved from the keyboard, so it does not need a `ScrollContainerWidget`.  Only
+/// the rows that can be seen are drawn, so lists of thousands of items are as quick to draw as
+/// short ones.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::list_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("List", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let items = (1..=1000).map(|number| format!("Item {}", number)).collect();
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut list = ListWidget::new(pushrod.get_factory(), font_name, items);
+///
+///    list.set_origin(20, 20);
+///    list.set_size(200, 240);
+///    list.set_multi_select(true);
+///    list.on_selection_changed(Box::new(|row| eprintln!("Selected: {:?}", row)));
+///    list.on_item_activated(Box::new(|row| eprintln!("Activated: {}", row)));
+///    pushrod.add_widget("List", Box::new(list));
+/// # }
+/// ```
+pub struct ListWidget {
+    config: Configurable,
+    text_widget: Box<TextWidget>,
+    items: Vec<String>,
+    row_height: i32,
+    scroll_y: i32,
+    hovered_row: Option<usize>,
+    multi_select: bool,
+    selection: ListSelection,
+    on_selection_changed: Option<SelectionCallback>,
+    on_item_activated: Option<ItemCallback>,
+}
+
+/// Implementation of the constructor for the `ListWidget`.
+impl ListWidget {
+    /// Creates a new list of `items`, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  No row is selected, and only one
+    /// row can be selected at a time.  The list can be focused, so that it can be moved through
+    /// from the keyboard.  Panics if the font cannot be loaded; use `font_cache::load_font` and
+    /// `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String, items: Vec<String>) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, items)
+    }
+
+    /// Creates a new list of `items` that draws its text in a font from the font cache.
+    pub fn from_font(font: SharedGlyphs, items: Vec<String>) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font, String::new(), font_size, TextJustify::Left);
+
+        text_widget.set_padding(make_insets(0, LIST_TEXT_INDENT, 0, LIST_TEXT_INDENT));
+        text_widget.set_ellipsize(true);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: Box::new(text_widget),
+            items,
+            row_height: DEFAULT_LIST_ROW_HEIGHT,
+            scroll_y: 0,
+            hovered_row: None,
+            multi_select: false,
+            selection: ListSelection::new(),
+            on_selection_changed: None,
+            on_item_activated: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget
+    }
+
+    /// Sets the closure that is called with the current row when the selection changes.
+    pub fn on_selection_changed(&mut self, callback: SelectionCallback) {
+        self.on_selection_changed = Some(callback);
+    }
+
+    /// Sets the closure that is called with the row that is double-clicked, or that is current
+    /// when `Enter` is pressed.
+    pub fn on_item_activated(&mut self, callback: ItemCallback) {
+        self.on_item_activated = Some(callback);
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.  The text of selected rows
+    /// is drawn in the background color of the current `Theme`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Replaces all of the items of the list.  The selection is cleared, and the list is
+    /// scrolled back to the top.
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.hovered_row = None;
+        self.scroll_y = 0;
+        self.update_selection(ListSelection::clear);
+        self.invalidate();
+    }
+
+    /// Retrieves the items of the list.
+    pub fn get_items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Inserts `item` into the list at `row`, moving the rows after it down.  A `row` past the
+    /// end of the list appends the item.  The same items stay selected.
+    pub fn insert_item(&mut self, row: usize, item: String) {
+        let row = row.min(self.items.len());
+
+        self.items.insert(row, item);
+        self.update_selection(|selection| selection.row_inserted(row));
+        self.invalidate();
+    }
+
+    /// Removes the item at `row` from the list, returning it, or `None` if there is no such row.
+    /// The item is deselected, and the same other items stay selected.
+    pub fn remove_item(&mut self, row: usize) -> Option<String> {
+        if row >= self.items.len() {
+            return None;
+        }
+
+        let item = self.items.remove(row);
+
+        self.hovered_row = None;
+        self.update_selection(|selection| selection.row_removed(row));
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+        Some(item)
+    }
+
+    /// Sets the height of each row, in pixels.  Invalidates the widget afterward.
+    pub fn set_row_height(&mut self, row_height: i32) {
+        self.row_height = row_height.max(1);
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Retrieves the height of each row, in pixels.  Defaults to `DEFAULT_LIST_ROW_HEIGHT`.
+    pub fn get_row_height(&self) -> i32 {
+        self.row_height
+    }
+
+    /// Turns multiple selection with `Ctrl` and `Shift` on or off.  Turning it off selects only
+    /// the current row.
+    pub fn set_multi_select(&mut self, multi_select: bool) {
+        self.multi_select = multi_select;
+
+        if !multi_select {
+            self.update_selection(|selection| match selection.get_current() {
+                Some(row) => selection.select(row),
+                None => selection.clear(),
+            });
+        }
+    }
+
+    /// Indicates whether multiple rows can be selected.  Defaults to `false`.
+    pub fn is_multi_select(&self) -> bool {
+        self.multi_select
+    }
+
+    /// Selects only `row`, or clears the selection if `row` is `None`, and scrolls the row into
+    /// view.  A row past the end of the list clears the selection.
+    pub fn set_selected_row(&mut self, row: Option<usize>) {
+        match row {
+            Some(row) if row < self.items.len() => {
+                self.update_selection(|selection| selection.select(row));
+                self.ensure_row_visible(row);
+            }
+            _ => self.update_selection(ListSelection::clear),
+        }
+    }
+
+    /// Retrieves the current row, which was selected last, or `None` if no row is selected.
+    pub fn get_selected_row(&self) -> Option<usize> {
+        self.selection.get_current()
+    }
+
+    /// Retrieves all of the selected rows, in ascending order.
+    pub fn get_selected_rows(&self) -> &[usize] {
+        self.selection.get_rows()
+    }
+
+    /// Scrolls the list as little as possible to bring `row` into view.
+    pub fn ensure_row_visible(&mut self, row: usize) {
+        let top = row as i32 * self.row_height;
+        let view_h = self.get_size().h;
+        let scroll_y = if top < self.scroll_y {
+            top
+        } else if top + self.row_height > self.scroll_y + view_h {
+            top + self.row_height - view_h
+        } else {
+            self.scroll_y
+        };
+
+        self.scroll_to(0, scroll_y);
+    }
+
+    /// Returns the row at the height `y` of the widget, taking the scrolling of the rows into
+    /// account, or `None` if there is no row there.
+    pub fn get_row_at(&self, y: i32) -> Option<usize> {
+        let y = y + self.scroll_y;
+
+        if y < 0 {
+            return None;
+        }
+
+        let row = (y / self.row_height) as usize;
+
+        if row < self.items.len() {
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    /// Changes the selection, calling the `on_selection_changed` callback and invalidating the
+    /// widget if it changed.
+    fn update_selection<F: FnOnce(&mut ListSelection)>(&mut self, change: F) {
+        let previous = self.selection.clone();
+
+        change(&mut self.selection);
+
+        if self.selection == previous {
+            return;
+        }
+
+        if let Some(callback) = &mut self.on_selection_changed {
+            callback(self.selection.get_current());
+        }
+
+        self.invalidate();
+    }
+
+    /// Selects `row` in response to a click, extending the selection with `Ctrl` or `Shift`
+    /// if multiple selection is turned on.
+    fn click_row(&mut self, row: usize, modifiers: KeyModifiers) {
+        let multi_select = self.multi_select;
+
+        self.update_selection(|selection| {
+            if multi_select && modifiers.shift {
+                selection.extend_to(row);
+            } else if multi_select && modifiers.ctrl {
+                selection.toggle(row);
+            } else {
+                selection.select(row);
+            }
+        });
+    }
+
+    /// Moves the selection by `rows`, selecting the first row if none was selected.
+    fn move_selection(&mut self, rows: i32) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let row = match self.selection.get_current() {
+            Some(current) => (current as i32 + rows).clamp(0, self.items.len() as i32 - 1),
+            None => 0,
+        } as usize;
+
+        self.set_selected_row(Some(row));
+    }
+
+    fn activate_row(&mut self, row: usize) {
+        if let Some(callback) = &mut self.on_item_activated {
+            callback(row);
+        }
+    }
+
+    fn set_hovered_row(&mut self, row: Option<usize>) {
+        if row != self.hovered_row {
+            self.hovered_row = row;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `ListWidget` object with the `Widget` traits implemented.  The rows
+/// are drawn with a single `TextWidget`, which is given the text of each visible row in turn.
+impl Widget for ListWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::List
+    }
+
+    /// The value is the current row, which was selected last.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        self.get_selected_row().map(|row| self.items[row].clone())
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget.set_size(w, self.row_height);
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Scrolls the rows so that the height `y` of the list is at the top of the widget, limited
+    /// to the rows of the list.  The horizontal position is ignored.  Invalidates the widget if
+    /// the list scrolled.
+    fn scroll_to(&mut self, _x: i32, y: i32) {
+        let max_y = (self.items.len() as i32 * self.row_height - self.get_size().h).max(0);
+        let y = y.clamp(0, max_y);
+
+        if y != self.scroll_y {
+            self.scroll_y = y;
+            self.invalidate();
+        }
+    }
+
+    /// Enables or disables this widget.  A disabled list is drawn in the disabled color of the
+    /// current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Scrolls the list in response to the mouse wheel, passing the scroll on to the widget
+    /// underneath if the list cannot be scrolled any further in that direction.  All other
+    /// events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let previous_y = self.scroll_y;
+                let scroll_y = self.scroll_y - delta.to_points().1.round() as i32;
+
+                self.scroll_to(0, scroll_y);
+
+                if self.scroll_y == previous_y {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                let row = self.get_row_at(point.y);
+
+                self.set_hovered_row(row);
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered_row(None),
+
+            CallbackEvent::MouseClicked {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                click_count,
+                modifiers,
+                ..
+            } => {
+                if let Some(row) = self.get_row_at(point.y) {
+                    if click_count == 2 {
+                        self.activate_row(row);
+                    } else {
+                        self.click_row(row, modifiers);
+                    }
+                }
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => match key {
+                Key::Up => self.move_selection(-1),
+                Key::Down => self.move_selection(1),
+                Key::Return => {
+                    if let Some(row) = self.selection.get_current() {
+                        self.activate_row(row);
+                    }
+                }
+                _ => (),
+            },
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, and then the rows that can be seen.  Selected rows are drawn in
+    /// the accent color of the current `Theme`, and the hovered row in the background color
+    /// blended toward it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let background = self.get_color();
+        let mut hover_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * LIST_HOVER_AMOUNT;
+        }
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let first_row = (self.scroll_y / self.row_height) as usize;
+        let last_row = ((self.scroll_y + size.h) / self.row_height + 1) as usize;
+        let text_color = self.get_text_color();
+        let disabled = self.is_disabled();
+
+        for row in first_row..last_row.min(self.items.len()) {
+            let row_y = row as i32 * self.row_height - self.scroll_y;
+            let selected = self.selection.is_selected(row);
+            let row_color = if selected && !disabled {
+                Some(theme.accent_color)
+            } else if self.hovered_row == Some(row) && !disabled {
+                Some(hover_color)
+            } else {
+                None
+            };
+
+            if let Some(row_color) = row_color {
+                Rectangle::new(apply_opacity(row_color)).draw(
+                    [
+                        0.0,
+                        f64::from(row_y),
+                        f64::from(size.w),
+                        f64::from(self.row_height),
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            self.text_widget.set_text(self.items[row].as_str());
+            self.text_widget.set_text_color(if selected {
+                theme.background_color
+            } else {
+                text_color
+            });
+            self.text_widget
+                .draw(c.trans(0.0, f64::from(row_y)), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,1181 @@
+// Menu Widgets
+// Extensible widgets for the widget library - handles menu bars and popup menus.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the ID of the menu item that was selected.
+pub type MenuCallback = Box<dyn FnMut(&str)>;
+
+/// The default height of a `MenuBarWidget`, in pixels.
+pub const DEFAULT_MENU_BAR_HEIGHT: i32 = 28;
+
+/// The height of an item of a `PopupMenuWidget`, in pixels.
+pub const MENU_ITEM_HEIGHT: i32 = 24;
+
+/// The height of a separator of a `PopupMenuWidget`, in pixels.
+pub const MENU_SEPARATOR_HEIGHT: i32 = 9;
+
+/// The number of pixels between the edges of a menu title or item and its text.
+const MENU_TEXT_PADDING: i32 = 10;
+
+/// The width of the space at the right of a menu item that is kept for the arrow of a submenu.
+const MENU_ARROW_SPACE: i32 = 16;
+
+/// The narrowest that a `PopupMenuWidget` is drawn, in pixels.
+const MENU_MIN_WIDTH: i32 = 120;
+
+/// How far the background of a hovered menu title is blended toward the accent color of the
+/// current `Theme`.
+const MENU_HOVER_AMOUNT: f32 = 0.2;
+
+/// The number that is given to the name of the next popup menu that is added to a `WidgetStore`,
+/// so that the names of popup menus are unique.
+static NEXT_POPUP_NUMBER: AtomicUsize = AtomicUsize::new(1);
+
+/// An entry of a `Menu`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuItem {
+    /// An item that can be selected, identified by `id`, and shown as `label`.  A disabled item
+    /// is shown, but cannot be selected.
+    Item {
+        id: String,
+        label: String,
+        disabled: bool,
+    },
+
+    /// A line between groups of items.
+    Separator,
+
+    /// An item that opens another menu to its side, shown with the title of the menu.
+    Submenu(Menu),
+}
+
+/// Implementation of the `MenuItem`.
+impl MenuItem {
+    /// Indicates whether the item can be selected: an enabled item, or a submenu that has items.
+    pub fn is_selectable(&self) -> bool {
+        match self {
+            MenuItem::Item { disabled, .. } => !disabled,
+            MenuItem::Separator => false,
+            MenuItem::Submenu(menu) => !menu.items.is_empty(),
+        }
+    }
+
+    /// Retrieves the text that the item is shown as, which is empty for a separator.
+    pub fn get_label(&self) -> &str {
+        match self {
+            MenuItem::Item { label, .. } => label,
+            MenuItem::Separator => "",
+            MenuItem::Submenu(menu) => menu.get_title(),
+        }
+    }
+}
+
+/// This is a `Menu`, which has a title, and a list of `MenuItem`s.  Menus are built up by
+/// chaining calls, and given to a `MenuBarWidget`, or shown with a `PopupMenuWidget`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::menu_widget::*;
+/// # fn main() {
+///    let menu = Menu::new("File")
+///        .item("open", "Open…")
+///        .submenu(Menu::new("Recent").item("recent-1", "notes.txt"))
+///        .separator()
+///        .disabled_item("save", "Save")
+///        .item("quit", "Quit");
+///
+///    assert_eq!(menu.get_title(), "File");
+///    assert_eq!(menu.get_items().len(), 5);
+///    assert_eq!(menu.get_items()[1].get_label(), "Recent");
+///    assert!(!menu.get_items()[3].is_selectable());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Menu {
+    title: String,
+    items: Vec<MenuItem>,
+}
+
+/// Implementation of the `Menu`.
+impl Menu {
+    /// Creates a new menu with a `title`, and no items.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Adds an item that is identified by `id`, and shown as `label`.
+    pub fn item(mut self, id: &str, label: &str) -> Self {
+        self.items.push(MenuItem::Item {
+            id: id.to_string(),
+            label: label.to_string(),
+            disabled: false,
+        });
+        self
+    }
+
+    /// Adds an item that is shown in the disabled color of the current `Theme`, and cannot be
+    /// selected.
+    pub fn disabled_item(mut self, id: &str, label: &str) -> Self {
+        self.items.push(MenuItem::Item {
+            id: id.to_string(),
+            label: label.to_string(),
+            disabled: true,
+        });
+        self
+    }
+
+    /// Adds a separator.
+    pub fn separator(mut self) -> Self {
+        self.items.push(MenuItem::Separator);
+        self
+    }
+
+    /// Adds an item that opens `menu` to the side.
+    pub fn submenu(mut self, menu: Menu) -> Self {
+        self.items.push(MenuItem::Submenu(menu));
+        self
+    }
+
+    /// Retrieves the title of the menu.
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Retrieves the items of the menu.
+    pub fn get_items(&self) -> &[MenuItem] {
+        &self.items
+    }
+}
+
+/// Returns the index of the next item after `from` that can be selected, wrapping around, or
+/// the first one if `from` is `None`.  If `forward` is `false`, this searches backward, from
+/// the last item.  Returns `None` if no item can be selected.  This is how the `Up` and `Down`
+/// keys move through a menu.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::menu_widget::*;
+/// # fn main() {
+///    let menu = Menu::new("File")
+///        .item("open", "Open")
+///        .separator()
+///        .disabled_item("save", "Save")
+///        .item("quit", "Quit");
+///    let items = menu.get_items();
+///
+///    assert_eq!(next_selectable_item(items, None, true), Some(0));
+///    assert_eq!(next_selectable_item(items, Some(0), true), Some(3));
+///    assert_eq!(next_selectable_item(items, Some(3), true), Some(0));
+///    assert_eq!(next_selectable_item(items, None, false), Some(3));
+///    assert_eq!(next_selectable_item(&[], None, true), None);
+/// # }
+/// ```
+pub fn next_selectable_item(
+    items: &[MenuItem],
+    from: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    let count = items.len();
+
+    (1..=count)
+        .map(|step| match (from, forward) {
+            (Some(from), true) => (from + step) % count,
+            (Some(from), false) => (from + count - step) % count,
+            (None, true) => step - 1,
+            (None, false) => count - step,
+        })
+        .find(|index| items[*index].is_selectable())
+}
+
+/// The state that a `MenuBarWidget` shares with the `PopupMenuWidget`s that it opens.
+#[derive(Default)]
+struct MenuState {
+    /// The closure that is called with the ID of the selected item.
+    on_item_selected: Option<MenuCallback>,
+
+    /// The index of the title of the menu bar whose menu is open.
+    open_title: Option<usize>,
+
+    /// The direction in which the menu bar is asked to open the next menu, from the keyboard.
+    switch_by: i32,
+
+    /// For each open submenu, the row of the item that opened it, and its ID, or
+    /// `WidgetId::INVALID` until it has been added to the `WidgetStore`.  The first entry belongs
+    /// to the first popup menu.
+    cascade: Vec<(usize, WidgetId)>,
+}
+
+type SharedMenuState = Rc<RefCell<MenuState>>;
+
+/// Queues `callback` to be called with the `WidgetStore` once the current update is over.
+fn queue_callback<F>(deferred: &mut Vec<DeferredCallback>, callback: F)
+where
+    F: FnMut(&mut WidgetStore) + 'static,
+{
+    deferred.push(Rc::new(RefCell::new(Box::new(callback))));
+}
+
+/// Closes any popups that were opened after the popup with the ID `popup_id`.
+fn close_popups_after(widget_store: &mut WidgetStore, popup_id: WidgetId) {
+    let popups = widget_store.get_open_popups();
+
+    if let Some(index) = popups.iter().position(|widget_id| *widget_id == popup_id) {
+        if let Some(next_id) = popups.get(index + 1) {
+            widget_store.close_popup(*next_id);
+        }
+    }
+}
+
+/// This is the `PopupMenuWidget`, which shows the items of a `Menu` in a column, above all other
+/// `Widget`s.  The item under the mouse is highlighted in the accent color of the current
+/// `Theme`, and clicking it calls the `on_item_selected` callback with its ID, and closes the
+/// menu.  Hovering over a submenu opens it to the side, as another `PopupMenuWidget`.
+///
+/// While the menu is open, it has the keyboard focus: `Up` and `Down` move through the items,
+/// `Right` opens a submenu, `Left` closes one, `Enter` selects the highlighted item, and
+/// `Escape` closes the menu.  The menu also closes when the mouse is pressed anywhere outside of
+/// it; see `WidgetStore::open_popup`.  A closed menu removes itself from the `WidgetStore`.
+///
+/// The `MenuBarWidget` opens these when its titles are clicked.  To show one anywhere else, such
+/// as for a context menu, create it and `open` it at a point:
+///
+/// ```no_run
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::font_cache::*;
+/// # use pushrod::widget::menu_widget::*;
+/// # fn open_context_menu(widget_store: &mut WidgetStore, x: i32, y: i32) {
+///    let font = get_font("OpenSans-Regular.ttf").unwrap();
+///    let menu = Menu::new("Edit").item("cut", "Cut").item("copy", "Copy");
+///    let mut popup = PopupMenuWidget::from_font(font, menu);
+///
+///    popup.on_item_selected(Box::new(|item_id| eprintln!("Selected {}", item_id)));
+///    popup.open(widget_store, x, y);
+/// # }
+/// # fn main() {}
+/// ```
+pub struct PopupMenuWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    text_widget: Box<TextWidget>,
+    menu: Menu,
+    hovered: Option<usize>,
+    depth: usize,
+    title: Option<usize>,
+    state: SharedMenuState,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `PopupMenuWidget`.
+impl PopupMenuWidget {
+    /// Creates a new popup menu that shows the items of `menu`, drawn in a font from the font
+    /// cache, in the font size of the current `Theme`.  It is sized to fit its items.
+    pub fn from_font(font: SharedGlyphs, menu: Menu) -> Self {
+        Self::with_state(
+            font,
+            menu,
+            Rc::new(RefCell::new(MenuState::default())),
+            0,
+            None,
+        )
+    }
+
+    /// Creates a new popup menu that shares `state` with the rest of its menus, at `depth` in
+    /// the cascade of submenus, for the title `title` of a menu bar.
+    fn with_state(
+        font: SharedGlyphs,
+        menu: Menu,
+        state: SharedMenuState,
+        depth: usize,
+        title: Option<usize>,
+    ) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font.clone(), String::new(), font_size, TextJustify::Left);
+
+        text_widget.set_padding(make_insets(0, MENU_TEXT_PADDING, 0, MENU_TEXT_PADDING));
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            font,
+            text_widget: Box::new(text_widget),
+            menu,
+            hovered: None,
+            depth,
+            title,
+            state,
+            deferred: Vec::new(),
+        };
+        let size = widget.get_fitted_size();
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(true, true);
+        widget.set_size(size.w, size.h);
+        widget
+    }
+
+    /// Sets the closure that is called with the ID of the item that is selected, from this menu
+    /// or any of its submenus.
+    pub fn on_item_selected(&mut self, callback: MenuCallback) {
+        self.state.borrow_mut().on_item_selected = Some(callback);
+    }
+
+    /// Retrieves the menu whose items are shown.
+    pub fn get_menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    /// Retrieves the index of the highlighted item, or `None` if no item is highlighted.
+    pub fn get_hovered_item(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Adds the popup menu to the base widget of `widget_store` with its top left corner at
+    /// `x` and `y`, moved as little as possible to fit inside of the window, and opens it with
+    /// the keyboard focus.  Returns the ID of the popup menu.
+    pub fn open(mut self, widget_store: &mut WidgetStore, x: i32, y: i32) -> WidgetId {
+        let size = self.get_size();
+        let window_size = widget_store
+            .get_widget_for_id(WidgetId::ROOT)
+            .borrow_mut()
+            .get_size();
+        let number = NEXT_POPUP_NUMBER.fetch_add(1, Ordering::Relaxed);
+
+        self.set_origin(
+            x.min(window_size.w - size.w).max(0),
+            y.min(window_size.h - size.h).max(0),
+        );
+
+        let widget_id =
+            widget_store.add_widget(&format!("_PushrodPopupMenu{}", number), Box::new(self));
+
+        widget_store.open_popup(widget_id);
+        widget_store.set_focused_widget(widget_id);
+        widget_id
+    }
+
+    /// Returns the size that fits the widest item, and all of the items.
+    fn get_fitted_size(&mut self) -> crate::core::point::Size {
+        let font_size = self.text_widget.get_font_size();
+        let mut glyphs = self.font.borrow_mut();
+        let label_w = self
+            .menu
+            .items
+            .iter()
+            .map(|item| text_width(&mut glyphs, font_size, item.get_label()))
+            .max()
+            .unwrap_or(0);
+        let items_h: i32 = self.menu.items.iter().map(get_item_height).sum();
+
+        crate::core::point::Size {
+            w: (label_w + MENU_TEXT_PADDING * 2 + MENU_ARROW_SPACE).max(MENU_MIN_WIDTH),
+            h: items_h + 2,
+        }
+    }
+
+    /// Returns the top of each item, inside of the border.
+    fn get_item_tops(&self) -> Vec<i32> {
+        self.menu
+            .items
+            .iter()
+            .scan(1, |top, item| {
+                let item_top = *top;
+
+                *top += get_item_height(item);
+                Some(item_top)
+            })
+            .collect()
+    }
+
+    /// Returns the index of the item at the height `y` of the widget, or `None` if there is no
+    /// item there.
+    fn get_item_at(&self, y: i32) -> Option<usize> {
+        self.get_item_tops()
+            .iter()
+            .zip(self.menu.items.iter())
+            .position(|(top, item)| y >= *top && y < top + get_item_height(item))
+    }
+
+    fn set_hovered(&mut self, hovered: Option<usize>) {
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.invalidate();
+        }
+    }
+
+    /// Returns the row of the item whose submenu is open, if any.
+    fn get_open_submenu(&self) -> Option<usize> {
+        self.state
+            .borrow()
+            .cascade
+            .get(self.depth)
+            .map(|(row, _)| *row)
+    }
+
+    /// Opens the submenu of the item at `row` to the side of this menu, closing any other
+    /// submenu.  If `focus` is `true`, the submenu takes the keyboard focus, with its first
+    /// item highlighted.
+    fn open_submenu(&mut self, popup_id: WidgetId, row: usize, focus: bool) {
+        let menu = match &self.menu.items[row] {
+            MenuItem::Submenu(menu) if !menu.items.is_empty() => menu.clone(),
+            _ => return,
+        };
+
+        if self.get_open_submenu() == Some(row) {
+            return;
+        }
+
+        self.close_submenu(popup_id);
+        self.state
+            .borrow_mut()
+            .cascade
+            .push((row, WidgetId::INVALID));
+
+        let mut submenu = PopupMenuWidget::with_state(
+            self.font.clone(),
+            menu,
+            self.state.clone(),
+            self.depth + 1,
+            None,
+        );
+
+        if focus {
+            submenu.hovered = next_selectable_item(&submenu.menu.items, None, true);
+        }
+
+        let submenu_w = submenu.get_size().w;
+        let width = self.get_size().w;
+        let top = self.get_item_tops()[row] - 1;
+        let state = self.state.clone();
+        let depth = self.depth;
+        let mut submenu = Some(submenu);
+
+        queue_callback(&mut self.deferred, move |widget_store| {
+            let submenu = match submenu.take() {
+                Some(submenu) => submenu,
+                None => return,
+            };
+            let origin = widget_store.get_absolute_origin(popup_id);
+            let window_w = widget_store
+                .get_widget_for_id(WidgetId::ROOT)
+                .borrow_mut()
+                .get_size()
+                .w;
+            let x = if origin.x + width + submenu_w > window_w {
+                origin.x - submenu_w
+            } else {
+                origin.x + width
+            };
+            let submenu_id = submenu.open(widget_store, x, origin.y + top);
+
+            if let Some(entry) = state.borrow_mut().cascade.get_mut(depth) {
+                entry.1 = submenu_id;
+            }
+
+            if !focus {
+                widget_store.set_focused_widget(popup_id);
+            }
+        });
+    }
+
+    /// Closes the open submenu of this menu, if any.
+    fn close_submenu(&mut self, popup_id: WidgetId) {
+        let mut state = self.state.borrow_mut();
+
+        if state.cascade.len() > self.depth {
+            state.cascade.truncate(self.depth);
+            queue_callback(&mut self.deferred, move |widget_store| {
+                close_popups_after(widget_store, popup_id)
+            });
+        }
+    }
+
+    /// Closes this menu, and its submenus.  A submenu gives the keyboard focus back to the menu
+    /// that opened it.
+    fn close(&mut self, popup_id: WidgetId) {
+        queue_callback(&mut self.deferred, move |widget_store| {
+            let popups = widget_store.get_open_popups();
+            let parent_id = popups
+                .iter()
+                .position(|widget_id| *widget_id == popup_id)
+                .and_then(|index| index.checked_sub(1))
+                .map(|index| popups[index]);
+
+            widget_store.close_popup(popup_id);
+
+            if let Some(parent_id) = parent_id {
+                widget_store.set_focused_widget(parent_id);
+            }
+        });
+    }
+
+    /// Selects the item at `row`: an item calls the `on_item_selected` callback, and closes all
+    /// of the menus, and a submenu is opened with the keyboard focus.
+    fn activate(&mut self, popup_id: WidgetId, row: usize) {
+        let item_id = match &self.menu.items[row] {
+            MenuItem::Item {
+                id,
+                disabled: false,
+                ..
+            } => id.clone(),
+            MenuItem::Submenu(_) => return self.open_submenu(popup_id, row, true),
+            _ => return,
+        };
+
+        if let Some(callback) = &mut self.state.borrow_mut().on_item_selected {
+            callback(&item_id);
+        }
+
+        queue_callback(&mut self.deferred, |widget_store| {
+            widget_store.close_all_popups()
+        });
+    }
+
+    /// Moves the highlight in response to the mouse, opening the submenu under it, or closing
+    /// the open submenu when another item is highlighted.
+    fn hover(&mut self, popup_id: WidgetId, point: &Point) {
+        let row = if point.x >= 0 && point.x < self.get_size().w {
+            self.get_item_at(point.y)
+        } else {
+            None
+        };
+        let row = row.filter(|row| self.menu.items[*row].is_selectable());
+
+        self.set_hovered(row);
+
+        match row.map(|row| &self.menu.items[row]) {
+            Some(MenuItem::Submenu(_)) => self.open_submenu(popup_id, row.unwrap(), false),
+            Some(_) => self.close_submenu(popup_id),
+            None => (),
+        }
+    }
+
+    /// Handles a key press while the menu has the keyboard focus.
+    fn press_key(&mut self, popup_id: WidgetId, key: Key) {
+        match key {
+            Key::Up | Key::Down => {
+                let row = next_selectable_item(&self.menu.items, self.hovered, key == Key::Down);
+
+                self.set_hovered(row);
+            }
+            Key::Right => match self.hovered {
+                Some(row) if matches!(self.menu.items[row], MenuItem::Submenu(_)) => {
+                    self.open_submenu(popup_id, row, true)
+                }
+                _ if self.depth == 0 => self.state.borrow_mut().switch_by = 1,
+                _ => (),
+            },
+            Key::Left if self.depth > 0 => self.close(popup_id),
+            Key::Left => self.state.borrow_mut().switch_by = -1,
+            Key::Return | Key::Space => {
+                if let Some(row) = self.hovered {
+                    self.activate(popup_id, row);
+                }
+            }
+            Key::Escape => self.close(popup_id),
+            _ => (),
+        }
+    }
+}
+
+/// Returns the height of `item` in a `PopupMenuWidget`.
+fn get_item_height(item: &MenuItem) -> i32 {
+    match item {
+        MenuItem::Separator => MENU_SEPARATOR_HEIGHT,
+        _ => MENU_ITEM_HEIGHT,
+    }
+}
+
+/// Implementation of the `PopupMenuWidget` object with the `Widget` traits implemented.  The
+/// items are drawn with a single `TextWidget`, which is given the label of each item in turn.
+impl Widget for PopupMenuWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Menu
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget
+            .set_size(w - MENU_ARROW_SPACE, MENU_ITEM_HEIGHT);
+        self.invalidate();
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        match event {
+            CallbackEvent::MouseMoved {
+                widget_id, point, ..
+            } => self.hover(widget_id, &point),
+
+            CallbackEvent::MouseExited { .. } => {
+                let row = self.get_open_submenu();
+
+                self.set_hovered(row);
+            }
+
+            CallbackEvent::MouseButtonUpInside {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                point,
+            } => {
+                if let Some(row) = self.get_item_at(point.y) {
+                    self.activate(widget_id, row);
+                }
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key,
+                state: ButtonState::Press,
+                ..
+            } => self.press_key(widget_id, key),
+
+            CallbackEvent::PopupClosed { widget_id } => {
+                {
+                    let mut state = self.state.borrow_mut();
+
+                    if let Some(index) = state.cascade.iter().position(|(_, id)| *id == widget_id) {
+                        state.cascade.truncate(index);
+                    }
+
+                    if self.depth == 0 {
+                        state.cascade.clear();
+
+                        if state.open_title == self.title {
+                            state.open_title = None;
+                        }
+                    }
+                }
+
+                self.hovered = None;
+                queue_callback(&mut self.deferred, move |widget_store| {
+                    widget_store.remove_widget(widget_id)
+                });
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background and the border, and then each item: the highlighted item in the
+    /// accent color of the current `Theme`, disabled items in its disabled color, separators as
+    /// lines, and submenus with an arrow.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+
+        Rectangle::new(apply_opacity(theme.background_color)).draw(
+            [0.0, 0.0, w, h],
+            clip,
+            c.transform,
+            g,
+        );
+        Rectangle::new_border(apply_opacity(theme.border_color), 0.5).draw(
+            [0.5, 0.5, w - 1.0, h - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let items = self.menu.items.clone();
+
+        for (row, (item, top)) in items.iter().zip(self.get_item_tops()).enumerate() {
+            let top = f64::from(top);
+
+            if let MenuItem::Separator = item {
+                let middle = top + f64::from(MENU_SEPARATOR_HEIGHT / 2);
+
+                Rectangle::new(apply_opacity(theme.border_color)).draw(
+                    [4.0, middle, w - 8.0, 1.0],
+                    clip,
+                    c.transform,
+                    g,
+                );
+                continue;
+            }
+
+            let hovered = self.hovered == Some(row);
+            let text_color = if !item.is_selectable() {
+                theme.disabled_color
+            } else if hovered {
+                theme.background_color
+            } else {
+                theme.text_color
+            };
+
+            if hovered {
+                Rectangle::new(apply_opacity(theme.accent_color)).draw(
+                    [1.0, top, w - 2.0, f64::from(MENU_ITEM_HEIGHT)],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            if let MenuItem::Submenu(_) = item {
+                let middle = top + f64::from(MENU_ITEM_HEIGHT) / 2.0;
+                let arrow_x = w - f64::from(MENU_ARROW_SPACE);
+
+                Polygon::new(apply_opacity(text_color)).draw(
+                    &[
+                        [arrow_x, middle - 4.0],
+                        [arrow_x + 4.0, middle],
+                        [arrow_x, middle + 4.0],
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            self.text_widget.set_text(item.get_label());
+            self.text_widget.set_text_color(text_color);
+            self.text_widget.draw(c.trans(0.0, top), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+/// This is the `MenuBarWidget`, which draws the titles of its `Menu`s in a row.  Clicking a
+/// title opens its menu below it as a `PopupMenuWidget`, and while a menu is open, moving the
+/// mouse over another title opens that menu instead, as do `Left` and `Right` from the keyboard.
+/// The title of the open menu is drawn in the accent color of the current `Theme`.
+///
+/// The `on_item_selected` callback is called with the ID of the item that is selected from any
+/// of the menus, or their submenus.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::menu_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Menus", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut menu_bar =
+///        MenuBarWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///
+///    menu_bar.set_origin(0, 0);
+///    menu_bar.set_size(400, DEFAULT_MENU_BAR_HEIGHT);
+///    menu_bar.add_menu(
+///        Menu::new("File")
+///            .item("open", "Open…")
+///            .separator()
+///            .item("quit", "Quit"),
+///    );
+///    menu_bar.add_menu(Menu::new("Edit").item("cut", "Cut").disabled_item("paste", "Paste"));
+///    menu_bar.on_item_selected(Box::new(|item_id| eprintln!("Selected {}", item_id)));
+///    pushrod.add_widget("MenuBar", Box::new(menu_bar));
+/// # }
+/// ```
+pub struct MenuBarWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    text_widget: Box<TextWidget>,
+    menus: Vec<Menu>,
+    hovered_title: Option<usize>,
+    shown_open_title: Option<usize>,
+    widget_id: WidgetId,
+    state: SharedMenuState,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `MenuBarWidget`.
+impl MenuBarWidget {
+    /// Creates a new menu bar without any menus, drawn with the font `font_name` from the
+    /// `assets` directory, in the font size of the current `Theme`.  Panics if the font cannot
+    /// be loaded; use `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs)
+    }
+
+    /// Creates a new menu bar without any menus, drawn in a font from the font cache.
+    pub fn from_font(font: SharedGlyphs) -> Self {
+        let font_size = get_current_theme().font_size;
+
+        Self {
+            config: Configurable::new(),
+            font: font.clone(),
+            text_widget: Box::new(TextWidget::from_font(
+                font,
+                String::new(),
+                font_size,
+                TextJustify::Center,
+            )),
+            menus: Vec::new(),
+            hovered_title: None,
+            shown_open_title: None,
+            widget_id: WidgetId::INVALID,
+            state: Rc::new(RefCell::new(MenuState::default())),
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Adds `menu` after the other menus.  Invalidates the widget afterward.
+    pub fn add_menu(&mut self, menu: Menu) {
+        self.menus.push(menu);
+        self.invalidate();
+    }
+
+    /// Retrieves the menus of the menu bar.
+    pub fn get_menus(&self) -> &[Menu] {
+        &self.menus
+    }
+
+    /// Sets the closure that is called with the ID of the item that is selected.
+    pub fn on_item_selected(&mut self, callback: MenuCallback) {
+        self.state.borrow_mut().on_item_selected = Some(callback);
+    }
+
+    /// Retrieves the index of the menu that is open, or `None` if no menu is open.
+    pub fn get_open_menu(&self) -> Option<usize> {
+        self.state.borrow().open_title
+    }
+
+    /// Returns the left edge and the width of each title.
+    fn get_title_bounds(&self) -> Vec<(i32, i32)> {
+        let font_size = self.text_widget.get_font_size();
+        let mut glyphs = self.font.borrow_mut();
+
+        self.menus
+            .iter()
+            .scan(0, |x, menu| {
+                let title_x = *x;
+                let title_w =
+                    text_width(&mut glyphs, font_size, &menu.title) + MENU_TEXT_PADDING * 2;
+
+                *x += title_w;
+                Some((title_x, title_w))
+            })
+            .collect()
+    }
+
+    /// Returns the index of the title at `point` of the widget, or `None` if there is no title
+    /// there.
+    fn get_title_at(&self, point: &Point) -> Option<usize> {
+        if point.y < 0 || point.y >= self.config.get_or(BodySize(make_unsized())).0.h {
+            return None;
+        }
+
+        self.get_title_bounds()
+            .iter()
+            .position(|(x, w)| point.x >= *x && point.x < x + w)
+    }
+
+    /// Opens the menu of the title `index` below it, closing any menu that is already open.  If
+    /// `select_first` is `true`, the first item of the menu is highlighted.
+    fn open_menu(&mut self, index: usize, select_first: bool) {
+        let bar_id = self.widget_id;
+
+        if !bar_id.is_valid() || index >= self.menus.len() {
+            return;
+        }
+
+        self.state.borrow_mut().open_title = Some(index);
+        self.update_open_title();
+
+        let mut popup = PopupMenuWidget::with_state(
+            self.font.clone(),
+            self.menus[index].clone(),
+            self.state.clone(),
+            0,
+            Some(index),
+        );
+
+        if select_first {
+            popup.hovered = next_selectable_item(&popup.menu.items, None, true);
+        }
+
+        let (title_x, _) = self.get_title_bounds()[index];
+        let bar_h = self.get_size().h;
+        let mut popup = Some(popup);
+
+        queue_callback(&mut self.deferred, move |widget_store| {
+            if let Some(popup) = popup.take() {
+                let origin = widget_store.get_absolute_origin(bar_id);
+
+                widget_store.close_all_popups();
+                popup.open(widget_store, origin.x + title_x, origin.y + bar_h);
+            }
+        });
+    }
+
+    /// Redraws the titles if the menu that is open has changed.
+    fn update_open_title(&mut self) {
+        let open_title = self.state.borrow().open_title;
+
+        if open_title != self.shown_open_title {
+            self.shown_open_title = open_title;
+            self.invalidate();
+        }
+    }
+
+    fn set_hovered_title(&mut self, title: Option<usize>) {
+        if title != self.hovered_title {
+            self.hovered_title = title;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `MenuBarWidget` object with the `Widget` traits implemented.  The
+/// titles are drawn with a single `TextWidget`, which is given each title in turn.
+impl Widget for MenuBarWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Menu
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the current
+    /// width, and `DEFAULT_MENU_BAR_HEIGHT`, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => crate::core::point::Size {
+                w: self.get_size().w,
+                h: DEFAULT_MENU_BAR_HEIGHT,
+            },
+        }
+    }
+
+    /// Opens the next or the previous menu when it is asked to from the keyboard, and redraws
+    /// the titles when a menu is closed.
+    fn update(&mut self, _delta_ms: u64) {
+        let (open_title, switch_by) = {
+            let mut state = self.state.borrow_mut();
+
+            (state.open_title, std::mem::replace(&mut state.switch_by, 0))
+        };
+
+        if let Some(open_title) = open_title {
+            if switch_by != 0 && !self.menus.is_empty() {
+                let count = self.menus.len() as i32;
+                let index = (open_title as i32 + switch_by).rem_euclid(count) as usize;
+
+                self.open_menu(index, true);
+            }
+        }
+
+        self.update_open_title();
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseEntered { widget_id } => self.widget_id = widget_id,
+
+            CallbackEvent::MouseMoved {
+                widget_id, point, ..
+            } => {
+                let title = self.get_title_at(&point);
+                let open_title = self.state.borrow().open_title;
+
+                self.widget_id = widget_id;
+                self.set_hovered_title(title);
+
+                if let (Some(title), Some(open_title)) = (title, open_title) {
+                    if title != open_title {
+                        self.open_menu(title, false);
+                    }
+                }
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered_title(None),
+
+            CallbackEvent::MouseButtonDown {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                point,
+            } => {
+                self.widget_id = widget_id;
+
+                if let Some(title) = self.get_title_at(&point) {
+                    self.open_menu(title, false);
+                }
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Enables or disables this widget.  The titles of a disabled menu bar are drawn in the
+    /// disabled color of the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.hovered_title = None;
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Draws the background, a line along the bottom, and then each title: the title of the
+    /// open menu in the accent color of the current `Theme`, and the hovered title in the
+    /// background color blended toward it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let background = self.get_color();
+        let mut hover_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * MENU_HOVER_AMOUNT;
+        }
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+        Rectangle::new(apply_opacity(theme.border_color)).draw(
+            [0.0, f64::from(size.h) - 1.0, f64::from(size.w), 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let open_title = self.shown_open_title;
+
+        for (index, (title_x, title_w)) in self.get_title_bounds().into_iter().enumerate() {
+            let open = open_title == Some(index);
+            let title_color = if open {
+                Some(theme.accent_color)
+            } else if self.hovered_title == Some(index) {
+                Some(hover_color)
+            } else {
+                None
+            };
+
+            if let Some(title_color) = title_color {
+                Rectangle::new(apply_opacity(title_color)).draw(
+                    [
+                        f64::from(title_x),
+                        0.0,
+                        f64::from(title_w),
+                        f64::from(size.h - 1),
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            let title = self.menus[index].title.clone();
+
+            self.text_widget.set_size(title_w, size.h);
+            self.text_widget.set_text(title);
+            self.text_widget.set_text_color(if open {
+                theme.background_color
+            } else {
+                theme.text_color
+            });
+            self.text_widget
+                .draw(c.trans(f64::from(title_x), 0.0), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,483 @@
+// Message Box
+// Extensible widget for the widget library - handles ready-made dialogs for messages and questions.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::draw_target::*;
+use crate::core::point::Size;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::button_widget::*;
+use crate::widget::config::*;
+use crate::widget::dialog_widget::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the button that closed a `MessageBox`.
+pub type MessageBoxCallback = Box<dyn FnMut(MessageBoxResult)>;
+
+/// The space between the edges of a `MessageBox` and its contents, and between its icon, its
+/// message, and its buttons, in pixels.
+pub const MESSAGE_BOX_PADDING: i32 = 16;
+
+/// The widest that the message of a `MessageBox` is drawn before it is wrapped, in pixels.
+pub const MESSAGE_BOX_TEXT_WIDTH: i32 = 360;
+
+/// The width and height of the icon of a `MessageBox`, in pixels.
+pub const MESSAGE_BOX_ICON_SIZE: i32 = 32;
+
+/// The size of each button of a `MessageBox`, in pixels.
+pub const MESSAGE_BOX_BUTTON_SIZE: (i32, i32) = (80, 28);
+
+/// The space between the buttons of a `MessageBox`, in pixels.
+const MESSAGE_BOX_BUTTON_GAP: i32 = 8;
+
+/// The narrowest that a `MessageBox` is made, in pixels.
+const MESSAGE_BOX_MIN_WIDTH: i32 = 240;
+
+/// The kind of message that a `MessageBox` shows, which determines its icon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageBoxKind {
+    /// Information, shown with an `i` in the accent color of the current `Theme`.
+    Info,
+
+    /// A warning, shown with an `!` in amber.
+    Warning,
+
+    /// An error, shown with an `x` in red.
+    Error,
+
+    /// A question, shown with a `?` in the accent color of the current `Theme`.
+    Question,
+}
+
+/// The button that closed a `MessageBox`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageBoxResult {
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+/// Implementation of the `MessageBoxResult`.
+impl MessageBoxResult {
+    /// Returns the result that `DialogWidget` closes with for this button.
+    pub fn get_id(self) -> &'static str {
+        match self {
+            MessageBoxResult::Ok => "ok",
+            MessageBoxResult::Cancel => "cancel",
+            MessageBoxResult::Yes => "yes",
+            MessageBoxResult::No => "no",
+        }
+    }
+
+    /// Returns the button for a result of a `DialogWidget`, or `None` if it is not the result
+    /// of a button of a `MessageBox`.
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "ok" => Some(MessageBoxResult::Ok),
+            "cancel" => Some(MessageBoxResult::Cancel),
+            "yes" => Some(MessageBoxResult::Yes),
+            "no" => Some(MessageBoxResult::No),
+            _ => None,
+        }
+    }
+
+    /// Returns the label of the button.
+    pub fn get_label(self) -> &'static str {
+        match self {
+            MessageBoxResult::Ok => "OK",
+            MessageBoxResult::Cancel => "Cancel",
+            MessageBoxResult::Yes => "Yes",
+            MessageBoxResult::No => "No",
+        }
+    }
+}
+
+/// The buttons that a `MessageBox` is closed with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageBoxButtons {
+    Ok,
+    OkCancel,
+    YesNo,
+    YesNoCancel,
+}
+
+/// Implementation of the `MessageBoxButtons`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::message_box::*;
+/// # fn main() {
+///    let buttons = MessageBoxButtons::YesNoCancel;
+///
+///    assert_eq!(
+///        buttons.get_results(),
+///        vec![MessageBoxResult::Yes, MessageBoxResult::No, MessageBoxResult::Cancel]
+///    );
+///    assert_eq!(buttons.get_default_result(), MessageBoxResult::Yes);
+///    assert_eq!(buttons.get_cancel_result(), MessageBoxResult::Cancel);
+///    assert_eq!(MessageBoxButtons::YesNo.get_cancel_result(), MessageBoxResult::No);
+///    assert_eq!(MessageBoxButtons::Ok.get_cancel_result(), MessageBoxResult::Ok);
+/// # }
+/// ```
+impl MessageBoxButtons {
+    /// Returns the buttons, in the order in which they are shown, from left to right.
+    pub fn get_results(self) -> Vec<MessageBoxResult> {
+        match self {
+            MessageBoxButtons::Ok => vec![MessageBoxResult::Ok],
+            MessageBoxButtons::OkCancel => vec![MessageBoxResult::Ok, MessageBoxResult::Cancel],
+            MessageBoxButtons::YesNo => vec![MessageBoxResult::Yes, MessageBoxResult::No],
+            MessageBoxButtons::YesNoCancel => vec![
+                MessageBoxResult::Yes,
+                MessageBoxResult::No,
+                MessageBoxResult::Cancel,
+            ],
+        }
+    }
+
+    /// Returns the button that `Enter` activates: the first button.
+    pub fn get_default_result(self) -> MessageBoxResult {
+        self.get_results()[0]
+    }
+
+    /// Returns the button that `Escape` activates: the last button.
+    pub fn get_cancel_result(self) -> MessageBoxResult {
+        *self.get_results().last().unwrap_or(&MessageBoxResult::Ok)
+    }
+}
+
+/// The positions of the contents of a `MessageBox`, relative to the message box, as returned by
+/// `get_message_box_layout`.  Each rectangle is X, Y, width, and height.
+#[derive(Clone, Debug)]
+pub struct MessageBoxLayout {
+    pub size: Size,
+    pub icon: [i32; 4],
+    pub message: [i32; 4],
+    pub buttons: Vec<[i32; 4]>,
+}
+
+/// Lays out a `MessageBox` with a title bar that is `title_h` pixels high, a message of the size
+/// `message_size`, and `button_count` buttons.  The icon is placed to the left of the message,
+/// and the buttons in a row under both, aligned to the right.  The message box is sized to fit
+/// its contents, but is at least 240 pixels wide.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::message_box::*;
+/// # fn main() {
+///    let layout = get_message_box_layout(30, Size { w: 200, h: 20 }, 2);
+///
+///    assert_eq!((layout.size.w, layout.size.h), (280, 138));
+///    assert_eq!(layout.icon, [16, 46, 32, 32]);
+///    assert_eq!(layout.message, [64, 46, 200, 20]);
+///    assert_eq!(layout.buttons, vec![[96, 94, 80, 28], [184, 94, 80, 28]]);
+/// # }
+/// ```
+pub fn get_message_box_layout(
+    title_h: i32,
+    message_size: Size,
+    button_count: usize,
+) -> MessageBoxLayout {
+    let (button_w, button_h) = MESSAGE_BOX_BUTTON_SIZE;
+    let button_count = button_count as i32;
+    let buttons_w = button_count * button_w + (button_count - 1).max(0) * MESSAGE_BOX_BUTTON_GAP;
+    let content_w = MESSAGE_BOX_ICON_SIZE + MESSAGE_BOX_PADDING + message_size.w;
+    let w = (content_w.max(buttons_w) + MESSAGE_BOX_PADDING * 2).max(MESSAGE_BOX_MIN_WIDTH);
+    let top = title_h + MESSAGE_BOX_PADDING;
+    let content_h = MESSAGE_BOX_ICON_SIZE.max(message_size.h);
+    let buttons_y = top + content_h + MESSAGE_BOX_PADDING;
+    let buttons_x = w - MESSAGE_BOX_PADDING - buttons_w;
+
+    MessageBoxLayout {
+        size: Size {
+            w,
+            h: buttons_y + button_h + MESSAGE_BOX_PADDING,
+        },
+        icon: [
+            MESSAGE_BOX_PADDING,
+            top,
+            MESSAGE_BOX_ICON_SIZE,
+            MESSAGE_BOX_ICON_SIZE,
+        ],
+        message: [
+            MESSAGE_BOX_PADDING * 2 + MESSAGE_BOX_ICON_SIZE,
+            top,
+            message_size.w,
+            message_size.h,
+        ],
+        buttons: (0..button_count)
+            .map(|index| {
+                [
+                    buttons_x + index * (button_w + MESSAGE_BOX_BUTTON_GAP),
+                    buttons_y,
+                    button_w,
+                    button_h,
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// This is the `MessageBox`, which shows a message, or asks a question, in a modal
+/// `DialogWidget` that it lays out itself: an icon for the kind of message, the message,
+/// wrapped to fit, and a row of buttons, aligned to the right.  The message box is sized to fit
+/// its contents, centered in the window, and drawn in the current `Theme`.  The first button is
+/// focused, so `Enter` activates it, and `Escape` activates the last button, such as `Cancel`.
+/// Once a button has been clicked, the message box is closed, and the callback, if any, is
+/// called with the button.
+///
+/// Message boxes are drawn in the font set with `WidgetStore::set_dialog_font`, or
+/// `Pushrod::set_dialog_font`, which must be set before the first message box is shown.  Each
+/// function returns the ID of the `DialogWidget`, like `WidgetStore::show_modal`.
+///
+/// Example usage:
+/// ```no_run
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::message_box::*;
+/// # fn delete_file(widget_store: &mut WidgetStore) {
+///    MessageBox::confirm(
+///        widget_store,
+///        "Delete",
+///        "Are you sure that you want to delete this file?",
+///        Box::new(|result| {
+///            if result == MessageBoxResult::Ok {
+///                eprintln!("Deleting the file");
+///            }
+///        }),
+///    );
+/// # }
+/// # fn main() {}
+/// ```
+pub struct MessageBox {}
+
+/// Implementation of the `MessageBox`.
+impl MessageBox {
+    /// Shows an informational `message`, with an `OK` button.
+    pub fn info(widget_store: &mut WidgetStore, title: &str, message: &str) -> WidgetId {
+        Self::show(
+            widget_store,
+            MessageBoxKind::Info,
+            title,
+            message,
+            MessageBoxButtons::Ok,
+            None,
+        )
+    }
+
+    /// Shows a warning `message`, with an `OK` button.
+    pub fn warning(widget_store: &mut WidgetStore, title: &str, message: &str) -> WidgetId {
+        Self::show(
+            widget_store,
+            MessageBoxKind::Warning,
+            title,
+            message,
+            MessageBoxButtons::Ok,
+            None,
+        )
+    }
+
+    /// Shows an error `message`, with an `OK` button.
+    pub fn error(widget_store: &mut WidgetStore, title: &str, message: &str) -> WidgetId {
+        Self::show(
+            widget_store,
+            MessageBoxKind::Error,
+            title,
+            message,
+            MessageBoxButtons::Ok,
+            None,
+        )
+    }
+
+    /// Asks a question with `message`, with `OK` and `Cancel` buttons, and calls `on_result`
+    /// with the button that was chosen.
+    pub fn confirm(
+        widget_store: &mut WidgetStore,
+        title: &str,
+        message: &str,
+        on_result: MessageBoxCallback,
+    ) -> WidgetId {
+        Self::show(
+            widget_store,
+            MessageBoxKind::Question,
+            title,
+            message,
+            MessageBoxButtons::OkCancel,
+            Some(on_result),
+        )
+    }
+
+    /// Shows a message box of any `kind`, with any `buttons`, and calls `on_result`, if given,
+    /// with the button that was chosen.  Panics if no dialog font has been set.
+    pub fn show(
+        widget_store: &mut WidgetStore,
+        kind: MessageBoxKind,
+        title: &str,
+        message: &str,
+        buttons: MessageBoxButtons,
+        mut on_result: Option<MessageBoxCallback>,
+    ) -> WidgetId {
+        let font = widget_store.get_dialog_font().unwrap_or_else(|| {
+            panic!("No font has been set for message boxes; see WidgetStore::set_dialog_font")
+        });
+        let font_size = get_current_theme().font_size;
+        let wrapped = wrap_text(
+            &mut font.borrow_mut(),
+            font_size,
+            message,
+            MESSAGE_BOX_TEXT_WIDTH,
+        );
+        let mut text_widget =
+            TextWidget::from_font(font.clone(), wrapped, font_size, TextJustify::Left);
+
+        text_widget.set_vertical_align(TextVerticalAlign::Top);
+
+        let mut dialog = DialogWidget::from_font(font.clone(), title.to_string());
+        let results = buttons.get_results();
+        let layout = get_message_box_layout(
+            dialog.get_content_rect()[1],
+            text_widget.get_text_size(),
+            results.len(),
+        );
+        let cancel_result = buttons.get_cancel_result();
+
+        dialog.set_size(layout.size.w, layout.size.h);
+        dialog.on_closed(Box::new(move |result, _| {
+            let result = result
+                .as_deref()
+                .and_then(MessageBoxResult::from_id)
+                .unwrap_or(cancel_result);
+
+            if let Some(callback) = &mut on_result {
+                callback(result);
+            }
+        }));
+
+        let closer = dialog.get_closer();
+        let dialog_id = widget_store.show_modal(dialog);
+        let mut icon = MessageIconWidget::new(font.clone(), kind);
+        let [x, y, w, h] = layout.icon;
+
+        icon.set_origin(x, y);
+        icon.set_size(w, h);
+        widget_store.add_widget_to_parent("_MessageBoxIcon", Box::new(icon), dialog_id);
+
+        let [x, y, w, h] = layout.message;
+
+        text_widget.set_origin(x, y);
+        text_widget.set_size(w, h);
+        widget_store.add_widget_to_parent("_MessageBoxText", Box::new(text_widget), dialog_id);
+
+        let mut default_id = WidgetId::INVALID;
+
+        for (result, [x, y, w, h]) in results.into_iter().zip(layout.buttons) {
+            let mut button =
+                ButtonWidget::from_font(font.clone(), result.get_label().to_string(), font_size);
+            let closer = closer.clone();
+
+            button.set_origin(x, y);
+            button.set_size(w, h);
+            button.on_click(Box::new(move |_| closer.close(Some(result.get_id()))));
+
+            let button_id =
+                widget_store.add_widget_to_parent("_MessageBoxButton", Box::new(button), dialog_id);
+
+            if !default_id.is_valid() {
+                default_id = button_id;
+            }
+        }
+
+        widget_store.set_focused_widget(default_id);
+        dialog_id
+    }
+}
+
+/// The icon of a `MessageBox`: a colored circle with a symbol in it.
+struct MessageIconWidget {
+    config: Configurable,
+    kind: MessageBoxKind,
+    text_widget: Box<TextWidget>,
+}
+
+impl MessageIconWidget {
+    fn new(font: SharedGlyphs, kind: MessageBoxKind) -> Self {
+        let symbol = match kind {
+            MessageBoxKind::Info => "i",
+            MessageBoxKind::Warning => "!",
+            MessageBoxKind::Error => "x",
+            MessageBoxKind::Question => "?",
+        };
+        let text_widget = TextWidget::from_font(
+            font,
+            symbol.to_string(),
+            (MESSAGE_BOX_ICON_SIZE * 2 / 3) as u32,
+            TextJustify::Center,
+        );
+
+        Self {
+            config: Configurable::new(),
+            kind,
+            text_widget: Box::new(text_widget),
+        }
+    }
+}
+
+impl Widget for MessageIconWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Passes the new `Theme` on to the symbol, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the symbol, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the circle in the color of the kind of message, and the symbol in it in the
+    /// background color of the current `Theme`.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let color = match self.kind {
+            MessageBoxKind::Info | MessageBoxKind::Question => theme.accent_color,
+            MessageBoxKind::Warning => [0.95, 0.65, 0.1, 1.0],
+            MessageBoxKind::Error => [0.85, 0.2, 0.2, 1.0],
+        };
+
+        Ellipse::new(apply_opacity(color)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        self.text_widget.set_size(size.w, size.h);
+        self.text_widget.set_text_color(theme.background_color);
+        self.text_widget.draw(c, g, clip);
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -18,18 +18,59 @@ use std::cell::RefCell;
 /// between the user and the application.
 pub mod widget;
 
+/// Widget builder: builds a `Widget` from a chain of settings, returning a `BuildError` for
+/// settings that make no sense, such as a visible `Widget` with no size.
+pub mod builder;
+
 /// Box component: draws a box on the screen with adjustable border color and width.
 pub mod box_widget;
 
 /// Timer component: triggers a callback after a certain amount of time.
 pub mod timer_widget;
 
-/// Text component: draws text on the screen with an adjustable text, font size, color, and font name.
+/// Animation component: interpolates a value over a period of time, triggering a callback with
+/// the value on each update.
+pub mod animation_widget;
+
+/// Countdown component: draws the number of seconds remaining in a countdown, triggering a callback
+/// when the countdown reaches zero.
+pub mod countdown_widget;
+
+/// Text cache: keeps each line of text that has been drawn as a texture, shared between the
+/// `Widget`s that show the same text, so that text that does not change is only rasterized once.
+pub mod text_cache;
+
+/// Text component: draws text on the screen with an adjustable text, font size, color, and font
+/// name, aligned horizontally and vertically, over one or more lines.
 pub mod text_widget;
 
+/// Font cache: loads each font once, from a TTF file or from bytes, and shares it between the
+/// `Widget`s that draw text in it.
+pub mod font_cache;
+
+/// Grapheme clusters: splits text into the characters that a reader sees, so that editing text
+/// never splits an accented letter, an emoji, or a composed character.
+pub mod grapheme;
+
 /// Image component: draws an image on the screen in `png`, `jpg` or `gif` formats.
 pub mod image_widget;
 
+/// Sprite component: plays a frame-based animation from a spritesheet, looping, once, or back
+/// and forth.
+pub mod sprite_widget;
+
+/// Graph component: plots series of samples, such as the frame rate, as lines that scroll
+/// across a grid as new samples arrive.
+pub mod graph_widget;
+
+/// Color picker component: chooses a color from a saturation and value square, a hue strip, and
+/// an optional alpha strip, or from a compact swatch that opens the picker as a popup.
+pub mod color_picker_widget;
+
+/// Button component: draws a clickable box with a text label, which is highlighted while the mouse
+/// is over it or it is pressed, triggering an `on_click` callback when it is clicked.
+pub mod button_widget;
+
 /// Push Button component: draws a clickable box on the screen, triggering an `on_clicked` callback
 /// when appropriate.
 pub mod push_button_widget;
@@ -38,19 +79,144 @@ pub mod push_button_widget;
 /// where appropriate.
 pub mod toggle_button_widget;
 
+/// Checkbox component: draws a box that can be checked, with a text label next to it, triggering
+/// an `on_toggle` callback when it is checked or unchecked.
+pub mod checkbox_widget;
+
+/// Radio Button component: draws a circle with a text label next to it, for picking one of the
+/// options in a radio group.
+pub mod radio_button_widget;
+
 /// Progress component: draws a progress meter widget.
 pub mod progress_widget;
 
+/// Progress Bar component: draws a horizontal or vertical progress bar, with an optional
+/// percentage, or a segment that moves back and forth while the progress is not known.
+pub mod progress_bar_widget;
+
+/// Slider component: draws a horizontal or vertical track with a thumb that is dragged to pick a
+/// value between a smallest and a largest value, triggering an `on_value_changed` callback.
+pub mod slider_widget;
+
+/// Tooltip component: draws a line of text in a bordered box, shown by the run loop when the mouse
+/// rests over a `Widget` that has a tooltip.
+pub mod tooltip_widget;
+
+/// Scroll Container component: hosts child widgets in a content area that is larger than the
+/// widget, scrolled with the mouse wheel and proportional scrollbars.
+pub mod scroll_container_widget;
+
+/// List component: draws a list of text items as rows, which can be selected and activated,
+/// triggering `on_selection_changed` and `on_item_activated` callbacks.
+pub mod list_widget;
+
+/// Table component: shows rows of data in columns, which can be sorted by clicking their titles,
+/// and resized by dragging the dividers between them.
+pub mod table_widget;
+
+/// Tree component: shows a hierarchy of nodes with icons, which can be expanded and collapsed,
+/// and filled in lazily the first time they are expanded.
+pub mod tree_widget;
+
+/// Text input component: edits a single line of text with a blinking caret, triggering
+/// `on_text_changed` and `on_enter_pressed` callbacks.
+pub mod text_input_widget;
+
+/// Spinner component: edits a number as text, next to arrow buttons that step it up and down,
+/// triggering an `on_value_changed` callback.
+pub mod spinner_widget;
+
+/// Dropdown component: draws a field that shows the selected item of a list, which opens as a
+/// popup to pick another item, triggering an `on_selection_changed` callback.
+pub mod dropdown_widget;
+
+/// Menu components: draws a bar of menu titles, which open popup menus of items, separators,
+/// and submenus, triggering an `on_item_selected` callback.
+pub mod menu_widget;
+
+/// Toolbar component: draws a row of tool buttons with icons, collapsing the ones that do not fit
+/// into a popup menu, triggering an `on_tool_selected` callback.
+pub mod toolbar_widget;
+
+/// Tab component: draws a row of tab headers, and shows the page of the selected tab, triggering
+/// an `on_tab_changed` callback.
+pub mod tab_widget;
+
+/// Split pane component: divides itself into two panes, which are its children, with a divider
+/// that can be dragged to resize them.
+pub mod split_pane_widget;
+
+/// Group box component: draws a border with a title around its children, and can be collapsed
+/// by clicking the title, hiding the children.
+pub mod group_box_widget;
+
+/// Status bar component: docks itself to the bottom of the window, and shows text in named
+/// sections, along with messages that can clear themselves after a timeout.
+pub mod status_bar_widget;
+
+/// Dialog component: a modal panel with a title bar, which dims and blocks the rest of the
+/// window until it is closed with a result.
+pub mod dialog_widget;
+
+/// Message box component: ready-made modal dialogs that show a message, or ask a question,
+/// with an icon and a row of buttons.
+pub mod message_box;
+
+/// Layout components: containers that place their children in a row, a column, or a grid, each
+/// child keeping its preferred size or stretching to fill the space that is left.
+pub mod layout_widget;
+
+/// Gradient fills: linear gradients that blend between two or more colors across the bounds of
+/// a `Widget`, horizontally, vertically, or along an angle.
+pub mod gradient;
+
+/// Shape helpers: rectangles with rounded corners, filled or outlined, that `Widget`s draw their
+/// backgrounds and borders with.
+pub mod shape;
+
 /// `Configurable` definition, used by `Widget` objects to store configuration settings.
 pub mod config;
 
+/// Default colors and font size that `Widget`s use when they have not been configured otherwise.
+pub mod theme;
+
 pub enum Widgets {
     CanvasWidget(RefCell<crate::widget::widget::CanvasWidget>),
     BoxWidget(RefCell<crate::widget::box_widget::BoxWidget>),
     TimerWidget(RefCell<crate::widget::timer_widget::TimerWidget>),
+    AnimationWidget(RefCell<crate::widget::animation_widget::AnimationWidget>),
+    CountdownWidget(RefCell<crate::widget::countdown_widget::CountdownWidget>),
     TextWidget(RefCell<crate::widget::text_widget::TextWidget>),
     ImageWidget(RefCell<crate::widget::image_widget::ImageWidget>),
+    SpriteWidget(RefCell<crate::widget::sprite_widget::SpriteWidget>),
+    GraphWidget(RefCell<crate::widget::graph_widget::GraphWidget>),
+    ColorPickerWidget(RefCell<crate::widget::color_picker_widget::ColorPickerWidget>),
+    ButtonWidget(RefCell<crate::widget::button_widget::ButtonWidget>),
     PushButtonWidget(RefCell<crate::widget::push_button_widget::PushButtonWidget>),
     ToggleButtonWidget(RefCell<crate::widget::toggle_button_widget::ToggleButtonWidget>),
+    CheckboxWidget(RefCell<crate::widget::checkbox_widget::CheckboxWidget>),
+    RadioButtonWidget(RefCell<crate::widget::radio_button_widget::RadioButtonWidget>),
     ProgressWidget(RefCell<crate::widget::progress_widget::ProgressWidget>),
+    ProgressBarWidget(RefCell<crate::widget::progress_bar_widget::ProgressBarWidget>),
+    SliderWidget(RefCell<crate::widget::slider_widget::SliderWidget>),
+    TooltipWidget(RefCell<crate::widget::tooltip_widget::TooltipWidget>),
+    ScrollContainerWidget(RefCell<crate::widget::scroll_container_widget::ScrollContainerWidget>),
+    ListWidget(RefCell<crate::widget::list_widget::ListWidget>),
+    TableWidget(RefCell<crate::widget::table_widget::TableWidget>),
+    TreeWidget(RefCell<crate::widget::tree_widget::TreeWidget>),
+    TextInputWidget(RefCell<crate::widget::text_input_widget::TextInputWidget>),
+    SpinnerWidget(RefCell<crate::widget::spinner_widget::SpinnerWidget>),
+    DropdownWidget(RefCell<crate::widget::dropdown_widget::DropdownWidget>),
+    DropdownListWidget(RefCell<crate::widget::dropdown_widget::DropdownListWidget>),
+    MenuBarWidget(RefCell<crate::widget::menu_widget::MenuBarWidget>),
+    PopupMenuWidget(RefCell<crate::widget::menu_widget::PopupMenuWidget>),
+    ToolbarWidget(RefCell<crate::widget::toolbar_widget::ToolbarWidget>),
+    TabWidget(RefCell<crate::widget::tab_widget::TabWidget>),
+    SplitPaneWidget(RefCell<crate::widget::split_pane_widget::SplitPaneWidget>),
+    GroupBoxWidget(RefCell<crate::widget::group_box_widget::GroupBoxWidget>),
+    StatusBarWidget(RefCell<crate::widget::status_bar_widget::StatusBarWidget>),
+    DialogWidget(RefCell<crate::widget::dialog_widget::DialogWidget>),
+    HorizontalLayoutWidget(RefCell<crate::widget::layout_widget::HorizontalLayoutWidget>),
+    VerticalLayoutWidget(RefCell<crate::widget::layout_widget::VerticalLayoutWidget>),
+    GridLayoutWidget(RefCell<crate::widget::layout_widget::GridLayoutWidget>),
 }
@@ -0,0 +1,355 @@
+// Progress Bar Widget
+// Handles the display of a progress bar, with determinate and indeterminate modes
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::animation_widget::*;
+use crate::widget::box_widget::*;
+use crate::widget::config::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The number of milliseconds that the segment of an indeterminate `ProgressBarWidget` takes to
+/// move from one end of the bar to the other.
+pub const INDETERMINATE_SWEEP_MS: u64 = 1000;
+
+/// The length of the segment of an indeterminate `ProgressBarWidget`, as a fraction of the
+/// length of the bar.
+const INDETERMINATE_SEGMENT_LENGTH: f64 = 0.25;
+
+/// This is the `ProgressBarWidget`, which shows the progress of a long operation as a bar that
+/// is filled from 0.0 to 1.0, from left to right, or from bottom to top.  The track of the bar
+/// is drawn in the color of the widget, and the fill in its secondary color, which default to
+/// the background and accent colors of the current `Theme`.  The percentage can be shown in the
+/// middle of the bar with `show_percentage`.
+///
+/// When the progress of an operation is not known, the bar can be made indeterminate, when a
+/// segment of the bar moves back and forth instead, driven by an `AnimationWidget`.
+///
+/// Changing the progress only invalidates this widget, so that it can be updated as often as
+/// needed, such as from the events that a worker thread sends over the event bus.
+///
+/// Example usage:
+/// ```
+/// # use pushrod::core::clock::*;
+/// # use pushrod::widget::progress_bar_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let mut progress_bar = ProgressBarWidget::with_clock(Box::new(clock.clone()));
+///
+///    progress_bar.set_progress(0.25);
+///    assert_eq!(progress_bar.get_progress(), 0.25);
+///
+///    // Progress is clamped between 0.0 and 1.0.
+///    progress_bar.set_progress(1.5);
+///    assert_eq!(progress_bar.get_progress(), 1.0);
+///
+///    // The segment of an indeterminate bar moves to the other end, and then back again.
+///    progress_bar.set_indeterminate(true);
+///    clock.advance(INDETERMINATE_SWEEP_MS);
+///    progress_bar.update(16);
+///    assert_eq!(progress_bar.get_indeterminate_position(), 1.0);
+///
+///    clock.advance(INDETERMINATE_SWEEP_MS / 2);
+///    progress_bar.update(16);
+///    assert_eq!(progress_bar.get_indeterminate_position(), 0.5);
+/// # }
+/// ```
+pub struct ProgressBarWidget {
+    config: Configurable,
+    base_widget: BoxWidget,
+    progress: f64,
+    orientation: Orientation,
+    indeterminate: bool,
+    animation: AnimationWidget,
+    sweeping_back: bool,
+    percentage_widget: Option<Box<TextWidget>>,
+}
+
+/// Implementation of the constructor for the `ProgressBarWidget`.
+impl ProgressBarWidget {
+    /// Creates a new horizontal progress bar, with no progress.
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Creates a new progress bar that moves the segment of its indeterminate mode using the
+    /// specified `Clock`.  This is mainly useful for testing, by supplying a `ManualClock`.
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        let mut base = BoxWidget::new();
+        let mut animation = AnimationWidget::with_clock(0.0, 1.0, INDETERMINATE_SWEEP_MS, clock);
+
+        base.set_border_thickness(1);
+        animation.set_easing(Easing::EaseInOut);
+
+        Self {
+            config: Configurable::new(),
+            base_widget: base,
+            progress: 0.0,
+            orientation: Orientation::Horizontal,
+            indeterminate: false,
+            animation,
+            sweeping_back: false,
+            percentage_widget: None,
+        }
+    }
+
+    /// Sets the progress to be indicated, from 0.0 to 1.0.  Values outside of this range are
+    /// clamped to it.  Invalidates the widget if the progress changed.
+    pub fn set_progress(&mut self, progress: f64) {
+        let progress = if progress.is_nan() {
+            0.0
+        } else {
+            progress.clamp(0.0, 1.0)
+        };
+
+        if (progress - self.progress).abs() > f64::EPSILON {
+            self.progress = progress;
+            self.update_percentage_text();
+            self.invalidate();
+        }
+    }
+
+    /// Returns the current progress shown, from 0.0 to 1.0.
+    pub fn get_progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// Sets the direction in which the bar is filled.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.invalidate();
+    }
+
+    /// Retrieves the direction in which the bar is filled.  Defaults to
+    /// `Orientation::Horizontal`.
+    pub fn get_orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Turns the indeterminate mode on or off.  An indeterminate bar shows a segment that moves
+    /// back and forth instead of the progress, starting at the beginning of the bar.
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        if indeterminate && !self.indeterminate {
+            if self.sweeping_back {
+                self.animation.reverse();
+                self.sweeping_back = false;
+            }
+
+            self.animation.restart();
+        }
+
+        self.indeterminate = indeterminate;
+        self.invalidate();
+    }
+
+    /// Indicates whether the bar is in indeterminate mode.
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate
+    }
+
+    /// Returns the position of the segment of an indeterminate bar, from 0.0 at the beginning of
+    /// the bar to 1.0 at its end.
+    pub fn get_indeterminate_position(&self) -> f64 {
+        self.animation.get_value()
+    }
+
+    /// Shows the progress as a percentage in the middle of the bar, drawn with the font
+    /// `font_name` in the text color and font size of the current `Theme`.  The percentage is
+    /// not shown while the bar is indeterminate.
+    pub fn show_percentage(&mut self, factory: &mut GfxFactory, font_name: String) {
+        let size = self.get_size();
+        let mut text_widget = TextWidget::new(
+            factory,
+            font_name,
+            String::new(),
+            get_current_theme().font_size,
+            TextJustify::Center,
+        );
+
+        text_widget.set_size(size.w, size.h);
+        self.percentage_widget = Some(Box::new(text_widget));
+        self.update_percentage_text();
+        self.invalidate();
+    }
+
+    /// Stops showing the percentage in the middle of the bar.
+    pub fn hide_percentage(&mut self) {
+        self.percentage_widget = None;
+        self.invalidate();
+    }
+
+    fn update_percentage_text(&mut self) {
+        let percentage = (self.progress * 100.0).round() as u32;
+
+        if let Some(text_widget) = &mut self.percentage_widget {
+            text_widget.set_text(format!("{}%", percentage));
+        }
+    }
+
+    /// Returns the rectangle of the fill inside of the border, for the part of the bar from
+    /// `start` to `end`, each from 0.0 to 1.0.
+    fn get_fill_rect(&mut self, start: f64, end: f64) -> [f64; 4] {
+        let size = self.get_size();
+        let border = f64::from(self.base_widget.get_border_thickness());
+        let inner_w = (f64::from(size.w) - border * 2.0).max(0.0);
+        let inner_h = (f64::from(size.h) - border * 2.0).max(0.0);
+
+        match self.orientation {
+            Orientation::Horizontal => [
+                border + inner_w * start,
+                border,
+                inner_w * (end - start),
+                inner_h,
+            ],
+            Orientation::Vertical => [
+                border,
+                border + inner_h * (1.0 - end),
+                inner_w,
+                inner_h * (end - start),
+            ],
+        }
+    }
+}
+
+impl Default for ProgressBarWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `ProgressBarWidget` object with the `Widget` traits implemented.
+/// The base widget is a `BoxWidget`, which draws the track and the border of the bar.
+impl Widget for ProgressBarWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::ProgressBar
+    }
+
+    /// The value is the progress as a percentage, such as `"40%"`.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        Some(format!("{}%", (self.progress * 100.0).round()))
+    }
+
+    /// Sets the `Point` of origin for this widget and the base widget, given the X and Y
+    /// coordinates.  Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.base_widget.set_origin(x, y);
+
+        if let Some(text_widget) = &mut self.percentage_widget {
+            text_widget.set_origin(x, y);
+        }
+
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and the base widget, given width and height.
+    /// Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.base_widget.set_size(w, h);
+
+        if let Some(text_widget) = &mut self.percentage_widget {
+            text_widget.set_size(w, h);
+        }
+
+        self.invalidate();
+    }
+
+    /// Sets the color of the track for this widget.  Invalidates the widget afterward.
+    fn set_color(&mut self, color: types::Color) {
+        self.config().set(MainColor(color));
+        self.base_widget.set_color(color);
+        self.invalidate();
+    }
+
+    /// Moves the segment of an indeterminate bar on every update of the run loop, turning
+    /// around at either end of the bar.
+    fn update(&mut self, _delta_ms: u64) {
+        if !self.indeterminate {
+            return;
+        }
+
+        self.animation.tick();
+
+        if !self.animation.is_running() {
+            self.animation.reverse();
+            self.sweeping_back = !self.sweeping_back;
+        }
+
+        self.invalidate();
+    }
+
+    /// Passes the new `Theme` on to the base widget and the percentage, and redraws the bar.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.base_widget.theme_changed(theme);
+
+        if let Some(text_widget) = &mut self.percentage_widget {
+            text_widget.theme_changed(theme);
+        }
+
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the percentage, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        if let Some(text_widget) = &mut self.percentage_widget {
+            text_widget.scale_factor_changed(scale_factor);
+        }
+
+        self.invalidate();
+    }
+
+    /// Draws the track of the bar, and then the fill in the secondary color: the progress, or
+    /// the moving segment of an indeterminate bar.  The percentage is drawn over the top.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.base_widget.draw(c, g, clip);
+
+        let fill_rect = if self.indeterminate {
+            let start = self.get_indeterminate_position() * (1.0 - INDETERMINATE_SEGMENT_LENGTH);
+
+            self.get_fill_rect(start, start + INDETERMINATE_SEGMENT_LENGTH)
+        } else {
+            self.get_fill_rect(0.0, self.progress)
+        };
+
+        Rectangle::new(apply_opacity(self.get_secondary_color())).draw(
+            fill_rect,
+            clip,
+            c.transform,
+            g,
+        );
+
+        if !self.indeterminate {
+            if let Some(text_widget) = &mut self.percentage_widget {
+                text_widget.draw(c, g, clip);
+            }
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -15,6 +15,8 @@
 
 use piston_window::*;
 
+use crate::core::accessibility::*;
+use crate::core::draw_target::*;
 use crate::core::point::*;
 use crate::widget::box_widget::*;
 use crate::widget::config::*;
@@ -67,6 +69,15 @@ impl Widget for ProgressWidget {
         &mut self.config
     }
 
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::ProgressBar
+    }
+
+    /// The value is the progress as a percentage, such as `"40%"`.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        Some(format!("{}%", self.progress))
+    }
+
     /// Sets the `Point` of origin for this widget and the base widget, given the X and Y
     /// coordinates.  Invalidates the widget afterward.
     fn set_origin(&mut self, x: i32, y: i32) {
@@ -90,7 +101,7 @@ impl Widget for ProgressWidget {
     }
 
     /// Draws the widget.  The progress bar is the secondary color.
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         let size: crate::core::point::Size = self.get_size();
 
         self.base_widget.draw(c, g, clip);
@@ -98,7 +109,7 @@ impl Widget for ProgressWidget {
         let draw_width = (size.w as f64 * (self.progress as f64 / 100.0)) as f64;
 
         // Paint the secondary color to display the progress color.
-        Rectangle::new(self.get_secondary_color()).draw(
+        Rectangle::new(apply_opacity(self.get_secondary_color())).draw(
             [1.0 as f64, 1.0 as f64, draw_width, (size.h - 2) as f64],
             clip,
             c.transform,
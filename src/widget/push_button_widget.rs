@@ -15,15 +15,20 @@
 
 use piston_window::*;
 
+use crate::core::accessibility::*;
 use crate::core::callbacks::CallbackEvent::WidgetClicked;
 use crate::core::callbacks::*;
+use crate::core::draw_target::*;
 use crate::core::point::*;
 use crate::widget::box_widget::*;
+use crate::widget::builder::*;
 use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::theme::*;
 use crate::widget::text_widget::*;
 use crate::widget::widget::*;
 
-pub type MutableBlankCallback = Box<FnMut() -> ()>;
+pub type MutableBlankCallback = Box<dyn FnMut()>;
 
 /// This is the `PushButtonWidget`, which contains a top-level widget for display, overriding the
 /// draw method to draw the base widget and the border for this box.
@@ -45,17 +50,55 @@ impl PushButtonWidget {
         font_size: u32,
         justify: TextJustify,
     ) -> Self {
-        Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, text, font_size, justify)
+    }
+
+    /// Creates a new button with a `text` label, drawn in a font from the font cache, in
+    /// `font_size`.
+    pub fn from_font(
+        font: SharedGlyphs,
+        text: String,
+        font_size: u32,
+        justify: TextJustify,
+    ) -> Self {
+        let mut widget = Self {
             config: Configurable::new(),
             base_widget: BoxWidget::new(),
-            text_widget: TextWidget::new(
-                factory,
-                font_name.to_string(),
-                text.to_string(),
-                font_size,
-                justify,
-            ),
-        }
+            text_widget: TextWidget::from_font(font, text, font_size, justify),
+        };
+
+        widget.update_text_padding();
+        widget
+    }
+
+    /// Creates a `WidgetBuilder` for a new button with no label, centered, drawn in a font from
+    /// the font cache, in the font size of the current `Theme`.
+    pub fn builder(font: SharedGlyphs) -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::from_font(
+            font,
+            String::new(),
+            get_current_theme().font_size,
+            TextJustify::Center,
+        ))
+    }
+
+    /// Sets the font size of the text label of the button.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.text_widget.set_font_size(font_size);
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the text label of the button.
+    pub fn get_font_size(&self) -> u32 {
+        self.text_widget.get_font_size()
+    }
+
+    /// Sets the justification of the text label of the button.
+    pub fn set_justify(&mut self, justify: TextJustify) {
+        self.text_widget.set_justify(justify);
+        self.invalidate();
     }
 
     /// Sets the border color for this widget.
@@ -66,11 +109,13 @@ impl PushButtonWidget {
 
     /// Sets the color of the text for this `Widget`.
     pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
         self.text_widget.set_text_color(color);
+        self.invalidate();
     }
 
     /// Retrieves the color of the text for this `Widget`.
-    /// Defaults to black if not set.
+    /// Defaults to the text color of the current `Theme` if not set.
     pub fn get_text_color(&mut self) -> types::Color {
         self.text_widget.get_text_color()
     }
@@ -83,7 +128,7 @@ impl PushButtonWidget {
     }
 
     /// Retrieves the border color of this widget.
-    /// Defaults to black color `[0.0, 0.0, 0.0, 1.0]` if not set.
+    /// Defaults to the border color of the current `Theme` if not set.
     pub fn get_border_color(&mut self) -> types::Color {
         self.base_widget.get_border_color()
     }
@@ -91,7 +136,7 @@ impl PushButtonWidget {
     /// Sets the thickness of the border for this widget.
     pub fn set_border_thickness(&mut self, thickness: u8) {
         self.base_widget.set_border_thickness(thickness);
-        self.text_widget.invalidate();
+        self.update_text_padding();
         self.invalidate();
     }
 
@@ -106,6 +151,83 @@ impl PushButtonWidget {
         self.set_border_color(color);
         self.set_border_thickness(thickness);
     }
+
+    /// Draws the button as pressed, in the accent color of the current `Theme`.
+    fn show_pressed(&mut self) {
+        let theme = get_current_theme();
+
+        self.base_widget.set_color(theme.accent_color);
+        self.text_widget.set_text_color(theme.background_color);
+        self.invalidate();
+    }
+
+    /// Draws the button as released, returning to the colors that were set on it, or to the
+    /// colors of the current `Theme` if none were set.
+    fn show_released(&mut self) {
+        match self.config().get::<MainColor>().map(|color| color.0) {
+            Some(color) => self.base_widget.set_color(color),
+            None => self.base_widget.config().remove::<MainColor>(),
+        }
+
+        match self.config().get::<TextColor>().map(|color| color.0) {
+            Some(color) => self.text_widget.set_text_color(color),
+            None => self.text_widget.config().remove::<TextColor>(),
+        }
+
+        self.invalidate();
+    }
+
+    /// Insets the text by the border and the padding, so that it is drawn inside of both.
+    fn update_text_padding(&mut self) {
+        let border = self.base_widget.get_border_thickness() as i32;
+        let padding = self.get_padding().grow(border);
+
+        self.text_widget.set_padding(padding);
+    }
+}
+
+/// A `PushButtonWidget` also needs a font size.
+impl Buildable for PushButtonWidget {
+    fn validate(&mut self) -> Result<(), BuildError> {
+        if self.get_font_size() == 0 {
+            return Err(BuildError::ZeroFontSize);
+        }
+
+        validate_size(self)
+    }
+}
+
+/// Implementation of the `WidgetBuilder` settings of the `PushButtonWidget`.
+impl WidgetBuilder<PushButtonWidget> {
+    /// Sets the text label of the button.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.widget().set_text(text.into());
+        self
+    }
+
+    /// Sets the color of the text label.
+    pub fn text_color(mut self, color: types::Color) -> Self {
+        self.widget().set_text_color(color);
+        self
+    }
+
+    /// Sets the font size of the text label.
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.widget().set_font_size(font_size);
+        self
+    }
+
+    /// Sets the justification of the text label.
+    pub fn justify(mut self, justify: TextJustify) -> Self {
+        self.widget().set_justify(justify);
+        self
+    }
+
+    /// Sets both the color and the thickness of the border.
+    pub fn border(mut self, color: types::Color, thickness: u8) -> Self {
+        self.widget().set_border(color, thickness);
+        self
+    }
 }
 
 /// Implementation of the `PushButtonWidget` object with the `Widget` traits implemented.
@@ -117,30 +239,46 @@ impl Widget for PushButtonWidget {
         &mut self.config
     }
 
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Button
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text_widget.get_text().to_string()
+    }
+
     /// Sets the `Point` of origin for this widget and the base widget, given the X and Y
     /// coordinates.  Invalidates the widget afterward.
     fn set_origin(&mut self, x: i32, y: i32) {
-        let border_size = self.base_widget.get_border_thickness();
-
         self.config().set(Origin(Point { x, y }));
         self.base_widget.set_origin(x, y);
-        self.text_widget
-            .set_origin(x + border_size as i32, y + border_size as i32);
+        self.text_widget.set_origin(x, y);
         self.invalidate();
     }
 
     /// Sets the `Size` for this widget and the base widget, given width and height.  Invalidates the widget afterward.
     fn set_size(&mut self, w: i32, h: i32) {
-        let border_size = self.base_widget.get_border_thickness();
-
         self.config()
             .set(BodySize(crate::core::point::Size { w, h }));
         self.base_widget.set_size(w, h);
-        self.text_widget
-            .set_size(w - (border_size as i32 * 2), h - (border_size as i32 * 2));
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the padding between the border of this widget and its text.  Invalidates the widget
+    /// afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.update_text_padding();
         self.invalidate();
     }
 
+    /// Retrieves the rectangle inside of which the text is drawn: the size of the widget, inset
+    /// by the thickness of the border, and then by the padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        self.text_widget.get_content_rect()
+    }
+
     /// Sets the color for this widget.  Invalidates the widget afterward.
     fn set_color(&mut self, color: types::Color) {
         self.config().set(MainColor(color));
@@ -149,28 +287,39 @@ impl Widget for PushButtonWidget {
     }
 
     /// Retrieves the color of this widget.
-    /// Defaults to white color `[1.0; 4]` if not set.
+    /// Defaults to the background color of the current `Theme` if not set.
     fn get_color(&mut self) -> types::Color {
         self.base_widget.get_color()
     }
 
+    /// Enables or disables this widget.  The text of a disabled button is drawn in the disabled
+    /// color of the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
     fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
         match event {
-            CallbackEvent::MouseButtonDown { widget_id: _, button } => match button {
+            CallbackEvent::MouseButtonDown { button, .. } => match button {
                 Button::Mouse(mouse_button) => {
                     if mouse_button == MouseButton::Left {
-                        self.base_widget.set_color([0.0, 0.0, 0.0, 1.0]);
-                        self.text_widget.set_text_color([1.0; 4]);
+                        self.show_pressed();
                     }
                 }
                 _ => (),
             },
 
-            CallbackEvent::MouseButtonUpInside { widget_id, button } => match button {
+            CallbackEvent::MouseButtonUpInside { widget_id, button, .. } => match button {
                 Button::Mouse(mouse_button) => {
                     if mouse_button == MouseButton::Left {
-                        self.base_widget.set_color([1.0; 4]);
-                        self.text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
+                        self.show_released();
 
                         return Some(WidgetClicked { widget_id, button });
                     }
@@ -178,11 +327,10 @@ impl Widget for PushButtonWidget {
                 _ => (),
             },
 
-            CallbackEvent::MouseButtonUpOutside { widget_id: _, button } => match button {
+            CallbackEvent::MouseButtonUpOutside { button, .. } => match button {
                 Button::Mouse(mouse_button) => {
                     if mouse_button == MouseButton::Left {
-                        self.base_widget.set_color([1.0; 4]);
-                        self.text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
+                        self.show_released();
                     }
                 }
                 _ => (),
@@ -194,11 +342,24 @@ impl Widget for PushButtonWidget {
         None
     }
 
+    /// Passes the new `Theme` on to the base widget and text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.base_widget.theme_changed(theme);
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
     /// Draws the contents of the widget in this order:
     ///
     /// - Base widget first
     /// - Box graphic for the specified width
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
         self.base_widget.draw(c, g, &clip);
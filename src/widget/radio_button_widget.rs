@@ -0,0 +1,323 @@
+// Radio Button Widget
+// Extensible widget for the widget library - handles a radio button with a text label.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The width and height of the circle of a `RadioButtonWidget`, in pixels.
+pub const RADIO_BUTTON_SIZE: i32 = 16;
+
+/// The number of pixels between the circle of a `RadioButtonWidget` and its label.
+const RADIO_BUTTON_LABEL_SPACING: i32 = 6;
+
+/// This is the `RadioButtonWidget`, which draws a small circle followed by a text label, for
+/// picking exactly one of several options.  Each radio button belongs to a radio group, named by
+/// its `RadioGroupId` config key.  Clicking anywhere on the circle or the label, or pressing
+/// `Space` while it is focused, selects the radio button, and the `WidgetStore` unchecks the
+/// other radio buttons in the same group, and calls the group's callback, which is set with
+/// `WidgetStore::on_radio_selection_changed`.  A selected radio button is drawn with a dot in
+/// its circle.
+///
+/// Radio buttons are selected from code with `WidgetStore::select_radio_button`, which also
+/// unchecks the rest of the group.  A disabled radio button is drawn in the disabled color of
+/// the current `Theme`, and cannot be selected by the user.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::radio_button_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Radio Buttons", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///
+///    for (index, label) in ["Small", "Medium", "Large"].iter().enumerate() {
+///        let mut radio_button = RadioButtonWidget::new(
+///            pushrod.get_factory(),
+///            "OpenSans-Regular.ttf".to_string(),
+///            label.to_string(),
+///            "size",
+///        );
+///
+///        radio_button.set_origin(20, 20 + index as i32 * 30);
+///        radio_button.set_size(200, 24);
+///        pushrod.add_widget(label, Box::new(radio_button));
+///    }
+///
+///    pushrod.widget_store.borrow_mut().on_radio_selection_changed(
+///        "size",
+///        Box::new(|widget_id| eprintln!("Selected radio button {}", widget_id)),
+///    );
+/// # }
+/// ```
+pub struct RadioButtonWidget {
+    config: Configurable,
+    text_widget: TextWidget,
+}
+
+/// Implementation of the constructor for the `RadioButtonWidget`.
+impl RadioButtonWidget {
+    /// Creates a new radio button in the radio group `group`, with a `text` label, drawn with
+    /// the font `font_name` from the `assets` directory, in the font size of the current
+    /// `Theme`.  The radio button is not selected, and can be focused, so that it can be
+    /// selected from the keyboard.
+    pub fn new(factory: &mut GfxFactory, font_name: String, text: String, group: &str) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: TextWidget::new(factory, font_name, text, font_size, TextJustify::Left),
+        };
+
+        widget.set_radio_group(group);
+        widget.set_focusable(true);
+        widget.update_text_padding();
+        widget
+    }
+
+    /// Sets the text label of the radio button.
+    pub fn set_text(&mut self, text: String) {
+        self.text_widget.set_text(text);
+        self.invalidate();
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.text_widget.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.text_widget.get_text_color()
+    }
+
+    /// Moves the radio button to the radio group `group`.
+    pub fn set_radio_group(&mut self, group: &str) {
+        self.config().set(RadioGroupId(group.to_string()));
+    }
+
+    /// Retrieves the name of the radio group of the radio button.
+    pub fn get_radio_group(&mut self) -> String {
+        self.config()
+            .get::<RadioGroupId>()
+            .map(|group| group.0.clone())
+            .unwrap_or_default()
+    }
+
+    /// Indicates whether the radio button is the selected one in its radio group.
+    pub fn is_selected(&mut self) -> bool {
+        self.config()
+            .get::<CheckedState>()
+            .map(|state| state.0 == CheckState::Checked)
+            .unwrap_or(false)
+    }
+
+    /// Selects the radio button, which the `WidgetStore` sees after the event that is being
+    /// handled, unchecking the rest of its radio group.
+    fn select(&mut self) {
+        if !self.is_selected() {
+            self.config().set(CheckedState(CheckState::Checked));
+            self.invalidate();
+        }
+    }
+
+    /// Returns the rectangle of the circle, centered vertically at the left of the content
+    /// rectangle.
+    fn get_circle_rect(&mut self) -> [f64; 4] {
+        let [x, y, _, h] = self.get_content_rect();
+        let box_y = y + (h - RADIO_BUTTON_SIZE) / 2;
+        let size = f64::from(RADIO_BUTTON_SIZE);
+
+        [f64::from(x), f64::from(box_y), size, size]
+    }
+
+    /// Insets the text by the padding, and by the circle and the spacing next to it, so that
+    /// the label is drawn to the right of the circle.
+    fn update_text_padding(&mut self) {
+        let mut padding = self.get_padding();
+
+        padding.left += RADIO_BUTTON_SIZE + RADIO_BUTTON_LABEL_SPACING;
+        self.text_widget.set_padding(padding);
+    }
+}
+
+/// Implementation of the `RadioButtonWidget` object with the `Widget` traits implemented.
+/// The label is a `TextWidget`, which is drawn next to the circle.  This `Widget` responds to
+/// the mouse and the keyboard internally, and selects itself when it is clicked.
+impl Widget for RadioButtonWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::RadioButton
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text_widget.get_text().to_string()
+    }
+
+    fn get_accessible_checked(&mut self) -> Option<CheckState> {
+        Some(if self.is_selected() {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        })
+    }
+
+    /// Sets the `Point` of origin for this widget and the label, given the X and Y coordinates.
+    /// Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.text_widget.set_origin(x, y);
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and the label, given width and height.  Invalidates the
+    /// widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the padding around the circle and the label.  Invalidates the widget afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.update_text_padding();
+        self.invalidate();
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the size
+    /// of the circle and the label next to it, plus the padding, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => {
+                let text_size = self.text_widget.get_preferred_size();
+
+                crate::core::point::Size {
+                    w: text_size.w,
+                    h: text_size.h.max(RADIO_BUTTON_SIZE),
+                }
+            }
+        }
+    }
+
+    /// Enables or disables this widget.  A disabled radio button is drawn in the disabled color of
+    /// the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseButtonUpInside {
+                widget_id, button, ..
+            } if button == Button::Mouse(MouseButton::Left) => {
+                self.select();
+
+                return Some(CallbackEvent::WidgetClicked { widget_id, button });
+            }
+
+            CallbackEvent::KeyPressed {
+                widget_id,
+                key: Key::Space,
+                state: ButtonState::Release,
+                ..
+            } => {
+                self.select();
+
+                return Some(CallbackEvent::WidgetClicked {
+                    widget_id,
+                    button: Button::Keyboard(Key::Space),
+                });
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the label, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the label, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the circle, the dot inside of it if the radio button is selected, and then the
+    /// label.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let [x, y, w, h] = self.get_circle_rect();
+        let (border_color, dot_color) = if self.is_disabled() {
+            (theme.disabled_color, theme.disabled_color)
+        } else {
+            (theme.border_color, theme.accent_color)
+        };
+
+        Ellipse::new(apply_opacity(self.get_color())).draw([x, y, w, h], clip, c.transform, g);
+        Ellipse::new_border(apply_opacity(border_color), 0.5).draw(
+            [x + 0.5, y + 0.5, w - 1.0, h - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        if self.is_selected() {
+            Ellipse::new(apply_opacity(dot_color)).draw(
+                [x + w * 0.25, y + h * 0.25, w * 0.5, h * 0.5],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        self.text_widget.draw(c, g, clip);
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,504 @@
+// Scroll Container Widget
+// Hosts child widgets in a content area that is larger than the widget, with scrollbars.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The thickness of the scrollbars of a `ScrollContainerWidget`, in pixels.
+pub const SCROLLBAR_THICKNESS: i32 = 10;
+
+/// The number of pixels that a `ScrollContainerWidget` scrolls for each notch of the mouse
+/// wheel.
+pub use crate::core::callbacks::SCROLL_LINE_PIXELS;
+
+/// The shortest that the thumb of a scrollbar is drawn, in pixels, so that it can still be
+/// grabbed when the content is very large.
+const SCROLLBAR_MIN_THUMB: i32 = 16;
+
+/// Returns the start and length of the thumb of a scrollbar along a track of `track_length`
+/// pixels, for a viewport of `view_length` pixels that is scrolled `offset` pixels into content
+/// of `content_length` pixels.  The length of the thumb is proportional to how much of the
+/// content is visible, and fills the track when all of it is.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::scroll_container_widget::*;
+/// # fn main() {
+///    // A quarter of the content is visible, scrolled halfway.
+///    assert_eq!(get_thumb_span(100, 400, 150, 100), (37, 25));
+///
+///    // All of the content is visible.
+///    assert_eq!(get_thumb_span(100, 50, 0, 100), (0, 100));
+/// # }
+/// ```
+pub fn get_thumb_span(
+    view_length: i32,
+    content_length: i32,
+    offset: i32,
+    track_length: i32,
+) -> (i32, i32) {
+    let max_offset = content_length - view_length;
+
+    if max_offset <= 0 || track_length <= 0 {
+        return (0, track_length.max(0));
+    }
+
+    let thumb_length =
+        (i64::from(track_length) * i64::from(view_length) / i64::from(content_length)) as i32;
+    let thumb_length = thumb_length.max(SCROLLBAR_MIN_THUMB).min(track_length);
+    let start = i64::from(track_length - thumb_length) * i64::from(offset.clamp(0, max_offset))
+        / i64::from(max_offset);
+
+    (start as i32, thumb_length)
+}
+
+/// The scrollbar whose thumb is being dragged, with the position of the mouse along the
+/// scrollbar when the thumb was grabbed, and the scroll offset at that time.
+struct ThumbDrag {
+    orientation: Orientation,
+    grab_position: i32,
+    grab_offset: i32,
+}
+
+/// This is the `ScrollContainerWidget`, which hosts its children in a virtual content area that
+/// can be larger than the widget itself.  The children are placed in the content area as they
+/// would be in any other parent, and the `WidgetStore` offsets them by the scroll position when
+/// they are drawn and clicked, and clips them to the viewport of the container.
+///
+/// The mouse wheel and touchpads scroll along both axes, by the number of pixels that they
+/// report, or by `SCROLL_LINE_PIXELS` for each line.  `Pushrod` turns the mouse wheel sideways
+/// while `Shift` is held down.  A scroll that the container cannot move any further is passed on
+/// to the widget underneath, so that scroll containers can be nested.  Proportional scrollbars
+/// are drawn along the right and bottom edges when the content is larger than the viewport:
+/// their thumbs can be dragged, and clicking the track on either side of a thumb scrolls by a
+/// page.  The scroll position is always limited to the content, which is set with
+/// `set_content_size`.
+///
+/// Example usage:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::core::widget_store::*;
+/// # use pushrod::widget::scroll_container_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut widget_store = WidgetStore::new();
+///    let mut container = ScrollContainerWidget::new();
+///    let mut child = CanvasWidget::new();
+///
+///    container.set_origin(10, 10);
+///    container.set_size(200, 100);
+///    container.set_content_size(190, 400);
+///    child.set_origin(20, 150);
+///    child.set_size(50, 20);
+///
+///    let container_id = widget_store.add_widget("Container", Box::new(container));
+///    let child_id = widget_store.add_widget_to_parent("Child", Box::new(child), container_id);
+///
+///    // The child is below the viewport, so it cannot be clicked.
+///    assert_ne!(widget_store.get_widget_id_for_point(make_point_i32(40, 170)), child_id);
+///
+///    widget_store.get_widget_for_id(container_id).borrow_mut().scroll_to(0, 100);
+///    assert_eq!(widget_store.get_widget_id_for_point(make_point_i32(40, 70)), child_id);
+///
+///    // Scrolling is limited to the content.
+///    let container = widget_store.get_widget_for_id(container_id);
+///
+///    container.borrow_mut().scroll_to(0, 1000);
+///    assert_eq!(container.borrow_mut().get_scroll_offset().y, 300);
+/// # }
+/// ```
+pub struct ScrollContainerWidget {
+    config: Configurable,
+    content_size: crate::core::point::Size,
+    scroll_offset: Point,
+    scroll_remainder: [f64; 2],
+    thumb_drag: Option<ThumbDrag>,
+}
+
+/// Implementation of the constructor for the `ScrollContainerWidget`.
+impl ScrollContainerWidget {
+    /// Creates a new scroll container, with no content, scrolled to the top left.
+    pub fn new() -> Self {
+        Self {
+            config: Configurable::new(),
+            content_size: make_unsized(),
+            scroll_offset: make_origin_point(),
+            scroll_remainder: [0.0, 0.0],
+            thumb_drag: None,
+        }
+    }
+
+    /// Sets the size of the content area in which the children are placed.  The scroll position
+    /// is limited to the new size.  Invalidates the widget afterward.
+    pub fn set_content_size(&mut self, w: i32, h: i32) {
+        self.content_size = crate::core::point::Size { w, h };
+
+        let offset = self.scroll_offset.clone();
+
+        self.scroll_to(offset.x, offset.y);
+        self.invalidate();
+    }
+
+    /// Retrieves the size of the content area.  Defaults to no size, so nothing scrolls.
+    pub fn get_content_size(&self) -> crate::core::point::Size {
+        self.content_size.clone()
+    }
+
+    /// Scrolls the content by `dx` and `dy` pixels, limited to the content.
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let offset = self.scroll_offset.clone();
+
+        self.scroll_to(offset.x + dx, offset.y + dy);
+    }
+
+    /// Returns the furthest that the content can be scrolled along each axis.
+    pub fn get_max_scroll_offset(&mut self) -> Point {
+        let [_, _, view_w, view_h] = self.get_viewport_rect();
+
+        make_point_i32(
+            (self.content_size.w - view_w).max(0),
+            (self.content_size.h - view_h).max(0),
+        )
+    }
+
+    /// Returns whether the vertical and horizontal scrollbars are shown, which they are when
+    /// the content does not fit along their axis.  A scrollbar takes space from the viewport,
+    /// which can make the content too large for the other axis as well.
+    fn get_scrollbars(&mut self) -> (bool, bool) {
+        let size = self.get_size();
+        let mut vertical = self.content_size.h > size.h;
+        let horizontal =
+            self.content_size.w > size.w - if vertical { SCROLLBAR_THICKNESS } else { 0 };
+
+        if horizontal && !vertical {
+            vertical = self.content_size.h > size.h - SCROLLBAR_THICKNESS;
+        }
+
+        (vertical, horizontal)
+    }
+
+    /// Returns the track of the scrollbar along `orientation`, relative to the origin of the
+    /// widget, or `None` if the scrollbar is not shown.
+    fn get_track_rect(&mut self, orientation: Orientation) -> Option<[i32; 4]> {
+        let size = self.get_size();
+        let (vertical, horizontal) = self.get_scrollbars();
+
+        match orientation {
+            Orientation::Vertical if vertical => Some([
+                size.w - SCROLLBAR_THICKNESS,
+                0,
+                SCROLLBAR_THICKNESS,
+                size.h - if horizontal { SCROLLBAR_THICKNESS } else { 0 },
+            ]),
+            Orientation::Horizontal if horizontal => Some([
+                0,
+                size.h - SCROLLBAR_THICKNESS,
+                size.w - if vertical { SCROLLBAR_THICKNESS } else { 0 },
+                SCROLLBAR_THICKNESS,
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Returns the thumb of the scrollbar along `orientation`, relative to the origin of the
+    /// widget, or `None` if the scrollbar is not shown.
+    fn get_thumb_rect(&mut self, orientation: Orientation) -> Option<[i32; 4]> {
+        let [x, y, w, h] = self.get_track_rect(orientation)?;
+        let [_, _, view_w, view_h] = self.get_viewport_rect();
+
+        Some(match orientation {
+            Orientation::Vertical => {
+                let (start, length) =
+                    get_thumb_span(view_h, self.content_size.h, self.scroll_offset.y, h);
+
+                [x, y + start, w, length]
+            }
+            Orientation::Horizontal => {
+                let (start, length) =
+                    get_thumb_span(view_w, self.content_size.w, self.scroll_offset.x, w);
+
+                [x + start, y, length, h]
+            }
+        })
+    }
+
+    /// Grabs the thumb of a scrollbar, or scrolls a page toward the point, if the mouse button
+    /// was pressed on the track of a scrollbar.
+    fn press_scrollbar(&mut self, point: &Point) {
+        for orientation in [Orientation::Vertical, Orientation::Horizontal]
+            .iter()
+            .copied()
+        {
+            let track = match self.get_track_rect(orientation) {
+                Some(track) if point_in_bounds(point, &track) => track,
+                _ => continue,
+            };
+            let thumb = self.get_thumb_rect(orientation).unwrap_or(track);
+            let [_, _, view_w, view_h] = self.get_viewport_rect();
+
+            if point_in_bounds(point, &thumb) {
+                let (grab_position, grab_offset) = match orientation {
+                    Orientation::Vertical => (point.y, self.scroll_offset.y),
+                    Orientation::Horizontal => (point.x, self.scroll_offset.x),
+                };
+
+                self.thumb_drag = Some(ThumbDrag {
+                    orientation,
+                    grab_position,
+                    grab_offset,
+                });
+                self.invalidate();
+            } else {
+                match orientation {
+                    Orientation::Vertical if point.y < thumb[1] => self.scroll_by(0, -view_h),
+                    Orientation::Vertical => self.scroll_by(0, view_h),
+                    Orientation::Horizontal if point.x < thumb[0] => self.scroll_by(-view_w, 0),
+                    Orientation::Horizontal => self.scroll_by(view_w, 0),
+                }
+            }
+
+            return;
+        }
+    }
+
+    /// Scrolls the content along with the thumb that is being dragged, as the mouse moves to
+    /// `point`.
+    fn drag_thumb(&mut self, point: &Point) {
+        let (orientation, grab_position, grab_offset) = match &self.thumb_drag {
+            Some(drag) => (drag.orientation, drag.grab_position, drag.grab_offset),
+            None => return,
+        };
+        let (track, thumb) = match (
+            self.get_track_rect(orientation),
+            self.get_thumb_rect(orientation),
+        ) {
+            (Some(track), Some(thumb)) => (track, thumb),
+            _ => return,
+        };
+        let max_offset = self.get_max_scroll_offset();
+        let (moved, travel, max_offset) = match orientation {
+            Orientation::Vertical => (point.y - grab_position, track[3] - thumb[3], max_offset.y),
+            Orientation::Horizontal => (point.x - grab_position, track[2] - thumb[2], max_offset.x),
+        };
+
+        if travel <= 0 {
+            return;
+        }
+
+        let offset =
+            grab_offset + (i64::from(moved) * i64::from(max_offset) / i64::from(travel)) as i32;
+
+        match orientation {
+            Orientation::Vertical => {
+                let x = self.scroll_offset.x;
+
+                self.scroll_to(x, offset);
+            }
+            Orientation::Horizontal => {
+                let y = self.scroll_offset.y;
+
+                self.scroll_to(offset, y);
+            }
+        }
+    }
+}
+
+impl Default for ScrollContainerWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `ScrollContainerWidget` object with the `Widget` traits implemented.
+/// This `Widget` scrolls in response to the mouse wheel and its scrollbars internally.
+impl Widget for ScrollContainerWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::ScrollArea
+    }
+
+    /// Sets the `Size` for this widget, given width and height, and limits the scroll position
+    /// to the new viewport.  Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+
+        let offset = self.scroll_offset.clone();
+
+        self.scroll_to(offset.x, offset.y);
+        self.invalidate();
+    }
+
+    fn get_scroll_offset(&mut self) -> Point {
+        self.scroll_offset.clone()
+    }
+
+    /// Scrolls the content so that the point `x`, `y` of the content is at the top left of
+    /// the widget, limited so that the content always fills the viewport if it is large
+    /// enough.  Invalidates the widget if the scroll position changed.
+    fn scroll_to(&mut self, x: i32, y: i32) {
+        let max_offset = self.get_max_scroll_offset();
+        let offset = make_point_i32(x.clamp(0, max_offset.x), y.clamp(0, max_offset.y));
+
+        if offset.x != self.scroll_offset.x || offset.y != self.scroll_offset.y {
+            self.scroll_offset = offset;
+            self.invalidate();
+        }
+    }
+
+    /// Indicates whether the content is larger than the viewport, so that it can be scrolled.
+    fn is_scrollable(&mut self) -> bool {
+        let max_offset = self.get_max_scroll_offset();
+
+        max_offset.x > 0 || max_offset.y > 0
+    }
+
+    /// Returns the area of the widget that is not covered by its scrollbars.
+    fn get_viewport_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+        let (vertical, horizontal) = self.get_scrollbars();
+
+        [
+            0,
+            0,
+            size.w - if vertical { SCROLLBAR_THICKNESS } else { 0 },
+            size.h - if horizontal { SCROLLBAR_THICKNESS } else { 0 },
+        ]
+    }
+
+    /// Scrolls in response to the mouse wheel or a touchpad, along both axes, passing the scroll
+    /// on to the widget underneath if the content cannot be scrolled any further in that
+    /// direction.  The fractions of a pixel that touchpads scroll by are kept until they add up
+    /// to a whole pixel.  All other events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let (dx, dy) = delta.to_points();
+                let x = self.scroll_remainder[0] - dx;
+                let y = self.scroll_remainder[1] - dy;
+                let previous_offset = self.scroll_offset.clone();
+
+                self.scroll_remainder = [x.fract(), y.fract()];
+                self.scroll_by(x.trunc() as i32, y.trunc() as i32);
+
+                if self.scroll_offset.x == previous_offset.x
+                    && self.scroll_offset.y == previous_offset.y
+                {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::MouseButtonDown { button, point, .. } if button == left_button => {
+                self.press_scrollbar(&point);
+            }
+
+            CallbackEvent::MouseMoved { point, .. } => self.drag_thumb(&point),
+
+            CallbackEvent::MouseButtonUpInside { button, .. }
+            | CallbackEvent::MouseButtonUpOutside { button, .. }
+                if button == left_button && self.thumb_drag.is_some() =>
+            {
+                self.thumb_drag = None;
+                self.invalidate();
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Draws the background of the viewport, and then the scrollbars that are shown.  The
+    /// thumb of a scrollbar that is being dragged is drawn in the accent color of the current
+    /// `Theme`.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        for orientation in [Orientation::Vertical, Orientation::Horizontal]
+            .iter()
+            .copied()
+        {
+            let (track, thumb) = match (
+                self.get_track_rect(orientation),
+                self.get_thumb_rect(orientation),
+            ) {
+                (Some(track), Some(thumb)) => (track, thumb),
+                _ => continue,
+            };
+            let dragging = match &self.thumb_drag {
+                Some(drag) => drag.orientation == orientation,
+                None => false,
+            };
+            let thumb_color = if dragging {
+                theme.accent_color
+            } else {
+                theme.border_color
+            };
+
+            Rectangle::new(apply_opacity(theme.disabled_color)).draw(
+                rect_to_f64(&track),
+                clip,
+                c.transform,
+                g,
+            );
+            Rectangle::new(apply_opacity(thumb_color)).draw(
+                rect_to_f64(&[thumb[0] + 2, thumb[1] + 2, thumb[2] - 4, thumb[3] - 4]),
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
+
+fn rect_to_f64(rect: &[i32; 4]) -> [f64; 4] {
+    [
+        f64::from(rect[0]),
+        f64::from(rect[1]),
+        f64::from(rect[2]),
+        f64::from(rect[3]),
+    ]
+}
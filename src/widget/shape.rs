@@ -0,0 +1,478 @@
+// Shape Helpers
+// Rounded rectangles, filled and stroked, shared by the widgets that draw them
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::f64::consts::PI;
+
+use crate::core::draw_target::*;
+use crate::core::point::{make_insets, Insets, Point, Size};
+
+/// The largest number of straight segments that each rounded corner is drawn with.
+const MAX_CORNER_SEGMENTS: usize = 16;
+
+/// The largest number of layers that a blurred shadow is drawn with.
+const MAX_SHADOW_LAYERS: usize = 8;
+
+/// The shortest dash, or gap between dashes, in pixels, that a border is stroked with.  Shorter
+/// ones could not be seen, and would take a very large number of rectangles to draw.
+const MIN_DASH_LENGTH: f64 = 0.5;
+
+/// This `enum` specifies how the sides of a border are stroked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BorderStyle {
+    /// A solid line.
+    Solid,
+
+    /// Dashes that are `dash` pixels long, with `gap` pixels between them.
+    Dashed { dash: f64, gap: f64 },
+
+    /// Square dots, as long as the side is thick, with `gap` pixels between them.
+    Dotted { gap: f64 },
+}
+
+/// Returns `radius`, clamped so that the corners of a rectangle of `w` by `h` do not overlap:
+/// no larger than half of the smaller of the two, and never negative.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    assert_eq!(clamp_corner_radius(100.0, 40.0, 8.0), 8.0);
+///    assert_eq!(clamp_corner_radius(100.0, 40.0, 50.0), 20.0);
+///    assert_eq!(clamp_corner_radius(100.0, 40.0, -5.0), 0.0);
+///    assert_eq!(clamp_corner_radius(0.0, 40.0, 8.0), 0.0);
+/// # }
+/// ```
+pub fn clamp_corner_radius(w: f64, h: f64, radius: f64) -> f64 {
+    radius.min(w.min(h) / 2.0).max(0.0)
+}
+
+/// Returns the corners of the polygon that outlines `rect` (X, Y, width, height), with corners
+/// rounded by `radius`, in clockwise order, starting at the left of the upper left-hand corner.
+/// The radius is clamped with `clamp_corner_radius`, so the polygon is always convex, and never
+/// crosses itself.  A radius of 0 gives the four corners of the rectangle.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    let square = get_rounded_rect_polygon([10.0, 10.0, 100.0, 40.0], 0.0);
+///
+///    assert_eq!(square, vec![[10.0, 10.0], [110.0, 10.0], [110.0, 50.0], [10.0, 50.0]]);
+///
+///    // Every point of a rounded rectangle is inside of the rectangle.
+///    let rounded = get_rounded_rect_polygon([10.0, 10.0, 100.0, 40.0], 500.0);
+///
+///    assert!(rounded.len() > 4);
+///    assert!(rounded.iter().all(|point| {
+///        point[0] >= 10.0 && point[0] <= 110.0 && point[1] >= 10.0 && point[1] <= 50.0
+///    }));
+/// # }
+/// ```
+pub fn get_rounded_rect_polygon(rect: [f64; 4], radius: f64) -> Vec<[f64; 2]> {
+    let radius = clamp_corner_radius(rect[2], rect[3], radius);
+
+    get_corner_points(rect, radius, get_corner_segments(radius))
+}
+
+/// Indicates whether `point`, relative to the upper left-hand corner of an object of `size`, is
+/// inside of the object once its corners are rounded by `radius`.  The center of the pixel at
+/// `point` is tested, so that the pixels that are drawn are the pixels that are hit.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    let size = Size { w: 100, h: 40 };
+///
+///    assert!(point_in_rounded_rect(&make_point_i32(50, 0), &size, 10.0));
+///    assert!(point_in_rounded_rect(&make_point_i32(5, 5), &size, 10.0));
+///    assert!(!point_in_rounded_rect(&make_point_i32(0, 0), &size, 10.0));
+///    assert!(!point_in_rounded_rect(&make_point_i32(99, 39), &size, 10.0));
+///    assert!(point_in_rounded_rect(&make_point_i32(99, 39), &size, 0.0));
+///    assert!(!point_in_rounded_rect(&make_point_i32(100, 20), &size, 0.0));
+/// # }
+/// ```
+pub fn point_in_rounded_rect(point: &Point, size: &Size, radius: f64) -> bool {
+    let (w, h) = (f64::from(size.w), f64::from(size.h));
+    let (x, y) = (f64::from(point.x) + 0.5, f64::from(point.y) + 0.5);
+
+    if x < 0.0 || y < 0.0 || x > w || y > h {
+        return false;
+    }
+
+    let radius = clamp_corner_radius(w, h, radius);
+
+    // Only the points in the square of each corner can be outside of its arc.
+    let dx = (radius - x).max(x - (w - radius)).max(0.0);
+    let dy = (radius - y).max(y - (h - radius)).max(0.0);
+
+    dx * dx + dy * dy <= radius * radius
+}
+
+/// Fills `rect` (X, Y, width, height) with `color`, with its corners rounded by `radius`.
+pub fn fill_rounded_rect(
+    color: types::Color,
+    rect: [f64; 4],
+    radius: f64,
+    clip: &DrawState,
+    transform: math::Matrix2d,
+    g: &mut DrawTarget,
+) {
+    Polygon::new(color).draw(&get_rounded_rect_polygon(rect, radius), clip, transform, g);
+}
+
+/// Draws a border of `thickness` pixels with `color` just inside of the edges of `rect` (X, Y,
+/// width, height), with its corners rounded by `radius`.  The inside of the border follows the
+/// curve of the outside, so the border is the same thickness all of the way around.  A border
+/// that is thicker than half of the rectangle fills it.
+pub fn stroke_rounded_rect(
+    color: types::Color,
+    rect: [f64; 4],
+    radius: f64,
+    thickness: f64,
+    clip: &DrawState,
+    transform: math::Matrix2d,
+    g: &mut DrawTarget,
+) {
+    let radius = clamp_corner_radius(rect[2], rect[3], radius);
+    let segments = get_corner_segments(radius);
+    let outer = get_corner_points(rect, radius, segments);
+    let inner_rect = [
+        rect[0] + thickness,
+        rect[1] + thickness,
+        rect[2] - thickness * 2.0,
+        rect[3] - thickness * 2.0,
+    ];
+
+    if inner_rect[2] <= 0.0 || inner_rect[3] <= 0.0 {
+        Polygon::new(color).draw(&outer, clip, transform, g);
+        return;
+    }
+
+    // Both outlines have the same number of points, so the border is drawn as the quads between
+    // each pair of points along them.
+    let inner_radius = clamp_corner_radius(inner_rect[2], inner_rect[3], radius - thickness);
+    let inner = get_corner_points(inner_rect, inner_radius, segments);
+
+    for index in 0..outer.len() {
+        let next = (index + 1) % outer.len();
+
+        Polygon::new(color).draw(
+            &[outer[index], outer[next], inner[next], inner[index]],
+            clip,
+            transform,
+            g,
+        );
+    }
+}
+
+/// Returns the number of segments that each corner of `radius` is drawn with: about one for each
+/// two pixels along the curve.
+fn get_corner_segments(radius: f64) -> usize {
+    if radius <= 0.0 {
+        0
+    } else {
+        ((radius * PI / 4.0).ceil() as usize).clamp(1, MAX_CORNER_SEGMENTS)
+    }
+}
+
+/// Returns the points along each of the four corners of `rect`, in clockwise order, with
+/// `segments` straight segments per corner of `radius`.  The radius must already be clamped.
+fn get_corner_points(rect: [f64; 4], radius: f64, segments: usize) -> Vec<[f64; 2]> {
+    let [x, y, w, h] = rect;
+    let centers = [
+        [x + radius, y + radius],
+        [x + w - radius, y + radius],
+        [x + w - radius, y + h - radius],
+        [x + radius, y + h - radius],
+    ];
+    let mut points = Vec::with_capacity(4 * (segments + 1));
+
+    for (corner, center) in centers.iter().enumerate() {
+        // Angles are clockwise from the right, so the upper left-hand corner turns from the
+        // left to the top, and each of the other corners continues a quarter of a turn further.
+        let start = PI + corner as f64 * PI / 2.0;
+
+        for step in 0..=segments {
+            let angle = if segments > 0 {
+                start + step as f64 * PI / 2.0 / segments as f64
+            } else {
+                start
+            };
+
+            points.push([
+                center[0] + radius * angle.cos(),
+                center[1] + radius * angle.sin(),
+            ]);
+        }
+    }
+
+    points
+}
+
+/// Returns the rectangles (X, Y, width, height) that the side of a border in `rect` is stroked
+/// with, in the `style` of the border.  The side runs along the width of `rect` if `horizontal`,
+/// or else along its height, and its thickness is the other dimension.  Dashes and dots always
+/// start at the top or left end of the side, so they stay in place from one frame to the next,
+/// and the last one is cut short where the side ends, so a side that is shorter than one dash is
+/// a single, short dash.  A dash or gap length that is not positive strokes a solid line.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    let dashed = BorderStyle::Dashed { dash: 10.0, gap: 5.0 };
+///    let dashes = get_dash_rects([0.0, 0.0, 100.0, 2.0], true, dashed);
+///
+///    assert_eq!(dashes.len(), 7);
+///    assert_eq!(dashes[0], [0.0, 0.0, 10.0, 2.0]);
+///    assert_eq!(dashes[1], [15.0, 0.0, 10.0, 2.0]);
+///    assert_eq!(dashes[6], [90.0, 0.0, 10.0, 2.0]);
+///
+///    // Dots are square, and sides shorter than a dash get a single, shorter one.
+///    let dots = get_dash_rects([0.0, 0.0, 2.0, 9.0], false, BorderStyle::Dotted { gap: 2.0 });
+///
+///    assert_eq!(dots, vec![[0.0, 0.0, 2.0, 2.0], [0.0, 4.0, 2.0, 2.0], [0.0, 8.0, 2.0, 1.0]]);
+///    assert_eq!(get_dash_rects([0.0, 0.0, 4.0, 1.0], true, dashed), vec![[0.0, 0.0, 4.0, 1.0]]);
+///    assert!(get_dash_rects([0.0, 0.0, 0.0, 1.0], true, dashed).is_empty());
+///
+///    // Without a gap, the line is solid.
+///    let no_gap = BorderStyle::Dashed { dash: 10.0, gap: 0.0 };
+///
+///    assert_eq!(get_dash_rects([0.0, 0.0, 50.0, 1.0], true, no_gap), vec![[0.0, 0.0, 50.0, 1.0]]);
+/// # }
+/// ```
+pub fn get_dash_rects(rect: [f64; 4], horizontal: bool, style: BorderStyle) -> Vec<[f64; 4]> {
+    let (length, thickness) = if horizontal {
+        (rect[2], rect[3])
+    } else {
+        (rect[3], rect[2])
+    };
+
+    if length <= 0.0 || thickness <= 0.0 {
+        return Vec::new();
+    }
+
+    let (dash, gap) = match style {
+        BorderStyle::Solid => return vec![rect],
+        BorderStyle::Dashed { dash, gap } => (dash, gap),
+        BorderStyle::Dotted { gap } => (thickness, gap),
+    };
+
+    if !(dash > 0.0 && gap > 0.0) {
+        return vec![rect];
+    }
+
+    let (dash, gap) = (dash.max(MIN_DASH_LENGTH), gap.max(MIN_DASH_LENGTH));
+    let mut rects = Vec::new();
+    let mut position = 0.0;
+
+    while position < length {
+        let piece = dash.min(length - position);
+
+        rects.push(if horizontal {
+            [rect[0] + position, rect[1], piece, thickness]
+        } else {
+            [rect[0], rect[1] + position, thickness, piece]
+        });
+        position += dash + gap;
+    }
+
+    rects
+}
+
+/// Returns the rectangles (X, Y, width, height) that a border just inside of the edges of `rect`
+/// is stroked with, given the thickness of each of its `sides`, and its `style`.  Sides without
+/// a thickness are not drawn, so a border of `make_insets(0, 0, 1, 0)` is a divider line along
+/// the bottom.  Where two sides meet, the top and bottom sides cover the corner, and the left and
+/// right sides run between them, so sides of different thicknesses meet without overlapping.
+/// Sides that are thicker than `rect` are cut down to fit.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    let rect = [0.0, 0.0, 100.0, 40.0];
+///    let divider = get_border_rects(rect, &make_insets(0, 0, 1, 0), BorderStyle::Solid);
+///
+///    assert_eq!(divider, vec![[0.0, 39.0, 100.0, 1.0]]);
+///
+///    let sides = get_border_rects(rect, &make_insets(4, 1, 2, 1), BorderStyle::Solid);
+///
+///    assert_eq!(
+///        sides,
+///        vec![
+///            [0.0, 0.0, 100.0, 4.0],
+///            [0.0, 38.0, 100.0, 2.0],
+///            [0.0, 4.0, 1.0, 34.0],
+///            [99.0, 4.0, 1.0, 34.0],
+///        ]
+///    );
+///
+///    let thick = make_uniform_insets(8);
+///
+///    assert_eq!(
+///        get_border_rects([0.0, 0.0, 10.0, 10.0], &thick, BorderStyle::Solid),
+///        vec![[0.0, 0.0, 10.0, 8.0], [0.0, 8.0, 10.0, 2.0]]
+///    );
+/// # }
+/// ```
+pub fn get_border_rects(rect: [f64; 4], sides: &Insets, style: BorderStyle) -> Vec<[f64; 4]> {
+    let [x, y, w, h] = rect;
+    let top = f64::from(sides.top).min(h).max(0.0);
+    let bottom = f64::from(sides.bottom).min(h - top).max(0.0);
+    let left = f64::from(sides.left).min(w).max(0.0);
+    let right = f64::from(sides.right).min(w - left).max(0.0);
+    let middle = h - top - bottom;
+    let edges = [
+        ([x, y, w, top], true),
+        ([x, y + h - bottom, w, bottom], true),
+        ([x, y + top, left, middle], false),
+        ([x + w - right, y + top, right, middle], false),
+    ];
+
+    edges
+        .iter()
+        .flat_map(|(edge, horizontal)| get_dash_rects(*edge, *horizontal, style))
+        .collect()
+}
+
+/// Strokes a border with `color` just inside of the edges of `rect` (X, Y, width, height), with
+/// the thickness of each of its `sides`, in `style`.  See `get_border_rects`.
+pub fn stroke_border(
+    color: types::Color,
+    rect: [f64; 4],
+    sides: &Insets,
+    style: BorderStyle,
+    clip: &DrawState,
+    transform: math::Matrix2d,
+    g: &mut DrawTarget,
+) {
+    let rectangle = Rectangle::new(color);
+
+    for border_rect in get_border_rects(rect, sides, style) {
+        rectangle.draw(border_rect, clip, transform, g);
+    }
+}
+
+/// A drop shadow, drawn underneath a `Widget` with the same shape, moved by `offset`, and
+/// softened over `blur` pixels.  The soft edge is drawn as a stack of translucent layers, from
+/// half of the blur outside of the offset shape to half of the blur inside of it, so a shadow
+/// needs no textures, and costs a handful of polygons to draw, at any size.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::shape::*;
+/// # fn main() {
+///    let shadow = Shadow {
+///        color: [0.0, 0.0, 0.0, 0.5],
+///        offset: make_point_i32(0, 4),
+///        blur: 8.0,
+///    };
+///
+///    // The shadow reaches four pixels outside of the widget, and eight below it.
+///    assert_eq!(shadow.get_extent(), make_insets(0, 4, 8, 4));
+///
+///    let layers = shadow.get_layers(&Size { w: 100, h: 40 }, 0.0);
+///
+///    assert_eq!(layers.len(), 8);
+///
+///    // The layers add up to the color of the shadow where they all overlap.
+///    let alpha = 1.0 - layers.iter().fold(1.0, |left, layer| left * (1.0 - layer.0[3]));
+///
+///    assert!((alpha - 0.5).abs() < 0.0001);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shadow {
+    pub color: types::Color,
+    pub offset: Point,
+    pub blur: f64,
+}
+
+/// Implementation of the `Shadow`.
+impl Shadow {
+    /// Returns how far the shadow reaches outside of the bounds of the `Widget` that casts it,
+    /// on each side, in whole pixels.
+    pub fn get_extent(&self) -> Insets {
+        let spread = self.blur.max(0.0) / 2.0;
+        let reach = |offset: i32| (spread + f64::from(offset)).ceil().max(0.0) as i32;
+
+        make_insets(
+            reach(-self.offset.y),
+            reach(self.offset.x),
+            reach(self.offset.y),
+            reach(-self.offset.x),
+        )
+    }
+
+    /// Returns the layers that the shadow of a `Widget` of `size`, with corners rounded by
+    /// `radius`, is drawn with: the color of each layer, and its polygon, relative to the upper
+    /// left-hand corner of the `Widget`.
+    pub fn get_layers(&self, size: &Size, radius: f64) -> Vec<(types::Color, Vec<[f64; 2]>)> {
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let blur = self.blur.max(0.0);
+
+        if w <= 0.0 || h <= 0.0 || self.color[3] <= 0.0 {
+            return Vec::new();
+        }
+
+        let count = (blur.ceil() as usize).clamp(1, MAX_SHADOW_LAYERS);
+
+        // Each layer covers what is left of the shadow under the layers above it, so that all
+        // of them together reach the alpha of the shadow color.
+        let alpha = 1.0 - (1.0 - self.color[3]).powf(1.0 / count as f32);
+        let color = [self.color[0], self.color[1], self.color[2], alpha];
+        let (x, y) = (f64::from(self.offset.x), f64::from(self.offset.y));
+        let mut layers = Vec::with_capacity(count);
+
+        for layer in 0..count {
+            let grow = if count > 1 {
+                blur / 2.0 - blur * layer as f64 / (count - 1) as f64
+            } else {
+                0.0
+            };
+            let rect = [x - grow, y - grow, w + grow * 2.0, h + grow * 2.0];
+
+            if rect[2] > 0.0 && rect[3] > 0.0 {
+                let polygon = get_rounded_rect_polygon(rect, (radius + grow).max(0.0));
+
+                layers.push((color, polygon));
+            }
+        }
+
+        layers
+    }
+
+    /// Draws the shadow of a `Widget` of `size`, with corners rounded by `radius`, using the
+    /// transform of the `Widget`, so that it lines up with it.  The colors are drawn as they are,
+    /// so the draw opacity has to be applied to the color of the shadow first.
+    pub fn draw(
+        &self,
+        size: &Size,
+        radius: f64,
+        clip: &DrawState,
+        transform: math::Matrix2d,
+        g: &mut DrawTarget,
+    ) {
+        for (color, polygon) in self.get_layers(size, radius) {
+            Polygon::new(color).draw(&polygon, clip, transform, g);
+        }
+    }
+}
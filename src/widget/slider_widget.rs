@@ -0,0 +1,534 @@
+// Slider Widget
+// Extensible widget for the widget library - handles a slider with a draggable thumb.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the value of a `SliderWidget`.
+pub type SliderCallback = Box<dyn FnMut(f64)>;
+
+/// The length of the thumb of a `SliderWidget` along its track, in pixels.
+pub const SLIDER_THUMB_LENGTH: i32 = 12;
+
+/// The thickness of the track of a `SliderWidget`, in pixels.
+const SLIDER_TRACK_THICKNESS: f64 = 4.0;
+
+/// The fraction of the range that the arrow keys move a `SliderWidget` that has no step.
+const SLIDER_KEY_FRACTION: f64 = 0.01;
+
+/// Clamps `value` to the range from `min` to `max`, and snaps it to the nearest multiple of
+/// `step` above `min`, or to `max` if that is nearer.  A `step` of 0 allows any value in the
+/// range.  A range in which `min` and `max` are equal only contains `min`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::slider_widget::*;
+/// # fn main() {
+///    assert_eq!(snap_value(0.37, 0.0, 1.0, 0.25), 0.25);
+///    assert_eq!(snap_value(0.38, 0.0, 1.0, 0.25), 0.5);
+///    assert_eq!(snap_value(8.0, 0.0, 10.0, 3.0), 9.0);
+///    assert_eq!(snap_value(12.0, 0.0, 10.0, 3.0), 10.0);
+///    assert_eq!(snap_value(-4.0, 0.0, 10.0, 0.0), 0.0);
+///    assert_eq!(snap_value(4.0, 5.0, 5.0, 1.0), 5.0);
+/// # }
+/// ```
+pub fn snap_value(value: f64, min: f64, max: f64, step: f64) -> f64 {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+    if value.is_nan() {
+        return min;
+    }
+
+    let value = value.max(min).min(max);
+
+    if step <= 0.0 {
+        return value;
+    }
+
+    let snapped = (min + ((value - min) / step).round() * step).min(max);
+
+    // The largest value stays reachable when the range is not a whole number of steps.
+    if max - value < (value - snapped).abs() {
+        max
+    } else {
+        snapped
+    }
+}
+
+/// Returns the fraction of the range from `min` to `max` at which `value` lies, from 0.0 to
+/// 1.0.  The fraction of a range in which `min` and `max` are equal is always 0.0.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::slider_widget::*;
+/// # fn main() {
+///    assert_eq!(value_to_fraction(25.0, 0.0, 100.0), 0.25);
+///    assert_eq!(value_to_fraction(150.0, 0.0, 100.0), 1.0);
+///    assert_eq!(value_to_fraction(5.0, 5.0, 5.0), 0.0);
+/// # }
+/// ```
+pub fn value_to_fraction(value: f64, min: f64, max: f64) -> f64 {
+    if (max - min).abs() < f64::EPSILON {
+        return 0.0;
+    }
+
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+/// This is the `SliderWidget`, which edits a number between a smallest and a largest value by
+/// moving a thumb along a track, from left to right, or from bottom to top.  Pressing anywhere on
+/// the track moves the thumb there, and dragging moves it continuously, even when the mouse
+/// leaves the slider, as it is captured while the button is held.  The arrow keys move the thumb
+/// by one step while the slider is focused, and `Home` and `End` move it to either end.  Scrolling
+/// over the slider moves the thumb by one step for each line scrolled, up or to the right;
+/// touchpads move it by a step once enough has been scrolled.  Once the thumb has reached the end
+/// it is scrolled towards, the scroll is passed on, such as to an enclosing
+/// `ScrollContainerWidget`.
+///
+/// The range and step are stored in the `MinValue`, `MaxValue`, and `StepValue` config keys,
+/// which default to a range from 0.0 to 1.0 without a step.  The value snaps to the step, so the
+/// thumb only stops at the positions of whole steps.  The `on_value_changed` callback is called
+/// every time the value changes, and `on_drag_finished` with the final value when the mouse
+/// button is released.
+///
+/// Example usage:
+/// ```
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # use pushrod::widget::slider_widget::*;
+/// # fn main() {
+///    let mut slider = SliderWidget::new();
+///    let value = Rc::new(Cell::new(0.0));
+///    let value_clone = value.clone();
+///
+///    slider.set_range(0.0, 100.0, 5.0);
+///    slider.on_value_changed(Box::new(move |new_value| value_clone.set(new_value)));
+///
+///    slider.set_value(42.0);
+///    assert_eq!(slider.get_value(), 40.0);
+///    assert_eq!(value.get(), 40.0);
+///
+///    slider.set_value(250.0);
+///    assert_eq!(slider.get_value(), 100.0);
+/// # }
+/// ```
+///
+/// Scrolling a slider in a `ScrollContainerWidget`:
+/// ```
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # use pushrod::core::test_harness::*;
+/// # use pushrod::widget::scroll_container_widget::*;
+/// # use pushrod::widget::slider_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut harness = TestHarness::new(400, 300);
+///    let mut container = ScrollContainerWidget::new();
+///    let mut slider = SliderWidget::new();
+///    let value = Rc::new(Cell::new(0.0));
+///    let value_clone = value.clone();
+///
+///    container.set_size(200, 100);
+///    container.set_content_size(200, 400);
+///    slider.set_origin(10, 10);
+///    slider.set_size(100, 40);
+///    slider.set_range(0.0, 10.0, 1.0);
+///    slider.on_value_changed(Box::new(move |new_value| value_clone.set(new_value)));
+///
+///    let container_id = harness.add_widget("Container", Box::new(container));
+///
+///    harness.add_widget_to_parent("Slider", Box::new(slider), container_id);
+///    harness.mouse_move(50, 30);
+///
+///    harness.scroll(0.0, 3.0);
+///    assert_eq!(value.get(), 3.0);
+///
+///    // Once the thumb is at the end, the container is scrolled instead.
+///    harness.scroll(0.0, -5.0);
+///    harness.scroll(0.0, -1.0);
+///    assert_eq!(value.get(), 0.0);
+///
+///    let mut widget_store = harness.get_widget_store();
+///
+///    assert_eq!(
+///        widget_store.get_widget_for_id(container_id).borrow_mut().get_scroll_offset().y,
+///        20
+///    );
+/// # }
+/// ```
+pub struct SliderWidget {
+    config: Configurable,
+    value: f64,
+    orientation: Orientation,
+    dragging: bool,
+    scroll_lines: f64,
+    on_value_changed: Option<SliderCallback>,
+    on_drag_finished: Option<SliderCallback>,
+}
+
+/// Implementation of the constructor for the `SliderWidget`.
+impl SliderWidget {
+    /// Creates a new horizontal slider, with a range from 0.0 to 1.0, and a value of 0.0.  The
+    /// slider can be focused, so that it can be moved from the keyboard.
+    pub fn new() -> Self {
+        let mut widget = Self {
+            config: Configurable::new(),
+            value: 0.0,
+            orientation: Orientation::Horizontal,
+            dragging: false,
+            scroll_lines: 0.0,
+            on_value_changed: None,
+            on_drag_finished: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(true, false);
+        widget
+    }
+
+    /// Sets the closure that is called with the new value every time the value changes.
+    pub fn on_value_changed(&mut self, callback: SliderCallback) {
+        self.on_value_changed = Some(callback);
+    }
+
+    /// Sets the closure that is called with the final value when the thumb has been dragged,
+    /// and the mouse button is released.
+    pub fn on_drag_finished(&mut self, callback: SliderCallback) {
+        self.on_drag_finished = Some(callback);
+    }
+
+    /// Sets the smallest and largest values of the slider, and the step between its values, or
+    /// 0 to allow any value.  The value is moved into the new range.
+    pub fn set_range(&mut self, min: f64, max: f64, step: f64) {
+        self.config().set(MinValue(min));
+        self.config().set(MaxValue(max));
+        self.config().set(StepValue(step));
+
+        let value = self.value;
+
+        self.set_value(value);
+        self.invalidate();
+    }
+
+    /// Retrieves the smallest value of the slider.  Defaults to 0.0.
+    pub fn get_min(&mut self) -> f64 {
+        self.config().get_or(MinValue(0.0)).0
+    }
+
+    /// Retrieves the largest value of the slider.  Defaults to 1.0.
+    pub fn get_max(&mut self) -> f64 {
+        self.config().get_or(MaxValue(1.0)).0
+    }
+
+    /// Retrieves the step between the values of the slider.  Defaults to 0, which allows any
+    /// value.
+    pub fn get_step(&mut self) -> f64 {
+        self.config().get_or(StepValue(0.0)).0
+    }
+
+    /// Sets the value of the slider, clamped to its range and snapped to its step.  If the value
+    /// changed, the `on_value_changed` callback is called, and the widget is invalidated.
+    pub fn set_value(&mut self, value: f64) {
+        let value = snap_value(value, self.get_min(), self.get_max(), self.get_step());
+
+        if (value - self.value).abs() < f64::EPSILON {
+            return;
+        }
+
+        self.value = value;
+
+        if let Some(callback) = &mut self.on_value_changed {
+            callback(value);
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the value of the slider.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Sets the direction of the slider.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.invalidate();
+    }
+
+    /// Retrieves the direction of the slider.  Defaults to `Orientation::Horizontal`.
+    pub fn get_orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Indicates whether the thumb is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Returns the distance that the thumb can travel along the track, in pixels.
+    fn get_travel(&mut self) -> i32 {
+        let size = self.get_size();
+        let length = match self.orientation {
+            Orientation::Horizontal => size.w,
+            Orientation::Vertical => size.h,
+        };
+
+        (length - SLIDER_THUMB_LENGTH).max(0)
+    }
+
+    /// Returns the fraction of the track that the thumb is at, from 0.0 at the smallest value,
+    /// to 1.0 at the largest.
+    fn get_fraction(&mut self) -> f64 {
+        value_to_fraction(self.value, self.get_min(), self.get_max())
+    }
+
+    /// Moves the thumb so that it is centered on `point`, which is relative to the origin of
+    /// the slider.
+    fn set_value_from_point(&mut self, point: &Point) {
+        let travel = f64::from(self.get_travel());
+        let half_thumb = f64::from(SLIDER_THUMB_LENGTH) / 2.0;
+        let fraction = if travel <= 0.0 {
+            0.0
+        } else {
+            match self.orientation {
+                Orientation::Horizontal => (f64::from(point.x) - half_thumb) / travel,
+                Orientation::Vertical => 1.0 - (f64::from(point.y) - half_thumb) / travel,
+            }
+        };
+        let (min, max) = (self.get_min(), self.get_max());
+
+        self.set_value(min + (max - min) * fraction.clamp(0.0, 1.0));
+    }
+
+    /// Moves the value by `steps` steps, or by a hundredth of the range if the slider has no
+    /// step.
+    fn nudge(&mut self, steps: f64) {
+        let step = self.get_step();
+        let step = if step > 0.0 {
+            step
+        } else {
+            (self.get_max() - self.get_min()).abs() * SLIDER_KEY_FRACTION
+        };
+        let value = self.value + step * steps;
+
+        self.set_value(value);
+    }
+
+    /// Moves the value in response to a scroll of `lines` lines.  A slider with a step moves by
+    /// whole steps, keeping the fractions of a line that touchpads scroll by until they add up to
+    /// a step.  Returns `false` if the value is already at the end that it is scrolled towards.
+    fn scroll_by_lines(&mut self, lines: f64) -> bool {
+        let (low, high) = (self.get_min(), self.get_max());
+        let (min, max) = (low.min(high), low.max(high));
+
+        if (lines > 0.0 && self.value >= max) || (lines < 0.0 && self.value <= min) {
+            self.scroll_lines = 0.0;
+            return false;
+        }
+
+        if self.get_step() > 0.0 {
+            let lines = self.scroll_lines + lines;
+
+            self.scroll_lines = lines.fract();
+            self.nudge(lines.trunc());
+        } else {
+            self.nudge(lines);
+        }
+
+        true
+    }
+
+    fn finish_drag(&mut self) {
+        if !self.dragging {
+            return;
+        }
+
+        self.dragging = false;
+
+        if let Some(callback) = &mut self.on_drag_finished {
+            callback(self.value);
+        }
+
+        self.invalidate();
+    }
+}
+
+impl Default for SliderWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of the `SliderWidget` object with the `Widget` traits implemented.  This
+/// `Widget` responds to the mouse and the keyboard internally, and calls its callbacks when its
+/// value changes.
+impl Widget for SliderWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Slider
+    }
+
+    fn get_accessible_value(&mut self) -> Option<String> {
+        Some(format!("{}", self.value))
+    }
+
+    /// Enables or disables this widget.  A disabled slider is drawn in the disabled color of the
+    /// current `Theme`, and stops any drag that is in progress.  Invalidates the widget
+    /// afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+            self.finish_drag();
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.invalidate();
+    }
+
+    /// Moves the thumb when the slider is scrolled over, passing the scroll on when the slider
+    /// is disabled, or the thumb is already at the end that it is scrolled towards.  Scrolling
+    /// to the left moves the thumb the same way as scrolling down.  All other events are handled
+    /// by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let (x, y) = delta.to_lines();
+
+                if self.is_disabled() || !self.scroll_by_lines(y - x) {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::MouseButtonDown { button, point, .. } if button == left_button => {
+                self.dragging = true;
+                self.set_value_from_point(&point);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseMoved { point, .. } if self.dragging => {
+                self.set_value_from_point(&point);
+            }
+
+            CallbackEvent::MouseButtonUpInside { button, .. }
+            | CallbackEvent::MouseButtonUpOutside { button, .. }
+                if button == left_button =>
+            {
+                self.finish_drag();
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => match key {
+                Key::Left | Key::Down => self.nudge(-1.0),
+                Key::Right | Key::Up => self.nudge(1.0),
+                Key::Home => {
+                    let min = self.get_min();
+
+                    self.set_value(min);
+                }
+                Key::End => {
+                    let max = self.get_max();
+
+                    self.set_value(max);
+                }
+                _ => (),
+            },
+
+            CallbackEvent::LostFocus { .. } => self.finish_drag(),
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Draws the track, the part of the track up to the thumb in the secondary color, and the
+    /// thumb.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let thumb_length = f64::from(SLIDER_THUMB_LENGTH);
+        let thumb_offset = f64::from(self.get_travel()) * self.get_fraction();
+        let (fill_color, thumb_color) = if self.is_disabled() {
+            (theme.disabled_color, theme.disabled_color)
+        } else if self.dragging {
+            (self.get_secondary_color(), theme.accent_color)
+        } else {
+            (self.get_secondary_color(), theme.border_color)
+        };
+        let (track, fill, thumb) = match self.orientation {
+            Orientation::Horizontal => {
+                let track_y = (h - SLIDER_TRACK_THICKNESS) / 2.0;
+
+                (
+                    [0.0, track_y, w, SLIDER_TRACK_THICKNESS],
+                    [0.0, track_y, thumb_offset, SLIDER_TRACK_THICKNESS],
+                    [thumb_offset, 0.0, thumb_length, h],
+                )
+            }
+            Orientation::Vertical => {
+                let track_x = (w - SLIDER_TRACK_THICKNESS) / 2.0;
+                let thumb_y = h - thumb_length - thumb_offset;
+
+                (
+                    [track_x, 0.0, SLIDER_TRACK_THICKNESS, h],
+                    [track_x, thumb_y, SLIDER_TRACK_THICKNESS, h - thumb_y],
+                    [0.0, thumb_y, w, thumb_length],
+                )
+            }
+        };
+
+        Rectangle::new(apply_opacity(theme.border_color)).draw(track, clip, c.transform, g);
+        Rectangle::new(apply_opacity(fill_color)).draw(fill, clip, c.transform, g);
+        Rectangle::new(apply_opacity(self.get_color())).draw(thumb, clip, c.transform, g);
+        Rectangle::new_border(apply_opacity(thumb_color), 0.5).draw(
+            [thumb[0] + 0.5, thumb[1] + 0.5, thumb[2] - 1.0, thumb[3] - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
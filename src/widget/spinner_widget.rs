@@ -0,0 +1,779 @@
+// Spinner Widget
+// Extensible widget for the widget library - handles a number that is stepped up and down.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::slider_widget::snap_value;
+use crate::widget::text_input_widget::*;
+use crate::widget::theme::*;
+use crate::widget::timer_widget::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the new value of a `SpinnerWidget`.
+pub type SpinnerCallback = Box<dyn FnMut(f64)>;
+
+/// The width of the column of arrow buttons of a `SpinnerWidget`, in pixels.
+pub const DEFAULT_SPINNER_ARROW_WIDTH: i32 = 16;
+
+/// The number of milliseconds that an arrow button of a `SpinnerWidget` is held down before it
+/// starts to repeat.
+pub const SPINNER_REPEAT_DELAY_MS: u64 = 400;
+
+/// The number of milliseconds between the first repeats of a held arrow button.
+pub const SPINNER_REPEAT_INTERVAL_MS: u64 = 100;
+
+/// The shortest number of milliseconds between repeats of a held arrow button, which it
+/// reaches as it accelerates.
+pub const SPINNER_MIN_REPEAT_INTERVAL_MS: u64 = 20;
+
+/// The number of repeats after which the interval between repeats is halved.
+const SPINNER_ACCELERATION_REPEATS: u32 = 10;
+
+/// How far the background of a hovered arrow button is blended toward the accent color of the
+/// current `Theme`.  Pressed buttons are blended twice as far.
+const SPINNER_HOVER_AMOUNT: f32 = 0.2;
+
+/// Returns the number of milliseconds until the next repeat of a held arrow button that has
+/// repeated `repeats` times.  The interval halves every ten repeats, until it reaches
+/// `SPINNER_MIN_REPEAT_INTERVAL_MS`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::spinner_widget::*;
+/// # fn main() {
+///    assert_eq!(get_repeat_interval(0), 100);
+///    assert_eq!(get_repeat_interval(9), 100);
+///    assert_eq!(get_repeat_interval(10), 50);
+///    assert_eq!(get_repeat_interval(25), 25);
+///    assert_eq!(get_repeat_interval(1000), SPINNER_MIN_REPEAT_INTERVAL_MS);
+/// # }
+/// ```
+pub fn get_repeat_interval(repeats: u32) -> u64 {
+    let halvings = (repeats / SPINNER_ACCELERATION_REPEATS).min(63);
+
+    (SPINNER_REPEAT_INTERVAL_MS >> halvings).max(SPINNER_MIN_REPEAT_INTERVAL_MS)
+}
+
+/// Returns `value` as text with `decimals` digits after the decimal point, or as a whole number
+/// if `decimals` is 0.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::spinner_widget::*;
+/// # fn main() {
+///    assert_eq!(format_spinner_value(42.0, 0), "42");
+///    assert_eq!(format_spinner_value(0.1 + 0.2, 2), "0.30");
+///    assert_eq!(format_spinner_value(-1.5, 1), "-1.5");
+/// # }
+/// ```
+pub fn format_spinner_value(value: f64, decimals: u32) -> String {
+    format!("{:.*}", decimals as usize, value)
+}
+
+/// Reads a value from `text`, which may be surrounded by spaces.  Returns `None` if the text is
+/// not a number, or if it has a decimal point when `decimals` is 0.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::spinner_widget::*;
+/// # fn main() {
+///    assert_eq!(parse_spinner_value(" 12 ", 0), Some(12.0));
+///    assert_eq!(parse_spinner_value("-2.25", 2), Some(-2.25));
+///    assert_eq!(parse_spinner_value("2.5", 0), None);
+///    assert_eq!(parse_spinner_value("-", 0), None);
+///    assert_eq!(parse_spinner_value("", 2), None);
+/// # }
+/// ```
+pub fn parse_spinner_value(text: &str, decimals: u32) -> Option<f64> {
+    let text = text.trim();
+
+    if decimals == 0 && text.contains('.') {
+        return None;
+    }
+
+    text.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// Rounds `value` to `decimals` digits after the decimal point.
+fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    let scale = 10_f64.powi(decimals as i32);
+
+    (value * scale).round() / scale
+}
+
+/// The arrow buttons of a `SpinnerWidget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SpinnerArrow {
+    Up,
+    Down,
+}
+
+impl SpinnerArrow {
+    /// Returns the number of steps that the arrow moves the value by.
+    fn get_steps(self) -> f64 {
+        match self {
+            SpinnerArrow::Up => 1.0,
+            SpinnerArrow::Down => -1.0,
+        }
+    }
+}
+
+/// This is the `SpinnerWidget`, which edits a number between a smallest and a largest value.
+/// The number is shown as text, next to a pair of arrow buttons that step it up and down.
+/// Holding an arrow button down repeats it after `SPINNER_REPEAT_DELAY_MS`, faster the longer
+/// it is held.  The mouse wheel steps the value once per notch, and so do the `Up` and `Down`
+/// keys while the spinner is focused.
+///
+/// While it is focused, the number can also be typed in.  Only digits are accepted, along with
+/// a decimal point if the spinner has decimals, and a minus sign if its range includes negative
+/// numbers.  The typed number is clamped to the range when `Enter` is pressed, or the spinner
+/// loses focus, and text that is not a number is replaced with the value.  `Escape` undoes the
+/// typing.
+///
+/// The range and step are stored in the `MinValue`, `MaxValue`, and `StepValue` config keys,
+/// which default to a range from 0 to 100 in steps of 1.  The `on_value_changed` callback is
+/// called every time the value changes.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::spinner_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Spinner", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut spinner = SpinnerWidget::new(pushrod.get_factory(), font_name);
+///
+///    spinner.set_origin(20, 20);
+///    spinner.set_size(100, 28);
+///    spinner.set_range(-10.0, 10.0, 0.5);
+///    spinner.set_decimals(1);
+///    spinner.on_value_changed(Box::new(|value| eprintln!("Value: {}", value)));
+///    pushrod.add_widget("Spinner", Box::new(spinner));
+/// # }
+/// ```
+pub struct SpinnerWidget {
+    config: Configurable,
+    text_input: Box<TextInputWidget>,
+    value: f64,
+    decimals: u32,
+    hovered_arrow: Option<SpinnerArrow>,
+    pressed_arrow: Option<SpinnerArrow>,
+    repeat_timer: TimerWidget,
+    repeat_count: u32,
+    on_value_changed: Option<SpinnerCallback>,
+}
+
+/// Implementation of the constructor for the `SpinnerWidget`.
+impl SpinnerWidget {
+    /// Creates a new spinner with a value of 0, drawn with the font `font_name` from the
+    /// `assets` directory.  Panics if the font cannot be loaded; use `font_cache::load_font` and
+    /// `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, Box::new(SystemClock))
+    }
+
+    /// Creates a new spinner with a value of 0, that draws its text in a font from the font
+    /// cache, and repeats its held arrow buttons using the specified `Clock`.  The spinner can
+    /// be focused, so that it can be typed into.
+    ///
+    /// Example:
+    /// ```
+    /// # use piston_window::*;
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::spinner_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn hold_up_arrow(font: SharedGlyphs) {
+    ///    let clock = ManualClock::new();
+    ///    let mut spinner = SpinnerWidget::from_font(font, Box::new(clock.clone()));
+    ///    let left = Button::Mouse(MouseButton::Left);
+    ///    let up_arrow = Point { x: 90, y: 4 };
+    ///
+    ///    spinner.set_size(100, 28);
+    ///    let point = up_arrow.clone();
+    ///
+    ///    spinner.handle_event(CallbackEvent::MouseMoved { widget_id: WidgetId::from(1), point });
+    ///    spinner.handle_event(CallbackEvent::MouseButtonDown {
+    ///        widget_id: WidgetId::from(1),
+    ///        button: left,
+    ///        point: up_arrow,
+    ///    });
+    ///    assert_eq!(spinner.get_value(), 1.0);
+    ///
+    ///    // The arrow starts repeating once it has been held down for a while.
+    ///    clock.advance(SPINNER_REPEAT_DELAY_MS);
+    ///    spinner.update(0);
+    ///    assert_eq!(spinner.get_value(), 2.0);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn from_font(font: SharedGlyphs, clock: Box<dyn Clock>) -> Self {
+        let mut repeat_timer = TimerWidget::with_clock(clock);
+
+        repeat_timer.set_enabled(false);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_input: Box::new(TextInputWidget::from_font(font, Box::new(SystemClock))),
+            value: 0.0,
+            decimals: 0,
+            hovered_arrow: None,
+            pressed_arrow: None,
+            repeat_timer,
+            repeat_count: 0,
+            on_value_changed: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget.sync_text();
+        widget
+    }
+
+    /// Sets the closure that is called with the new value every time the value changes.
+    pub fn on_value_changed(&mut self, callback: SpinnerCallback) {
+        self.on_value_changed = Some(callback);
+    }
+
+    /// Sets the smallest and largest values of the spinner, and the step that the arrow buttons
+    /// move the value by.  The value is moved into the new range.
+    pub fn set_range(&mut self, min: f64, max: f64, step: f64) {
+        self.config().set(MinValue(min));
+        self.config().set(MaxValue(max));
+        self.config().set(StepValue(step));
+
+        let value = self.value;
+
+        self.set_value(value);
+        self.invalidate();
+    }
+
+    /// Retrieves the smallest value of the spinner.  Defaults to 0.0.
+    pub fn get_min(&mut self) -> f64 {
+        self.config().get_or(MinValue(0.0)).0
+    }
+
+    /// Retrieves the largest value of the spinner.  Defaults to 100.0.
+    pub fn get_max(&mut self) -> f64 {
+        self.config().get_or(MaxValue(100.0)).0
+    }
+
+    /// Retrieves the step that the arrow buttons move the value by.  Defaults to 1.0.
+    pub fn get_step(&mut self) -> f64 {
+        self.config().get_or(StepValue(1.0)).0
+    }
+
+    /// Sets the number of digits after the decimal point with which the value is shown and
+    /// typed, and to which it is rounded.  A spinner with no decimals only holds whole numbers.
+    pub fn set_decimals(&mut self, decimals: u32) {
+        self.decimals = decimals;
+
+        let value = self.value;
+
+        self.set_value(value);
+        self.sync_text();
+    }
+
+    /// Retrieves the number of digits after the decimal point of the value.  Defaults to 0.
+    pub fn get_decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Sets the value of the spinner, clamped to its range, and rounded to its decimals.  If the
+    /// value changed, the `on_value_changed` callback is called.  The text is replaced with the
+    /// value, and the widget is invalidated.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::spinner_widget::*;
+    /// # fn set_spinner_value(font: SharedGlyphs) {
+    ///    let mut spinner = SpinnerWidget::from_font(font, Box::new(SystemClock));
+    ///    let value = Rc::new(Cell::new(0.0));
+    ///    let value_clone = value.clone();
+    ///
+    ///    spinner.set_range(0.0, 10.0, 0.25);
+    ///    spinner.set_decimals(1);
+    ///    spinner.on_value_changed(Box::new(move |new_value| value_clone.set(new_value)));
+    ///
+    ///    spinner.set_value(2.25);
+    ///    assert_eq!(spinner.get_value(), 2.3);
+    ///    assert_eq!(value.get(), 2.3);
+    ///    assert_eq!(spinner.get_text(), "2.3");
+    ///
+    ///    spinner.set_value(250.0);
+    ///    assert_eq!(spinner.get_value(), 10.0);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn set_value(&mut self, value: f64) {
+        let value = snap_value(value, self.get_min(), self.get_max(), 0.0);
+        let value = round_to_decimals(value, self.decimals);
+
+        if (value - self.value).abs() >= f64::EPSILON {
+            self.value = value;
+
+            if let Some(callback) = &mut self.on_value_changed {
+                callback(value);
+            }
+        }
+
+        self.sync_text();
+        self.invalidate();
+    }
+
+    /// Retrieves the value of the spinner.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Retrieves the text that is shown, which is the value, or the number that is being typed.
+    pub fn get_text(&self) -> &str {
+        self.text_input.get_text()
+    }
+
+    /// Sets the font size of the text.  Invalidates the widget afterward.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.text_input.set_font_size(font_size);
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the text.  Defaults to the font size of the current `Theme`.
+    pub fn get_font_size(&self) -> u32 {
+        self.text_input.get_font_size()
+    }
+
+    /// Sets the color of the text and the arrows for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.text_input.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text and the arrows for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.text_input.get_text_color()
+    }
+
+    /// Moves the value by `steps` steps.
+    fn step_by(&mut self, steps: f64) {
+        let value = self.value + self.get_step() * steps;
+
+        self.set_value(value);
+    }
+
+    /// Replaces the text with the value.
+    fn sync_text(&mut self) {
+        let text = format_spinner_value(self.value, self.decimals);
+
+        if self.text_input.get_text() != text {
+            self.text_input.set_text(&text);
+        }
+    }
+
+    /// Sets the value to the number that was typed, or puts the value back into the text if
+    /// the text is not a number.
+    fn commit(&mut self) {
+        match parse_spinner_value(self.text_input.get_text(), self.decimals) {
+            Some(value) => self.set_value(value),
+            None => self.sync_text(),
+        }
+    }
+
+    /// Indicates whether `c` can be typed into the spinner.
+    fn accepts_char(&mut self, c: char) -> bool {
+        c.is_ascii_digit() || (c == '.' && self.decimals > 0) || (c == '-' && self.get_min() < 0.0)
+    }
+
+    /// Returns the arrow button under `point`, if there is one.
+    fn get_arrow_at(&mut self, point: &Point) -> Option<SpinnerArrow> {
+        let size = self.get_size();
+
+        if point.x < size.w - DEFAULT_SPINNER_ARROW_WIDTH
+            || point.x >= size.w
+            || point.y < 0
+            || point.y >= size.h
+        {
+            None
+        } else if point.y < size.h / 2 {
+            Some(SpinnerArrow::Up)
+        } else {
+            Some(SpinnerArrow::Down)
+        }
+    }
+
+    /// Stops repeating the held arrow button.
+    fn release_arrow(&mut self) {
+        if self.pressed_arrow.take().is_some() {
+            self.repeat_timer.set_enabled(false);
+            self.invalidate();
+        }
+    }
+
+    /// Passes `event` on to the text input, invalidating this widget if it needs to be redrawn.
+    fn forward_event(&mut self, event: CallbackEvent) {
+        self.text_input.handle_event(event);
+
+        if self.text_input.is_invalidated() {
+            self.invalidate();
+        }
+    }
+
+    /// Draws the arrow button `arrow` in the rectangle `bounds`.
+    fn draw_arrow(
+        &mut self,
+        arrow: SpinnerArrow,
+        bounds: [f64; 4],
+        c: Context,
+        g: &mut DrawTarget,
+        clip: &DrawState,
+    ) {
+        let theme = get_current_theme();
+        let [x, y, w, h] = bounds;
+        let at_limit = match arrow {
+            SpinnerArrow::Up => self.value >= self.get_max(),
+            SpinnerArrow::Down => self.value <= self.get_min(),
+        };
+        let disabled = self.is_disabled();
+        let hover_amount = if disabled {
+            0.0
+        } else if self.pressed_arrow == Some(arrow) && self.hovered_arrow == Some(arrow) {
+            SPINNER_HOVER_AMOUNT * 2.0
+        } else if self.hovered_arrow == Some(arrow) {
+            SPINNER_HOVER_AMOUNT
+        } else {
+            0.0
+        };
+        let mut background = self.get_color();
+
+        for (channel, accent) in background.iter_mut().zip(theme.accent_color.iter()).take(3) {
+            *channel += (accent - *channel) * hover_amount;
+        }
+
+        let arrow_color = if disabled || at_limit {
+            theme.disabled_color
+        } else {
+            self.get_text_color()
+        };
+        let (center_x, center_y) = (x + w / 2.0, y + h / 2.0);
+        let points = match arrow {
+            SpinnerArrow::Up => [
+                [center_x - 4.0, center_y + 2.0],
+                [center_x, center_y - 2.0],
+                [center_x + 4.0, center_y + 2.0],
+            ],
+            SpinnerArrow::Down => [
+                [center_x - 4.0, center_y - 2.0],
+                [center_x, center_y + 2.0],
+                [center_x + 4.0, center_y - 2.0],
+            ],
+        };
+
+        Rectangle::new(apply_opacity(background)).draw(bounds, clip, c.transform, g);
+        Polygon::new(apply_opacity(arrow_color)).draw(&points, clip, c.transform, g);
+        Rectangle::new_border(apply_opacity(theme.border_color), 0.5).draw(
+            [x + 0.5, y + 0.5, w - 1.0, h - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+    }
+}
+
+/// Implementation of the `SpinnerWidget` object with the `Widget` traits implemented.  The text
+/// is edited and drawn by a `TextInputWidget`, next to the column of arrow buttons, which this
+/// `Widget` handles internally.
+impl Widget for SpinnerWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::SpinButton
+    }
+
+    /// The value is formatted with the number of decimals of the spinner.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        Some(format_spinner_value(self.value, self.decimals))
+    }
+
+    /// Sets the `Size` for this widget, given width and height, leaving room for the arrow
+    /// buttons next to the text.  Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.text_input
+            .set_size((w - DEFAULT_SPINNER_ARROW_WIDTH).max(0), h);
+        self.invalidate();
+    }
+
+    /// Sets the padding between the border of the text and the text.  Invalidates the widget
+    /// afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.text_input.set_padding(padding);
+        self.invalidate();
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the height
+    /// of a line of text, plus the padding, and the width of ten average characters and the
+    /// arrow buttons, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => {
+                let size = self.text_input.get_preferred_size();
+
+                crate::core::point::Size {
+                    w: size.w / 2 + DEFAULT_SPINNER_ARROW_WIDTH,
+                    h: size.h,
+                }
+            }
+        }
+    }
+
+    /// Sets the background color of the text and the arrow buttons.  Invalidates the widget
+    /// afterward.
+    fn set_color(&mut self, color: types::Color) {
+        self.config().set(MainColor(color));
+        self.text_input.set_color(color);
+        self.invalidate();
+    }
+
+    /// Enables or disables this widget.  A disabled spinner is drawn in the disabled color of
+    /// the current `Theme`, and stops repeating a held arrow button.  Invalidates the widget
+    /// afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+            self.release_arrow();
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_input.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Shows the I-beam cursor over the text, and the default cursor over the arrow buttons.
+    fn get_cursor(&mut self) -> CursorStyle {
+        if self.hovered_arrow.is_none() {
+            CursorStyle::Text
+        } else {
+            self.config()
+                .get::<Cursor>()
+                .map(|cursor| cursor.0)
+                .unwrap_or(CursorStyle::Default)
+        }
+    }
+
+    /// Blinks the caret of the text, and repeats the held arrow button while the mouse is over
+    /// it, on every update of the run loop.
+    fn update(&mut self, delta_ms: u64) {
+        self.text_input.update(delta_ms);
+
+        if self.text_input.is_invalidated() {
+            self.invalidate();
+        }
+
+        if let Some(arrow) = self.pressed_arrow {
+            self.repeat_timer.tick();
+
+            let fire_count = self.repeat_timer.get_fire_count();
+
+            if fire_count != self.repeat_count {
+                self.repeat_count = fire_count;
+                self.repeat_timer
+                    .set_timeout(get_repeat_interval(fire_count));
+
+                if self.hovered_arrow == Some(arrow) {
+                    self.step_by(arrow.get_steps());
+                }
+            }
+        }
+    }
+
+    /// Steps the value once per notch of the mouse wheel, passing the scroll on to the widget
+    /// underneath if the value cannot be stepped any further in that direction.  All other
+    /// events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let (_, lines) = delta.to_lines();
+
+                if self.is_disabled() || lines == 0.0 {
+                    return (EventResult::NotHandled, None);
+                }
+
+                let previous_value = self.value;
+
+                self.commit();
+                self.step_by(lines);
+
+                if (self.value - previous_value).abs() < f64::EPSILON {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::LostFocus { .. } => {
+                self.release_arrow();
+                self.forward_event(event);
+                self.commit();
+            }
+
+            CallbackEvent::MouseMoved { ref point, .. } => {
+                let hovered_arrow = self.get_arrow_at(point);
+
+                if hovered_arrow != self.hovered_arrow {
+                    self.hovered_arrow = hovered_arrow;
+                    self.invalidate();
+                }
+
+                self.forward_event(event);
+            }
+
+            CallbackEvent::MouseExited { .. } => {
+                self.hovered_arrow = None;
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonDown {
+                button, ref point, ..
+            } if button == left_button => match self.get_arrow_at(point) {
+                Some(arrow) => {
+                    self.commit();
+                    self.pressed_arrow = Some(arrow);
+                    self.repeat_count = 0;
+                    self.repeat_timer.set_timeout(SPINNER_REPEAT_DELAY_MS);
+                    self.repeat_timer.reset();
+                    self.step_by(arrow.get_steps());
+                }
+                None => self.forward_event(event),
+            },
+
+            CallbackEvent::MouseButtonUpInside { button, .. }
+            | CallbackEvent::MouseButtonUpOutside { button, .. }
+                if button == left_button =>
+            {
+                self.release_arrow();
+                self.forward_event(event);
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => match key {
+                Key::Up => {
+                    self.commit();
+                    self.step_by(1.0);
+                }
+                Key::Down => {
+                    self.commit();
+                    self.step_by(-1.0);
+                }
+                Key::Return => self.commit(),
+                Key::Escape => self.sync_text(),
+                _ => self.forward_event(event),
+            },
+
+            _ => self.forward_event(event),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_input.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the characters of typed text that can be part of a number on to the text.
+    fn text_input(&mut self, widget_id: WidgetId, text: &str) {
+        let text: String = text.chars().filter(|c| self.accepts_char(*c)).collect();
+
+        if !self.is_disabled() && !text.is_empty() {
+            self.text_input.text_input(widget_id, &text);
+
+            if self.text_input.is_invalidated() {
+                self.invalidate();
+            }
+        }
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_input.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the text, and then the arrow buttons to its right.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let size = self.get_size();
+        let arrow_w = f64::from(DEFAULT_SPINNER_ARROW_WIDTH.min(size.w));
+        let arrow_x = f64::from(size.w) - arrow_w;
+        let up_h = f64::from(size.h / 2);
+
+        self.text_input.draw(c, g, clip);
+        self.draw_arrow(SpinnerArrow::Up, [arrow_x, 0.0, arrow_w, up_h], c, g, clip);
+        self.draw_arrow(
+            SpinnerArrow::Down,
+            [arrow_x, up_h, arrow_w, f64::from(size.h) - up_h],
+            c,
+            g,
+            clip,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,450 @@
+// Split Pane Widget
+// Extensible widget for the widget library - handles two panes with a draggable divider.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::layout_widget::LayoutChild;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The default thickness of the divider of a `SplitPaneWidget`, in pixels.
+pub const DEFAULT_DIVIDER_THICKNESS: i32 = 6;
+
+/// The default smallest width or height of each pane of a `SplitPaneWidget`, in pixels.
+pub const DEFAULT_MIN_PANE_SIZE: i32 = 20;
+
+/// How far the divider is blended toward the accent color of the current `Theme` while the
+/// mouse is over it.
+const DIVIDER_HOVER_AMOUNT: f32 = 0.4;
+
+/// Returns the width or height to give the first pane of a `SplitPaneWidget` that would like
+/// it to be `position`, when `available` pixels are shared by both panes, and they must be at
+/// least `min_first` and `min_second` pixels.  When both minimums cannot be met, the first pane
+/// keeps its minimum.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::split_pane_widget::*;
+/// # fn main() {
+///    assert_eq!(clamp_split_position(150, 400, 20, 20), 150);
+///    assert_eq!(clamp_split_position(5, 400, 20, 20), 20);
+///    assert_eq!(clamp_split_position(395, 400, 20, 20), 380);
+///    assert_eq!(clamp_split_position(395, 400, 0, 0), 395);
+///    assert_eq!(clamp_split_position(50, 30, 20, 20), 20);
+/// # }
+/// ```
+pub fn clamp_split_position(position: i32, available: i32, min_first: i32, min_second: i32) -> i32 {
+    position
+        .min(available - min_second)
+        .max(min_first)
+        .min(available)
+        .max(0)
+}
+
+/// This is the `SplitPaneWidget`, which divides itself into two panes with a divider between
+/// them, which can be dragged to share the space between the panes differently.  The panes are
+/// the first two children of the widget, in the order in which they were added, and are resized
+/// and moved as the divider is dragged.  If only one of them is visible, it fills the widget.
+/// The panes can be containers, including other split panes.
+///
+/// A `Horizontal` split pane places its panes side by side, and a `Vertical` one places the
+/// first pane above the second.  The mouse cursor changes to a resize cursor over the divider.
+/// The divider cannot be dragged closer to either edge than the smallest size of the pane
+/// there, which can be set to 0 to allow a pane to be collapsed.
+///
+/// The position of the divider can be set in pixels, or as a percentage of the space for both
+/// panes.  Either way, once the widget has a size, it is kept as a percentage, so both panes
+/// grow and shrink in proportion when the widget is resized, such as along with the window.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::config::*;
+/// # use pushrod::widget::split_pane_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Split", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut split_pane = SplitPaneWidget::new(Orientation::Horizontal);
+///
+///    split_pane.set_size(400, 300);
+///    split_pane.set_split_position(Dimension::Pixels(120));
+///
+///    let split_id = pushrod.add_widget("Split", Box::new(split_pane));
+///    let widget_store = &mut pushrod.widget_store.borrow_mut();
+///
+///    widget_store.add_widget_to_parent("Sidebar", Box::new(CanvasWidget::new()), split_id);
+///    widget_store.add_widget_to_parent("Editor", Box::new(CanvasWidget::new()), split_id);
+/// # }
+/// ```
+pub struct SplitPaneWidget {
+    config: Configurable,
+    orientation: Orientation,
+    position: Dimension,
+    divider_thickness: i32,
+    min_sizes: (i32, i32),
+    hovered: bool,
+    drag_offset: Option<i32>,
+}
+
+/// Implementation of the constructor for the `SplitPaneWidget`.
+impl SplitPaneWidget {
+    /// Creates a new split pane with the divider in the middle.
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            config: Configurable::new(),
+            orientation,
+            position: Dimension::Percent(50.0),
+            divider_thickness: DEFAULT_DIVIDER_THICKNESS,
+            min_sizes: (DEFAULT_MIN_PANE_SIZE, DEFAULT_MIN_PANE_SIZE),
+            hovered: false,
+            drag_offset: None,
+        }
+    }
+
+    /// Sets the direction in which the panes are placed.  Invalidates the widget afterward.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.invalidate();
+    }
+
+    /// Retrieves the direction in which the panes are placed.
+    pub fn get_orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Sets the position of the divider: the width or the height of the first pane, in pixels,
+    /// or as a percentage of the space for both panes.  Invalidates the widget afterward.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::config::*;
+    /// # use pushrod::widget::split_pane_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut split_pane = SplitPaneWidget::new(Orientation::Horizontal);
+    ///
+    ///    split_pane.set_size(206, 100);
+    ///    split_pane.set_split_position(Dimension::Pixels(50));
+    ///
+    ///    let split_id = widget_store.add_widget("Split", Box::new(split_pane));
+    ///    let left_id =
+    ///        widget_store.add_widget_to_parent("Left", Box::new(CanvasWidget::new()), split_id);
+    ///    let right_id =
+    ///        widget_store.add_widget_to_parent("Right", Box::new(CanvasWidget::new()), split_id);
+    ///
+    ///    widget_store.apply_layouts();
+    ///    assert_eq!(widget_store.get_widget_for_id(left_id).borrow_mut().get_size().w, 50);
+    ///    assert_eq!(widget_store.get_widget_for_id(right_id).borrow_mut().get_origin().x, 56);
+    ///    assert_eq!(widget_store.get_widget_for_id(right_id).borrow_mut().get_size().w, 150);
+    ///
+    ///    // The panes keep their proportions when the split pane is resized.
+    ///    widget_store.get_widget_for_id(split_id).borrow_mut().set_size(406, 100);
+    ///    widget_store.apply_layouts();
+    ///    assert_eq!(widget_store.get_widget_for_id(left_id).borrow_mut().get_size().w, 100);
+    ///    assert_eq!(widget_store.get_widget_for_id(right_id).borrow_mut().get_size().w, 300);
+    /// # }
+    /// ```
+    pub fn set_split_position(&mut self, position: Dimension) {
+        self.position = position;
+        self.invalidate();
+    }
+
+    /// Retrieves the width or the height of the first pane, in pixels, at the current size of
+    /// the widget.
+    pub fn get_split_position(&mut self) -> i32 {
+        let available = self.get_available();
+        let position = match self.position {
+            Dimension::Pixels(pixels) => pixels as i32,
+            Dimension::Percent(percent) => {
+                (f64::from(percent) * f64::from(available) / 100.0).round() as i32
+            }
+        };
+
+        clamp_split_position(position, available, self.min_sizes.0, self.min_sizes.1)
+    }
+
+    /// Retrieves the position of the divider as a percentage of the space for both panes.
+    pub fn get_split_percent(&mut self) -> f32 {
+        let available = self.get_available();
+
+        if available == 0 {
+            return match self.position {
+                Dimension::Percent(percent) => percent,
+                Dimension::Pixels(_) => 0.0,
+            };
+        }
+
+        self.get_split_position() as f32 * 100.0 / available as f32
+    }
+
+    /// Sets the thickness of the divider.  Invalidates the widget afterward.
+    pub fn set_divider_thickness(&mut self, thickness: i32) {
+        self.divider_thickness = thickness.max(0);
+        self.invalidate();
+    }
+
+    /// Retrieves the thickness of the divider.  Defaults to `DEFAULT_DIVIDER_THICKNESS`.
+    pub fn get_divider_thickness(&self) -> i32 {
+        self.divider_thickness
+    }
+
+    /// Sets the smallest width or height of the first and the second pane.  A pane whose
+    /// smallest size is 0 can be collapsed by dragging the divider to its edge.  Invalidates the
+    /// widget afterward.
+    pub fn set_min_pane_sizes(&mut self, first: i32, second: i32) {
+        self.min_sizes = (first.max(0), second.max(0));
+        self.invalidate();
+    }
+
+    /// Retrieves the smallest widths or heights of the first and the second pane.  Both default
+    /// to `DEFAULT_MIN_PANE_SIZE`.
+    pub fn get_min_pane_sizes(&self) -> (i32, i32) {
+        self.min_sizes
+    }
+
+    /// Returns the length of the widget along its orientation.
+    fn get_length(&mut self) -> i32 {
+        let size = self.get_size();
+
+        match self.orientation {
+            Orientation::Horizontal => size.w,
+            Orientation::Vertical => size.h,
+        }
+    }
+
+    /// Returns the space for both panes, which is the length of the widget less the divider.
+    /// Once there is space, a position in pixels is turned into a percentage, so that it is
+    /// kept in proportion from then on.
+    fn get_available(&mut self) -> i32 {
+        let available = (self.get_length() - self.divider_thickness).max(0);
+
+        if let Dimension::Pixels(pixels) = self.position {
+            if available > 0 {
+                self.position = Dimension::Percent(pixels as f32 * 100.0 / available as f32);
+            }
+        }
+
+        available
+    }
+
+    /// Returns the distance of `point` along the orientation of the widget.
+    fn get_along(&self, point: &Point) -> i32 {
+        match self.orientation {
+            Orientation::Horizontal => point.x,
+            Orientation::Vertical => point.y,
+        }
+    }
+
+    /// Returns the bounds `[x, y, w, h]` of a span of the widget along its orientation.
+    fn get_span_bounds(&mut self, start: i32, length: i32) -> [i32; 4] {
+        let size = self.get_size();
+
+        match self.orientation {
+            Orientation::Horizontal => [start, 0, length, size.h],
+            Orientation::Vertical => [0, start, size.w, length],
+        }
+    }
+
+    fn is_over_divider(&mut self, point: &Point) -> bool {
+        let divider = self.get_split_position();
+        let along = self.get_along(point);
+        let size = self.get_size();
+
+        point_in_rect(point, &make_origin_point(), &size)
+            && along >= divider
+            && along < divider + self.divider_thickness
+    }
+}
+
+/// Implementation of the `SplitPaneWidget` object with the `Widget` traits implemented.
+impl Widget for SplitPaneWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::SplitPane
+    }
+
+    /// Retrieves the resize cursor while the mouse is over the divider, or while it is being
+    /// dragged, and the cursor of the widget otherwise.
+    fn get_cursor(&mut self) -> CursorStyle {
+        if self.hovered || self.drag_offset.is_some() {
+            return match self.orientation {
+                Orientation::Horizontal => CursorStyle::ResizeHorizontal,
+                Orientation::Vertical => CursorStyle::ResizeVertical,
+            };
+        }
+
+        self.config()
+            .get::<Cursor>()
+            .map(|cursor| cursor.0)
+            .unwrap_or(CursorStyle::Default)
+    }
+
+    /// Places the first two visible children on either side of the divider, or the only one
+    /// over the whole widget.  Any other children keep their bounds.
+    fn layout_children(&mut self, children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        if children.len() == 1 {
+            let length = self.get_length();
+
+            return vec![self.get_span_bounds(0, length)];
+        }
+
+        let available = self.get_available();
+        let divider = self.get_split_position();
+        let second_start = divider + self.divider_thickness;
+
+        vec![
+            self.get_span_bounds(0, divider),
+            self.get_span_bounds(second_start, available - divider),
+        ]
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                if let Some(offset) = self.drag_offset {
+                    let available = self.get_available();
+                    let position = clamp_split_position(
+                        self.get_along(&point) - offset,
+                        available,
+                        self.min_sizes.0,
+                        self.min_sizes.1,
+                    );
+
+                    if position != self.get_split_position() && available > 0 {
+                        self.position =
+                            Dimension::Percent(position as f32 * 100.0 / available as f32);
+                        self.invalidate();
+                    }
+                } else {
+                    let hovered = self.is_over_divider(&point);
+
+                    if hovered != self.hovered {
+                        self.hovered = hovered;
+                        self.invalidate();
+                    }
+                }
+            }
+
+            CallbackEvent::MouseExited { .. } if self.hovered => {
+                self.hovered = false;
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonDown {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } if self.is_over_divider(&point) => {
+                self.drag_offset = Some(self.get_along(&point) - self.get_split_position());
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonUpInside {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } if self.drag_offset.is_some() => {
+                self.drag_offset = None;
+                self.hovered = self.is_over_divider(&point);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonUpOutside {
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } if self.drag_offset.is_some() => {
+                self.drag_offset = None;
+                self.hovered = false;
+                self.invalidate();
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Draws the background, and the divider, in the border color of the current `Theme`, or
+    /// blended toward its accent color while the mouse is over it, with a grip in its middle.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let divider = self.get_split_position();
+        let mut divider_color = theme.border_color;
+
+        if self.hovered || self.drag_offset.is_some() {
+            for (channel, accent) in divider_color
+                .iter_mut()
+                .zip(theme.accent_color.iter())
+                .take(3)
+            {
+                *channel += (accent - *channel) * DIVIDER_HOVER_AMOUNT;
+            }
+        }
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let bounds = self.get_span_bounds(divider, self.divider_thickness);
+        let [x, y, w, h] = [
+            f64::from(bounds[0]),
+            f64::from(bounds[1]),
+            f64::from(bounds[2]),
+            f64::from(bounds[3]),
+        ];
+
+        Rectangle::new(apply_opacity(divider_color)).draw([x, y, w, h], clip, c.transform, g);
+
+        let grip_color = apply_opacity(theme.background_color);
+        let (middle_x, middle_y) = (x + w / 2.0, y + h / 2.0);
+
+        for offset in [-4.0, 0.0, 4.0].iter() {
+            let (dot_x, dot_y) = match self.orientation {
+                Orientation::Horizontal => (middle_x, middle_y + offset),
+                Orientation::Vertical => (middle_x + offset, middle_y),
+            };
+
+            Rectangle::new(grip_color).draw(
+                [dot_x - 1.0, dot_y - 1.0, 2.0, 2.0],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
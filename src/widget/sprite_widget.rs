@@ -0,0 +1,547 @@
+// Sprite Widget
+// Extensible widget for the widget library - handles frame-based animations from a spritesheet.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ::image::RgbaImage;
+use piston_window::*;
+use std::time::Instant;
+
+use crate::core::accessibility::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::widget::config::*;
+use crate::widget::image_widget::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called when a `SpriteWidget` that plays once reaches its last frame.
+pub type SpriteFinishedCallback = Box<dyn FnMut()>;
+
+/// Default number of milliseconds that each frame of a `SpriteWidget` is shown for.
+pub const DEFAULT_FRAME_DURATION: u64 = 100;
+
+/// This `enum` specifies where the frames of a `SpriteWidget` are in its spritesheet.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpriteFrames {
+    /// The spritesheet is divided into a grid of equally sized frames, with the specified
+    /// number of columns and rows.  The frames are numbered from left to right, and then from
+    /// top to bottom.
+    Grid { columns: u32, rows: u32 },
+
+    /// Each frame is the rectangle `[x, y, w, h]` of the spritesheet, in pixels.
+    Rects(Vec<[i32; 4]>),
+}
+
+/// Implementation of the `SpriteFrames`.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::Size;
+/// # use pushrod::widget::sprite_widget::*;
+/// # fn main() {
+///    let frames = SpriteFrames::Grid { columns: 4, rows: 2 };
+///    let image_size = Size { w: 256, h: 128 };
+///
+///    assert_eq!(frames.get_frame_count(), 8);
+///    assert_eq!(frames.get_frame_rect(&image_size, 0), Some([0, 0, 64, 64]));
+///    assert_eq!(frames.get_frame_rect(&image_size, 5), Some([64, 64, 64, 64]));
+///    assert_eq!(frames.get_frame_rect(&image_size, 8), None);
+///
+///    let frames = SpriteFrames::Rects(vec![[0, 0, 10, 20], [10, 0, 12, 20]]);
+///
+///    assert_eq!(frames.get_frame_count(), 2);
+///    assert_eq!(frames.get_frame_rect(&image_size, 1), Some([10, 0, 12, 20]));
+/// # }
+/// ```
+impl SpriteFrames {
+    /// Returns the number of frames.
+    pub fn get_frame_count(&self) -> usize {
+        match self {
+            SpriteFrames::Grid { columns, rows } => (*columns as usize) * (*rows as usize),
+            SpriteFrames::Rects(rects) => rects.len(),
+        }
+    }
+
+    /// Returns the rectangle `[x, y, w, h]` of frame `index` in a spritesheet of `image_size`
+    /// pixels, or `None` if there is no such frame.
+    pub fn get_frame_rect(
+        &self,
+        image_size: &crate::core::point::Size,
+        index: usize,
+    ) -> Option<[i32; 4]> {
+        if index >= self.get_frame_count() {
+            return None;
+        }
+
+        match self {
+            SpriteFrames::Grid { columns, rows } => {
+                let (columns, rows) = (*columns as i32, *rows as i32);
+                let (w, h) = (image_size.w / columns, image_size.h / rows);
+                let index = index as i32;
+
+                Some([(index % columns) * w, (index / columns) * h, w, h])
+            }
+            SpriteFrames::Rects(rects) => Some(rects[index]),
+        }
+    }
+}
+
+/// This `enum` specifies the order in which a `SpriteWidget` plays its frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpritePlayMode {
+    /// Plays the frames from first to last, and then starts over from the first.  This is the
+    /// default.
+    Loop,
+
+    /// Plays the frames from first to last, and then stops on the last frame.
+    Once,
+
+    /// Plays the frames from first to last, then back from last to first, and so on.
+    PingPong,
+}
+
+/// Implementation of the `SpritePlayMode`.
+impl SpritePlayMode {
+    /// Moves `steps` frames on from `frame` in an animation of `frame_count` frames, in the
+    /// direction given by `forward`, which is only used for `SpritePlayMode::PingPong`.  Returns
+    /// the new frame, the new direction, and whether or not an animation that plays once has
+    /// reached its last frame.  Any number of frames can be skipped at once.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::sprite_widget::*;
+    /// # fn main() {
+    ///    assert_eq!(SpritePlayMode::Loop.advance(4, 3, true, 1), (0, true, false));
+    ///    assert_eq!(SpritePlayMode::Loop.advance(4, 0, true, 10), (2, true, false));
+    ///
+    ///    assert_eq!(SpritePlayMode::Once.advance(4, 1, true, 1), (2, true, false));
+    ///    assert_eq!(SpritePlayMode::Once.advance(4, 1, true, 10), (3, true, true));
+    ///
+    ///    // Frames 0, 1, 2, 3, 2, 1, 0, 1, ...
+    ///    assert_eq!(SpritePlayMode::PingPong.advance(4, 2, true, 1), (3, true, false));
+    ///    assert_eq!(SpritePlayMode::PingPong.advance(4, 2, true, 3), (1, false, false));
+    ///    assert_eq!(SpritePlayMode::PingPong.advance(4, 1, false, 2), (1, true, false));
+    /// # }
+    /// ```
+    pub fn advance(
+        self,
+        frame_count: usize,
+        frame: usize,
+        forward: bool,
+        steps: u64,
+    ) -> (usize, bool, bool) {
+        if frame_count < 2 {
+            return (0, forward, self == SpritePlayMode::Once);
+        }
+
+        let count = frame_count as u64;
+        let frame = (frame as u64).min(count - 1);
+
+        match self {
+            SpritePlayMode::Loop => (((frame + steps) % count) as usize, forward, false),
+
+            SpritePlayMode::Once => {
+                let frame = (frame + steps).min(count - 1);
+
+                (frame as usize, forward, frame == count - 1)
+            }
+
+            SpritePlayMode::PingPong => {
+                let cycle = count * 2 - 2;
+                let position = if forward { frame } else { cycle - frame };
+                let position = (position % cycle + steps % cycle) % cycle;
+
+                if position < count {
+                    (position as usize, true, false)
+                } else {
+                    ((cycle - position) as usize, false, false)
+                }
+            }
+        }
+    }
+}
+
+/// This is the `SpriteWidget`, which plays a frame-based animation from a spritesheet: a single
+/// image that contains all of the frames of the animation, laid out in a grid, or at the
+/// rectangles given in `SpriteFrames::Rects`.  The current frame is drawn stretched to the
+/// bounds of the widget, unless another `ImageScaling` is set with `set_scaling`.  The
+/// spritesheet is loaded in the same way as by the `ImageWidget`, and a spritesheet that cannot
+/// be loaded is drawn as a crossed out box.
+///
+/// Each frame is shown for the frame duration, which is measured in the same way as the timeout
+/// of a `TimerWidget`: the time that has elapsed is accumulated on every update of the run loop,
+/// and the animation moves on by as many frames as have passed.  A stall of the application
+/// therefore skips frames, rather than slowing the animation down.  The animation starts
+/// playing as soon as the widget is created, and can be controlled with `play`, `pause`,
+/// `stop` and `set_frame`.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::sprite_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Sprite", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let frames = SpriteFrames::Grid { columns: 8, rows: 1 };
+///    let image_name = "explosion.png".to_string();
+///    let mut sprite = SpriteWidget::new(pushrod.get_factory(), image_name, frames);
+///
+///    sprite.set_origin(168, 118);
+///    sprite.set_size(64, 64);
+///    sprite.set_frame_duration(50);
+///    sprite.set_play_mode(SpritePlayMode::Once);
+///    sprite.on_animation_finished(Box::new(|| eprintln!("Boom.")));
+///    pushrod.add_widget("Explosion", Box::new(sprite));
+///    pushrod.run_without_handler();
+/// # }
+/// ```
+pub struct SpriteWidget {
+    config: Configurable,
+    factory: GfxFactory,
+    image_name: Option<String>,
+    pixels: Option<RgbaImage>,
+    texture: Option<G2dTexture>,
+    image_size: crate::core::point::Size,
+    error: Option<PushrodError>,
+    frames: SpriteFrames,
+    frame_duration: u64,
+    play_mode: SpritePlayMode,
+    scaling: ImageScaling,
+    clock: Box<dyn Clock>,
+    playing: bool,
+    initiated: Instant,
+    accumulated_ms: u64,
+    frame: usize,
+    forward: bool,
+    on_animation_finished: Option<SpriteFinishedCallback>,
+}
+
+/// Implementation of the constructors for the `SpriteWidget`.
+impl SpriteWidget {
+    /// Creates a new `SpriteWidget` object, requiring the current `PistonWindow`'s factory
+    /// object, the name of the spritesheet to load, as with `ImageWidget::new`, and where its
+    /// frames are.  The spritesheet is loaded when the widget is first drawn.
+    pub fn new(factory: &mut GfxFactory, image_name: String, frames: SpriteFrames) -> Self {
+        let mut widget = Self::empty(factory, frames);
+
+        widget.image_name = Some(image_name);
+        widget
+    }
+
+    /// Creates a new `SpriteWidget` object that plays the frames of a spritesheet decoded from
+    /// the contents of an image file.  Returns a `PushrodError::ResourceLoading` if the image
+    /// cannot be decoded.
+    pub fn from_bytes(
+        factory: &mut GfxFactory,
+        bytes: &[u8],
+        frames: SpriteFrames,
+    ) -> Result<Self, PushrodError> {
+        let pixels = ::image::load_from_memory(bytes)
+            .map_err(decode_error)?
+            .to_rgba();
+        let mut widget = Self::empty(factory, frames);
+
+        widget.image_size = crate::core::point::Size {
+            w: pixels.width() as i32,
+            h: pixels.height() as i32,
+        };
+        widget.pixels = Some(pixels);
+        Ok(widget)
+    }
+
+    fn empty(factory: &mut GfxFactory, frames: SpriteFrames) -> Self {
+        let clock: Box<dyn Clock> = Box::new(SystemClock);
+        let initiated = clock.now();
+
+        Self {
+            config: Configurable::new(),
+            factory: factory.clone(),
+            image_name: None,
+            pixels: None,
+            texture: None,
+            image_size: crate::core::point::Size { w: 0, h: 0 },
+            error: None,
+            frames,
+            frame_duration: DEFAULT_FRAME_DURATION,
+            play_mode: SpritePlayMode::Loop,
+            scaling: ImageScaling::Stretch,
+            clock,
+            playing: true,
+            initiated,
+            accumulated_ms: 0,
+            frame: 0,
+            forward: true,
+            on_animation_finished: None,
+        }
+    }
+
+    /// Sets the `Clock` that the frame duration is measured with.  Defaults to the monotonic
+    /// `SystemClock`.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.initiated = clock.now();
+        self.clock = clock;
+    }
+
+    /// Sets where the frames are in the spritesheet, and goes back to the first frame.
+    pub fn set_frames(&mut self, frames: SpriteFrames) {
+        self.frames = frames;
+        self.set_frame(0);
+    }
+
+    /// Retrieves where the frames are in the spritesheet.
+    pub fn get_frames(&self) -> &SpriteFrames {
+        &self.frames
+    }
+
+    /// Returns the number of frames in the animation.
+    pub fn get_frame_count(&self) -> usize {
+        self.frames.get_frame_count()
+    }
+
+    /// Sets the number of milliseconds that each frame is shown for.  Values lower than 1 are
+    /// treated as 1.
+    pub fn set_frame_duration(&mut self, frame_duration: u64) {
+        self.frame_duration = frame_duration.max(1);
+    }
+
+    /// Retrieves the number of milliseconds that each frame is shown for.
+    pub fn get_frame_duration(&self) -> u64 {
+        self.frame_duration
+    }
+
+    /// Sets the order in which the frames are played.
+    pub fn set_play_mode(&mut self, play_mode: SpritePlayMode) {
+        self.play_mode = play_mode;
+    }
+
+    /// Retrieves the order in which the frames are played.  Defaults to `SpritePlayMode::Loop`.
+    pub fn get_play_mode(&self) -> SpritePlayMode {
+        self.play_mode
+    }
+
+    /// Sets how the current frame is fit into the bounds of the widget.
+    pub fn set_scaling(&mut self, scaling: ImageScaling) {
+        self.scaling = scaling;
+        self.invalidate();
+    }
+
+    /// Retrieves how the current frame is fit into the bounds of the widget.  Defaults to
+    /// `ImageScaling::Stretch`.
+    pub fn get_scaling(&self) -> ImageScaling {
+        self.scaling
+    }
+
+    /// Sets the closure function that is called when an animation that plays once with
+    /// `SpritePlayMode::Once` reaches its last frame.
+    pub fn on_animation_finished(&mut self, callback: SpriteFinishedCallback) {
+        self.on_animation_finished = Some(callback);
+    }
+
+    /// Starts playing the animation, or continues it after `pause`, keeping the time that had
+    /// already elapsed on the current frame.  An animation that has played once is played again
+    /// from its first frame.
+    pub fn play(&mut self) {
+        if self.playing {
+            return;
+        }
+
+        if self.play_mode == SpritePlayMode::Once && self.frame + 1 >= self.get_frame_count() {
+            self.set_frame(0);
+        }
+
+        self.initiated = self.clock.now();
+        self.playing = true;
+    }
+
+    /// Pauses the animation on the current frame, remembering how long the frame has been shown.
+    pub fn pause(&mut self) {
+        if !self.playing {
+            return;
+        }
+
+        self.accumulated_ms += self.clock.elapsed_ms(self.initiated);
+        self.playing = false;
+    }
+
+    /// Stops the animation, and goes back to the first frame.
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.forward = true;
+        self.set_frame(0);
+    }
+
+    /// Indicates whether or not the animation is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Shows frame `frame`, restarting its frame duration.  Frames past the last frame show the
+    /// last frame.
+    pub fn set_frame(&mut self, frame: usize) {
+        self.frame = frame.min(self.get_frame_count().saturating_sub(1));
+        self.initiated = self.clock.now();
+        self.accumulated_ms = 0;
+        self.invalidate();
+    }
+
+    /// Retrieves the number of the frame that is shown.
+    pub fn get_frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Retrieves the reason that the spritesheet could not be loaded, if it could not.  The error
+    /// is also reported to `Pushrod::on_error` when the spritesheet is first drawn.
+    pub fn get_error(&self) -> Option<&PushrodError> {
+        self.error.as_ref()
+    }
+
+    /// Called to check the time that the current frame has been shown for, and to move on by as
+    /// many frames as their duration has passed.  An animation that plays once stops, and calls
+    /// the function set in `on_animation_finished`, when it reaches its last frame.
+    pub fn tick(&mut self) {
+        if !self.playing {
+            return;
+        }
+
+        let elapsed = self.accumulated_ms + self.clock.elapsed_ms(self.initiated);
+        let steps = elapsed / self.frame_duration;
+
+        if steps == 0 {
+            return;
+        }
+
+        self.initiated = self.clock.now();
+        self.accumulated_ms = elapsed % self.frame_duration;
+
+        let (frame, forward, finished) =
+            self.play_mode
+                .advance(self.get_frame_count(), self.frame, self.forward, steps);
+
+        if frame != self.frame {
+            self.frame = frame;
+            self.invalidate();
+        }
+
+        self.forward = forward;
+
+        if finished {
+            self.playing = false;
+            self.accumulated_ms = 0;
+
+            if let Some(callback) = &mut self.on_animation_finished {
+                callback();
+            }
+        }
+    }
+
+    /// Creates the texture for the spritesheet the first time it is needed, remembering the
+    /// error if it cannot be loaded, so that it is only tried once.
+    fn load_texture(&mut self) {
+        if self.texture.is_some() || self.error.is_some() {
+            return;
+        }
+
+        let result = self.decode_image().and_then(|_| match self.pixels.take() {
+            Some(pixels) => {
+                Texture::from_image(&mut self.factory, &pixels, &TextureSettings::new()).map_err(
+                    |error| {
+                        PushrodError::Rendering(format!(
+                            "cannot create a texture for the image: {:?}",
+                            error
+                        ))
+                    },
+                )
+            }
+            None => Err(no_image_error()),
+        });
+
+        match result {
+            Ok(texture) => self.texture = Some(texture),
+            Err(error) => {
+                report_error(error.clone());
+                self.error = Some(error);
+            }
+        }
+    }
+
+    /// Decodes the spritesheet, if it has not been decoded already.
+    fn decode_image(&mut self) -> Result<(), PushrodError> {
+        if self.pixels.is_some() {
+            return Ok(());
+        }
+
+        let image_name = match &self.image_name {
+            Some(image_name) => image_name.clone(),
+            None => return Err(no_image_error()),
+        };
+        let path = find_image(&image_name)?;
+        let pixels = ::image::open(&path)
+            .map_err(|error| PushrodError::ResourceLoading {
+                name: path.display().to_string(),
+                reason: error.to_string(),
+            })?
+            .to_rgba();
+
+        self.image_size = crate::core::point::Size {
+            w: pixels.width() as i32,
+            h: pixels.height() as i32,
+        };
+        self.pixels = Some(pixels);
+        Ok(())
+    }
+}
+
+/// Implementation of the `SpriteWidget` object with the `Widget` traits implemented.
+impl Widget for SpriteWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Image
+    }
+
+    /// Moves the animation on by every frame whose duration has passed since the last update.
+    fn update(&mut self, _delta_ms: u64) {
+        self.tick();
+    }
+
+    /// Draws the current frame of the animation, creating the texture for the spritesheet the
+    /// first time.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.load_texture();
+
+        let size = self.get_size();
+        let frame_rect = self.frames.get_frame_rect(&self.image_size, self.frame);
+
+        match (&self.texture, frame_rect) {
+            (Some(texture), Some([x, y, w, h])) => {
+                let frame_size = crate::core::point::Size { w, h };
+
+                Image::new()
+                    .color(apply_opacity([1.0; 4]))
+                    .src_rect([f64::from(x), f64::from(y), f64::from(w), f64::from(h)])
+                    .rect(get_image_rect(self.scaling, &frame_size, &size))
+                    .draw(texture, clip, c.transform, g);
+            }
+            (Some(_), None) => {}
+            (None, _) => draw_broken_image(&size, c, g, clip),
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,610 @@
+// Status Bar Widget
+// Extensible widget for the widget library - handles a bar of text sections along the window.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::timer_widget::*;
+use crate::widget::widget::*;
+
+/// The space above and below the text of a `StatusBarWidget`, in pixels.
+pub const STATUS_BAR_PADDING: i32 = 3;
+
+/// The space between the text of a section of a `StatusBarWidget` and its left and right edges,
+/// in pixels.
+pub const STATUS_SECTION_PADDING: i32 = 6;
+
+/// The width of a section of a `StatusBarWidget`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatusSectionWidth {
+    /// The section is always this many pixels wide, as long as it fits.
+    Fixed(i32),
+
+    /// The section shares the width that is left over by the fixed sections evenly with the
+    /// other stretchy sections.
+    Stretch,
+}
+
+/// Returns the left edge and the width of each section of a `StatusBarWidget` that is `bar_w`
+/// pixels wide.  Each section is given as its width, and whether it is right-aligned.  Sections
+/// that are not right-aligned are placed from the left edge in order, and right-aligned
+/// sections are placed, in order, so that the last one is flush with the right edge.  When the
+/// bar is too narrow for all of the fixed sections, stretchy sections are given no width, and
+/// the sections on the left are cut short where the right-aligned sections start.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::status_bar_widget::*;
+/// # fn main() {
+///    let sections = [
+///        (StatusSectionWidth::Stretch, false),
+///        (StatusSectionWidth::Fixed(100), false),
+///        (StatusSectionWidth::Fixed(80), true),
+///    ];
+///
+///    assert_eq!(
+///        get_status_section_bounds(&sections, 400),
+///        vec![(0, 220), (220, 100), (320, 80)]
+///    );
+///
+///    // The right-aligned section stays flush with the right edge at any width.
+///    assert_eq!(
+///        get_status_section_bounds(&sections, 1000),
+///        vec![(0, 820), (820, 100), (920, 80)]
+///    );
+///    assert_eq!(
+///        get_status_section_bounds(&sections, 150),
+///        vec![(0, 0), (0, 70), (70, 80)]
+///    );
+/// # }
+/// ```
+pub fn get_status_section_bounds(
+    sections: &[(StatusSectionWidth, bool)],
+    bar_w: i32,
+) -> Vec<(i32, i32)> {
+    let fixed_w: i32 = sections
+        .iter()
+        .map(|(width, _)| match width {
+            StatusSectionWidth::Fixed(width) => (*width).max(0),
+            StatusSectionWidth::Stretch => 0,
+        })
+        .sum();
+    let stretch_count = sections
+        .iter()
+        .filter(|(width, _)| *width == StatusSectionWidth::Stretch)
+        .count() as i32;
+    let left_over = (bar_w - fixed_w).max(0);
+    let mut stretch_index = 0;
+    let widths: Vec<i32> = sections
+        .iter()
+        .map(|(width, _)| match width {
+            StatusSectionWidth::Fixed(width) => (*width).max(0),
+            StatusSectionWidth::Stretch => {
+                // Any pixels that cannot be shared evenly go to the first stretchy sections.
+                let extra = if stretch_index < left_over % stretch_count {
+                    1
+                } else {
+                    0
+                };
+
+                stretch_index += 1;
+                left_over / stretch_count + extra
+            }
+        })
+        .collect();
+    let right_w: i32 = sections
+        .iter()
+        .zip(widths.iter())
+        .filter(|((_, right_aligned), _)| *right_aligned)
+        .map(|(_, width)| *width)
+        .sum();
+    let right_start = bar_w - right_w;
+    let mut left_x = 0;
+    let mut right_x = right_start;
+
+    sections
+        .iter()
+        .zip(widths.iter())
+        .map(|((_, right_aligned), width)| {
+            if *right_aligned {
+                right_x += width;
+                (right_x - width, *width)
+            } else {
+                let x = left_x.min(right_start.max(0));
+
+                left_x += width;
+                (x, (*width).min(right_start - x).max(0))
+            }
+        })
+        .collect()
+}
+
+/// A section of a `StatusBarWidget`.
+struct StatusSection {
+    name: String,
+    width: StatusSectionWidth,
+    right_aligned: bool,
+    text: String,
+    text_widget: TextWidget,
+}
+
+/// Private helper that queues a closure to be called with the `WidgetStore` once the current
+/// event has been handled.
+fn queue_callback<F>(deferred: &mut Vec<DeferredCallback>, callback: F)
+where
+    F: FnMut(&mut WidgetStore) + 'static,
+{
+    deferred.push(Rc::new(RefCell::new(Box::new(callback))));
+}
+
+/// This is the `StatusBarWidget`, which docks itself to the bottom edge of the window, and
+/// divides itself into named sections of text.  Each section is either a fixed width, or
+/// stretches to share the width that is left over, and right-aligned sections, such as a clock,
+/// stay flush with the right edge of the window at any width.  Text that does not fit in its
+/// section is cut short with an ellipsis.  Changing the text of a section only redraws that
+/// section.
+///
+/// The status bar must be added to the base widget.  It docks itself to the size of the base
+/// widget the first time it is updated, and follows the window every time it is resized, as
+/// high as its font needs, but it can also be docked right away with `dock`.  A transient
+/// message can be shown in place of the text of the first section, with `set_message`, or with
+/// `set_message_with_timeout`, which clears the message again after a number of milliseconds.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::status_bar_widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Status Bar", [640, 480]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut status_bar =
+///        StatusBarWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///
+///    status_bar.add_section("Status", StatusSectionWidth::Stretch);
+///    status_bar.add_section("Position", StatusSectionWidth::Fixed(120));
+///    status_bar.add_right_section("Clock", StatusSectionWidth::Fixed(80));
+///    status_bar.dock(640, 480);
+///
+///    status_bar.set_section_text("Status", "Ready");
+///    status_bar.set_section_text("Position", "Line 1, Column 1");
+///    status_bar.set_message_with_timeout("File saved", 3_000);
+///
+///    pushrod.add_widget("StatusBar", Box::new(status_bar));
+/// # }
+/// ```
+pub struct StatusBarWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    font_size: u32,
+    sections: Vec<StatusSection>,
+    message: Option<String>,
+    message_timer: TimerWidget,
+    window_size: Option<(i32, i32)>,
+    base_size: Rc<Cell<Option<(i32, i32)>>>,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `StatusBarWidget`.
+impl StatusBarWidget {
+    /// Creates a new status bar without any sections, drawn with the font `font_name` from the
+    /// `assets` directory, in the font size of the current `Theme`.  Panics if the font cannot
+    /// be loaded; use `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, Box::new(SystemClock))
+    }
+
+    /// Creates a new status bar without any sections, drawn in a font from the font cache.
+    /// Messages set with `set_message_with_timeout` are timed by `clock`.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::status_bar_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn show_message(font: SharedGlyphs) {
+    ///    let clock = ManualClock::new();
+    ///    let mut status_bar = StatusBarWidget::from_font(font, Box::new(clock.clone()));
+    ///
+    ///    status_bar.add_section("Status", StatusSectionWidth::Stretch);
+    ///    status_bar.set_section_text("Status", "Ready");
+    ///    status_bar.set_message_with_timeout("File saved", 3_000);
+    ///    assert_eq!(status_bar.get_message(), Some("File saved"));
+    ///
+    ///    // The message clears itself once its time is up.
+    ///    clock.advance(3_000);
+    ///    status_bar.update(0);
+    ///    assert_eq!(status_bar.get_message(), None);
+    ///    assert_eq!(status_bar.get_section_text("Status"), Some("Ready"));
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn from_font(font: SharedGlyphs, clock: Box<dyn Clock>) -> Self {
+        let mut message_timer = TimerWidget::with_clock(clock);
+
+        message_timer.set_one_shot(true);
+        message_timer.set_enabled(false);
+
+        Self {
+            config: Configurable::new(),
+            font,
+            font_size: get_current_theme().font_size,
+            sections: Vec::new(),
+            message: None,
+            message_timer,
+            window_size: None,
+            base_size: Rc::new(Cell::new(None)),
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Adds a section named `name` after the sections that are placed from the left edge.  Its
+    /// text is left-justified.  Invalidates the widget afterward.
+    pub fn add_section(&mut self, name: &str, width: StatusSectionWidth) {
+        self.push_section(name, width, false);
+    }
+
+    /// Adds a section named `name` after the sections that are flush with the right edge, so
+    /// that it becomes the rightmost section.  Its text is right-justified.  Invalidates the
+    /// widget afterward.
+    pub fn add_right_section(&mut self, name: &str, width: StatusSectionWidth) {
+        self.push_section(name, width, true);
+    }
+
+    /// Removes the section named `name`.  Returns `false` if there is no such section.
+    /// Invalidates the widget afterward.
+    pub fn remove_section(&mut self, name: &str) -> bool {
+        match self.find_section(name) {
+            Some(index) => {
+                self.sections.remove(index);
+                self.invalidate();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Retrieves the names of the sections, in the order in which they were added.
+    pub fn get_section_names(&self) -> Vec<&str> {
+        self.sections
+            .iter()
+            .map(|section| section.name.as_str())
+            .collect()
+    }
+
+    /// Sets the text of the section named `name`, and invalidates only the area of that section.
+    /// Does nothing if there is no such section.
+    pub fn set_section_text(&mut self, name: &str, text: &str) {
+        let index = match self.find_section(name) {
+            Some(index) => index,
+            None => return,
+        };
+
+        if self.sections[index].text == text {
+            return;
+        }
+
+        self.sections[index].text = text.to_string();
+
+        if index > 0 || self.message.is_none() {
+            self.sections[index].text_widget.set_text(text);
+            self.invalidate_section(index);
+        }
+    }
+
+    /// Retrieves the text of the section named `name`, or `None` if there is no such section.
+    pub fn get_section_text(&self, name: &str) -> Option<&str> {
+        self.find_section(name)
+            .map(|index| self.sections[index].text.as_str())
+    }
+
+    /// Shows `message` in place of the text of the first section, until it is cleared with
+    /// `clear_message`, or another message is set.
+    pub fn set_message(&mut self, message: &str) {
+        self.message_timer.set_enabled(false);
+        self.show_message(Some(message.to_string()));
+    }
+
+    /// Shows `message` in place of the text of the first section, and clears it again once
+    /// `timeout` milliseconds have passed, unless another message has been set by then.
+    pub fn set_message_with_timeout(&mut self, message: &str, timeout: u64) {
+        self.message_timer.set_timeout(timeout);
+        self.message_timer.reset();
+        self.show_message(Some(message.to_string()));
+    }
+
+    /// Clears the message, showing the text of the first section again.
+    pub fn clear_message(&mut self) {
+        self.message_timer.set_enabled(false);
+        self.show_message(None);
+    }
+
+    /// Retrieves the message that is shown, if any.
+    pub fn get_message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Sets the font size of the text of all sections, which also sets the height of the status
+    /// bar.  Invalidates the widget afterward.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.font_size = font_size;
+
+        for section in self.sections.iter_mut() {
+            section.text_widget.set_font_size(font_size);
+        }
+
+        if let Some((window_w, window_h)) = self.window_size {
+            self.dock(window_w, window_h);
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the text.  Defaults to the font size of the current `Theme`.
+    pub fn get_font_size(&self) -> u32 {
+        self.font_size
+    }
+
+    /// Sets the color of the text of all sections for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Retrieves the height of the status bar, which is the height of a line of text in its
+    /// font size, plus `STATUS_BAR_PADDING` above and below.
+    pub fn get_bar_height(&self) -> i32 {
+        (f64::from(self.font_size) * DEFAULT_LINE_SPACING).ceil() as i32 + STATUS_BAR_PADDING * 2
+    }
+
+    /// Docks the status bar to the bottom edge of a window that is `window_w` by `window_h`
+    /// pixels, stretching it across the width of the window.  This is done automatically when
+    /// the status bar is first updated, and whenever the window is resized.
+    pub fn dock(&mut self, window_w: i32, window_h: i32) {
+        let bar_h = self.get_bar_height();
+
+        self.window_size = Some((window_w, window_h));
+        self.set_origin(0, window_h - bar_h);
+        self.set_size(window_w, bar_h);
+    }
+
+    fn push_section(&mut self, name: &str, width: StatusSectionWidth, right_aligned: bool) {
+        let justify = if right_aligned {
+            TextJustify::Right
+        } else {
+            TextJustify::Left
+        };
+        let mut text_widget =
+            TextWidget::from_font(self.font.clone(), String::new(), self.font_size, justify);
+
+        text_widget.set_ellipsize(true);
+
+        let section = StatusSection {
+            name: name.to_string(),
+            width,
+            right_aligned,
+            text: String::new(),
+            text_widget,
+        };
+
+        // Right-aligned sections are kept after all of the others, so the first section is
+        // always the leftmost one.
+        if right_aligned {
+            self.sections.push(section);
+        } else {
+            let index = self
+                .sections
+                .iter()
+                .position(|section| section.right_aligned)
+                .unwrap_or(self.sections.len());
+
+            self.sections.insert(index, section);
+        }
+
+        if self.message.is_some() {
+            let message = self.message.take();
+
+            self.show_message(message);
+        }
+
+        self.invalidate();
+    }
+
+    fn find_section(&self, name: &str) -> Option<usize> {
+        self.sections
+            .iter()
+            .position(|section| section.name == name)
+    }
+
+    /// Shows a message, or the text of the first section again, and invalidates the area in
+    /// which it is shown.
+    fn show_message(&mut self, message: Option<String>) {
+        if self.message == message {
+            return;
+        }
+
+        self.message = message;
+
+        match self.sections.first_mut() {
+            Some(section) => {
+                let text = self.message.as_ref().unwrap_or(&section.text).clone();
+
+                section.text_widget.set_text(&text);
+                self.invalidate_section(0);
+            }
+            None => self.invalidate(),
+        }
+    }
+
+    /// Returns the left edge and the width of each section at the current width of the widget.
+    fn get_section_bounds(&mut self) -> Vec<(i32, i32)> {
+        let sections: Vec<(StatusSectionWidth, bool)> = self
+            .sections
+            .iter()
+            .map(|section| (section.width, section.right_aligned))
+            .collect();
+
+        get_status_section_bounds(&sections, self.get_size().w)
+    }
+
+    fn invalidate_section(&mut self, index: usize) {
+        let (x, w) = self.get_section_bounds()[index];
+        let h = self.get_size().h;
+
+        self.invalidate_area([x, 0, w, h]);
+    }
+}
+
+/// Implementation of the `StatusBarWidget` object with the `Widget` traits implemented.  The
+/// text of each section is drawn by a `TextWidget`.
+impl Widget for StatusBarWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::StatusBar
+    }
+
+    /// The value is the message that is shown, if there is one.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        self.get_message().map(String::from)
+    }
+
+    /// Docks the status bar to the bottom of the window, and follows it as it is resized.
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if let CallbackEvent::WindowResized { size } = event {
+            self.dock(size.w, size.h);
+        }
+
+        None
+    }
+
+    /// Docks the status bar to the size of the base widget, if it has not been docked yet, and
+    /// clears a message once its time is up.
+    fn update(&mut self, _delta_ms: u64) {
+        if let Some((window_w, window_h)) = self.base_size.take() {
+            if self.window_size.is_none() {
+                self.dock(window_w, window_h);
+            }
+        } else if self.window_size.is_none() {
+            let base_size = self.base_size.clone();
+
+            queue_callback(&mut self.deferred, move |widget_store| {
+                let size = widget_store
+                    .get_widget_for_id(WidgetId::ROOT)
+                    .borrow_mut()
+                    .get_size();
+
+                base_size.set(Some((size.w, size.h)));
+            });
+        }
+
+        self.message_timer.tick();
+
+        if self.message_timer.get_fire_count() > 0 {
+            self.message_timer.set_enabled(false);
+            self.show_message(None);
+        }
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the text of each section, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        for section in self.sections.iter_mut() {
+            section.text_widget.theme_changed(theme);
+        }
+
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text of each section, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        for section in self.sections.iter_mut() {
+            section.text_widget.scale_factor_changed(scale_factor);
+        }
+
+        self.invalidate();
+    }
+
+    /// Draws the background, a line along the top edge, a line between each pair of adjacent
+    /// sections, and then the text of each section.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let bounds = self.get_section_bounds();
+        let text_color = if self.is_disabled() {
+            theme.disabled_color
+        } else {
+            self.get_text_color()
+        };
+        let line = Rectangle::new(apply_opacity(theme.border_color));
+
+        Rectangle::new(apply_opacity(self.get_color())).draw(
+            [0.0, 0.0, w, h],
+            clip,
+            c.transform,
+            g,
+        );
+        line.draw([0.0, 0.0, w, 1.0], clip, c.transform, g);
+
+        for (section, (x, section_w)) in self.sections.iter_mut().zip(bounds) {
+            if section_w <= 0 {
+                continue;
+            }
+
+            if x > 0 {
+                line.draw([f64::from(x), 4.0, 1.0, h - 6.0], clip, c.transform, g);
+            }
+
+            section
+                .text_widget
+                .set_size((section_w - STATUS_SECTION_PADDING * 2).max(0), size.h);
+            section.text_widget.set_text_color(text_color);
+            section
+                .text_widget
+                .draw(c.trans(f64::from(x + STATUS_SECTION_PADDING), 0.0), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
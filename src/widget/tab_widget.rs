@@ -0,0 +1,551 @@
+// Tab Widget
+// Extensible widget for the widget library - handles a container of tabbed pages.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::layout_widget::LayoutChild;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the index of the newly selected tab of a `TabWidget`.
+pub type TabCallback = Box<dyn FnMut(usize)>;
+
+/// The default height of the row of tab headers of a `TabWidget`, in pixels.
+pub const DEFAULT_TAB_HEADER_HEIGHT: i32 = 28;
+
+/// The number of pixels between the edges of a tab header and its title.
+const TAB_TEXT_PADDING: i32 = 12;
+
+/// The width of the close button of a closable tab, in pixels.
+const TAB_CLOSE_WIDTH: i32 = 16;
+
+/// How far the background of a hovered tab header is blended toward the accent color of the
+/// current `Theme`.
+const TAB_HOVER_AMOUNT: f32 = 0.2;
+
+/// Returns the widths of tab headers that would like to be `natural` pixels wide, in a row that
+/// is `available` pixels wide.  The headers keep their widths if they all fit, and otherwise
+/// share the row equally, the leftmost headers taking any pixels that are left over.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::tab_widget::*;
+/// # fn main() {
+///    assert_eq!(get_tab_header_widths(&[60, 80, 40], 300), vec![60, 80, 40]);
+///    assert_eq!(get_tab_header_widths(&[60, 80, 40], 100), vec![34, 33, 33]);
+///    assert!(get_tab_header_widths(&[], 100).is_empty());
+/// # }
+/// ```
+pub fn get_tab_header_widths(natural: &[i32], available: i32) -> Vec<i32> {
+    let count = natural.len() as i32;
+
+    if natural.iter().sum::<i32>() <= available {
+        return natural.to_vec();
+    }
+
+    let (share, extra) = (available.max(0) / count, available.max(0) % count);
+
+    (0..count)
+        .map(|index| share + if index < extra { 1 } else { 0 })
+        .collect()
+}
+
+/// This is the `TabWidget`, which draws a row of tab headers across its top, and shows the page
+/// of the selected tab below them.  The pages are the children of the `TabWidget`, and are
+/// added and removed along with their tabs with `WidgetStore::add_tab` and `remove_tab`, which
+/// keep the titles of the tabs in the `TabTitles` of the widget.  Only the page of the selected
+/// tab is visible, and it is laid out to fill the area below the headers.
+///
+/// Clicking a header, or pressing `Left` or `Right` while the widget is focused, selects its
+/// tab, hiding the page that was shown, showing the new one, and calling the `on_tab_changed`
+/// callback with the index of the tab.  The header of the selected tab is drawn in the accent
+/// color of the current `Theme`.  When the headers do not fit in the widget, they share its
+/// width, and their titles are shortened.  Closable tabs have a small `×` button, which removes
+/// the tab and its page.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::tab_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Tabs", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut tabs = TabWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///
+///    tabs.set_origin(10, 10);
+///    tabs.set_size(380, 280);
+///    tabs.on_tab_changed(Box::new(|index| eprintln!("Tab {} selected", index)));
+///
+///    let tabs_id = pushrod.add_widget("Tabs", Box::new(tabs));
+///    let widget_store = &mut pushrod.widget_store.borrow_mut();
+///
+///    widget_store.add_tab(tabs_id, "General", "GeneralPage", Box::new(CanvasWidget::new()));
+///    widget_store.add_tab(tabs_id, "Advanced", "AdvancedPage", Box::new(CanvasWidget::new()));
+/// # }
+/// ```
+pub struct TabWidget {
+    config: Configurable,
+    font: SharedGlyphs,
+    text_widget: Box<TextWidget>,
+    header_height: i32,
+    closable: bool,
+    hovered: Option<usize>,
+    hovered_close: bool,
+    pressed_close: Option<usize>,
+    reported_tab: Option<usize>,
+    on_tab_changed: Option<TabCallback>,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `TabWidget`.
+impl TabWidget {
+    /// Creates a new tab widget without any tabs, whose titles are drawn with the font
+    /// `font_name` from the `assets` directory, in the font size of the current `Theme`.
+    /// Panics if the font cannot be loaded; use `font_cache::load_font` and `from_font` to
+    /// handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs)
+    }
+
+    /// Creates a new tab widget without any tabs, whose titles are drawn in a font from the
+    /// font cache.  The widget can be focused, so that tabs can be selected from the keyboard.
+    pub fn from_font(font: SharedGlyphs) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font.clone(), String::new(), font_size, TextJustify::Center);
+
+        text_widget.set_ellipsize(true);
+        text_widget.set_padding(make_insets(
+            0,
+            TAB_TEXT_PADDING / 2,
+            0,
+            TAB_TEXT_PADDING / 2,
+        ));
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            font,
+            text_widget: Box::new(text_widget),
+            header_height: DEFAULT_TAB_HEADER_HEIGHT,
+            closable: false,
+            hovered: None,
+            hovered_close: false,
+            pressed_close: None,
+            reported_tab: None,
+            on_tab_changed: None,
+            deferred: Vec::new(),
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(true, false);
+        widget
+    }
+
+    /// Sets the closure that is called with the index of the newly selected tab.  It is called
+    /// when a header is clicked, and when the selection is changed by the `WidgetStore`, such as
+    /// when the selected tab is removed, but not when the first tab is added.
+    pub fn on_tab_changed(&mut self, callback: TabCallback) {
+        self.on_tab_changed = Some(callback);
+    }
+
+    /// Retrieves the titles of the tabs.
+    pub fn get_tab_titles(&mut self) -> Vec<String> {
+        self.config()
+            .get::<TabTitles>()
+            .map(|titles| titles.0.clone())
+            .unwrap_or_default()
+    }
+
+    /// Retrieves the index of the selected tab, or `None` if there are no tabs.
+    pub fn get_selected_tab(&mut self) -> Option<usize> {
+        self.config()
+            .get::<SelectedTab>()
+            .map(|selected| selected.0)
+    }
+
+    /// Sets whether the tabs have a close button, which removes the tab and its page when it is
+    /// clicked.  Invalidates the widget afterward.
+    pub fn set_closable(&mut self, closable: bool) {
+        self.closable = closable;
+        self.invalidate();
+    }
+
+    /// Indicates whether the tabs have a close button.  Defaults to `false`.
+    pub fn is_closable(&self) -> bool {
+        self.closable
+    }
+
+    /// Sets the height of the row of tab headers.  Invalidates the widget afterward.
+    pub fn set_header_height(&mut self, header_height: i32) {
+        self.header_height = header_height.max(0);
+        self.invalidate();
+    }
+
+    /// Retrieves the height of the row of tab headers.  Defaults to
+    /// `DEFAULT_TAB_HEADER_HEIGHT`.
+    pub fn get_header_height(&self) -> i32 {
+        self.header_height
+    }
+
+    /// Returns the left edge and the width of each tab header.
+    fn get_header_bounds(&mut self) -> Vec<(i32, i32)> {
+        let titles = self.get_tab_titles();
+        let available = self.get_size().w;
+        let close_w = if self.closable { TAB_CLOSE_WIDTH } else { 0 };
+        let natural: Vec<i32> = {
+            let font_size = self.text_widget.get_font_size();
+            let mut glyphs = self.font.borrow_mut();
+
+            titles
+                .iter()
+                .map(|title| text_width(&mut glyphs, font_size, title) + TAB_TEXT_PADDING + close_w)
+                .collect()
+        };
+
+        get_tab_header_widths(&natural, available)
+            .into_iter()
+            .scan(0, |x, w| {
+                let header_x = *x;
+
+                *x += w;
+                Some((header_x, w))
+            })
+            .collect()
+    }
+
+    /// Returns the index of the tab whose header is at `point`, and whether `point` is over its
+    /// close button.
+    fn get_header_at(&mut self, point: &Point) -> Option<(usize, bool)> {
+        if point.y < 0 || point.y >= self.header_height {
+            return None;
+        }
+
+        let closable = self.closable;
+
+        self.get_header_bounds()
+            .into_iter()
+            .enumerate()
+            .find(|(_, (x, w))| point.x >= *x && point.x < x + w)
+            .map(|(index, (x, w))| (index, closable && point.x >= x + w - TAB_CLOSE_WIDTH))
+    }
+
+    /// Selects the tab at `index`, and calls the `on_tab_changed` callback if the selection
+    /// changed.  The `WidgetStore` shows its page once this widget has handled the event.
+    fn select(&mut self, index: usize) {
+        if index >= self.get_tab_titles().len() || Some(index) == self.get_selected_tab() {
+            return;
+        }
+
+        self.config().set(SelectedTab(index));
+        self.invalidate();
+        self.report_selection();
+    }
+
+    /// Calls the `on_tab_changed` callback if the selected tab is not the one that was last
+    /// reported.  The first tab that is added is selected without calling it.
+    fn report_selection(&mut self) {
+        let selected = self.get_selected_tab();
+
+        if selected != self.reported_tab {
+            let reported_tab = std::mem::replace(&mut self.reported_tab, selected);
+
+            if let (Some(_), Some(index), Some(callback)) =
+                (reported_tab, selected, &mut self.on_tab_changed)
+            {
+                callback(index);
+            }
+        }
+    }
+
+    fn set_hovered(&mut self, hovered: Option<(usize, bool)>) {
+        let (index, close) = match hovered {
+            Some((index, close)) => (Some(index), close),
+            None => (None, false),
+        };
+
+        if index != self.hovered || close != self.hovered_close {
+            self.hovered = index;
+            self.hovered_close = close;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `TabWidget` object with the `Widget` traits implemented.  The titles
+/// are drawn with a single `TextWidget`, which is given each title in turn.
+impl Widget for TabWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::TabList
+    }
+
+    /// The value is the title of the selected tab.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        let titles = self.get_tab_titles();
+
+        self.get_selected_tab()
+            .and_then(|index| titles.get(index).cloned())
+    }
+
+    /// Places the page of the selected tab, which is the only visible child, in the area below
+    /// the tab headers, inside of the border.
+    fn layout_children(&mut self, children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        let size = self.get_size();
+        let bounds = [
+            1,
+            self.header_height + 1,
+            (size.w - 2).max(0),
+            (size.h - self.header_height - 2).max(0),
+        ];
+
+        children.iter().map(|_| bounds).collect()
+    }
+
+    /// Calls the `on_tab_changed` callback when the selected tab was changed by the
+    /// `WidgetStore`.
+    fn update(&mut self, _delta_ms: u64) {
+        self.report_selection();
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                let hovered = self.get_header_at(&point);
+
+                self.set_hovered(hovered);
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered(None),
+
+            CallbackEvent::MouseButtonDown {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } => match self.get_header_at(&point) {
+                Some((index, true)) => {
+                    self.pressed_close = Some(index);
+                    self.invalidate();
+                }
+                Some((index, false)) => self.select(index),
+                None => (),
+            },
+
+            CallbackEvent::MouseButtonUpInside {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                point,
+            } => {
+                if let Some(index) = self.pressed_close.take() {
+                    if self.get_header_at(&point) == Some((index, true)) {
+                        self.deferred.push(Rc::new(RefCell::new(Box::new(
+                            move |widget_store: &mut WidgetStore| {
+                                widget_store.remove_tab(widget_id, index)
+                            },
+                        ))));
+                    }
+
+                    self.invalidate();
+                }
+            }
+
+            CallbackEvent::MouseButtonUpOutside {
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } => {
+                self.pressed_close = None;
+                self.invalidate();
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => {
+                if let Some(selected) = self.get_selected_tab() {
+                    match key {
+                        Key::Left if selected > 0 => self.select(selected - 1),
+                        Key::Right => self.select(selected + 1),
+                        _ => (),
+                    }
+                }
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new `Theme` on to the titles, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the titles, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Enables or disables this widget.  The titles of a disabled tab widget are drawn in the
+    /// disabled color of the current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.hovered = None;
+        self.pressed_close = None;
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Draws the background, a border around the page, and then each tab header: the header of
+    /// the selected tab in the accent color of the current `Theme`, and the hovered header in
+    /// the background color blended toward it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let (w, h) = (f64::from(size.w), f64::from(size.h));
+        let header_h = f64::from(self.header_height);
+        let background = self.get_color();
+        let mut hover_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * TAB_HOVER_AMOUNT;
+        }
+
+        Rectangle::new(apply_opacity(background)).draw([0.0, 0.0, w, h], clip, c.transform, g);
+        Rectangle::new_border(apply_opacity(theme.border_color), 0.5).draw(
+            [0.5, header_h + 0.5, w - 1.0, h - header_h - 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let titles = self.get_tab_titles();
+        let selected = self.get_selected_tab();
+        let close_w = if self.closable { TAB_CLOSE_WIDTH } else { 0 };
+
+        for (index, (x, header_w)) in self.get_header_bounds().into_iter().enumerate() {
+            let (header_x, header_width) = (f64::from(x), f64::from(header_w));
+            let is_selected = selected == Some(index);
+            let text_color = if is_selected {
+                theme.background_color
+            } else {
+                theme.text_color
+            };
+            let header_color = if is_selected {
+                Some(theme.accent_color)
+            } else if self.hovered == Some(index) {
+                Some(hover_color)
+            } else {
+                None
+            };
+
+            if let Some(header_color) = header_color {
+                Rectangle::new(apply_opacity(header_color)).draw(
+                    [header_x, 0.0, header_width, header_h],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            Rectangle::new_border(apply_opacity(theme.border_color), 0.5).draw(
+                [header_x + 0.5, 0.5, header_width - 1.0, header_h],
+                clip,
+                c.transform,
+                g,
+            );
+
+            self.text_widget
+                .set_size(header_w - close_w, self.header_height);
+            self.text_widget.set_text(titles[index].clone());
+            self.text_widget.set_text_color(text_color);
+            self.text_widget.draw(c.trans(header_x, 0.0), g, clip);
+
+            if self.closable {
+                let middle_x = header_x + header_width - f64::from(TAB_CLOSE_WIDTH) / 2.0 - 2.0;
+                let middle_y = header_h / 2.0;
+                let thickness = if self.hovered == Some(index) && self.hovered_close {
+                    1.25
+                } else {
+                    0.75
+                };
+                let line = Line::new(apply_opacity(text_color), thickness);
+
+                line.draw(
+                    [
+                        middle_x - 3.0,
+                        middle_y - 3.0,
+                        middle_x + 3.0,
+                        middle_y + 3.0,
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+                line.draw(
+                    [
+                        middle_x - 3.0,
+                        middle_y + 3.0,
+                        middle_x + 3.0,
+                        middle_y - 3.0,
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,1154 @@
+// Table Widget
+// Extensible widget for the widget library - handles rows of data in sortable, resizable columns.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+use std::cmp::Ordering;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::list_widget::{ItemCallback, SelectionCallback};
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The default height of the header and the rows of a `TableWidget`, in pixels.
+pub const DEFAULT_TABLE_ROW_HEIGHT: i32 = 24;
+
+/// The narrowest that a column of a `TableWidget` can be made by dragging its divider, in
+/// pixels.
+pub const MIN_TABLE_COLUMN_WIDTH: i32 = 16;
+
+/// The number of pixels between the edges of a cell of a `TableWidget` and its text.
+const TABLE_TEXT_INDENT: i32 = 4;
+
+/// The number of pixels on either side of a divider between two column titles in which the
+/// divider can be grabbed.
+const TABLE_DIVIDER_GRAB: i32 = 3;
+
+/// The width of the sort indicator at the right of the title of the sorted column.
+const TABLE_SORT_INDICATOR_SPACE: i32 = 14;
+
+/// How far the background of a hovered row is blended toward the accent color of the current
+/// `Theme`.
+const TABLE_HOVER_AMOUNT: f32 = 0.2;
+
+/// How the width of a column of a `TableWidget` is chosen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableColumnWidth {
+    /// A fixed number of pixels.  A column that is resized by dragging its divider is given a
+    /// fixed width.
+    Fixed(i32),
+
+    /// A share of the width that is left over by the fixed columns, in proportion to the weights
+    /// of all weighted columns.
+    Weight(u32),
+}
+
+/// The order in which the rows of a `TableWidget` are sorted by a column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A column of a `TableWidget`: the title shown in its header, its width, and how the text of
+/// its title and cells is justified.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumn {
+    pub title: String,
+    pub width: TableColumnWidth,
+    pub justify: TextJustify,
+}
+
+/// Implementation of the `TableColumn`.
+impl TableColumn {
+    /// Creates a column with a `title` and a `width`, with left-justified text.
+    pub fn new(title: &str, width: TableColumnWidth) -> Self {
+        Self {
+            title: title.to_string(),
+            width,
+            justify: TextJustify::Left,
+        }
+    }
+
+    /// Returns a copy of this column with its text justified by `justify`.
+    pub fn with_justify(self, justify: TextJustify) -> Self {
+        Self { justify, ..self }
+    }
+}
+
+/// Compares the text of two cells for sorting.  Cells that are both numbers are compared by
+/// value, and any others alphabetically, ignoring case unless they only differ by case.
+///
+/// Example:
+/// ```
+/// # use std::cmp::Ordering;
+/// # use pushrod::widget::table_widget::*;
+/// # fn main() {
+///    assert_eq!(compare_table_cells("9", "10"), Ordering::Less);
+///    assert_eq!(compare_table_cells("-2.5", "-3"), Ordering::Greater);
+///    assert_eq!(compare_table_cells("apple", "Banana"), Ordering::Less);
+///    assert_eq!(compare_table_cells("Apple", "apple"), Ordering::Less);
+///    assert_eq!(compare_table_cells("10", "apple"), Ordering::Less);
+/// # }
+/// ```
+pub fn compare_table_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a
+            .to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b)),
+    }
+}
+
+/// Returns the width of each column of a `TableWidget` that is `table_w` pixels wide.  Fixed
+/// columns keep their width, and the weighted columns share whatever is left, in proportion to
+/// their weights.  Any pixels that cannot be shared exactly go to the last weighted column.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::table_widget::*;
+/// # fn main() {
+///    let widths = [
+///        TableColumnWidth::Fixed(100),
+///        TableColumnWidth::Weight(2),
+///        TableColumnWidth::Weight(1),
+///    ];
+///
+///    assert_eq!(get_table_column_widths(&widths, 400), vec![100, 200, 100]);
+///    assert_eq!(get_table_column_widths(&widths, 402), vec![100, 201, 101]);
+///
+///    // Weighted columns are given no width if the fixed columns fill the table.
+///    assert_eq!(get_table_column_widths(&widths, 80), vec![100, 0, 0]);
+/// # }
+/// ```
+pub fn get_table_column_widths(widths: &[TableColumnWidth], table_w: i32) -> Vec<i32> {
+    let fixed_w: i32 = widths
+        .iter()
+        .map(|width| match width {
+            TableColumnWidth::Fixed(width) => (*width).max(0),
+            TableColumnWidth::Weight(_) => 0,
+        })
+        .sum();
+    let total_weight: u32 = widths
+        .iter()
+        .map(|width| match width {
+            TableColumnWidth::Fixed(_) => 0,
+            TableColumnWidth::Weight(weight) => *weight,
+        })
+        .sum();
+    let left_over = i64::from((table_w - fixed_w).max(0));
+    let mut weight_so_far = 0;
+    let mut given_so_far = 0;
+
+    widths
+        .iter()
+        .map(|width| match width {
+            TableColumnWidth::Fixed(width) => (*width).max(0),
+            TableColumnWidth::Weight(_) if total_weight == 0 => 0,
+            TableColumnWidth::Weight(weight) => {
+                // Each column is given its share of the running total, so that the rounding
+                // errors never add up.
+                weight_so_far += weight;
+
+                let share = left_over * i64::from(weight_so_far) / i64::from(total_weight);
+                let given = share - given_so_far;
+
+                given_so_far = share;
+                given as i32
+            }
+        })
+        .collect()
+}
+
+/// The rows of data that are shown by a `TableWidget`, one cell for each column.  A model only
+/// needs to provide the cells that are drawn, so the data of a very large table can be looked
+/// up or generated as it is needed, rather than all being kept in memory as text.  A
+/// `Vec<Vec<String>>` is a model of its rows, and of the cells in each row.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::table_widget::*;
+/// # fn main() {
+///    // A table of a million squares, which are never stored.
+///    struct Squares;
+///
+///    impl TableModel for Squares {
+///        fn get_row_count(&self) -> usize {
+///            1_000_000
+///        }
+///
+///        fn get_cell(&self, row: usize, column: usize) -> String {
+///            match column {
+///                0 => row.to_string(),
+///                _ => (row * row).to_string(),
+///            }
+///        }
+///    }
+///
+///    assert_eq!(Squares.get_cell(12, 1), "144");
+///
+///    let mut rows = vec![vec!["Pears".to_string(), "3".to_string()]];
+///
+///    assert!(rows.set_cell(0, 1, "4"));
+///    assert_eq!(rows.get_cell(0, 1), "4");
+///    assert_eq!(rows.get_cell(5, 0), "");
+/// # }
+/// ```
+pub trait TableModel {
+    /// Retrieves the number of rows.
+    fn get_row_count(&self) -> usize;
+
+    /// Retrieves the text of the cell at `row` and `column`.
+    fn get_cell(&self, row: usize, column: usize) -> String;
+
+    /// Changes the text of the cell at `row` and `column`, returning whether it was changed.  By
+    /// default, the cells cannot be changed through the table.
+    fn set_cell(&mut self, _row: usize, _column: usize, _text: &str) -> bool {
+        false
+    }
+
+    /// Compares two rows by their cells in `column`, for sorting.  Defaults to comparing the text
+    /// of the cells with `compare_table_cells`.
+    fn compare_rows(&self, a: usize, b: usize, column: usize) -> Ordering {
+        compare_table_cells(&self.get_cell(a, column), &self.get_cell(b, column))
+    }
+}
+
+/// Implementation of the `TableModel` for rows of cells that are kept in memory.  Missing cells
+/// are empty.
+impl TableModel for Vec<Vec<String>> {
+    fn get_row_count(&self) -> usize {
+        self.len()
+    }
+
+    fn get_cell(&self, row: usize, column: usize) -> String {
+        self.get(row)
+            .and_then(|cells| cells.get(column))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_cell(&mut self, row: usize, column: usize, text: &str) -> bool {
+        match self.get_mut(row).and_then(|cells| cells.get_mut(column)) {
+            Some(cell) if cell != text => {
+                *cell = text.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn compare_rows(&self, a: usize, b: usize, column: usize) -> Ordering {
+        let cell = |row: usize| {
+            self[row]
+                .get(column)
+                .map(|cell| cell.as_str())
+                .unwrap_or("")
+        };
+
+        compare_table_cells(cell(a), cell(b))
+    }
+}
+
+/// This is the `TableWidget`, which displays rows of data in columns, below a header with the
+/// title of each column.  The data comes from a `TableModel`, and the columns are described by
+/// `TableColumn`s.
+///
+/// Clicking the title of a column sorts the rows by that column, in ascending order, and
+/// clicking it again reverses the order.  The sorted column shows an arrow pointing in the
+/// direction of the order.  The dividers between the titles can be dragged to resize the
+/// columns.
+///
+/// Clicking a row selects it, and the `Up` and `Down` keys move the selection while the table
+/// is focused.  The `on_selection_changed` callback is called with the selected row every time
+/// the selection changes, and `on_row_activated` when a row is double-clicked, or `Enter` is
+/// pressed.  Rows are always identified by their index in the model, however they are sorted.
+///
+/// Like the `ListWidget`, the table scrolls its rows itself, below a header that stays in
+/// place, and passes the mouse wheel on to the widget underneath, such as a
+/// `ScrollContainerWidget`, once it cannot scroll any further.  Only the rows that can be seen
+/// are drawn, and only the cells that are changed with `set_cell` or `invalidate_cell` are
+/// redrawn.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::table_widget::*;
+/// # use pushrod::widget::text_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Table", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let columns = vec![
+///        TableColumn::new("Name", TableColumnWidth::Weight(1)),
+///        TableColumn::new("Size", TableColumnWidth::Fixed(80)).with_justify(TextJustify::Right),
+///    ];
+///    let rows: Vec<Vec<String>> = (1..=100)
+///        .map(|number| vec![format!("File {}", number), format!("{} KB", number * 4)])
+///        .collect();
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut table = TableWidget::new(pushrod.get_factory(), font_name, columns, Box::new(rows));
+///
+///    table.set_origin(20, 20);
+///    table.set_size(360, 260);
+///    table.on_selection_changed(Box::new(|row| eprintln!("Selected: {:?}", row)));
+///    table.on_row_activated(Box::new(|row| eprintln!("Activated: {}", row)));
+///    pushrod.add_widget("Table", Box::new(table));
+/// # }
+/// ```
+pub struct TableWidget {
+    config: Configurable,
+    text_widget: Box<TextWidget>,
+    columns: Vec<TableColumn>,
+    model: Box<dyn TableModel>,
+    row_count: usize,
+    row_order: Vec<usize>,
+    sort: Option<(usize, SortOrder)>,
+    row_height: i32,
+    scroll_y: i32,
+    hovered_row: Option<usize>,
+    hovered_divider: Option<usize>,
+    resizing: Option<(usize, i32)>,
+    resized: bool,
+    selected_row: Option<usize>,
+    on_selection_changed: Option<SelectionCallback>,
+    on_row_activated: Option<ItemCallback>,
+}
+
+/// Implementation of the constructor for the `TableWidget`.
+impl TableWidget {
+    /// Creates a new table of the rows of `model` in `columns`, drawn with the font `font_name`
+    /// from the `assets` directory, in the font size of the current `Theme`.  The rows are not
+    /// sorted, and no row is selected.  The table can be focused, so that it can be moved
+    /// through from the keyboard.  Panics if the font cannot be loaded; use
+    /// `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(
+        factory: &mut GfxFactory,
+        font_name: String,
+        columns: Vec<TableColumn>,
+        model: Box<dyn TableModel>,
+    ) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, columns, model)
+    }
+
+    /// Creates a new table of the rows of `model` in `columns` that draws its text in a font
+    /// from the font cache.
+    ///
+    /// Example:
+    /// ```
+    /// # use piston_window::*;
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::table_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn sort_by_clicking(font: SharedGlyphs) {
+    ///    let columns = vec![TableColumn::new("Fruit", TableColumnWidth::Weight(1))];
+    ///    let rows = vec![vec!["Pears".to_string()], vec!["Apples".to_string()]];
+    ///    let mut table = TableWidget::from_font(font, columns, Box::new(rows));
+    ///
+    ///    table.set_size(200, 100);
+    ///    table.set_selected_row(Some(0));
+    ///
+    ///    // Clicking the title sorts the rows by that column.
+    ///    table.handle_event(CallbackEvent::MouseClicked {
+    ///        widget_id: WidgetId::from(1),
+    ///        button: Button::Mouse(MouseButton::Left),
+    ///        point: make_point_i32(50, 10),
+    ///        click_count: 1,
+    ///        modifiers: KeyModifiers::default(),
+    ///    });
+    ///    assert_eq!(table.get_sort(), Some((0, SortOrder::Ascending)));
+    ///    assert_eq!(table.get_row_at(30), Some(1));
+    ///
+    ///    // The same row stays selected.
+    ///    assert_eq!(table.get_selected_row(), Some(0));
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn from_font(
+        font: SharedGlyphs,
+        columns: Vec<TableColumn>,
+        model: Box<dyn TableModel>,
+    ) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font, String::new(), font_size, TextJustify::Left);
+
+        text_widget.set_padding(make_insets(0, TABLE_TEXT_INDENT, 0, TABLE_TEXT_INDENT));
+        text_widget.set_ellipsize(true);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            text_widget: Box::new(text_widget),
+            columns,
+            row_count: model.get_row_count(),
+            model,
+            row_order: Vec::new(),
+            sort: None,
+            row_height: DEFAULT_TABLE_ROW_HEIGHT,
+            scroll_y: 0,
+            hovered_row: None,
+            hovered_divider: None,
+            resizing: None,
+            resized: false,
+            selected_row: None,
+            on_selection_changed: None,
+            on_row_activated: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget
+    }
+
+    /// Sets the closure that is called with the selected row when the selection changes.
+    pub fn on_selection_changed(&mut self, callback: SelectionCallback) {
+        self.on_selection_changed = Some(callback);
+    }
+
+    /// Sets the closure that is called with the row that is double-clicked, or that is selected
+    /// when `Enter` is pressed.
+    pub fn on_row_activated(&mut self, callback: ItemCallback) {
+        self.on_row_activated = Some(callback);
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.  The text of the selected
+    /// row is drawn in the background color of the current `Theme`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Replaces the columns of the table.  If the rows were sorted by a column that no longer
+    /// exists, they are no longer sorted.  Invalidates the widget afterward.
+    pub fn set_columns(&mut self, columns: Vec<TableColumn>) {
+        self.columns = columns;
+
+        if let Some((column, _)) = self.sort {
+            if column >= self.columns.len() {
+                self.set_sort(None);
+            }
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the columns of the table, with the widths that they have been resized to.
+    pub fn get_columns(&self) -> &[TableColumn] {
+        &self.columns
+    }
+
+    /// Sets the width of `column`.  Does nothing if there is no such column.  Invalidates the
+    /// widget afterward.
+    pub fn set_column_width(&mut self, column: usize, width: TableColumnWidth) {
+        if let Some(table_column) = self.columns.get_mut(column) {
+            table_column.width = width;
+            self.invalidate();
+        }
+    }
+
+    /// Retrieves the width of each column in pixels, at the current width of the table.
+    pub fn get_column_widths(&mut self) -> Vec<i32> {
+        let widths: Vec<TableColumnWidth> =
+            self.columns.iter().map(|column| column.width).collect();
+
+        get_table_column_widths(&widths, self.get_size().w)
+    }
+
+    /// Replaces the model of the table.  The rows are sorted again, the selection is cleared,
+    /// and the table is scrolled back to the top.
+    pub fn set_model(&mut self, model: Box<dyn TableModel>) {
+        self.model = model;
+        self.hovered_row = None;
+        self.scroll_y = 0;
+        self.set_selected_row(None);
+        self.rows_changed();
+    }
+
+    /// Replaces the rows of the table with rows of cells that are kept in memory.  See
+    /// `set_model`.
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.set_model(Box::new(rows));
+    }
+
+    /// Retrieves the model of the table.
+    pub fn get_model(&self) -> &dyn TableModel {
+        self.model.as_ref()
+    }
+
+    /// Reads the number of rows from the model again, and sorts the rows again, after rows have
+    /// been added to or removed from the model.  A selected row that no longer exists is
+    /// deselected.  Invalidates the widget afterward.
+    pub fn rows_changed(&mut self) {
+        self.row_count = self.model.get_row_count();
+        self.hovered_row = None;
+        self.sort_rows();
+
+        if let Some(row) = self.selected_row {
+            if row >= self.row_count {
+                self.set_selected_row(None);
+            }
+        }
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Changes the text of the cell at `row` and `column` of the model, if the model allows it,
+    /// and redraws only that cell, unless the rows are sorted in a different order because of
+    /// the change.
+    pub fn set_cell(&mut self, row: usize, column: usize, text: &str) {
+        if self.model.set_cell(row, column, text) {
+            self.invalidate_cell(row, column);
+        }
+    }
+
+    /// Redraws the cell at `row` and `column` of the model, after it has been changed in the
+    /// model.  If the rows are sorted by `column`, they are sorted again, and the whole table is
+    /// redrawn if their order changed.  Otherwise, only the area of the cell is invalidated, if
+    /// the cell can be seen.
+    pub fn invalidate_cell(&mut self, row: usize, column: usize) {
+        if let Some((sort_column, _)) = self.sort {
+            if sort_column == column {
+                let previous_order = self.row_order.clone();
+
+                self.sort_rows();
+
+                if self.row_order != previous_order {
+                    self.invalidate();
+                    return;
+                }
+            }
+        }
+
+        if let Some(area) = self.get_cell_area(row, column) {
+            self.invalidate_area(area);
+        }
+    }
+
+    /// Sorts the rows by a column, in the given order, or shows them in the order of the model
+    /// if `sort` is `None`.  The same row stays selected.  Invalidates the widget afterward.
+    pub fn set_sort(&mut self, sort: Option<(usize, SortOrder)>) {
+        self.sort = sort.filter(|(column, _)| *column < self.columns.len());
+        self.sort_rows();
+        self.invalidate();
+    }
+
+    /// Retrieves the column that the rows are sorted by, and the order, or `None` if the rows are
+    /// shown in the order of the model.
+    pub fn get_sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Sets the height of the header and of each row, in pixels.  Invalidates the widget
+    /// afterward.
+    pub fn set_row_height(&mut self, row_height: i32) {
+        self.row_height = row_height.max(1);
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Retrieves the height of the header and of each row, in pixels.  Defaults to
+    /// `DEFAULT_TABLE_ROW_HEIGHT`.
+    pub fn get_row_height(&self) -> i32 {
+        self.row_height
+    }
+
+    /// Selects `row` of the model, or clears the selection if `row` is `None`, and scrolls the
+    /// row into view.  A row past the end of the model clears the selection.  The
+    /// `on_selection_changed` callback is called if the selection changed.
+    pub fn set_selected_row(&mut self, row: Option<usize>) {
+        let row = row.filter(|row| *row < self.row_count);
+
+        if let Some(row) = row {
+            self.ensure_row_visible(row);
+        }
+
+        if row == self.selected_row {
+            return;
+        }
+
+        self.selected_row = row;
+
+        if let Some(callback) = &mut self.on_selection_changed {
+            callback(row);
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the selected row of the model, or `None` if no row is selected.
+    pub fn get_selected_row(&self) -> Option<usize> {
+        self.selected_row
+    }
+
+    /// Scrolls the table as little as possible to bring `row` of the model into view.
+    pub fn ensure_row_visible(&mut self, row: usize) {
+        let top = self.get_view_row(row) as i32 * self.row_height;
+        let view_h = self.get_body_height();
+        let scroll_y = if top < self.scroll_y {
+            top
+        } else if top + self.row_height > self.scroll_y + view_h {
+            top + self.row_height - view_h
+        } else {
+            self.scroll_y
+        };
+
+        self.scroll_to(0, scroll_y);
+    }
+
+    /// Returns the row of the model that is shown at the height `y` of the widget, taking the
+    /// sorting and scrolling of the rows into account, or `None` if there is no row there.
+    pub fn get_row_at(&self, y: i32) -> Option<usize> {
+        if y < self.row_height {
+            return None;
+        }
+
+        let view_row = ((y - self.row_height + self.scroll_y) / self.row_height) as usize;
+
+        if view_row < self.row_count {
+            Some(self.get_model_row(view_row))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the column at the position `x` of the widget, or `None` if there is no column
+    /// there.
+    pub fn get_column_at(&mut self, x: i32) -> Option<usize> {
+        self.get_column_bounds()
+            .iter()
+            .position(|(column_x, column_w)| x >= *column_x && x < column_x + column_w)
+    }
+
+    /// Returns the left edge and the width of each column.
+    fn get_column_bounds(&mut self) -> Vec<(i32, i32)> {
+        let mut x = 0;
+
+        self.get_column_widths()
+            .into_iter()
+            .map(|width| {
+                x += width;
+                (x - width, width)
+            })
+            .collect()
+    }
+
+    /// Returns the height of the area below the header in which the rows are shown.
+    fn get_body_height(&mut self) -> i32 {
+        (self.get_size().h - self.row_height).max(0)
+    }
+
+    /// Returns the row of the model that is shown at `view_row`.
+    fn get_model_row(&self, view_row: usize) -> usize {
+        self.row_order.get(view_row).copied().unwrap_or(view_row)
+    }
+
+    /// Returns the position at which `row` of the model is shown.
+    fn get_view_row(&self, row: usize) -> usize {
+        if self.row_order.is_empty() {
+            row
+        } else {
+            self.row_order
+                .iter()
+                .position(|model_row| *model_row == row)
+                .unwrap_or(row)
+        }
+    }
+
+    /// Returns the area of the cell at `row` and `column` of the model, in the coordinates of
+    /// the widget, or `None` if the cell cannot be seen.
+    fn get_cell_area(&mut self, row: usize, column: usize) -> Option<[i32; 4]> {
+        let (x, w) = *self.get_column_bounds().get(column)?;
+        let y = self.row_height + self.get_view_row(row) as i32 * self.row_height - self.scroll_y;
+        let top = y.max(self.row_height);
+        let bottom = (y + self.row_height).min(self.get_size().h);
+
+        if row < self.row_count && w > 0 && bottom > top {
+            Some([x, top, w, bottom - top])
+        } else {
+            None
+        }
+    }
+
+    /// Sorts the rows by the sort column, keeping rows with equal cells in the order of the
+    /// model, or clears the order if the rows are not sorted.
+    fn sort_rows(&mut self) {
+        let (column, order) = match self.sort {
+            Some(sort) => sort,
+            None => {
+                self.row_order.clear();
+                return;
+            }
+        };
+        let model = &self.model;
+        let mut row_order: Vec<usize> = (0..self.row_count).collect();
+
+        row_order.sort_by(|a, b| {
+            let ordering = model.compare_rows(*a, *b, column);
+
+            match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            }
+        });
+
+        self.row_order = row_order;
+    }
+
+    /// Returns the column whose right divider is at `point` in the header, if any.
+    fn get_divider_at(&mut self, point: &Point) -> Option<usize> {
+        if point.y < 0 || point.y >= self.row_height {
+            return None;
+        }
+
+        self.get_column_bounds()
+            .iter()
+            .rposition(|(x, w)| (point.x - (x + w)).abs() <= TABLE_DIVIDER_GRAB)
+    }
+
+    /// Sorts the rows by `column` in ascending order, or reverses the order if they are already
+    /// sorted by it.
+    fn toggle_sort(&mut self, column: usize) {
+        let order = match self.sort {
+            Some((sort_column, SortOrder::Ascending)) if sort_column == column => {
+                SortOrder::Descending
+            }
+            _ => SortOrder::Ascending,
+        };
+
+        self.set_sort(Some((column, order)));
+    }
+
+    /// Moves the selection by `rows` in the order in which the rows are shown, selecting the
+    /// first row that is shown if none was selected.
+    fn move_selection(&mut self, rows: i32) {
+        if self.row_count == 0 {
+            return;
+        }
+
+        let view_row = match self.selected_row {
+            Some(row) => (self.get_view_row(row) as i32 + rows).clamp(0, self.row_count as i32 - 1),
+            None => 0,
+        } as usize;
+        let row = self.get_model_row(view_row);
+
+        self.set_selected_row(Some(row));
+    }
+
+    fn activate_row(&mut self, row: usize) {
+        if let Some(callback) = &mut self.on_row_activated {
+            callback(row);
+        }
+    }
+
+    fn set_hovered(&mut self, row: Option<usize>, divider: Option<usize>) {
+        if row != self.hovered_row || divider != self.hovered_divider {
+            self.hovered_row = row;
+            self.hovered_divider = divider;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `TableWidget` object with the `Widget` traits implemented.  The titles
+/// and cells are drawn with a single `TextWidget`, which is given the text of each visible cell
+/// in turn.
+impl Widget for TableWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Table
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Scrolls the rows so that the height `y` of the rows is at the top of the area below the
+    /// header, limited to the rows of the table.  The horizontal position is ignored.
+    /// Invalidates the widget if the table scrolled.
+    fn scroll_to(&mut self, _x: i32, y: i32) {
+        let max_y = (self.row_count as i32 * self.row_height - self.get_body_height()).max(0);
+        let y = y.clamp(0, max_y);
+
+        if y != self.scroll_y {
+            self.scroll_y = y;
+            self.invalidate();
+        }
+    }
+
+    /// Enables or disables this widget.  A disabled table is drawn in the disabled color of the
+    /// current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Retrieves the resize cursor while the mouse is over a divider between two column titles,
+    /// or while one is being dragged, and the cursor of the widget otherwise.
+    fn get_cursor(&mut self) -> CursorStyle {
+        if self.hovered_divider.is_some() || self.resizing.is_some() {
+            return CursorStyle::ResizeHorizontal;
+        }
+
+        self.config()
+            .get::<Cursor>()
+            .map(|cursor| cursor.0)
+            .unwrap_or(CursorStyle::Default)
+    }
+
+    /// Scrolls the rows in response to the mouse wheel, passing the scroll on to the widget
+    /// underneath if the table cannot be scrolled any further in that direction.  All other
+    /// events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let previous_y = self.scroll_y;
+                let scroll_y = self.scroll_y - delta.to_points().1.round() as i32;
+
+                self.scroll_to(0, scroll_y);
+
+                if self.scroll_y == previous_y {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                if let Some((column, offset)) = self.resizing {
+                    let (column_x, column_w) = self.get_column_bounds()[column];
+                    let width = (point.x - offset - column_x).max(MIN_TABLE_COLUMN_WIDTH);
+
+                    if width != column_w {
+                        self.columns[column].width = TableColumnWidth::Fixed(width);
+                        self.resized = true;
+                        self.invalidate();
+                    }
+                } else {
+                    let row = self.get_row_at(point.y).map(|row| self.get_view_row(row));
+                    let divider = self.get_divider_at(&point);
+
+                    self.set_hovered(row, divider);
+                }
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered(None, None),
+
+            CallbackEvent::MouseButtonDown {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } => {
+                self.resized = false;
+
+                if let Some(column) = self.get_divider_at(&point) {
+                    let (column_x, column_w) = self.get_column_bounds()[column];
+
+                    self.resizing = Some((column, point.x - (column_x + column_w)));
+                    self.invalidate();
+                }
+            }
+
+            CallbackEvent::MouseButtonUpInside {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } if self.resizing.is_some() => {
+                self.resizing = None;
+
+                let divider = self.get_divider_at(&point);
+
+                self.set_hovered(None, divider);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonUpOutside {
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } if self.resizing.is_some() => {
+                self.resizing = None;
+                self.set_hovered(None, None);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseClicked {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                click_count,
+                ..
+            } => {
+                if point.y < self.row_height {
+                    // A click that ends a resize does not sort the rows.
+                    if !self.resized && self.get_divider_at(&point).is_none() {
+                        if let Some(column) = self.get_column_at(point.x) {
+                            self.toggle_sort(column);
+                        }
+                    }
+                } else if let Some(row) = self.get_row_at(point.y) {
+                    if click_count == 2 {
+                        self.activate_row(row);
+                    } else {
+                        self.set_selected_row(Some(row));
+                    }
+                }
+
+                self.resized = false;
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => match key {
+                Key::Up => self.move_selection(-1),
+                Key::Down => self.move_selection(1),
+                Key::Return => {
+                    if let Some(row) = self.selected_row {
+                        self.activate_row(row);
+                    }
+                }
+                _ => (),
+            },
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the background, the rows that can be seen, and then the header over them.  The
+    /// selected row is drawn in the accent color of the current `Theme`, and the hovered row in
+    /// the background color blended toward it.  The dividers between the titles are drawn in
+    /// the border color of the current `Theme`, or in its accent color while they are hovered
+    /// or dragged.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let background = self.get_color();
+        let mut hover_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * TABLE_HOVER_AMOUNT;
+        }
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let columns = self.get_column_bounds();
+        let row_height = self.row_height;
+        let first_row = (self.scroll_y / row_height) as usize;
+        let last_row = ((self.scroll_y + self.get_body_height()) / row_height + 1) as usize;
+        let text_color = self.get_text_color();
+        let disabled = self.is_disabled();
+
+        for view_row in first_row..last_row.min(self.row_count) {
+            let row = self.get_model_row(view_row);
+            let row_y = row_height + view_row as i32 * row_height - self.scroll_y;
+            let selected = self.selected_row == Some(row);
+            let row_color = if selected && !disabled {
+                Some(theme.accent_color)
+            } else if self.hovered_row == Some(view_row) && !disabled {
+                Some(hover_color)
+            } else {
+                None
+            };
+
+            if let Some(row_color) = row_color {
+                Rectangle::new(apply_opacity(row_color)).draw(
+                    [
+                        0.0,
+                        f64::from(row_y),
+                        f64::from(size.w),
+                        f64::from(row_height),
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            self.text_widget.set_text_color(if selected {
+                theme.background_color
+            } else {
+                text_color
+            });
+
+            for (column, (column_x, column_w)) in columns.iter().enumerate() {
+                if *column_w <= 0 {
+                    continue;
+                }
+
+                let cell = self.model.get_cell(row, column);
+
+                self.text_widget.set_justify(self.columns[column].justify);
+                self.text_widget.set_size(*column_w, row_height);
+                self.text_widget.set_text(&cell);
+                self.text_widget
+                    .draw(c.trans(f64::from(*column_x), f64::from(row_y)), g, clip);
+            }
+        }
+
+        // The header is drawn over any row that is scrolled partly underneath it.
+        let header_h = f64::from(row_height);
+        let border = Rectangle::new(apply_opacity(theme.border_color));
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), header_h],
+            clip,
+            c.transform,
+            g,
+        );
+        border.draw(
+            [0.0, header_h - 1.0, f64::from(size.w), 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+        self.text_widget.set_text_color(text_color);
+
+        let active_divider = self
+            .resizing
+            .map(|(column, _)| column)
+            .or(self.hovered_divider);
+
+        for (column, (column_x, column_w)) in columns.iter().enumerate() {
+            let right = f64::from(column_x + column_w);
+            let sorted = self.sort.filter(|(sort_column, _)| *sort_column == column);
+            let indicator_w = if sorted.is_some() {
+                TABLE_SORT_INDICATOR_SPACE
+            } else {
+                0
+            };
+
+            if active_divider == Some(column) && !disabled {
+                Rectangle::new(apply_opacity(theme.accent_color)).draw(
+                    [right - 1.0, 0.0, 2.0, header_h],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            } else {
+                border.draw(
+                    [right - 1.0, 4.0, 1.0, header_h - 8.0],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            if let Some((_, order)) = sorted {
+                let (arrow_x, arrow_y) = (right - 10.0, header_h / 2.0);
+                let points = match order {
+                    SortOrder::Ascending => [
+                        [arrow_x - 4.0, arrow_y + 2.0],
+                        [arrow_x + 4.0, arrow_y + 2.0],
+                        [arrow_x, arrow_y - 2.0],
+                    ],
+                    SortOrder::Descending => [
+                        [arrow_x - 4.0, arrow_y - 2.0],
+                        [arrow_x + 4.0, arrow_y - 2.0],
+                        [arrow_x, arrow_y + 2.0],
+                    ],
+                };
+
+                Polygon::new(apply_opacity(text_color)).draw(&points, clip, c.transform, g);
+            }
+
+            let title = self.columns[column].title.clone();
+
+            self.text_widget.set_justify(self.columns[column].justify);
+            self.text_widget
+                .set_size((column_w - indicator_w).max(0), row_height);
+            self.text_widget.set_text(&title);
+            self.text_widget
+                .draw(c.trans(f64::from(*column_x), 0.0), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,422 @@
+// Text Cache
+// Keeps each line of text that has been drawn as a texture, so that it is not drawn again
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::{Rc, Weak};
+
+use piston_window::math::Matrix2d;
+use piston_window::texture::{CreateTexture, Format};
+use piston_window::*;
+use rusttype::{point, GlyphId, PositionedGlyph, Scale};
+
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::widget::font_cache::*;
+
+/// The number of bytes of texture memory that the text cache keeps, unless another budget is set
+/// with `set_text_cache_budget`.
+pub const DEFAULT_TEXT_CACHE_BYTES: usize = 16 * 1024 * 1024;
+
+/// The widest or tallest texture, in pixels, that a line of text is drawn into.  Longer lines are
+/// drawn a glyph at a time, as they would be without the cache.
+const MAX_TEXT_TEXTURE_SIZE: i32 = 4096;
+
+/// Identifies a line of text that has been drawn into a texture: the font that it was drawn in,
+/// the text itself, and the size of the font in device pixels.  The scale of the display is part
+/// of the font size, so text that is moved to a display of another scale is drawn again.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TextKey {
+    /// The address of the font's `SharedGlyphs`, which is the same for every `Widget` that
+    /// shares the font.
+    pub font: usize,
+
+    /// The line of text.
+    pub text: String,
+
+    /// The size of the font in device pixels.
+    pub font_size: u32,
+}
+
+/// Implementation of the `TextKey`.
+impl TextKey {
+    /// Creates the key for `text`, drawn in `font` at `font_size` device pixels.
+    pub fn new(font: &SharedGlyphs, text: &str, font_size: u32) -> Self {
+        Self {
+            font: Rc::as_ptr(font) as usize,
+            text: text.to_string(),
+            font_size,
+        }
+    }
+}
+
+/// An entry in a `TextCache`, with the number of bytes that it takes, and when it was last used.
+struct TextEntry<T> {
+    value: T,
+    bytes: usize,
+    last_used: u64,
+}
+
+/// This is the `TextCache`, which keeps values by `TextKey` up to a budget of bytes, evicting the
+/// values that were used the longest time ago when the budget is exceeded.  Pushrod keeps one
+/// `TextCache` of textures, which `TextWidget`s draw their text from, so every `Widget` that
+/// shows the same text, in the same font and size, shares the same texture, and text that does
+/// not change is only rasterized once.
+///
+/// The color of the text is not part of the key, as each texture only holds the coverage of the
+/// glyphs, and is tinted when it is drawn: text that changes color, or fades, is not rasterized
+/// again.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::text_cache::*;
+/// # fn main() {
+///    let key = |text: &str| TextKey {
+///        font: 1,
+///        text: text.to_string(),
+///        font_size: 14,
+///    };
+///    let mut cache = TextCache::with_budget(1000);
+///
+///    assert!(cache.insert(key("Hello"), "hello texture", 400));
+///    assert!(cache.insert(key("World"), "world texture", 400));
+///    assert_eq!(cache.get_total_bytes(), 800);
+///
+///    // Using "Hello" makes "World" the least recently used, so it is evicted first.
+///    assert_eq!(cache.get(&key("Hello")), Some(&"hello texture"));
+///    assert!(cache.insert(key("Again"), "again texture", 400));
+///    assert_eq!(cache.get(&key("World")), None);
+///    assert_eq!(cache.len(), 2);
+///
+///    // The same text at another size is another entry.
+///    let mut larger = key("Hello");
+///
+///    larger.font_size = 28;
+///    assert_eq!(cache.get(&larger), None);
+///
+///    // A value that is larger than the whole budget is not kept.
+///    assert!(!cache.insert(key("Huge"), "huge texture", 2000));
+///    assert_eq!(cache.get_total_bytes(), 800);
+///
+///    cache.set_budget(500);
+///    assert_eq!(cache.len(), 1);
+///    assert_eq!(cache.get(&key("Again")), Some(&"again texture"));
+/// # }
+/// ```
+pub struct TextCache<T> {
+    entries: HashMap<TextKey, TextEntry<T>>,
+    recent: BTreeMap<u64, TextKey>,
+    budget: usize,
+    total_bytes: usize,
+    clock: u64,
+}
+
+/// Implementation of the `TextCache`.
+impl<T> TextCache<T> {
+    /// Creates a new, empty `TextCache` with a budget of `DEFAULT_TEXT_CACHE_BYTES`.
+    pub fn new() -> Self {
+        Self::with_budget(DEFAULT_TEXT_CACHE_BYTES)
+    }
+
+    /// Creates a new, empty `TextCache` that keeps up to `budget` bytes.
+    pub fn with_budget(budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recent: BTreeMap::new(),
+            budget,
+            total_bytes: 0,
+            clock: 0,
+        }
+    }
+
+    /// Retrieves the value for `key`, marking it as the most recently used.
+    pub fn get(&mut self, key: &TextKey) -> Option<&T> {
+        let entry = self.entries.get_mut(key)?;
+
+        self.clock += 1;
+        self.recent.remove(&entry.last_used);
+        self.recent.insert(self.clock, key.clone());
+        entry.last_used = self.clock;
+
+        Some(&entry.value)
+    }
+
+    /// Stores `value`, which takes `bytes` bytes, for `key`, replacing any value that it had, and
+    /// evicts the least recently used values until the cache is within its budget.  Returns
+    /// `false` if the value is larger than the whole budget, in which case it is not kept.
+    pub fn insert(&mut self, key: TextKey, value: T, bytes: usize) -> bool {
+        self.remove(&key);
+
+        if bytes > self.budget {
+            return false;
+        }
+
+        self.clock += 1;
+        self.total_bytes += bytes;
+        self.recent.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            TextEntry {
+                value,
+                bytes,
+                last_used: self.clock,
+            },
+        );
+        self.evict();
+
+        true
+    }
+
+    /// Removes the value for `key`, returning it, or `None` if it was not kept.
+    pub fn remove(&mut self, key: &TextKey) -> Option<T> {
+        let entry = self.entries.remove(key)?;
+
+        self.recent.remove(&entry.last_used);
+        self.total_bytes -= entry.bytes;
+
+        Some(entry.value)
+    }
+
+    /// Sets the number of bytes that the cache keeps, evicting the least recently used values
+    /// until it is within the new budget.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict();
+    }
+
+    /// Retrieves the number of bytes that the cache keeps.
+    pub fn get_budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Retrieves the number of bytes that the values in the cache take.
+    pub fn get_total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Returns the number of values in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether the cache has no values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all of the values.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recent.clear();
+        self.total_bytes = 0;
+    }
+
+    fn evict(&mut self) {
+        while self.total_bytes > self.budget {
+            let key = match self.recent.values().next() {
+                Some(key) => key.clone(),
+                None => break,
+            };
+
+            self.remove(&key);
+        }
+    }
+}
+
+impl<T> Default for TextCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A line of text that has been drawn into a texture, white, with the coverage of the glyphs in
+/// its alpha channel, as the glyph cache draws each glyph.  `offset` is where the top left of the
+/// texture is drawn, in device pixels, from the start of the baseline of the text.
+struct TextTexture {
+    texture: G2dTexture,
+    offset: [f64; 2],
+    font: Weak<RefCell<Glyphs>>,
+}
+
+thread_local! {
+    static TEXT_CACHE: RefCell<TextCache<TextTexture>> = RefCell::new(TextCache::new());
+}
+
+/// Draws a line of `text` in `font`, at `font_size` device pixels, in `color`, with `transform`
+/// placing the start of its baseline, as `Text::draw` does.  The line is drawn from the text
+/// cache, and is only rasterized the first time that it is drawn, or once it has been evicted.
+/// Lines that cannot be kept in a texture are drawn a glyph at a time instead.  `font` must not
+/// be borrowed when this is called.
+pub fn draw_cached_text(
+    font: &SharedGlyphs,
+    text: &str,
+    font_size: u32,
+    color: types::Color,
+    clip: &DrawState,
+    transform: Matrix2d,
+    g: &mut DrawTarget,
+) {
+    if text.chars().all(char::is_whitespace) {
+        return;
+    }
+
+    let key = TextKey::new(font, text, font_size);
+
+    TEXT_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let cached = match cache.get(&key) {
+            Some(cached) => cached.font.upgrade().is_some_and(|f| Rc::ptr_eq(&f, font)),
+            None => false,
+        };
+
+        if !cached {
+            let kept = match rasterize_text(font, text, font_size) {
+                Some((texture, bytes)) => cache.insert(key.clone(), texture, bytes),
+                None => false,
+            };
+
+            if !kept {
+                cache.remove(&key);
+
+                if let Err(error) = Text::new_color(color, font_size).draw(
+                    text,
+                    &mut *font.borrow_mut(),
+                    clip,
+                    transform,
+                    g,
+                ) {
+                    report_error(PushrodError::Rendering(format!(
+                        "cannot draw text: {:?}",
+                        error
+                    )));
+                }
+
+                return;
+            }
+        }
+
+        let cached = cache.get(&key).unwrap();
+
+        Image::new_color(color).draw(
+            &cached.texture,
+            clip,
+            transform.trans(cached.offset[0], cached.offset[1]),
+            g,
+        );
+    });
+}
+
+/// Sets the number of bytes of texture memory that the text cache keeps, evicting the least
+/// recently drawn text until it is within the new budget.  A budget of 0 turns the cache off.
+pub fn set_text_cache_budget(bytes: usize) {
+    TEXT_CACHE.with(|cache| cache.borrow_mut().set_budget(bytes));
+}
+
+/// Retrieves the number of bytes of texture memory that the text cache keeps.  Defaults to
+/// `DEFAULT_TEXT_CACHE_BYTES`.
+pub fn get_text_cache_budget() -> usize {
+    TEXT_CACHE.with(|cache| cache.borrow().get_budget())
+}
+
+/// Retrieves the number of bytes of texture memory that the text cache is using.
+pub fn get_text_cache_bytes() -> usize {
+    TEXT_CACHE.with(|cache| cache.borrow().get_total_bytes())
+}
+
+/// Frees all of the textures in the text cache.
+pub fn clear_text_cache() {
+    TEXT_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Rasterizes a line of `text` into a new texture, returning it with the number of bytes that it
+/// takes, or `None` if the line has no visible glyphs, is too large, or the texture cannot be
+/// created.  Glyphs are laid out and scaled as the glyph cache lays them out.
+fn rasterize_text(font: &SharedGlyphs, text: &str, font_size: u32) -> Option<(TextTexture, usize)> {
+    let mut glyphs = font.borrow_mut();
+    let scale = Scale::uniform((font_size as f32 * 1.333).round());
+    let mut x = 0.0;
+    let positioned: Vec<PositionedGlyph> = text
+        .chars()
+        .map(|ch| {
+            let mut glyph = glyphs.font.glyph(ch).scaled(scale);
+
+            if glyph.id() == GlyphId(0) && glyph.shape().is_none() {
+                glyph = glyphs.font.glyph('\u{FFFD}').scaled(scale);
+            }
+
+            let advance = glyph.h_metrics().advance_width;
+            let glyph = glyph.positioned(point(x, 0.0));
+
+            x += advance;
+            glyph
+        })
+        .collect();
+
+    let (min, max) = positioned
+        .iter()
+        .filter_map(|glyph| glyph.pixel_bounding_box())
+        .fold(None, |bounds: Option<([i32; 2], [i32; 2])>, rect| {
+            Some(match bounds {
+                Some((min, max)) => (
+                    [min[0].min(rect.min.x), min[1].min(rect.min.y)],
+                    [max[0].max(rect.max.x), max[1].max(rect.max.y)],
+                ),
+                None => ([rect.min.x, rect.min.y], [rect.max.x, rect.max.y]),
+            })
+        })?;
+
+    // As in the glyph cache, each texture has a transparent border of a pixel, so that the edges of
+    // the glyphs are not cut off when they are filtered.
+    let width = max[0] - min[0] + 2;
+    let height = max[1] - min[1] + 2;
+
+    if width > MAX_TEXT_TEXTURE_SIZE || height > MAX_TEXT_TEXTURE_SIZE {
+        return None;
+    }
+
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+    for glyph in &positioned {
+        if let Some(rect) = glyph.pixel_bounding_box() {
+            glyph.draw(|gx, gy, coverage| {
+                let px = rect.min.x - min[0] + 1 + gx as i32;
+                let py = rect.min.y - min[1] + 1 + gy as i32;
+                let index = ((py * width + px) * 4) as usize;
+                let alpha = (255.0 * coverage) as u8;
+
+                pixels[index..index + 3].copy_from_slice(&[255, 255, 255]);
+                pixels[index + 3] = pixels[index + 3].max(alpha);
+            });
+        }
+    }
+
+    let texture = G2dTexture::create(
+        &mut glyphs.factory,
+        Format::Rgba8,
+        &pixels,
+        [width as u32, height as u32],
+        &TextureSettings::new(),
+    )
+    .ok()?;
+
+    Some((
+        TextTexture {
+            texture,
+            offset: [f64::from(min[0] - 1), f64::from(min[1] - 1)],
+            font: Rc::downgrade(font),
+        },
+        pixels.len(),
+    ))
+}
@@ -0,0 +1,920 @@
+// Text Input Widget
+// Extensible widget for the widget library - handles a single line of editable text.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::clipboard::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
+use crate::core::error::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::grapheme::*;
+use crate::widget::shape::{stroke_border, BorderStyle};
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::timer_widget::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the text of a `TextInputWidget`.
+pub type TextCallback = Box<dyn FnMut(&str)>;
+
+/// The number of milliseconds that the caret of a `TextInputWidget` is shown, and then hidden,
+/// while it blinks.
+pub const CARET_BLINK_MS: u64 = 500;
+
+/// The number of pixels between the border of a `TextInputWidget` and its text.
+const TEXT_INPUT_PADDING: i32 = 4;
+
+/// This is the `LineEditor`, which edits a single line of text: it keeps the text, the caret,
+/// which is the index of the character that it is in front of, and the anchor of the selection,
+/// which is the other end of the selected text.  Typed text replaces the selection.
+///
+/// Characters are grapheme clusters, so the caret never moves into the middle of a character
+/// that is made of several `char`s, such as an accented letter or an emoji, and `backspace`
+/// and `delete` remove the whole character; see `get_grapheme_boundaries`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::text_input_widget::*;
+/// # fn main() {
+///    let mut editor = LineEditor::new();
+///
+///    editor.insert("Hello world");
+///    editor.move_home(false);
+///    editor.move_right(false);
+///    editor.move_right(true);
+///    assert_eq!(editor.get_selected_text(), "e");
+///
+///    editor.insert("a");
+///    assert_eq!(editor.get_text(), "Hallo world");
+///
+///    editor.move_end(false);
+///    editor.backspace();
+///    assert_eq!(editor.get_text(), "Hallo worl");
+///    assert_eq!(editor.get_caret(), 10);
+///
+///    // An accented letter of two `char`s, and an emoji of three, are one character each.
+///    editor.set_text("Cafe\u{301} \u{1f44d}\u{1f3fd}!");
+///    assert_eq!(editor.get_length(), 7);
+///
+///    editor.move_left(false);
+///    editor.backspace();
+///    assert_eq!(editor.get_text(), "Cafe\u{301} !");
+///
+///    editor.move_home(false);
+///    editor.move_right(false);
+///    editor.move_right(false);
+///    editor.move_right(false);
+///    editor.move_right(true);
+///    assert_eq!(editor.get_selected_text(), "e\u{301}");
+///
+///    editor.insert("\u{e9}s");
+///    assert_eq!(editor.get_text(), "Caf\u{e9}s !");
+///    assert_eq!(editor.get_caret(), 5);
+///
+///    // A combining mark that is typed on its own joins the character in front of the caret.
+///    editor.set_text("\u{65e5}\u{672c}a");
+///    editor.insert("\u{308}");
+///    assert_eq!(editor.get_length(), 3);
+///    assert_eq!(editor.get_caret(), 3);
+///
+///    editor.delete();
+///    editor.move_left(false);
+///    editor.delete();
+///    assert_eq!(editor.get_text(), "\u{65e5}\u{672c}");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineEditor {
+    text: String,
+    caret: usize,
+    anchor: Option<usize>,
+}
+
+/// Implementation of the `LineEditor`.
+impl LineEditor {
+    /// Creates a new editor with no text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces all of the text, moving the caret to the end, and deselecting everything.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.caret = self.get_length();
+        self.anchor = None;
+    }
+
+    /// Retrieves the text.
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Retrieves the number of characters in the text.
+    pub fn get_length(&self) -> usize {
+        get_grapheme_count(&self.text)
+    }
+
+    /// Retrieves the index of the character that the caret is in front of.
+    pub fn get_caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Moves the caret in front of the character at `index`, or to the end of the text if
+    /// `index` is past it.  If `extend` is `true`, the selection is extended to the caret, and
+    /// otherwise everything is deselected.
+    pub fn set_caret(&mut self, index: usize, extend: bool) {
+        if extend {
+            self.anchor = self.anchor.or(Some(self.caret));
+        } else {
+            self.anchor = None;
+        }
+
+        self.caret = index.min(self.get_length());
+    }
+
+    /// Returns the range of characters that are selected, from the first to just past the last,
+    /// or `None` if nothing is selected.
+    pub fn get_selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor != self.caret => {
+                Some((anchor.min(self.caret), anchor.max(self.caret)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the range of bytes of the text that are selected, which start and end on the
+    /// boundaries of characters, or `None` if nothing is selected.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::text_input_widget::*;
+    /// # fn main() {
+    ///    let mut editor = LineEditor::new();
+    ///
+    ///    // An accented letter with two combining marks, the family emoji, and an "s".
+    ///    editor.set_text("e\u{301}\u{323}\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}s");
+    ///    assert_eq!(editor.get_length(), 3);
+    ///    assert_eq!(editor.get_selection_bytes(), None);
+    ///
+    ///    editor.move_left(true);
+    ///    assert_eq!(editor.get_selection_bytes(), Some((23, 24)));
+    ///
+    ///    editor.move_left(true);
+    ///    assert_eq!(editor.get_selection_bytes(), Some((5, 24)));
+    ///
+    ///    editor.move_home(false);
+    ///    editor.move_right(true);
+    ///    assert_eq!(editor.get_selection_bytes(), Some((0, 5)));
+    ///    assert_eq!(editor.get_selected_text(), "e\u{301}\u{323}");
+    /// # }
+    /// ```
+    pub fn get_selection_bytes(&self) -> Option<(usize, usize)> {
+        self.get_selection()
+            .map(|(start, end)| (self.byte_index(start), self.byte_index(end)))
+    }
+
+    /// Retrieves the text that is selected, which is empty if nothing is selected.
+    pub fn get_selected_text(&self) -> &str {
+        match self.get_selection_bytes() {
+            Some((start_byte, end_byte)) => &self.text[start_byte..end_byte],
+            None => "",
+        }
+    }
+
+    /// Selects all of the text, with the caret at the end.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.get_length();
+    }
+
+    /// Inserts `text` at the caret, replacing the selection, and moves the caret past it.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+
+        let index = self.byte_index(self.caret) + text.len();
+
+        self.text.insert_str(index - text.len(), text);
+
+        // Inserted text can join the character in front of it, so the caret is placed after the
+        // character that it ends in.
+        self.caret = get_grapheme_boundaries(&self.text)
+            .iter()
+            .position(|boundary| *boundary >= index)
+            .unwrap_or(0);
+    }
+
+    /// Deletes the selection, or the character in front of the caret if nothing is selected.
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() && self.caret > 0 {
+            self.caret -= 1;
+            self.remove_char(self.caret);
+        }
+    }
+
+    /// Deletes the selection, or the character behind the caret if nothing is selected.
+    pub fn delete(&mut self) {
+        if !self.delete_selection() && self.caret < self.get_length() {
+            self.remove_char(self.caret);
+        }
+    }
+
+    /// Moves the caret one character to the left, extending the selection if `extend` is
+    /// `true`.  Without `extend`, a selection is deselected, leaving the caret at its start.
+    pub fn move_left(&mut self, extend: bool) {
+        match self.get_selection() {
+            Some((start, _)) if !extend => self.set_caret(start, false),
+            _ => self.set_caret(self.caret.saturating_sub(1), extend),
+        }
+    }
+
+    /// Moves the caret one character to the right, extending the selection if `extend` is
+    /// `true`.  Without `extend`, a selection is deselected, leaving the caret at its end.
+    pub fn move_right(&mut self, extend: bool) {
+        match self.get_selection() {
+            Some((_, end)) if !extend => self.set_caret(end, false),
+            _ => self.set_caret(self.caret + 1, extend),
+        }
+    }
+
+    /// Moves the caret to the start of the text, extending the selection if `extend` is `true`.
+    pub fn move_home(&mut self, extend: bool) {
+        self.set_caret(0, extend);
+    }
+
+    /// Moves the caret to the end of the text, extending the selection if `extend` is `true`.
+    pub fn move_end(&mut self, extend: bool) {
+        self.set_caret(self.get_length(), extend);
+    }
+
+    /// Deletes the selection, leaving the caret where it started.  Returns whether anything
+    /// was selected.
+    fn delete_selection(&mut self) -> bool {
+        let (start, end) = match self.get_selection() {
+            Some(selection) => selection,
+            None => {
+                self.anchor = None;
+                return false;
+            }
+        };
+        let range = self.byte_index(start)..self.byte_index(end);
+
+        self.text.replace_range(range, "");
+        self.caret = start;
+        self.anchor = None;
+        true
+    }
+
+    fn remove_char(&mut self, index: usize) {
+        let range = self.byte_index(index)..self.byte_index(index + 1);
+
+        self.text.replace_range(range, "");
+    }
+
+    /// Returns the index in bytes of the character at `index`.
+    fn byte_index(&self, index: usize) -> usize {
+        get_grapheme_boundaries(&self.text)
+            .get(index)
+            .copied()
+            .unwrap_or(self.text.len())
+    }
+}
+
+/// Returns the index of the character boundary nearest to `x`, given the position of each
+/// boundary of a line of text, from the start of the first character to the end of the last.
+/// This is where the caret is placed when a line of text is clicked at `x`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::text_input_widget::*;
+/// # fn main() {
+///    let boundaries = [0, 10, 18, 30];
+///
+///    assert_eq!(get_nearest_boundary(&boundaries, -5), 0);
+///    assert_eq!(get_nearest_boundary(&boundaries, 13), 1);
+///    assert_eq!(get_nearest_boundary(&boundaries, 15), 2);
+///    assert_eq!(get_nearest_boundary(&boundaries, 100), 3);
+/// # }
+/// ```
+pub fn get_nearest_boundary(boundaries: &[i32], x: i32) -> usize {
+    boundaries
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, boundary)| (**boundary - x).abs())
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+/// Returns `text` without any control characters, such as line breaks and tabs, so that it can
+/// be inserted into a single line of text.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::text_input_widget::*;
+/// # fn main() {
+///    assert_eq!(filter_line("First line\r\nSecond\tline"), "First lineSecondline");
+/// # }
+/// ```
+pub fn filter_line(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// This is the `TextInputWidget`, which edits a single line of text.  While it is focused, a
+/// blinking caret shows where typed text is inserted.  `Backspace` and `Delete` delete text,
+/// `Home`, `End`, `Left`, and `Right` move the caret, and extend the selection while `Shift`
+/// is held down, and `Ctrl-A` selects all of the text.  `Ctrl-C` and `Ctrl-X` copy and cut the
+/// selection to the current `Clipboard`, and `Ctrl-V` pastes its text at the caret, without any
+/// line breaks; on macOS, `Cmd` is used instead of `Ctrl`.  Clicking the text moves the caret to
+/// the nearest character, and dragging selects text.  Selected text is drawn in the accent color
+/// of the current `Theme`, and is replaced by typed text.
+///
+/// Text that is wider than the widget scrolls horizontally, to keep the caret in view.  When
+/// there is no text, the placeholder is shown in the disabled color of the current `Theme`.
+///
+/// The `on_text_changed` callback is called with the new text every time the text changes, and
+/// `on_enter_pressed` with the text when `Enter` is pressed.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::text_input_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Text Input", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut text_input =
+///        TextInputWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///
+///    text_input.set_origin(20, 20);
+///    text_input.set_size(200, 28);
+///    text_input.set_placeholder("Search");
+///    text_input.on_text_changed(Box::new(|text| eprintln!("Text: {}", text)));
+///    text_input.on_enter_pressed(Box::new(|text| eprintln!("Search for: {}", text)));
+///    pushrod.add_widget("Search", Box::new(text_input));
+/// # }
+/// ```
+pub struct TextInputWidget {
+    config: Configurable,
+    font_cache: SharedGlyphs,
+    font_size: u32,
+    editor: LineEditor,
+    placeholder: String,
+    scroll_x: i32,
+    focused: bool,
+    selecting: bool,
+    caret_visible: bool,
+    blink_timer: TimerWidget,
+    blink_count: u32,
+    scale_factor: f64,
+    on_text_changed: Option<TextCallback>,
+    on_enter_pressed: Option<TextCallback>,
+}
+
+/// Implementation of the constructor for the `TextInputWidget`.
+impl TextInputWidget {
+    /// Creates a new empty text input, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  Panics if the font cannot be loaded;
+    /// use `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, Box::new(SystemClock))
+    }
+
+    /// Creates a new empty text input that draws its text in a font from the font cache, and
+    /// blinks its caret using the specified `Clock`.
+    pub fn from_font(font: SharedGlyphs, clock: Box<dyn Clock>) -> Self {
+        let mut blink_timer = TimerWidget::with_clock(clock);
+
+        blink_timer.set_timeout(CARET_BLINK_MS);
+
+        let mut widget = Self {
+            config: Configurable::new(),
+            font_cache: font,
+            font_size: get_current_theme().font_size,
+            editor: LineEditor::new(),
+            placeholder: String::new(),
+            scroll_x: 0,
+            focused: false,
+            selecting: false,
+            caret_visible: true,
+            blink_timer,
+            blink_count: 0,
+            scale_factor: 1.0,
+            on_text_changed: None,
+            on_enter_pressed: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_cursor(CursorStyle::Text);
+        widget
+    }
+
+    /// Sets the closure that is called with the new text every time the text changes.
+    pub fn on_text_changed(&mut self, callback: TextCallback) {
+        self.on_text_changed = Some(callback);
+    }
+
+    /// Sets the closure that is called with the text when `Enter` is pressed.
+    pub fn on_enter_pressed(&mut self, callback: TextCallback) {
+        self.on_enter_pressed = Some(callback);
+    }
+
+    /// Replaces the text, moving the caret to its end.  If the text changed, the
+    /// `on_text_changed` callback is called.
+    pub fn set_text(&mut self, text: &str) {
+        self.edit(|editor| editor.set_text(text));
+    }
+
+    /// Retrieves the text.
+    pub fn get_text(&self) -> &str {
+        self.editor.get_text()
+    }
+
+    /// Retrieves the text that is selected, which is empty if nothing is selected.
+    pub fn get_selected_text(&self) -> &str {
+        self.editor.get_selected_text()
+    }
+
+    /// Sets the text that is shown when there is no text.  Invalidates the widget afterward.
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = placeholder.to_string();
+        self.invalidate();
+    }
+
+    /// Retrieves the text that is shown when there is no text.  Defaults to no text.
+    pub fn get_placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Sets the font size of the text.  Invalidates the widget afterward.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.font_size = font_size;
+        self.keep_caret_visible();
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of the text.  Defaults to the font size of the current `Theme`.
+    pub fn get_font_size(&self) -> u32 {
+        self.font_size
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Indicates whether the caret is currently shown, which it is while the widget is focused,
+    /// and its blink has not hidden it.
+    pub fn is_caret_visible(&self) -> bool {
+        self.focused && self.caret_visible
+    }
+
+    /// Changes the text or the caret with `change`, calling the `on_text_changed` callback if
+    /// the text changed.  The caret is shown again, and scrolled into view.
+    fn edit<F: FnOnce(&mut LineEditor)>(&mut self, change: F) {
+        let previous = self.editor.clone();
+
+        change(&mut self.editor);
+
+        if self.editor.get_text() != previous.get_text() {
+            if let Some(callback) = &mut self.on_text_changed {
+                callback(self.editor.get_text());
+            }
+        }
+
+        if self.editor != previous {
+            self.restart_blink();
+            self.keep_caret_visible();
+            self.invalidate();
+        }
+    }
+
+    /// Shows the caret, and restarts its blink.
+    fn restart_blink(&mut self) {
+        self.caret_visible = true;
+        self.blink_timer.reset();
+        self.blink_count = 0;
+    }
+
+    /// Returns the position of each character boundary of the text, from the start of the text,
+    /// in pixels.
+    fn get_boundaries(&self) -> Vec<i32> {
+        let mut glyphs = self.font_cache.borrow_mut();
+        let text = self.editor.get_text();
+
+        get_grapheme_boundaries(text)
+            .into_iter()
+            .map(|byte_index| text_width(&mut glyphs, self.font_size, &text[..byte_index]))
+            .collect()
+    }
+
+    /// Returns the width of the content rectangle, in which the text is drawn.
+    fn get_view_width(&mut self) -> i32 {
+        let [_, _, w, _] = self.get_content_rect();
+
+        w
+    }
+
+    /// Scrolls the text horizontally, as little as possible, to keep the caret in view, and as
+    /// far to the left as the text allows.
+    fn keep_caret_visible(&mut self) {
+        let boundaries = self.get_boundaries();
+        let caret_x = boundaries[self.editor.get_caret()];
+        let text_w = boundaries.last().copied().unwrap_or(0);
+        let view_w = self.get_view_width();
+
+        if caret_x - self.scroll_x > view_w {
+            self.scroll_x = caret_x - view_w;
+        } else if caret_x < self.scroll_x {
+            self.scroll_x = caret_x;
+        }
+
+        self.scroll_x = self.scroll_x.min((text_w - view_w).max(0)).max(0);
+    }
+
+    /// Returns the index of the character boundary nearest to the point `x` of the widget.
+    fn get_caret_at(&mut self, x: i32) -> usize {
+        let [content_x, _, _, _] = self.get_content_rect();
+
+        get_nearest_boundary(&self.get_boundaries(), x - content_x + self.scroll_x)
+    }
+
+    /// Copies the selection to the current `Clipboard`.  Returns whether anything was selected.
+    fn copy_selection(&self) -> bool {
+        let selected = self.editor.get_selected_text();
+
+        if !selected.is_empty() {
+            set_clipboard_text(selected);
+        }
+
+        !selected.is_empty()
+    }
+
+    /// Edits the text in response to a key press.
+    fn press_key(&mut self, key: Key, modifiers: KeyModifiers) {
+        let shift = modifiers.shift;
+        let command = modifiers.is_command();
+
+        match key {
+            Key::Backspace => self.edit(LineEditor::backspace),
+            Key::Delete => self.edit(LineEditor::delete),
+            Key::Left => self.edit(|editor| editor.move_left(shift)),
+            Key::Right => self.edit(|editor| editor.move_right(shift)),
+            Key::Home => self.edit(|editor| editor.move_home(shift)),
+            Key::End => self.edit(|editor| editor.move_end(shift)),
+            Key::A if command => self.edit(LineEditor::select_all),
+            Key::C if command => {
+                self.copy_selection();
+            }
+            Key::X if command && self.copy_selection() => self.edit(|editor| editor.insert("")),
+            Key::V if command => {
+                let text = get_clipboard_text().map(|text| filter_line(&text));
+
+                if let Some(text) = text.filter(|text| !text.is_empty()) {
+                    self.edit(|editor| editor.insert(&text));
+                }
+            }
+            Key::Return => {
+                if let Some(callback) = &mut self.on_enter_pressed {
+                    callback(self.editor.get_text());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Draws `text` starting at the point `x` of the widget, on the baseline of the text.
+    fn draw_run(
+        &mut self,
+        text: &str,
+        x: i32,
+        color: types::Color,
+        c: Context,
+        g: &mut DrawTarget,
+        clip: &DrawState,
+    ) {
+        let [_, content_y, _, content_h] = self.get_content_rect();
+        let font_size = self.font_size as i32;
+        let baseline = content_y + (font_size - 2 + content_h) / 2 - 1;
+        let scale = self.scale_factor;
+        let device_font_size = (f64::from(self.font_size) * scale).round() as u32;
+        let mut glyphs = self.font_cache.borrow_mut();
+
+        let drawn = Text::new_color(apply_opacity(color), device_font_size).draw(
+            text,
+            &mut *glyphs,
+            clip,
+            c.transform
+                .trans(f64::from(x), f64::from(baseline))
+                .scale(1.0 / scale, 1.0 / scale),
+            g,
+        );
+
+        if let Err(error) = drawn {
+            report_error(PushrodError::Rendering(format!(
+                "cannot draw text: {:?}",
+                error
+            )));
+        }
+    }
+}
+
+/// Implementation of the `TextInputWidget` object with the `Widget` traits implemented.  This
+/// `Widget` responds to the mouse, the keyboard, and text input internally, and calls its
+/// callbacks when the text changes, or `Enter` is pressed.
+impl Widget for TextInputWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::TextInput
+    }
+
+    fn get_accessible_value(&mut self) -> Option<String> {
+        Some(self.get_text().to_string())
+    }
+
+    /// Sets the `Size` for this widget, given width and height, and scrolls the caret into view.
+    /// Invalidates the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.keep_caret_visible();
+        self.invalidate();
+    }
+
+    /// Retrieves the padding between the border and the text.  Defaults to 4 pixels on each
+    /// side, if not set.
+    fn get_padding(&mut self) -> Insets {
+        self.config()
+            .get_or(Padding(make_uniform_insets(TEXT_INPUT_PADDING)))
+            .0
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the height
+    /// of a line of text, plus the padding, and the width of twenty average characters, if not
+    /// set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => {
+                let padding = self.get_padding();
+                let line_height = (f64::from(self.font_size) * DEFAULT_LINE_SPACING) as i32;
+                let char_width = {
+                    let mut glyphs = self.font_cache.borrow_mut();
+
+                    text_width(&mut glyphs, self.font_size, "n")
+                };
+
+                crate::core::point::Size {
+                    w: char_width * 20 + padding.left + padding.right,
+                    h: line_height + padding.top + padding.bottom,
+                }
+            }
+        }
+    }
+
+    /// Blinks the caret while the widget is focused, on every update of the run loop.
+    fn update(&mut self, _delta_ms: u64) {
+        if !self.focused {
+            return;
+        }
+
+        self.blink_timer.tick();
+
+        let blink_count = self.blink_timer.get_fire_count();
+
+        if blink_count != self.blink_count {
+            self.blink_count = blink_count;
+            self.caret_visible = !self.caret_visible;
+            self.invalidate();
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let left_button = Button::Mouse(MouseButton::Left);
+
+        match event {
+            CallbackEvent::GotFocus { .. } => {
+                self.focused = true;
+                self.restart_blink();
+                self.invalidate();
+            }
+
+            CallbackEvent::LostFocus { .. } => {
+                self.focused = false;
+                self.selecting = false;
+                self.edit(|editor| {
+                    let caret = editor.get_caret();
+
+                    editor.set_caret(caret, false);
+                });
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonDown { button, point, .. } if button == left_button => {
+                let caret = self.get_caret_at(point.x);
+
+                self.selecting = true;
+                self.edit(|editor| editor.set_caret(caret, false));
+            }
+
+            CallbackEvent::MouseMoved { point, .. } if self.selecting => {
+                let caret = self.get_caret_at(point.x);
+
+                self.edit(|editor| editor.set_caret(caret, true));
+            }
+
+            CallbackEvent::MouseButtonUpInside { button, .. }
+            | CallbackEvent::MouseButtonUpOutside { button, .. }
+                if button == left_button =>
+            {
+                self.selecting = false;
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                modifiers,
+                ..
+            } => self.press_key(key, modifiers),
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Inserts typed text at the caret, replacing the selection, without any control characters.
+    fn text_input(&mut self, _widget_id: WidgetId, text: &str) {
+        let text = filter_line(text);
+
+        if !self.is_disabled() && !text.is_empty() {
+            self.edit(|editor| editor.insert(&text));
+        }
+    }
+
+    /// Rasterizes the text at the new scale factor from the next time it is drawn.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.invalidate();
+    }
+
+    /// Draws the background, the selection, the text or the placeholder, the caret, and then
+    /// the border, which is drawn in the accent color of the current `Theme` while the widget
+    /// is focused.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let [content_x, content_y, content_w, content_h] = self.get_content_rect();
+        let disabled = self.is_disabled();
+        let text_color = if disabled {
+            theme.disabled_color
+        } else {
+            self.get_text_color()
+        };
+        let text_x = content_x - self.scroll_x;
+        let background = Rectangle::new(apply_opacity(self.get_color()));
+
+        background.draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        if self.editor.get_text().is_empty() {
+            let placeholder = self.placeholder.clone();
+
+            self.draw_run(&placeholder, content_x, theme.disabled_color, c, g, clip);
+        } else {
+            let boundaries = self.get_boundaries();
+            let text = self.editor.get_text().to_string();
+            let selection = if self.focused {
+                self.editor
+                    .get_selection()
+                    .zip(self.editor.get_selection_bytes())
+            } else {
+                None
+            };
+
+            match selection {
+                Some(((start, end), (start_byte, end_byte))) => {
+                    let start_x = text_x + boundaries[start];
+                    let end_x = text_x + boundaries[end];
+
+                    Rectangle::new(apply_opacity(theme.accent_color)).draw(
+                        [
+                            f64::from(start_x),
+                            f64::from(content_y),
+                            f64::from(end_x - start_x),
+                            f64::from(content_h),
+                        ],
+                        clip,
+                        c.transform,
+                        g,
+                    );
+
+                    self.draw_run(&text[..start_byte], text_x, text_color, c, g, clip);
+                    self.draw_run(
+                        &text[start_byte..end_byte],
+                        start_x,
+                        theme.background_color,
+                        c,
+                        g,
+                        clip,
+                    );
+                    self.draw_run(&text[end_byte..], end_x, text_color, c, g, clip);
+                }
+                None => self.draw_run(&text, text_x, text_color, c, g, clip),
+            }
+        }
+
+        // Text that is scrolled past the content rectangle is covered up by the padding, so
+        // that it does not run into the border.
+        let right_x = content_x + content_w;
+
+        for [strip_x, strip_w] in &[[0, content_x], [right_x, size.w - right_x]] {
+            background.draw(
+                [
+                    f64::from(*strip_x),
+                    0.0,
+                    f64::from(*strip_w),
+                    f64::from(size.h),
+                ],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        if self.is_caret_visible() && !disabled {
+            let caret_x = text_x + self.get_boundaries()[self.editor.get_caret()];
+
+            Rectangle::new(apply_opacity(text_color)).draw(
+                [
+                    f64::from(caret_x),
+                    f64::from(content_y),
+                    1.0,
+                    f64::from(content_h),
+                ],
+                clip,
+                c.transform,
+                g,
+            );
+        }
+
+        let border_color = if self.focused && !disabled {
+            theme.accent_color
+        } else {
+            theme.border_color
+        };
+
+        stroke_border(
+            apply_opacity(border_color),
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            &make_uniform_insets(1),
+            BorderStyle::Solid,
+            clip,
+            c.transform,
+            g,
+        );
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
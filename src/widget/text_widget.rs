@@ -15,49 +15,24 @@
 
 use piston_window::*;
 
+use crate::core::accessibility::*;
+use crate::core::draw_target::*;
+use crate::widget::builder::*;
 use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::text_cache::*;
+use crate::widget::theme::*;
 use crate::widget::widget::*;
 
-mod private {
-    use piston_window::character::CharacterCache;
-    use piston_window::types::FontSize;
-    use piston_window::Graphics;
-
-    pub struct TextHelper {
-        /// The font size
-        pub font_size: FontSize,
-    }
-
-    impl TextHelper {
-        /// Creates a new text with black color
-        pub fn new(font_size: FontSize) -> TextHelper {
-            TextHelper { font_size }
-        }
+/// The string that is drawn at the end of a line of text that is shortened to fit into the
+/// widget.
+const ELLIPSIS: &str = "...";
 
-        /// Determines draw width and height with a character cache
-        pub fn determine_size<C, G>(
-            &self,
-            text: &str,
-            cache: &mut C,
-            _: &mut G,
-        ) -> Result<(i32, i32), C::Error>
-        where
-            C: CharacterCache,
-            G: Graphics<Texture = <C as CharacterCache>::Texture>,
-        {
-            let mut x = 0.0;
-            let mut y = 0.0;
-            for ch in text.chars() {
-                let character = cache.character(self.font_size, ch)?;
-                x += character.width();
-                y += character.height();
-            }
-            Ok((x as i32, y as i32))
-        }
-    }
-}
+/// The default distance between the baselines of lines of text, as a multiple of the font size.
+pub const DEFAULT_LINE_SPACING: f64 = 1.2;
 
 /// This `enum` specifies the desired justification of the text to be drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TextJustify {
     /// Left-justified text.
     Left,
@@ -69,27 +44,74 @@ pub enum TextJustify {
     Right,
 }
 
-/// This is the `TextWidget`, which draws a line of text on the screen.  This structure contains
-/// no accessable objects, they are all internal to `TextWidget`'s implementation.
+/// This `enum` specifies where the text is drawn vertically within the widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextVerticalAlign {
+    /// The first line of text is drawn at the top of the widget.
+    Top,
+
+    /// The text is centered vertically: `(total height - text height) / 2`
+    Middle,
+
+    /// The last line of text is drawn at the bottom of the widget.
+    Bottom,
+}
+
+/// This is the `TextWidget`, which draws text on the screen, within the content rectangle of
+/// the widget.  The text is justified horizontally, and aligned vertically, within the widget.
+/// Text may span multiple lines, separated by `\n`, which are drawn `line_spacing` times the
+/// font size apart.  Text that does not fit into the widget is clipped at its bounds, or, if
+/// ellipsizing is turned on, each line that is too wide is shortened and ends in `...`.
+///
+/// Fonts are loaded through the font cache (see `font_cache::load_font`), so every `TextWidget`
+/// that uses the same font shares it.  The size of the text is measured once after it changes,
+/// and is used as the preferred size of the widget in layouts, unless a preferred size is set.
 ///
 /// Example usage:
-/// IN PROGRESS
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::text_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Text", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut text_widget = TextWidget::new(
+///        pushrod.get_factory(),
+///        "OpenSans-Regular.ttf".to_string(),
+///        "A first line,\nand a much longer second line".to_string(),
+///        18,
+///        TextJustify::Center,
+///    );
+///
+///    text_widget.set_origin(20, 20);
+///    text_widget.set_size(200, 60);
+///    text_widget.set_vertical_align(TextVerticalAlign::Top);
+///    text_widget.set_ellipsize(true);
+///    pushrod.add_widget("Text", Box::new(text_widget));
+/// # }
+/// ```
 pub struct TextWidget {
     config: Configurable,
-    font_cache: Glyphs,
+    font_cache: SharedGlyphs,
     text: String,
     font_size: u32,
     justify: TextJustify,
-    desired_size: (i32, i32),
+    vertical_align: TextVerticalAlign,
+    line_spacing: f64,
+    ellipsize: bool,
+    desired_size: Option<(i32, i32)>,
+    scale_factor: f64,
 }
 
 /// Implementation of the constructor for the `TextWidget`.  Creates a new text object to be
 /// displayed on the screen, given a font name, font size, and text message.
 impl TextWidget {
     /// Creates a new `TextWidget` object, requiring the current `PistonWindow`'s factory object
-    /// (which can be cloned), the name of the font (filename in the `assets` directory), the
-    /// text to display, the font size in which to use, and the desired text justification
-    /// strategy.
+    /// (which can be cloned), the name of the font (the path to a TTF file, or the filename in
+    /// the `assets` directory), the text to display, the font size in which to use, and the
+    /// desired text justification strategy.  Panics if the font cannot be loaded; use
+    /// `font_cache::load_font` and `from_font` to handle the error instead.
     pub fn new(
         factory: &mut GfxFactory,
         font_name: String,
@@ -97,22 +119,66 @@ impl TextWidget {
         font_size: u32,
         justify: TextJustify,
     ) -> Self {
-        let assets = find_folder::Search::ParentsThenKids(3, 3)
-            .for_folder("assets")
-            .unwrap();
-        let ref font = assets.join(font_name.clone());
-        let glyphs = Glyphs::new(font, factory.clone(), TextureSettings::new()).unwrap();
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(glyphs, text, font_size, justify)
+    }
 
+    /// Creates a new `TextWidget` object that draws its text in a font from the font cache.
+    pub fn from_font(
+        font: SharedGlyphs,
+        text: String,
+        font_size: u32,
+        justify: TextJustify,
+    ) -> Self {
         Self {
             config: Configurable::new(),
-            font_cache: glyphs,
+            font_cache: font,
             text,
             font_size,
             justify,
-            desired_size: (0, 0),
+            vertical_align: TextVerticalAlign::Middle,
+            line_spacing: DEFAULT_LINE_SPACING,
+            ellipsize: false,
+            desired_size: None,
+            scale_factor: 1.0,
         }
     }
 
+    /// Creates a `WidgetBuilder` for a new `TextWidget` that draws in a font from the font
+    /// cache, with no text, left-justified, in the font size of the current `Theme`.
+    ///
+    /// Example:
+    /// ```no_run
+    /// # use piston_window::*;
+    /// # use pushrod::core::main::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::text_widget::*;
+    /// # fn main() {
+    ///    let window: PistonWindow = WindowSettings::new("Text", [400, 300]).build().unwrap();
+    ///    let mut pushrod = Pushrod::new(window);
+    ///    let font = load_font(pushrod.get_factory(), "OpenSans-Regular.ttf").unwrap();
+    ///    let text_widget = TextWidget::builder(font)
+    ///        .origin(20, 20)
+    ///        .size(200, 40)
+    ///        .text("Hello, world!")
+    ///        .font_size(24)
+    ///        .justify(TextJustify::Center)
+    ///        .build()
+    ///        .unwrap();
+    ///
+    ///    pushrod.add_widget("Text", Box::new(text_widget));
+    /// # }
+    /// ```
+    pub fn builder(font: SharedGlyphs) -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::from_font(
+            font,
+            String::new(),
+            get_current_theme().font_size,
+            TextJustify::Left,
+        ))
+    }
+
     /// Sets the color of the text for this `Widget`.
     pub fn set_text_color(&mut self, color: types::Color) {
         self.config().set(TextColor(color));
@@ -120,62 +186,364 @@ impl TextWidget {
     }
 
     /// Retrieves the color of the text for this `Widget`.
-    /// Defaults to black if not set.
+    /// Defaults to the text color of the current `Theme` if not set.
     pub fn get_text_color(&mut self) -> types::Color {
-        self.config().get::<TextColor>().unwrap().0
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
     }
 
     /// Changes the text, recalculates the desired draw size, and redraws after change.
-    pub fn set_text(&mut self, text: String) {
-        self.text = text.clone();
-        self.desired_size = (0, 0);
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.text = text.into();
+        self.desired_size = None;
         self.invalidate();
     }
 
+    /// Retrieves the text of this widget.
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Retrieves the width of the text in pixels, when drawn in this widget's font and font size.
+    /// For text with multiple lines, this is the width of the widest line.
+    pub fn get_text_width(&mut self) -> i32 {
+        self.get_text_size().w
+    }
+
+    /// Retrieves the size of the text in pixels, when drawn in this widget's font and font size:
+    /// the width of the widest line, and the height of all of the lines.  The size is measured
+    /// once after the text or font size changes.
+    pub fn get_text_size(&mut self) -> crate::core::point::Size {
+        if self.desired_size.is_none() {
+            let line_height = self.get_line_height();
+            let size = measure_text(
+                &mut self.font_cache.borrow_mut(),
+                self.font_size,
+                line_height,
+                &self.text,
+            );
+
+            self.desired_size = Some((size.w, size.h));
+        }
+
+        let (w, h) = self.desired_size.unwrap_or((0, 0));
+
+        crate::core::point::Size { w, h }
+    }
+
+    /// Sets the font size of this widget, and redraws after change.
+    pub fn set_font_size(&mut self, font_size: u32) {
+        self.font_size = font_size;
+        self.desired_size = None;
+        self.invalidate();
+    }
+
+    /// Retrieves the font size of this widget.
+    pub fn get_font_size(&self) -> u32 {
+        self.font_size
+    }
+
+    /// Sets the horizontal justification of the text, and redraws after change.
+    pub fn set_justify(&mut self, justify: TextJustify) {
+        self.justify = justify;
+        self.invalidate();
+    }
+
+    /// Retrieves the horizontal justification of the text.
+    pub fn get_justify(&self) -> TextJustify {
+        self.justify
+    }
+
+    /// Sets the vertical alignment of the text, and redraws after change.
+    pub fn set_vertical_align(&mut self, vertical_align: TextVerticalAlign) {
+        self.vertical_align = vertical_align;
+        self.invalidate();
+    }
+
+    /// Retrieves the vertical alignment of the text.  Defaults to `TextVerticalAlign::Middle`.
+    pub fn get_vertical_align(&self) -> TextVerticalAlign {
+        self.vertical_align
+    }
+
+    /// Sets the distance between the baselines of lines of text, as a multiple of the font size,
+    /// and redraws after change.
+    pub fn set_line_spacing(&mut self, line_spacing: f64) {
+        self.line_spacing = line_spacing;
+        self.desired_size = None;
+        self.invalidate();
+    }
+
+    /// Retrieves the distance between the baselines of lines of text, as a multiple of the font
+    /// size.  Defaults to `DEFAULT_LINE_SPACING`.
+    pub fn get_line_spacing(&self) -> f64 {
+        self.line_spacing
+    }
+
+    /// Turns on or off ellipsizing, which shortens each line of text that is too wide to fit
+    /// into the widget, ending it in `...`.  Text is clipped at the bounds of the widget
+    /// otherwise.  Redraws after change.
+    pub fn set_ellipsize(&mut self, ellipsize: bool) {
+        self.ellipsize = ellipsize;
+        self.invalidate();
+    }
+
+    /// Indicates whether lines of text that are too wide are ellipsized.  Defaults to `false`.
+    pub fn get_ellipsize(&self) -> bool {
+        self.ellipsize
+    }
+
+    /// Retrieves the distance between the baselines of lines of text in pixels.
+    fn get_line_height(&self) -> i32 {
+        (f64::from(self.font_size) * self.line_spacing).round() as i32
+    }
+
     /// Function to draw the text.  Generates a context transformation to display the text based on
     /// the point of origin's X and Y coordinates.  Since the text is drawn upwards from the point
-    /// of origin, the starting point is the lower left-hand corner of the widget.  (This may change
-    /// based on text justification, and other optional padding, once padding is introduced.)
-    pub fn draw_text(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
-        let size: crate::core::point::Size = self.get_size();
-
-        // This prevents the calculation from occurring at every single draw cycle.  It only needs
-        // to occur once.
-        if self.desired_size.0 == 0 {
-            self.desired_size = private::TextHelper::new(self.font_size)
-                .determine_size(&self.text, &mut self.font_cache, g)
-                .unwrap();
-
-            eprintln!("Desired size={:?} bounds={:?}", self.desired_size, size);
-        }
+    /// of origin, the starting point is the lower left-hand corner of the widget.  Each line of
+    /// text is justified, and the lines are aligned vertically, within the content rectangle,
+    /// which is the widget's size inset by its padding.
+    pub fn draw_text(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let [content_x, content_y, content_w, content_h] = self.get_content_rect();
+        let font_size = self.font_size as i32;
+        let line_height = self.get_line_height();
+        let font_cache = self.font_cache.clone();
+        let mut glyphs = font_cache.borrow_mut();
+        let lines: Vec<String> = self
+            .text
+            .split('\n')
+            .map(|line| {
+                if self.ellipsize {
+                    ellipsize_line(&mut glyphs, self.font_size, line, content_w)
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        let widths: Vec<i32> = lines
+            .iter()
+            .map(|line| text_width(&mut glyphs, self.font_size, line))
+            .collect();
+        let extra_height = (lines.len() as i32 - 1) * line_height;
 
-        // Modify transform here based on the width of the text being drawn, which is element 0 of
-        // self.desired_size
-        let start_x = match self.justify {
-            TextJustify::Left => 0.0,
-            TextJustify::Center => ((size.w - self.desired_size.0) / 2) as f64,
-            TextJustify::Right => (size.w - self.desired_size.0) as f64,
-        };
+        // The lines are drawn from the text cache, which borrows the font if a line has to be
+        // rasterized.
+        drop(glyphs);
 
-        // Vertically justify the text as default.
-        let start_y = (self.font_size - 2 + size.h as u32) / 2 - 1;
+        // The baseline of the first line.  Text in the middle is vertically justified as it
+        // always has been, shifted up by half of the height of any extra lines.
+        let start_y = content_y
+            + match self.vertical_align {
+                TextVerticalAlign::Top => font_size - 2,
+                TextVerticalAlign::Middle => (font_size - 2 + content_h - extra_height) / 2 - 1,
+                TextVerticalAlign::Bottom => content_h - 2 - extra_height,
+            };
 
-        // And draw the remaining text based on the starting point adjusted by the text justification.
-        //
         // IMPORTANT NOTE:
         // The provided transform from the run loop must be modified, as Piston's text drawing
         // routines treats the top "y" value specified as the _baseline_ for the image drawing
         // start point.  We want to treat the _inside_ of the box as the baseline, so we simply
         // add the size of the font (in pixels), which adjusts the baseline to the desired area.
-        Text::new_color(self.get_text_color(), self.font_size)
-            .draw(
-                &self.text,
-                &mut self.font_cache,
+        // Disabled text is drawn in the disabled color of the current theme.
+        let color = if self.is_disabled() {
+            get_current_theme().disabled_color
+        } else {
+            self.get_text_color()
+        };
+
+        //
+        // On high density displays, the glyphs are rasterized at the size of the font in device
+        // pixels, and scaled back down to points, so that the text stays sharp.
+        let scale = self.scale_factor;
+        let device_font_size = (f64::from(self.font_size) * scale).round() as u32;
+        let color = apply_opacity(color);
+
+        for (index, (line, line_width)) in lines.iter().zip(widths).enumerate() {
+            let start_x = content_x
+                + match self.justify {
+                    TextJustify::Left => 0,
+                    TextJustify::Center => (content_w - line_width) / 2,
+                    TextJustify::Right => content_w - line_width,
+                };
+            let line_y = start_y + index as i32 * line_height;
+
+            draw_cached_text(
+                &font_cache,
+                line,
+                device_font_size,
+                color,
                 clip,
-                c.transform.trans(start_x, start_y as f64),
+                c.transform
+                    .trans(start_x as f64, line_y as f64)
+                    .scale(1.0 / scale, 1.0 / scale),
                 g,
-            )
-            .unwrap();
+            );
+        }
+    }
+}
+
+/// Measures the width of a line of text in pixels, rounded up, when drawn in the font of `glyphs`
+/// at `font_size`.
+pub fn text_width(glyphs: &mut Glyphs, font_size: u32, text: &str) -> i32 {
+    use piston_window::character::CharacterCache;
+
+    glyphs
+        .width(font_size, text)
+        .map(|width| width.ceil() as i32)
+        .unwrap_or(0)
+}
+
+/// Measures text that may have several lines, separated by `\n`, when drawn in the font of
+/// `glyphs` at `font_size`, with `line_height` pixels between the baselines of the lines: the
+/// width of the widest line, and the height from the top of the first line to the baseline of
+/// the last.
+pub fn measure_text(
+    glyphs: &mut Glyphs,
+    font_size: u32,
+    line_height: i32,
+    text: &str,
+) -> crate::core::point::Size {
+    let width = text
+        .split('\n')
+        .map(|line| text_width(glyphs, font_size, line))
+        .max()
+        .unwrap_or(0);
+    let line_count = text.split('\n').count() as i32;
+
+    crate::core::point::Size {
+        w: width,
+        h: font_size as i32 + (line_count - 1) * line_height,
+    }
+}
+
+/// Breaks `text` into lines that are at most `width` pixels wide, as measured by `measure`, by
+/// moving words that do not fit onto the next line.  Lines that are separated by `\n` are kept
+/// apart, and a word that is wider than `width` by itself is given a line of its own.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::text_widget::*;
+/// # fn main() {
+///    // Every character is 10 pixels wide.
+///    let measure = |line: &str| line.chars().count() as i32 * 10;
+///
+///    assert_eq!(
+///        wrap_lines("The quick brown fox\njumps", 110, measure),
+///        vec!["The quick", "brown fox", "jumps"]
+///    );
+///    assert_eq!(wrap_lines("Unbreakable words", 50, measure), vec!["Unbreakable", "words"]);
+/// # }
+/// ```
+pub fn wrap_lines<F>(text: &str, width: i32, mut measure: F) -> Vec<String>
+where
+    F: FnMut(&str) -> i32,
+{
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            if line.is_empty() {
+                line.push_str(word);
+                continue;
+            }
+
+            let longer_line = format!("{} {}", line, word);
+
+            if measure(&longer_line) <= width {
+                line = longer_line;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Breaks `text` into lines that fit into `width` pixels when drawn in the font of `glyphs` at
+/// `font_size`, separated by `\n`, so that it can be drawn by a `TextWidget`.  See `wrap_lines`.
+pub fn wrap_text(glyphs: &mut Glyphs, font_size: u32, text: &str, width: i32) -> String {
+    wrap_lines(text, width, |line| text_width(glyphs, font_size, line)).join("\n")
+}
+
+/// Shortens a line of text that is wider than `width`, ending it in an ellipsis, so that it
+/// fits.  Returns an empty line if not even the ellipsis fits.
+fn ellipsize_line(glyphs: &mut Glyphs, font_size: u32, line: &str, width: i32) -> String {
+    if text_width(glyphs, font_size, line) <= width {
+        return line.to_string();
+    }
+
+    let mut chars: Vec<char> = line.chars().collect();
+
+    while chars.pop().is_some() {
+        let shortened: String = chars.iter().collect();
+        let candidate = format!("{}{}", shortened.trim_end(), ELLIPSIS);
+
+        if text_width(glyphs, font_size, &candidate) <= width {
+            return candidate;
+        }
+    }
+
+    String::new()
+}
+
+/// A `TextWidget` also needs a font size.
+impl Buildable for TextWidget {
+    fn validate(&mut self) -> Result<(), BuildError> {
+        if self.font_size == 0 {
+            return Err(BuildError::ZeroFontSize);
+        }
+
+        validate_size(self)
+    }
+}
+
+/// Implementation of the `WidgetBuilder` settings of the `TextWidget`.
+impl WidgetBuilder<TextWidget> {
+    /// Sets the text to draw.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.widget().set_text(text);
+        self
+    }
+
+    /// Sets the color of the text.
+    pub fn text_color(mut self, color: types::Color) -> Self {
+        self.widget().set_text_color(color);
+        self
+    }
+
+    /// Sets the font size of the text.
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.widget().set_font_size(font_size);
+        self
+    }
+
+    /// Sets the horizontal justification of the text.
+    pub fn justify(mut self, justify: TextJustify) -> Self {
+        self.widget().set_justify(justify);
+        self
+    }
+
+    /// Sets the vertical alignment of the text.
+    pub fn vertical_align(mut self, vertical_align: TextVerticalAlign) -> Self {
+        self.widget().set_vertical_align(vertical_align);
+        self
+    }
+
+    /// Sets the distance between lines of text, as a multiple of the font size.
+    pub fn line_spacing(mut self, line_spacing: f64) -> Self {
+        self.widget().set_line_spacing(line_spacing);
+        self
+    }
+
+    /// Shortens lines of text that are too wide, ending them in `...`.
+    pub fn ellipsize(mut self) -> Self {
+        self.widget().set_ellipsize(true);
+        self
     }
 }
 
@@ -185,12 +553,43 @@ impl Widget for TextWidget {
         &mut self.config
     }
 
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Text
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text.clone()
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the size of
+    /// the text, plus the padding, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => {
+                let text_size = self.get_text_size();
+                let padding = self.get_padding();
+
+                crate::core::point::Size {
+                    w: text_size.w + padding.left + padding.right,
+                    h: text_size.h + padding.top + padding.bottom,
+                }
+            }
+        }
+    }
+
     /// Draws the contents of the widget.
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         // Draw the text.
         self.draw_text(c, g, &clip);
 
         // Then clear invalidation.
         self.clear_invalidate();
     }
+
+    /// Rasterizes the text at the new scale factor from the next time it is drawn.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.invalidate();
+    }
 }
@@ -0,0 +1,169 @@
+// Theme
+// Default colors and font size used by Widgets that have not been configured otherwise.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::types;
+use std::cell::Cell;
+
+/// This is the `Theme`, which contains the colors and font size that `Widget`s use when the
+/// corresponding configuration key has not been set.  A color or font size that is set on a
+/// `Widget` always overrides the theme.  The current theme is set with `Pushrod::set_theme`.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::box_widget::*;
+/// # use pushrod::widget::theme::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let mut box_widget = BoxWidget::new();
+///
+///    set_current_theme(Theme::dark());
+///    assert_eq!(box_widget.get_color(), Theme::dark().background_color);
+///    assert_eq!(box_widget.get_border_color(), Theme::dark().border_color);
+///
+///    box_widget.set_color([1.0, 0.0, 0.0, 1.0]);
+///    assert_eq!(box_widget.get_color(), [1.0, 0.0, 0.0, 1.0]);
+///
+///    set_current_theme(Theme::light());
+///    assert_eq!(box_widget.get_border_color(), [0.0, 0.0, 0.0, 1.0]);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Color of the body of a `Widget`.
+    pub background_color: types::Color,
+
+    /// Color of text.
+    pub text_color: types::Color,
+
+    /// Color of borders.
+    pub border_color: types::Color,
+
+    /// Color used to highlight part of a `Widget`, such as the bar of a `ProgressWidget`, or a
+    /// button that is being pressed.
+    pub accent_color: types::Color,
+
+    /// Color of text in a `Widget` that is disabled.
+    pub disabled_color: types::Color,
+
+    /// Font size used by `Widget`s that are created without one, such as those in a layout.
+    pub font_size: u32,
+}
+
+/// Implementation of the built-in `Theme`s.
+impl Theme {
+    /// Creates the light `Theme`, which is the default: black text and borders on white.
+    pub const fn light() -> Self {
+        Self {
+            background_color: [1.0; 4],
+            text_color: [0.0, 0.0, 0.0, 1.0],
+            border_color: [0.0, 0.0, 0.0, 1.0],
+            accent_color: [0.0, 0.0, 0.0, 1.0],
+            disabled_color: [0.6, 0.6, 0.6, 1.0],
+            font_size: 16,
+        }
+    }
+
+    /// Creates the dark `Theme`: light text and borders on dark gray.
+    pub const fn dark() -> Self {
+        Self {
+            background_color: [0.15, 0.15, 0.15, 1.0],
+            text_color: [0.9, 0.9, 0.9, 1.0],
+            border_color: [0.5, 0.5, 0.5, 1.0],
+            accent_color: [0.25, 0.5, 0.9, 1.0],
+            disabled_color: [0.4, 0.4, 0.4, 1.0],
+            font_size: 16,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+thread_local! {
+    static CURRENT_THEME: Cell<Theme> = const { Cell::new(Theme::light()) };
+}
+
+/// Sets the `Theme` that `Widget`s use for any color or font size that they have not been
+/// configured with.  This is called by `Pushrod::set_theme`, which also redraws all `Widget`s.
+pub fn set_current_theme(theme: Theme) {
+    CURRENT_THEME.with(|current| current.set(theme));
+}
+
+/// Retrieves the `Theme` that `Widget`s currently use.
+pub fn get_current_theme() -> Theme {
+    CURRENT_THEME.with(|current| current.get())
+}
+
+/// Converts the red, green and blue channels of a color, each from 0.0 to 1.0, to its hue, from
+/// 0.0 to 360.0 degrees, and its saturation and value, from 0.0 to 1.0.  Gray colors have a hue
+/// of 0.0.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::theme::*;
+/// # fn main() {
+///    assert_eq!(rgb_to_hsv([1.0, 0.0, 0.0]), [0.0, 1.0, 1.0]);
+///    assert_eq!(rgb_to_hsv([0.0, 0.5, 0.5]), [180.0, 1.0, 0.5]);
+///    assert_eq!(rgb_to_hsv([0.5, 0.5, 0.5]), [0.0, 0.0, 0.5]);
+///    assert_eq!(hsv_to_rgb([240.0, 1.0, 1.0]), [0.0, 0.0, 1.0]);
+///    assert_eq!(hsv_to_rgb([60.0, 0.5, 1.0]), [1.0, 1.0, 0.5]);
+///
+///    let color = hsv_to_rgb(rgb_to_hsv([0.25, 0.5, 1.0]));
+///
+///    assert!((color[0] - 0.25).abs() < 1e-6 && (color[1] - 0.5).abs() < 1e-6);
+/// # }
+/// ```
+pub fn rgb_to_hsv(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max <= 0.0 { 0.0 } else { delta / max };
+
+    [hue, saturation, max]
+}
+
+/// Converts the hue of a color, in degrees, and its saturation and value, from 0.0 to 1.0, to its
+/// red, green and blue channels.  Hues outside of 0.0 to 360.0 are wrapped around.  See
+/// `rgb_to_hsv`.
+pub fn hsv_to_rgb(hsv: [f32; 3]) -> [f32; 3] {
+    let [hue, saturation, value] = hsv;
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue.rem_euclid(2.0) - 1.0).abs());
+    let m = value - chroma;
+    let [r, g, b] = match hue as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+
+    [r + m, g + m, b + m]
+}
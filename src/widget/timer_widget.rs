@@ -14,32 +14,129 @@
 // limitations under the License.
 
 use piston_window::*;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Instant;
 
+use crate::core::callbacks::*;
+use crate::core::clock::*;
+use crate::core::draw_target::*;
 use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::event::event::*;
+use crate::widget::builder::*;
 use crate::widget::config::*;
 use crate::widget::widget::*;
 
 pub const CALLBACK_TIMER: u32 = 100;
 
+/// Closure type that is called when a `TimerWidget` times out.  The closure receives the actual
+/// number of milliseconds that elapsed since the timer last fired (or was started), which may be
+/// larger than the timeout, as the timer is only checked once per update.
+pub type TimeoutCallback = Box<dyn FnMut(u64)>;
+
+/// A `TimerWidget` that stores its timeout function as a `TimeoutCallback`, so that any closure
+/// can be set, and changed, at any time.  This is what `TimerWidget::new` creates.
+pub type DynTimerWidget = TimerWidget<TimeoutCallback>;
+
+/// Default maximum number of times a timer catches up on missed timeout periods in a single
+/// `tick()`.  See `TimerWidget::set_max_burst`.
+pub const DEFAULT_MAX_BURST: u32 = 10;
+
+/// This `enum` specifies what a `TimerWidget` does when more than one timeout period has passed
+/// between two calls to `tick()`, for instance, when the application stalls while a window is
+/// being dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TickPolicy {
+    /// Fires once, and restarts the timeout period from the current time.  Missed periods are
+    /// dropped.  This is the default.
+    Skip,
+
+    /// Fires once for every missed timeout period immediately, up to the maximum burst.  The
+    /// timer phase is preserved.
+    Burst,
+
+    /// Fires once per `tick()`, advancing the timer by exactly one timeout period each time, so
+    /// the timer catches up over the following ticks without bursting.  If the timer is further
+    /// behind than the maximum burst, the excess periods are dropped.
+    Delay,
+}
+
 /// This is the `TimerWidget`.  It contains no base widget, it only contains a start and end
 /// time,
 ///
 /// Example usage:
-/// IN PROGRESS
-pub struct TimerWidget {
+/// ```
+/// # use pushrod::widget::timer_widget::*;
+/// # fn main() {
+///    let mut timer = TimerWidget::new();
+///
+///    timer.set_timeout(1000);
+///    timer.set_one_shot(true);
+///    timer.on_timeout(Box::new(|elapsed| eprintln!("Timer fired once after {}ms.", elapsed)));
+/// # }
+/// ```
+///
+/// Timers are ticked by the `Pushrod` run loop, so running one in a window only takes a handful
+/// of lines:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::timer_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Timer", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut timer = TimerWidget::new();
+///
+///    timer.set_timeout(1000);
+///    timer.on_timeout(Box::new(|elapsed| eprintln!("Timer fired after {}ms.", elapsed)));
+///    pushrod.add_widget("Timer", Box::new(timer));
+///    pushrod.run_without_handler();
+/// # }
+/// ```
+///
+/// The `TimerWidget` is generic over the type of its timeout function, `F`, which defaults to
+/// the boxed `TimeoutCallback`.  A timer that is created with `with_callback` stores its closure
+/// as it is, without boxing it, and is only boxed once, as a whole, when it is added to a
+/// `WidgetStore`.  As long as it is not added, the closure can borrow local state:
+/// ```
+/// # use pushrod::core::clock::*;
+/// # use pushrod::widget::timer_widget::*;
+/// # fn main() {
+///    let clock = ManualClock::new();
+///    let mut fired = Vec::new();
+///
+///    {
+///        let mut timer =
+///            TimerWidget::with_callback(Box::new(clock.clone()), |elapsed| fired.push(elapsed));
+///
+///        timer.set_timeout(100);
+///
+///        for _ in 0..3 {
+///            clock.advance(150);
+///            timer.tick();
+///        }
+///    }
+///
+///    assert_eq!(fired, vec![150, 150, 150]);
+/// # }
+/// ```
+pub struct TimerWidget<F: FnMut(u64) = TimeoutCallback> {
     config: Configurable,
-    enabled: bool,
-    initiated: u64,
-    timeout: u64,
-}
-
-/// Helper function that returns the current time in milliseconds since the `UNIX_EPOCH`.  This
-/// function is the equivalent of a `System.currentTimeMillis()` in Java.
-fn time_ms() -> u64 {
-    let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-
-    (since_the_epoch.as_secs() * 1_000) + (since_the_epoch.subsec_nanos() / 1_000_000) as u64
+    paused: bool,
+    repeat_count: u32,
+    fire_count: u32,
+    tick_policy: TickPolicy,
+    max_burst: u32,
+    clock: Box<dyn Clock>,
+    initiated: Instant,
+    accumulated_ms: u64,
+    on_timeout: Option<F>,
+    on_timeout_store: Option<DeferredCallback>,
+    deferred: Vec<DeferredCallback>,
+    post_events: bool,
+    unposted_fires: u32,
 }
 
 /// Implementation of the constructor for the `TimerWidget`.  Timer widgets are not accessible
@@ -48,79 +145,608 @@ fn time_ms() -> u64 {
 /// The timer provides a simple way to call a callback function after a certain amount of time
 /// has passed.  Upon instantiation, the timer is enabled.
 ///
-/// Here are a few limitations of the timer as it currently stands:
+/// A timer can either repeat forever (the default), be limited to a number of repeats with
+/// `set_repeat_count`, or be set as a one-shot timer with `set_one_shot(true)`, which disables
+/// itself after the first time it fires.  An exhausted timer can be re-armed with `reset()`.
 ///
-/// - Timer cannot be paused; it is enabled or disabled, and the timer resets when enabled.
-/// - Timer is called when the screen refreshes, so slower FPS settings will affect the timer.
+/// A running timer can be paused with `pause()` and continued with `resume()`, which keeps the
+/// time that had already elapsed before the pause.  `set_enabled` is a hard reset by comparison.
+///
+/// The timer is checked from the `Pushrod` run loop's update events, not from `draw()`, so it
+/// keeps firing even when the window is not being rendered (ie. when it is minimized.)  The
+/// accuracy of the timer is limited by the update rate of the run loop.
 impl TimerWidget {
-    /// Constructor, creates a new `TimerWidget` struct with an empty timeout function.
+    /// Constructor, creates a new `TimerWidget` struct with an empty timeout function.  Time is
+    /// measured using the monotonic `SystemClock`.
     pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Constructor, creates a new `TimerWidget` struct that measures time using the specified
+    /// `Clock`.  This is mainly useful for testing, by supplying a `ManualClock`.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let fired = Rc::new(Cell::new(0));
+    ///    let last_elapsed = Rc::new(Cell::new(0));
+    ///    let fired_clone = fired.clone();
+    ///    let last_elapsed_clone = last_elapsed.clone();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///
+    ///    timer.set_timeout(1000);
+    ///    timer.on_timeout(Box::new(move |elapsed| {
+    ///        fired_clone.set(fired_clone.get() + 1);
+    ///        last_elapsed_clone.set(elapsed);
+    ///    }));
+    ///
+    ///    // A very large gap between ticks fires the timer once, and restarts the period.
+    ///    clock.advance(10 * 60 * 60 * 1000);
+    ///    timer.tick();
+    ///    assert_eq!(fired.get(), 1);
+    ///    assert_eq!(last_elapsed.get(), 10 * 60 * 60 * 1000);
+    ///
+    ///    clock.advance(500);
+    ///    timer.tick();
+    ///    assert_eq!(fired.get(), 1);
+    /// # }
+    /// ```
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self::build(clock, None)
+    }
+
+    /// Creates a `WidgetBuilder` for a new `TimerWidget`, which needs a timeout.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::builder::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let fired = Rc::new(Cell::new(0));
+    ///    let fired_clone = fired.clone();
+    ///    let mut timer = TimerWidget::builder()
+    ///        .clock(Box::new(clock.clone()))
+    ///        .timeout_ms(500)
+    ///        .one_shot()
+    ///        .on_timeout(move |_| fired_clone.set(fired_clone.get() + 1))
+    ///        .build()
+    ///        .unwrap();
+    ///
+    ///    for _ in 0..3 {
+    ///        clock.advance(500);
+    ///        timer.tick();
+    ///    }
+    ///
+    ///    assert_eq!(fired.get(), 1);
+    ///
+    ///    // Without a timeout, a timer would fire on every update.
+    ///    assert_eq!(
+    ///        TimerWidget::builder().build().err(),
+    ///        Some(BuildError::MissingTimeout)
+    ///    );
+    /// # }
+    /// ```
+    pub fn builder() -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::new())
+    }
+}
+
+/// Implementation of the `TimerWidget`, for any type of timeout function.
+impl<F: FnMut(u64)> TimerWidget<F> {
+    /// Constructor, creates a new `TimerWidget` struct that calls `callback` when it times out,
+    /// and measures time using the specified `Clock`.  The closure is stored as it is, so it is
+    /// not boxed, and can borrow local state as long as the timer is not added to a
+    /// `WidgetStore`.  See `on_timeout`.
+    pub fn with_callback(clock: Box<dyn Clock>, callback: F) -> Self {
+        Self::build(clock, Some(callback))
+    }
+
+    fn build(clock: Box<dyn Clock>, on_timeout: Option<F>) -> Self {
+        let initiated = clock.now();
+
         Self {
             config: Configurable::new(),
-            enabled: true,
-            initiated: time_ms(),
-            timeout: 0,
+            paused: false,
+            repeat_count: 0,
+            fire_count: 0,
+            tick_policy: TickPolicy::Skip,
+            max_burst: DEFAULT_MAX_BURST,
+            clock,
+            initiated,
+            accumulated_ms: 0,
+            on_timeout,
+            on_timeout_store: None,
+            deferred: Vec::new(),
+            post_events: false,
+            unposted_fires: 0,
+        }
+    }
+
+    /// Called to check the time since initiation, and call the timeout function when a timer has
+    /// been triggered.  If more than one timeout period has passed, the `TickPolicy` decides how
+    /// many times the timer fires.  If the timer has fired as many times as its repeat count
+    /// allows, the timer is disabled after it fires.
+    pub fn tick(&mut self) {
+        if !self.is_enabled() || self.paused {
+            return;
+        }
+
+        let elapsed = self.get_elapsed_ms();
+        let timeout = self.get_timeout();
+
+        if elapsed < timeout {
+            return;
         }
+
+        // A zero timeout fires on every tick, so there is nothing to catch up on.
+        let policy = if timeout == 0 {
+            TickPolicy::Skip
+        } else {
+            self.tick_policy
+        };
+
+        match policy {
+            TickPolicy::Skip => {
+                self.restart_period(0);
+                self.fire(elapsed);
+            }
+
+            TickPolicy::Burst => {
+                let missed = elapsed / timeout;
+                let fires = missed.min(u64::from(self.max_burst));
+
+                self.restart_period(elapsed % timeout);
+
+                for _ in 0..fires {
+                    if !self.is_enabled() {
+                        break;
+                    }
+
+                    self.fire(timeout);
+                }
+            }
+
+            TickPolicy::Delay => {
+                let missed = elapsed / timeout;
+
+                if missed > u64::from(self.max_burst) {
+                    self.restart_period(elapsed % timeout);
+                } else {
+                    self.restart_period(elapsed - timeout);
+                }
+
+                self.fire(timeout);
+            }
+        }
+    }
+
+    /// Starts a new timeout period, with `elapsed_ms` of it already elapsed.
+    fn restart_period(&mut self, elapsed_ms: u64) {
+        self.initiated = self.clock.now();
+        self.accumulated_ms = elapsed_ms;
     }
 
-    //    // Called to check the time since initiation, and call the timeout function when a timer has
-    //    // been triggered.
-    //    fn tick(&mut self) {
-    //        if !self.enabled {
-    //            return;
-    //        }
-    //
-    //        let elapsed = time_ms() - self.initiated;
-    //
-    //        if elapsed > self.timeout {
-    //            self.initiated = time_ms();
-    //            self.timeout();
-    //        }
-    //    }
+    /// Counts a fire, disables the timer if it is exhausted, and calls the timeout function.
+    fn fire(&mut self, elapsed: u64) {
+        self.fire_count += 1;
+
+        if self.repeat_count > 0 && self.fire_count >= self.repeat_count {
+            self.config.set(Disabled);
+        }
+
+        self.timeout(elapsed);
+    }
+
+    /// Sets the policy used when more than one timeout period passes between two ticks.  Defaults
+    /// to `TickPolicy::Skip`.  With `TickPolicy::Burst` and `TickPolicy::Delay`, each fire
+    /// accounts for exactly one timeout period, so the timeout function receives the timeout as
+    /// its elapsed time.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let mut skip = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///    let mut burst = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///    let mut delay = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///
+    ///    skip.set_timeout(100);
+    ///    burst.set_timeout(100);
+    ///    burst.set_tick_policy(TickPolicy::Burst);
+    ///    delay.set_timeout(100);
+    ///    delay.set_tick_policy(TickPolicy::Delay);
+    ///
+    ///    // Stall for five timeout periods, and a bit.
+    ///    clock.advance(530);
+    ///    skip.tick();
+    ///    burst.tick();
+    ///    delay.tick();
+    ///
+    ///    assert_eq!(skip.get_fire_count(), 1);
+    ///    assert_eq!(skip.get_elapsed_ms(), 0);
+    ///    assert_eq!(burst.get_fire_count(), 5);
+    ///    assert_eq!(burst.get_elapsed_ms(), 30);
+    ///    assert_eq!(delay.get_fire_count(), 1);
+    ///    assert_eq!(delay.get_elapsed_ms(), 430);
+    ///
+    ///    // The delayed timer catches up one period per tick, keeping its phase.
+    ///    for _ in 0..5 {
+    ///        delay.tick();
+    ///    }
+    ///
+    ///    assert_eq!(delay.get_fire_count(), 5);
+    ///    assert_eq!(delay.get_elapsed_ms(), 30);
+    ///
+    ///    // Long stalls are capped at the maximum burst.
+    ///    burst.set_max_burst(3);
+    ///    clock.advance(10_000);
+    ///    burst.tick();
+    ///    assert_eq!(burst.get_fire_count(), 8);
+    /// # }
+    /// ```
+    pub fn set_tick_policy(&mut self, tick_policy: TickPolicy) {
+        self.tick_policy = tick_policy;
+    }
+
+    /// Sets the maximum number of missed timeout periods that are caught up on.  With
+    /// `TickPolicy::Burst`, this is the maximum number of times the timer fires in a single tick.
+    /// With `TickPolicy::Delay`, if the timer falls further behind than this, the excess periods
+    /// are dropped.  Values lower than 1 are treated as 1.
+    pub fn set_max_burst(&mut self, max_burst: u32) {
+        self.max_burst = max_burst.max(1);
+    }
 
     /// Enables or disables the timer.  When disabled, the timer will not initiate the callback
     /// function.  When re-enabled, the initiation time resets, so the timer will reset back to
     /// zero, effectively resetting the entire timer.
     pub fn set_enabled(&mut self, enabled: bool) {
-        self.enabled = enabled;
-        self.initiated = time_ms();
-    }
-
-    //    /// Sets the closure function for the timer when a timeout has been triggered.  This closure
-    //    /// needs to be `Boxed`.
-    //    pub fn on_timeout(&mut self, callback: BlankCallback) {
-    //        self.callbacks()
-    //            .put(CALLBACK_TIMER, CallbackTypes::BlankCallback { callback });
-    //    }
-    //
-    //    /// Calls the timeout function.
-    //    fn timeout(&mut self) {
-    //        match self.callbacks().get(CALLBACK_TIMER) {
-    //            CallbackTypes::BlankCallback { callback } => callback(),
-    //            _ => (),
-    //        }
-    //    }
+        if enabled {
+            self.config.remove::<Disabled>();
+        } else {
+            self.config.set(Disabled);
+        }
+
+        self.paused = false;
+        self.initiated = self.clock.now();
+        self.accumulated_ms = 0;
+    }
+
+    /// Changes the `Clock` that the timer measures time with, and restarts the timeout period
+    /// from the current time of the new clock.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+        self.initiated = self.clock.now();
+        self.accumulated_ms = 0;
+    }
+
+    /// Pauses the timer, remembering how much of the timeout period has already elapsed.  Has no
+    /// effect if the timer is disabled or already paused.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let fired = Rc::new(Cell::new(0));
+    ///    let fired_clone = fired.clone();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///
+    ///    timer.set_timeout(200);
+    ///    timer.on_timeout(Box::new(move |_| fired_clone.set(fired_clone.get() + 1)));
+    ///
+    ///    // Pause the timer about halfway through the timeout period.
+    ///    clock.advance(120);
+    ///    timer.pause();
+    ///
+    ///    // Time spent paused does not count towards the timeout.
+    ///    clock.advance(200);
+    ///    timer.tick();
+    ///    assert_eq!(fired.get(), 0);
+    ///
+    ///    // Once resumed, only the remaining time needs to pass.
+    ///    timer.resume();
+    ///    timer.tick();
+    ///    assert_eq!(fired.get(), 0);
+    ///
+    ///    clock.advance(100);
+    ///    timer.tick();
+    ///    assert_eq!(fired.get(), 1);
+    /// # }
+    /// ```
+    pub fn pause(&mut self) {
+        if !self.is_enabled() || self.paused {
+            return;
+        }
+
+        self.accumulated_ms += self.clock.elapsed_ms(self.initiated);
+        self.paused = true;
+    }
+
+    /// Resumes a paused timer, continuing from the time that had elapsed when it was paused.
+    pub fn resume(&mut self) {
+        if !self.paused {
+            return;
+        }
+
+        self.initiated = self.clock.now();
+        self.paused = false;
+    }
+
+    /// Indicates whether or not this timer is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Indicates whether or not this timer is enabled.  A paused timer is still enabled.  This is
+    /// the opposite of `Widget::is_disabled`.
+    pub fn is_enabled(&self) -> bool {
+        !self.config.contains_key::<Disabled>()
+    }
+
+    /// Returns the number of milliseconds that have elapsed in the current timeout period.  This
+    /// includes time elapsed before a `pause()`.  A disabled timer reports no elapsed time.
+    pub fn get_elapsed_ms(&self) -> u64 {
+        if !self.is_enabled() {
+            0
+        } else if self.paused {
+            self.accumulated_ms
+        } else {
+            self.accumulated_ms + self.clock.elapsed_ms(self.initiated)
+        }
+    }
+
+    /// Returns the number of milliseconds remaining until this timer fires.  This is 0 when the
+    /// next call to `tick()` would fire the timer, so it never reports the full timeout before
+    /// the callback has actually been called.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///
+    ///    timer.set_timeout(1000);
+    ///    clock.advance(400);
+    ///    assert_eq!(timer.get_elapsed_ms(), 400);
+    ///    assert_eq!(timer.get_remaining_ms(), 600);
+    ///
+    ///    clock.advance(800);
+    ///    assert_eq!(timer.get_remaining_ms(), 0);
+    ///
+    ///    timer.tick();
+    ///    assert_eq!(timer.get_elapsed_ms(), 0);
+    ///    assert_eq!(timer.get_remaining_ms(), 1000);
+    /// # }
+    /// ```
+    pub fn get_remaining_ms(&self) -> u64 {
+        self.get_timeout().saturating_sub(self.get_elapsed_ms())
+    }
+
+    /// Sets whether or not this timer only fires once.  When set, the timer disables itself after
+    /// the timeout function is called, and must be re-armed with `reset()`.  This is the same as
+    /// setting a repeat count of 1, and clearing it sets the repeat count back to unlimited.
+    pub fn set_one_shot(&mut self, one_shot: bool) {
+        self.set_repeat_count(if one_shot { 1 } else { 0 });
+    }
+
+    /// Sets the number of times this timer fires before it disables itself.  A count of 0 means
+    /// the timer repeats until it is disabled.  Once exhausted, the timer can be re-armed with
+    /// `reset()`, which also clears the fire count.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///
+    ///    timer.set_timeout(100);
+    ///    timer.set_repeat_count(3);
+    ///
+    ///    for _ in 0..5 {
+    ///        clock.advance(101);
+    ///        timer.tick();
+    ///    }
+    ///
+    ///    assert_eq!(timer.get_fire_count(), 3);
+    ///
+    ///    // Re-arming the exhausted timer allows it to fire again.
+    ///    timer.reset();
+    ///    assert_eq!(timer.get_fire_count(), 0);
+    ///
+    ///    clock.advance(101);
+    ///    timer.tick();
+    ///    assert_eq!(timer.get_fire_count(), 1);
+    ///
+    ///    // A repeat count of 1 is a one-shot timer.
+    ///    timer.reset();
+    ///    timer.set_repeat_count(1);
+    ///
+    ///    for _ in 0..3 {
+    ///        clock.advance(101);
+    ///        timer.tick();
+    ///    }
+    ///
+    ///    assert_eq!(timer.get_fire_count(), 1);
+    /// # }
+    /// ```
+    pub fn set_repeat_count(&mut self, repeat_count: u32) {
+        self.repeat_count = repeat_count;
+    }
+
+    /// Returns the number of times this timer has fired since it was created or last `reset()`.
+    pub fn get_fire_count(&self) -> u32 {
+        self.fire_count
+    }
+
+    /// Re-arms the timer: enables it, clears the fire count, and restarts the timeout period from
+    /// the current time.
+    pub fn reset(&mut self) {
+        self.fire_count = 0;
+        self.set_enabled(true);
+    }
+
+    /// Sets the closure function for the timer when a timeout has been triggered.  The closure
+    /// receives the number of milliseconds that actually elapsed since the previous time the
+    /// timer fired.  For a `DynTimerWidget`, as created by `new`, this closure needs to be
+    /// `Boxed`.
+    pub fn on_timeout(&mut self, callback: F) {
+        self.on_timeout = Some(callback);
+    }
+
+    /// Sets a closure function for the timer that is given access to the `WidgetStore` when a
+    /// timeout has been triggered, so that it can modify other `Widget`s.  When the timer is
+    /// driven by the run loop, the closure is called after all `Widget`s have been updated.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::box_widget::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///    let mut lit = false;
+    ///
+    ///    timer.set_timeout(500);
+    ///    timer.on_timeout_with_store(Box::new(move |widget_store| {
+    ///        lit = !lit;
+    ///
+    ///        let color = if lit { [1.0, 0.0, 0.0, 1.0] } else { [1.0; 4] };
+    ///
+    ///        widget_store
+    ///            .get_widget_for_name("BlinkBox")
+    ///            .borrow_mut()
+    ///            .set_color(color);
+    ///    }));
+    ///
+    ///    widget_store.add_widget("BlinkBox", Box::new(BoxWidget::new()));
+    ///    widget_store.add_widget("BlinkTimer", Box::new(timer));
+    ///
+    ///    clock.advance(500);
+    ///    widget_store.update(0);
+    ///
+    ///    let color = widget_store.get_widget_for_name("BlinkBox").borrow_mut().get_color();
+    ///    assert_eq!(color, [1.0, 0.0, 0.0, 1.0]);
+    /// # }
+    /// ```
+    pub fn on_timeout_with_store(&mut self, callback: WidgetStoreCallback) {
+        self.on_timeout_store = Some(Rc::new(RefCell::new(callback)));
+    }
+
+    /// Removes the closure functions set in `on_timeout` and `on_timeout_with_store`, if any.  The
+    /// timer keeps running, but no longer calls anything when it fires.
+    pub fn clear_timeout(&mut self) {
+        self.on_timeout = None;
+        self.on_timeout_store = None;
+    }
+
+    /// Calls the timeout function with the elapsed time, and queues the `WidgetStore` timeout
+    /// function.
+    fn timeout(&mut self, elapsed: u64) {
+        if let Some(callback) = &mut self.on_timeout {
+            callback(elapsed);
+        }
+
+        if let Some(callback) = &self.on_timeout_store {
+            self.deferred.push(callback.clone());
+        }
+
+        if self.post_events {
+            self.unposted_fires += 1;
+        }
+    }
+
+    /// Sets whether or not this timer posts a `PushrodEvent::TimerTriggered` event to the event
+    /// bus of its `WidgetStore` every time it fires, in addition to calling its timeout functions.
+    /// A timer that posts events does not need a timeout function at all.  Defaults to `false`.
+    ///
+    /// Example:
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use pushrod::core::clock::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::event::event::*;
+    /// # use pushrod::widget::timer_widget::*;
+    /// # fn main() {
+    ///    let clock = ManualClock::new();
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut timer = TimerWidget::with_clock(Box::new(clock.clone()));
+    ///    let triggered = Rc::new(Cell::new(WidgetId::INVALID));
+    ///    let triggered_clone = triggered.clone();
+    ///
+    ///    timer.set_timeout(100);
+    ///    timer.set_post_events(true);
+    ///
+    ///    let timer_id = widget_store.add_widget("Timer", Box::new(timer));
+    ///
+    ///    widget_store.subscribe(MASK_EVENT_TIMER, Box::new(move |event, _widget_store| {
+    ///        if let PushrodEvent::TimerTriggered { widget_id } = event {
+    ///            triggered_clone.set(*widget_id);
+    ///        }
+    ///    }));
+    ///
+    ///    clock.advance(100);
+    ///    widget_store.update(0);
+    ///    widget_store.deliver_posted_events();
+    ///    assert_eq!(triggered.get(), timer_id);
+    /// # }
+    /// ```
+    pub fn set_post_events(&mut self, post_events: bool) {
+        self.post_events = post_events;
+    }
+
+    /// Indicates whether or not this timer posts a `PushrodEvent::TimerTriggered` event every
+    /// time it fires.
+    pub fn is_posting_events(&self) -> bool {
+        self.post_events
+    }
 
     /// Sets the timeout in milliseconds for this timer.  Will trigger a call to the function
     /// set in `on_timeout` when triggered, and will continue to call that function until this
-    /// timer is disabled by using `self.set_enabled(false)`.
+    /// timer is disabled by using `self.set_enabled(false)`.  The timeout is stored as the
+    /// `Timeout` configuration value, so it can also be changed through `config()`.
     pub fn set_timeout(&mut self, timeout: u64) {
-        self.timeout = timeout;
+        self.config.set(Timeout(timeout));
+    }
+
+    /// Retrieves the timeout in milliseconds for this timer.  Defaults to 0, which fires on every
+    /// tick.
+    pub fn get_timeout(&self) -> u64 {
+        self.config.get_or(Timeout(0)).0
     }
 }
 
 /// Implementation of the `TimerWidget` object with the `Widget` traits implemented.
-impl Widget for TimerWidget {
+impl<F: FnMut(u64)> Widget for TimerWidget<F> {
     fn config(&mut self) -> &mut Configurable {
         &mut self.config
     }
 
-    /// Timer is always invalidated, this way, the tick function is always called on each
-    /// screen refresh.
-    fn is_invalidated(&mut self) -> bool {
-        true
-    }
-
     /// Origin is always set to X/Y at points 0x0.
     fn get_origin(&mut self) -> Point {
         make_origin_point()
@@ -131,9 +757,102 @@ impl Widget for TimerWidget {
         make_unsized()
     }
 
-    /// Does not draw anything - only calls the timer `tick()` function to increment the
-    /// timer.
-    fn draw(&mut self, _context: Context, _graphics: &mut G2d, _clip: &DrawState) {
-        //        self.tick();
+    /// Disabling a timer is the same as calling `set_enabled(false)`, and enabling it resets it,
+    /// in the same way as `set_enabled(true)`.
+    fn set_disabled(&mut self, disabled: bool) {
+        self.set_enabled(!disabled);
+    }
+
+    /// Calls the timer `tick()` function on every update of the run loop.
+    fn update(&mut self, _delta_ms: u64) {
+        self.tick();
+    }
+
+    /// Returns the `WidgetStore` timeout functions queued since the last update.
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Returns a `PushrodEvent::TimerTriggered` event for every time the timer fired since the
+    /// last call, if the timer posts its events.
+    fn take_posted_events(&mut self, widget_id: WidgetId) -> Vec<PushrodEvent> {
+        let fires = std::mem::take(&mut self.unposted_fires);
+
+        (0..fires)
+            .map(|_| PushrodEvent::TimerTriggered { widget_id })
+            .collect()
+    }
+
+    /// Does not draw anything, as timers are invisible.
+    fn draw(&mut self, _context: Context, _graphics: &mut DrawTarget, _clip: &DrawState) {
+        self.clear_invalidate();
+    }
+}
+
+/// A `TimerWidget` needs a timeout, but no size, as it is invisible.
+impl Buildable for TimerWidget {
+    fn validate(&mut self) -> Result<(), BuildError> {
+        if !self.config.contains_key::<Timeout>() {
+            return Err(BuildError::MissingTimeout);
+        }
+
+        Ok(())
+    }
+}
+
+/// Implementation of the `WidgetBuilder` settings of the `TimerWidget`.
+impl WidgetBuilder<TimerWidget> {
+    /// Sets the `Clock` that the timer measures time with.  Defaults to the `SystemClock`.
+    pub fn clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.widget().set_clock(clock);
+        self
+    }
+
+    /// Sets the timeout of the timer in milliseconds.
+    pub fn timeout_ms(mut self, timeout: u64) -> Self {
+        self.widget().set_timeout(timeout);
+        self
+    }
+
+    /// Makes the timer fire only once.
+    pub fn one_shot(mut self) -> Self {
+        self.widget().set_one_shot(true);
+        self
+    }
+
+    /// Sets the number of times that the timer fires before it disables itself.
+    pub fn repeat_count(mut self, repeat_count: u32) -> Self {
+        self.widget().set_repeat_count(repeat_count);
+        self
+    }
+
+    /// Sets how the timer catches up on timeout periods that it missed.
+    pub fn tick_policy(mut self, tick_policy: TickPolicy) -> Self {
+        self.widget().set_tick_policy(tick_policy);
+        self
+    }
+
+    /// Sets the most times that the timer fires on one tick, when it catches up.
+    pub fn max_burst(mut self, max_burst: u32) -> Self {
+        self.widget().set_max_burst(max_burst);
+        self
+    }
+
+    /// Makes the timer post a `PushrodEvent::TimerTriggered` event every time it fires.
+    pub fn post_events(mut self) -> Self {
+        self.widget().set_post_events(true);
+        self
+    }
+
+    /// Sets the closure that is called with the elapsed milliseconds when the timer fires.
+    pub fn on_timeout<F: FnMut(u64) + 'static>(mut self, callback: F) -> Self {
+        self.widget().on_timeout(Box::new(callback));
+        self
+    }
+
+    /// Sets the closure that is given access to the `WidgetStore` when the timer fires.
+    pub fn on_timeout_with_store(mut self, callback: WidgetStoreCallback) -> Self {
+        self.widget().on_timeout_with_store(callback);
+        self
     }
 }
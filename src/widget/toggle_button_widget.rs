@@ -15,28 +15,68 @@
 
 use piston_window::*;
 
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
 use crate::core::point::*;
-use crate::widget::box_widget::*;
+use crate::widget::button_widget::*;
 use crate::widget::config::*;
 use crate::widget::text_widget::*;
+use crate::widget::theme::*;
 use crate::widget::widget::*;
 
-pub type MutableSelectedCallback = Box<FnMut(bool) -> ()>;
+/// Closure type that is called with the new selected state of a `ToggleButtonWidget` when it
+/// changes.
+pub type MutableSelectedCallback = Box<dyn FnMut(bool)>;
 
-/// This is the `ToggleButtonWidget`, which contains a top-level widget for display, overriding the
-/// draw method to draw the base widget and the border for this box.
+/// This is the `ToggleButtonWidget`, a button that latches: clicking it, or pressing `Space` or
+/// `Enter` while it is focused, selects it, and it stays drawn as pressed until it is clicked
+/// again.  It is drawn by a `ButtonWidget`, so it looks the same as one while the mouse is over
+/// it, or while it is being pressed.  The `on_toggle` callback is called with the new state
+/// every time it changes.
+///
+/// The selected state is stored in the `CheckedState` config key.  A toggle button can be added
+/// to an exclusive group with `set_exclusive_group`, which works like a radio group: selecting a
+/// button deselects the rest of the group, and clicking the selected button leaves it selected.
+/// Buttons that are deselected by their group call their `on_toggle` callbacks on the next
+/// update of the run loop, and `WidgetStore::on_radio_selection_changed` reports the selection
+/// of the group as a whole.
 ///
 /// Example usage:
-/// IN PROGRESS
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::text_widget::*;
+/// # use pushrod::widget::toggle_button_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Toggle", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut bold_button = ToggleButtonWidget::new(
+///        pushrod.get_factory(),
+///        "OpenSans-Regular.ttf".to_string(),
+///        "Bold".to_string(),
+///        18,
+///        TextJustify::Center,
+///    );
+///
+///    bold_button.set_origin(20, 20);
+///    bold_button.set_size(80, 32);
+///    bold_button.on_toggle(Box::new(|selected| eprintln!("Bold: {}", selected)));
+///    pushrod.add_widget("BoldButton", Box::new(bold_button));
+/// # }
+/// ```
 pub struct ToggleButtonWidget {
     config: Configurable,
-    base_widget: BoxWidget,
-    text_widget: TextWidget,
-    selected_state: bool,
+    button: Box<ButtonWidget>,
+    on_toggle: Option<MutableSelectedCallback>,
 }
 
 /// Implementation of the constructor for the `ToggleButtonWidget`.
 impl ToggleButtonWidget {
+    /// Creates a new toggle button that is not selected, with a `text` label, drawn with the
+    /// font `font_name` from the `assets` directory, in `font_size`, and justified by `justify`.
+    /// The button can be focused, so that it can be toggled from the keyboard.
     pub fn new(
         factory: &mut GfxFactory,
         font_name: String,
@@ -44,67 +84,66 @@ impl ToggleButtonWidget {
         font_size: u32,
         justify: TextJustify,
     ) -> Self {
-        Self {
+        let mut button = ButtonWidget::with_font_size(factory, font_name, text, font_size);
+
+        button.set_justify(justify);
+
+        let mut widget = Self {
             config: Configurable::new(),
-            base_widget: BoxWidget::new(),
-            text_widget: TextWidget::new(
-                factory,
-                font_name.to_string(),
-                text.to_string(),
-                font_size,
-                justify,
-            ),
-            selected_state: false,
-        }
+            button: Box::new(button),
+            on_toggle: None,
+        };
+
+        widget.set_focusable(true);
+        widget
     }
 
-    /// Sets the border color for this widget.
+    /// Sets the closure that is called with the new selected state when it changes.
+    pub fn on_toggle(&mut self, callback: MutableSelectedCallback) {
+        self.on_toggle = Some(callback);
+    }
+
+    /// Sets the text label of the button.
     pub fn set_text(&mut self, text: String) {
-        self.text_widget.set_text(text);
+        self.button.set_text(text);
         self.invalidate();
     }
 
     /// Sets the color of the text for this `Widget`.
     pub fn set_text_color(&mut self, color: types::Color) {
-        self.text_widget.set_text_color(color);
+        self.config().set(TextColor(color));
+        self.button.set_text_color(color);
+        self.invalidate();
     }
 
     /// Retrieves the color of the text for this `Widget`.
-    /// Defaults to black if not set.
+    /// Defaults to the text color of the current `Theme` if not set.
     pub fn get_text_color(&mut self) -> types::Color {
-        self.text_widget.get_text_color()
+        self.button.get_text_color()
     }
 
     /// Sets the border color for this widget.
     pub fn set_border_color(&mut self, color: types::Color) {
-        self.base_widget.set_border_color(color);
-        self.text_widget.invalidate();
+        self.button.set_border_color(color);
         self.invalidate();
     }
 
     /// Retrieves the border color of this widget.
-    /// Defaults to black color `[0.0, 0.0, 0.0, 1.0]` if not set.
+    /// Defaults to the border color of the current `Theme` if not set.
     pub fn get_border_color(&mut self) -> types::Color {
-        self.base_widget.get_border_color()
+        self.button.get_border_color()
     }
 
     /// Sets the thickness of the border for this widget.
     pub fn set_border_thickness(&mut self, thickness: u8) {
-        self.base_widget.set_border_thickness(thickness);
-        self.text_widget.invalidate();
+        self.button.set_border_thickness(thickness);
         self.invalidate();
     }
 
     /// Retrieves the border thickness of this widget.
-    /// Defaults to 1 if not set.
+    /// Defaults to 1.
     pub fn get_border_thickness(&mut self) -> u8 {
-        self.base_widget.get_border_thickness()
-    }
-
-    /// Returns the selected state of this button.  `true` indicates the button is selected,
-    /// `false` otherwise.
-    pub fn get_selected(&self) -> bool {
-        self.selected_state
+        self.button.get_border_thickness()
     }
 
     /// Helper function that sets both the color of the border and the thickness at the same time.
@@ -112,119 +151,205 @@ impl ToggleButtonWidget {
         self.set_border_color(color);
         self.set_border_thickness(thickness);
     }
+
+    /// Selects or deselects the button.  If the state changed, the `on_toggle` callback is
+    /// called with the new state, just as it is when the button is clicked, and the widget is
+    /// invalidated.  To select a button in an exclusive group from code, so that the rest of
+    /// the group is deselected, use `WidgetStore::select_radio_button`.
+    pub fn set_selected(&mut self, selected: bool) {
+        if selected != self.is_selected() {
+            self.set_selected_silently(selected);
+            self.call_on_toggle(selected);
+        }
+    }
+
+    /// Selects or deselects the button without calling the `on_toggle` callback.  Invalidates
+    /// the widget if the state changed.
+    pub fn set_selected_silently(&mut self, selected: bool) {
+        if selected != self.is_selected() {
+            self.config().set(CheckedState(if selected {
+                CheckState::Checked
+            } else {
+                CheckState::Unchecked
+            }));
+            self.button.set_latched(selected);
+            self.invalidate();
+        }
+    }
+
+    /// Indicates whether the button is selected.  Defaults to `false`.
+    pub fn is_selected(&mut self) -> bool {
+        self.config()
+            .get::<CheckedState>()
+            .map(|state| state.0 == CheckState::Checked)
+            .unwrap_or(false)
+    }
+
+    /// Adds the button to the exclusive group `group`, in which only one button can be
+    /// selected at a time.  Exclusive groups are radio groups, so toggle buttons and
+    /// `RadioButtonWidget`s should not share a group name.
+    pub fn set_exclusive_group(&mut self, group: &str) {
+        self.config().set(RadioGroupId(group.to_string()));
+    }
+
+    /// Removes the button from its exclusive group, so that it toggles on its own again.
+    pub fn clear_exclusive_group(&mut self) {
+        self.config().remove::<RadioGroupId>();
+    }
+
+    /// Retrieves the name of the exclusive group of the button, or `None` if it toggles on its
+    /// own.
+    pub fn get_exclusive_group(&mut self) -> Option<String> {
+        self.config()
+            .get::<RadioGroupId>()
+            .map(|group| group.0.clone())
+    }
+
+    /// Toggles the button in response to a click.  A button in an exclusive group is only ever
+    /// selected by a click, so that the group always keeps its selection.
+    fn toggle(&mut self) {
+        let selected = self.is_selected();
+
+        if !selected || self.get_exclusive_group().is_none() {
+            self.set_selected(!selected);
+        }
+    }
+
+    fn call_on_toggle(&mut self, selected: bool) {
+        if let Some(callback) = &mut self.on_toggle {
+            callback(selected);
+        }
+    }
 }
 
-/// Implementation of the `PushButtonWidget` object with the `Widget` traits implemented.
-/// The base widget is a `BoxWidget`, which overlays a `TextWidget` over the top.  This `Widget`
-/// responds to the button down/up callbacks internally, and generates an `on_clicked` callback
-/// when appropriate.
+/// Implementation of the `ToggleButtonWidget` object with the `Widget` traits implemented.
+/// Events are handled by a `ButtonWidget`, which draws the button, and the button is toggled
+/// when it reports a click.
 impl Widget for ToggleButtonWidget {
     fn config(&mut self) -> &mut Configurable {
         &mut self.config
     }
 
-    /// Sets the `Point` of origin for this widget and the base widget, given the X and Y
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::ToggleButton
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.button.get_accessible_name()
+    }
+
+    fn get_accessible_checked(&mut self) -> Option<CheckState> {
+        Some(if self.is_selected() {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        })
+    }
+
+    /// Sets the `Point` of origin for this widget and the button, given the X and Y
     /// coordinates.  Invalidates the widget afterward.
     fn set_origin(&mut self, x: i32, y: i32) {
-        let border_size = self.base_widget.get_border_thickness();
-
         self.config().set(Origin(Point { x, y }));
-        self.base_widget.set_origin(x, y);
-        self.text_widget
-            .set_origin(x + border_size as i32, y + border_size as i32);
+        self.button.set_origin(x, y);
         self.invalidate();
     }
 
-    /// Sets the `Size` for this widget and the base widget, given width and height.  Invalidates the widget afterward.
+    /// Sets the `Size` for this widget and the button, given width and height.  Invalidates the
+    /// widget afterward.
     fn set_size(&mut self, w: i32, h: i32) {
-        let border_size = self.base_widget.get_border_thickness();
-
         self.config()
             .set(BodySize(crate::core::point::Size { w, h }));
-        self.base_widget.set_size(w, h);
-        self.text_widget
-            .set_size(w - (border_size as i32 * 2), h - (border_size as i32 * 2));
+        self.button.set_size(w, h);
         self.invalidate();
     }
 
-    /// Sets the color for this widget.  Invalidates the widget afterward.
+    /// Sets the padding between the border of this widget and its text.  Invalidates the widget
+    /// afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.button.set_padding(padding);
+        self.invalidate();
+    }
+
+    /// Retrieves the rectangle inside of which the text is drawn: the size of the widget, inset
+    /// by the thickness of the border, and then by the padding.
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        self.button.get_content_rect()
+    }
+
+    /// Sets the color for this widget, which is drawn while the button is not selected.
+    /// Invalidates the widget afterward.
     fn set_color(&mut self, color: types::Color) {
         self.config().set(MainColor(color));
-        self.base_widget.set_color(color);
+        self.button.set_color(color);
         self.invalidate();
     }
 
     /// Retrieves the color of this widget.
-    /// Defaults to white color `[1.0; 4]` if not set.
+    /// Defaults to the background color of the current `Theme` if not set.
     fn get_color(&mut self) -> types::Color {
-        self.base_widget.get_color()
-    }
-
-    //    /// Overrides button down.
-    //    fn button_down(&mut self, _: i32, button: Button) {
-    //        match button {
-    //            Button::Mouse(mouse_button) => {
-    //                if mouse_button == MouseButton::Left {
-    //                    if self.selected_state {
-    //                        self.base_widget.set_color([1.0, 1.0, 1.0, 1.0]);
-    //                        self.text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
-    //                    } else {
-    //                        self.base_widget.set_color([0.0, 0.0, 0.0, 1.0]);
-    //                        self.text_widget.set_text_color([1.0, 1.0, 1.0, 1.0]);
-    //                    }
-    //                }
-    //            }
-    //            _ => (),
-    //        }
-    //    }
-    //
-    //    /// Overrides button up inside, triggering an `on_selected` callback.
-    //    fn button_up_inside(&mut self, _: i32, button: Button) {
-    //        match button {
-    //            Button::Mouse(mouse_button) => {
-    //                if mouse_button == MouseButton::Left {
-    //                    self.call_on_selected();
-    //
-    //                    if self.selected_state {
-    //                        self.base_widget.set_color([0.0, 0.0, 0.0, 1.0]);
-    //                        self.text_widget.set_text_color([1.0, 1.0, 1.0, 1.0]);
-    //                    } else {
-    //                        self.base_widget.set_color([1.0, 1.0, 1.0, 1.0]);
-    //                        self.text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
-    //                    }
-    //                }
-    //            }
-    //            _ => (),
-    //        }
-    //    }
-    //
-    //    /// Overrides button up outside.
-    //    fn button_up_outside(&mut self, _: i32, button: Button) {
-    //        match button {
-    //            Button::Mouse(mouse_button) => {
-    //                if mouse_button == MouseButton::Left {
-    //                    if self.selected_state {
-    //                        self.base_widget.set_color([0.0, 0.0, 0.0, 1.0]);
-    //                        self.text_widget.set_text_color([1.0, 1.0, 1.0, 1.0]);
-    //                    } else {
-    //                        self.base_widget.set_color([1.0, 1.0, 1.0, 1.0]);
-    //                        self.text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
-    //                    }
-    //                }
-    //            }
-    //            _ => (),
-    //        }
-    //    }
-
-    /// Draws the contents of the widget in this order:
-    ///
-    /// - Base widget first
-    /// - Box graphic for the specified width
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
-        // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
-        // Invalidation is controlled by the top level widget (this box).
-        self.base_widget.draw(c, g, &clip);
-        self.text_widget.draw(c, g, &clip);
+        self.button.get_color()
+    }
+
+    /// Enables or disables this widget.  The text of a disabled button is drawn in the disabled
+    /// color of the current `Theme`, and it keeps its selected state.  Invalidates the widget
+    /// afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.button.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Catches up with a change of the selected state that was made by the `WidgetStore`, such
+    /// as the button being deselected by its exclusive group, calling the `on_toggle` callback.
+    fn update(&mut self, _delta_ms: u64) {
+        let selected = self.is_selected();
+
+        if selected != self.button.is_latched() {
+            self.button.set_latched(selected);
+            self.call_on_toggle(selected);
+            self.invalidate();
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        let result = self.button.handle_event(event);
+
+        if let Some(CallbackEvent::WidgetClicked { .. }) = result {
+            self.toggle();
+        }
+
+        if self.button.is_invalidated() {
+            self.invalidate();
+        }
+
+        result
+    }
+
+    /// Passes the new `Theme` on to the button, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.button.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the button, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.button.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the button, as pressed while it is selected.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.button.draw(c, g, clip);
 
         // Then clear invalidation.
         self.clear_invalidate();
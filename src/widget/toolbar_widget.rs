@@ -0,0 +1,856 @@
+// Toolbar Widget
+// Extensible widget for the widget library - handles a row of tool buttons.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::core::widget_store::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::image_widget::*;
+use crate::widget::menu_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Closure type that is called with the ID of the tool that was selected.
+pub type ToolCallback = Box<dyn FnMut(&str)>;
+
+/// The default width and height of a tool, in pixels.
+pub const DEFAULT_TOOL_SIZE: i32 = 24;
+
+/// The default number of pixels between tools, and between the tools and the edges of the
+/// toolbar.
+pub const DEFAULT_TOOL_SPACING: i32 = 4;
+
+/// The width of a separator between tools, in pixels.
+pub const TOOL_SEPARATOR_WIDTH: i32 = 9;
+
+/// The width of the chevron button that opens the tools that do not fit, in pixels.
+pub const TOOL_OVERFLOW_WIDTH: i32 = 16;
+
+/// The number of pixels between the edges of a tool and its icon.
+const TOOL_ICON_PADDING: i32 = 3;
+
+/// How far the background of a hovered tool is blended toward the accent color of the current
+/// `Theme`.
+const TOOL_HOVER_AMOUNT: f32 = 0.2;
+
+/// A tool button of a `ToolbarWidget`.
+struct Tool {
+    id: String,
+    icon: Box<ImageWidget>,
+    tooltip: String,
+    disabled: bool,
+    toggle: bool,
+    group: Option<String>,
+    selected: bool,
+}
+
+/// An entry of a `ToolbarWidget`.
+enum ToolbarItem {
+    Tool(Tool),
+    Separator,
+}
+
+/// The part of a `ToolbarWidget` that the mouse is over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ToolbarTarget {
+    Item(usize),
+    Overflow,
+}
+
+/// Returns how many of the items with the widths `widths` fit in a row that is `available`
+/// pixels wide, with `spacing` pixels before, between and after them.  If they do not all fit,
+/// room is also kept for the chevron button that is `overflow_w` pixels wide, after which the
+/// rest of the items are collapsed.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::toolbar_widget::*;
+/// # fn main() {
+///    let widths = [24, 24, 24];
+///
+///    // 4 + (24 + 4) * 3 pixels are needed for all of the items.
+///    assert_eq!(get_visible_item_count(&widths, 4, 88, 16), 3);
+///
+///    // Otherwise, the chevron button and its spacing take 20 pixels.
+///    assert_eq!(get_visible_item_count(&widths, 4, 87, 16), 2);
+///    assert_eq!(get_visible_item_count(&widths, 4, 40, 16), 0);
+/// # }
+/// ```
+pub fn get_visible_item_count(
+    widths: &[i32],
+    spacing: i32,
+    available: i32,
+    overflow_w: i32,
+) -> usize {
+    let ends: Vec<i32> = widths
+        .iter()
+        .scan(spacing, |x, w| {
+            *x += w + spacing;
+            Some(*x)
+        })
+        .collect();
+
+    match ends.last() {
+        Some(end) if *end > available => ends
+            .iter()
+            .take_while(|end| **end + overflow_w + spacing <= available)
+            .count(),
+        _ => widths.len(),
+    }
+}
+
+/// This is the `ToolbarWidget`, which draws a row of tool buttons with icons, and separators
+/// between groups of them.  Clicking a tool calls the `on_tool_selected` callback with its ID.
+/// The tool under the mouse is highlighted, and its tooltip is shown when tooltips are enabled
+/// with `Pushrod::enable_tooltips`.  Tools can be added, removed, enabled and disabled by ID at
+/// any time, and the toolbar lays itself out again.
+///
+/// Toggle tools stay selected until they are clicked again, and are drawn as pressed while they
+/// are.  Toggle tools can be put in an exclusive group, like `ToggleButtonWidget`s, so that
+/// selecting one deselects the rest of the group, and selecting the selected tool leaves it
+/// selected, as in the tool palette of a drawing application.
+///
+/// When the toolbar is too narrow for all of its tools, the ones that do not fit are collapsed
+/// behind a chevron button at its right, which opens them in a `PopupMenuWidget`, listed by
+/// their tooltips.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::toolbar_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Toolbar", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mut toolbar =
+///        ToolbarWidget::new(pushrod.get_factory(), "OpenSans-Regular.ttf".to_string());
+///
+///    toolbar.set_origin(0, 0);
+///    toolbar.set_size(400, 32);
+///    toolbar.add_tool("new", "new.png", "New drawing");
+///    toolbar.add_separator();
+///    toolbar.add_toggle_tool("pencil", "pencil.png", "Pencil", Some("tools"));
+///    toolbar.add_toggle_tool("eraser", "eraser.png", "Eraser", Some("tools"));
+///    toolbar.set_tool_selected("pencil", true);
+///    toolbar.on_tool_selected(Box::new(|tool_id| eprintln!("Selected {}", tool_id)));
+///    pushrod.enable_tooltips("OpenSans-Regular.ttf", 14);
+///    pushrod.add_widget("Toolbar", Box::new(toolbar));
+/// # }
+/// ```
+pub struct ToolbarWidget {
+    config: Configurable,
+    factory: GfxFactory,
+    font: SharedGlyphs,
+    items: Vec<ToolbarItem>,
+    tool_size: i32,
+    spacing: i32,
+    scale_factor: f64,
+    hovered: Option<ToolbarTarget>,
+    pressed: Option<ToolbarTarget>,
+    widget_id: WidgetId,
+    on_tool_selected: Option<ToolCallback>,
+    overflow_selection: Rc<RefCell<Option<String>>>,
+    deferred: Vec<DeferredCallback>,
+}
+
+/// Implementation of the constructor for the `ToolbarWidget`.
+impl ToolbarWidget {
+    /// Creates a new toolbar without any tools.  The tools that do not fit are listed in a
+    /// popup menu that is drawn with the font `font_name` from the `assets` directory.  Panics
+    /// if the font cannot be loaded; use `font_cache::load_font` and `from_font` to handle the
+    /// error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(factory, glyphs)
+    }
+
+    /// Creates a new toolbar without any tools, whose overflow popup menu is drawn in a font
+    /// from the font cache.
+    pub fn from_font(factory: &mut GfxFactory, font: SharedGlyphs) -> Self {
+        Self {
+            config: Configurable::new(),
+            factory: factory.clone(),
+            font,
+            items: Vec::new(),
+            tool_size: DEFAULT_TOOL_SIZE,
+            spacing: DEFAULT_TOOL_SPACING,
+            scale_factor: 1.0,
+            hovered: None,
+            pressed: None,
+            widget_id: WidgetId::INVALID,
+            on_tool_selected: None,
+            overflow_selection: Rc::new(RefCell::new(None)),
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Sets the closure that is called with the ID of a tool when it is selected: when it is
+    /// clicked, or chosen from the overflow popup menu.  It is called for every click, even
+    /// when the tool was already selected.
+    pub fn on_tool_selected(&mut self, callback: ToolCallback) {
+        self.on_tool_selected = Some(callback);
+    }
+
+    /// Adds a tool that is identified by `id`, and drawn with the image `image_name` (a path,
+    /// or the filename of an image in the `assets` directory), after the other tools.  The
+    /// `tooltip` is shown when the mouse rests over the tool.  A tool that already has the ID
+    /// is replaced.  Invalidates the widget afterward.
+    pub fn add_tool(&mut self, id: &str, image_name: &str, tooltip: &str) {
+        self.insert_tool(id, image_name, tooltip, false, None);
+    }
+
+    /// Adds a toggle tool, which stays selected until it is clicked again, as with `add_tool`.
+    /// If `group` is set, the tool is part of that exclusive group of tools, in which only one
+    /// can be selected at a time.
+    pub fn add_toggle_tool(
+        &mut self,
+        id: &str,
+        image_name: &str,
+        tooltip: &str,
+        group: Option<&str>,
+    ) {
+        self.insert_tool(id, image_name, tooltip, true, group);
+    }
+
+    /// Adds a separator after the tools.  Invalidates the widget afterward.
+    pub fn add_separator(&mut self) {
+        self.items.push(ToolbarItem::Separator);
+        self.invalidate();
+    }
+
+    /// Removes the tool with the ID `id`, returning whether it was found.  Separators that are
+    /// left at either end of the toolbar, or next to each other, are removed along with it.
+    /// Invalidates the widget afterward.
+    pub fn remove_tool(&mut self, id: &str) -> bool {
+        let index = match self.find_tool(id) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        self.items.remove(index);
+
+        let mut after_separator = true;
+
+        self.items.retain(|item| {
+            let separator = match item {
+                ToolbarItem::Separator => true,
+                ToolbarItem::Tool(_) => false,
+            };
+            let keep = !(separator && after_separator);
+
+            if keep {
+                after_separator = separator;
+            }
+
+            keep
+        });
+
+        if let Some(ToolbarItem::Separator) = self.items.last() {
+            self.items.pop();
+        }
+
+        self.hovered = None;
+        self.pressed = None;
+        self.invalidate();
+        true
+    }
+
+    /// Retrieves the IDs of the tools, in order.
+    pub fn get_tool_ids(&self) -> Vec<&str> {
+        self.tools().map(|tool| tool.id.as_str()).collect()
+    }
+
+    /// Retrieves the IDs of the tools that do not fit in the toolbar at its current size, and
+    /// are listed in the overflow popup menu instead.
+    pub fn get_overflow_tool_ids(&mut self) -> Vec<&str> {
+        let visible_count = self.get_visible_count();
+
+        self.items[visible_count..]
+            .iter()
+            .filter_map(|item| match item {
+                ToolbarItem::Tool(tool) => Some(tool.id.as_str()),
+                ToolbarItem::Separator => None,
+            })
+            .collect()
+    }
+
+    /// Enables or disables the tool with the ID `id`.  A disabled tool is drawn faded, and
+    /// cannot be selected.  Invalidates the widget afterward.
+    pub fn set_tool_disabled(&mut self, id: &str, disabled: bool) {
+        if let Some(tool) = self.get_tool_mut(id) {
+            tool.disabled = disabled;
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether the tool with the ID `id` is disabled.  Returns `false` if there is no
+    /// such tool.
+    pub fn is_tool_disabled(&self, id: &str) -> bool {
+        self.tools().any(|tool| tool.id == id && tool.disabled)
+    }
+
+    /// Selects or deselects the toggle tool with the ID `id`, without calling the
+    /// `on_tool_selected` callback.  Selecting a tool in an exclusive group deselects the rest
+    /// of the group.  Does nothing for tools that do not toggle.  Invalidates the widget
+    /// afterward.
+    pub fn set_tool_selected(&mut self, id: &str, selected: bool) {
+        let group = match self.tools().find(|tool| tool.id == id && tool.toggle) {
+            Some(tool) => tool.group.clone(),
+            None => return,
+        };
+
+        for item in self.items.iter_mut() {
+            if let ToolbarItem::Tool(tool) = item {
+                if tool.id == id {
+                    tool.selected = selected;
+                } else if selected && group.is_some() && tool.group == group {
+                    tool.selected = false;
+                }
+            }
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether the toggle tool with the ID `id` is selected.
+    pub fn is_tool_selected(&self, id: &str) -> bool {
+        self.tools().any(|tool| tool.id == id && tool.selected)
+    }
+
+    /// Retrieves the ID of the selected tool of the exclusive group `group`, or `None` if none
+    /// of its tools is selected.
+    pub fn get_selected_tool(&self, group: &str) -> Option<&str> {
+        self.tools()
+            .find(|tool| tool.selected && tool.group.as_deref() == Some(group))
+            .map(|tool| tool.id.as_str())
+    }
+
+    /// Sets the width and height of the tools.  Invalidates the widget afterward.
+    pub fn set_tool_size(&mut self, tool_size: i32) {
+        self.tool_size = tool_size.max(1);
+        self.invalidate();
+    }
+
+    /// Retrieves the width and height of the tools.  Defaults to `DEFAULT_TOOL_SIZE`.
+    pub fn get_tool_size(&self) -> i32 {
+        self.tool_size
+    }
+
+    /// Sets the number of pixels between the tools, and between the tools and the edges of the
+    /// toolbar.  Invalidates the widget afterward.
+    pub fn set_spacing(&mut self, spacing: i32) {
+        self.spacing = spacing.max(0);
+        self.invalidate();
+    }
+
+    /// Retrieves the spacing between the tools.  Defaults to `DEFAULT_TOOL_SPACING`.
+    pub fn get_spacing(&self) -> i32 {
+        self.spacing
+    }
+
+    fn insert_tool(
+        &mut self,
+        id: &str,
+        image_name: &str,
+        tooltip: &str,
+        toggle: bool,
+        group: Option<&str>,
+    ) {
+        let mut icon = ImageWidget::new(&mut self.factory, image_name.to_string());
+
+        icon.set_scaling(ImageScaling::Fit);
+
+        if (self.scale_factor - 1.0).abs() > f64::EPSILON {
+            icon.scale_factor_changed(self.scale_factor);
+        }
+
+        let tool = ToolbarItem::Tool(Tool {
+            id: id.to_string(),
+            icon: Box::new(icon),
+            tooltip: tooltip.to_string(),
+            disabled: false,
+            toggle,
+            group: group.map(str::to_string),
+            selected: false,
+        });
+
+        match self.find_tool(id) {
+            Some(index) => self.items[index] = tool,
+            None => self.items.push(tool),
+        }
+
+        self.invalidate();
+    }
+
+    fn tools(&self) -> impl Iterator<Item = &Tool> {
+        self.items.iter().filter_map(|item| match item {
+            ToolbarItem::Tool(tool) => Some(tool),
+            ToolbarItem::Separator => None,
+        })
+    }
+
+    fn find_tool(&self, id: &str) -> Option<usize> {
+        self.items.iter().position(|item| match item {
+            ToolbarItem::Tool(tool) => tool.id == id,
+            ToolbarItem::Separator => false,
+        })
+    }
+
+    fn get_tool_mut(&mut self, id: &str) -> Option<&mut Tool> {
+        self.items.iter_mut().find_map(|item| match item {
+            ToolbarItem::Tool(tool) if tool.id == id => Some(tool),
+            _ => None,
+        })
+    }
+
+    fn get_item_width(&self, item: &ToolbarItem) -> i32 {
+        match item {
+            ToolbarItem::Tool(_) => self.tool_size,
+            ToolbarItem::Separator => TOOL_SEPARATOR_WIDTH,
+        }
+    }
+
+    /// Returns how many of the items fit in the toolbar.
+    fn get_visible_count(&mut self) -> usize {
+        let widths: Vec<i32> = self
+            .items
+            .iter()
+            .map(|item| self.get_item_width(item))
+            .collect();
+
+        get_visible_item_count(
+            &widths,
+            self.spacing,
+            self.get_size().w,
+            TOOL_OVERFLOW_WIDTH,
+        )
+    }
+
+    /// Returns the bounds of each item that fits in the toolbar, as `[x, y, w, h]`, and the
+    /// bounds of the chevron button, if any items do not fit.
+    fn get_layout(&mut self) -> (Vec<[i32; 4]>, Option<[i32; 4]>) {
+        let visible_count = self.get_visible_count();
+        let size = self.get_size();
+        let top = (size.h - self.tool_size) / 2;
+        let mut x = self.spacing;
+        let mut bounds = Vec::new();
+
+        for item in self.items.iter().take(visible_count) {
+            let w = self.get_item_width(item);
+
+            bounds.push([x, top, w, self.tool_size]);
+            x += w + self.spacing;
+        }
+
+        let overflow = if visible_count < self.items.len() {
+            Some([x, top, TOOL_OVERFLOW_WIDTH, self.tool_size])
+        } else {
+            None
+        };
+
+        (bounds, overflow)
+    }
+
+    /// Returns the tool or the chevron button at `point`, if any.
+    fn get_target_at(&mut self, point: &Point) -> Option<ToolbarTarget> {
+        let inside = |rect: &[i32; 4]| point_in_bounds(point, rect);
+        let (bounds, overflow) = self.get_layout();
+
+        if overflow.map(|rect| inside(&rect)).unwrap_or(false) {
+            return Some(ToolbarTarget::Overflow);
+        }
+
+        bounds
+            .iter()
+            .position(inside)
+            .filter(|index| match &self.items[*index] {
+                ToolbarItem::Tool(tool) => !tool.disabled,
+                ToolbarItem::Separator => false,
+            })
+            .map(ToolbarTarget::Item)
+    }
+
+    /// Selects the tool with the ID `id`: toggles it if it is a toggle tool, and calls the
+    /// `on_tool_selected` callback.
+    fn select_tool(&mut self, id: &str) {
+        let (toggle, grouped, selected) = match self.tools().find(|tool| tool.id == id) {
+            Some(tool) if !tool.disabled => (tool.toggle, tool.group.is_some(), tool.selected),
+            _ => return,
+        };
+
+        if toggle {
+            self.set_tool_selected(id, grouped || !selected);
+        }
+
+        if let Some(callback) = &mut self.on_tool_selected {
+            callback(id);
+        }
+    }
+
+    /// Opens the tools that do not fit in a popup menu below the chevron button.
+    fn open_overflow(&mut self, overflow: [i32; 4]) {
+        let toolbar_id = self.widget_id;
+        let visible_count = self.get_visible_count();
+        let mut menu = Menu::new("");
+        let mut separator_pending = false;
+
+        for item in self.items[visible_count..].iter() {
+            match item {
+                ToolbarItem::Tool(tool) => {
+                    if separator_pending && !menu.get_items().is_empty() {
+                        menu = menu.separator();
+                    }
+
+                    let label = if tool.tooltip.is_empty() {
+                        &tool.id
+                    } else {
+                        &tool.tooltip
+                    };
+
+                    menu = if tool.disabled {
+                        menu.disabled_item(&tool.id, label)
+                    } else {
+                        menu.item(&tool.id, label)
+                    };
+                    separator_pending = false;
+                }
+                ToolbarItem::Separator => separator_pending = true,
+            }
+        }
+
+        let selection = self.overflow_selection.clone();
+        let mut popup = PopupMenuWidget::from_font(self.font.clone(), menu);
+
+        popup.on_item_selected(Box::new(move |tool_id| {
+            selection.replace(Some(tool_id.to_string()));
+        }));
+
+        let mut popup = Some(popup);
+
+        self.deferred.push(Rc::new(RefCell::new(Box::new(
+            move |widget_store: &mut WidgetStore| {
+                if let Some(popup) = popup.take() {
+                    let origin = widget_store.get_absolute_origin(toolbar_id);
+
+                    popup.open(
+                        widget_store,
+                        origin.x + overflow[0],
+                        origin.y + overflow[1] + overflow[3],
+                    );
+                }
+            },
+        ))));
+    }
+
+    fn set_hovered(&mut self, hovered: Option<ToolbarTarget>) {
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `ToolbarWidget` object with the `Widget` traits implemented.  Each tool
+/// draws its icon with an `ImageWidget`.
+impl Widget for ToolbarWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Toolbar
+    }
+
+    /// Retrieves the tooltip of the tool under the mouse, or the tooltip of the toolbar itself
+    /// when the mouse is not over a tool.
+    fn get_tooltip(&mut self) -> Option<String> {
+        if let Some(ToolbarTarget::Item(index)) = self.hovered {
+            if let Some(ToolbarItem::Tool(tool)) = self.items.get(index) {
+                if !tool.tooltip.is_empty() {
+                    return Some(tool.tooltip.clone());
+                }
+            }
+        }
+
+        self.config()
+            .get::<TooltipText>()
+            .map(|tooltip| tooltip.0.clone())
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the width
+    /// of all of the tools, and the height of a tool, with spacing around them, if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        if let Some(preferred_size) = self.config().get::<PreferredSize>() {
+            return preferred_size.0.clone();
+        }
+
+        let items_w: i32 = self
+            .items
+            .iter()
+            .map(|item| self.get_item_width(item) + self.spacing)
+            .sum();
+
+        crate::core::point::Size {
+            w: items_w + self.spacing,
+            h: self.tool_size + self.spacing * 2,
+        }
+    }
+
+    /// Selects a tool that was chosen from the overflow popup menu.
+    fn update(&mut self, _delta_ms: u64) {
+        let selection = self.overflow_selection.borrow_mut().take();
+
+        if let Some(tool_id) = selection {
+            self.select_tool(&tool_id);
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseEntered { widget_id } => self.widget_id = widget_id,
+
+            CallbackEvent::MouseMoved { widget_id, point } => {
+                let target = self.get_target_at(&point);
+
+                self.widget_id = widget_id;
+                self.set_hovered(target);
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered(None),
+
+            CallbackEvent::MouseButtonDown {
+                widget_id,
+                button: Button::Mouse(MouseButton::Left),
+                point,
+            } => {
+                self.widget_id = widget_id;
+                self.pressed = self.get_target_at(&point);
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonUpInside {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                ..
+            } => {
+                let pressed = self.pressed.take();
+
+                if pressed.is_some() && pressed == self.get_target_at(&point) {
+                    match pressed {
+                        Some(ToolbarTarget::Item(index)) => {
+                            if let ToolbarItem::Tool(tool) = &self.items[index] {
+                                let tool_id = tool.id.clone();
+
+                                self.select_tool(&tool_id);
+                            }
+                        }
+                        Some(ToolbarTarget::Overflow) => {
+                            if let (_, Some(overflow)) = self.get_layout() {
+                                self.open_overflow(overflow);
+                            }
+                        }
+                        None => (),
+                    }
+                }
+
+                self.invalidate();
+            }
+
+            CallbackEvent::MouseButtonUpOutside {
+                button: Button::Mouse(MouseButton::Left),
+                ..
+            } => {
+                self.pressed = None;
+                self.invalidate();
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Passes the new scale factor on to the icons, so that they can load the versions of their
+    /// images for it, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+
+        for item in self.items.iter_mut() {
+            if let ToolbarItem::Tool(tool) = item {
+                tool.icon.scale_factor_changed(scale_factor);
+            }
+        }
+
+        self.invalidate();
+    }
+
+    /// Enables or disables this widget.  A disabled toolbar draws all of its tools faded.
+    /// Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.hovered = None;
+        self.pressed = None;
+        self.invalidate();
+    }
+
+    /// Draws the background, a line along the bottom, and then each tool that fits: pressed and
+    /// selected tools in the accent color of the current `Theme`, the hovered tool in the
+    /// background color blended toward it, and separators as lines.  Disabled tools are faded
+    /// by drawing the background color over them at half of its opacity.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let background = self.get_color();
+        let toolbar_disabled = self.is_disabled();
+        let mut hover_color = background;
+        let mut faded_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * TOOL_HOVER_AMOUNT;
+        }
+
+        faded_color[3] *= 0.5;
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+        Rectangle::new(apply_opacity(theme.border_color)).draw(
+            [0.0, f64::from(size.h) - 1.0, f64::from(size.w), 1.0],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let (bounds, overflow) = self.get_layout();
+        let (hovered, pressed) = (self.hovered, self.pressed);
+        let tool_color = |target: ToolbarTarget, selected: bool| {
+            if pressed == Some(target) || selected {
+                Some(theme.accent_color)
+            } else if hovered == Some(target) {
+                Some(hover_color)
+            } else {
+                None
+            }
+        };
+
+        for (index, rect) in bounds.iter().enumerate() {
+            let [x, y, w, h] = [
+                f64::from(rect[0]),
+                f64::from(rect[1]),
+                f64::from(rect[2]),
+                f64::from(rect[3]),
+            ];
+
+            let tool = match &mut self.items[index] {
+                ToolbarItem::Tool(tool) => tool,
+                ToolbarItem::Separator => {
+                    Rectangle::new(apply_opacity(theme.border_color)).draw(
+                        [x + (w / 2.0).floor(), y + 2.0, 1.0, h - 4.0],
+                        clip,
+                        c.transform,
+                        g,
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(color) = tool_color(ToolbarTarget::Item(index), tool.selected) {
+                Rectangle::new(apply_opacity(color)).draw([x, y, w, h], clip, c.transform, g);
+            }
+
+            tool.icon.set_size(
+                rect[2] - TOOL_ICON_PADDING * 2,
+                rect[3] - TOOL_ICON_PADDING * 2,
+            );
+            tool.icon.draw(
+                c.trans(
+                    x + f64::from(TOOL_ICON_PADDING),
+                    y + f64::from(TOOL_ICON_PADDING),
+                ),
+                g,
+                clip,
+            );
+
+            if tool.disabled || toolbar_disabled {
+                Rectangle::new(apply_opacity(faded_color)).draw([x, y, w, h], clip, c.transform, g);
+            }
+        }
+
+        if let Some(rect) = overflow {
+            let (x, y, w, h) = (
+                f64::from(rect[0]),
+                f64::from(rect[1]),
+                f64::from(rect[2]),
+                f64::from(rect[3]),
+            );
+            let chevron_color = match tool_color(ToolbarTarget::Overflow, false) {
+                Some(color) => {
+                    Rectangle::new(apply_opacity(color)).draw([x, y, w, h], clip, c.transform, g);
+
+                    if pressed == Some(ToolbarTarget::Overflow) {
+                        theme.background_color
+                    } else {
+                        theme.text_color
+                    }
+                }
+                None => theme.text_color,
+            };
+            let middle = y + h / 2.0;
+            let line = Line::new(apply_opacity(chevron_color), 0.75);
+
+            for offset in [-3.0, 1.0].iter() {
+                let tip = x + w / 2.0 + offset + 3.0;
+
+                line.draw([tip - 3.0, middle - 3.0, tip, middle], clip, c.transform, g);
+                line.draw([tip, middle, tip - 3.0, middle + 3.0], clip, c.transform, g);
+            }
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
@@ -0,0 +1,210 @@
+// Tooltip Widget
+// Draws a small box of text near the mouse pointer, shown by the run loop when the mouse rests.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::draw_target::*;
+use crate::core::point::*;
+use crate::widget::box_widget::*;
+use crate::widget::config::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// Number of pixels between the text of a tooltip and its border.
+pub const TOOLTIP_PADDING: i32 = 4;
+
+/// Number of pixels below the mouse pointer at which a tooltip is shown, so that it is not
+/// covered by the pointer.
+pub const TOOLTIP_OFFSET: i32 = 20;
+
+/// Calculates where a tooltip of `tooltip_size` is shown for the mouse pointer at `mouse_point`.
+/// The tooltip is shown below the pointer, moved to the left if it would extend past the right
+/// edge of the window, and shown above the pointer if it would extend past the bottom edge.
+///
+/// Example:
+/// ```
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::tooltip_widget::*;
+/// # fn main() {
+///    let window_size = Size { w: 400, h: 300 };
+///    let tooltip_size = Size { w: 100, h: 20 };
+///
+///    let origin = get_tooltip_origin(&make_point_i32(50, 50), &tooltip_size, &window_size);
+///    assert_eq!((origin.x, origin.y), (50, 70));
+///
+///    let origin = get_tooltip_origin(&make_point_i32(350, 290), &tooltip_size, &window_size);
+///    assert_eq!((origin.x, origin.y), (300, 270));
+/// # }
+/// ```
+pub fn get_tooltip_origin(
+    mouse_point: &Point,
+    tooltip_size: &crate::core::point::Size,
+    window_size: &crate::core::point::Size,
+) -> Point {
+    let mut x = mouse_point.x;
+    let mut y = mouse_point.y + TOOLTIP_OFFSET;
+
+    if x + tooltip_size.w > window_size.w {
+        x = window_size.w - tooltip_size.w;
+    }
+
+    if y + tooltip_size.h > window_size.h {
+        y = mouse_point.y - tooltip_size.h;
+    }
+
+    Point {
+        x: x.max(0),
+        y: y.max(0),
+    }
+}
+
+/// This is the `TooltipWidget`, which draws a line of text inside of a bordered box.  It is
+/// created and managed by `Pushrod` when tooltips are enabled with `Pushrod::enable_tooltips`,
+/// so applications do not normally create one themselves.
+///
+/// Setting the tooltip of a `TooltipWidget` with `set_tooltip` changes the text that it displays,
+/// and resizes the widget to fit the text.  The widget lets mouse events pass through, so that
+/// it never takes the hover away from the `Widget` that it describes.
+pub struct TooltipWidget {
+    config: Configurable,
+    base_widget: BoxWidget,
+    text_widget: TextWidget,
+}
+
+/// Implementation of the constructor for the `TooltipWidget`.
+impl TooltipWidget {
+    /// Creates a new `TooltipWidget` object, requiring the current `PistonWindow`'s factory
+    /// object, the name of the font (filename in the `assets` directory), and the font size.
+    /// The tooltip starts out hidden.
+    pub fn new(factory: &mut GfxFactory, font_name: String, font_size: u32) -> Self {
+        let mut base_widget = BoxWidget::new();
+        let mut text_widget =
+            TextWidget::new(factory, font_name, String::new(), font_size, TextJustify::Center);
+        let mut config = Configurable::new();
+
+        base_widget.set_color([1.0, 1.0, 0.85, 1.0]);
+        base_widget.set_border([0.0, 0.0, 0.0, 1.0], 1);
+        text_widget.set_text_color([0.0, 0.0, 0.0, 1.0]);
+        config.set(Hidden);
+        config.set(PassThrough);
+
+        Self {
+            config,
+            base_widget,
+            text_widget,
+        }
+    }
+
+    /// Sets the color of the text for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.text_widget.set_text_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the text for this `Widget`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.text_widget.get_text_color()
+    }
+
+    /// Sets the color and thickness of the border for this `Widget`.
+    pub fn set_border(&mut self, color: types::Color, thickness: u8) {
+        self.base_widget.set_border(color, thickness);
+        self.invalidate();
+    }
+}
+
+/// Implementation of the `TooltipWidget` object with the `Widget` traits implemented.
+/// The base widget is a `BoxWidget`, with a `TextWidget` drawn over the top.
+impl Widget for TooltipWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Tooltip
+    }
+
+    fn default_accessible_name(&mut self) -> String {
+        self.text_widget.get_text().to_string()
+    }
+
+    /// Sets the `Point` of origin for this widget and its components, given the X and Y
+    /// coordinates.  Invalidates the widget afterward.
+    fn set_origin(&mut self, x: i32, y: i32) {
+        self.config().set(Origin(Point { x, y }));
+        self.base_widget.set_origin(x, y);
+        self.text_widget.set_origin(x, y);
+        self.invalidate();
+    }
+
+    /// Sets the `Size` for this widget and its components, given width and height.  Invalidates
+    /// the widget afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+        self.base_widget.set_size(w, h);
+        self.text_widget.set_size(w, h);
+        self.invalidate();
+    }
+
+    /// Sets the background color for this widget.  Invalidates the widget afterward.
+    fn set_color(&mut self, color: types::Color) {
+        self.config().set(MainColor(color));
+        self.base_widget.set_color(color);
+        self.invalidate();
+    }
+
+    /// Retrieves the background color of this widget.
+    fn get_color(&mut self) -> types::Color {
+        self.base_widget.get_color()
+    }
+
+    /// Sets the text that this tooltip displays, and resizes the widget to fit it.
+    fn set_tooltip(&mut self, text: &str) {
+        self.config().set(TooltipText(text.to_string()));
+        self.text_widget.set_text(text.to_string());
+
+        let w = self.text_widget.get_text_width() + TOOLTIP_PADDING * 2;
+        let h = self.text_widget.get_font_size() as i32 + TOOLTIP_PADDING * 2;
+
+        self.set_size(w, h);
+    }
+
+    /// Passes the new `Theme` on to the base widget and text, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.base_widget.theme_changed(theme);
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the text, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.text_widget.scale_factor_changed(scale_factor);
+        self.invalidate();
+    }
+
+    /// Draws the contents of the widget in this order:
+    ///
+    /// - Base widget first
+    /// - Tooltip text
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        self.base_widget.draw(c, g, clip);
+        self.text_widget.draw(c, g, clip);
+
+        self.clear_invalidate();
+    }
+}
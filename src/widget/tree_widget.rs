@@ -0,0 +1,968 @@
+// Tree Widget
+// Extensible widget for the widget library - handles a hierarchy of expandable nodes.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::accessibility::*;
+use crate::core::callbacks::*;
+use crate::core::draw_target::*;
+use crate::widget::config::*;
+use crate::widget::font_cache::*;
+use crate::widget::image_widget::*;
+use crate::widget::text_widget::*;
+use crate::widget::theme::*;
+use crate::widget::widget::*;
+
+/// The identifier of a node of a `TreeWidget`.  Identifiers are never reused by the same tree,
+/// even once their nodes have been removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(pub usize);
+
+/// The invisible root of every `TreeWidget`.  Nodes that are added to it are shown at the top
+/// level of the tree.
+pub const ROOT_NODE: NodeId = NodeId(0);
+
+/// Closure type that is called with the selected node of a `TreeWidget` when its selection
+/// changes, or `None` if no node is selected.
+pub type NodeSelectionCallback = Box<dyn FnMut(Option<NodeId>)>;
+
+/// Closure type that is called with the node of a `TreeWidget` that was activated.
+pub type NodeCallback = Box<dyn FnMut(NodeId)>;
+
+/// Closure type that is called with the `TreeWidget`, and a node of it that is expanded for the
+/// first time, so that the children of the node can be added.
+pub type NodeExpandCallback = Box<dyn FnMut(&mut TreeWidget, NodeId)>;
+
+/// The default height of the rows of a `TreeWidget`, in pixels.
+pub const DEFAULT_TREE_ROW_HEIGHT: i32 = 24;
+
+/// The number of pixels by which each level of a `TreeWidget` is indented, which is also the
+/// width of the disclosure triangle in front of each node.
+pub const TREE_INDENT: i32 = 16;
+
+/// The space between the icon of a node and its top and bottom, in pixels.
+const TREE_ICON_PADDING: i32 = 4;
+
+/// The space between the icon or the triangle of a node and its label, in pixels.
+const TREE_LABEL_GAP: i32 = 4;
+
+/// How far the background of a hovered row is blended toward the accent color of the current
+/// `Theme`.
+const TREE_HOVER_AMOUNT: f32 = 0.2;
+
+/// A node of a `TreeWidget`.
+struct TreeNode {
+    label: String,
+    icon: Option<Box<ImageWidget>>,
+    parent: NodeId,
+    children: Vec<NodeId>,
+    expanded: bool,
+    expandable: bool,
+    populated: bool,
+}
+
+/// This is the `TreeWidget`, which displays a hierarchy of nodes, each with a label, an optional
+/// icon, and children.  The children of a node are only shown while it is expanded, indented
+/// below it, and a node with children shows a disclosure triangle, which expands or collapses it
+/// when clicked.  While the tree is focused, `Up` and `Down` move the selection, `Right`
+/// expands the selected node, or moves to its first child, and `Left` collapses it, or moves
+/// to its parent.
+///
+/// Nodes are identified by the `NodeId` that `add_node` returns, and can be added, removed
+/// and changed at any time.  The `on_selection_changed` callback is called every time the
+/// selection changes, and `on_node_activated` when a node is double-clicked, or `Enter` is
+/// pressed.  Collapsing a node that contains the selected node selects the collapsed node
+/// instead, and removing the selected node clears the selection.
+///
+/// Large trees can be filled in as they are opened: a node that is made expandable with
+/// `set_expandable` shows a triangle before it has any children, and the `on_expand` callback
+/// is called the first time each node is expanded, so that its children can be added then.
+///
+/// Like the `ListWidget`, the tree scrolls itself with the mouse wheel, and to keep the
+/// selected node in view, and only the rows that can be seen are drawn.
+///
+/// Example usage:
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::widget::tree_widget::*;
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Tree", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let font_name = "OpenSans-Regular.ttf".to_string();
+///    let mut tree = TreeWidget::new(pushrod.get_factory(), font_name);
+///    let documents = tree.add_node(ROOT_NODE, "Documents");
+///    let pictures = tree.add_node(ROOT_NODE, "Pictures");
+///
+///    tree.add_node(documents, "Letter.txt");
+///    tree.set_icon(pictures, Some("folder.png"));
+///    tree.set_expandable(pictures, true);
+///    tree.expand(documents);
+///
+///    // The pictures are only listed when their folder is first opened.
+///    tree.on_expand(Box::new(|tree, node| {
+///        for number in 1..=3 {
+///            tree.add_node(node, &format!("Picture {}.png", number));
+///        }
+///    }));
+///
+///    tree.set_origin(20, 20);
+///    tree.set_size(200, 240);
+///    tree.on_selection_changed(Box::new(|node| eprintln!("Selected: {:?}", node)));
+///    pushrod.add_widget("Tree", Box::new(tree));
+/// # }
+/// ```
+pub struct TreeWidget {
+    config: Configurable,
+    factory: GfxFactory,
+    text_widget: Box<TextWidget>,
+    nodes: Vec<Option<TreeNode>>,
+    rows: Vec<(NodeId, i32)>,
+    row_height: i32,
+    scroll_y: i32,
+    scale_factor: f64,
+    hovered_row: Option<usize>,
+    selected_node: Option<NodeId>,
+    on_selection_changed: Option<NodeSelectionCallback>,
+    on_node_activated: Option<NodeCallback>,
+    on_expand: Option<NodeExpandCallback>,
+}
+
+/// Implementation of the constructor for the `TreeWidget`.
+impl TreeWidget {
+    /// Creates a new tree without any nodes, drawn with the font `font_name` from the `assets`
+    /// directory, in the font size of the current `Theme`.  The tree can be focused, so that it
+    /// can be moved through from the keyboard.  Panics if the font cannot be loaded; use
+    /// `font_cache::load_font` and `from_font` to handle the error instead.
+    pub fn new(factory: &mut GfxFactory, font_name: String) -> Self {
+        let glyphs = load_font(factory, &font_name).unwrap_or_else(|error| panic!("{}", error));
+
+        Self::from_font(factory, glyphs)
+    }
+
+    /// Creates a new tree without any nodes that draws its labels in a font from the font
+    /// cache.  The icons of the nodes are loaded with `factory`.
+    ///
+    /// Example:
+    /// ```
+    /// # use piston_window::*;
+    /// # use pushrod::widget::font_cache::*;
+    /// # use pushrod::widget::tree_widget::*;
+    /// # fn collapse_parent(factory: &mut GfxFactory, font: SharedGlyphs) {
+    ///    let mut tree = TreeWidget::from_font(factory, font);
+    ///    let fruit = tree.add_node(ROOT_NODE, "Fruit");
+    ///    let apple = tree.add_node(fruit, "Apple");
+    ///
+    ///    tree.expand(fruit);
+    ///    tree.set_selected_node(Some(apple));
+    ///    assert_eq!(tree.get_visible_nodes(), vec![fruit, apple]);
+    ///
+    ///    // Collapsing the parent of the selected node selects the parent instead.
+    ///    tree.collapse(fruit);
+    ///    assert_eq!(tree.get_visible_nodes(), vec![fruit]);
+    ///    assert_eq!(tree.get_selected_node(), Some(fruit));
+    ///
+    ///    // Removing the selected node clears the selection.
+    ///    tree.remove_node(fruit);
+    ///    assert_eq!(tree.get_selected_node(), None);
+    ///    assert!(tree.get_visible_nodes().is_empty());
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn from_font(factory: &mut GfxFactory, font: SharedGlyphs) -> Self {
+        let font_size = get_current_theme().font_size;
+        let mut text_widget =
+            TextWidget::from_font(font, String::new(), font_size, TextJustify::Left);
+
+        text_widget.set_ellipsize(true);
+
+        let root = TreeNode {
+            label: String::new(),
+            icon: None,
+            parent: ROOT_NODE,
+            children: Vec::new(),
+            expanded: true,
+            expandable: true,
+            populated: true,
+        };
+        let mut widget = Self {
+            config: Configurable::new(),
+            factory: factory.clone(),
+            text_widget: Box::new(text_widget),
+            nodes: vec![Some(root)],
+            rows: Vec::new(),
+            row_height: DEFAULT_TREE_ROW_HEIGHT,
+            scroll_y: 0,
+            scale_factor: 1.0,
+            hovered_row: None,
+            selected_node: None,
+            on_selection_changed: None,
+            on_node_activated: None,
+            on_expand: None,
+        };
+
+        widget.set_focusable(true);
+        widget.set_consumes_arrows(false, true);
+        widget
+    }
+
+    /// Sets the closure that is called with the selected node when the selection changes.
+    pub fn on_selection_changed(&mut self, callback: NodeSelectionCallback) {
+        self.on_selection_changed = Some(callback);
+    }
+
+    /// Sets the closure that is called with the node that is double-clicked, or that is
+    /// selected when `Enter` is pressed.
+    pub fn on_node_activated(&mut self, callback: NodeCallback) {
+        self.on_node_activated = Some(callback);
+    }
+
+    /// Sets the closure that is called with a node the first time it is expanded, just before
+    /// its children are shown, so that they can be added with `add_node`.
+    pub fn on_expand(&mut self, callback: NodeExpandCallback) {
+        self.on_expand = Some(callback);
+    }
+
+    /// Sets the color of the labels for this `Widget`.
+    pub fn set_text_color(&mut self, color: types::Color) {
+        self.config().set(TextColor(color));
+        self.invalidate();
+    }
+
+    /// Retrieves the color of the labels for this `Widget`.
+    /// Defaults to the text color of the current `Theme` if not set.  The label of the selected
+    /// node is drawn in the background color of the current `Theme`.
+    pub fn get_text_color(&mut self) -> types::Color {
+        self.config()
+            .get_or(TextColor(get_current_theme().text_color))
+            .0
+    }
+
+    /// Adds a node with a `label` after the other children of `parent`, and returns its ID.  The
+    /// node is collapsed, and has no icon.  Use `ROOT_NODE` as the parent to add a node at the
+    /// top level.  Panics if `parent` is not a node of the tree.
+    pub fn add_node(&mut self, parent: NodeId, label: &str) -> NodeId {
+        let node = NodeId(self.nodes.len());
+
+        self.get_node_mut(parent)
+            .unwrap_or_else(|| panic!("{:?} is not a node of the tree", parent))
+            .children
+            .push(node);
+        self.nodes.push(Some(TreeNode {
+            label: label.to_string(),
+            icon: None,
+            parent,
+            children: Vec::new(),
+            expanded: false,
+            expandable: false,
+            populated: false,
+        }));
+        self.refresh_rows();
+        node
+    }
+
+    /// Removes a node, along with all of its children.  If the selected node is removed, the
+    /// selection is cleared.  Returns `false` if there is no such node, or it is the root.
+    pub fn remove_node(&mut self, node: NodeId) -> bool {
+        if node == ROOT_NODE || !self.contains_node(node) {
+            return false;
+        }
+
+        if let Some(selected) = self.selected_node {
+            if self.is_ancestor_or_self(node, selected) {
+                self.set_selected_node(None);
+            }
+        }
+
+        let parent = self.nodes[node.0]
+            .as_ref()
+            .map(|tree_node| tree_node.parent);
+
+        if let Some(parent) = parent.and_then(|parent| self.get_node_mut(parent)) {
+            parent.children.retain(|child| *child != node);
+        }
+
+        let mut removed = vec![node];
+
+        while let Some(removed_node) = removed.pop() {
+            if let Some(tree_node) = self.nodes[removed_node.0].take() {
+                removed.extend(tree_node.children);
+            }
+        }
+
+        self.refresh_rows();
+        true
+    }
+
+    /// Removes all of the nodes of the tree.
+    pub fn clear(&mut self) {
+        let children = self.get_children(ROOT_NODE).to_vec();
+
+        for child in children {
+            self.remove_node(child);
+        }
+    }
+
+    /// Indicates whether `node` is a node of the tree.
+    pub fn contains_node(&self, node: NodeId) -> bool {
+        self.get_node(node).is_some()
+    }
+
+    /// Sets the label of a node, and invalidates only the row of the node, if it can be seen.
+    pub fn set_label(&mut self, node: NodeId, label: &str) {
+        if let Some(tree_node) = self.get_node_mut(node) {
+            tree_node.label = label.to_string();
+            self.invalidate_node(node);
+        }
+    }
+
+    /// Retrieves the label of a node, or `None` if there is no such node.
+    pub fn get_label(&self, node: NodeId) -> Option<&str> {
+        self.get_node(node)
+            .map(|tree_node| tree_node.label.as_str())
+    }
+
+    /// Sets the icon that is drawn in front of the label of a node to the image `image_name` (a
+    /// path, or the filename of an image in the `assets` directory), or removes it.  The image
+    /// is scaled to fit the height of the row.  Invalidates the row of the node afterward.
+    pub fn set_icon(&mut self, node: NodeId, image_name: Option<&str>) {
+        let icon = image_name.map(|image_name| {
+            let mut icon = ImageWidget::new(&mut self.factory, image_name.to_string());
+
+            icon.set_scaling(ImageScaling::Fit);
+
+            if (self.scale_factor - 1.0).abs() > f64::EPSILON {
+                icon.scale_factor_changed(self.scale_factor);
+            }
+
+            Box::new(icon)
+        });
+
+        if let Some(tree_node) = self.get_node_mut(node) {
+            tree_node.icon = icon;
+            self.invalidate_node(node);
+        }
+    }
+
+    /// Retrieves the children of a node, in order.  A node that does not exist has no children.
+    pub fn get_children(&self, node: NodeId) -> &[NodeId] {
+        self.get_node(node)
+            .map(|tree_node| tree_node.children.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Retrieves the parent of a node, which is `ROOT_NODE` for nodes at the top level, or `None`
+    /// for the root, or a node that does not exist.
+    pub fn get_parent(&self, node: NodeId) -> Option<NodeId> {
+        if node == ROOT_NODE {
+            return None;
+        }
+
+        self.get_node(node).map(|tree_node| tree_node.parent)
+    }
+
+    /// Shows a disclosure triangle in front of a node, even while it has no children, so that it
+    /// can be expanded, and its children added by the `on_expand` callback.
+    pub fn set_expandable(&mut self, node: NodeId, expandable: bool) {
+        if let Some(tree_node) = self.get_node_mut(node) {
+            tree_node.expandable = expandable;
+            self.invalidate_node(node);
+        }
+    }
+
+    /// Indicates whether a node can be expanded: whether it has children, or has been made
+    /// expandable.
+    pub fn is_expandable(&self, node: NodeId) -> bool {
+        self.get_node(node)
+            .map(|tree_node| tree_node.expandable || !tree_node.children.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Expands a node, showing its children.  The first time a node is expanded, the
+    /// `on_expand` callback is called with it before its children are shown.  Invalidates the
+    /// widget if the node was collapsed.
+    pub fn expand(&mut self, node: NodeId) {
+        let populated = match self.get_node(node) {
+            Some(tree_node) if !tree_node.expanded => tree_node.populated,
+            _ => return,
+        };
+
+        if !populated {
+            if let Some(tree_node) = self.get_node_mut(node) {
+                tree_node.populated = true;
+            }
+
+            if let Some(mut callback) = self.on_expand.take() {
+                callback(self, node);
+
+                if self.on_expand.is_none() {
+                    self.on_expand = Some(callback);
+                }
+            }
+        }
+
+        if let Some(tree_node) = self.get_node_mut(node) {
+            tree_node.expanded = true;
+        }
+
+        self.refresh_rows();
+    }
+
+    /// Collapses a node, hiding its children.  If the selected node is hidden, the collapsed
+    /// node is selected instead.  Invalidates the widget if the node was expanded.
+    pub fn collapse(&mut self, node: NodeId) {
+        match self.get_node_mut(node) {
+            Some(tree_node) if tree_node.expanded && node != ROOT_NODE => {
+                tree_node.expanded = false
+            }
+            _ => return,
+        }
+
+        if let Some(selected) = self.selected_node {
+            if selected != node && self.is_ancestor_or_self(node, selected) {
+                self.set_selected_node(Some(node));
+            }
+        }
+
+        self.refresh_rows();
+    }
+
+    /// Indicates whether a node is expanded.  Defaults to `false`.
+    pub fn is_expanded(&self, node: NodeId) -> bool {
+        self.get_node(node)
+            .map(|tree_node| tree_node.expanded)
+            .unwrap_or(false)
+    }
+
+    /// Retrieves the nodes that are shown, from the top of the tree to the bottom: the nodes at
+    /// the top level, and the children of every node that is shown and expanded.
+    pub fn get_visible_nodes(&self) -> Vec<NodeId> {
+        self.rows.iter().map(|(node, _)| *node).collect()
+    }
+
+    /// Selects `node`, or clears the selection if `node` is `None`, expanding the parents of
+    /// the node, and scrolling it into view.  A node that does not exist clears the selection.
+    /// The `on_selection_changed` callback is called if the selection changed.
+    pub fn set_selected_node(&mut self, node: Option<NodeId>) {
+        let node = node.filter(|node| *node != ROOT_NODE && self.contains_node(*node));
+
+        if let Some(node) = node {
+            self.ensure_node_visible(node);
+        }
+
+        if node == self.selected_node {
+            return;
+        }
+
+        self.selected_node = node;
+
+        if let Some(callback) = &mut self.on_selection_changed {
+            callback(node);
+        }
+
+        self.invalidate();
+    }
+
+    /// Retrieves the selected node, or `None` if no node is selected.
+    pub fn get_selected_node(&self) -> Option<NodeId> {
+        self.selected_node
+    }
+
+    /// Expands the parents of `node`, and scrolls the tree as little as possible to bring it into
+    /// view.
+    pub fn ensure_node_visible(&mut self, node: NodeId) {
+        let mut parent = self.get_parent(node);
+
+        while let Some(ancestor) = parent {
+            self.expand(ancestor);
+            parent = self.get_parent(ancestor);
+        }
+
+        let row = match self.get_row_of(node) {
+            Some(row) => row,
+            None => return,
+        };
+        let top = row as i32 * self.row_height;
+        let view_h = self.get_size().h;
+        let scroll_y = if top < self.scroll_y {
+            top
+        } else if top + self.row_height > self.scroll_y + view_h {
+            top + self.row_height - view_h
+        } else {
+            self.scroll_y
+        };
+
+        self.scroll_to(0, scroll_y);
+    }
+
+    /// Returns the node that is shown at the height `y` of the widget, taking the scrolling of
+    /// the rows into account, or `None` if there is no node there.
+    pub fn get_node_at(&self, y: i32) -> Option<NodeId> {
+        self.get_row_at(y).map(|row| self.rows[row].0)
+    }
+
+    /// Sets the height of each row, in pixels.  Invalidates the widget afterward.
+    pub fn set_row_height(&mut self, row_height: i32) {
+        self.row_height = row_height.max(1);
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Retrieves the height of each row, in pixels.  Defaults to `DEFAULT_TREE_ROW_HEIGHT`.
+    pub fn get_row_height(&self) -> i32 {
+        self.row_height
+    }
+
+    fn get_node(&self, node: NodeId) -> Option<&TreeNode> {
+        self.nodes
+            .get(node.0)
+            .and_then(|tree_node| tree_node.as_ref())
+    }
+
+    fn get_node_mut(&mut self, node: NodeId) -> Option<&mut TreeNode> {
+        self.nodes
+            .get_mut(node.0)
+            .and_then(|tree_node| tree_node.as_mut())
+    }
+
+    /// Indicates whether `ancestor` is `node`, or one of its parents.
+    fn is_ancestor_or_self(&self, ancestor: NodeId, node: NodeId) -> bool {
+        let mut current = Some(node);
+
+        while let Some(current_node) = current {
+            if current_node == ancestor {
+                return true;
+            }
+
+            current = self.get_parent(current_node);
+        }
+
+        false
+    }
+
+    /// Lists the nodes that are shown again, with the depth of each, after the nodes or their
+    /// expanded states have changed, and invalidates the widget.
+    fn refresh_rows(&mut self) {
+        let mut rows = Vec::new();
+        let mut pending: Vec<(NodeId, i32)> = self
+            .get_children(ROOT_NODE)
+            .iter()
+            .rev()
+            .map(|child| (*child, 0))
+            .collect();
+
+        while let Some((node, depth)) = pending.pop() {
+            rows.push((node, depth));
+
+            if self.is_expanded(node) {
+                pending.extend(
+                    self.get_children(node)
+                        .iter()
+                        .rev()
+                        .map(|child| (*child, depth + 1)),
+                );
+            }
+        }
+
+        self.rows = rows;
+        self.hovered_row = None;
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    fn get_row_of(&self, node: NodeId) -> Option<usize> {
+        self.rows.iter().position(|(row_node, _)| *row_node == node)
+    }
+
+    fn get_row_at(&self, y: i32) -> Option<usize> {
+        let y = y + self.scroll_y;
+
+        if y < 0 {
+            return None;
+        }
+
+        let row = (y / self.row_height) as usize;
+
+        if row < self.rows.len() {
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    /// Invalidates only the row of `node`, if it can be seen.
+    fn invalidate_node(&mut self, node: NodeId) {
+        if let Some(row) = self.get_row_of(node) {
+            let size = self.get_size();
+            let y = row as i32 * self.row_height - self.scroll_y;
+
+            if y + self.row_height > 0 && y < size.h {
+                self.invalidate_area([0, y, size.w, self.row_height]);
+            }
+        }
+    }
+
+    /// Expands a collapsed node, and collapses an expanded one.
+    fn toggle_node(&mut self, node: NodeId) {
+        if self.is_expanded(node) {
+            self.collapse(node);
+        } else {
+            self.expand(node);
+        }
+    }
+
+    /// Moves the selection by `rows`, selecting the first node if none was selected.
+    fn move_selection(&mut self, rows: i32) {
+        if self.rows.is_empty() {
+            return;
+        }
+
+        let row = match self.selected_node.and_then(|node| self.get_row_of(node)) {
+            Some(current) => (current as i32 + rows).clamp(0, self.rows.len() as i32 - 1),
+            None => 0,
+        } as usize;
+        let node = self.rows[row].0;
+
+        self.set_selected_node(Some(node));
+    }
+
+    /// Expands the selected node, or moves to its first child if it is already expanded.
+    fn expand_selection(&mut self) {
+        let node = match self.selected_node {
+            Some(node) => node,
+            None => return self.move_selection(0),
+        };
+
+        if !self.is_expanded(node) {
+            if self.is_expandable(node) {
+                self.expand(node);
+            }
+        } else if let Some(child) = self.get_children(node).first().copied() {
+            self.set_selected_node(Some(child));
+        }
+    }
+
+    /// Collapses the selected node, or moves to its parent if it is already collapsed.
+    fn collapse_selection(&mut self) {
+        let node = match self.selected_node {
+            Some(node) => node,
+            None => return self.move_selection(0),
+        };
+
+        if self.is_expanded(node) {
+            self.collapse(node);
+        } else if let Some(parent) = self.get_parent(node).filter(|parent| *parent != ROOT_NODE) {
+            self.set_selected_node(Some(parent));
+        }
+    }
+
+    fn activate_node(&mut self, node: NodeId) {
+        if let Some(callback) = &mut self.on_node_activated {
+            callback(node);
+        }
+    }
+
+    fn set_hovered_row(&mut self, row: Option<usize>) {
+        if row != self.hovered_row {
+            self.hovered_row = row;
+            self.invalidate();
+        }
+    }
+}
+
+/// Implementation of the `TreeWidget` object with the `Widget` traits implemented.  The labels
+/// are drawn with a single `TextWidget`, which is given the label of each visible node in turn,
+/// and the icons with an `ImageWidget` for each node that has one.
+impl Widget for TreeWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Tree
+    }
+
+    /// The value is the label of the selected node.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        self.get_selected_node()
+            .and_then(|node| self.get_label(node))
+            .map(String::from)
+    }
+
+    /// Sets the `Size` for this widget, given width and height.  Invalidates the widget
+    /// afterward.
+    fn set_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(BodySize(crate::core::point::Size { w, h }));
+
+        let scroll_y = self.scroll_y;
+
+        self.scroll_to(0, scroll_y);
+        self.invalidate();
+    }
+
+    /// Scrolls the rows so that the height `y` of the tree is at the top of the widget, limited
+    /// to the rows that are shown.  The horizontal position is ignored.  Invalidates the widget
+    /// if the tree scrolled.
+    fn scroll_to(&mut self, _x: i32, y: i32) {
+        let max_y = (self.rows.len() as i32 * self.row_height - self.get_size().h).max(0);
+        let y = y.clamp(0, max_y);
+
+        if y != self.scroll_y {
+            self.scroll_y = y;
+            self.invalidate();
+        }
+    }
+
+    /// Enables or disables this widget.  A disabled tree is drawn in the disabled color of the
+    /// current `Theme`.  Invalidates the widget afterward.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.text_widget.set_disabled(disabled);
+        self.invalidate();
+    }
+
+    /// Scrolls the tree in response to the mouse wheel, passing the scroll on to the widget
+    /// underneath if the tree cannot be scrolled any further in that direction.  All other
+    /// events are handled by `handle_event`.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        match event {
+            CallbackEvent::MouseScrolled { delta, .. } => {
+                let previous_y = self.scroll_y;
+                let scroll_y = self.scroll_y - delta.to_points().1.round() as i32;
+
+                self.scroll_to(0, scroll_y);
+
+                if self.scroll_y == previous_y {
+                    (EventResult::NotHandled, None)
+                } else {
+                    (EventResult::Consumed, None)
+                }
+            }
+
+            _ => (EventResult::Handled, self.handle_event(event)),
+        }
+    }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                let row = self.get_row_at(point.y);
+
+                self.set_hovered_row(row);
+            }
+
+            CallbackEvent::MouseExited { .. } => self.set_hovered_row(None),
+
+            CallbackEvent::MouseClicked {
+                button: Button::Mouse(MouseButton::Left),
+                point,
+                click_count,
+                ..
+            } => {
+                if let Some(row) = self.get_row_at(point.y) {
+                    let (node, depth) = self.rows[row];
+                    let triangle_x = depth * TREE_INDENT;
+                    let on_triangle = point.x >= triangle_x && point.x < triangle_x + TREE_INDENT;
+
+                    if on_triangle && self.is_expandable(node) {
+                        self.toggle_node(node);
+                    } else if click_count == 2 {
+                        self.activate_node(node);
+                    } else {
+                        self.set_selected_node(Some(node));
+                    }
+                }
+            }
+
+            CallbackEvent::KeyPressed {
+                key,
+                state: ButtonState::Press,
+                ..
+            } => match key {
+                Key::Up => self.move_selection(-1),
+                Key::Down => self.move_selection(1),
+                Key::Right => self.expand_selection(),
+                Key::Left => self.collapse_selection(),
+                Key::Return => {
+                    if let Some(node) = self.selected_node {
+                        self.activate_node(node);
+                    }
+                }
+                _ => (),
+            },
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Passes the new `Theme` on to the labels, and invalidates the widget.
+    fn theme_changed(&mut self, theme: &Theme) {
+        self.text_widget.theme_changed(theme);
+        self.invalidate();
+    }
+
+    /// Passes the new scale factor on to the labels and the icons, so that they can load the
+    /// versions of their images for it, and invalidates the widget.
+    fn scale_factor_changed(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.text_widget.scale_factor_changed(scale_factor);
+
+        for tree_node in self.nodes.iter_mut().flatten() {
+            if let Some(icon) = &mut tree_node.icon {
+                icon.scale_factor_changed(scale_factor);
+            }
+        }
+
+        self.invalidate();
+    }
+
+    /// Draws the background, and then the rows that can be seen: the disclosure triangle of each
+    /// node that can be expanded, its icon, and its label, indented by its depth.  The selected
+    /// node is drawn in the accent color of the current `Theme`, and the hovered row in the
+    /// background color blended toward it.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let theme = get_current_theme();
+        let size = self.get_size();
+        let background = self.get_color();
+        let mut hover_color = background;
+
+        for (channel, accent) in hover_color
+            .iter_mut()
+            .zip(theme.accent_color.iter())
+            .take(3)
+        {
+            *channel += (accent - *channel) * TREE_HOVER_AMOUNT;
+        }
+
+        Rectangle::new(apply_opacity(background)).draw(
+            [0.0, 0.0, f64::from(size.w), f64::from(size.h)],
+            clip,
+            c.transform,
+            g,
+        );
+
+        let row_height = self.row_height;
+        let first_row = (self.scroll_y / row_height) as usize;
+        let last_row = ((self.scroll_y + size.h) / row_height + 1) as usize;
+        let text_color = self.get_text_color();
+        let disabled = self.is_disabled();
+
+        for row in first_row..last_row.min(self.rows.len()) {
+            let (node, depth) = self.rows[row];
+            let row_y = row as i32 * row_height - self.scroll_y;
+            let selected = self.selected_node == Some(node);
+            let row_color = if selected && !disabled {
+                Some(theme.accent_color)
+            } else if self.hovered_row == Some(row) && !disabled {
+                Some(hover_color)
+            } else {
+                None
+            };
+            let foreground = if disabled {
+                theme.disabled_color
+            } else if selected {
+                theme.background_color
+            } else {
+                text_color
+            };
+
+            if let Some(row_color) = row_color {
+                Rectangle::new(apply_opacity(row_color)).draw(
+                    [
+                        0.0,
+                        f64::from(row_y),
+                        f64::from(size.w),
+                        f64::from(row_height),
+                    ],
+                    clip,
+                    c.transform,
+                    g,
+                );
+            }
+
+            let mut x = depth * TREE_INDENT;
+
+            if self.is_expandable(node) {
+                let (arrow_x, arrow_y) = (
+                    f64::from(x + TREE_INDENT / 2),
+                    f64::from(row_y + row_height / 2),
+                );
+                let points = if self.is_expanded(node) {
+                    [
+                        [arrow_x - 4.0, arrow_y - 2.0],
+                        [arrow_x + 4.0, arrow_y - 2.0],
+                        [arrow_x, arrow_y + 2.0],
+                    ]
+                } else {
+                    [
+                        [arrow_x - 2.0, arrow_y - 4.0],
+                        [arrow_x + 2.0, arrow_y],
+                        [arrow_x - 2.0, arrow_y + 4.0],
+                    ]
+                };
+
+                Polygon::new(apply_opacity(foreground)).draw(&points, clip, c.transform, g);
+            }
+
+            x += TREE_INDENT;
+
+            let tree_node = match self.nodes[node.0].as_mut() {
+                Some(tree_node) => tree_node,
+                None => continue,
+            };
+
+            if let Some(icon) = &mut tree_node.icon {
+                let icon_size = row_height - TREE_ICON_PADDING * 2;
+
+                icon.set_size(icon_size, icon_size);
+                icon.draw(
+                    c.trans(f64::from(x), f64::from(row_y + TREE_ICON_PADDING)),
+                    g,
+                    clip,
+                );
+                x += icon_size + TREE_LABEL_GAP;
+            }
+
+            self.text_widget.set_text(&tree_node.label);
+            self.text_widget.set_text_color(foreground);
+            self.text_widget
+                .set_size((size.w - x - TREE_LABEL_GAP).max(0), row_height);
+            self.text_widget
+                .draw(c.trans(f64::from(x), f64::from(row_y)), g, clip);
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
+}
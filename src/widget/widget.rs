@@ -13,10 +13,84 @@
 // limitations under the License.
 
 use piston_window::*;
+use std::cell::Cell;
 
+use crate::core::accessibility::*;
 use crate::core::callbacks::*;
+use crate::core::draw_target::*;
 use crate::core::point::*;
+use crate::core::widget_id::*;
+use crate::event::event::*;
+use crate::widget::builder::{Buildable, WidgetBuilder};
 use crate::widget::config::*;
+use crate::widget::gradient::*;
+use crate::widget::layout_widget::{GridCell, LayoutChild};
+use crate::widget::shape::{get_rounded_rect_polygon, Shadow};
+use crate::widget::theme::*;
+
+thread_local! {
+    static DRAW_OPACITY: Cell<f32> = const { Cell::new(1.0) };
+}
+
+/// Sets the opacity that `apply_opacity` multiplies into colors.  This is set by the
+/// `WidgetStore` to the effective opacity of each `Widget` before it is drawn, so that the
+/// `Widget`, and any other `Widget`s that it draws as part of itself, are faded together.
+pub fn set_draw_opacity(opacity: f32) {
+    DRAW_OPACITY.with(|draw_opacity| draw_opacity.set(opacity));
+}
+
+/// Retrieves the opacity of the `Widget` that is currently being drawn.  Defaults to 1.0.
+pub fn get_draw_opacity() -> f32 {
+    DRAW_OPACITY.with(|draw_opacity| draw_opacity.get())
+}
+
+/// Multiplies the opacity of the `Widget` that is currently being drawn into the alpha value of
+/// a color.  `Widget`s should pass every color that they draw with through this function.
+///
+/// Example:
+/// ```
+/// # use pushrod::widget::widget::*;
+/// # fn main() {
+///    assert_eq!(apply_opacity([1.0, 0.5, 0.0, 1.0]), [1.0, 0.5, 0.0, 1.0]);
+///
+///    set_draw_opacity(0.5);
+///    assert_eq!(apply_opacity([1.0, 0.5, 0.0, 0.5]), [1.0, 0.5, 0.0, 0.25]);
+///    set_draw_opacity(1.0);
+/// # }
+/// ```
+pub fn apply_opacity(color: types::Color) -> types::Color {
+    [color[0], color[1], color[2], color[3] * get_draw_opacity()]
+}
+
+/// Fills `rect` (X, Y, width, height) with the gradient of `widget`, if it has one, or else with
+/// its color, at the current draw opacity, with its corners rounded by its corner radius.  This is
+/// how the `CanvasWidget` and the `BoxWidget` paint their bounds.
+pub fn draw_fill<W: Widget + ?Sized>(
+    widget: &mut W,
+    rect: [f64; 4],
+    c: Context,
+    g: &mut DrawTarget,
+    clip: &DrawState,
+) {
+    let radius = widget.get_corner_radius();
+
+    if radius > 0.0 {
+        let polygon = get_rounded_rect_polygon(rect, radius);
+
+        match widget.get_gradient() {
+            Some(gradient) => gradient.draw_polygon(&polygon, clip, c.transform, g),
+            None => Polygon::new(apply_opacity(widget.get_color()))
+                .draw(&polygon, clip, c.transform, g),
+        }
+
+        return;
+    }
+
+    match widget.get_gradient() {
+        Some(gradient) => gradient.draw(rect, clip, c.transform, g),
+        None => Rectangle::new(apply_opacity(widget.get_color())).draw(rect, clip, c.transform, g),
+    }
+}
 
 /// Implementable trait that is used by every `Widget`.  These are the public methods,
 /// and a function _may_ override them.
@@ -53,9 +127,83 @@ pub trait Widget {
         self.config().set(Invalidate);
     }
 
+    /// Indicates that only `area` of the widget, in its own coordinates (X, Y, width, height),
+    /// needs to be redrawn, so that the `WidgetStore` only repaints that part of the window.
+    /// Areas that are invalidated before the widget is drawn are combined, and a widget that has
+    /// already been invalidated as a whole stays invalidated as a whole.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut canvas = CanvasWidget::new();
+    ///
+    ///    canvas.set_origin(200, 200);
+    ///    canvas.set_size(100, 20);
+    ///
+    ///    let canvas_id = widget_store.add_widget("Canvas", Box::new(canvas));
+    ///
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///    widget_store.prepare_draw(WidgetId::ROOT);
+    ///
+    ///    let canvas = widget_store.get_widget_for_id(canvas_id);
+    ///
+    ///    canvas.borrow_mut().invalidate_area([10, 0, 20, 20]);
+    ///    canvas.borrow_mut().invalidate_area([50, 0, 10, 20]);
+    ///    assert_eq!(canvas.borrow_mut().get_invalidated_area(), Some([10, 0, 50, 20]));
+    ///    assert_eq!(
+    ///        widget_store.prepare_draw(WidgetId::ROOT),
+    ///        vec![(canvas_id, make_rect(210, 200, 50, 20))]
+    ///    );
+    ///
+    ///    // Changing the widget itself invalidates all of it.
+    ///    let canvas = widget_store.get_widget_for_id(canvas_id);
+    ///
+    ///    canvas.borrow_mut().invalidate_area([10, 0, 20, 20]);
+    ///    canvas.borrow_mut().set_color([1.0, 0.0, 0.0, 1.0]);
+    ///    assert_eq!(canvas.borrow_mut().get_invalidated_area(), None);
+    /// # }
+    /// ```
+    fn invalidate_area(&mut self, area: [i32; 4]) {
+        let current = self.config().get::<InvalidatedArea>().map(|current| current.0);
+        let area = match current {
+            Some(current) => Rect::from(current).union(&Rect::from(area)).into(),
+            None if self.is_invalidated() => return,
+            None => area,
+        };
+
+        self.config().set(InvalidatedArea(area));
+    }
+
+    /// Retrieves the area of the widget that needs to be redrawn, in its own coordinates, if only
+    /// part of it has been invalidated with `invalidate_area`.  Returns `None` if the whole
+    /// widget needs to be redrawn, or if it has not been invalidated at all.
+    fn get_invalidated_area(&mut self) -> Option<[i32; 4]> {
+        if !self.is_invalidated() {
+            return None;
+        }
+
+        self.config().get::<InvalidatedArea>().map(|area| area.0)
+    }
+
     /// Clears the invalidation flag.
     fn clear_invalidate(&mut self) {
         self.config().remove::<Invalidate>();
+        self.config().remove::<InvalidatedArea>();
+    }
+
+    /// Sets or clears the invalidation flag.  The `WidgetStore` clears the flag for each widget
+    /// that it is about to draw.
+    fn set_invalidated(&mut self, invalidated: bool) {
+        if invalidated {
+            self.invalidate();
+        } else {
+            self.clear_invalidate();
+        }
     }
 
     /// Checks to see whether or not the widget needs to be redrawn/refreshed.
@@ -73,10 +221,8 @@ pub trait Widget {
     /// Defaults to origin (0, 0) if not set.
     fn get_origin(&mut self) -> Point {
         self.config()
-            .get::<Origin>()
-            .unwrap_or(&Origin(Point { x: 0, y: 0 }))
+            .get_or(Origin(Point { x: 0, y: 0 }))
             .0
-            .clone()
     }
 
     /// Sets the `Size` for this widget, given a width and height.  Invalidates the widget afterward.
@@ -90,10 +236,16 @@ pub trait Widget {
     /// Defaults to size (0, 0) if not set.
     fn get_size(&mut self) -> crate::core::point::Size {
         self.config()
-            .get::<BodySize>()
-            .unwrap_or(&BodySize(crate::core::point::Size { w: 0, h: 0 }))
+            .get_or(BodySize(crate::core::point::Size { w: 0, h: 0 }))
             .0
-            .clone()
+    }
+
+    /// Retrieves the bounds of this widget: its origin and size, relative to its parent.
+    fn get_bounds(&mut self) -> Rect {
+        Rect {
+            origin: self.get_origin(),
+            size: self.get_size(),
+        }
     }
 
     /// Sets the color for this widget.  Invalidates the widget afterward.
@@ -103,11 +255,10 @@ pub trait Widget {
     }
 
     /// Retrieves the color of this widget.
-    /// Defaults to white color `[1.0; 4]` if not set.
+    /// Defaults to the background color of the current `Theme` if not set.
     fn get_color(&mut self) -> types::Color {
         self.config()
-            .get::<MainColor>()
-            .unwrap_or(&MainColor([1.0; 4]))
+            .get_or(MainColor(get_current_theme().background_color))
             .0
     }
 
@@ -118,14 +269,686 @@ pub trait Widget {
     }
 
     /// Retrieves the secondary color of this widget.
-    /// Defaults to black color `[0.0, 0.0, 0.0, 1.0]` if not set.
+    /// Defaults to the accent color of the current `Theme` if not set.
     fn get_secondary_color(&mut self) -> types::Color {
         self.config()
-            .get::<SecondaryColor>()
-            .unwrap_or(&SecondaryColor([1.0; 4]))
+            .get_or(SecondaryColor(get_current_theme().accent_color))
             .0
     }
 
+    /// Fills this widget with a gradient that blends from its color to `color`, along
+    /// `direction`, instead of a single color.  Invalidates the widget afterward.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::widget::gradient::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut canvas = CanvasWidget::new();
+    ///
+    ///    canvas.set_color([1.0, 1.0, 1.0, 1.0]);
+    ///    assert_eq!(canvas.get_gradient(), None);
+    ///
+    ///    canvas.set_gradient([0.0, 0.0, 0.5, 1.0], GradientDirection::Angle(30.0));
+    ///    assert_eq!(
+    ///        canvas.get_gradient(),
+    ///        Some(Gradient::new(
+    ///            GradientDirection::Angle(30.0),
+    ///            [1.0, 1.0, 1.0, 1.0],
+    ///            [0.0, 0.0, 0.5, 1.0]
+    ///        ))
+    ///    );
+    ///
+    ///    canvas.clear_gradient();
+    ///    assert_eq!(canvas.get_gradient(), None);
+    /// # }
+    /// ```
+    fn set_gradient(&mut self, color: types::Color, direction: GradientDirection) {
+        self.config().set(GradientColor(color));
+        self.config().set(GradientOrientation(direction));
+        self.invalidate();
+    }
+
+    /// Removes the gradient from this widget, so that it is filled with its color again.
+    /// Invalidates the widget afterward.
+    fn clear_gradient(&mut self) {
+        self.config().remove::<GradientColor>();
+        self.config().remove::<GradientOrientation>();
+        self.invalidate();
+    }
+
+    /// Retrieves the gradient that this widget is filled with, from its color to its gradient
+    /// color, or `None` if it is filled with a single color.
+    fn get_gradient(&mut self) -> Option<Gradient> {
+        let end = self.config().get::<GradientColor>()?.0;
+        let direction = self
+            .config()
+            .get_or(GradientOrientation(GradientDirection::Vertical))
+            .0;
+
+        Some(Gradient::new(direction, self.get_color(), end))
+    }
+
+    /// Sets the radius, in pixels, of the rounded corners of this widget's background and
+    /// border.  A radius of 0.0 gives square corners.  Invalidates the widget afterward.
+    fn set_corner_radius(&mut self, radius: f64) {
+        self.config().set(CornerRadius(radius));
+        self.invalidate();
+    }
+
+    /// Retrieves the radius of the rounded corners of this widget.
+    /// Defaults to 0.0 if not set.
+    fn get_corner_radius(&mut self) -> f64 {
+        self.config().get_or(CornerRadius(0.0)).0
+    }
+
+    /// Sets whether the corners of this widget that are cut away by its corner radius are left
+    /// out of hit-testing, so that clicks in them reach the widgets underneath.  Off by default.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut rounded = CanvasWidget::new();
+    ///
+    ///    rounded.set_origin(100, 100);
+    ///    rounded.set_size(100, 40);
+    ///    rounded.set_corner_radius(20.0);
+    ///
+    ///    let rounded_id = widget_store.add_widget("Rounded", Box::new(rounded));
+    ///    let corner = make_point_i32(101, 101);
+    ///    let middle = make_point_i32(150, 101);
+    ///
+    ///    assert_eq!(widget_store.get_widget_ids_for_point(corner.clone())[0], rounded_id);
+    ///
+    ///    widget_store
+    ///        .get_widget_for_id(rounded_id)
+    ///        .borrow_mut()
+    ///        .set_rounded_hit_test(true);
+    ///
+    ///    assert_eq!(widget_store.get_widget_ids_for_point(corner), vec![WidgetId::ROOT]);
+    ///    assert_eq!(widget_store.get_widget_ids_for_point(middle)[0], rounded_id);
+    /// # }
+    /// ```
+    fn set_rounded_hit_test(&mut self, rounded: bool) {
+        if rounded {
+            self.config().set(RoundedHitTest);
+        } else {
+            self.config().remove::<RoundedHitTest>();
+        }
+    }
+
+    /// Indicates whether the rounded corners of this widget are left out of hit-testing.
+    fn is_rounded_hit_test(&mut self) -> bool {
+        self.config().contains_key::<RoundedHitTest>()
+    }
+
+    /// Casts a drop shadow of `color` underneath this widget, moved by `offset`, and faded out
+    /// over `blur` pixels.  The shadow has the same shape as the widget, including its rounded
+    /// corners, and is drawn outside of its bounds, but not outside of the bounds of its parents.
+    /// Invalidates the widget afterward.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_store::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut widget_store = WidgetStore::new();
+    ///    let mut popup = CanvasWidget::new();
+    ///
+    ///    popup.set_origin(100, 100);
+    ///    popup.set_size(100, 40);
+    ///    popup.set_shadow([0.0, 0.0, 0.0, 0.4], make_point_i32(0, 4), 8.0);
+    ///
+    ///    let popup_id = widget_store.add_widget("Popup", Box::new(popup));
+    ///
+    ///    // The shadow is painted, and repainted, along with the widget.
+    ///    assert_eq!(widget_store.get_clip_bounds(popup_id), Some(make_rect(96, 100, 108, 48)));
+    ///
+    ///    widget_store.get_widget_for_id(popup_id).borrow_mut().clear_shadow();
+    ///    assert_eq!(widget_store.get_clip_bounds(popup_id), Some(make_rect(100, 100, 100, 40)));
+    /// # }
+    /// ```
+    fn set_shadow(&mut self, color: types::Color, offset: Point, blur: f64) {
+        self.config().set(ShadowColor(color));
+        self.config().set(ShadowOffset(offset));
+        self.config().set(ShadowBlur(blur));
+        self.invalidate();
+    }
+
+    /// Removes the drop shadow from this widget.  Invalidates the widget afterward.
+    fn clear_shadow(&mut self) {
+        self.config().remove::<ShadowColor>();
+        self.config().remove::<ShadowOffset>();
+        self.config().remove::<ShadowBlur>();
+        self.invalidate();
+    }
+
+    /// Retrieves the drop shadow of this widget, or `None` if it casts no shadow.
+    fn get_shadow(&mut self) -> Option<Shadow> {
+        let color = self.config().get::<ShadowColor>()?.0;
+        let offset = self.config().get_or(ShadowOffset(make_origin_point())).0;
+        let blur = self.config().get_or(ShadowBlur(0.0)).0;
+
+        Some(Shadow {
+            color,
+            offset,
+            blur,
+        })
+    }
+
+    /// Shows or hides this widget.  Hidden widgets, and their children, are not drawn, and do not
+    /// receive mouse events or keyboard focus.  Invalidates the widget afterward.  To also repaint
+    /// the area that a hidden widget used to cover, use `WidgetStore::set_widget_visible`.
+    fn set_visible(&mut self, visible: bool) {
+        if visible {
+            self.config().remove::<Hidden>();
+        } else {
+            self.config().set(Hidden);
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether or not this widget is visible.  Defaults to `true`.
+    fn is_visible(&mut self) -> bool {
+        !self.config().contains_key::<Hidden>()
+    }
+
+    /// Disables or enables this widget.  Disabled widgets, and their children, do not receive
+    /// mouse or keyboard events, or keyboard focus.  Invalidates the widget afterward, so that it
+    /// can be drawn greyed out.  To also notify the widget with a `DisabledChanged` event, and
+    /// to update the mouse and focus state, use `WidgetStore::set_widget_disabled`.
+    fn set_disabled(&mut self, disabled: bool) {
+        if disabled {
+            self.config().set(Disabled);
+        } else {
+            self.config().remove::<Disabled>();
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether or not this widget is disabled.  Defaults to `false`.
+    fn is_disabled(&mut self) -> bool {
+        self.config().contains_key::<Disabled>()
+    }
+
+    /// Sets whether or not this widget is clipped to its own bounds when it is drawn.  Widgets are
+    /// always clipped to the bounds of their parents.  Unclipped widgets repaint more of the
+    /// window when they change, as the `WidgetStore` cannot tell how far they draw.
+    fn set_clipped(&mut self, clipped: bool) {
+        if clipped {
+            self.config().remove::<Unclipped>();
+        } else {
+            self.config().set(Unclipped);
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether or not this widget is clipped to its own bounds.  Defaults to `true`.
+    fn is_clipped(&mut self) -> bool {
+        !self.config().contains_key::<Unclipped>()
+    }
+
+    /// Sets whether or not this widget can receive keyboard focus using Tab and Shift+Tab.
+    fn set_focusable(&mut self, focusable: bool) {
+        if focusable {
+            self.config().set(Focusable);
+        } else {
+            self.config().remove::<Focusable>();
+        }
+    }
+
+    /// Indicates whether or not this widget can receive keyboard focus using Tab and Shift+Tab.
+    /// Defaults to `false`.
+    fn is_focusable(&mut self) -> bool {
+        self.config().contains_key::<Focusable>()
+    }
+
+    /// Sets whether or not this widget handles the Tab key itself while it has focus.  If set,
+    /// Tab key events are sent to this widget, rather than moving the focus.
+    fn set_consumes_tab(&mut self, consumes_tab: bool) {
+        if consumes_tab {
+            self.config().set(ConsumesTab);
+        } else {
+            self.config().remove::<ConsumesTab>();
+        }
+    }
+
+    /// Indicates whether or not this widget handles the Tab key itself while it has focus.
+    /// Defaults to `false`.
+    fn consumes_tab(&mut self) -> bool {
+        self.config().contains_key::<ConsumesTab>()
+    }
+
+    /// Sets whether or not this widget handles the left and right, and the up and down arrow
+    /// keys itself while it has focus, such as a list that moves its selection up and down.  A
+    /// gamepad sends the arrow keys that are handled to this widget, and moves the focus with
+    /// the others.
+    fn set_consumes_arrows(&mut self, horizontal: bool, vertical: bool) {
+        if horizontal || vertical {
+            self.config().set(ConsumesArrows(horizontal, vertical));
+        } else {
+            self.config().remove::<ConsumesArrows>();
+        }
+    }
+
+    /// Indicates whether or not this widget handles the arrow key `key` itself while it has
+    /// focus.  Defaults to `false` for every key.
+    fn consumes_arrow_key(&mut self, key: Key) -> bool {
+        match self.config().get::<ConsumesArrows>() {
+            Some(ConsumesArrows(horizontal, vertical)) => match key {
+                Key::Left | Key::Right => *horizontal,
+                Key::Up | Key::Down => *vertical,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Sets the opacity of this widget, between 0.0 (fully transparent) and 1.0 (fully opaque).
+    /// Values outside of that range are clamped.  The opacity also applies to the widget's
+    /// children.  Fully transparent widgets are not drawn, but still receive mouse events, unless
+    /// they are set to let mouse events pass through with `set_pass_through`.  Invalidates the
+    /// widget afterward.
+    fn set_opacity(&mut self, opacity: f32) {
+        self.config().set(Opacity(opacity.clamp(0.0, 1.0)));
+    }
+
+    /// Retrieves the opacity of this widget.  Defaults to 1.0.
+    fn get_opacity(&mut self) -> f32 {
+        self.config()
+            .get::<Opacity>()
+            .map(|opacity| opacity.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Sets the mouse cursor that is shown while the mouse is over this widget.
+    fn set_cursor(&mut self, cursor: CursorStyle) {
+        self.config().set(Cursor(cursor));
+    }
+
+    /// Retrieves the mouse cursor that is shown while the mouse is over this widget.  Defaults
+    /// to `CursorStyle::Default`.
+    fn get_cursor(&mut self) -> CursorStyle {
+        self.config()
+            .get::<Cursor>()
+            .map(|cursor| cursor.0)
+            .unwrap_or(CursorStyle::Default)
+    }
+
+    /// Sets the tooltip text that is shown when the mouse rests over this widget, once tooltips
+    /// have been enabled with `Pushrod::enable_tooltips`.  An empty string removes the tooltip.
+    fn set_tooltip(&mut self, text: &str) {
+        if text.is_empty() {
+            self.config().remove::<TooltipText>();
+        } else {
+            self.config().set(TooltipText(text.to_string()));
+        }
+    }
+
+    /// Retrieves the tooltip text of this widget, if it has one.
+    fn get_tooltip(&mut self) -> Option<String> {
+        self.config()
+            .get::<TooltipText>()
+            .map(|tooltip| tooltip.0.clone())
+    }
+
+    /// Sets the role that assistive tools are told this widget has, instead of the role of its
+    /// type, such as `AccessibleRole::Button` for a `CanvasWidget` that draws a button.
+    fn set_accessible_role(&mut self, role: AccessibleRole) {
+        self.config().set(AccessibilityRole(role));
+    }
+
+    /// Retrieves the role that assistive tools are told this widget has.  Defaults to
+    /// `default_accessible_role`.
+    fn get_accessible_role(&mut self) -> AccessibleRole {
+        match self.config().get::<AccessibilityRole>() {
+            Some(role) => role.0,
+            None => self.default_accessible_role(),
+        }
+    }
+
+    /// Retrieves the role of this type of widget.  `Widget`s override this to describe
+    /// themselves.  Defaults to `AccessibleRole::Generic`.
+    fn default_accessible_role(&mut self) -> AccessibleRole {
+        AccessibleRole::Generic
+    }
+
+    /// Sets the name that assistive tools read out for this widget, instead of its text, such as
+    /// for a button that only shows an icon.  An empty string removes the name.
+    fn set_accessible_name(&mut self, name: &str) {
+        if name.is_empty() {
+            self.config().remove::<AccessibilityName>();
+        } else {
+            self.config().set(AccessibilityName(name.to_string()));
+        }
+    }
+
+    /// Retrieves the name that assistive tools read out for this widget.  Defaults to
+    /// `default_accessible_name`.
+    fn get_accessible_name(&mut self) -> String {
+        match self.config().get::<AccessibilityName>() {
+            Some(name) => name.0.clone(),
+            None => self.default_accessible_name(),
+        }
+    }
+
+    /// Retrieves the name of this widget from what it shows, such as the text of a button.
+    /// `Widget`s with text override this.  Defaults to the tooltip, or an empty string.
+    fn default_accessible_name(&mut self) -> String {
+        self.get_tooltip().unwrap_or_default()
+    }
+
+    /// Retrieves the current value of this widget, as assistive tools read it out, such as the
+    /// text of a text input, or the position of a slider.  Defaults to `None`, for widgets that
+    /// have no value.
+    fn get_accessible_value(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Retrieves whether this widget is checked, for widgets that can be checked.  Defaults to
+    /// the `CheckedState` config key, if it is set.
+    fn get_accessible_checked(&mut self) -> Option<CheckState> {
+        self.config().get::<CheckedState>().map(|state| state.0)
+    }
+
+    /// Sets whether or not mouse events pass through this widget to whatever is underneath it.
+    /// Widgets that let mouse events pass through can never be hovered or clicked.
+    fn set_pass_through(&mut self, pass_through: bool) {
+        if pass_through {
+            self.config().set(PassThrough);
+        } else {
+            self.config().remove::<PassThrough>();
+        }
+    }
+
+    /// Indicates whether or not mouse events pass through this widget.  Defaults to `false`.
+    fn is_pass_through(&mut self) -> bool {
+        self.config().contains_key::<PassThrough>()
+    }
+
+    /// Sets whether or not this widget handles multi-touch.  Touches that start on a widget that
+    /// handles multi-touch are delivered to it as `CallbackEvent::TouchStarted`, `TouchMoved`,
+    /// and `TouchEnded` events, one for each finger, however far they move.  Touches on other
+    /// widgets are delivered as the left mouse button, one touch at a time, and pan the
+    /// scrollable widget under them once they are dragged.  See `Pushrod::set_touch_slop`.
+    fn set_multi_touch(&mut self, multi_touch: bool) {
+        if multi_touch {
+            self.config().set(MultiTouch);
+        } else {
+            self.config().remove::<MultiTouch>();
+        }
+    }
+
+    /// Indicates whether or not this widget handles multi-touch.  Defaults to `false`.
+    fn is_multi_touch(&mut self) -> bool {
+        self.config().contains_key::<MultiTouch>()
+    }
+
+    /// Sets whether or not this widget is drawn into an offscreen texture, which is copied to the
+    /// screen on every frame, and only drawn into again when the widget is invalidated or
+    /// resized.  Caching is meant for widgets that are expensive to draw, but rarely change.  A
+    /// cached widget is drawn with a `Context` that starts at its own origin, and without any
+    /// clipping from its parents.  Invalidates the widget afterward.
+    fn set_cached(&mut self, cached: bool) {
+        if cached {
+            self.config().set(Cached);
+        } else {
+            self.config().remove::<Cached>();
+        }
+
+        self.invalidate();
+    }
+
+    /// Indicates whether or not this widget is drawn into an offscreen texture.  Defaults to
+    /// `false`.
+    fn is_cached(&mut self) -> bool {
+        self.config().contains_key::<Cached>()
+    }
+
+    /// Sets the padding between the edges of this widget and its contents.  Invalidates the
+    /// widget afterward.
+    fn set_padding(&mut self, padding: Insets) {
+        self.config().set(Padding(padding));
+        self.invalidate();
+    }
+
+    /// Retrieves the padding of this widget.  Defaults to no padding.
+    fn get_padding(&mut self) -> Insets {
+        self.config().get_or(Padding(Insets::default())).0
+    }
+
+    /// Sets the margin that is kept clear around the outside of this widget by layout managers.
+    fn set_margin(&mut self, margin: Insets) {
+        self.config().set(Margin(margin));
+    }
+
+    /// Retrieves the margin of this widget.  Defaults to no margin.
+    fn get_margin(&mut self) -> Insets {
+        self.config().get_or(Margin(Insets::default())).0
+    }
+
+    /// Sets the size that this widget is given by layout managers, such as the
+    /// `HorizontalLayoutWidget`, when it is not stretched to fill the layout.
+    fn set_preferred_size(&mut self, w: i32, h: i32) {
+        self.config()
+            .set(PreferredSize(crate::core::point::Size { w, h }));
+    }
+
+    /// Retrieves the size that this widget is given by layout managers.  Defaults to the current
+    /// size of the widget if not set.
+    fn get_preferred_size(&mut self) -> crate::core::point::Size {
+        match self.config().get::<PreferredSize>() {
+            Some(preferred_size) => preferred_size.0.clone(),
+            None => self.get_size(),
+        }
+    }
+
+    /// Sets the share of the space left over in a layout that this widget is stretched to fill,
+    /// relative to the weights of the other widgets in the layout.  A widget with a weight of 0
+    /// keeps its preferred size.
+    fn set_layout_weight(&mut self, weight: u32) {
+        self.config().set(LayoutWeight(weight));
+    }
+
+    /// Retrieves the layout weight of this widget.  Defaults to 0 if not set.
+    fn get_layout_weight(&mut self) -> u32 {
+        self.config().get_or(LayoutWeight(0)).0
+    }
+
+    /// Sets the cell, or block of cells, that this widget is placed in by a `GridLayoutWidget`.
+    /// To check that the cell is free, add the widget with `WidgetStore::add_widget_to_grid`.
+    fn set_grid_cell(&mut self, cell: GridCell) {
+        self.config().set(GridPlacement(cell));
+    }
+
+    /// Retrieves the cell that this widget is placed in by a `GridLayoutWidget`, or `None` if it
+    /// has not been given one.
+    fn get_grid_cell(&mut self) -> Option<GridCell> {
+        self.config()
+            .get::<GridPlacement>()
+            .map(|placement| placement.0)
+    }
+
+    /// Sets the width and height of this widget as `Dimension`s, so that either can be a
+    /// percentage of the width or height of its parent, or of the window, which is followed as
+    /// the parent is resized.  The dimensions win over the size that was set with `set_size`,
+    /// and are resolved by `WidgetStore::apply_layouts`; see `resolve_dimensions`.  They are
+    /// ignored for the children of layout managers.
+    fn set_size_dimensions(&mut self, width: Dimension, height: Dimension) {
+        self.config().set(SizeDimensions(width, height));
+    }
+
+    /// Retrieves the width and height of this widget as `Dimension`s, or `None` if they have not
+    /// been set.
+    fn get_size_dimensions(&mut self) -> Option<(Dimension, Dimension)> {
+        self.config()
+            .get::<SizeDimensions>()
+            .map(|dimensions| (dimensions.0, dimensions.1))
+    }
+
+    /// Sets the position of this widget in its parent as `Dimension`s, so that either can be a
+    /// percentage of the width or height of the parent.  This is used together with percentage
+    /// sizes to place widgets next to each other.  See `set_size_dimensions`.
+    fn set_origin_dimensions(&mut self, x: Dimension, y: Dimension) {
+        self.config().set(OriginDimensions(x, y));
+    }
+
+    /// Retrieves the position of this widget as `Dimension`s, or `None` if it has not been set.
+    fn get_origin_dimensions(&mut self) -> Option<(Dimension, Dimension)> {
+        self.config()
+            .get::<OriginDimensions>()
+            .map(|dimensions| (dimensions.0, dimensions.1))
+    }
+
+    /// Removes the size and position dimensions of this widget.  The widget keeps the origin and
+    /// size they were last resolved to.
+    fn clear_dimensions(&mut self) {
+        self.config().remove::<SizeDimensions>();
+        self.config().remove::<OriginDimensions>();
+    }
+
+    /// Anchors an edge of this widget to the same edge of its parent, `offset` pixels inside of
+    /// it, so that the widget follows that edge as the parent, or the window, is resized.  When
+    /// opposite edges are both anchored, the widget is stretched between them.  Anchors win over
+    /// the origin and size of the widget, and are resolved by `WidgetStore::apply_layouts`; see
+    /// `Anchors::resolve`.  An anchored edge wins over size and position dimensions too, which
+    /// are resolved first.  Anchors are ignored for the children of layout managers, such as the
+    /// `HorizontalLayoutWidget`, which place their children themselves.
+    fn set_anchor(&mut self, edge: AnchorEdge, offset: i32) {
+        match edge {
+            AnchorEdge::Left => self.config().set(AnchorLeft(offset)),
+            AnchorEdge::Top => self.config().set(AnchorTop(offset)),
+            AnchorEdge::Right => self.config().set(AnchorRight(offset)),
+            AnchorEdge::Bottom => self.config().set(AnchorBottom(offset)),
+        }
+    }
+
+    /// Removes the anchor from an edge of this widget.  The widget keeps the origin and size it
+    /// was last given.
+    fn clear_anchor(&mut self, edge: AnchorEdge) {
+        match edge {
+            AnchorEdge::Left => self.config().remove::<AnchorLeft>(),
+            AnchorEdge::Top => self.config().remove::<AnchorTop>(),
+            AnchorEdge::Right => self.config().remove::<AnchorRight>(),
+            AnchorEdge::Bottom => self.config().remove::<AnchorBottom>(),
+        }
+    }
+
+    /// Retrieves the anchors of every edge of this widget.  Defaults to no anchors.
+    fn get_anchors(&mut self) -> Anchors {
+        let config = self.config();
+
+        Anchors {
+            left: config.get::<AnchorLeft>().map(|anchor| anchor.0),
+            top: config.get::<AnchorTop>().map(|anchor| anchor.0),
+            right: config.get::<AnchorRight>().map(|anchor| anchor.0),
+            bottom: config.get::<AnchorBottom>().map(|anchor| anchor.0),
+        }
+    }
+
+    /// Retrieves the rectangle `[x, y, w, h]` inside of which the contents of this widget are
+    /// drawn, relative to its origin, in the same coordinates that `draw` uses.  By default, this
+    /// is the size of the widget inset by its padding.  `Widget`s with a border also inset the
+    /// rectangle by the thickness of the border.  Mouse events still use the full bounds of the
+    /// widget.
+    ///
+    /// Example:
+    /// ```
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::widget::box_widget::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut canvas = CanvasWidget::new();
+    ///
+    ///    canvas.set_size(100, 50);
+    ///    canvas.set_padding(make_uniform_insets(5));
+    ///    assert_eq!(canvas.get_content_rect(), [5, 5, 90, 40]);
+    ///
+    ///    let mut box_widget = BoxWidget::new();
+    ///
+    ///    box_widget.set_size(100, 50);
+    ///    box_widget.set_border([0.0, 0.0, 0.0, 1.0], 2);
+    ///    box_widget.set_padding(make_insets(5, 0, 5, 0));
+    ///    assert_eq!(box_widget.get_content_rect(), [2, 7, 96, 36]);
+    /// # }
+    /// ```
+    fn get_content_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+
+        self.get_padding().inset_size(&size)
+    }
+
+    /// Arranges the visible children of this widget, for widgets that are layout managers, such
+    /// as the `HorizontalLayoutWidget`.  This is called by `WidgetStore::apply_layouts` with the
+    /// children in the order in which they were added, and returns the bounds `[x, y, w, h]` to
+    /// give each child, relative to the origin of this widget.  By default, this returns no
+    /// bounds, so the children keep the origins and sizes they were given.
+    fn layout_children(&mut self, _children: &[LayoutChild]) -> Vec<[i32; 4]> {
+        Vec::new()
+    }
+
+    /// Retrieves the distance by which the children of this widget are scrolled, for widgets
+    /// that scroll their children, such as the `ScrollContainerWidget`.  The `WidgetStore`
+    /// moves the children up and to the left by this distance when it draws them and finds them
+    /// under the mouse.  Defaults to no distance.
+    fn get_scroll_offset(&mut self) -> Point {
+        make_origin_point()
+    }
+
+    /// Scrolls the children of this widget, so that the point `x`, `y` of its content is at
+    /// the top left of its viewport.  Widgets that scroll limit this to the size of their
+    /// content.  Does nothing by default.  See `WidgetStore::ensure_visible`.
+    fn scroll_to(&mut self, _x: i32, _y: i32) {}
+
+    /// Indicates whether this widget has content that can be scrolled with `scroll_to`, so that
+    /// a touch that is dragged across it, or its children, pans the content rather than
+    /// dragging the mouse.  Defaults to `false`.
+    fn is_scrollable(&mut self) -> bool {
+        false
+    }
+
+    /// Returns the area of this widget in which its children can be seen, and receive mouse
+    /// events, as `[x, y, w, h]` relative to its origin.  Defaults to the whole widget.
+    fn get_viewport_rect(&mut self) -> [i32; 4] {
+        let size = self.get_size();
+
+        [0, 0, size.w, size.h]
+    }
+
+    /// Called when the `Theme` is changed with `WidgetStore::set_theme`, after the new theme has
+    /// become the current theme.  `Widget`s that keep anything that is derived from the colors
+    /// or font size of the theme should rebuild it here.  By default, this only invalidates the
+    /// widget.
+    fn theme_changed(&mut self, _theme: &Theme) {
+        self.invalidate();
+    }
+
+    /// Called when the number of device pixels per point of the window changes, such as when the
+    /// window is moved to a display with a different density, and when the widget is added to a
+    /// window whose scale factor is not 1.0.  `Widget`s are laid out and drawn in points, so
+    /// only `Widget`s that rasterize text or images need to do anything here, to stay sharp.  By
+    /// default, this only invalidates the widget.  See `WidgetStore::set_scale_factor`.
+    fn scale_factor_changed(&mut self, _scale_factor: f64) {
+        self.invalidate();
+    }
+
+    /// Called with text that was typed while this widget, or one of its children, is focused,
+    /// which can be more than one character, such as a character composed with a dead key, or
+    /// text from an input method.  Text should be inserted from here, rather than by mapping the
+    /// `Key`s that are pressed to characters.  `widget_id` is the ID of this widget.  This is
+    /// called just before the `CallbackEvent::TextInput` event is handled.  Does nothing by
+    /// default.
+    fn text_input(&mut self, _widget_id: WidgetId, _text: &str) {}
+
     /// Handles an event that was sent by the event loop.  It is up to the `Widget` to handle the
     /// event, or to ignore it.  If this function is _not_ overridden, the event will be ignored,
     /// and no event will be returned as a result.  This function _returns_ an `Option<CallbackEvent>`,
@@ -136,34 +959,130 @@ pub trait Widget {
         None
     }
 
+    /// Handles a mouse button press, scroll, or key event, and decides whether it propagates any
+    /// further; see `EventResult`.  A widget that sits over another, but only reacts to some
+    /// events, can return `EventResult::NotHandled` for the rest, so that they reach the widget
+    /// underneath.  When a mouse button press is passed on, the widget that handles it receives
+    /// the rest of the click, including the release.  By default, this calls `handle_event`, and
+    /// returns `EventResult::Handled`, so that the event is delivered as it always has been.
+    fn handle_input_event(&mut self, event: CallbackEvent) -> (EventResult, Option<CallbackEvent>) {
+        (EventResult::Handled, self.handle_event(event))
+    }
+
+    /// Called on every update event of the run loop, regardless of whether or not the screen
+    /// is redrawn.  `delta_ms` is the number of milliseconds since the previous update.  Use this
+    /// for time-based work, such as timers and animations.  Does nothing by default.
+    fn update(&mut self, _delta_ms: u64) {}
+
+    /// Returns, and clears, the list of `WidgetStoreCallback`s that this `Widget` has queued
+    /// since the last time this was called.  The `WidgetStore` calls these after it has finished
+    /// updating all of its `Widget`s, so the callbacks are free to modify any `Widget` in the
+    /// store, including this one.  Returns an empty list by default.
+    fn take_deferred_callbacks(&mut self) -> Vec<DeferredCallback> {
+        Vec::new()
+    }
+
+    /// Returns, and clears, the `PushrodEvent`s that this `Widget` has posted to the event bus
+    /// since the last time this was called.  `widget_id` is the ID of this `Widget`, for events
+    /// that identify where they came from.  The `WidgetStore` collects these once per frame, and
+    /// they are delivered on the next frame.  Returns an empty list by default.
+    fn take_posted_events(&mut self, _widget_id: WidgetId) -> Vec<PushrodEvent> {
+        Vec::new()
+    }
+
+    /// Identifies which `PushrodEvent`s posted to the event bus this `Widget` receives in
+    /// `handle_pushrod_event`.  Defaults to `MASK_EVENT_NONE`, so `Widget`s only receive bus
+    /// events once they opt in.
+    fn event_mask(&mut self) -> EventMask {
+        MASK_EVENT_NONE
+    }
+
+    /// Called with every `PushrodEvent` posted to the event bus that matches the `event_mask` of
+    /// this `Widget`.  Does nothing by default.
+    fn handle_pushrod_event(&mut self, _event: &PushrodEvent) {}
+
     // Draw routines
 
-    /// Draws the contents of the widget, provided a `piston2d` `Context` and `G2d` object.
+    /// Draws the contents of the widget, provided a `piston2d` `Context` and `DrawTarget` object.
     ///
     /// It is **highly recommended** that you call `clear_invalidate()` after the draw completes,
     /// otherwise, this will continue to be redrawn continuously (unless this is the desired
     /// behavior.)
-    fn draw(&mut self, c: Context, g: &mut G2d, clip: &DrawState) {
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
         let size: crate::core::point::Size = self.get_size();
 
-        Rectangle::new(self.get_color()).draw(
-            [0.0 as f64, 0.0 as f64, size.w as f64, size.h as f64],
-            clip,
-            c.transform,
-            g,
-        );
+        draw_fill(self, [0.0, 0.0, size.w as f64, size.h as f64], c, g, clip);
 
         self.clear_invalidate();
     }
 }
 
-/// This is the `CanvasWidget`, which contains a top-level widget for display.  It does
-/// not contain any special logic other than being a base for a display layer.
+/// Closure type that draws the contents of a `CanvasWidget`.  It is called with a `Context`
+/// that is translated to the origin of the `Widget`, and whose `draw_state` clips drawing to its
+/// bounds, along with the `Size` of the `Widget`, so that it can draw in local coordinates.
+/// Drawing is only clipped when it uses that `draw_state`.
+pub type CanvasDrawCallback = Box<dyn FnMut(Context, &mut DrawTarget, crate::core::point::Size)>;
+
+/// Closure type that is called when the mouse moves over a `CanvasWidget`, with the `Widget`
+/// and the position of the mouse relative to its origin.
+pub type CanvasMouseCallback = Box<dyn FnMut(&mut CanvasWidget, Point)>;
+
+/// Closure type that is called when a `CanvasWidget` is clicked, with the `Widget`, the position
+/// of the mouse relative to its origin, and the button that was clicked.
+pub type CanvasClickCallback = Box<dyn FnMut(&mut CanvasWidget, Point, Button)>;
+
+/// This is the `CanvasWidget`, which contains a top-level widget for display.  It paints its
+/// bounds with the color that has been specified with `set_color`, and is the base of the
+/// display layer of the `WidgetStore`.
+///
+/// Custom graphics can be drawn on top of the color by an `on_draw` closure, without defining a
+/// new type of `Widget`.  Since the `WidgetStore` cannot know when the output of the closure
+/// changes, it is only called again after `request_redraw` is called, or after the `Widget` is
+/// otherwise invalidated.  Mouse movement and clicks can be handled by the `on_mouse_moved` and
+/// `on_click` closures, which are given the `Widget`, so that they can request a redraw.
 ///
 /// Example usage:
-/// IN PROGRESS
+/// ```no_run
+/// # use piston_window::*;
+/// # use pushrod::core::main::*;
+/// # use pushrod::core::point::*;
+/// # use pushrod::widget::widget::*;
+/// # use std::cell::RefCell;
+/// # use std::rc::Rc;
+/// # fn main() {
+///    let window: PistonWindow = WindowSettings::new("Canvas", [400, 300]).build().unwrap();
+///    let mut pushrod = Pushrod::new(window);
+///    let mouse = Rc::new(RefCell::new(Point { x: 0, y: 0 }));
+///    let draw_mouse = mouse.clone();
+///    let mut canvas = CanvasWidget::new();
+///
+///    canvas.set_origin(20, 20);
+///    canvas.set_size(200, 150);
+///    canvas.on_draw(Box::new(move |c, g, size| {
+///        let point = draw_mouse.borrow().clone();
+///
+///        let dot = [point.x as f64 - 4.0, point.y as f64 - 4.0, 8.0, 8.0];
+///
+///        Line::new([0.0, 0.0, 0.0, 1.0], 1.0).draw(
+///            [0.0, 0.0, size.w as f64, size.h as f64],
+///            &c.draw_state,
+///            c.transform,
+///            g,
+///        );
+///        Ellipse::new([1.0, 0.0, 0.0, 1.0]).draw(dot, &c.draw_state, c.transform, g);
+///    }));
+///    canvas.on_mouse_moved(Box::new(move |canvas, point| {
+///        *mouse.borrow_mut() = point;
+///        canvas.request_redraw();
+///    }));
+///    pushrod.add_widget("Canvas", Box::new(canvas));
+/// # }
+/// ```
 pub struct CanvasWidget {
     config: Configurable,
+    on_draw: Option<CanvasDrawCallback>,
+    on_mouse_moved: Option<CanvasMouseCallback>,
+    on_click: Option<CanvasClickCallback>,
 }
 
 /// Implementation of the constructor for the `CanvasWidget`.  Creates a new base widget
@@ -172,15 +1091,131 @@ impl CanvasWidget {
     pub fn new() -> Self {
         Self {
             config: Configurable::new(),
+            on_draw: None,
+            on_mouse_moved: None,
+            on_click: None,
         }
     }
+
+    /// Creates a `WidgetBuilder` for a new `CanvasWidget`, which needs a size unless it is
+    /// hidden.
+    pub fn builder() -> WidgetBuilder<Self> {
+        WidgetBuilder::new(Self::new())
+    }
+
+    /// Sets the closure that draws the contents of this `Widget`, after its bounds are painted
+    /// with its color.  Invalidates the widget afterward.
+    pub fn on_draw(&mut self, callback: CanvasDrawCallback) {
+        self.on_draw = Some(callback);
+        self.invalidate();
+    }
+
+    /// Sets the closure that is called when the mouse moves over this `Widget`.
+    pub fn on_mouse_moved(&mut self, callback: CanvasMouseCallback) {
+        self.on_mouse_moved = Some(callback);
+    }
+
+    /// Sets the closure that is called when this `Widget` is clicked.
+    pub fn on_click(&mut self, callback: CanvasClickCallback) {
+        self.on_click = Some(callback);
+    }
+
+    /// Requests that the `on_draw` closure be called again on the next draw, because what it
+    /// draws has changed.  This can be called from the mouse closures, or through the
+    /// `WidgetStore` by invalidating the widget.
+    ///
+    /// Example usage:
+    /// ```
+    /// # use pushrod::core::callbacks::*;
+    /// # use pushrod::core::point::*;
+    /// # use pushrod::core::widget_id::*;
+    /// # use pushrod::widget::widget::*;
+    /// # fn main() {
+    ///    let mut canvas = CanvasWidget::new();
+    ///
+    ///    canvas.on_mouse_moved(Box::new(|canvas, point| {
+    ///        if point.x > 50 {
+    ///            canvas.request_redraw();
+    ///        }
+    ///    }));
+    ///    canvas.clear_invalidate();
+    ///
+    ///    let point = Point { x: 10, y: 0 };
+    ///
+    ///    canvas.handle_event(CallbackEvent::MouseMoved { widget_id: WidgetId::from(1), point });
+    ///    assert!(!canvas.is_invalidated());
+    ///
+    ///    let point = Point { x: 60, y: 0 };
+    ///
+    ///    canvas.handle_event(CallbackEvent::MouseMoved { widget_id: WidgetId::from(1), point });
+    ///    assert!(canvas.is_invalidated());
+    /// # }
+    /// ```
+    pub fn request_redraw(&mut self) {
+        self.invalidate();
+    }
 }
 
+impl Buildable for CanvasWidget {}
+
 /// Implementation of the `CanvasWidget` object with the `Widget` traits implemented.
-/// This function only implements `config` and `callbacks`, which are used as a base for
-/// all `Widget`s.
+/// Mouse movement and clicks are passed on to the mouse closures while the widget is enabled.
 impl Widget for CanvasWidget {
     fn config(&mut self) -> &mut Configurable {
         &mut self.config
     }
+
+    fn handle_event(&mut self, event: CallbackEvent) -> Option<CallbackEvent> {
+        if self.is_disabled() {
+            return None;
+        }
+
+        match event {
+            CallbackEvent::MouseMoved { point, .. } => {
+                if let Some(mut callback) = self.on_mouse_moved.take() {
+                    callback(self, point);
+
+                    if self.on_mouse_moved.is_none() {
+                        self.on_mouse_moved = Some(callback);
+                    }
+                }
+            }
+
+            CallbackEvent::MouseClicked { point, button, .. } => {
+                if let Some(mut callback) = self.on_click.take() {
+                    callback(self, point, button);
+
+                    if self.on_click.is_none() {
+                        self.on_click = Some(callback);
+                    }
+                }
+            }
+
+            _ => (),
+        }
+
+        None
+    }
+
+    /// Paints the bounds of this widget with its color, or its gradient, and then calls the
+    /// `on_draw` closure with the context clipped to its bounds.
+    fn draw(&mut self, c: Context, g: &mut DrawTarget, clip: &DrawState) {
+        let size: crate::core::point::Size = self.get_size();
+
+        draw_fill(self, [0.0, 0.0, size.w as f64, size.h as f64], c, g, clip);
+
+        if let Some(callback) = &mut self.on_draw {
+            callback(
+                Context {
+                    draw_state: *clip,
+                    ..c
+                },
+                g,
+                size,
+            );
+        }
+
+        // Then clear invalidation.
+        self.clear_invalidate();
+    }
 }